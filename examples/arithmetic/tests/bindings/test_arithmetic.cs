@@ -0,0 +1,36 @@
+using System;
+using uniffi.arithmetic;
+
+static void Check(bool condition, string message) {
+    if (!condition) {
+        throw new Exception($"Assertion failed: {message}");
+    }
+}
+
+Check(ArithmeticMethods.Add(2, 4) == 6, "2 + 4");
+Check(ArithmeticMethods.Add(4, 8) == 12, "4 + 8");
+
+try {
+    ArithmeticMethods.Sub(0, 2);
+    throw new Exception("Should have thrown a IntegerOverflow exception!");
+} catch (ArithmeticException.IntegerOverflow) {
+    // It's okay!
+}
+
+Check(ArithmeticMethods.Sub(4, 2) == 2, "4 - 2");
+Check(ArithmeticMethods.Sub(8, 4) == 4, "8 - 4");
+
+Check(ArithmeticMethods.Div(8, 4) == 2, "8 / 4");
+
+try {
+    ArithmeticMethods.Div(8, 0);
+    throw new Exception("Should have panicked when dividing by zero");
+} catch (InternalException) {
+    // It's okay!
+}
+
+Check(ArithmeticMethods.Equal(2, 2), "2 == 2");
+Check(ArithmeticMethods.Equal(4, 4), "4 == 4");
+
+Check(!ArithmeticMethods.Equal(2, 4), "2 != 4");
+Check(!ArithmeticMethods.Equal(4, 8), "4 != 8");
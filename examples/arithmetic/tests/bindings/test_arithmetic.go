@@ -0,0 +1,55 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+
+	"uniffi/arithmetic"
+)
+
+func check(condition bool, message string) {
+	if !condition {
+		panic(fmt.Sprintf("Assertion failed: %s", message))
+	}
+}
+
+func mustPanic(message string, f func()) {
+	defer func() {
+		var internal *arithmetic.InternalError
+		err, ok := recover().(error)
+		check(ok && errors.As(err, &internal), message)
+	}()
+	f()
+}
+
+func main() {
+	var overflow *arithmetic.ArithmeticErrorIntegerOverflow
+
+	_, err := arithmetic.Add(18446744073709551615, 1)
+	check(errors.As(err, &overflow), "Should have returned an IntegerOverflow error!")
+
+	sum, err := arithmetic.Add(2, 4)
+	check(err == nil && sum == 6, "2 + 4")
+	sum, err = arithmetic.Add(4, 8)
+	check(err == nil && sum == 12, "4 + 8")
+
+	_, err = arithmetic.Sub(0, 1)
+	check(errors.As(err, &overflow), "Should have returned an IntegerOverflow error!")
+
+	difference, err := arithmetic.Sub(4, 2)
+	check(err == nil && difference == 2, "4 - 2")
+	difference, err = arithmetic.Sub(8, 4)
+	check(err == nil && difference == 4, "8 - 4")
+
+	check(arithmetic.Div(8, 4) == 2, "8 / 4")
+
+	mustPanic("Should have panicked when dividing by zero", func() {
+		arithmetic.Div(8, 0)
+	})
+
+	check(arithmetic.Equal(2, 2), "2 == 2")
+	check(arithmetic.Equal(4, 4), "4 == 4")
+
+	check(!arithmetic.Equal(2, 4), "2 != 4")
+	check(!arithmetic.Equal(4, 8), "4 != 8")
+}
@@ -5,5 +5,12 @@ uniffi_macros::build_foreign_language_testcases!(
         "tests/bindings/test_arithmetic.py",
         "tests/bindings/test_arithmetic.kts",
         "tests/bindings/test_arithmetic.swift",
+        "tests/bindings/test_arithmetic.cs",
+        "tests/bindings/test_arithmetic.go",
     ]
 );
+
+uniffi_macros::build_golden_file_testcases!(
+    ["src/arithmetic.udl"],
+    ["kotlin", "swift", "python", "ruby"]
+);
@@ -0,0 +1,49 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use url::Url;
+
+// `Url` is a `[Custom]` type in our UDL, so we implement the UniffiCustomTypeConverter trait
+// to say how it's converted from and into the builtin type that it's passed as.
+impl UniffiCustomTypeConverter for Url {
+    type Builtin = String;
+
+    fn into_custom(val: Self::Builtin) -> uniffi::Result<Self> {
+        Ok(Url::parse(&val)?)
+    }
+
+    fn from_custom(obj: Self) -> Self::Builtin {
+        obj.into()
+    }
+}
+
+// And we also have a trivial "Handle" type which wraps an i64.
+pub struct Handle(pub i64);
+
+impl UniffiCustomTypeConverter for Handle {
+    type Builtin = i64;
+
+    fn into_custom(val: Self::Builtin) -> uniffi::Result<Self> {
+        Ok(Handle(val))
+    }
+
+    fn from_custom(obj: Self) -> Self::Builtin {
+        obj.0
+    }
+}
+
+// And a little struct and function that ties them together.
+pub struct CustomTypesDemo {
+    url: Url,
+    handle: Handle,
+}
+
+pub fn get_custom_types_demo(v: Option<CustomTypesDemo>) -> CustomTypesDemo {
+    v.unwrap_or_else(|| CustomTypesDemo {
+        url: Url::parse("http://example.com/").unwrap(),
+        handle: Handle(123),
+    })
+}
+
+include!(concat!(env!("OUT_DIR"), "/custom-types.uniffi.rs"));
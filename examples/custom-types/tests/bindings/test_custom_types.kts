@@ -0,0 +1,18 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.custom_types.*
+
+// TODO: use an actual test runner.
+
+// `java.net.URL.equals` looks the host up, so the URLs are compared as strings.
+val demo = getCustomTypesDemo(null)
+assert(demo.url.toString() == "http://example.com/")
+assert(demo.handle == 123L)
+
+demo.url = java.net.URL("http://new.example.com/")
+demo.handle = 456
+val demo2 = getCustomTypesDemo(demo)
+assert(demo2.url.toString() == "http://new.example.com/")
+assert(demo2.handle == 456L)
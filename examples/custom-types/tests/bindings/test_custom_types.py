@@ -0,0 +1,11 @@
+import urllib.parse
+
+from custom_types import *
+
+val = get_custom_types_demo(None)
+assert val.url == urllib.parse.urlparse("http://example.com/")
+assert val.handle == 123
+
+val = CustomTypesDemo(urllib.parse.urlparse("http://new.example.com/"), 456)
+val2 = get_custom_types_demo(val)
+assert val == val2
@@ -0,0 +1,19 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import Foundation
+import custom_types
+
+// TODO: use an actual test runner.
+
+do {
+    let demo = getCustomTypesDemo(demo: nil)
+    assert(demo.url == URL(string: "http://example.com/"))
+    assert(demo.handle == 123)
+}
+
+do {
+    let demo = CustomTypesDemo(url: URL(string: "http://new.example.com/")!, handle: 456)
+    assert(getCustomTypesDemo(demo: demo) == demo)
+}
@@ -0,0 +1,13 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["src/custom-types.udl"],
+    [
+        "tests/bindings/test_custom_types.kts",
+        "tests/bindings/test_custom_types.py",
+        "tests/bindings/test_custom_types.swift",
+    ]
+);
+
+uniffi_macros::build_golden_file_testcases!(
+    ["src/custom-types.udl"],
+    ["kotlin", "swift", "python"]
+);
@@ -7,3 +7,8 @@ uniffi_macros::build_foreign_language_testcases!(
         "tests/bindings/test_geometry.swift",
     ]
 );
+
+uniffi_macros::build_golden_file_testcases!(
+    ["src/geometry.udl"],
+    ["kotlin", "swift", "python", "ruby"]
+);
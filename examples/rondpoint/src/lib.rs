@@ -3,6 +3,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use std::collections::HashMap;
+use uniffi::deps::indexmap::IndexMap;
 
 #[derive(Debug, Clone)]
 pub struct Dictionnaire {
@@ -28,7 +29,7 @@ pub struct DictionnaireNombresSignes {
     gros_nombre: i64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Enumeration {
     Un,
     Deux,
@@ -67,6 +68,22 @@ fn copie_carte(
     e
 }
 
+fn copie_carte_entiers(c: HashMap<u64, String>) -> HashMap<u64, String> {
+    c
+}
+
+fn compte_enumerations(e: Vec<Enumeration>) -> HashMap<Enumeration, u32> {
+    let mut comptes = HashMap::new();
+    for v in e {
+        *comptes.entry(v).or_insert(0) += 1;
+    }
+    comptes
+}
+
+fn inverse_carte_ordonnee(c: IndexMap<String, u32>) -> IndexMap<String, u32> {
+    c.into_iter().rev().collect()
+}
+
 fn copie_dictionnaire(d: Dictionnaire) -> Dictionnaire {
     d
 }
@@ -288,6 +305,9 @@ pub struct OptionneurDictionnaire {
     list_var: Vec<String>,
     enumeration_var: Enumeration,
     dictionnaire_var: Option<minusculeMAJUSCULEEnum>,
+    noms_var: Option<Vec<String>>,
+    entiers_var: Option<Vec<u32>>,
+    compteurs_var: HashMap<String, i32>,
 }
 
 include!(concat!(env!("OUT_DIR"), "/rondpoint.uniffi.rs"));
@@ -16,6 +16,13 @@ assert(copieCarte(mapOf(
     "2" to EnumerationAvecDonnees.Deux(2u, "deux")
 ))
 
+// Maps can have integer and enum keys as well as strings.
+assert(copieCarteEntiers(mapOf(0UL to "zero", ULong.MAX_VALUE to "max")) == mapOf(0UL to "zero", ULong.MAX_VALUE to "max"))
+assert(compteEnumerations(listOf(Enumeration.UN, Enumeration.TROIS, Enumeration.UN)) == mapOf(Enumeration.UN to 2u, Enumeration.TROIS to 1u))
+
+// Ordered maps keep their entries in the order they were inserted, on both sides.
+assert(inverseCarteOrdonnee(linkedMapOf("z" to 1u, "a" to 2u, "m" to 3u)).toList() == listOf("m" to 3u, "a" to 2u, "z" to 1u))
+
 val var1: EnumerationAvecDonnees = EnumerationAvecDonnees.Zero
 val var2: EnumerationAvecDonnees = EnumerationAvecDonnees.Un(1u)
 val var3: EnumerationAvecDonnees = EnumerationAvecDonnees.Un(2u)
@@ -239,7 +246,10 @@ val explicite = OptionneurDictionnaire(
     stringVar = "default",
     listVar = listOf(),
     enumerationVar = Enumeration.DEUX,
-    dictionnaireVar = null
+    dictionnaireVar = null,
+    nomsVar = null,
+    entiersVar = listOf(),
+    compteursVar = mapOf()
 )
 assert(defaultes == explicite)
 
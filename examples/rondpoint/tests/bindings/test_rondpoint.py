@@ -18,6 +18,13 @@ assert copie_carte({
     "2": EnumerationAvecDonnees.DEUX(2, "deux"),
 }
 
+# Maps can have integer and enum keys as well as strings.
+assert copie_carte_entiers({0: "zero", 2**64 - 1: "max"}) == {0: "zero", 2**64 - 1: "max"}
+assert compte_enumerations([Enumeration.UN, Enumeration.TROIS, Enumeration.UN]) == {Enumeration.UN: 2, Enumeration.TROIS: 1}
+
+# Ordered maps keep their entries in the order they were inserted, on both sides.
+assert list(inverse_carte_ordonnee({"z": 1, "a": 2, "m": 3}).items()) == [("m", 3), ("a", 2), ("z", 1)]
+
 assert switcheroo(False) is True
 
 assert EnumerationAvecDonnees.ZERO() != EnumerationAvecDonnees.UN(1)
@@ -82,6 +89,17 @@ affirmAllerRetour(
   rt.identique_string
 )
 
+# Defaults of dictionary fields, which Python can only give the fields after the last one without.
+defaultes = OptionneurDictionnaire()
+assert defaultes.string_var == "default"
+assert defaultes.noms_var is None
+assert defaultes.entiers_var == []
+assert defaultes.compteurs_var == {}
+# Each dictionary gets its own empty list and dict.
+defaultes.entiers_var.append(1)
+assert OptionneurDictionnaire().entiers_var == []
+affirmAllerRetour([OptionneurDictionnaire(), OptionneurDictionnaire(compteurs_var={"un": 1})], rt.identique_optionneur_dictionnaire)
+
 # Test one way across the FFI.
 #
 # We send one representation of a value to lib.rs, and it transforms it into another, a string.
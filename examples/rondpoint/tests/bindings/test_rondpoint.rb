@@ -27,6 +27,14 @@ assert_equal Rondpoint.copie_carte({
                                      '2' => EnumerationAvecDonnees::DEUX.new(2, 'deux')
                                    }
 
+# Maps can have integer and enum keys as well as strings.
+assert_equal Rondpoint.copie_carte_entiers({ 0 => 'zero', 2**64 - 1 => 'max' }), { 0 => 'zero', 2**64 - 1 => 'max' }
+assert_equal Rondpoint.compte_enumerations([Enumeration::UN, Enumeration::TROIS, Enumeration::UN]),
+             { Enumeration::UN => 2, Enumeration::TROIS => 1 }
+
+# Ordered maps keep their entries in the order they were inserted, on both sides.
+assert_equal Rondpoint.inverse_carte_ordonnee({ 'z' => 1, 'a' => 2, 'm' => 3 }).to_a, [['m', 3], ['a', 2], ['z', 1]]
+
 assert Rondpoint.switcheroo(false)
 
 assert_not_equal EnumerationAvecDonnees::ZERO.new, EnumerationAvecDonnees::UN.new(1)
@@ -95,6 +103,14 @@ affirm_aller_retour(
   :identique_string
 )
 
+# Defaults of dictionary fields, which Ruby can only give the fields after the last one without.
+defaultes = OptionneurDictionnaire.new
+assert_equal defaultes.string_var, 'default'
+assert_nil defaultes.noms_var
+assert_equal defaultes.entiers_var, []
+assert_equal defaultes.compteurs_var, {}
+affirm_aller_retour([defaultes], :identique_optionneur_dictionnaire)
+
 # Test one way across the FFI.
 #
 # We send one representation of a value to lib.rs, and it transforms it into another, a string.
@@ -16,6 +16,16 @@ assert(copieCarte(c:
     "2": .deux(premier: 2, second: "deux")
 ])
 
+// Maps can have integer and enum keys as well as strings.
+assert(copieCarteEntiers(c: [0: "zero", UInt64.max: "max"]) == [0: "zero", UInt64.max: "max"])
+assert(compteEnumerations(e: [.un, .trois, .un]) == [.un: 2, .trois: 1])
+
+// Ordered maps keep their entries in the order they were inserted, on both sides.
+let inversee = inverseCarteOrdonnee(c: ["z": 1, "a": 2, "m": 3])
+assert(inversee.keys == ["m", "a", "z"])
+assert(inversee == ["m": 3, "a": 2, "z": 1])
+assert(inversee != ["z": 1, "a": 2, "m": 3])
+
 assert(EnumerationAvecDonnees.zero != EnumerationAvecDonnees.un(premier: 1))
 assert(EnumerationAvecDonnees.un(premier: 1) == EnumerationAvecDonnees.un(premier: 1))
 assert(EnumerationAvecDonnees.un(premier: 1) != EnumerationAvecDonnees.un(premier: 2))
@@ -224,7 +234,10 @@ let explicite = OptionneurDictionnaire(
     stringVar: "default",
     listVar: [],
     enumerationVar: .deux,
-    dictionnaireVar: nil
+    dictionnaireVar: nil,
+    nomsVar: nil,
+    entiersVar: [],
+    compteursVar: [:]
 )  
 
 // …and makes sure they travel across and back the FFI.
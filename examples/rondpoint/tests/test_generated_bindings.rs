@@ -7,3 +7,8 @@ uniffi_macros::build_foreign_language_testcases!(
         "tests/bindings/test_rondpoint.rb",
     ]
 );
+
+uniffi_macros::build_golden_file_testcases!(
+    ["src/rondpoint.udl"],
+    ["kotlin", "swift", "python", "ruby"]
+);
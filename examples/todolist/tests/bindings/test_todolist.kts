@@ -50,6 +50,12 @@ todo.addItems(listOf("bobo", "fofo"))
 assert(todo.getItems().size == 9)
 assert(todo.getItems()[7] == "bobo")
 
+// `[Paginated]` methods also come in a variant that returns one page at a time.
+val page = todo.getItemsPage(7UL, 10U)
+assert(page.items == listOf("bobo", "fofo"))
+assert(page.total == 9UL)
+assert(todo.getEntriesPage(0UL, 2U).items == todo.getEntries().take(2))
+
 assert(getDefaultList() == null)
 
 // Note that each individual object instance needs to be explicitly destroyed,
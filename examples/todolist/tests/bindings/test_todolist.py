@@ -24,6 +24,13 @@ entry2 = TodoEntry("Test Ünicode hàndling in an entry can't believe I didn't t
 todo.add_entry(entry2)
 assert(todo.get_last_entry().text == "Test Ünicode hàndling in an entry can't believe I didn't test this at first 🤣")
 
+# `[Paginated]` methods also come in a variant that returns one page at a time.
+page = todo.get_items_page(1, 2)
+assert(page.items == ["Write tests for bindings", "Write bindings for strings in records"])
+assert(page.total == 5)
+assert(todo.get_entries_page(4, 10).items == [entry2])
+assert(todo.get_entries_page(10, 10).items == [])
+
 todo2 = TodoList()
 assert(todo != todo2)
 assert(todo is not todo2)
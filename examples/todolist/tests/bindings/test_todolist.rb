@@ -29,6 +29,12 @@ entry2 = TodoEntry.new("Test Ünicode hàndling in an entry can't believe I didn
 todo.add_entry(entry2)
 assert_equal todo.get_last_entry.text, "Test Ünicode hàndling in an entry can't believe I didn't test this at first 🤣"
 
+# `[Paginated]` methods also come in a variant that returns one page at a time.
+page = todo.get_items_page 1, 2
+assert_equal page.items, todo.get_items[1, 2]
+assert_equal page.total, todo.get_items.length
+assert_equal todo.get_entries_page(10, 10).items, []
+
 todo2 = TodoList.new
 assert todo2.get_items != todo.get_items
 
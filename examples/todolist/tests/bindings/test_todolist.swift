@@ -43,6 +43,12 @@ todo.addItems(items: ["bobo", "fofo"])
 assert(todo.getItems().count == 9)
 assert(todo.getItems()[7] == "bobo")
 
+// `[Paginated]` methods also come in a variant that returns one page at a time.
+let page = todo.getItemsPage(offset: 7, limit: 10)
+assert(page.items == ["bobo", "fofo"])
+assert(page.total == 9)
+assert(todo.getEntriesPage(offset: 0, limit: 2).items == Array(todo.getEntries().prefix(2)))
+
 // Ensure deinit doesn't crash.
 for _ in 0..<10 {
     let list = TodoList()
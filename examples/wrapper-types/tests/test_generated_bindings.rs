@@ -6,3 +6,8 @@ uniffi_macros::build_foreign_language_testcases!(
         "tests/bindings/test_wrapper_types.swift",
     ]
 );
+
+uniffi_macros::build_golden_file_testcases!(
+    ["src/wrapper-types.udl"],
+    ["kotlin", "swift", "python"]
+);
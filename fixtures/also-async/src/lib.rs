@@ -0,0 +1,44 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Mutex;
+
+#[derive(Debug, thiserror::Error)]
+pub enum LibraryError {
+    #[error("The library is empty")]
+    Empty,
+}
+
+// The async variants of its `[AlsoAsync]` methods only exist in the bindings, so the Rust
+// methods are the same as any others.
+pub struct Library {
+    titles: Mutex<Vec<String>>,
+}
+
+impl Library {
+    fn new() -> Self {
+        Self {
+            titles: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn add(&self, title: String) {
+        self.titles.lock().unwrap().push(title);
+    }
+
+    fn last(&self) -> Result<String, LibraryError> {
+        self.titles
+            .lock()
+            .unwrap()
+            .last()
+            .cloned()
+            .ok_or(LibraryError::Empty)
+    }
+
+    fn count(&self) -> u32 {
+        self.titles.lock().unwrap().len() as u32
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/also_async.uniffi.rs"));
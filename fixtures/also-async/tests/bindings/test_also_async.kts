@@ -0,0 +1,39 @@
+import uniffi.also_async.*
+import java.util.concurrent.CountDownLatch
+import kotlin.coroutines.Continuation
+import kotlin.coroutines.EmptyCoroutineContext
+import kotlin.coroutines.startCoroutine
+
+// Run a coroutine to completion on this thread, without needing `kotlinx.coroutines`.
+fun <T> waitFor(block: suspend () -> T): T {
+    val done = CountDownLatch(1)
+    var result: Result<T>? = null
+    block.startCoroutine(Continuation(EmptyCoroutineContext) {
+        result = it
+        done.countDown()
+    })
+    done.await()
+    return result!!.getOrThrow()
+}
+
+val library = Library()
+
+// The `suspend` variants throw the same errors as the methods.
+try {
+    waitFor { library.lastAsync() }
+    throw RuntimeException("Should have thrown an Empty exception!")
+} catch (e: LibraryException.Empty) {
+    // It's okay!
+}
+assert(waitFor { library.countAsync() } == 0U)
+
+library.add("Dune")
+library.add("Emma")
+assert(waitFor { library.lastAsync() } == "Emma")
+assert(waitFor { library.countAsync() } == 2U)
+
+// And the blocking methods are still there.
+assert(library.last() == "Emma")
+assert(library.count() == 2U)
+
+library.destroy()
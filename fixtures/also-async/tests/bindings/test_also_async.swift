@@ -0,0 +1,40 @@
+import Foundation
+import also_async
+
+// Run an async block to completion, blocking the script until it has finished.
+func waitFor(_ block: @escaping () async throws -> Void) {
+    guard #available(macOS 10.15, *) else {
+        fatalError("[AlsoAsync] methods need macOS 10.15")
+    }
+    let done = DispatchSemaphore(value: 0)
+    Task {
+        try! await block()
+        done.signal()
+    }
+    done.wait()
+}
+
+let library = Library()
+
+waitFor {
+    // The `async` variants throw the same errors as the methods.
+    do {
+        let _ = try await library.lastAsync()
+        fatalError("Should have thrown an Empty error!")
+    } catch LibraryError.Empty {
+        // It's okay!
+    }
+    let count = await library.countAsync()
+    assert(count == 0)
+
+    library.add(title: "Dune")
+    library.add(title: "Emma")
+    let last = try await library.lastAsync()
+    assert(last == "Emma")
+    let newCount = await library.countAsync()
+    assert(newCount == 2)
+}
+
+// And the blocking methods are still there.
+assert(try! library.last() == "Emma")
+assert(library.count() == 2)
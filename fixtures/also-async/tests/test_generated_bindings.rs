@@ -0,0 +1,9 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["src/also_async.udl",],
+    [
+        "tests/bindings/test_also_async.kts",
+        "tests/bindings/test_also_async.swift",
+    ]
+);
+
+uniffi_macros::build_golden_file_testcases!(["src/also_async.udl"], ["kotlin", "swift"]);
@@ -0,0 +1,31 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import asyncio
+import unittest
+import first
+import second
+
+# Both components number their calls from 1, so the first call to each has the same handle.
+class TestAsyncComponents(unittest.TestCase):
+    def test_calls_complete_in_their_own_component(self):
+        async def both():
+            return await asyncio.gather(first.name_after(50), second.name_after(10))
+
+        self.assertEqual(asyncio.run(both()), ["first", "second"])
+
+    def test_cancelling_a_call_leaves_the_other_component_alone(self):
+        async def cancel_second():
+            done = asyncio.ensure_future(first.name_after(100))
+            cancelled = asyncio.ensure_future(second.name_after(10000))
+            await asyncio.sleep(0.01)
+            cancelled.cancel()
+            with self.assertRaises(asyncio.CancelledError):
+                await cancelled
+            return await done
+
+        self.assertEqual(asyncio.run(cancel_second()), "first")
+
+if __name__ == "__main__":
+    unittest.main()
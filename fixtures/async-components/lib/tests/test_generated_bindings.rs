@@ -0,0 +1,10 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["../first/src/first.udl", "../second/src/second.udl",],
+    ["tests/bindings/test_async_components.py",],
+    ["async_functions"]
+);
+
+uniffi_macros::build_golden_file_testcases!(
+    ["../first/src/first.udl", "../second/src/second.udl"],
+    ["python"]
+);
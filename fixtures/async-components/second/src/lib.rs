@@ -0,0 +1,44 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::Duration;
+
+// A future that's ready once `delay_ms` have passed, which a thread of its own wakes up.
+struct Delay {
+    delay_ms: u32,
+    started: bool,
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.started {
+            return Poll::Ready(());
+        }
+        self.started = true;
+        let waker = cx.waker().clone();
+        let delay_ms = self.delay_ms;
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(delay_ms.into()));
+            waker.wake();
+        });
+        Poll::Pending
+    }
+}
+
+async fn name_after(delay_ms: u32) -> String {
+    Delay {
+        delay_ms,
+        started: false,
+    }
+    .await;
+    "second".to_string()
+}
+
+include!(concat!(env!("OUT_DIR"), "/second.uniffi.rs"));
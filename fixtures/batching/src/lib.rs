@@ -0,0 +1,35 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static RELOADS: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Debug, thiserror::Error)]
+pub enum SettingsError {
+    #[error("No setting has that key")]
+    UnknownKey,
+}
+
+fn get_setting(key: String) -> String {
+    format!("value of {}", key)
+}
+
+fn get_limit(key: String) -> Result<u32, SettingsError> {
+    match key.as_str() {
+        "downloads" => Ok(4),
+        "uploads" => Ok(2),
+        _ => Err(SettingsError::UnknownKey),
+    }
+}
+
+fn reload() {
+    RELOADS.fetch_add(1, Ordering::SeqCst);
+}
+
+fn get_reload_count() -> u32 {
+    RELOADS.load(Ordering::SeqCst)
+}
+
+include!(concat!(env!("OUT_DIR"), "/batching.uniffi.rs"));
@@ -0,0 +1,43 @@
+import uniffi.batching.*
+
+// Calls to `[Batchable]` functions can be queued up in a batch and made all at once.
+run {
+    val reloads = getReloadCount()
+    lateinit var theme: BatchResult<String>
+    lateinit var downloads: BatchResult<UInt>
+    lateinit var unknown: BatchResult<UInt>
+    lateinit var reloaded: BatchResult<Unit>
+    batch {
+        theme = getSetting("theme")
+        downloads = getLimit("downloads")
+        unknown = getLimit("nothing")
+        reloaded = reload()
+        // Nothing is called until the block returns.
+        assert(getReloadCount() == reloads)
+    }
+    assert(theme.get() == "value of theme")
+    assert(downloads.get() == 4U)
+    // A call that throws doesn't stop the others.
+    try {
+        unknown.get()
+        throw RuntimeException("Expected the batched getLimit to throw")
+    } catch (e: SettingsException.UnknownKey) {
+        // It's okay!
+    }
+    reloaded.get()
+    assert(getReloadCount() == reloads + 1U)
+}
+
+// The calls in a block that throws aren't made.
+run {
+    val reloads = getReloadCount()
+    try {
+        batch {
+            reload()
+            throw UnsupportedOperationException("changed my mind")
+        }
+    } catch (e: UnsupportedOperationException) {
+        // It's okay!
+    }
+    assert(getReloadCount() == reloads)
+}
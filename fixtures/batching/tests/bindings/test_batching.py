@@ -0,0 +1,41 @@
+import unittest
+from batching import *
+
+class TestBatching(unittest.TestCase):
+    def test_batch(self):
+        reloads = get_reload_count()
+        with batch() as b:
+            theme = b.get_setting("theme")
+            downloads = b.get_limit("downloads")
+            unknown = b.get_limit("nothing")
+            reloaded = b.reload()
+            # Nothing is called until the block ends.
+            with self.assertRaises(RuntimeError):
+                theme.get()
+            self.assertEqual(get_reload_count(), reloads)
+        self.assertEqual(theme.get(), "value of theme")
+        self.assertEqual(downloads.get(), 4)
+        # A call that throws doesn't stop the others.
+        with self.assertRaises(SettingsError.UnknownKey):
+            unknown.get()
+        self.assertIsNone(reloaded.get())
+        self.assertEqual(get_reload_count(), reloads + 1)
+
+    def test_failed_block(self):
+        reloads = get_reload_count()
+        with self.assertRaises(ValueError):
+            with batch() as b:
+                reloaded = b.reload()
+                raise ValueError("changed my mind")
+        # The calls in a block that raises aren't made.
+        with self.assertRaises(RuntimeError):
+            reloaded.get()
+        self.assertEqual(get_reload_count(), reloads)
+
+    def test_arguments_are_checked(self):
+        with batch() as b:
+            with self.assertRaises(ValueError):
+                b.get_setting(None)
+
+if __name__=='__main__':
+    unittest.main()
@@ -0,0 +1,44 @@
+import batching
+
+struct ChangedMyMind: Error {}
+
+// Calls to `[Batchable]` functions can be queued up in a batch and made all at once.
+do {
+    let reloads = getReloadCount()
+    var theme: BatchResult<String>!
+    var downloads: BatchResult<UInt32>!
+    var unknown: BatchResult<UInt32>!
+    batch { batch in
+        theme = batch.getSetting(key: "theme")
+        downloads = batch.getLimit(key: "downloads")
+        unknown = batch.getLimit(key: "nothing")
+        _ = batch.reload()
+        // Nothing is called until the block returns.
+        assert(getReloadCount() == reloads)
+    }
+    assert(try theme.get() == "value of theme")
+    assert(try downloads.get() == 4)
+    // A call that throws doesn't stop the others.
+    do {
+        _ = try unknown.get()
+        fatalError("should have thrown")
+    } catch SettingsError.UnknownKey {
+        // It's okay!
+    }
+    assert(getReloadCount() == reloads + 1)
+}
+
+// The calls in a block that throws aren't made.
+do {
+    let reloads = getReloadCount()
+    do {
+        try batch { batch in
+            _ = batch.reload()
+            throw ChangedMyMind()
+        }
+        fatalError("should have thrown")
+    } catch is ChangedMyMind {
+        // It's okay!
+    }
+    assert(getReloadCount() == reloads)
+}
@@ -0,0 +1,10 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["src/batching.udl",],
+    [
+        "tests/bindings/test_batching.py",
+        "tests/bindings/test_batching.kts",
+        "tests/bindings/test_batching.swift",
+    ]
+);
+
+uniffi_macros::build_golden_file_testcases!(["src/batching.udl"], ["kotlin", "swift", "python"]);
@@ -72,4 +72,387 @@ impl RustStringifier {
     }
 }
 
+lazy_static::lazy_static! {
+    static ref STASHED_STRINGIFIERS: std::sync::Mutex<Vec<Box<dyn StoredForeignStringifier>>> =
+        std::sync::Mutex::new(Vec::new());
+}
+
+#[derive(Debug)]
+pub struct StashingStringifier;
+
+impl StashingStringifier {
+    fn new(callback: Box<dyn StoredForeignStringifier>) -> Self {
+        STASHED_STRINGIFIERS.lock().unwrap().push(callback);
+        StashingStringifier
+    }
+
+    fn share(self: std::sync::Arc<Self>) -> std::sync::Arc<Self> {
+        self
+    }
+}
+
+fn call_stashed_stringifier(value: i32) -> Option<String> {
+    let stashed = STASHED_STRINGIFIERS.lock().unwrap();
+    let callback = stashed.last()?;
+    // Calling a released callback panics, which we report as `None`.
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        callback.from_simple_type(value)
+    }))
+    .ok()
+}
+
+trait ProgressListener {
+    fn on_start(&self, task: String);
+    fn on_progress(&self, percent: u32);
+    fn retries_allowed(&self) -> u32;
+}
+
+#[derive(Debug, Clone)]
+pub struct RustTask;
+
+impl RustTask {
+    fn new() -> Self {
+        RustTask
+    }
+
+    // Report some progress, then ask how many times the task may be retried.
+    fn run(&self, listener: Box<dyn ProgressListener>) -> u32 {
+        listener.on_start("counting".to_string());
+        listener.on_progress(50);
+        listener.on_progress(100);
+        listener.retries_allowed()
+    }
+}
+
+trait SampleProcessor {
+    fn process(&self, sample: f64, index: u32) -> f64;
+    fn is_done(&self, count: u32) -> bool;
+    fn describe(&self) -> String;
+}
+
+#[derive(Debug, Clone)]
+pub struct RustSampler;
+
+impl RustSampler {
+    fn new() -> Self {
+        RustSampler
+    }
+
+    // Sum up the processed samples, until there are `count` of them or the processor has had enough.
+    fn run(&self, processor: Box<dyn SampleProcessor>, count: u32) -> f64 {
+        let mut total = 0.0;
+        for index in 0..count {
+            if processor.is_done(index) {
+                break;
+            }
+            total += processor.process(index as f64 * 0.5, index);
+        }
+        total
+    }
+
+    fn describe(&self, processor: Box<dyn SampleProcessor>) -> String {
+        processor.describe()
+    }
+}
+
+trait EventListener: Send + Sync {
+    fn on_event(&self, thread: u32, index: u32);
+    fn on_done(&self);
+}
+
+#[derive(Debug, Clone)]
+pub struct RustEventSource;
+
+impl RustEventSource {
+    fn new() -> Self {
+        RustEventSource
+    }
+
+    // Fire `events` events from each of `threads` threads, then say that they're all done.
+    fn fire(&self, listener: Box<dyn EventListener>, threads: u32, events: u32) {
+        let listener: std::sync::Arc<dyn EventListener> = listener.into();
+        let handles: Vec<_> = (0..threads)
+            .map(|thread| {
+                let listener = std::sync::Arc::clone(&listener);
+                std::thread::spawn(move || {
+                    for index in 0..events {
+                        listener.on_event(thread, index);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        listener.on_done();
+    }
+}
+
+trait Downloader: uniffi::VersionedCallback + Send {
+    fn fetch(&self, url: String) -> String;
+    fn fetch_cached(&self, url: String) -> Option<String>;
+}
+
+#[derive(Debug, Clone)]
+pub struct RustDownloadManager;
+
+impl RustDownloadManager {
+    fn new() -> Self {
+        RustDownloadManager
+    }
+
+    // Only ask for a cached copy if the downloader is new enough to have a cache.
+    fn download(&self, downloader: Box<dyn Downloader>, url: String) -> String {
+        if downloader.capabilities().implements("fetch_cached") {
+            if let Some(cached) = downloader.fetch_cached(url.clone()) {
+                return cached;
+            }
+        }
+        downloader.fetch(url)
+    }
+
+    fn is_current(&self, downloader: Box<dyn Downloader>) -> bool {
+        downloader.capabilities().is_current()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CounterState {
+    label: String,
+    count: u32,
+}
+
+pub struct ObservableCounter {
+    state: std::sync::Mutex<CounterState>,
+    observers: uniffi::Observers<CounterState>,
+}
+
+impl uniffi::Observable for ObservableCounter {
+    type Snapshot = CounterState;
+
+    fn observers(&self) -> &uniffi::Observers<CounterState> {
+        &self.observers
+    }
+}
+
+impl ObservableCounter {
+    fn new(label: String) -> Self {
+        ObservableCounter {
+            state: std::sync::Mutex::new(CounterState { label, count: 0 }),
+            observers: uniffi::Observers::new(),
+        }
+    }
+
+    // Change the state, then tell the observers which field changed, without holding the lock.
+    fn update(&self, field: &str, change: impl FnOnce(&mut CounterState)) {
+        let snapshot = {
+            let mut state = self.state.lock().unwrap();
+            change(&mut state);
+            state.clone()
+        };
+        self.observers.notify_changed(field, snapshot);
+    }
+
+    fn increment(&self) {
+        self.update("count", |state| state.count += 1);
+    }
+
+    fn rename(&self, label: String) {
+        self.update("label", |state| state.label = label);
+    }
+
+    fn snapshot(&self) -> CounterState {
+        self.state.lock().unwrap().clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum MetronomeEvent {
+    Tick { count: u32 },
+    Stopped,
+}
+
+#[derive(Default)]
+pub struct Metronome {
+    count: std::sync::atomic::AtomicU32,
+    listeners: uniffi::Listeners<MetronomeEvent>,
+}
+
+impl uniffi::EventSource for Metronome {
+    type Event = MetronomeEvent;
+
+    fn listeners(&self) -> &uniffi::Listeners<MetronomeEvent> {
+        &self.listeners
+    }
+}
+
+impl Metronome {
+    fn new() -> Self {
+        Metronome::default()
+    }
+
+    fn tick(&self) {
+        let count = self.count.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+        self.listeners.emit(MetronomeEvent::Tick { count });
+    }
+
+    fn stop(&self) {
+        self.listeners.emit(MetronomeEvent::Stopped);
+    }
+}
+
+#[derive(Default)]
+pub struct Heartbeat {
+    lifecycle: uniffi::ServiceLifecycle,
+    starts: std::sync::atomic::AtomicU32,
+}
+
+impl uniffi::Service for Heartbeat {
+    fn lifecycle(&self) -> &uniffi::ServiceLifecycle {
+        &self.lifecycle
+    }
+
+    fn on_start(&self) {
+        self.starts
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    fn on_stop(&self) {}
+}
+
+impl Heartbeat {
+    fn new() -> Self {
+        Heartbeat::default()
+    }
+
+    fn starts(&self) -> u32 {
+        self.starts.load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shelf {
+    Fiction,
+    Reference,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Book {
+    title: String,
+    author_names: Vec<String>,
+    shelf: Shelf,
+    year_published: Option<u32>,
+}
+
+trait ForeignLibrary {
+    fn book_by_title(&self, book_title: String) -> Book;
+    fn books_on_shelf(&self, shelf: Shelf) -> Vec<Book>;
+    fn find_book(&self, book_title: String, year_published: Option<u32>) -> Option<Book>;
+    fn count_by_shelf(&self) -> std::collections::HashMap<String, u32>;
+    fn shelf_of(&self, book_title: String) -> Shelf;
+}
+
+#[derive(Debug, Clone)]
+pub struct RustLibrarian;
+
+impl RustLibrarian {
+    fn new() -> Self {
+        RustLibrarian
+    }
+
+    fn book_by_title(&self, library: Box<dyn ForeignLibrary>, book_title: String) -> Book {
+        library.book_by_title(book_title)
+    }
+
+    fn books_on_shelf(&self, library: Box<dyn ForeignLibrary>, shelf: Shelf) -> Vec<Book> {
+        library.books_on_shelf(shelf)
+    }
+
+    fn find_book(
+        &self,
+        library: Box<dyn ForeignLibrary>,
+        book_title: String,
+        year_published: Option<u32>,
+    ) -> Option<Book> {
+        library.find_book(book_title, year_published)
+    }
+
+    fn count_by_shelf(
+        &self,
+        library: Box<dyn ForeignLibrary>,
+    ) -> std::collections::HashMap<String, u32> {
+        library.count_by_shelf()
+    }
+
+    fn shelf_of(&self, library: Box<dyn ForeignLibrary>, book_title: String) -> Shelf {
+        library.shelf_of(book_title)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LoanError {
+    #[error("There's no book called {book_title}")]
+    NotFound { book_title: String },
+    #[error("{book_title} is on loan for another {days_left} days")]
+    OnLoan { book_title: String, days_left: u32 },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CatalogError {
+    #[error("The catalog is offline")]
+    Offline,
+}
+
+trait ForeignLender {
+    fn lend(&self, book_title: String) -> Result<Book, LoanError>;
+    fn reindex(&self) -> Result<(), CatalogError>;
+}
+
+#[derive(Debug, Clone)]
+pub struct RustBorrower;
+
+impl RustBorrower {
+    fn new() -> Self {
+        RustBorrower
+    }
+
+    fn checkout(
+        &self,
+        lender: Box<dyn ForeignLender>,
+        book_title: String,
+    ) -> Result<Book, LoanError> {
+        lender.lend(book_title)
+    }
+
+    fn reindex(&self, lender: Box<dyn ForeignLender>) -> Result<(), CatalogError> {
+        lender.reindex()
+    }
+}
+
+trait ForeignArchive {
+    fn retrieve(&self, book_title: String) -> uniffi::CallbackFuture<Result<Book, LoanError>>;
+    fn count(&self) -> uniffi::CallbackFuture<u32>;
+}
+
+#[derive(Debug, Clone)]
+pub struct RustArchivist;
+
+impl RustArchivist {
+    fn new() -> Self {
+        RustArchivist
+    }
+
+    fn retrieve(
+        &self,
+        archive: Box<dyn ForeignArchive>,
+        book_title: String,
+    ) -> Result<Book, LoanError> {
+        archive.retrieve(book_title).wait()
+    }
+
+    fn count(&self, archive: Box<dyn ForeignArchive>) -> u32 {
+        archive.count().wait()
+    }
+}
+
 include!(concat!(env!("OUT_DIR"), "/callbacks.uniffi.rs"));
@@ -3,6 +3,10 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 import uniffi.callbacks.*
+import kotlin.concurrent.thread
+import kotlin.coroutines.resume
+import kotlin.coroutines.resumeWithException
+import kotlin.coroutines.suspendCoroutine
 
 // A bit more systematic in testing, but this time in English.
 //
@@ -71,3 +75,285 @@ listOf(1, 2).forEach { v ->
     assert(expected == observed) { "callback is sent on construction: $expected != $observed" }
 }
 rustStringifier.destroy()
+
+// 3. Callbacks passed to the constructor of a `[ReleaseCallbacks]` interface are released
+// when the object is destroyed, even though the Rust code is still holding on to them.
+val stashingStringifier = StashingStringifier(kotlinStringifier)
+assert(callStashedStringifier(3) == "kotlin: 3") { "stashed callback is live" }
+stashingStringifier.destroy()
+assert(callStashedStringifier(3) == null) { "stashed callback is released with its object" }
+
+// They're only released once every handle to the object has been destroyed.
+val sharedStringifier = StashingStringifier(kotlinStringifier)
+val otherHandle = sharedStringifier.share()
+otherHandle.destroy()
+assert(callStashedStringifier(3) == "kotlin: 3") { "stashed callback outlives the other handle" }
+sharedStringifier.destroy()
+assert(callStashedStringifier(3) == null) { "stashed callback is released with the last handle" }
+
+// 4. `[Optional]` callback methods can be left out of an implementation, in which case
+// they do nothing, or return an empty value.
+class MinimalListener: ProgressListener {
+    val started = mutableListOf<String>()
+    override fun onStart(task: String) { started.add(task) }
+}
+
+class FullListener: ProgressListener {
+    val progress = mutableListOf<UInt>()
+    override fun onStart(task: String) {}
+    override fun onProgress(percent: UInt) { progress.add(percent) }
+    override fun retriesAllowed(): UInt = 3u
+}
+
+val rustTask = RustTask()
+val minimalListener = MinimalListener()
+assert(rustTask.run(minimalListener) == 0u) { "optional method returns its default" }
+assert(minimalListener.started == listOf("counting"))
+val fullListener = FullListener()
+assert(rustTask.run(fullListener) == 3u) { "implemented optional method is called" }
+assert(fullListener.progress == listOf(50u, 100u))
+rustTask.destroy()
+
+// 5. The primitive-only methods of a `[Direct]` callback interface are called through function
+// pointers of their own, and the rest as usual; implementations can't tell the difference.
+class Doubler: SampleProcessor {
+    val seen = mutableListOf<UInt>()
+    override fun process(sample: Double, index: UInt): Double {
+        seen.add(index)
+        return sample * 2.0
+    }
+    override fun isDone(count: UInt): Boolean = count >= 4u
+    override fun describe(): String = "doubler"
+}
+
+val rustSampler = RustSampler()
+val doubler = Doubler()
+assert(rustSampler.run(doubler, 10u) == 6.0) { "direct methods pass arguments and results" }
+assert(doubler.seen == listOf(0u, 1u, 2u, 3u))
+assert(rustSampler.describe(doubler) == "doubler") { "other methods still work" }
+rustSampler.destroy()
+
+// 6. Calls to an `[Ordered]` callback interface arrive one at a time, in the order they were
+// made, even though Rust makes them from several threads at once.
+class RecordingListener: EventListener {
+    val events = mutableListOf<Pair<UInt, UInt>>()
+    val active = java.util.concurrent.atomic.AtomicInteger(0)
+    var overlapped = false
+    val done = java.util.concurrent.CountDownLatch(1)
+    override fun onEvent(thread: UInt, index: UInt) {
+        if (active.incrementAndGet() > 1) {
+            overlapped = true
+        }
+        events.add(Pair(thread, index))
+        active.decrementAndGet()
+    }
+    override fun onDone() {
+        done.countDown()
+    }
+}
+
+val eventSource = RustEventSource()
+val recorder = RecordingListener()
+eventSource.fire(recorder, 4u, 50u)
+assert(recorder.done.await(10, java.util.concurrent.TimeUnit.SECONDS)) { "all the calls are delivered" }
+assert(recorder.events.size == 200)
+assert(!recorder.overlapped) { "calls are delivered one at a time" }
+for (thread in 0u until 4u) {
+    val indices = recorder.events.filter { it.first == thread }.map { it.second }
+    assert(indices == (0u until 50u).toList()) { "calls from each thread arrive in order" }
+}
+eventSource.destroy()
+
+// 7. An `[Observable]` object tells each of its observers which field changed, along with a
+// snapshot of its new state, until they're removed.
+class RecordingObserver: ObservableCounterObserver {
+    val changes = mutableListOf<Pair<String, CounterState>>()
+    override fun onChanged(field: String, snapshot: CounterState) {
+        changes.add(Pair(field, snapshot))
+    }
+}
+
+val counter = ObservableCounter("clicks")
+val observer = RecordingObserver()
+val observerId = counter.addObserver(observer)
+counter.increment()
+counter.rename("taps")
+counter.removeObserver(observerId)
+counter.increment()
+assert(observer.changes == listOf(
+    Pair("count", CounterState("clicks", 1u)),
+    Pair("label", CounterState("taps", 1u))
+)) { "observers are told about each change until they're removed" }
+assert(counter.snapshot() == CounterState("taps", 2u))
+counter.destroy()
+
+// 8. The objects of a `[Versioned]` callback interface tell Rust which version of it they're
+// from, and which of its methods they have.
+class PlainDownloader: Downloader {
+    override fun fetch(url: String): String = "fetched $url"
+}
+
+class CachingDownloader: Downloader {
+    override fun fetch(url: String): String = "fetched $url"
+    override fun fetchCached(url: String): String? = if (url == "home") "cached home" else null
+}
+
+val downloadManager = RustDownloadManager()
+assert(downloadManager.isCurrent(PlainDownloader())) { "bindings from the same UDL are current" }
+assert(downloadManager.download(PlainDownloader(), "home") == "fetched home")
+assert(downloadManager.download(CachingDownloader(), "home") == "cached home")
+assert(downloadManager.download(CachingDownloader(), "away") == "fetched away")
+downloadManager.destroy()
+
+// 9. A `[Service]` object can be started and stopped, and tells its state observers about each
+// state it moves into. Starting it while it's running does nothing.
+class RecordingStateObserver: HeartbeatStateObserver {
+    val states = mutableListOf<ServiceState>()
+    override fun onStateChanged(state: ServiceState) {
+        states.add(state)
+    }
+}
+
+val heartbeat = Heartbeat()
+val stateObserver = RecordingStateObserver()
+val stateObserverId = heartbeat.addStateObserver(stateObserver)
+assert(!heartbeat.isRunning())
+heartbeat.start()
+heartbeat.start()
+assert(heartbeat.isRunning())
+heartbeat.stop()
+heartbeat.removeStateObserver(stateObserverId)
+heartbeat.start()
+assert(heartbeat.starts() == 2u)
+assert(stateObserver.states == listOf(
+    ServiceState.STARTING,
+    ServiceState.RUNNING,
+    ServiceState.STOPPING,
+    ServiceState.STOPPED
+)) { "state observers are told about each state until they're removed" }
+heartbeat.destroy()
+
+// 10. Callback methods can return records, sequences, optionals, maps and enums, which come back
+// to Rust intact.
+class KotlinLibrary : ForeignLibrary {
+    val books = listOf(
+        Book("Dune", listOf("Frank Herbert"), Shelf.FICTION, 1965u),
+        Book("Good Omens", listOf("Terry Pratchett", "Neil Gaiman"), Shelf.FICTION, null),
+        Book("Atlas", listOf(), Shelf.REFERENCE, 2001u)
+    )
+    override fun bookByTitle(bookTitle: String): Book = books.first { it.title == bookTitle }
+    override fun booksOnShelf(shelf: Shelf): List<Book> = books.filter { it.shelf == shelf }
+    override fun findBook(bookTitle: String, yearPublished: UInt?): Book? =
+        books.firstOrNull { it.title == bookTitle && it.yearPublished == yearPublished }
+    override fun countByShelf(): Map<String, UInt> = mapOf(
+        "fiction" to booksOnShelf(Shelf.FICTION).size.toUInt(),
+        "reference" to booksOnShelf(Shelf.REFERENCE).size.toUInt()
+    )
+    override fun shelfOf(bookTitle: String): Shelf = bookByTitle(bookTitle).shelf
+}
+
+val librarian = RustLibrarian()
+val library = KotlinLibrary()
+assert(librarian.bookByTitle(library, "Good Omens") == library.books[1]) { "records come back" }
+assert(librarian.booksOnShelf(library, Shelf.FICTION) == library.books.take(2)) { "sequences come back" }
+assert(librarian.booksOnShelf(library, Shelf.REFERENCE).size == 1)
+assert(librarian.findBook(library, "Dune", 1965u) == library.books[0]) { "optionals come back" }
+assert(librarian.findBook(library, "Good Omens", null) == library.books[1])
+assert(librarian.findBook(library, "Dune", null) == null)
+assert(librarian.countByShelf(library) == mapOf("fiction" to 2u, "reference" to 1u)) { "maps come back" }
+assert(librarian.shelfOf(library, "Atlas") == Shelf.REFERENCE) { "enums come back" }
+librarian.destroy()
+
+// 11. Callback methods can throw the errors that they declare, which come back to Rust as the
+// errors of the methods that called them.
+class KotlinLender : ForeignLender {
+    var reindexed = false
+    override fun lend(bookTitle: String): Book = when (bookTitle) {
+        "Dune" -> Book("Dune", listOf("Frank Herbert"), Shelf.FICTION, 1965u)
+        "Atlas" -> throw LoanException.OnLoan(bookTitle, 3u)
+        else -> throw LoanException.NotFound(bookTitle)
+    }
+    override fun reindex() {
+        if (reindexed) {
+            throw CatalogException.Offline("still reindexing")
+        }
+        reindexed = true
+    }
+}
+
+val borrower = RustBorrower()
+val lender = KotlinLender()
+assert(borrower.checkout(lender, "Dune").title == "Dune")
+try {
+    borrower.checkout(lender, "Atlas")
+    throw RuntimeException("Should have thrown a LoanException")
+} catch (e: LoanException.OnLoan) {
+    assert(e.bookTitle == "Atlas" && e.daysLeft == 3u) { "thrown errors keep their fields" }
+}
+try {
+    borrower.checkout(lender, "Emma")
+    throw RuntimeException("Should have thrown a LoanException")
+} catch (e: LoanException.NotFound) {
+    assert(e.bookTitle == "Emma")
+}
+borrower.reindex(lender)
+try {
+    borrower.reindex(lender)
+    throw RuntimeException("Should have thrown a CatalogException")
+} catch (e: CatalogException.Offline) {
+    // Flat errors come back with the message of the Rust error.
+    assert(e.message == "The catalog is offline") { "unexpected message: ${e.message}" }
+}
+borrower.destroy()
+
+// 12. `[Async]` callback methods are `suspend` functions, which Rust gets a future for.
+class KotlinArchive : ForeignArchive {
+    override suspend fun retrieve(bookTitle: String): Book = suspendCoroutine { continuation ->
+        // Finish on another thread, as an archive that has to fetch the book would.
+        thread {
+            if (bookTitle == "Dune") {
+                continuation.resume(Book("Dune", listOf("Frank Herbert"), Shelf.FICTION, 1965u))
+            } else {
+                continuation.resumeWithException(LoanException.NotFound(bookTitle))
+            }
+        }
+    }
+    // This one finishes without suspending.
+    override suspend fun count(): UInt = 42u
+}
+
+val archivist = RustArchivist()
+val archive = KotlinArchive()
+assert(archivist.retrieve(archive, "Dune").title == "Dune")
+try {
+    archivist.retrieve(archive, "Emma")
+    throw RuntimeException("Should have thrown a LoanException")
+} catch (e: LoanException.NotFound) {
+    assert(e.bookTitle == "Emma")
+}
+assert(archivist.count(archive) == 42u)
+archivist.destroy()
+
+// 13. An `[Events]` object gives each of its listeners the events that it emits, until they're
+// removed.
+class RecordingListener: MetronomeListener {
+    val events = mutableListOf<MetronomeEvent>()
+    override fun onEvent(event: MetronomeEvent) {
+        events.add(event)
+    }
+}
+
+val metronome = Metronome()
+val listener = RecordingListener()
+val listenerId = metronome.addListener(listener)
+metronome.tick()
+metronome.tick()
+metronome.stop()
+metronome.removeListener(listenerId)
+metronome.tick()
+assert(listener.events == listOf(
+    MetronomeEvent.Tick(1u),
+    MetronomeEvent.Tick(2u),
+    MetronomeEvent.Stopped
+)) { "listeners get each event until they're removed" }
+metronome.destroy()
@@ -2,6 +2,8 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+import Foundation
+
 #if canImport(callbacks)
     import callbacks
 #endif
@@ -74,4 +76,317 @@ func test() {
         assert(expected == observed, "callback is sent on construction: \(expected) != \(observed)")
     }
 
+    // 3. Callbacks passed to the constructor of a `[ReleaseCallbacks]` interface are released
+    // when the object is destroyed, even though the Rust code is still holding on to them.
+    do {
+        let stashingStringifier = StashingStringifier(callback: swiftStringifier)
+        assert(callStashedStringifier(value: 3) == "swift: 3", "stashed callback is live")
+        _ = stashingStringifier
+    }
+    assert(callStashedStringifier(value: 3) == nil, "stashed callback is released with its object")
+
+    // They're only released once every handle to the object has been destroyed.
+    do {
+        let sharedStringifier = StashingStringifier(callback: swiftStringifier)
+        do {
+            let otherHandle = sharedStringifier.share()
+            _ = otherHandle
+        }
+        assert(callStashedStringifier(value: 3) == "swift: 3", "stashed callback outlives the other handle")
+        _ = sharedStringifier
+    }
+    assert(callStashedStringifier(value: 3) == nil, "stashed callback is released with the last handle")
+}
+
+// 4. `[Optional]` callback methods can be left out of an implementation, in which case
+// they do nothing, or return an empty value.
+do {
+    class MinimalListener: ProgressListener {
+        var started: [String] = []
+        func onStart(task: String) { started.append(task) }
+    }
+
+    class FullListener: ProgressListener {
+        var progress: [UInt32] = []
+        func onStart(task: String) {}
+        func onProgress(percent: UInt32) { progress.append(percent) }
+        func retriesAllowed() -> UInt32 { 3 }
+    }
+
+    let rustTask = RustTask()
+    let minimalListener = MinimalListener()
+    assert(rustTask.run(listener: minimalListener) == 0, "optional method returns its default")
+    assert(minimalListener.started == ["counting"])
+    let fullListener = FullListener()
+    assert(rustTask.run(listener: fullListener) == 3, "implemented optional method is called")
+    assert(fullListener.progress == [50, 100])
+}
+
+// 5. The primitive-only methods of a `[Direct]` callback interface are called through function
+// pointers of their own, and the rest as usual; implementations can't tell the difference.
+do {
+    class Doubler: SampleProcessor {
+        var seen: [UInt32] = []
+        func process(sample: Double, index: UInt32) -> Double {
+            seen.append(index)
+            return sample * 2.0
+        }
+        func isDone(count: UInt32) -> Bool { count >= 4 }
+        func describe() -> String { "doubler" }
+    }
+
+    let rustSampler = RustSampler()
+    let doubler = Doubler()
+    assert(rustSampler.run(processor: doubler, count: 10) == 6.0, "direct methods pass arguments and results")
+    assert(doubler.seen == [0, 1, 2, 3])
+    assert(rustSampler.describe(processor: doubler) == "doubler", "other methods still work")
+}
+
+// 6. Calls to an `[Ordered]` callback interface arrive one at a time, in the order they were
+// made, even though Rust makes them from several threads at once.
+do {
+    class RecordingListener: EventListener {
+        var events: [(UInt32, UInt32)] = []
+        let done = DispatchSemaphore(value: 0)
+        func onEvent(thread: UInt32, index: UInt32) {
+            events.append((thread, index))
+        }
+        func onDone() {
+            done.signal()
+        }
+    }
+
+    let eventSource = RustEventSource()
+    let recorder = RecordingListener()
+    eventSource.fire(listener: recorder, threads: 4, events: 50)
+    assert(recorder.done.wait(timeout: .now() + 10) == .success, "all the calls are delivered")
+    assert(recorder.events.count == 200)
+    for thread in UInt32(0)..<4 {
+        let indices = recorder.events.filter { $0.0 == thread }.map { $0.1 }
+        assert(indices == Array(UInt32(0)..<50), "calls from each thread arrive in order")
+    }
+}
+
+// 7. An `[Observable]` object tells each of its observers which field changed, along with a
+// snapshot of its new state, until they're removed.
+do {
+    class RecordingObserver: ObservableCounterObserver {
+        var changes: [(String, CounterState)] = []
+        func onChanged(field: String, snapshot: CounterState) {
+            changes.append((field, snapshot))
+        }
+    }
+
+    let counter = ObservableCounter(label: "clicks")
+    let observer = RecordingObserver()
+    let observerId = counter.addObserver(observer: observer)
+    counter.increment()
+    counter.rename(label: "taps")
+    counter.removeObserver(id: observerId)
+    counter.increment()
+    assert(observer.changes.map { $0.0 } == ["count", "label"], "observers are told which field changed")
+    assert(observer.changes.map { $0.1 } == [
+        CounterState(label: "clicks", count: 1),
+        CounterState(label: "taps", count: 1),
+    ], "with a snapshot of the new state")
+    assert(counter.snapshot() == CounterState(label: "taps", count: 2))
+
+    // Where there's Combine, the model publishes the latest snapshot for SwiftUI.
+    #if canImport(Combine)
+    if #available(macOS 10.15, iOS 13, tvOS 13, watchOS 6, *) {
+        let model = ObservableCounterModel(counter, initial: counter.snapshot())
+        counter.increment()
+        assert(model.state == CounterState(label: "taps", count: 3), "the model has the latest snapshot")
+    }
+    #endif
+}
+
+// 8. The objects of a `[Versioned]` callback interface tell Rust which version of it they're
+// from, and which of its methods they have.
+do {
+    class PlainDownloader: Downloader {
+        func fetch(url: String) -> String { "fetched \(url)" }
+    }
+
+    class CachingDownloader: Downloader {
+        func fetch(url: String) -> String { "fetched \(url)" }
+        func fetchCached(url: String) -> String? { url == "home" ? "cached home" : nil }
+    }
+
+    let downloadManager = RustDownloadManager()
+    assert(downloadManager.isCurrent(downloader: PlainDownloader()), "bindings from the same UDL are current")
+    assert(downloadManager.download(downloader: PlainDownloader(), url: "home") == "fetched home")
+    assert(downloadManager.download(downloader: CachingDownloader(), url: "home") == "cached home")
+    assert(downloadManager.download(downloader: CachingDownloader(), url: "away") == "fetched away")
+}
+
+// 9. A `[Service]` object can be started and stopped, and tells its state observers about each
+// state it moves into. Starting it while it's running does nothing.
+do {
+    class RecordingStateObserver: HeartbeatStateObserver {
+        var states: [ServiceState] = []
+        func onStateChanged(state: ServiceState) {
+            states.append(state)
+        }
+    }
+
+    let heartbeat = Heartbeat()
+    let observer = RecordingStateObserver()
+    let observerId = heartbeat.addStateObserver(observer: observer)
+    assert(!heartbeat.isRunning())
+    heartbeat.start()
+    heartbeat.start()
+    assert(heartbeat.isRunning())
+    heartbeat.stop()
+    heartbeat.removeStateObserver(id: observerId)
+    heartbeat.start()
+    assert(heartbeat.starts() == 2)
+    assert(observer.states == [.starting, .running, .stopping, .stopped], "state observers are told about each state")
+}
+
+// 10. Callback methods can return records, sequences, optionals, maps and enums, which come back
+// to Rust intact, and take arguments whose names are more than one word.
+do {
+    class SwiftLibrary: ForeignLibrary {
+        let books = [
+            Book(title: "Dune", authorNames: ["Frank Herbert"], shelf: .fiction, yearPublished: 1965),
+            Book(title: "Good Omens", authorNames: ["Terry Pratchett", "Neil Gaiman"], shelf: .fiction, yearPublished: nil),
+            Book(title: "Atlas", authorNames: [], shelf: .reference, yearPublished: 2001),
+        ]
+        func bookByTitle(bookTitle: String) -> Book { books.first { $0.title == bookTitle }! }
+        func booksOnShelf(shelf: Shelf) -> [Book] { books.filter { $0.shelf == shelf } }
+        func findBook(bookTitle: String, yearPublished: UInt32?) -> Book? {
+            books.first { $0.title == bookTitle && $0.yearPublished == yearPublished }
+        }
+        func countByShelf() -> [String: UInt32] {
+            ["fiction": UInt32(booksOnShelf(shelf: .fiction).count), "reference": UInt32(booksOnShelf(shelf: .reference).count)]
+        }
+        func shelfOf(bookTitle: String) -> Shelf { bookByTitle(bookTitle: bookTitle).shelf }
+    }
+
+    let librarian = RustLibrarian()
+    let library = SwiftLibrary()
+    assert(librarian.bookByTitle(library: library, bookTitle: "Good Omens") == library.books[1], "records come back")
+    assert(librarian.booksOnShelf(library: library, shelf: .fiction) == Array(library.books[0...1]), "sequences come back")
+    assert(librarian.booksOnShelf(library: library, shelf: .reference).count == 1)
+    assert(librarian.findBook(library: library, bookTitle: "Dune", yearPublished: 1965) == library.books[0], "optionals come back")
+    assert(librarian.findBook(library: library, bookTitle: "Good Omens", yearPublished: nil) == library.books[1])
+    assert(librarian.findBook(library: library, bookTitle: "Dune", yearPublished: nil) == nil)
+    assert(librarian.countByShelf(library: library) == ["fiction": 2, "reference": 1], "maps come back")
+    assert(librarian.shelfOf(library: library, bookTitle: "Atlas") == .reference, "enums come back")
+}
+
+// 11. Callback methods can throw the errors that they declare, which come back to Rust as the
+// errors of the methods that called them.
+do {
+    class SwiftLender: ForeignLender {
+        var reindexed = false
+        func lend(bookTitle: String) throws -> Book {
+            switch bookTitle {
+            case "Dune": return Book(title: "Dune", authorNames: ["Frank Herbert"], shelf: .fiction, yearPublished: 1965)
+            case "Atlas": throw LoanError.OnLoan(bookTitle: bookTitle, daysLeft: 3)
+            default: throw LoanError.NotFound(bookTitle: bookTitle)
+            }
+        }
+        func reindex() throws {
+            if reindexed {
+                throw CatalogError.Offline(message: "still reindexing")
+            }
+            reindexed = true
+        }
+    }
+
+    let borrower = RustBorrower()
+    let lender = SwiftLender()
+    assert(try! borrower.checkout(lender: lender, bookTitle: "Dune").title == "Dune")
+    do {
+        _ = try borrower.checkout(lender: lender, bookTitle: "Atlas")
+        fatalError("Should have thrown a LoanError")
+    } catch LoanError.OnLoan(let bookTitle, let daysLeft) {
+        assert(bookTitle == "Atlas" && daysLeft == 3, "thrown errors keep their fields")
+    } catch {
+        fatalError("Unexpected error: \(error)")
+    }
+    do {
+        _ = try borrower.checkout(lender: lender, bookTitle: "Emma")
+        fatalError("Should have thrown a LoanError")
+    } catch LoanError.NotFound(let bookTitle) {
+        assert(bookTitle == "Emma")
+    } catch {
+        fatalError("Unexpected error: \(error)")
+    }
+    try! borrower.reindex(lender: lender)
+    do {
+        try borrower.reindex(lender: lender)
+        fatalError("Should have thrown a CatalogError")
+    } catch CatalogError.Offline(let message) {
+        // Flat errors come back with the message of the Rust error.
+        assert(message == "The catalog is offline")
+    } catch {
+        fatalError("Unexpected error: \(error)")
+    }
+}
+
+// 12. `[Async]` callback methods are `async` functions, which Rust gets a future for.
+if #available(iOS 13, macOS 10.15, tvOS 13, watchOS 6, *) {
+    class SwiftArchive: ForeignArchive {
+        func retrieve(bookTitle: String) async throws -> Book {
+            // Finish later, as an archive that has to fetch the book would.
+            try await Task.sleep(nanoseconds: 1_000_000)
+            if bookTitle == "Dune" {
+                return Book(title: "Dune", authorNames: ["Frank Herbert"], shelf: .fiction, yearPublished: 1965)
+            }
+            throw LoanError.NotFound(bookTitle: bookTitle)
+        }
+        func count() async -> UInt32 {
+            return 42
+        }
+    }
+
+    let archivist = RustArchivist()
+    let archive = SwiftArchive()
+    assert(try! archivist.retrieve(archive: archive, bookTitle: "Dune").title == "Dune")
+    do {
+        _ = try archivist.retrieve(archive: archive, bookTitle: "Emma")
+        fatalError("Should have thrown a LoanError")
+    } catch LoanError.NotFound(let bookTitle) {
+        assert(bookTitle == "Emma")
+    } catch {
+        fatalError("Unexpected error: \(error)")
+    }
+    assert(archivist.count(archive: archive) == 42)
+}
+
+// 13. An `[Events]` object gives each of its listeners the events that it emits, until they're
+// removed.
+do {
+    class RecordingListener: MetronomeListener {
+        var events: [MetronomeEvent] = []
+        func onEvent(event: MetronomeEvent) {
+            events.append(event)
+        }
+    }
+
+    let metronome = Metronome()
+    let listener = RecordingListener()
+    let listenerId = metronome.addListener(listener: listener)
+    metronome.tick()
+    metronome.tick()
+    metronome.stop()
+    metronome.removeListener(id: listenerId)
+    metronome.tick()
+    assert(listener.events == [.tick(count: 1), .tick(count: 2), .stopped],
+        "listeners get each event until they're removed")
+
+    // Where there's Combine, the events can be subscribed to as a publisher too.
+    #if canImport(Combine)
+    if #available(macOS 10.15, iOS 13, tvOS 13, watchOS 6, *) {
+        var received: [MetronomeEvent] = []
+        let subscription = metronome.eventPublisher.sink { received.append($0) }
+        metronome.tick()
+        subscription.cancel()
+        metronome.tick()
+        assert(received == [.tick(count: 4)], "the publisher gets the events until it's cancelled")
+    }
+    #endif
 }
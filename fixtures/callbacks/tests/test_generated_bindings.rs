@@ -6,3 +6,5 @@ uniffi_macros::build_foreign_language_testcases!(
         //"tests/bindings/test_callbacks.py", // see https://github.com/mozilla/uniffi-rs/pull/1068
     ]
 );
+
+uniffi_macros::build_golden_file_testcases!(["src/callbacks.udl"], ["kotlin", "swift"]);
@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 
 lazy_static::lazy_static! {
@@ -33,6 +33,195 @@ impl From<InternalCoverallError> for CoverallError {
     }
 }
 
+/// An error with lots of variants, for benchmarking how quickly the bindings can lift them.
+#[derive(Debug, thiserror::Error)]
+pub enum ChainedError {
+    #[error("Failed to load the coverall")]
+    LoadFailed(#[source] CoverallConfigError),
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("The coverall config is invalid")]
+pub struct CoverallConfigError(#[source] std::io::Error);
+
+fn throw_chained_error(reason: String) -> Result<(), ChainedError> {
+    let err = std::io::Error::new(std::io::ErrorKind::Other, reason);
+    Err(ChainedError::LoadFailed(CoverallConfigError(err)))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseError {
+    #[error("InvalidNumber: {reason}")]
+    InvalidNumber { reason: String },
+    #[error("Other: {reason}")]
+    Other { reason: String },
+}
+
+// The `From` impls that `?` uses here are generated from the `[ErrorFrom]` attributes.
+fn parse_count(text: String) -> Result<u32, ParseError> {
+    if text.trim().is_empty() {
+        let err: Box<dyn std::error::Error + Send + Sync> = "nothing to parse".into();
+        return Err(err.into());
+    }
+    Ok(text.trim().parse::<u32>()?)
+}
+
+// The functions of the nested `tasks` and `tasks.queue` namespaces.
+mod tasks {
+    pub fn add(a: u32, b: u32) -> u32 {
+        a + b
+    }
+
+    pub mod queue {
+        pub fn describe(id: u32) -> String {
+            format!("task {}", id)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+pub enum ManyVariantsError {
+    #[error("VariantA")]
+    VariantA,
+    #[error("VariantB")]
+    VariantB,
+    #[error("VariantC")]
+    VariantC,
+    #[error("VariantD")]
+    VariantD,
+    #[error("VariantE")]
+    VariantE,
+    #[error("VariantF")]
+    VariantF,
+    #[error("VariantG")]
+    VariantG,
+    #[error("VariantH")]
+    VariantH,
+    #[error("VariantI")]
+    VariantI,
+    #[error("VariantJ")]
+    VariantJ,
+    #[error("VariantK")]
+    VariantK,
+    #[error("VariantL")]
+    VariantL,
+    #[error("VariantM")]
+    VariantM,
+    #[error("VariantN")]
+    VariantN,
+    #[error("VariantO")]
+    VariantO,
+    #[error("VariantP")]
+    VariantP,
+    #[error("VariantQ")]
+    VariantQ,
+    #[error("VariantR")]
+    VariantR,
+    #[error("VariantS")]
+    VariantS,
+    #[error("VariantT")]
+    VariantT,
+    #[error("VariantU")]
+    VariantU,
+    #[error("VariantV")]
+    VariantV,
+    #[error("VariantW")]
+    VariantW,
+    #[error("VariantX")]
+    VariantX,
+}
+
+const MANY_VARIANTS_ERRORS: [ManyVariantsError; 24] = [
+    ManyVariantsError::VariantA,
+    ManyVariantsError::VariantB,
+    ManyVariantsError::VariantC,
+    ManyVariantsError::VariantD,
+    ManyVariantsError::VariantE,
+    ManyVariantsError::VariantF,
+    ManyVariantsError::VariantG,
+    ManyVariantsError::VariantH,
+    ManyVariantsError::VariantI,
+    ManyVariantsError::VariantJ,
+    ManyVariantsError::VariantK,
+    ManyVariantsError::VariantL,
+    ManyVariantsError::VariantM,
+    ManyVariantsError::VariantN,
+    ManyVariantsError::VariantO,
+    ManyVariantsError::VariantP,
+    ManyVariantsError::VariantQ,
+    ManyVariantsError::VariantR,
+    ManyVariantsError::VariantS,
+    ManyVariantsError::VariantT,
+    ManyVariantsError::VariantU,
+    ManyVariantsError::VariantV,
+    ManyVariantsError::VariantW,
+    ManyVariantsError::VariantX,
+];
+
+fn throw_many_variants_error(index: u32) -> Result<(), ManyVariantsError> {
+    Err(MANY_VARIANTS_ERRORS[index as usize])
+}
+
+/// An enum with lots of variants, for benchmarking how quickly the bindings can lift them.
+#[derive(Debug, Clone)]
+pub enum ManyVariantsEnum {
+    VariantA { value: u32 },
+    VariantB { value: u32 },
+    VariantC { value: u32 },
+    VariantD { value: u32 },
+    VariantE { value: u32 },
+    VariantF { value: u32 },
+    VariantG { value: u32 },
+    VariantH { value: u32 },
+    VariantI { value: u32 },
+    VariantJ { value: u32 },
+    VariantK { value: u32 },
+    VariantL { value: u32 },
+    VariantM { value: u32 },
+    VariantN { value: u32 },
+    VariantO { value: u32 },
+    VariantP { value: u32 },
+    VariantQ { value: u32 },
+    VariantR { value: u32 },
+    VariantS { value: u32 },
+    VariantT { value: u32 },
+    VariantU { value: u32 },
+    VariantV { value: u32 },
+    VariantW { value: u32 },
+    VariantX { value: u32 },
+}
+
+const MANY_VARIANTS_ENUMS: [fn(u32) -> ManyVariantsEnum; 24] = [
+    |value| ManyVariantsEnum::VariantA { value },
+    |value| ManyVariantsEnum::VariantB { value },
+    |value| ManyVariantsEnum::VariantC { value },
+    |value| ManyVariantsEnum::VariantD { value },
+    |value| ManyVariantsEnum::VariantE { value },
+    |value| ManyVariantsEnum::VariantF { value },
+    |value| ManyVariantsEnum::VariantG { value },
+    |value| ManyVariantsEnum::VariantH { value },
+    |value| ManyVariantsEnum::VariantI { value },
+    |value| ManyVariantsEnum::VariantJ { value },
+    |value| ManyVariantsEnum::VariantK { value },
+    |value| ManyVariantsEnum::VariantL { value },
+    |value| ManyVariantsEnum::VariantM { value },
+    |value| ManyVariantsEnum::VariantN { value },
+    |value| ManyVariantsEnum::VariantO { value },
+    |value| ManyVariantsEnum::VariantP { value },
+    |value| ManyVariantsEnum::VariantQ { value },
+    |value| ManyVariantsEnum::VariantR { value },
+    |value| ManyVariantsEnum::VariantS { value },
+    |value| ManyVariantsEnum::VariantT { value },
+    |value| ManyVariantsEnum::VariantU { value },
+    |value| ManyVariantsEnum::VariantV { value },
+    |value| ManyVariantsEnum::VariantW { value },
+    |value| ManyVariantsEnum::VariantX { value },
+];
+
+fn get_many_variants_enum(index: u32) -> ManyVariantsEnum {
+    MANY_VARIANTS_ENUMS[index as usize](index)
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ComplexError {
     #[error("OsError: {code} ({extended_code})")]
@@ -118,10 +307,89 @@ fn create_none_dict() -> SimpleDict {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct Credentials {
+    user: String,
+    token: String,
+}
+
+fn sign_in(user: String, token: String) -> Credentials {
+    Credentials { user, token }
+}
+
+pub trait Shape: Send + Sync {
+    fn name(&self) -> String;
+    fn area(&self) -> f64;
+}
+
+struct Square {
+    side: f64,
+}
+
+impl Shape for Square {
+    fn name(&self) -> String {
+        "square".to_string()
+    }
+
+    fn area(&self) -> f64 {
+        self.side * self.side
+    }
+}
+
+struct Circle {
+    radius: f64,
+}
+
+impl Shape for Circle {
+    fn name(&self) -> String {
+        "circle".to_string()
+    }
+
+    fn area(&self) -> f64 {
+        std::f64::consts::PI * self.radius * self.radius
+    }
+}
+
+fn make_square(side: f64) -> Arc<dyn Shape> {
+    Arc::new(Square { side })
+}
+
+fn make_circle(radius: f64) -> Arc<dyn Shape> {
+    Arc::new(Circle { radius })
+}
+
 fn get_num_alive() -> u64 {
     *NUM_ALIVE.read().unwrap()
 }
 
+lazy_static::lazy_static! {
+    static ref MEMORY_PRESSURE_SEEN: Mutex<Vec<String>> = Mutex::new(Vec::new());
+}
+
+fn watch_memory_pressure() {
+    uniffi::on_memory_pressure(|pressure| {
+        let level = match pressure {
+            uniffi::MemoryPressure::Moderate => "moderate",
+            uniffi::MemoryPressure::Critical => "critical",
+        };
+        MEMORY_PRESSURE_SEEN.lock().unwrap().push(level.to_string());
+    });
+}
+
+fn get_memory_pressure_seen() -> Vec<String> {
+    MEMORY_PRESSURE_SEEN.lock().unwrap().clone()
+}
+
+static WARMED_UP: AtomicBool = AtomicBool::new(false);
+
+fn prepare_warmup() {
+    uniffi::on_warmup(|| WARMED_UP.store(true, Ordering::SeqCst));
+}
+
+fn is_warmed_up() -> bool {
+    WARMED_UP.load(Ordering::SeqCst)
+}
+
 type Result<T, E = CoverallError> = std::result::Result<T, E>;
 type ComplexResult<T, E = ComplexError> = std::result::Result<T, E>;
 
@@ -170,6 +438,16 @@ impl Coveralls {
         }
     }
 
+    fn maybe_throw_optional(&self, should_throw: bool, is_present: bool) -> Result<Option<String>> {
+        if should_throw {
+            Err(CoverallError::TooManyHoles)
+        } else if is_present {
+            Ok(Some(self.name.clone()))
+        } else {
+            Ok(None)
+        }
+    }
+
     fn maybe_throw_into(&self, should_throw: bool) -> Result<bool, InternalCoverallError> {
         if should_throw {
             Err(InternalCoverallError::ExcessiveHoles)
@@ -241,7 +519,7 @@ pub enum Color {
     Green,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Patch {
     color: Color,
 }
@@ -254,6 +532,187 @@ impl Patch {
     fn get_color(&self) -> Color {
         self.color
     }
+
+    fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+}
+
+struct Workshop;
+
+impl Workshop {
+    fn new() -> Self {
+        Self
+    }
+
+    fn make_patch(&self, color: Color) -> Arc<Patch> {
+        Arc::new(Patch::new(color))
+    }
+
+    fn color_of(&self, patch: Arc<Patch>) -> Color {
+        patch.get_color()
+    }
+
+    fn make_patches(&self, colors: Vec<Color>) -> Vec<Arc<Patch>> {
+        colors
+            .into_iter()
+            .map(|color| self.make_patch(color))
+            .collect()
+    }
+
+    fn repair(&self, color: Color, note: String) -> Repair {
+        Repair {
+            patch: self.make_patch(color),
+            note,
+        }
+    }
+
+    fn color_of_repair(&self, repair: Repair) -> Color {
+        repair.patch.get_color()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Repair {
+    patch: Arc<Patch>,
+    note: String,
+}
+
+#[derive(Debug, Default)]
+struct Journal {
+    entries: Mutex<Vec<String>>,
+}
+
+impl Journal {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&self, entry: String) {
+        self.entries.lock().unwrap().push(entry);
+    }
+
+    fn entry_count(&self) -> u32 {
+        self.entries.lock().unwrap().len() as u32
+    }
+
+    fn copy_from(&self, other: Arc<Journal>) {
+        let entries = other.entries.lock().unwrap().clone();
+        self.entries.lock().unwrap().extend(entries);
+    }
+}
+
+struct Ticket {
+    number: u32,
+}
+
+fn issue_ticket(number: u32) -> Arc<Ticket> {
+    Arc::new(Ticket { number })
+}
+
+fn same_ticket(ticket: Arc<Ticket>) -> Arc<Ticket> {
+    ticket
+}
+
+fn redeem_ticket(ticket: Arc<Ticket>) -> u32 {
+    ticket.number
+}
+
+struct Document {
+    text: String,
+}
+
+impl Document {
+    fn new(text: String) -> Self {
+        Self { text }
+    }
+
+    fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn bytes(&self) -> &[u8] {
+        self.text.as_bytes()
+    }
+}
+
+struct Fingerprint {
+    data: String,
+    digest_calls: AtomicU32,
+}
+
+impl Fingerprint {
+    fn new(data: String) -> Self {
+        Self {
+            data,
+            digest_calls: AtomicU32::new(0),
+        }
+    }
+
+    fn digest(&self) -> String {
+        self.digest_calls.fetch_add(1, Ordering::SeqCst);
+        self.data.bytes().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    fn digest_calls(&self) -> u32 {
+        self.digest_calls.load(Ordering::SeqCst)
+    }
+}
+
+struct Frame {
+    size: u32,
+}
+
+impl Frame {
+    fn new(size: u32) -> Self {
+        Self { size }
+    }
+
+    fn pixels(&self) -> Vec<u8> {
+        (0..self.size).map(|i| i as u8).collect()
+    }
+}
+
+struct Thumbnail {
+    width: u32,
+}
+
+impl Thumbnail {
+    fn new(width: u32) -> Self {
+        Self { width }
+    }
+
+    fn width(&self) -> u32 {
+        self.width
+    }
+}
+
+struct Ticker {
+    ticks: Arc<AtomicU32>,
+}
+
+impl Ticker {
+    fn new() -> Self {
+        Self {
+            ticks: Arc::new(AtomicU32::new(0)),
+        }
+    }
+
+    // Tick on a thread of the scope's until it's cancelled.
+    fn start(&self, scope: Arc<uniffi::Scope>) {
+        let ticks = Arc::clone(&self.ticks);
+        let worker_scope = Arc::clone(&scope);
+        scope.spawn(move || {
+            while !worker_scope.is_cancelled() {
+                ticks.fetch_add(1, Ordering::SeqCst);
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+        });
+    }
+
+    fn ticks(&self) -> u32 {
+        self.ticks.load(Ordering::SeqCst)
+    }
 }
 
 // This is a small implementation of a counter that allows waiting on one thread,
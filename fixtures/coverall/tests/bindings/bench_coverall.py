@@ -0,0 +1,38 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+# A microbenchmark for lifting errors and enums with many variants, which the generated
+# code looks up by index rather than testing for each variant in turn. It reports the
+# average time per call, and checks that each call lifted the right variant.
+
+import time
+from coverall import *
+
+ITERATIONS = 2000
+VARIANTS = 24
+
+def bench(name, call):
+    start = time.perf_counter()
+    for _ in range(ITERATIONS):
+        for index in range(VARIANTS):
+            call(index)
+    elapsed = time.perf_counter() - start
+    print("{}: {:.2f}us per call".format(name, elapsed * 1e6 / (ITERATIONS * VARIANTS)))
+
+def lift_error(index):
+    try:
+        throw_many_variants_error(index)
+    except Exception as err:
+        assert type(err).__name__ == "Variant" + chr(ord("A") + index)
+    else:
+        raise AssertionError("should have thrown")
+
+def lift_enum(index):
+    value = get_many_variants_enum(index)
+    assert value.value == index
+    if index == VARIANTS - 1:
+        assert value.is_variant_x()
+
+bench("ManyVariantsError", lift_error)
+bench("ManyVariantsEnum", lift_enum)
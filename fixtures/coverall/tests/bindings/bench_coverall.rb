@@ -0,0 +1,37 @@
+# frozen_string_literal: true
+
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+# A microbenchmark for lifting errors and enums with many variants, which the generated
+# code dispatches on by index rather than testing for each variant in turn. It reports the
+# average time per call, and checks that each call lifted the right variant.
+
+require 'benchmark'
+require 'coverall'
+
+ITERATIONS = 2000
+VARIANTS = 24
+
+def bench(name)
+  elapsed = Benchmark.realtime do
+    ITERATIONS.times do
+      VARIANTS.times { |index| yield index }
+    end
+  end
+  puts format('%<name>s: %<us>.2fus per call', name: name, us: elapsed * 1e6 / (ITERATIONS * VARIANTS))
+end
+
+bench('ManyVariantsError') do |index|
+  Coverall.throw_many_variants_error(index)
+  raise 'should have thrown'
+rescue StandardError => e
+  raise unless e.class.name == "Coverall::ManyVariantsError::Variant#{(65 + index).chr}"
+end
+
+bench('ManyVariantsEnum') do |index|
+  value = Coverall.get_many_variants_enum(index)
+  raise 'wrong value' unless value.value == index
+  raise 'wrong variant' if index == VARIANTS - 1 && !value.variant_x?
+end
@@ -39,6 +39,22 @@ createSomeDict().use { d ->
 }
 
 
+// `[Sensitive]` fields are left out of `toString()`.
+signIn("ada", "hunter2").let { credentials ->
+    assert(credentials.token == "hunter2")
+    assert(credentials.toString() == "Credentials(user=ada, token=***)")
+}
+
+// Different Rust types can be behind the same `[Trait]` interface.
+makeSquare(2.0).use { square ->
+    assert(square.name() == "square")
+    assert(Math.abs(square.area() - 4.0) < 0.000001)
+}
+makeCircle(1.0).use { circle ->
+    assert(circle.name() == "circle")
+    assert(Math.abs(circle.area() - Math.PI) < 0.000001)
+}
+
 // Test arcs.
 
 Coveralls("test_arcs").use { coveralls ->
@@ -119,6 +135,16 @@ Coveralls("test_simple_errors").use { coveralls ->
         // Expected result
     }
 
+    // A `null` return value is distinct from an error.
+    assert(coveralls.maybeThrowOptional(false, true) == "test_simple_errors")
+    assert(coveralls.maybeThrowOptional(false, false) == null)
+    try {
+        coveralls.maybeThrowOptional(true, false)
+        throw RuntimeException("Expected method to throw exception")
+    } catch(e: CoverallException.TooManyHoles) {
+        // Expected result
+    }
+
     try {
         coveralls.panic("oops")
         throw RuntimeException("Expected method to throw exception")
@@ -154,6 +180,184 @@ Coveralls("test_complex_errors").use { coveralls ->
     }
 }
 
+// Functions in nested namespaces are members of an object for each namespace.
+assert(Tasks.add(1u, 2u) == 3u)
+assert(Tasks.Queue.describe(7u) == "task 7")
+
+// `[SourceChain]` errors have the Rust error's sources as their cause.
+try {
+    throwChainedError("file not found")
+    throw RuntimeException("Expected method to throw exception")
+} catch(e: ChainedException.LoadFailed) {
+    assert(e.message == "Failed to load the coverall")
+    assert(e.cause is UniffiErrorSource)
+    assert(e.cause?.message == "The coverall config is invalid")
+    assert(e.cause?.cause?.message == "file not found")
+    assert(e.cause?.cause?.cause == null)
+}
+
+// `[Clone]` objects get a new, independent Rust struct.
+Patch(Color.GREEN).use { patch ->
+    patch.clone().use { patch2 ->
+        assert(patch2 !== patch)
+        assert(patch2.getColor() == Color.GREEN)
+        patch2.setColor(Color.BLUE)
+        assert(patch2.getColor() == Color.BLUE)
+        assert(patch.getColor() == Color.GREEN)
+    }
+}
+
+// Objects can make and take objects of other types.
+Workshop().use { workshop ->
+    workshop.makePatch(Color.BLUE).use { patch ->
+        assert(patch.getColor() == Color.BLUE)
+        assert(workshop.colorOf(patch) == Color.BLUE)
+    }
+    val patches = workshop.makePatches(listOf(Color.RED, Color.GREEN))
+    assert(patches.map { it.getColor() } == listOf(Color.RED, Color.GREEN))
+    patches.forEach { it.destroy() }
+    // Destroying the record destroys the object in it.
+    workshop.repair(Color.GREEN, "elbow").use { repair ->
+        assert(repair.note == "elbow")
+        assert(repair.patch.getColor() == Color.GREEN)
+        assert(workshop.colorOfRepair(repair) == Color.GREEN)
+    }
+}
+
+// `[Closeable]` objects refuse to be used once closed.
+Journal().let { journal ->
+    journal.use {
+        it.record("first")
+        assert(it.entryCount() == 1u)
+    }
+    try {
+        journal.record("second")
+        throw RuntimeException("Should have thrown an IllegalStateException!")
+    } catch (e: IllegalStateException) {
+        assert(e.message == "Journal object has already been closed")
+    }
+    // Closing again is harmless.
+    journal.close()
+}
+
+// `[OpaqueToken]` handles are equal when they refer to the same Rust object.
+issueTicket(7u).use { ticket ->
+    sameTicket(ticket).use { same ->
+        assert(same == ticket)
+        assert(same.hashCode() == ticket.hashCode())
+    }
+    issueTicket(7u).use { other ->
+        assert(other != ticket)
+    }
+    assert(redeemTicket(ticket) == 7u)
+}
+
+// `[ErrorFrom]` converts Rust errors into the variants of `ParseError`.
+assert(parseCount(" 42 ") == 42u)
+try {
+    parseCount("forty-two")
+    throw RuntimeException("Expected parseCount to throw")
+} catch (e: ParseException.InvalidNumber) {
+    assert(e.reason == "invalid digit found in string")
+}
+try {
+    parseCount("")
+    throw RuntimeException("Expected parseCount to throw")
+} catch (e: ParseException.Other) {
+    assert(e.reason == "nothing to parse")
+}
+
+// `[Borrowed]` methods return views, which keep the document alive while they're in use.
+Document("héllo world").text().use { text ->
+    assert(text.byteLength() == 12UL)
+    assert(text.substring(7UL, 12UL) == "world")
+    assert(text.contents() == "héllo world")
+    try {
+        text.substring(0UL, 2UL)
+        throw RuntimeException("Should have thrown on a range that splits a character")
+    } catch (e: InternalException) {
+        // It's okay!
+    }
+}
+Document("abc").bytes().use { data ->
+    assert(data.slice(1UL, 3UL) == listOf<UByte>(98u, 99u))
+}
+
+// `[Cached]` methods only call into Rust the first time.
+Fingerprint("ab").let { fingerprint ->
+    assert(fingerprint.digest() == "6162")
+    assert(fingerprint.digest() == "6162")
+    assert(fingerprint.digestCalls() == 1U)
+}
+
+// `[WeakRefs]` objects can be held without keeping them alive.
+Thumbnail(64U).let { thumbnail ->
+    val weak = thumbnail.downgrade()
+    weak.upgrade()!!.use { assert(it.width() == 64U) }
+    thumbnail.destroy()
+    assert(weak.upgrade() == null)
+    weak.destroy()
+}
+
+// `[Scoped]` methods register their work against the scope, which stops it all at once.
+Scope().use { scope ->
+    Ticker().use { ticker ->
+        ticker.start(scope)
+        while (ticker.ticks() == 0U) {
+            Thread.sleep(1)
+        }
+        scope.cancel()
+        scope.join()
+        assert(scope.isCancelled())
+        val ticks = ticker.ticks()
+        // Nothing more starts on a cancelled scope.
+        ticker.start(scope)
+        Thread.sleep(10)
+        assert(ticker.ticks() == ticks)
+    }
+}
+
+// `[NoCopy]` methods return a view of the bytes, which must be freed.
+Frame(1000U).pixels().let { pixels ->
+    try {
+        assert(pixels.size == 1000)
+        assert(pixels.useByteBuffer { it.get(257) } == 1.toByte())
+        assert(pixels.toByteArray().size == 1000)
+    } finally {
+        pixels.free()
+    }
+    // Freeing them again does nothing, but they can't be read any more.
+    pixels.close()
+    try {
+        pixels.toByteArray()
+        throw RuntimeException("Should have thrown reading bytes that were freed")
+    } catch (e: IllegalStateException) {
+        // It's okay!
+    }
+    try {
+        pixels.useByteBuffer { it.get(0) }
+        throw RuntimeException("Should have thrown viewing bytes that were freed")
+    } catch (e: IllegalStateException) {
+        // It's okay!
+    }
+}
+
+// Bytes that are never freed are freed by the cleaner once they're unreachable.
+repeat(100) { Frame(1000U).pixels() }
+System.gc()
+
+// Every exported function and method is listed, keyed by its qualified name.
+CoverallExport.fromQualifiedName("Coveralls.get_name").let { export ->
+    assert(export == CoverallExport.COVERALLS_GET_NAME)
+    assert(export?.kind == "method")
+    assert(export?.signatureHash?.length == 16)
+}
+assert(CoverallExport.fromQualifiedName("Coveralls.no_such_method") == null)
+
+// Objects say which threads they can be used from.
+assert(Coveralls.UNIFFI_THREADING == "threadsafe")
+assert(ThreadsafeCounter.UNIFFI_THREADING == "blocking")
+
 // This tests that the UniFFI-generated scaffolding doesn't introduce any unexpected locking.
 // We have one thread busy-wait for a some period of time, while a second thread repeatedly
 // increments the counter and then checks if the object is still busy. The second thread should
@@ -186,3 +390,19 @@ ThreadsafeCounter().use { counter ->
         executor.shutdown()
     }
 }
+
+uniffiComponentInfo().let { info ->
+    assert(info.componentName == "coverall")
+    assert(info.checksum == info.bindingsChecksum)
+}
+
+// Memory pressure from `onTrimMemory` reaches the hooks registered in Rust.
+watchMemoryPressure()
+uniffiOnMemoryPressure(5) // TRIM_MEMORY_RUNNING_MODERATE
+uniffiOnMemoryPressure(80) // TRIM_MEMORY_COMPLETE
+assert(getMemoryPressureSeen() == listOf("moderate", "critical"))
+
+// Warming the library up runs the hooks registered in Rust, on a thread of its own.
+prepareWarmup()
+uniffiWarmup().join()
+assert(isWarmedUp())
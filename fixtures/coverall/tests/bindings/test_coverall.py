@@ -2,6 +2,11 @@
 # License, v. 2.0. If a copy of the MPL was not distributed with this
 # file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+import copy
+import gc
+import math
+import time
+import coverall
 import unittest
 from coverall import *
 
@@ -59,6 +64,23 @@ class TestCoverall(unittest.TestCase):
         self.assertIsNone(d.maybe_float64)
         self.assertIsNone(d.coveralls)
 
+    def test_sensitive_fields(self):
+        credentials = sign_in("ada", "hunter2")
+        self.assertEqual(credentials.token, "hunter2")
+        self.assertEqual(str(credentials), "Credentials(user=ada, token=***)")
+
+    def test_threading_metadata(self):
+        self.assertEqual(Coveralls.UNIFFI_THREADING, "threadsafe")
+        self.assertEqual(ThreadsafeCounter.UNIFFI_THREADING, "blocking")
+
+    def test_trait_interfaces(self):
+        square = make_square(2.0)
+        self.assertEqual(square.name(), "square")
+        self.assertAlmostEqual(square.area(), 4.0)
+        circle = make_circle(1.0)
+        self.assertEqual(circle.name(), "circle")
+        self.assertAlmostEqual(circle.area(), math.pi)
+
     def test_constructors(self):
         self.assertEqual(get_num_alive(), 0)
         # must work.
@@ -98,6 +120,12 @@ class TestCoverall(unittest.TestCase):
         with self.assertRaisesRegex(InternalError, "expected panic: oh no"):
             coveralls.panic("expected panic: oh no")
 
+        # A `None` return value is distinct from an error.
+        self.assertEqual(coveralls.maybe_throw_optional(False, True), "test_simple_errors")
+        self.assertIsNone(coveralls.maybe_throw_optional(False, False))
+        with self.assertRaises(CoverallError.TooManyHoles):
+            coveralls.maybe_throw_optional(True, False)
+
     def test_complex_errors(self):
         coveralls = Coveralls("test_complex_errors")
 
@@ -120,6 +148,38 @@ class TestCoverall(unittest.TestCase):
         with self.assertRaises(InternalError) as cm:
             coveralls.maybe_throw_complex(3)
 
+    def test_argument_checks(self):
+        # Arguments that Rust can't take raise a `ValueError` that names them, before they're lowered.
+        coveralls = Coveralls("test_argument_checks")
+        with self.assertRaisesRegex(ValueError, "Invalid arg 'input': 128 is out of range"):
+            coveralls.maybe_throw_complex(128)
+        with self.assertRaisesRegex(ValueError, "Invalid arg 'should_throw': expected a value, got None"):
+            coveralls.maybe_throw(None)
+        with self.assertRaisesRegex(ValueError, "Invalid arg 'text': expected a string, got 42"):
+            parse_count(42)
+        with self.assertRaisesRegex(ValueError, "Invalid arg 'color': expected a Color, got 'red'"):
+            Patch("red")
+        with self.assertRaisesRegex(ValueError, "Invalid arg 'a': -1 is out of range"):
+            tasks.add(-1, 2)
+        # Optional arguments can still be None.
+        coveralls.take_other(None)
+
+    def test_nested_namespaces(self):
+        self.assertEqual(tasks.add(1, 2), 3)
+        self.assertEqual(tasks.queue.describe(7), "task 7")
+        from coverall.tasks.queue import describe
+        self.assertEqual(describe(8), "task 8")
+
+    def test_error_source_chain(self):
+        with self.assertRaises(ChainedError.LoadFailed) as cm:
+            throw_chained_error("file not found")
+        self.assertEqual(str(cm.exception), "Failed to load the coverall")
+        cause = cm.exception.__cause__
+        self.assertIsInstance(cause, UniffiErrorSource)
+        self.assertEqual(str(cause), "The coverall config is invalid")
+        self.assertEqual(str(cause.__cause__), "file not found")
+        self.assertIsNone(cause.__cause__.__cause__)
+
     def test_self_by_arc(self):
         coveralls = Coveralls("test_self_by_arc")
         # One reference is held by the handlemap, and one by the `Arc<Self>` method receiver.
@@ -180,5 +240,190 @@ class TestCoverall(unittest.TestCase):
         with self.assertRaisesRegex(TypeError, "Coveralls.*Patch"):
             coveralls.take_other(patch)
 
+    def test_clone_objects(self):
+        patch = Patch(Color.GREEN)
+        patch2 = copy.copy(patch)
+        self.assertIsNot(patch2, patch)
+        self.assertEqual(patch2.get_color(), Color.GREEN)
+        patch3 = copy.deepcopy(patch)
+        self.assertEqual(patch3.get_color(), Color.GREEN)
+        patch2.set_color(Color.BLUE)
+        self.assertEqual(patch2.get_color(), Color.BLUE)
+        self.assertEqual(patch.get_color(), Color.GREEN)
+
+    def test_objects_of_other_types(self):
+        workshop = Workshop()
+        patch = workshop.make_patch(Color.BLUE)
+        self.assertEqual(patch.get_color(), Color.BLUE)
+        self.assertEqual(workshop.color_of(patch), Color.BLUE)
+        patches = workshop.make_patches([Color.RED, Color.GREEN])
+        self.assertEqual([p.get_color() for p in patches], [Color.RED, Color.GREEN])
+        with self.assertRaisesRegex(TypeError, "Patch.*Workshop"):
+            workshop.color_of(workshop)
+        repair = workshop.repair(Color.GREEN, "elbow")
+        self.assertEqual(repair.note, "elbow")
+        self.assertEqual(repair.patch.get_color(), Color.GREEN)
+        self.assertEqual(workshop.color_of_repair(repair), Color.GREEN)
+        # Objects in records are checked before they're written, too.
+        with self.assertRaisesRegex(TypeError, "Patch.*Workshop"):
+            workshop.color_of_repair(Repair(workshop, "elbow"))
+
+    def test_closeable_objects(self):
+        with Journal() as journal:
+            journal.record("first")
+            self.assertEqual(journal.entry_count(), 1)
+        with self.assertRaisesRegex(ValueError, "Journal object has already been closed"):
+            journal.record("second")
+        # Closing again is harmless.
+        journal.close()
+
+    def test_closeable_arguments(self):
+        source = Journal()
+        source.record("entry")
+        with Journal() as journal:
+            journal.copy_from(source)
+            self.assertEqual(journal.entry_count(), 1)
+            # The argument is only claimed for the duration of the call, so it can be closed
+            # afterwards, and then can't be passed again.
+            source.close()
+            with self.assertRaisesRegex(ValueError, "Journal object has already been closed"):
+                journal.copy_from(source)
+            self.assertEqual(journal.entry_count(), 1)
+
+    def test_opaque_tokens(self):
+        ticket = issue_ticket(7)
+        same = same_ticket(ticket)
+        self.assertIsNot(same, ticket)
+        self.assertEqual(same, ticket)
+        self.assertEqual(len({ticket, same}), 1)
+        self.assertNotEqual(issue_ticket(7), ticket)
+        self.assertEqual(redeem_ticket(ticket), 7)
+
+    def test_error_from(self):
+        self.assertEqual(parse_count(" 42 "), 42)
+        with self.assertRaises(ParseError.InvalidNumber) as cm:
+            parse_count("forty-two")
+        self.assertEqual(cm.exception.reason, "invalid digit found in string")
+        with self.assertRaises(ParseError.Other) as cm:
+            parse_count("")
+        self.assertEqual(cm.exception.reason, "nothing to parse")
+
+    def test_invalid_strings(self):
+        # Python strings always encode to valid UTF-8, so we swap in a lowering that sends Rust
+        # bytes that aren't, as a corrupted buffer might.
+        def lower_invalid(value):
+            with coverall.RustBuffer.allocWithBuilder() as builder:
+                builder.write(b"4\xff2")
+                return builder.finalize()
+
+        lower = coverall.FfiConverterString._lower
+        coverall.FfiConverterString._lower = staticmethod(lower_invalid)
+        try:
+            with self.assertRaises(InvalidStringError):
+                Coveralls("name")
+            # Even for functions that throw errors of their own.
+            with self.assertRaisesRegex(InvalidStringError, "Failed to convert arg 'text'"):
+                parse_count("42")
+        finally:
+            coverall.FfiConverterString._lower = staticmethod(lower)
+
+    def test_borrowed_methods(self):
+        text = Document("héllo world").text()
+        self.assertEqual(text.byte_length(), 12)
+        self.assertEqual(text.substring(7, 12), "world")
+        self.assertEqual(text.contents(), "héllo world")
+        with self.assertRaises(InternalError):
+            text.substring(0, 2)
+        data = Document("abc").bytes()
+        self.assertEqual(data.slice(1, 3), [98, 99])
+        self.assertEqual(data.contents(), [97, 98, 99])
+
+    def test_cached_methods(self):
+        fingerprint = Fingerprint("ab")
+        self.assertEqual(fingerprint.digest(), "6162")
+        self.assertEqual(fingerprint.digest(), "6162")
+        self.assertEqual(fingerprint.digest_calls(), 1)
+        # Each object keeps its own value.
+        self.assertEqual(Fingerprint("c").digest(), "63")
+
+    def test_weak_refs(self):
+        thumbnail = Thumbnail(64)
+        weak = thumbnail.downgrade()
+        self.assertEqual(weak.upgrade().width(), 64)
+        # The weak reference doesn't keep the Rust object alive.
+        del thumbnail
+        self.assertIsNone(weak.upgrade())
+
+    def test_scoped_methods(self):
+        scope = Scope()
+        ticker = Ticker()
+        ticker.start(scope)
+        while ticker.ticks() == 0:
+            time.sleep(0.001)
+        scope.cancel()
+        scope.join()
+        self.assertTrue(scope.is_cancelled())
+        ticks = ticker.ticks()
+        # Nothing more starts on a cancelled scope.
+        ticker.start(scope)
+        time.sleep(0.01)
+        self.assertEqual(ticker.ticks(), ticks)
+
+    def test_no_copy_methods(self):
+        with Frame(1000).pixels() as pixels:
+            self.assertEqual(len(pixels), 1000)
+            self.assertEqual(pixels.memoryview()[257], 1)
+            self.assertEqual(pixels.tobytes()[:3], b"\x00\x01\x02")
+        with self.assertRaises(RuntimeError):
+            pixels.tobytes()
+        # Freeing them again does nothing.
+        pixels.free()
+        self.assertEqual(len(Frame(0).pixels().tobytes()), 0)
+        # A view keeps the bytes alive after the `OwnedBytes` that it came from is dropped.
+        view = Frame(1000).pixels().memoryview()
+        gc.collect()
+        self.assertEqual(view[0], 0)
+        self.assertEqual(view[257], 1)
+
+    def test_exports(self):
+        export = CoverallExport.from_qualified_name("Coveralls.get_name")
+        self.assertIs(export, CoverallExport.COVERALLS_GET_NAME)
+        self.assertEqual(export.kind, "method")
+        self.assertEqual(len(export.signature_hash), 16)
+        self.assertEqual(CoverallExport.CREATE_SOME_DICT.kind, "function")
+        self.assertIsNone(CoverallExport.from_qualified_name("Coveralls.no_such_method"))
+
+    def test_component_info(self):
+        info = coverall.uniffi_component_info()
+        self.assertEqual(info.component_name, "coverall")
+        self.assertEqual(info.checksum, info.bindings_checksum)
+        self.assertIn(info.build_profile, ["debug", "release"])
+        # The library was checked against these when it was loaded.
+        self.assertEqual(coverall.UNIFFI_COMPONENT_CHECKSUM, info.checksum)
+        self.assertEqual(coverall.UNIFFI_BINDGEN_VERSION, info.uniffi_version)
+
+    def test_warmup(self):
+        prepare_warmup()
+        coverall.uniffi_warmup().join()
+        self.assertTrue(is_warmed_up())
+
+    def test_reset_library(self):
+        coveralls = Coveralls("before")
+        journal = Journal()
+        self.assertGreaterEqual(get_num_alive(), 1)
+        coverall.reset_library()
+        # Every object was freed, including the ones that are still referenced here.
+        self.assertEqual(get_num_alive(), 0)
+        with self.assertRaisesRegex(ValueError, "Coveralls object was freed by reset_library"):
+            coveralls.get_name()
+        with self.assertRaisesRegex(ValueError, "Journal object has already been closed"):
+            journal.record("after")
+        # Objects created after the reset work as usual.
+        coveralls = Coveralls("after")
+        self.assertEqual(coveralls.get_name(), "after")
+        self.assertEqual(get_num_alive(), 1)
+        coveralls = None
+        self.assertEqual(get_num_alive(), 0)
+
 if __name__=='__main__':
     unittest.main()
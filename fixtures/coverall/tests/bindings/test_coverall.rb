@@ -35,6 +35,27 @@ class TestCoverall < Test::Unit::TestCase
     assert_equal(d.coveralls.get_name(), "some_dict")
   end
 
+  def test_sensitive_fields
+    credentials = Coverall.sign_in 'ada', 'hunter2'
+    assert_equal(credentials.token, 'hunter2')
+    assert_equal(credentials.inspect, '#<Coverall::Credentials user="ada", token=***>')
+    assert_not_match(/hunter2/, credentials.to_s)
+  end
+
+  def test_threading_metadata
+    assert_equal(Coverall::Coveralls::UNIFFI_THREADING, 'threadsafe')
+    assert_equal(Coverall::ThreadsafeCounter::UNIFFI_THREADING, 'blocking')
+  end
+
+  def test_trait_interfaces
+    square = Coverall.make_square 2.0
+    assert_equal(square.name, 'square')
+    assert_in_delta(square.area, 4.0)
+    circle = Coverall.make_circle 1.0
+    assert_equal(circle.name, 'circle')
+    assert_in_delta(circle.area, Math::PI)
+  end
+
   def test_none_dict
     d = Coverall.create_none_dict
     assert_equal(d.text, 'text')
@@ -107,6 +128,13 @@ class TestCoverall < Test::Unit::TestCase
       coveralls.maybe_throw_into true
     end
 
+    # A `nil` return value is distinct from an error.
+    assert_equal coveralls.maybe_throw_optional(false, true), 'test_simple_errors'
+    assert_nil coveralls.maybe_throw_optional(false, false)
+    assert_raise Coverall::CoverallError::TooManyHoles do
+      coveralls.maybe_throw_optional true, false
+    end
+
     err = assert_raise Coverall::InternalError do
       coveralls.panic 'expected panic: oh no'
     end
@@ -143,6 +171,21 @@ class TestCoverall < Test::Unit::TestCase
     end
   end
 
+  def test_nested_namespaces
+    assert_equal Coverall::Tasks.add(1, 2), 3
+    assert_equal Coverall::Tasks::Queue.describe(7), 'task 7'
+  end
+
+  def test_error_source_chain
+    err = assert_raise Coverall::ChainedError::LoadFailed do
+      Coverall.throw_chained_error 'file not found'
+    end
+    assert_equal err.message, 'Failed to load the coverall'
+    assert_equal err.cause.message, 'The coverall config is invalid'
+    assert_equal err.cause.cause.message, 'file not found'
+    assert_nil err.cause.cause.cause
+  end
+
   def test_self_by_arc
     coveralls = Coverall::Coveralls.new 'test_self_by_arc'
 
@@ -232,5 +275,117 @@ class TestCoverall < Test::Unit::TestCase
     end
   end
 
+  def test_clone_objects
+    patch = Coverall::Patch.new Coverall::Color::GREEN
+    patch2 = patch.dup
+    assert_not_same patch2, patch
+    assert_equal patch2.get_color, Coverall::Color::GREEN
+    patch3 = patch.clone
+    assert_equal patch3.get_color, Coverall::Color::GREEN
+    patch2.set_color Coverall::Color::BLUE
+    assert_equal patch2.get_color, Coverall::Color::BLUE
+    assert_equal patch.get_color, Coverall::Color::GREEN
+    # Each copy frees only its own Rust struct when it's collected, so this doesn't crash.
+    patch = patch2 = patch3 = nil
+    GC.start
+    assert_equal Coverall::Patch.new(Coverall::Color::RED).dup.get_color, Coverall::Color::RED
+  end
+
+  def test_objects_of_other_types
+    workshop = Coverall::Workshop.new
+    patch = workshop.make_patch Coverall::Color::BLUE
+    assert_equal patch.get_color, Coverall::Color::BLUE
+    assert_equal workshop.color_of(patch), Coverall::Color::BLUE
+    patches = workshop.make_patches [Coverall::Color::RED, Coverall::Color::GREEN]
+    assert_equal patches.map(&:get_color), [Coverall::Color::RED, Coverall::Color::GREEN]
+    repair = workshop.repair Coverall::Color::GREEN, 'elbow'
+    assert_equal repair.note, 'elbow'
+    assert_equal repair.patch.get_color, Coverall::Color::GREEN
+    assert_equal workshop.color_of_repair(repair), Coverall::Color::GREEN
+  end
+
+  def test_closeable_objects
+    journal = Coverall::Journal.new
+    journal.record "first"
+    assert_equal journal.entry_count, 1
+    journal.close
+    assert_raise_message /Journal object has already been closed/ do
+      journal.record "second"
+    end
+    # Closing again is harmless.
+    journal.close
+  end
+
+  def test_opaque_tokens
+    ticket = Coverall.issue_ticket 7
+    same = Coverall.same_ticket ticket
+    assert_not_same same, ticket
+    assert_equal same, ticket
+    assert_equal [ticket, same].uniq.length, 1
+    assert_not_equal Coverall.issue_ticket(7), ticket
+    assert_equal Coverall.redeem_ticket(ticket), 7
+  end
+
+  def test_reset_library
+    coveralls = Coverall::Coveralls.new 'before'
+    journal = Coverall::Journal.new
+    assert_operator Coverall.get_num_alive, :>=, 1
+    Coverall.reset_library
+    # Every object was freed, including the ones that are still referenced here.
+    assert_equal Coverall.get_num_alive, 0
+    assert_raise_message(/Coveralls object was freed by reset_library/) do
+      coveralls.get_name
+    end
+    assert_raise_message(/Journal object has already been closed/) do
+      journal.record 'after'
+    end
+    # Objects created after the reset work as usual.
+    coveralls = Coverall::Coveralls.new 'after'
+    assert_equal coveralls.get_name, 'after'
+  end
+
+  def test_invalid_strings
+    # Ruby strings can hold bytes that aren't valid UTF-8, which Rust refuses to lift.
+    invalid = "4\xFF2"
+    assert_false invalid.valid_encoding?
+    assert_raise_kind_of Coverall::InvalidStringError do
+      Coverall::Coveralls.new invalid
+    end
+    # Even for functions that throw errors of their own.
+    assert_raise_message(/Failed to convert arg 'text': invalid utf8 in string, after 1 valid bytes/) do
+      Coverall.parse_count invalid
+    end
+    assert_equal Coverall.get_num_alive, 0
+  end
+
+  def test_weak_refs
+    thumbnail = Coverall::Thumbnail.new(64)
+    weak = thumbnail.downgrade
+    assert_equal weak.upgrade.width, 64
+  end
+
+  def test_scoped_methods
+    scope = Coverall::Scope.new
+    ticker = Coverall::Ticker.new
+    ticker.start(scope)
+    sleep 0.001 while ticker.ticks.zero?
+    scope.cancel
+    scope.join
+    assert scope.is_cancelled
+    ticks = ticker.ticks
+    # Nothing more starts on a cancelled scope.
+    ticker.start(scope)
+    sleep 0.01
+    assert_equal ticker.ticks, ticks
+  end
+
+  def test_no_copy_methods
+    # Ruby copies the bytes out once, and frees them straight away.
+    pixels = Coverall::Frame.new(1000).pixels
+    assert_equal pixels.length, 1000
+    assert_equal pixels[257], 1
+    assert_equal Coverall::Frame.new(0).pixels, []
+  end
+
 
 end
@@ -2,6 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+import Foundation
 import coverall
 
 // TODO: use an actual test runner.
@@ -44,6 +45,28 @@ do {
     assert(d.coveralls!.getName() == "some_dict")
 }
 
+// `[Sensitive]` fields are left out when the record is printed.
+do {
+    let credentials = signIn(user: "ada", token: "hunter2")
+    assert(credentials.token == "hunter2")
+    assert(String(describing: credentials) == "Credentials(user: \"ada\", token: ***)")
+    assert(!String(reflecting: credentials).contains("hunter2"))
+}
+
+// Objects say which threads they can be used from.
+assert(Coveralls.uniffiThreading == "threadsafe")
+assert(ThreadsafeCounter.uniffiThreading == "blocking")
+
+// Different Rust types can be behind the same `[Trait]` interface.
+do {
+    let square = makeSquare(side: 2.0)
+    assert(square.name() == "square")
+    assert(square.area().almostEquals(4.0))
+    let circle = makeCircle(radius: 1.0)
+    assert(circle.name() == "circle")
+    assert(circle.area().almostEquals(Double.pi))
+}
+
 // Test arcs.
 do {
     let coveralls = Coveralls(name: "test_arcs")
@@ -96,6 +119,16 @@ do {
         // It's okay!
     }
 
+    // A `nil` return value is distinct from an error.
+    assert(try! coveralls.maybeThrowOptional(shouldThrow: false, isPresent: true) == "test_simple_errors")
+    assert(try! coveralls.maybeThrowOptional(shouldThrow: false, isPresent: false) == nil)
+    do {
+        let _ = try coveralls.maybeThrowOptional(shouldThrow: true, isPresent: false)
+        fatalError("Should have thrown")
+    } catch CoverallError.TooManyHoles {
+        // It's okay!
+    }
+
     // Note: Can't test coveralls.panic() because rust panics trigger a fatal error in swift
 }
 
@@ -132,6 +165,19 @@ do {
 // Swift GC is deterministic, `coveralls` is freed when it goes out of scope.
 assert(getNumAlive() == 0);
 
+// Functions in nested namespaces are static members of an enum for each namespace.
+assert(Tasks.add(a: 1, b: 2) == 3)
+assert(Tasks.Queue.describe(id: 7) == "task 7")
+
+// `[SourceChain]` errors carry the messages of the Rust error's sources.
+do {
+    try throwChainedError(reason: "file not found")
+    fatalError("should have thrown")
+} catch ChainedError.LoadFailed(let message, let sourceChain) {
+    assert(message == "Failed to load the coverall")
+    assert(sourceChain == ["The coverall config is invalid", "file not found"])
+}
+
 // Test return objects
 do {
     let coveralls = Coveralls(name: "test_return_objects")
@@ -155,3 +201,143 @@ do {
 }
 // Dropping `coveralls` will kill both.
 assert(getNumAlive() == 0)
+
+// `[Clone]` objects get a new, independent Rust struct.
+do {
+    let patch = Patch(color: .green)
+    let patch2 = patch.copy()
+    assert(patch2 !== patch)
+    assert(patch2.getColor() == .green)
+    patch2.setColor(color: .blue)
+    assert(patch2.getColor() == .blue)
+    assert(patch.getColor() == .green)
+}
+
+// Objects can make and take objects of other types.
+do {
+    let workshop = Workshop()
+    let patch = workshop.makePatch(color: .blue)
+    assert(patch.getColor() == .blue)
+    assert(workshop.colorOf(patch: patch) == .blue)
+    let patches = workshop.makePatches(colors: [.red, .green])
+    assert(patches.map { $0.getColor() } == [.red, .green])
+    let repair = workshop.repair(color: .green, note: "elbow")
+    assert(repair.note == "elbow")
+    assert(repair.patch.getColor() == .green)
+    assert(workshop.colorOfRepair(repair: repair) == .green)
+}
+
+// `[Closeable]` objects can be closed before they are deinitialized.
+do {
+    let journal = Journal()
+    journal.record(entry: "first")
+    assert(journal.entryCount() == 1)
+    journal.close()
+    // Closing again is harmless; using the journal now would be a fatal error.
+    journal.close()
+}
+
+// `[OpaqueToken]` handles are equal when they refer to the same Rust object.
+do {
+    let ticket = issueTicket(number: 7)
+    assert(sameTicket(ticket: ticket) == ticket)
+    assert(Set([ticket, sameTicket(ticket: ticket)]).count == 1)
+    assert(issueTicket(number: 7) != ticket)
+    assert(redeemTicket(ticket: ticket) == 7)
+}
+
+// `[ErrorFrom]` converts Rust errors into the variants of `ParseError`.
+do {
+    assert(try parseCount(text: " 42 ") == 42)
+    do {
+        _ = try parseCount(text: "forty-two")
+        fatalError("should have thrown")
+    } catch ParseError.InvalidNumber(let reason) {
+        assert(reason == "invalid digit found in string")
+    }
+    do {
+        _ = try parseCount(text: "")
+        fatalError("should have thrown")
+    } catch ParseError.Other(let reason) {
+        assert(reason == "nothing to parse")
+    }
+}
+
+// `[Borrowed]` methods return views, which keep the document alive while they're in use.
+do {
+    let text = Document(text: "héllo world").text()
+    assert(text.byteLength() == 12)
+    assert(text.substring(start: 7, end: 12) == "world")
+    assert(text.contents() == "héllo world")
+    let data = Document(text: "abc").bytes()
+    assert(data.slice(start: 1, end: 3) == [98, 99])
+}
+
+// `[Cached]` methods only call into Rust the first time.
+do {
+    let fingerprint = Fingerprint(data: "ab")
+    assert(fingerprint.digest() == "6162")
+    assert(fingerprint.digest() == "6162")
+    assert(fingerprint.digestCalls() == 1)
+}
+
+// `[WeakRefs]` objects can be held without keeping them alive.
+do {
+    var thumbnail: Thumbnail? = Thumbnail(width: 64)
+    let weak = thumbnail!.downgrade()
+    assert(weak.upgrade()?.width() == 64)
+    thumbnail = nil
+    assert(weak.upgrade() == nil)
+}
+
+// `[Scoped]` methods register their work against the scope, which stops it all at once.
+do {
+    let scope = Scope()
+    let ticker = Ticker()
+    ticker.start(scope: scope)
+    while ticker.ticks() == 0 {}
+    scope.cancel()
+    scope.join()
+    assert(scope.isCancelled())
+    let ticks = ticker.ticks()
+    // Nothing more starts on a cancelled scope.
+    ticker.start(scope: scope)
+    assert(ticker.ticks() == ticks)
+}
+
+// `[NoCopy]` methods return a view of the bytes, which is freed when it's deinitialized.
+do {
+    let pixels = Frame(size: 1000).pixels()
+    assert(pixels.count == 1000)
+    assert(pixels.withUnsafeBytes { $0[257] } == 1)
+    assert(pixels.data.count == 1000)
+    // Freeing early is optional, and can be done more than once.
+    pixels.free()
+    pixels.free()
+}
+
+// Every exported function and method is listed, keyed by its qualified name.
+do {
+    let export = CoverallExport(rawValue: "Coveralls.get_name")
+    assert(export == .coverallsGetName)
+    assert(export?.kind == "method")
+    assert(export?.signatureHash.count == 16)
+    assert(CoverallExport(rawValue: "Coveralls.no_such_method") == nil)
+}
+
+// Memory pressure reaches the hooks registered in Rust.
+do {
+    watchMemoryPressure()
+    uniffiOnMemoryPressure(.warning)
+    uniffiOnMemoryPressure()
+    assert(getMemoryPressureSeen() == ["moderate", "critical"])
+}
+
+// Warming the library up runs the hooks registered in Rust, on a background queue.
+do {
+    prepareWarmup()
+    let warmedUp = DispatchSemaphore(value: 0)
+    uniffiWarmup { warmedUp.signal() }
+    warmedUp.wait()
+    assert(isWarmedUp())
+}
@@ -6,5 +6,12 @@ uniffi_macros::build_foreign_language_testcases!(
         "tests/bindings/test_coverall.rb",
         "tests/bindings/test_coverall.swift",
         "tests/bindings/test_handlerace.kts",
+        "tests/bindings/bench_coverall.py",
+        "tests/bindings/bench_coverall.rb",
     ]
 );
+
+uniffi_macros::build_golden_file_testcases!(
+    ["src/coverall.udl"],
+    ["kotlin", "swift", "python", "ruby"]
+);
@@ -0,0 +1,42 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseError {
+    #[error("There's no count to parse")]
+    Empty,
+    #[error("The count isn't a number")]
+    NotANumber,
+}
+
+fn parse_count(text: String) -> Result<u32, ParseError> {
+    if text.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    text.parse().map_err(|_| ParseError::NotANumber)
+}
+
+#[derive(Default)]
+pub struct Counter {
+    total: AtomicU32,
+}
+
+impl Counter {
+    fn new() -> Self {
+        Counter::default()
+    }
+
+    fn add(&self, text: String) -> Result<(), ParseError> {
+        self.total.fetch_add(parse_count(text)?, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn total(&self) -> u32 {
+        self.total.load(Ordering::SeqCst)
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/errors_as_values.uniffi.rs"));
@@ -0,0 +1,16 @@
+import uniffi.errors_as_values.*
+
+// Functions that throw return a `Result` instead, holding what they returned or the exception.
+assert(parseCount("42") == Result.success(42u))
+assert(parseCount("forty-two").exceptionOrNull() is ParseException.NotANumber)
+assert(parseCount("").exceptionOrNull() is ParseException.Empty)
+
+// So do methods, including those that don't return anything.
+val counter = Counter()
+assert(counter.add("2").isSuccess)
+assert(counter.add("two").exceptionOrNull() is ParseException.NotANumber)
+assert(counter.add("3").isSuccess)
+
+// Methods that don't throw return what they did before.
+assert(counter.total() == 5u)
+counter.destroy()
@@ -0,0 +1,15 @@
+from errors_as_values import *
+
+# Functions that raise return the error instead.
+assert parse_count("42") == 42
+assert isinstance(parse_count("forty-two"), ParseError.NotANumber)
+assert isinstance(parse_count(""), ParseError.Empty)
+
+# So do methods, including those that don't return anything.
+counter = Counter()
+assert counter.add("2") is None
+assert isinstance(counter.add("two"), ParseError.NotANumber)
+assert counter.add("3") is None
+
+# Methods that don't raise return what they did before.
+assert counter.total() == 5
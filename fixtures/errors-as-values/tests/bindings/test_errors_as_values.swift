@@ -0,0 +1,26 @@
+import errors_as_values
+
+// Functions that throw return a `Result` instead, holding what they returned or the error.
+switch parseCount(text: "42") {
+    case .success(let count): assert(count == 42)
+    case .failure(let error): fatalError("Unexpected error: \(error)")
+}
+if case .failure(.NotANumber) = parseCount(text: "forty-two") {} else {
+    fatalError("Should have failed with ParseError.NotANumber")
+}
+if case .failure(.Empty) = parseCount(text: "") {} else {
+    fatalError("Should have failed with ParseError.Empty")
+}
+
+// So do methods, including those that don't return anything.
+let counter = Counter()
+if case .failure(let error) = counter.add(text: "2") {
+    fatalError("Unexpected error: \(error)")
+}
+if case .failure(.NotANumber) = counter.add(text: "two") {} else {
+    fatalError("Should have failed with ParseError.NotANumber")
+}
+_ = counter.add(text: "3")
+
+// Methods that don't throw return what they did before.
+assert(counter.total() == 5)
@@ -0,0 +1,13 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["src/errors_as_values.udl",],
+    [
+        "tests/bindings/test_errors_as_values.py",
+        "tests/bindings/test_errors_as_values.kts",
+        "tests/bindings/test_errors_as_values.swift",
+    ]
+);
+
+uniffi_macros::build_golden_file_testcases!(
+    ["src/errors_as_values.udl"],
+    ["kotlin", "swift", "python"]
+);
@@ -2,3 +2,5 @@ uniffi_macros::build_foreign_language_testcases!(
     ["src/guid.udl",],
     ["tests/bindings/test_guid.py",]
 );
+
+uniffi_macros::build_golden_file_testcases!(["src/guid.udl"], ["python"]);
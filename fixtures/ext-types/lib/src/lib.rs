@@ -1,5 +1,7 @@
+use std::sync::Arc;
+
 use ext_types_guid::Guid;
-use uniffi_one::UniffiOneType;
+use uniffi_one::{UniffiOneInterface, UniffiOneType};
 use wrapper_types::Handle;
 
 pub struct CombinedType {
@@ -49,4 +51,16 @@ fn get_combined_type(existing: Option<CombinedType>) -> CombinedType {
     })
 }
 
+fn get_uniffi_one_interface() -> Arc<UniffiOneInterface> {
+    Arc::new(UniffiOneInterface::new())
+}
+
+fn increment_uniffi_one_interface(interface: Arc<UniffiOneInterface>) -> i32 {
+    interface.increment()
+}
+
+fn get_uniffi_one_interfaces(count: u32) -> Vec<Arc<UniffiOneInterface>> {
+    (0..count).map(|_| get_uniffi_one_interface()).collect()
+}
+
 include!(concat!(env!("OUT_DIR"), "/ext-types-lib.uniffi.rs"));
@@ -0,0 +1,29 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.imported_types_lib.*
+import uniffi.uniffi_one.UniffiOneInterface
+
+val ct = getCombinedType(null)
+assert(ct.uot.sval == "hello")
+assert(ct.guid == "a-guid")
+assert(ct.json == """{"hello":"there"}""")
+assert(ct.handle == 123L)
+assert(ct.handles == listOf(1L, 2L, 3L))
+assert(ct.maybeHandle == 4L)
+
+val ct2 = getCombinedType(ct)
+assert(ct == ct2)
+
+// The same object is passed to the other component, not a copy of it.
+val counter = UniffiOneInterface()
+assert(counter.increment() == 1)
+assert(incrementUniffiOneInterface(counter) == 2)
+assert(counter.increment() == 3)
+
+val other = getUniffiOneInterface()
+assert(other.increment() == 1)
+
+val counters = getUniffiOneInterfaces(2u)
+assert(counters.map { it.increment() } == listOf(1, 1))
@@ -4,6 +4,7 @@
 
 import unittest
 from imported_types_lib import *
+from uniffi_one import UniffiOneInterface
 
 class TestIt(unittest.TestCase):
     def test_it(self):
@@ -15,6 +16,23 @@ class TestIt(unittest.TestCase):
         ct2 = get_combined_type(ct)
         self.assertEqual(ct, ct2)
 
+    def test_external_interfaces(self):
+        counter = UniffiOneInterface()
+        self.assertEqual(counter.increment(), 1)
+        # The same object is passed to the other component, not a copy of it.
+        self.assertEqual(increment_uniffi_one_interface(counter), 2)
+        self.assertEqual(counter.increment(), 3)
+
+        other = get_uniffi_one_interface()
+        self.assertIsInstance(other, UniffiOneInterface)
+        self.assertEqual(other.increment(), 1)
+
+        counters = get_uniffi_one_interfaces(2)
+        self.assertEqual([c.increment() for c in counters], [1, 1])
+
+        with self.assertRaises(TypeError):
+            increment_uniffi_one_interface(get_combined_type(None))
+
 
 if __name__=='__main__':
     unittest.main()
@@ -0,0 +1,33 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import imported_types_lib
+import uniffi_one
+
+do {
+    let ct = getCombinedType(val: nil)
+    assert(ct.uot.sval == "hello")
+    assert(ct.guid == "a-guid")
+    assert(ct.json == "{\"hello\":\"there\"}")
+    assert(ct.handle == 123)
+    assert(ct.handles == [1, 2, 3])
+    assert(ct.maybeHandle == 4)
+
+    let ct2 = getCombinedType(val: ct)
+    assert(ct == ct2)
+}
+
+do {
+    // The same object is passed to the other component, not a copy of it.
+    let counter = UniffiOneInterface()
+    assert(counter.increment() == 1)
+    assert(incrementUniffiOneInterface(interface: counter) == 2)
+    assert(counter.increment() == 3)
+
+    let other = getUniffiOneInterface()
+    assert(other.increment() == 1)
+
+    let counters = getUniffiOneInterfaces(count: 2)
+    assert(counters.map { $0.increment() } == [1, 1])
+}
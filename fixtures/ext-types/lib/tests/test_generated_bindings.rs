@@ -5,5 +5,15 @@ uniffi_macros::build_foreign_language_testcases!(
         "../uniffi-one/src/uniffi-one.udl",
         "src/ext-types-lib.udl",
     ],
-    ["tests/bindings/test_imported_types.py",]
+    [
+        "tests/bindings/test_imported_types.py",
+        "tests/bindings/test_imported_types.kts",
+        "tests/bindings/test_imported_types.swift",
+    ],
+    ["external_types", "wrapped_types"]
+);
+
+uniffi_macros::build_golden_file_testcases!(
+    ["../uniffi-one/src/uniffi-one.udl", "src/ext-types-lib.udl"],
+    ["kotlin", "swift", "python"]
 );
@@ -1,5 +1,22 @@
+use std::sync::atomic::{AtomicI32, Ordering};
+
 pub struct UniffiOneType {
     pub sval: String,
 }
 
+#[derive(Default)]
+pub struct UniffiOneInterface {
+    current: AtomicI32,
+}
+
+impl UniffiOneInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn increment(&self) -> i32 {
+        self.current.fetch_add(1, Ordering::SeqCst) + 1
+    }
+}
+
 include!(concat!(env!("OUT_DIR"), "/uniffi-one.uniffi.rs"));
@@ -0,0 +1,100 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, thiserror::Error)]
+pub enum FutureError {
+    #[error("The name is empty")]
+    EmptyName,
+}
+
+// A future that's ready once `delay_ms` have passed, which a thread of its own wakes up, so that
+// the futures here don't need an async runtime.
+struct Delay {
+    delay_ms: u32,
+    started: bool,
+}
+
+fn delay(delay_ms: u32) -> Delay {
+    Delay {
+        delay_ms,
+        started: false,
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.started {
+            return Poll::Ready(());
+        }
+        self.started = true;
+        let waker = cx.waker().clone();
+        let delay_ms = self.delay_ms;
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(delay_ms.into()));
+            waker.wake();
+        });
+        Poll::Pending
+    }
+}
+
+async fn add_after(a: u32, b: u32, delay_ms: u32) -> u32 {
+    delay(delay_ms).await;
+    a + b
+}
+
+async fn greet(name: String) -> Result<String, FutureError> {
+    delay(1).await;
+    if name.is_empty() {
+        return Err(FutureError::EmptyName);
+    }
+    Ok(format!("Hello, {}!", name))
+}
+
+async fn panic_after(delay_ms: u32) {
+    delay(delay_ms).await;
+    panic!("The future panicked");
+}
+
+// How many calls to `wait_until_cancelled` have finished.
+static CANCELLED_WAITS: AtomicU32 = AtomicU32::new(0);
+
+async fn wait_until_cancelled(token: uniffi::CancellationToken) {
+    token.cancelled().await;
+    CANCELLED_WAITS.fetch_add(1, Ordering::SeqCst);
+}
+
+fn cancelled_waits() -> u32 {
+    CANCELLED_WAITS.load(Ordering::SeqCst)
+}
+
+#[derive(Debug, Default)]
+pub struct Counter {
+    count: AtomicU32,
+}
+
+impl Counter {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    async fn increment_after(&self, delay_ms: u32) -> u32 {
+        delay(delay_ms).await;
+        self.count.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    fn value(&self) -> u32 {
+        self.count.load(Ordering::SeqCst)
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/futures.uniffi.rs"));
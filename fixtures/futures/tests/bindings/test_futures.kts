@@ -0,0 +1,42 @@
+import uniffi.futures.*
+import java.util.concurrent.CountDownLatch
+import kotlin.coroutines.Continuation
+import kotlin.coroutines.EmptyCoroutineContext
+import kotlin.coroutines.startCoroutine
+
+// Run a coroutine to completion on this thread, without needing `kotlinx.coroutines`.
+fun <T> waitFor(block: suspend () -> T): T {
+    val done = CountDownLatch(1)
+    var result: Result<T>? = null
+    block.startCoroutine(Continuation(EmptyCoroutineContext) {
+        result = it
+        done.countDown()
+    })
+    done.await()
+    return result!!.getOrThrow()
+}
+
+assert(waitFor { addAfter(1U, 2U, 10U) } == 3U)
+
+assert(waitFor { greet("Alice") } == "Hello, Alice!")
+try {
+    waitFor { greet("") }
+    throw RuntimeException("Should have thrown an EmptyName exception!")
+} catch (e: FutureException.EmptyName) {
+    // It's okay!
+}
+
+try {
+    waitFor { panicAfter(1U) }
+    throw RuntimeException("Should have thrown an InternalException!")
+} catch (e: InternalException) {
+    assert(e.message == "The future panicked")
+}
+
+val counter = Counter()
+val results = waitFor {
+    listOf(counter.incrementAfter(100U), counter.incrementAfter(100U))
+}
+assert(results == listOf(1U, 2U))
+assert(counter.value() == 2U)
+counter.destroy()
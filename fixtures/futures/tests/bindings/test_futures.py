@@ -0,0 +1,48 @@
+import asyncio
+import time
+import unittest
+from futures import *
+
+class TestFutures(unittest.TestCase):
+    def test_async_function(self):
+        self.assertEqual(asyncio.run(add_after(1, 2, 10)), 3)
+
+    def test_async_function_that_throws(self):
+        self.assertEqual(asyncio.run(greet("Alice")), "Hello, Alice!")
+        with self.assertRaises(FutureError.EmptyName):
+            asyncio.run(greet(""))
+
+    def test_async_function_that_panics(self):
+        with self.assertRaisesRegex(InternalError, "The future panicked"):
+            asyncio.run(panic_after(1))
+
+    def test_async_methods_run_concurrently(self):
+        counter = Counter()
+
+        async def increment_twice():
+            return await asyncio.gather(
+                counter.increment_after(50),
+                counter.increment_after(50),
+            )
+
+        self.assertEqual(sorted(asyncio.run(increment_twice())), [1, 2])
+        self.assertEqual(counter.value(), 2)
+
+    def test_cancelling_async_calls(self):
+        async def cancel_soon(call):
+            task = asyncio.ensure_future(call)
+            await asyncio.sleep(0.05)
+            task.cancel()
+            with self.assertRaises(asyncio.CancelledError):
+                await task
+
+        asyncio.run(cancel_soon(add_after(1, 2, 10000)))
+        asyncio.run(cancel_soon(wait_until_cancelled()))
+        # The task doesn't wait for a `[Cancellable]` call to finish once it's cancelled.
+        deadline = time.monotonic() + 5
+        while cancelled_waits() == 0 and time.monotonic() < deadline:
+            time.sleep(0.01)
+        self.assertEqual(cancelled_waits(), 1)
+
+if __name__ == "__main__":
+    unittest.main()
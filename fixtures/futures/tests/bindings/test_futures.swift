@@ -0,0 +1,43 @@
+import Foundation
+import futures
+
+// Run an async block to completion, blocking the script until it has finished.
+func waitFor(_ block: @escaping () async throws -> Void) {
+    guard #available(macOS 10.15, *) else {
+        fatalError("[Async] functions need macOS 10.15")
+    }
+    let done = DispatchSemaphore(value: 0)
+    Task {
+        try! await block()
+        done.signal()
+    }
+    done.wait()
+}
+
+waitFor {
+    let sum = await addAfter(a: 1, b: 2, delayMs: 10)
+    assert(sum == 3, "add after a delay")
+
+    let greeting = try await greet(name: "Alice")
+    assert(greeting == "Hello, Alice!", "greet")
+    do {
+        let _ = try await greet(name: "")
+        fatalError("Should have thrown an EmptyName error!")
+    } catch FutureError.EmptyName {
+        // It's okay!
+    }
+
+    let counter = Counter()
+    async let first = counter.incrementAfter(delayMs: 50)
+    async let second = counter.incrementAfter(delayMs: 50)
+    let results = await [first, second]
+    assert(results.sorted() == [1, 2], "concurrent calls")
+    assert(counter.value() == 2, "counter value")
+
+    // A `[Cancellable]` call finishes once its task is cancelled, and the task waits for it.
+    let waiting = Task { await waitUntilCancelled() }
+    try await Task.sleep(nanoseconds: 50_000_000)
+    waiting.cancel()
+    await waiting.value
+    assert(cancelledWaits() == 1, "cancelled wait")
+}
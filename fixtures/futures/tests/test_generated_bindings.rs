@@ -0,0 +1,11 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["src/futures.udl",],
+    [
+        "tests/bindings/test_futures.py",
+        "tests/bindings/test_futures.kts",
+        "tests/bindings/test_futures.swift",
+    ],
+    ["async_functions"]
+);
+
+uniffi_macros::build_golden_file_testcases!(["src/futures.udl"], ["kotlin", "swift", "python"]);
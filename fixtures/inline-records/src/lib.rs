@@ -0,0 +1,44 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The records are only inlined in the bindings, so on this side they're plain structs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserId {
+    value: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Email {
+    address: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct User {
+    id: UserId,
+    email: Email,
+}
+
+fn next_user_id(id: UserId) -> UserId {
+    UserId {
+        value: id.value + 1,
+    }
+}
+
+fn sort_user_ids(mut ids: Vec<UserId>) -> Vec<UserId> {
+    ids.sort_by_key(|id| id.value);
+    ids
+}
+
+fn make_user(id: UserId, email: Email) -> User {
+    User { id, email }
+}
+
+fn find_user(users: Vec<User>, email: Email) -> Option<UserId> {
+    users
+        .into_iter()
+        .find(|user| user.email == email)
+        .map(|user| user.id)
+}
+
+include!(concat!(env!("OUT_DIR"), "/inline_records.uniffi.rs"));
@@ -0,0 +1,32 @@
+import uniffi.inline_records.*
+
+// Value classes compare by the value of their field.
+assert(UserId(42L) == UserId(42L))
+assert(UserId(42L) != UserId(43L))
+assert(UserId(42L).hashCode() == UserId(42L).hashCode())
+assert(Email("a@example.com") == Email("a@example.com"))
+assert(setOf(UserId(1L), UserId(1L), UserId(2L)).size == 2)
+
+// They round-trip on their own...
+assert(nextUserId(UserId(41L)) == UserId(42L))
+assert(nextUserId(UserId(41L)).value == 42L)
+
+// ...and boxed, in sequences and optionals.
+assert(sortUserIds(listOf(UserId(3L), UserId(1L), UserId(2L))) == listOf(UserId(1L), UserId(2L), UserId(3L)))
+assert(sortUserIds(listOf()).isEmpty())
+
+// ...and as the fields of records that aren't inlined.
+val alice = makeUser(UserId(1L), Email("alice@example.com"))
+val bob = makeUser(UserId(2L), Email("bob@example.com"))
+assert(alice == User(UserId(1L), Email("alice@example.com")))
+assert(alice.id == UserId(1L))
+assert(alice.email.address == "alice@example.com")
+
+// `User` has `var` fields as usual, which can hold other inline records.
+alice.id = UserId(3L)
+assert(alice == User(UserId(3L), Email("alice@example.com")))
+
+val users = listOf(alice, bob)
+assert(findUser(users, Email("bob@example.com")) == UserId(2L))
+assert(findUser(users, Email("alice@example.com")) == UserId(3L))
+assert(findUser(users, Email("carol@example.com")) == null)
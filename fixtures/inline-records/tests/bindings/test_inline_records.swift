@@ -0,0 +1,35 @@
+import inline_records
+
+// Frozen structs compare by the value of their field.
+assert(UserId(value: 42) == UserId(value: 42))
+assert(UserId(value: 42) != UserId(value: 43))
+assert(UserId(value: 42).hashValue == UserId(value: 42).hashValue)
+assert(Email(address: "a@example.com") == Email(address: "a@example.com"))
+assert(Set([UserId(value: 1), UserId(value: 1), UserId(value: 2)]).count == 2)
+
+// They round-trip on their own...
+assert(nextUserId(id: UserId(value: 41)) == UserId(value: 42))
+assert(nextUserId(id: UserId(value: 41)).value == 42)
+
+// ...in sequences and optionals...
+assert(sortUserIds(ids: [UserId(value: 3), UserId(value: 1), UserId(value: 2)]) == [UserId(value: 1), UserId(value: 2), UserId(value: 3)])
+assert(sortUserIds(ids: []).isEmpty)
+
+// ...and as the fields of records that aren't inlined.
+var alice = makeUser(id: UserId(value: 1), email: Email(address: "alice@example.com"))
+let bob = makeUser(id: UserId(value: 2), email: Email(address: "bob@example.com"))
+assert(alice == User(id: UserId(value: 1), email: Email(address: "alice@example.com")))
+assert(alice.id == UserId(value: 1))
+assert(alice.email.address == "alice@example.com")
+
+// Structs are values, so changing a copy leaves the original alone.
+var copy = alice.id
+copy.value = 3
+assert(alice.id == UserId(value: 1))
+alice.id = copy
+assert(alice == User(id: UserId(value: 3), email: Email(address: "alice@example.com")))
+
+let users = [alice, bob]
+assert(findUser(users: users, email: Email(address: "bob@example.com")) == UserId(value: 2))
+assert(findUser(users: users, email: Email(address: "alice@example.com")) == UserId(value: 3))
+assert(findUser(users: users, email: Email(address: "carol@example.com")) == nil)
@@ -0,0 +1,9 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["src/inline_records.udl",],
+    [
+        "tests/bindings/test_inline_records.kts",
+        "tests/bindings/test_inline_records.swift",
+    ]
+);
+
+uniffi_macros::build_golden_file_testcases!(["src/inline_records.udl"], ["kotlin", "swift"]);
@@ -0,0 +1,27 @@
+using System;
+using System.Linq;
+using uniffi.invalid_strings;
+
+static void Check(bool condition, string message) {
+    if (!condition) {
+        throw new Exception($"Assertion failed: {message}");
+    }
+}
+
+Check(InvalidStringsMethods.ByteLength("héllo") == 6, "byte length");
+
+// C# strings always encode to valid UTF-8, so we call the FFI function directly, with bytes
+// that aren't, as a corrupted buffer might hold. Its name includes the checksum of the
+// component interface, so we look it up rather than spelling it out.
+var ffiByteLength = typeof(_UniFFILib).GetMethods().Single(m => m.Name.EndsWith("_byte_length"));
+try {
+    _UniFFIHelpers.RustCall<uint>((ref RustCallStatus status) => {
+        var args = new object[] { RustBuffer.FromBytes(new byte[] { 0x34, 0xFF, 0x32 }), status };
+        var length = (uint)ffiByteLength.Invoke(null, args)!;
+        status = (RustCallStatus)args[1];
+        return length;
+    });
+    throw new Exception("Should have thrown an InvalidStringException");
+} catch (InvalidStringException e) {
+    Check(e.Message == "Failed to convert arg 'text': invalid utf8 in string, after 1 valid bytes", e.Message);
+}
@@ -0,0 +1,39 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"unicode/utf8"
+
+	"uniffi/invalid_strings"
+)
+
+func check(condition bool, message string) {
+	if !condition {
+		panic(fmt.Sprintf("Assertion failed: %s", message))
+	}
+}
+
+func mustPanicWithInvalidString(message string, f func()) {
+	defer func() {
+		var invalid *invalid_strings.InvalidStringError
+		err, ok := recover().(error)
+		check(ok && errors.As(err, &invalid), message)
+		check(
+			invalid.Message == "Failed to convert arg 'text': invalid utf8 in string, after 1 valid bytes",
+			invalid.Message,
+		)
+	}()
+	f()
+}
+
+func main() {
+	check(invalid_strings.ByteLength("héllo") == 6, "byte length")
+
+	// Go strings can hold bytes that aren't valid UTF-8, which Rust refuses to lift.
+	invalid := "4\xff2"
+	check(!utf8.ValidString(invalid), "the string should be invalid")
+	mustPanicWithInvalidString("Should have panicked with an InvalidStringError", func() {
+		invalid_strings.ByteLength(invalid)
+	})
+}
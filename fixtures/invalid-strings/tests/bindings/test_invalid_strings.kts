@@ -0,0 +1,20 @@
+// The FFI layer of the bindings is internal, but this test needs to reach it.
+@file:Suppress("INVISIBLE_MEMBER", "INVISIBLE_REFERENCE")
+
+import uniffi.invalid_strings.*
+
+assert(byteLength("héllo") == 6U)
+
+// Kotlin strings always encode to valid UTF-8, so we call the FFI function directly,
+// with bytes that aren't, as a corrupted buffer might hold. Its name includes the checksum of
+// the component interface, so we look it up rather than spelling it out.
+val ffiByteLength = _UniFFILib::class.java.methods.single { it.name.endsWith("_byte_length") }
+val invalid = RustBufferBuilder().apply { put(byteArrayOf(0x34, 0xFF.toByte(), 0x32)) }.finalize()
+val status = RustCallStatus()
+ffiByteLength.invoke(_UniFFILib.INSTANCE, invalid, status)
+assert(status.isInvalidString()) { "Rust should have reported an invalid string, not status ${status.code}" }
+val message = status.error_buf.asByteBuffer()!!.let { buf ->
+    ByteArray(buf.remaining()).also { buf.get(it) }.toString(Charsets.UTF_8)
+}
+RustBuffer.free(status.error_buf)
+assert(message == "Failed to convert arg 'text': invalid utf8 in string, after 1 valid bytes") { message }
@@ -0,0 +1,44 @@
+import Foundation
+#if canImport(Glibc)
+import Glibc
+#endif
+import invalid_strings
+import invalid_stringsFFI
+
+assert(byteLength(text: "héllo") == 6, "byte length")
+
+// Swift strings always encode to valid UTF-8, so we call the FFI functions directly, with
+// bytes that aren't, as a corrupted buffer might hold. Their names include the checksum of
+// the component interface, so we look them up rather than spelling them out.
+let ffiNamespace = "invalid_strings_" + String(UInt16(uniffiComponentChecksum.suffix(4), radix: 16)!, radix: 16)
+let library = dlopen(nil, RTLD_NOW)
+
+func lookup<T>(_ name: String, as type: T.Type) -> T {
+    guard let symbol = dlsym(library, name) else {
+        fatalError("Missing FFI function \(name)")
+    }
+    return unsafeBitCast(symbol, to: type)
+}
+
+typealias FromBytes = @convention(c) (ForeignBytes, UnsafeMutablePointer<RustCallStatus>) -> RustBuffer
+typealias Free = @convention(c) (RustBuffer, UnsafeMutablePointer<RustCallStatus>) -> Void
+typealias ByteLength = @convention(c) (RustBuffer, UnsafeMutablePointer<RustCallStatus>) -> UInt32
+let fromBytes = lookup("ffi_\(ffiNamespace)_rustbuffer_from_bytes", as: FromBytes.self)
+let freeBuffer = lookup("ffi_\(ffiNamespace)_rustbuffer_free", as: Free.self)
+let ffiByteLength = lookup("\(ffiNamespace)_byte_length", as: ByteLength.self)
+
+var status = RustCallStatus(code: 0, errorBuf: RustBuffer(capacity: 0, len: 0, data: nil))
+let bytes: [UInt8] = [0x34, 0xFF, 0x32]
+let invalid = bytes.withUnsafeBufferPointer {
+    fromBytes(ForeignBytes(len: Int32($0.count), data: $0.baseAddress), &status)
+}
+assert(status.code == 0, "allocating the buffer")
+_ = ffiByteLength(invalid, &status)
+assert(status.code == 3, "Rust should have reported an invalid string, not status \(status.code)")
+let message = String(
+    bytes: UnsafeBufferPointer(start: status.errorBuf.data, count: Int(status.errorBuf.len)),
+    encoding: .utf8
+)
+var freeStatus = RustCallStatus(code: 0, errorBuf: RustBuffer(capacity: 0, len: 0, data: nil))
+freeBuffer(status.errorBuf, &freeStatus)
+assert(message == "Failed to convert arg 'text': invalid utf8 in string, after 1 valid bytes", message ?? "")
@@ -0,0 +1,11 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["src/invalid_strings.udl",],
+    [
+        "tests/bindings/test_invalid_strings.kts",
+        "tests/bindings/test_invalid_strings.swift",
+        "tests/bindings/test_invalid_strings.cs",
+        "tests/bindings/test_invalid_strings.go",
+    ]
+);
+
+uniffi_macros::build_golden_file_testcases!(["src/invalid_strings.udl"], ["kotlin", "swift"]);
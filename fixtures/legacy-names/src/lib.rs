@@ -0,0 +1,44 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+fn get_answer() -> u32 {
+    42
+}
+
+pub enum Mood {
+    Happy,
+    Grumpy,
+}
+
+pub struct Counter {
+    value: AtomicU32,
+}
+
+impl Counter {
+    fn new(start: u32) -> Self {
+        Counter {
+            value: AtomicU32::new(start),
+        }
+    }
+
+    fn increment(&self, by: u32) {
+        self.value.fetch_add(by, Ordering::SeqCst);
+    }
+
+    fn current_value(&self) -> u32 {
+        self.value.load(Ordering::SeqCst)
+    }
+
+    fn mood(&self) -> Mood {
+        if self.current_value() % 2 == 0 {
+            Mood::Happy
+        } else {
+            Mood::Grumpy
+        }
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/legacy_names.uniffi.rs"));
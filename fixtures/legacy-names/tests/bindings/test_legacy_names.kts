@@ -0,0 +1,11 @@
+import uniffi.legacy_names.*;
+
+assert(fetch_answer() == 42u)
+
+val counter = LegacyCounter(1u)
+counter.increment(amount = 2u)
+assert(counter.getCount() == 3u)
+assert(counter.mood() == Mood.CRANKY)
+counter.increment(1u)
+assert(counter.mood() == Mood.HAPPY)
+counter.destroy()
@@ -0,0 +1,13 @@
+from legacy_names import *
+
+# Identifiers without a rename keep their usual Python names.
+assert get_answer() == 42
+
+# `Counter` is renamed by both `renames.toml` and `uniffi.toml`, and the latter wins.
+counter = PyCounter(1)
+assert "LegacyCounter" not in globals()
+counter.increment(by=2)
+assert counter.getCount() == 3
+assert counter.mood() == Mood.cranky
+counter.increment(1)
+assert counter.mood() == Mood.HAPPY
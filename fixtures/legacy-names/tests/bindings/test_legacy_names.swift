@@ -0,0 +1,10 @@
+import legacy_names
+
+assert(getAnswer() == 42)
+
+let counter = LGCCounter(start: 1)
+counter.increment(amount: 2)
+assert(counter.count() == 3)
+assert(counter.mood() == .grumpy)
+counter.increment(amount: 1)
+assert(counter.mood() == .happy)
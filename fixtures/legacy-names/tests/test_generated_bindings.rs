@@ -0,0 +1,13 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["src/legacy_names.udl",],
+    [
+        "tests/bindings/test_legacy_names.py",
+        "tests/bindings/test_legacy_names.kts",
+        "tests/bindings/test_legacy_names.swift",
+    ]
+);
+
+uniffi_macros::build_golden_file_testcases!(
+    ["src/legacy_names.udl"],
+    ["kotlin", "swift", "python"]
+);
@@ -6,3 +6,5 @@ uniffi_macros::build_foreign_language_testcases!(
         "tests/bindings/test.swift",
     ]
 );
+
+uniffi_macros::build_golden_file_testcases!(["src/test.udl"], ["kotlin", "swift", "python"]);
@@ -1 +1,3 @@
 uniffi_macros::build_foreign_language_testcases!(["src/test.udl",], ["tests/bindings/test.kts",]);
+
+uniffi_macros::build_golden_file_testcases!(["src/test.udl"], ["kotlin"]);
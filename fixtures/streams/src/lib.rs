@@ -0,0 +1,52 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+// How many numbers the streams returned by `count_to` have handed out, so that the tests can
+// check that they're only taken as they're needed.
+static NUMBERS_TAKEN: AtomicU32 = AtomicU32::new(0);
+
+fn count_to(n: u32) -> Arc<Numbers> {
+    Arc::new(Numbers::new((1..=n).inspect(|_| {
+        NUMBERS_TAKEN.fetch_add(1, Ordering::SeqCst);
+    })))
+}
+
+fn numbers_taken() -> u32 {
+    NUMBERS_TAKEN.load(Ordering::SeqCst)
+}
+
+#[derive(Debug, Clone)]
+pub struct Line {
+    number: u32,
+    text: String,
+}
+
+#[derive(Debug)]
+pub struct Document {
+    text: String,
+}
+
+impl Document {
+    fn new(text: String) -> Self {
+        Document { text }
+    }
+
+    fn lines(&self) -> Arc<Lines> {
+        let lines: Vec<Line> = self
+            .text
+            .lines()
+            .enumerate()
+            .map(|(index, text)| Line {
+                number: index as u32 + 1,
+                text: text.to_string(),
+            })
+            .collect();
+        Arc::new(Lines::new(lines))
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/streams.uniffi.rs"));
@@ -0,0 +1,20 @@
+import uniffi.streams.*;
+
+// Test that streams can be iterated over, a chunk at a time
+assert(countTo(5U).toList() == listOf(1U, 2U, 3U, 4U, 5U))
+assert(countTo(0U).toList().isEmpty())
+assert(countTo(1000U).sumOf { it.toLong() } == 500500L)
+
+// Test that items are only taken from Rust as they're needed
+val taken = numbersTaken()
+val numbers = countTo(1000U)
+assert(numbers.first() == 1U)
+assert(numbersTaken() - taken < 1000U)
+
+// Test that iterating again only gets the items that no earlier iteration took
+assert(numbers.first() != 1U)
+
+// Test streams of records
+val lines = Document("first\nsecond\nthird").lines().toList()
+assert(lines.map { it.number } == listOf(1U, 2U, 3U))
+assert(lines.map { it.text } == listOf("first", "second", "third"))
@@ -0,0 +1,20 @@
+from streams import *
+
+# Test that streams can be iterated over, a chunk at a time
+assert list(count_to(5)) == [1, 2, 3, 4, 5]
+assert list(count_to(0)) == []
+assert sum(count_to(1000)) == 500500
+
+# Test that items are only taken from Rust as they're needed
+taken = numbers_taken()
+numbers = count_to(1000)
+assert next(iter(numbers)) == 1
+assert numbers_taken() - taken < 1000
+
+# Test that iterating again only gets the items that no earlier iteration took
+assert next(iter(numbers)) != 1
+
+# Test streams of records
+lines = list(Document("first\nsecond\nthird").lines())
+assert [line.number for line in lines] == [1, 2, 3]
+assert [line.text for line in lines] == ["first", "second", "third"]
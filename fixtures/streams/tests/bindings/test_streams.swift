@@ -0,0 +1,20 @@
+import streams
+
+// Test that streams can be iterated over, a chunk at a time
+assert(Array(countTo(n: 5)) == [1, 2, 3, 4, 5], "count to five")
+assert(Array(countTo(n: 0)).isEmpty, "count to nothing")
+assert(countTo(n: 1000).reduce(0, +) == 500500, "sum of a thousand")
+
+// Test that items are only taken from Rust as they're needed
+let taken = numbersTaken()
+let numbers = countTo(n: 1000)
+assert(numbers.first(where: { _ in true }) == 1, "first number")
+assert(numbersTaken() - taken < 1000, "numbers taken lazily")
+
+// Test that iterating again only gets the items that no earlier iteration took
+assert(numbers.first(where: { _ in true }) != 1, "iterating again")
+
+// Test streams of records
+let lines = Array(Document(text: "first\nsecond\nthird").lines())
+assert(lines.map { $0.number } == [1, 2, 3], "line numbers")
+assert(lines.map { $0.text } == ["first", "second", "third"], "line text")
@@ -0,0 +1,10 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["src/streams.udl",],
+    [
+        "tests/bindings/test_streams.py",
+        "tests/bindings/test_streams.kts",
+        "tests/bindings/test_streams.swift",
+    ]
+);
+
+uniffi_macros::build_golden_file_testcases!(["src/streams.udl"], ["kotlin", "swift", "python"]);
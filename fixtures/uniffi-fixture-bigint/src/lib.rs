@@ -0,0 +1,38 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(Debug, thiserror::Error)]
+pub enum BigIntError {
+    #[error("Overflow multiplying {a} by {b}")]
+    Overflow { a: i128, b: i128 },
+}
+
+#[derive(Debug, Clone)]
+pub struct Balance {
+    account: String,
+    amount: i128,
+}
+
+fn multiply(a: i128, b: i128) -> Result<i128> {
+    a.checked_mul(b).ok_or(BigIntError::Overflow { a, b })
+}
+
+fn negate(a: i128) -> i128 {
+    a.wrapping_neg()
+}
+
+fn largest(values: Vec<i128>) -> Option<i128> {
+    values.into_iter().max()
+}
+
+fn deposit(balance: Balance, amount: u64) -> Balance {
+    Balance {
+        amount: balance.amount + i128::from(amount),
+        ..balance
+    }
+}
+
+type Result<T, E = BigIntError> = std::result::Result<T, E>;
+
+include!(concat!(env!("OUT_DIR"), "/bigints.uniffi.rs"));
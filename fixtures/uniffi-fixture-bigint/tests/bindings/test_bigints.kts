@@ -0,0 +1,33 @@
+import uniffi.bigints.*;
+import java.math.BigInteger
+
+val two = BigInteger.valueOf(2)
+
+// Test values that don't fit in 64 bits
+assert(multiply(two.pow(40), two.pow(40)) == two.pow(80))
+assert(multiply(two.pow(63).negate(), two.pow(63)) == two.pow(126).negate())
+
+// Test the edges of the 128-bit range
+assert(negate(two.pow(127) - BigInteger.ONE) == two.pow(127).negate() + BigInteger.ONE)
+assert(negate(two.pow(127).negate()) == two.pow(127).negate())
+
+// Test exceptions are propagated
+try {
+        multiply(two.pow(100), two.pow(100))
+        throw RuntimeException("Should have thrown an Overflow exception!")
+} catch (e: BigIntException.Overflow) {
+        // It's okay!
+}
+
+// Test values that don't fit in 128 bits are rejected
+try {
+        negate(two.pow(127))
+        throw RuntimeException("Should have thrown an IllegalArgumentException!")
+} catch (e: IllegalArgumentException) {
+        // It's okay!
+}
+
+// Test bigints inside other types
+assert(largest(listOf(BigInteger.ONE, two.pow(90).negate(), two.pow(90))) == two.pow(90))
+assert(largest(listOf()) == null)
+assert(deposit(Balance("savings", two.pow(64)), 1UL).amount == two.pow(64) + BigInteger.ONE)
@@ -0,0 +1,30 @@
+from bigints import *
+
+# Test values that don't fit in 64 bits
+assert multiply(2 ** 40, 2 ** 40) == 2 ** 80
+assert multiply(-(2 ** 63), 2 ** 63) == -(2 ** 126)
+
+# Test the edges of the 128-bit range
+assert negate(2 ** 127 - 1) == -(2 ** 127) + 1
+assert negate(-(2 ** 127)) == -(2 ** 127)
+
+# Test exceptions are propagated
+try:
+    multiply(2 ** 100, 2 ** 100)
+    assert(not("Should have thrown an Overflow exception!"))
+except BigIntError.Overflow:
+    # It's okay!
+    pass
+
+# Test values that don't fit in 128 bits are rejected
+try:
+    negate(2 ** 127)
+    assert(not("Should have thrown a ValueError exception!"))
+except ValueError:
+    # It's okay!
+    pass
+
+# Test bigints inside other types
+assert largest([1, -(2 ** 90), 2 ** 90]) == 2 ** 90
+assert largest([]) is None
+assert deposit(Balance("savings", 2 ** 64), 1).amount == 2 ** 64 + 1
@@ -0,0 +1,30 @@
+import bigints
+
+// Test values that don't fit in 64 bits
+assert(try! multiply(a: BigInt(1 << 40), b: BigInt(1 << 40)) == BigInt(high: 1 << 16, low: 0), "multiply")
+assert(try! multiply(a: BigInt(Int64.min), b: -1) == BigInt(high: 0, low: 1 << 63), "multiply past Int64.max")
+
+// Test the edges of the 128-bit range
+let maxValue = BigInt(high: Int64.max, low: UInt64.max)
+let minValue = BigInt(high: Int64.min, low: 0)
+assert(negate(a: maxValue) == BigInt(high: Int64.min, low: 1), "negate max")
+assert(negate(a: minValue) == minValue, "negate min")
+
+// Test the decimal representation
+assert(maxValue.description == "170141183460469231731687303715884105727")
+assert(minValue.description == "-170141183460469231731687303715884105728")
+assert(BigInt(-42).description == "-42")
+assert(BigInt(0).description == "0")
+
+// Test exceptions are propagated
+do {
+    let _ = try multiply(a: maxValue, b: 2)
+    fatalError("Should have thrown an Overflow exception!")
+} catch BigIntError.Overflow {
+    // It's okay!
+}
+
+// Test bigints inside other types
+assert(largest(values: [1, minValue, maxValue]) == maxValue, "largest")
+assert(largest(values: []) == nil, "largest of nothing")
+assert(deposit(balance: Balance(account: "savings", amount: BigInt(high: 1, low: 0)), amount: 1).amount == BigInt(high: 1, low: 1), "deposit")
@@ -0,0 +1,10 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["src/bigints.udl",],
+    [
+        "tests/bindings/test_bigints.py",
+        "tests/bindings/test_bigints.kts",
+        "tests/bindings/test_bigints.swift",
+    ]
+);
+
+uniffi_macros::build_golden_file_testcases!(["src/bigints.udl"], ["kotlin", "swift", "python"]);
@@ -0,0 +1,38 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(Debug, Clone)]
+pub struct Blob {
+    name: String,
+    data: Vec<u8>,
+}
+
+fn reverse(mut data: Vec<u8>) -> Vec<u8> {
+    data.reverse();
+    data
+}
+
+fn checksum(data: Vec<u8>) -> u64 {
+    data.iter().map(|&b| u64::from(b)).sum()
+}
+
+fn split(data: Vec<u8>, separator: u8) -> Vec<Vec<u8>> {
+    data.split(|&b| b == separator)
+        .map(|s| s.to_vec())
+        .collect()
+}
+
+fn first_line(data: Vec<u8>) -> Option<Vec<u8>> {
+    data.split(|&b| b == b'\n')
+        .next()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.to_vec())
+}
+
+fn append(mut blob: Blob, more: Vec<u8>) -> Blob {
+    blob.data.extend(more);
+    blob
+}
+
+include!(concat!(env!("OUT_DIR"), "/blobs.uniffi.rs"));
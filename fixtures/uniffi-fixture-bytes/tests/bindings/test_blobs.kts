@@ -0,0 +1,19 @@
+import uniffi.blobs.*;
+
+// Test that bytes come back as a `ByteArray`
+assert(reverse("abc".toByteArray()).contentEquals("cba".toByteArray()))
+assert(reverse(ByteArray(0)).isEmpty())
+
+// Test large payloads
+val payload = ByteArray(102400) { it.toByte() }
+assert(checksum(payload) == 255UL * 256UL / 2UL * 400UL)
+assert(reverse(reverse(payload)).contentEquals(payload))
+
+// Test bytes inside other types
+assert(split("a,bc,,d".toByteArray(), ','.toByte().toUByte()).map { String(it) } == listOf("a", "bc", "", "d"))
+assert(firstLine("hello\nworld".toByteArray())?.let { String(it) } == "hello")
+assert(firstLine(ByteArray(0)) == null)
+append(Blob("greeting", "hello".toByteArray()), " world".toByteArray()).let { blob ->
+    assert(blob.name == "greeting")
+    assert(String(blob.data) == "hello world")
+}
@@ -0,0 +1,19 @@
+from blobs import *
+
+# Test that bytes come back as Python `bytes`
+assert reverse(b"abc") == b"cba"
+assert isinstance(reverse(b"abc"), bytes)
+assert reverse(b"") == b""
+
+# Test large payloads
+payload = bytes(range(256)) * 400
+assert checksum(payload) == sum(range(256)) * 400
+assert reverse(reverse(payload)) == payload
+
+# Test bytes inside other types
+assert split(b"a,bc,,d", ord(",")) == [b"a", b"bc", b"", b"d"]
+assert first_line(b"hello\nworld") == b"hello"
+assert first_line(b"") is None
+blob = append(Blob("greeting", b"hello"), b" world")
+assert blob.name == "greeting"
+assert blob.data == b"hello world"
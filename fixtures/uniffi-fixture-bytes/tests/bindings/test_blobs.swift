@@ -0,0 +1,19 @@
+import Foundation
+import blobs
+
+// Test that bytes come back as `Data`
+assert(reverse(data: Data("abc".utf8)) == Data("cba".utf8), "reverse")
+assert(reverse(data: Data()).isEmpty, "reverse of nothing")
+
+// Test large payloads
+let payload = Data((0..<102400).map { UInt8(truncatingIfNeeded: $0) })
+assert(checksum(data: payload) == 255 * 256 / 2 * 400, "checksum")
+assert(reverse(data: reverse(data: payload)) == payload, "reverse twice")
+
+// Test bytes inside other types
+assert(split(data: Data("a,bc,,d".utf8), separator: UInt8(ascii: ",")) == [Data("a".utf8), Data("bc".utf8), Data(), Data("d".utf8)], "split")
+assert(firstLine(data: Data("hello\nworld".utf8)) == Data("hello".utf8), "first line")
+assert(firstLine(data: Data()) == nil, "first line of nothing")
+let blob = append(blob: Blob(name: "greeting", data: Data("hello".utf8)), more: Data(" world".utf8))
+assert(blob.name == "greeting")
+assert(blob.data == Data("hello world".utf8))
@@ -0,0 +1,10 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["src/blobs.udl",],
+    [
+        "tests/bindings/test_blobs.py",
+        "tests/bindings/test_blobs.kts",
+        "tests/bindings/test_blobs.swift",
+    ]
+);
+
+uniffi_macros::build_golden_file_testcases!(["src/blobs.udl"], ["kotlin", "swift", "python"]);
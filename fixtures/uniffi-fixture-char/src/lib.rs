@@ -0,0 +1,34 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(Debug, Clone)]
+pub struct Glyph {
+    value: char,
+    name: String,
+}
+
+// The char after `c`, skipping the surrogates, or `c` itself if it's the last one.
+fn next_char(c: char) -> char {
+    (u32::from(c) + 1..=u32::from(char::MAX))
+        .find_map(char::from_u32)
+        .unwrap_or(c)
+}
+
+fn code_point(c: char) -> u32 {
+    c.into()
+}
+
+fn chars_of(s: String) -> Vec<char> {
+    s.chars().collect()
+}
+
+fn first_char(s: String) -> Option<char> {
+    s.chars().next()
+}
+
+fn glyph(value: char, name: String) -> Glyph {
+    Glyph { value, name }
+}
+
+include!(concat!(env!("OUT_DIR"), "/chars.uniffi.rs"));
@@ -0,0 +1,25 @@
+import uniffi.chars.*;
+
+// Test that chars come back as `Int` code points
+assert(nextChar(0x61) == 0x62)
+assert(codePoint(0x1F980) == 0x1F980U)
+assert(nextChar(0xD7FF) == 0xE000)
+
+// Test chars inside other types
+assert(charsOf("hé🦀") == listOf(0x68, 0xE9, 0x1F980))
+assert(firstChar("") == null)
+assert(firstChar("xyz") == 0x78)
+glyph(0xE9, "e acute").let { g ->
+    assert(g.value == 0xE9)
+    assert(g.name == "e acute")
+}
+
+// Test that code points that aren't Unicode scalar values are rejected
+for (invalid in listOf(-1, 0xD800, 0x110000)) {
+    try {
+        codePoint(invalid)
+        throw RuntimeException("Should have rejected $invalid")
+    } catch (e: IllegalArgumentException) {
+        // It's okay!
+    }
+}
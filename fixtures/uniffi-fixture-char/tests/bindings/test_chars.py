@@ -0,0 +1,23 @@
+from chars import *
+
+# Test that chars come back as Python strings of one character
+assert next_char("a") == "b"
+assert code_point("\U0001F980") == 0x1F980
+assert next_char("\uD7FF") == "\uE000"
+
+# Test chars inside other types
+assert chars_of("hé\U0001F980") == ["h", "é", "\U0001F980"]
+assert first_char("") is None
+assert first_char("xyz") == "x"
+g = glyph("é", "e acute")
+assert g.value == "é"
+assert g.name == "e acute"
+
+# Test that strings that aren't one Unicode scalar value are rejected
+for invalid in ["", "ab", "\uD800", None]:
+    try:
+        code_point(invalid)
+    except ValueError:
+        pass
+    else:
+        raise AssertionError("Should have rejected {!r}".format(invalid))
@@ -0,0 +1,14 @@
+import chars
+
+// Test that chars come back as `Unicode.Scalar`s
+assert(nextChar(c: "a") == "b", "next char")
+assert(codePoint(c: "\u{1F980}") == 0x1F980, "code point outside the BMP")
+assert(nextChar(c: "\u{D7FF}") == "\u{E000}", "next char skips the surrogates")
+
+// Test chars inside other types
+assert(charsOf(s: "h\u{E9}\u{1F980}") == ["h", "\u{E9}", "\u{1F980}"], "chars of a string")
+assert(firstChar(s: "") == nil, "first char of nothing")
+assert(firstChar(s: "xyz") == "x", "first char")
+let g = glyph(value: "\u{E9}", name: "e acute")
+assert(g.value == "\u{E9}")
+assert(g.name == "e acute")
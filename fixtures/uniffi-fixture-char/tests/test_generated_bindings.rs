@@ -0,0 +1,10 @@
+uniffi_macros::build_foreign_language_testcases!(
+    ["src/chars.udl",],
+    [
+        "tests/bindings/test_chars.py",
+        "tests/bindings/test_chars.kts",
+        "tests/bindings/test_chars.swift",
+    ]
+);
+
+uniffi_macros::build_golden_file_testcases!(["src/chars.udl"], ["kotlin", "swift", "python"]);
@@ -6,3 +6,8 @@ uniffi_macros::build_foreign_language_testcases!(
         "tests/bindings/test_chronological.swift",
     ]
 );
+
+uniffi_macros::build_golden_file_testcases!(
+    ["src/chronological.udl"],
+    ["kotlin", "swift", "python"]
+);
@@ -0,0 +1,170 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Views of data owned by an object
+//!
+//! A method marked `[Borrowed]` in the UDL returns a reference into its object, such as
+//! `fn text(&self) -> &str`, and the foreign-language code gets a view of that data instead of
+//! a copy. The view is itself an object, [`BorrowedString`] for a `string` or [`BorrowedBytes`]
+//! for a `sequence<u8>`, which holds a reference to the object that the data belongs to. That
+//! keeps the data alive, and unchanged, for as long as the view is: an object with `[Borrowed]`
+//! methods can't have `[Self=ByMut]` ones, so nothing can get a `&mut` to it in the meantime.
+//!
+//! Foreign-language code can then copy just the part of the data that it needs, with
+//! offsets in bytes that are checked against the length of the data.
+
+use std::any::Any;
+use std::sync::Arc;
+
+/// A view of a string owned by an object, as returned by a `[Borrowed]` method.
+pub struct BorrowedString {
+    // Keeps the object that owns the string alive, and so the string valid.
+    _owner: Arc<dyn Any + Send + Sync>,
+    data: *const str,
+}
+
+// Safety: the data is only ever read, and belongs to `_owner`, which is `Send + Sync`.
+unsafe impl Send for BorrowedString {}
+unsafe impl Sync for BorrowedString {}
+
+impl BorrowedString {
+    /// Make a view of the string that `borrow` returns from the object.
+    pub fn new<T: Send + Sync + 'static>(
+        owner: Arc<T>,
+        borrow: impl for<'a> FnOnce(&'a T) -> &'a str,
+    ) -> Self {
+        let data: *const str = borrow(&owner);
+        BorrowedString {
+            _owner: owner,
+            data,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // Safety: the string is borrowed from `_owner`, which we keep alive.
+        unsafe { &*self.data }
+    }
+
+    /// The length of the string, in bytes of UTF-8.
+    pub fn byte_length(&self) -> u64 {
+        self.as_str().len() as u64
+    }
+
+    /// Copy the part of the string from byte `start` up to byte `end`, which must both lie on
+    /// character boundaries.
+    pub fn substring(&self, start: u64, end: u64) -> String {
+        let s = self.as_str();
+        let (start, end) = check_range(start, end, s.len());
+        match s.get(start..end) {
+            Some(part) => part.to_string(),
+            None => panic!(
+                "Invalid substring {}..{}: not on a character boundary",
+                start, end
+            ),
+        }
+    }
+
+    /// Copy the whole string.
+    pub fn contents(&self) -> String {
+        self.as_str().to_string()
+    }
+}
+
+/// A view of bytes owned by an object, as returned by a `[Borrowed]` method.
+pub struct BorrowedBytes {
+    // Keeps the object that owns the bytes alive, and so the bytes valid.
+    _owner: Arc<dyn Any + Send + Sync>,
+    data: *const [u8],
+}
+
+// Safety: the data is only ever read, and belongs to `_owner`, which is `Send + Sync`.
+unsafe impl Send for BorrowedBytes {}
+unsafe impl Sync for BorrowedBytes {}
+
+impl BorrowedBytes {
+    /// Make a view of the bytes that `borrow` returns from the object.
+    pub fn new<T: Send + Sync + 'static>(
+        owner: Arc<T>,
+        borrow: impl for<'a> FnOnce(&'a T) -> &'a [u8],
+    ) -> Self {
+        let data: *const [u8] = borrow(&owner);
+        BorrowedBytes {
+            _owner: owner,
+            data,
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        // Safety: the bytes are borrowed from `_owner`, which we keep alive.
+        unsafe { &*self.data }
+    }
+
+    /// The number of bytes.
+    pub fn byte_length(&self) -> u64 {
+        self.as_slice().len() as u64
+    }
+
+    /// Copy the bytes from `start` up to `end`.
+    pub fn slice(&self, start: u64, end: u64) -> Vec<u8> {
+        let bytes = self.as_slice();
+        let (start, end) = check_range(start, end, bytes.len());
+        bytes[start..end].to_vec()
+    }
+
+    /// Copy all of the bytes.
+    pub fn contents(&self) -> Vec<u8> {
+        self.as_slice().to_vec()
+    }
+}
+
+// Panic, which the foreign-language code sees as an internal error, unless the range fits the data.
+fn check_range(start: u64, end: u64, len: usize) -> (usize, usize) {
+    if start > end || end > len as u64 {
+        panic!("Invalid range {}..{} of {} bytes", start, end, len);
+    }
+    (start as usize, end as usize)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    struct Document {
+        text: String,
+        data: Vec<u8>,
+    }
+
+    fn document() -> Arc<Document> {
+        Arc::new(Document {
+            text: "héllo world".into(),
+            data: vec![1, 2, 3, 4],
+        })
+    }
+
+    #[test]
+    fn test_borrowed_string() {
+        let doc = document();
+        let view = BorrowedString::new(Arc::clone(&doc), |doc| doc.text.as_str());
+        assert_eq!(Arc::strong_count(&doc), 2);
+        assert_eq!(view.byte_length(), 12);
+        assert_eq!(view.substring(0, 6), "héllo");
+        assert_eq!(view.substring(12, 12), "");
+        assert_eq!(view.contents(), "héllo world");
+        drop(doc);
+        // The view keeps the document alive on its own.
+        assert_eq!(view.substring(7, 12), "world");
+        assert!(catch_unwind(AssertUnwindSafe(|| view.substring(0, 2))).is_err());
+        assert!(catch_unwind(AssertUnwindSafe(|| view.substring(0, 13))).is_err());
+    }
+
+    #[test]
+    fn test_borrowed_bytes() {
+        let view = BorrowedBytes::new(document(), |doc| doc.data.as_slice());
+        assert_eq!(view.byte_length(), 4);
+        assert_eq!(view.slice(1, 3), vec![2, 3]);
+        assert_eq!(view.contents(), vec![1, 2, 3, 4]);
+        assert!(catch_unwind(AssertUnwindSafe(|| view.slice(3, 1))).is_err());
+    }
+}
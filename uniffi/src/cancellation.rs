@@ -0,0 +1,200 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Cancellation tokens for `[Cancellable]` functions and methods
+//!
+//! Foreign code can cancel a call to an `[Async]` function or method that it's waiting for,
+//! like when the coroutine or task that made it is cancelled. The future of the call is then
+//! dropped, and the call fails with the foreign language's usual cancellation error.
+//!
+//! Dropping a future stops it at whatever point it's waiting, which is fine for most of them,
+//! but not for one that has, say, a transaction to roll back, or that would rather return what
+//! it has so far. Marking it `[Cancellable]` in the UDL as well means that its Rust
+//! implementation takes a [`CancellationToken`] after the arguments that the UDL declares, and
+//! is left to carry on until it notices that the token has been cancelled:
+//!
+//! ```
+//! # struct Feed;
+//! # impl Feed {
+//! #     async fn next_chunk(&self) -> Option<Vec<u8>> { None }
+//! # }
+//! async fn download(feed: &Feed, token: uniffi::CancellationToken) -> Vec<u8> {
+//!     let mut data = Vec::new();
+//!     while !token.is_cancelled() {
+//!         match feed.next_chunk().await {
+//!             Some(chunk) => data.extend(chunk),
+//!             None => break,
+//!         }
+//!     }
+//!     // Whatever was downloaded before it was cancelled.
+//!     data
+//! }
+//! ```
+//!
+//! Whatever a `[Cancellable]` function returns, or the error that it fails with, is what the
+//! call completes with, although foreign code that has stopped waiting for it throws that away.
+
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+type CancelHook = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct TokenState {
+    cancelled: bool,
+    hooks: Vec<CancelHook>,
+    // The tasks waiting for `CancellationToken::cancelled`.
+    wakers: Vec<Waker>,
+}
+
+/// Tells a `[Cancellable]` function or method that foreign code has cancelled the call to it.
+/// The scaffolding creates one for each call, and cancels it when foreign code does.
+///
+/// Clones of a token all share the same state, so that one can be handed to work that the
+/// call starts elsewhere.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<Mutex<TokenState>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the call has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.lock().unwrap().cancelled
+    }
+
+    /// Cancel the token, which runs its cancel hooks and wakes the tasks waiting for
+    /// [`CancellationToken::cancelled`].
+    ///
+    /// Only the first call does anything. A hook that panics is logged and skipped, so that the
+    /// others still get to run.
+    pub fn cancel(&self) {
+        let (hooks, wakers) = {
+            let mut state = self.state.lock().unwrap();
+            if state.cancelled {
+                return;
+            }
+            state.cancelled = true;
+            (
+                std::mem::take(&mut state.hooks),
+                std::mem::take(&mut state.wakers),
+            )
+        };
+        for hook in hooks {
+            if catch_unwind(AssertUnwindSafe(hook)).is_err() {
+                log::error!("A cancellation token's cancel hook panicked");
+            }
+        }
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Register a hook to run when the token is cancelled, or straight away if it already is,
+    /// like to stop work that isn't a future.
+    pub fn on_cancel(&self, hook: impl FnOnce() + Send + 'static) {
+        {
+            let mut state = self.state.lock().unwrap();
+            if !state.cancelled {
+                state.hooks.push(Box::new(hook));
+                return;
+            }
+        }
+        hook()
+    }
+
+    /// A future that's ready once the token has been cancelled, for racing against the work
+    /// that the call is waiting for.
+    pub fn cancelled(&self) -> Cancelled {
+        Cancelled {
+            token: self.clone(),
+        }
+    }
+}
+
+impl std::fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// The future returned by [`CancellationToken::cancelled`].
+#[derive(Debug)]
+pub struct Cancelled {
+    token: CancellationToken,
+}
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.token.state.lock().unwrap();
+        if state.cancelled {
+            return Poll::Ready(());
+        }
+        if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+            state.wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::Wake;
+
+    struct CountWakes(AtomicUsize);
+
+    impl Wake for CountWakes {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_cancellation_tokens() {
+        let token = CancellationToken::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&runs);
+        token.on_cancel(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        token.on_cancel(|| panic!("cancelling failed"));
+
+        let wakes = Arc::new(CountWakes(AtomicUsize::new(0)));
+        let waker = Waker::from(Arc::clone(&wakes));
+        let mut cx = Context::from_waker(&waker);
+        let mut cancelled = token.cancelled();
+        assert!(Pin::new(&mut cancelled).poll(&mut cx).is_pending());
+        // Polling again doesn't register the same waker twice.
+        assert!(Pin::new(&mut cancelled).poll(&mut cx).is_pending());
+
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        clone.cancel();
+        token.cancel();
+        assert!(token.is_cancelled());
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+        assert!(Pin::new(&mut cancelled).poll(&mut cx).is_ready());
+
+        // Hooks registered once it's cancelled run straight away.
+        let counter = Arc::clone(&runs);
+        token.on_cancel(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+}
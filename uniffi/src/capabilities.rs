@@ -0,0 +1,167 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Capabilities of `[Versioned]` callback interfaces
+//!
+//! Rust calls the methods of a callback interface by their position in the UDL, so if the
+//! foreign-language implementation was generated from an older version of the interface,
+//! a method that's been added since could reach the wrong method, or none at all. When a
+//! callback interface has the `[Versioned]` attribute, the Rust proxy instead asks each
+//! foreign-language object which methods it has when it's passed to Rust, and calls them
+//! by name. The methods that the object doesn't have return their empty value, if they have
+//! one, like the default implementations of `[Optional]` methods do.
+//!
+//! Rust code can ask what the object supports, to degrade gracefully, through the
+//! [`VersionedCallback`] trait, which the Rust trait for the callback interface can extend:
+//!
+//! ```
+//! trait Downloader: uniffi::VersionedCallback + Send {
+//!     fn fetch(&self, url: String) -> String;
+//!     fn fetch_with_progress(&self, url: String, listener_id: u64) -> String;
+//! }
+//!
+//! fn download(downloader: &dyn Downloader, url: String) -> String {
+//!     if downloader.capabilities().implements("fetch_with_progress") {
+//!         downloader.fetch_with_progress(url, 1)
+//!     } else {
+//!         downloader.fetch(url)
+//!     }
+//! }
+//! ```
+
+use crate::{FfiConverter, ForeignCallbackHandle, RustBuffer, IDX_CALLBACK_CAPABILITIES};
+
+/// What the foreign-language implementation of a `[Versioned]` callback interface supports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallbackCapabilities {
+    current_version: u64,
+    // Both `None` if the bindings are too old to answer, when we carry on as before.
+    version: Option<u64>,
+    methods: Option<Vec<String>>,
+}
+
+impl CallbackCapabilities {
+    /// Ask the foreign-language object behind `handle` what it supports.
+    ///
+    /// `current_version` is the version of the interface that the scaffolding was generated
+    /// from, to compare the object's version with.
+    #[doc(hidden)]
+    pub fn query(handle: &ForeignCallbackHandle, current_version: u64) -> Self {
+        let vec = handle
+            .invoke(IDX_CALLBACK_CAPABILITIES, RustBuffer::new())
+            .destroy_into_vec();
+        Self::from_reply(current_version, &vec)
+    }
+
+    // The reply is the version of the interface, then the names of the methods, in the order
+    // of their method indexes. It's empty from bindings that don't know about `[Versioned]`.
+    fn from_reply(current_version: u64, mut buf: &[u8]) -> Self {
+        let unknown = CallbackCapabilities {
+            current_version,
+            version: None,
+            methods: None,
+        };
+        if buf.is_empty() {
+            return unknown;
+        }
+        let read = |buf: &mut &[u8]| -> anyhow::Result<(u64, Vec<String>)> {
+            let version = <u64 as FfiConverter>::try_read(buf)?;
+            let methods = <Vec<String> as FfiConverter>::try_read(buf)?;
+            Ok((version, methods))
+        };
+        match read(&mut buf) {
+            Ok((version, methods)) => CallbackCapabilities {
+                current_version,
+                version: Some(version),
+                methods: Some(methods),
+            },
+            Err(e) => {
+                log::error!("Invalid reply to a callback capabilities query: {}", e);
+                unknown
+            }
+        }
+    }
+
+    /// The version of the callback interface that the foreign-language bindings were
+    /// generated from, or `None` if they're too old to say.
+    pub fn version(&self) -> Option<u64> {
+        self.version
+    }
+
+    /// Whether the foreign-language object was generated from the same version of the
+    /// callback interface as the Rust scaffolding, and so has all of its methods.
+    pub fn is_current(&self) -> bool {
+        self.version == Some(self.current_version)
+    }
+
+    /// Whether the foreign-language object has the method with the given name, as it's
+    /// written in the UDL. This is `true` for every method if the bindings are too old to say.
+    pub fn implements(&self, method: &str) -> bool {
+        match &self.methods {
+            Some(methods) => methods.iter().any(|m| m == method),
+            None => true,
+        }
+    }
+
+    /// The index to call a method with, or `None` if the object doesn't have the method.
+    /// `position` is the index of the method in the scaffolding's version of the interface.
+    #[doc(hidden)]
+    pub fn method_index(&self, method: &str, position: u32) -> Option<u32> {
+        match &self.methods {
+            Some(methods) => methods
+                .iter()
+                .position(|m| m == method)
+                .map(|i| i as u32 + 1),
+            None => Some(position),
+        }
+    }
+}
+
+/// Implemented by the Rust proxies for `[Versioned]` callback interfaces, so that a Rust
+/// trait that extends it can ask the foreign-language object what it supports.
+pub trait VersionedCallback {
+    fn capabilities(&self) -> &CallbackCapabilities;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn reply(version: u64, methods: &[&str]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        <u64 as FfiConverter>::write(version, &mut buf);
+        <Vec<String> as FfiConverter>::write(
+            methods.iter().map(|m| m.to_string()).collect(),
+            &mut buf,
+        );
+        buf
+    }
+
+    #[test]
+    fn test_capabilities() {
+        // An older implementation, which is missing `on_progress` and has `on_done` first.
+        let caps = CallbackCapabilities::from_reply(2, &reply(1, &["on_done", "on_start"]));
+        assert_eq!(caps.version(), Some(1));
+        assert!(!caps.is_current());
+        assert!(caps.implements("on_start"));
+        assert!(!caps.implements("on_progress"));
+        assert_eq!(caps.method_index("on_start", 1), Some(2));
+        assert_eq!(caps.method_index("on_progress", 2), None);
+        assert_eq!(caps.method_index("on_done", 3), Some(1));
+
+        let caps = CallbackCapabilities::from_reply(2, &reply(2, &["on_start"]));
+        assert!(caps.is_current());
+    }
+
+    #[test]
+    fn test_capabilities_from_old_bindings() {
+        for reply in &[Vec::new(), vec![1, 2, 3]] {
+            let caps = CallbackCapabilities::from_reply(2, reply);
+            assert_eq!(caps.version(), None);
+            assert!(!caps.is_current());
+            assert!(caps.implements("anything"));
+            assert_eq!(caps.method_index("anything", 4), Some(4));
+        }
+    }
+}
@@ -0,0 +1,579 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Detecting deadlocks through callback interfaces
+//!
+//! An object with `[Self=ByMut]` methods is kept behind a `RwLock`, which its methods hold while
+//! they run. If one of them calls a callback interface, and the foreign-language implementation
+//! calls back into the same object, whether on the same thread or on another one that the
+//! calling thread waits for (say, by dispatching to the main thread synchronously), then that call
+//! waits for the lock forever, and the app hangs without saying why.
+//!
+//! So in debug builds, the scaffolding keeps track of the calls that each thread is in the middle
+//! of, from the foreign-language code into Rust and from Rust out to callback interfaces, and of
+//! the objects that they've locked. Rather than waiting for an object's lock when:
+//!
+//!  * the thread already holds it, further down its own stack;
+//!  * the threads holding it are themselves waiting, in a cycle, for locks that this thread holds;
+//!  * or the thread holding it is calling a callback interface, and hasn't let go of it after
+//!    [`deadlock_timeout`], which is most likely the callback waiting for this thread,
+//!
+//! the call panics with a message that shows what both sides are in the middle of, like
+//!
+//! ```text
+//! Deadlock: `Counter.increment` on thread 'main' is waiting for a lock on a `Counter` that
+//! thread 'worker' holds in `Counter.update`, while calling out to `Listener.on_change`.
+//!   thread 'main': Counter.increment
+//!   thread 'worker': Counter.update -> Listener.on_change (foreign)
+//! ```
+//!
+//! and the bindings throw it as an error, as they do for any other panic. Release builds just
+//! take the lock.
+
+use std::ops::{Deref, DerefMut};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::Duration;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(debug_assertions)]
+use std::sync::TryLockError;
+
+static DEADLOCK_TIMEOUT_MS: AtomicU64 = AtomicU64::new(2000);
+
+/// How long a call waits for an object that's locked by a thread which is calling out to a
+/// callback interface, before it reports a deadlock. It's two seconds unless
+/// [`set_deadlock_timeout`] changes it.
+pub fn deadlock_timeout() -> Duration {
+    Duration::from_millis(DEADLOCK_TIMEOUT_MS.load(Ordering::Relaxed))
+}
+
+/// Change how long a call waits for an object that's locked by a thread which is calling out to
+/// a callback interface. Components whose callbacks legitimately take a while make it longer.
+/// It has no effect in release builds, which don't look for deadlocks.
+pub fn set_deadlock_timeout(timeout: Duration) {
+    DEADLOCK_TIMEOUT_MS.store(timeout.as_millis() as u64, Ordering::Relaxed);
+}
+
+/// A call that the current thread is in the middle of, which it leaves when this is dropped.
+#[doc(hidden)]
+#[must_use]
+pub struct CallFrame(());
+
+impl Drop for CallFrame {
+    fn drop(&mut self) {
+        #[cfg(debug_assertions)]
+        tracking::leave_call();
+    }
+}
+
+/// Note that the current thread has been called from the foreign-language code, by the
+/// scaffolding function for `name`, which looks like `Object.method`.
+#[doc(hidden)]
+pub fn enter_rust_call(name: &'static str) -> CallFrame {
+    #[cfg(debug_assertions)]
+    tracking::enter_call(name, false);
+    #[cfg(not(debug_assertions))]
+    let _ = name;
+    CallFrame(())
+}
+
+/// Note that the current thread is calling out to the foreign-language implementation of a
+/// callback interface method, `name`, which looks like `Interface.method`.
+#[doc(hidden)]
+pub fn enter_foreign_call(name: &'static str) -> CallFrame {
+    #[cfg(debug_assertions)]
+    tracking::enter_call(name, true);
+    #[cfg(not(debug_assertions))]
+    let _ = name;
+    CallFrame(())
+}
+
+/// A lock on an object, for a method that takes `&self`.
+#[doc(hidden)]
+pub struct ObjectReadGuard<'a, T> {
+    guard: RwLockReadGuard<'a, T>,
+    #[cfg(debug_assertions)]
+    _held: tracking::HeldLock,
+}
+
+impl<'a, T> Deref for ObjectReadGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+/// A lock on an object, for a method that takes `&mut self`.
+#[doc(hidden)]
+pub struct ObjectWriteGuard<'a, T> {
+    guard: RwLockWriteGuard<'a, T>,
+    #[cfg(debug_assertions)]
+    _held: tracking::HeldLock,
+}
+
+impl<'a, T> Deref for ObjectWriteGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<'a, T> DerefMut for ObjectWriteGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+#[cfg(debug_assertions)]
+fn try_lock<G>(result: Result<G, TryLockError<G>>) -> Option<G> {
+    match result {
+        Ok(guard) => Some(guard),
+        Err(TryLockError::WouldBlock) => None,
+        Err(TryLockError::Poisoned(_)) => panic!("The object's lock is poisoned"),
+    }
+}
+
+/// Lock an object of type `object` to call one of its `&self` methods.
+#[doc(hidden)]
+pub fn read_object<'a, T>(lock: &'a RwLock<T>, object: &'static str) -> ObjectReadGuard<'a, T> {
+    #[cfg(debug_assertions)]
+    {
+        let id = lock as *const RwLock<T> as usize;
+        let (guard, held) = tracking::acquire(id, object, false, || try_lock(lock.try_read()));
+        ObjectReadGuard { guard, _held: held }
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        let _ = object;
+        ObjectReadGuard {
+            guard: lock.read().unwrap(),
+        }
+    }
+}
+
+/// Lock an object of type `object` to call one of its `&mut self` methods.
+#[doc(hidden)]
+pub fn write_object<'a, T>(lock: &'a RwLock<T>, object: &'static str) -> ObjectWriteGuard<'a, T> {
+    #[cfg(debug_assertions)]
+    {
+        let id = lock as *const RwLock<T> as usize;
+        let (guard, held) = tracking::acquire(id, object, true, || try_lock(lock.try_write()));
+        ObjectWriteGuard { guard, _held: held }
+    }
+    #[cfg(not(debug_assertions))]
+    {
+        let _ = object;
+        ObjectWriteGuard {
+            guard: lock.write().unwrap(),
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+mod tracking {
+    use std::collections::{HashMap, HashSet};
+    use std::sync::{Mutex, MutexGuard};
+    use std::thread::{self, ThreadId};
+    use std::time::{Duration, Instant};
+
+    use lazy_static::lazy_static;
+
+    // How often a call that's waiting for a lock checks whether it's deadlocked.
+    const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+    struct Frame {
+        name: &'static str,
+        foreign: bool,
+    }
+
+    struct Holder {
+        thread: ThreadId,
+        call: &'static str,
+        exclusive: bool,
+    }
+
+    // What a thread is in the middle of.
+    struct ThreadState {
+        name: String,
+        stack: Vec<Frame>,
+        waiting_for: Option<usize>,
+    }
+
+    impl ThreadState {
+        fn new() -> Self {
+            let thread = thread::current();
+            Self {
+                name: thread
+                    .name()
+                    .map(String::from)
+                    .unwrap_or_else(|| format!("{:?}", thread.id())),
+                stack: Vec::new(),
+                waiting_for: None,
+            }
+        }
+
+        fn describe(&self) -> String {
+            let frames: Vec<String> = self
+                .stack
+                .iter()
+                .map(|frame| {
+                    if frame.foreign {
+                        format!("{} (foreign)", frame.name)
+                    } else {
+                        frame.name.to_string()
+                    }
+                })
+                .collect();
+            format!("  thread '{}': {}", self.name, frames.join(" -> "))
+        }
+
+        fn current_call(&self) -> &'static str {
+            self.stack.last().map(|frame| frame.name).unwrap_or("?")
+        }
+
+        // The callback interface method that the thread is calling out to, if it is.
+        fn foreign_call(&self) -> Option<&'static str> {
+            self.stack
+                .iter()
+                .rev()
+                .find(|frame| frame.foreign)
+                .map(|frame| frame.name)
+        }
+    }
+
+    #[derive(Default)]
+    struct State {
+        threads: HashMap<ThreadId, ThreadState>,
+        // The threads that hold each lock, by its address.
+        locks: HashMap<usize, Vec<Holder>>,
+    }
+
+    impl State {
+        fn thread(&mut self, id: ThreadId) -> &mut ThreadState {
+            self.threads.entry(id).or_insert_with(ThreadState::new)
+        }
+
+        // Forget about a thread once it's out of its calls and isn't waiting for anything.
+        fn tidy(&mut self, id: ThreadId) {
+            let idle = match self.threads.get(&id) {
+                Some(state) => state.stack.is_empty() && state.waiting_for.is_none(),
+                None => false,
+            };
+            if idle {
+                self.threads.remove(&id);
+            }
+        }
+
+        fn holders(&self, id: usize) -> impl Iterator<Item = &Holder> {
+            self.locks.get(&id).into_iter().flatten()
+        }
+
+        fn describe(&self, thread: ThreadId) -> String {
+            match self.threads.get(&thread) {
+                Some(state) => state.describe(),
+                None => format!("  thread {:?}: ?", thread),
+            }
+        }
+
+        // The threads that hold lock `id`, and are waiting for the locks that `me` holds, through
+        // the locks that they're waiting for in turn, if there are any.
+        fn find_cycle(&self, me: ThreadId, id: usize) -> Option<Vec<ThreadId>> {
+            let mut path = Vec::new();
+            let mut seen = HashSet::new();
+            if self.visit(me, id, &mut path, &mut seen) {
+                Some(path)
+            } else {
+                None
+            }
+        }
+
+        fn visit(
+            &self,
+            me: ThreadId,
+            id: usize,
+            path: &mut Vec<ThreadId>,
+            seen: &mut HashSet<ThreadId>,
+        ) -> bool {
+            for holder in self.holders(id) {
+                if holder.thread == me {
+                    return true;
+                }
+                if !seen.insert(holder.thread) {
+                    continue;
+                }
+                path.push(holder.thread);
+                let waiting_for = self
+                    .threads
+                    .get(&holder.thread)
+                    .and_then(|state| state.waiting_for);
+                if let Some(next) = waiting_for {
+                    if self.visit(me, next, path, seen) {
+                        return true;
+                    }
+                }
+                path.pop();
+            }
+            false
+        }
+
+        // Why waiting any longer for lock `id` would deadlock, if it would.
+        fn diagnose(
+            &self,
+            me: ThreadId,
+            id: usize,
+            object: &'static str,
+            exclusive: bool,
+            waited: Duration,
+        ) -> Option<String> {
+            let mine = &self.threads[&me];
+            if let Some(holder) = self
+                .holders(id)
+                .find(|holder| holder.thread == me && (exclusive || holder.exclusive))
+            {
+                return Some(format!(
+                    "Deadlock: `{}` on thread '{}' is waiting for a lock on a `{}` that the same thread holds in `{}`.\n{}",
+                    mine.current_call(),
+                    mine.name,
+                    object,
+                    holder.call,
+                    mine.describe()
+                ));
+            }
+            if let Some(cycle) = self.find_cycle(me, id) {
+                let mut report = format!(
+                    "Deadlock: `{}` on thread '{}' is waiting for a lock on a `{}`, and the threads holding the locks that it needs are waiting for each other.\n{}",
+                    mine.current_call(),
+                    mine.name,
+                    object,
+                    mine.describe()
+                );
+                for thread in cycle {
+                    report.push('\n');
+                    report.push_str(&self.describe(thread));
+                }
+                return Some(report);
+            }
+            if waited < super::deadlock_timeout() {
+                return None;
+            }
+            for holder in self.holders(id) {
+                let theirs = match self.threads.get(&holder.thread) {
+                    Some(theirs) => theirs,
+                    None => continue,
+                };
+                if let Some(callback) = theirs.foreign_call() {
+                    return Some(format!(
+                        "Deadlock: `{}` on thread '{}' is waiting for a lock on a `{}` that thread '{}' holds in `{}`, while calling out to `{}`.\n{}\n{}",
+                        mine.current_call(),
+                        mine.name,
+                        object,
+                        theirs.name,
+                        holder.call,
+                        callback,
+                        mine.describe(),
+                        theirs.describe()
+                    ));
+                }
+            }
+            None
+        }
+    }
+
+    lazy_static! {
+        static ref STATE: Mutex<State> = Mutex::new(State::default());
+    }
+
+    fn state() -> MutexGuard<'static, State> {
+        // The deadlock reports are panics, which happen while nothing is halfway through
+        // changing the state, so it's fine to carry on using it after one.
+        STATE.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub(super) fn enter_call(name: &'static str, foreign: bool) {
+        let me = thread::current().id();
+        state().thread(me).stack.push(Frame { name, foreign });
+    }
+
+    pub(super) fn leave_call() {
+        let me = thread::current().id();
+        let mut state = state();
+        state.thread(me).stack.pop();
+        state.tidy(me);
+    }
+
+    // Marks a lock as held by the thread that took it, until it's dropped.
+    pub(super) struct HeldLock {
+        id: usize,
+        thread: ThreadId,
+    }
+
+    impl Drop for HeldLock {
+        fn drop(&mut self) {
+            let mut state = state();
+            let empty = match state.locks.get_mut(&self.id) {
+                Some(holders) => {
+                    if let Some(index) = holders.iter().position(|h| h.thread == self.thread) {
+                        holders.remove(index);
+                    }
+                    holders.is_empty()
+                }
+                None => false,
+            };
+            if empty {
+                state.locks.remove(&self.id);
+            }
+        }
+    }
+
+    // Take the lock at address `id` with `try_lock`, waiting until it's free, or until it
+    // looks like it never will be.
+    pub(super) fn acquire<G>(
+        id: usize,
+        object: &'static str,
+        exclusive: bool,
+        try_lock: impl Fn() -> Option<G>,
+    ) -> (G, HeldLock) {
+        let me = thread::current().id();
+        let started = Instant::now();
+        loop {
+            let mut state = state();
+            if let Some(guard) = try_lock() {
+                let call = state.thread(me).current_call();
+                state.thread(me).waiting_for = None;
+                state.tidy(me);
+                state.locks.entry(id).or_default().push(Holder {
+                    thread: me,
+                    call,
+                    exclusive,
+                });
+                return (guard, HeldLock { id, thread: me });
+            }
+            state.thread(me).waiting_for = Some(id);
+            if let Some(report) = state.diagnose(me, id, object, exclusive, started.elapsed()) {
+                state.thread(me).waiting_for = None;
+                state.tidy(me);
+                drop(state);
+                panic!("{}", report);
+            }
+            drop(state);
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+#[cfg(all(test, debug_assertions))]
+mod test {
+    use super::*;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::sync::{mpsc, Arc, Barrier};
+    use std::thread;
+
+    fn panic_message(result: std::thread::Result<()>) -> String {
+        let err = result.unwrap_err();
+        match err.downcast::<String>() {
+            Ok(message) => *message,
+            Err(_) => panic!("unexpected panic payload"),
+        }
+    }
+
+    #[test]
+    fn test_reentrant_call_on_same_thread() {
+        let counter = RwLock::new(0);
+        let _update = enter_rust_call("Counter.update");
+        let mut guard = write_object(&counter, "Counter");
+        *guard += 1;
+        let _callback = enter_foreign_call("Listener.on_change");
+        let _increment = enter_rust_call("Counter.increment");
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let _ = read_object(&counter, "Counter");
+        }));
+        let message = panic_message(result);
+        assert!(message.starts_with("Deadlock: `Counter.increment` on thread '"));
+        assert!(message.contains(
+            "is waiting for a lock on a `Counter` that the same thread holds in `Counter.update`."
+        ));
+        assert!(message
+            .ends_with("': Counter.update -> Listener.on_change (foreign) -> Counter.increment"));
+    }
+
+    #[test]
+    fn test_shared_locks_on_same_thread() {
+        let counter = RwLock::new(0);
+        let _get = enter_rust_call("Counter.get");
+        let first = read_object(&counter, "Counter");
+        let _callback = enter_foreign_call("Listener.on_change");
+        let _get_again = enter_rust_call("Counter.get");
+        let second = read_object(&counter, "Counter");
+        assert_eq!(*first + *second, 0);
+        drop(first);
+        drop(second);
+        *write_object(&counter, "Counter") += 1;
+    }
+
+    #[test]
+    fn test_waiting_for_a_thread_thats_calling_out() {
+        set_deadlock_timeout(Duration::from_millis(100));
+        let counter = Arc::new(RwLock::new(0));
+        let (locked, wait_for_lock) = mpsc::channel();
+        let (done, wait_for_done) = mpsc::channel::<()>();
+        let worker = thread::Builder::new()
+            .name("worker".into())
+            .spawn({
+                let counter = Arc::clone(&counter);
+                move || {
+                    let _update = enter_rust_call("Counter.update");
+                    let _guard = write_object(&counter, "Counter");
+                    let _callback = enter_foreign_call("Listener.on_change");
+                    locked.send(()).unwrap();
+                    wait_for_done.recv().unwrap();
+                }
+            })
+            .unwrap();
+        wait_for_lock.recv().unwrap();
+        let _increment = enter_rust_call("Counter.increment");
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let _ = write_object(&counter, "Counter");
+        }));
+        done.send(()).unwrap();
+        worker.join().unwrap();
+        let message = panic_message(result);
+        assert!(message.contains(
+            "is waiting for a lock on a `Counter` that thread 'worker' holds in `Counter.update`, while calling out to `Listener.on_change`."
+        ));
+        assert!(message
+            .ends_with("\n  thread 'worker': Counter.update -> Listener.on_change (foreign)"));
+    }
+
+    #[test]
+    fn test_lock_order_inversion() {
+        // Each thread only reads the first object, so that the one that reports the deadlock
+        // doesn't poison it for the other.
+        let first = Arc::new(RwLock::new(0));
+        let second = Arc::new(RwLock::new(0));
+        let barrier = Arc::new(Barrier::new(2));
+        let spawn = |name: &'static str, a: Arc<RwLock<i32>>, b: Arc<RwLock<i32>>| {
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                catch_unwind(AssertUnwindSafe(|| {
+                    let _call = enter_rust_call(name);
+                    let _a = read_object(&a, "Counter");
+                    barrier.wait();
+                    let _b = write_object(&b, "Counter");
+                }))
+            })
+        };
+        let one = spawn("Counter.merge", Arc::clone(&first), Arc::clone(&second));
+        let two = spawn("Counter.merge", second, first);
+        let results = vec![one.join().unwrap(), two.join().unwrap()];
+        let failures: Vec<String> = results
+            .into_iter()
+            .filter(|result| result.is_err())
+            .map(panic_message)
+            .collect();
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].contains("are waiting for each other"));
+    }
+}
@@ -0,0 +1,172 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Events emitted by `[Events]` objects
+//!
+//! An interface marked `[Events=DownloadEvent]` in the UDL gets an `add_listener` method,
+//! which registers a foreign implementation of the generated `DownloaderListener` callback
+//! interface and returns an id for it, and a `remove_listener` method, which takes that id.
+//! The Rust struct keeps its listeners in a [`Listeners`] and hands them out through the
+//! [`EventSource`] trait:
+//!
+//! ```
+//! # #[derive(Clone)]
+//! # enum DownloadEvent { Progress { bytes: u64 }, Finished }
+//! struct Downloader {
+//!     listeners: uniffi::Listeners<DownloadEvent>,
+//! }
+//!
+//! impl uniffi::EventSource for Downloader {
+//!     type Event = DownloadEvent;
+//!     fn listeners(&self) -> &uniffi::Listeners<DownloadEvent> {
+//!         &self.listeners
+//!     }
+//! }
+//!
+//! impl Downloader {
+//!     fn received(&self, bytes: u64) {
+//!         self.listeners.emit(DownloadEvent::Progress { bytes });
+//!     }
+//! }
+//! ```
+//!
+//! Each listener then gets its own copy of every event that's emitted after it was added.
+
+use std::sync::{Arc, Mutex};
+
+type Listener<E> = Arc<dyn Fn(E) + Send + Sync>;
+
+/// The listeners registered with an `[Events]` object.
+pub struct Listeners<E> {
+    inner: Mutex<ListenerList<E>>,
+}
+
+struct ListenerList<E> {
+    next_id: u64,
+    listeners: Vec<(u64, Listener<E>)>,
+}
+
+impl<E: Clone> Listeners<E> {
+    pub fn new() -> Self {
+        Listeners {
+            inner: Mutex::new(ListenerList {
+                next_id: 1,
+                listeners: Vec::new(),
+            }),
+        }
+    }
+
+    /// Register a listener, and return the id that removes it again.
+    pub fn add(&self, listener: impl Fn(E) + Send + Sync + 'static) -> u64 {
+        let mut inner = self.inner.lock().unwrap();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.listeners.push((id, Arc::new(listener)));
+        id
+    }
+
+    /// Unregister the listener with the given id. Ids that aren't registered are ignored, so
+    /// that removing a listener twice is harmless.
+    pub fn remove(&self, id: u64) {
+        self.inner
+            .lock()
+            .unwrap()
+            .listeners
+            .retain(|(listener_id, _)| *listener_id != id);
+    }
+
+    /// The number of listeners that are registered.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().listeners.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Give each listener the event.
+    ///
+    /// The listeners are called on the current thread, in the order they were added, and
+    /// without holding any lock, so they can add or remove listeners themselves.
+    pub fn emit(&self, event: E) {
+        let listeners: Vec<Listener<E>> = self
+            .inner
+            .lock()
+            .unwrap()
+            .listeners
+            .iter()
+            .map(|(_, listener)| Arc::clone(listener))
+            .collect();
+        for listener in listeners {
+            listener(event.clone());
+        }
+    }
+}
+
+impl<E: Clone> Default for Listeners<E> {
+    fn default() -> Self {
+        Listeners::new()
+    }
+}
+
+impl<E> std::fmt::Debug for Listeners<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Listeners")
+            .field("len", &self.inner.lock().unwrap().listeners.len())
+            .finish()
+    }
+}
+
+/// Implemented by the Rust structs of `[Events]` objects, to give the generated `add_listener`
+/// and `remove_listener` methods their [`Listeners`].
+pub trait EventSource {
+    /// The dictionary or enum that's sent to listeners, as named by `[Events=Event]`.
+    type Event: Clone;
+
+    fn listeners(&self) -> &Listeners<Self::Event>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_listeners() {
+        let listeners = Arc::new(Listeners::<u32>::new());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        listeners.emit(0);
+        let first = {
+            let seen = Arc::clone(&seen);
+            listeners.add(move |event| seen.lock().unwrap().push((1, event)))
+        };
+        let second = {
+            let seen = Arc::clone(&seen);
+            listeners.add(move |event| seen.lock().unwrap().push((2, event)))
+        };
+        assert_ne!(first, second);
+        assert_eq!(listeners.len(), 2);
+        listeners.emit(1);
+        listeners.remove(first);
+        listeners.remove(first);
+        listeners.emit(2);
+        assert_eq!(*seen.lock().unwrap(), vec![(1, 1), (2, 1), (2, 2)]);
+
+        // A listener can remove itself while it's being given an event.
+        let weak = Arc::downgrade(&listeners);
+        let id = Arc::new(Mutex::new(0));
+        listeners.remove(second);
+        let third = {
+            let (seen, id) = (Arc::clone(&seen), Arc::clone(&id));
+            listeners.add(move |event| {
+                seen.lock().unwrap().push((3, event));
+                weak.upgrade().unwrap().remove(*id.lock().unwrap());
+            })
+        };
+        *id.lock().unwrap() = third;
+        listeners.emit(3);
+        listeners.emit(4);
+        assert_eq!(seen.lock().unwrap().last(), Some(&(3, 3)));
+        assert!(listeners.is_empty());
+    }
+}
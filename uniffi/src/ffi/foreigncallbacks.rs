@@ -112,9 +112,52 @@
 //! `ForeignCallback`. The caller of `ForeignCallback`, the `KeychainProxy` unpacks the returned buffer into the correct
 //! type and then returns to client code.
 //!
+//! When the `KeychainProxy` is dropped, it tells the foreign language to remove the object from
+//! the handlemap. Interfaces with the `[ReleaseCallbacks]` attribute also do this as soon as the
+//! last reference to the object that a callback was passed to is dropped, even if Rust is still
+//! holding on to the proxy, via [`collect_foreign_callbacks`], [`register_object_callbacks`] and
+//! [`release_object_callbacks`].
+//!
+//! ## Direct callbacks
+//!
+//! Packing arguments into a `RustBuffer` and unpacking them again costs more than the call itself
+//! for methods that only deal in numbers, which adds up for callbacks that are called very often.
+//! For callback interfaces with the `[Direct]` attribute, the foreign language also passes Rust a
+//! C function pointer for each such method, which takes the object handle followed by the method's
+//! arguments as they are, and returns its result the same way. These are kept in a
+//! [`ForeignMethodInternals`] per method, and called through [`ForeignCallbackHandle::invoke_direct`].
+//! Every other method, along with freeing the object, still goes through the `ForeignCallback`.
+//!
+//! ## Errors
+//!
+//! Methods declared with `[Throws=Error]` return a `Result` in Rust. When the foreign-language
+//! implementation throws the error that the method declares, the buffer that the method returns
+//! holds the error instead of its return value, and the first byte of the buffer says which of
+//! the two it holds, see [`callback_threw`]. The proxy then reads the error from the rest of the
+//! buffer, and returns it as the `Err` of the method's result. Other exceptions still aren't sent
+//! back to Rust, which panics rather than return something that the method doesn't declare.
+//!
+//! ## Ordered delivery
+//!
+//! Rust code that calls a callback from several threads at once can't say in which order the
+//! foreign language sees those calls. The proxy for a callback interface with the `[Ordered]`
+//! attribute instead puts each call on an [`OrderedDelivery`] queue, and returns straight away.
+//! The calls are then made one at a time, first in first out, by a thread that belongs to the
+//! proxy, so the foreign-language object sees them in the order they were made, all on the
+//! same thread.
+//!
 
 use super::RustBuffer;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use lazy_static::lazy_static;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::os::raw::c_void;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
 
 /// ForeignCallback is the Rust representation of a foreign language function.
 /// It is the basis for all callbacks interfaces. It is registered exactly once per callback interface,
@@ -132,6 +175,35 @@ pub type ForeignCallback =
 /// and it can be deleted from the handle map.
 pub const IDX_CALLBACK_FREE: u32 = 0;
 
+/// The method index used to ask the foreign-language object of a `[Versioned]` callback
+/// interface which methods it has, see [`crate::CallbackCapabilities`].
+pub const IDX_CALLBACK_CAPABILITIES: u32 = u32::MAX;
+
+/// The first byte of the buffer returned by a throwing method, when the method returned a value.
+pub const CALLBACK_SUCCESS: i8 = 0;
+
+/// The first byte of the buffer returned by a throwing method, when the method threw its error.
+pub const CALLBACK_ERROR: i8 = 1;
+
+/// Read the first byte of the buffer returned by a `[Throws]` method of a callback interface,
+/// and say whether the rest of it is the error that the method threw, rather than its return
+/// value.
+///
+/// The buffer is empty, or starts with something else, when the foreign-language implementation
+/// failed in some other way, so this panics with the `method` that failed.
+pub fn callback_threw(buf: &mut &[u8], method: &str) -> bool {
+    let threw = match buf.first().map(|&status| status as i8) {
+        Some(CALLBACK_SUCCESS) => false,
+        Some(CALLBACK_ERROR) => true,
+        _ => panic!(
+            "The foreign implementation of {} failed with an error that it doesn't declare",
+            method
+        ),
+    };
+    *buf = &buf[1..];
+    threw
+}
+
 // Overly-paranoid sanity checking to ensure that these types are
 // convertible between each-other. `transmute` actually should check this for
 // us too, but this helps document the invariants we rely on in this code.
@@ -182,3 +254,422 @@ impl ForeignCallbackInternals {
         unsafe { std::mem::transmute::<usize, Option<ForeignCallback>>(ptr_value) }
     }
 }
+
+/// Struct to hold the foreign function that implements a single method of a callback interface.
+///
+/// `F` is the `unsafe extern "C" fn` type of the method, which the scaffolding generates
+/// from its signature.
+pub struct ForeignMethodInternals<F> {
+    callback_ptr: AtomicUsize,
+    _callback: PhantomData<F>,
+}
+
+impl<F> ForeignMethodInternals<F> {
+    pub const fn new() -> Self {
+        ForeignMethodInternals {
+            callback_ptr: AtomicUsize::new(EMPTY_PTR),
+            _callback: PhantomData,
+        }
+    }
+}
+
+impl<F: Copy> ForeignMethodInternals<F> {
+    pub fn set_callback(&self, callback: F) {
+        // `F` is always a function pointer, but there's no way to say so as a trait bound.
+        assert_eq!(std::mem::size_of::<F>(), std::mem::size_of::<usize>());
+        let as_usize = unsafe { std::mem::transmute_copy::<F, usize>(&callback) };
+        let old_ptr = self.callback_ptr.compare_exchange(
+            EMPTY_PTR,
+            as_usize,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        );
+        if old_ptr != Ok(EMPTY_PTR) {
+            panic!("Bug: call set_callback multiple times. This is likely a uniffi bug")
+        }
+    }
+
+    pub fn get_callback(&self) -> Option<F> {
+        match self.callback_ptr.load(Ordering::SeqCst) {
+            EMPTY_PTR => None,
+            ptr_value => Some(unsafe { std::mem::transmute_copy::<usize, F>(&ptr_value) }),
+        }
+    }
+}
+
+/// A reference to a foreign-language object that implements a callback interface.
+///
+/// This is held by the generated Rust proxy for the callback interface, and tells the
+/// foreign language to forget about the object once it is dropped. It can also be released
+/// early, if the object that the callback was passed to is destroyed, after which any attempt
+/// to call a method on it will panic.
+pub struct ForeignCallbackHandle {
+    handle: u64,
+    internals: &'static ForeignCallbackInternals,
+    released: AtomicBool,
+}
+
+impl ForeignCallbackHandle {
+    /// Wrap a handle received from the foreign language.
+    ///
+    /// If this happens during a call to [`collect_foreign_callbacks`], the new handle is
+    /// also recorded there.
+    pub fn new(handle: u64, internals: &'static ForeignCallbackInternals) -> Arc<Self> {
+        let handle = Arc::new(ForeignCallbackHandle {
+            handle,
+            internals,
+            released: AtomicBool::new(false),
+        });
+        COLLECTED_CALLBACKS.with(|collected| {
+            if let Some(collected) = collected.borrow_mut().as_mut() {
+                collected.push(Arc::clone(&handle));
+            }
+        });
+        handle
+    }
+
+    /// Call a method on the foreign-language object.
+    pub fn invoke(&self, method: u32, args: RustBuffer) -> RustBuffer {
+        self.check_deliverable();
+        let callback = self.internals.get_callback().unwrap();
+        unsafe { callback(self.handle, method, args) }
+    }
+
+    /// Call a method on the foreign-language object through its own function pointer,
+    /// which `f` is given the object's handle to pass along.
+    pub fn invoke_direct<T>(&self, f: impl FnOnce(u64) -> T) -> T {
+        self.check_deliverable();
+        f(self.handle)
+    }
+
+    // Refuse to call into the foreign language once the object has been released, or once the
+    // foreign-language runtime has started to exit, see [`crate::on_shutdown`].
+    fn check_deliverable(&self) {
+        if self.released.load(Ordering::SeqCst) {
+            panic!("Callback interface used after the object it was passed to was destroyed");
+        }
+        if crate::is_shut_down() {
+            panic!(
+                "Callback interface not called, because the foreign-language runtime is exiting"
+            );
+        }
+    }
+
+    /// Tell the foreign language that Rust has finished with the object.
+    ///
+    /// It's safe to call this more than once, only the first call has any effect. Once the
+    /// library has been shut down, the foreign language has already let go of its objects, so
+    /// it isn't told.
+    pub fn release(&self) {
+        if !self.released.swap(true, Ordering::SeqCst) && !crate::is_shut_down() {
+            let callback = self.internals.get_callback().unwrap();
+            unsafe { callback(self.handle, IDX_CALLBACK_FREE, Default::default()) };
+        }
+    }
+}
+
+impl Drop for ForeignCallbackHandle {
+    fn drop(&mut self) {
+        self.release()
+    }
+}
+
+impl fmt::Debug for ForeignCallbackHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ForeignCallbackHandle")
+            .field("handle", &self.handle)
+            .field("released", &self.released)
+            .finish()
+    }
+}
+
+type QueuedCall = Box<dyn FnOnce() + Send>;
+
+/// The queue of calls to a foreign-language object with an `[Ordered]` callback interface.
+///
+/// The thread making the calls is only started by the first of them. Calls made before the
+/// queue is dropped are still delivered after it has been, and a call that panics is logged
+/// without stopping the later ones.
+pub struct OrderedDelivery {
+    name: &'static str,
+    queue: Mutex<Option<mpsc::Sender<QueuedCall>>>,
+}
+
+impl OrderedDelivery {
+    /// Make a queue for calls to an implementation of the named callback interface.
+    pub fn new(name: &'static str) -> Self {
+        OrderedDelivery {
+            name,
+            queue: Mutex::new(None),
+        }
+    }
+
+    /// Queue up a call, to be made after every call queued before it.
+    pub fn deliver(&self, call: impl FnOnce() + Send + 'static) {
+        // Holding the lock while sending is what puts calls from different threads in order.
+        let mut queue = self.queue.lock().unwrap();
+        let sender = queue.get_or_insert_with(|| start_delivery(self.name));
+        if sender.send(Box::new(call)).is_err() {
+            panic!("The delivery thread for {} has stopped", self.name);
+        }
+    }
+}
+
+impl fmt::Debug for OrderedDelivery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OrderedDelivery")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+fn start_delivery(name: &'static str) -> mpsc::Sender<QueuedCall> {
+    let (sender, receiver) = mpsc::channel::<QueuedCall>();
+    thread::Builder::new()
+        .name(format!("uniffi-{}", name))
+        .spawn(move || {
+            // This ends once the `OrderedDelivery` has been dropped and the queue is empty.
+            for call in receiver {
+                if catch_unwind(AssertUnwindSafe(call)).is_err() {
+                    log::error!("A call to {} panicked", name);
+                }
+            }
+        })
+        .expect("failed to start a delivery thread");
+    sender
+}
+
+thread_local! {
+    static COLLECTED_CALLBACKS: RefCell<Option<Vec<Arc<ForeignCallbackHandle>>>> = RefCell::new(None);
+}
+
+// The callbacks passed to an object's constructor, and a way to tell whether the object is
+// still alive. The object can have many handles, from methods that return `Arc<Self>` or
+// `[WeakRefs]` upgrades, so freeing one of them doesn't mean that the object is gone.
+struct ObjectCallbacks {
+    is_alive: Box<dyn Fn() -> bool + Send>,
+    handles: Vec<Arc<ForeignCallbackHandle>>,
+}
+
+lazy_static! {
+    // The callbacks to release when each object is destroyed, keyed by the object's pointer.
+    // Each entry holds a `Weak` reference to the object, so the pointer can't be reused by
+    // another object until the entry has been removed.
+    static ref OBJECT_CALLBACKS: Mutex<HashMap<usize, ObjectCallbacks>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Run `f`, returning its result along with every [`ForeignCallbackHandle`] created while it ran.
+///
+/// The scaffolding uses this to find the callbacks passed to the constructor of an interface
+/// with the `[ReleaseCallbacks]` attribute.
+pub fn collect_foreign_callbacks<T>(f: impl FnOnce() -> T) -> (T, Vec<Arc<ForeignCallbackHandle>>) {
+    let previous = COLLECTED_CALLBACKS.with(|collected| collected.replace(Some(Vec::new())));
+    let result = f();
+    let handles = COLLECTED_CALLBACKS
+        .with(|collected| collected.replace(previous))
+        .unwrap_or_default();
+    (result, handles)
+}
+
+/// Arrange for the given callbacks to be released when the last reference to the object at
+/// `ptr` is dropped.
+///
+/// Nothing is registered for a null pointer, which the scaffolding returns when construction fails.
+///
+/// # Safety
+///
+/// A non-null `ptr` must have come from `Arc::into_raw`, and the reference it stands for must
+/// still be held.
+pub unsafe fn register_object_callbacks<T: Send + Sync + 'static>(
+    ptr: *const T,
+    handles: Vec<Arc<ForeignCallbackHandle>>,
+) {
+    if ptr.is_null() || handles.is_empty() {
+        return;
+    }
+    // Borrow the reference that `ptr` stands for, without taking it over.
+    let obj = std::mem::ManuallyDrop::new(Arc::from_raw(ptr));
+    let weak = Arc::downgrade(&obj);
+    let mut released = Vec::new();
+    {
+        let mut objects = OBJECT_CALLBACKS.lock().unwrap();
+        // Tidy up after any objects whose last reference was dropped by the Rust code, rather
+        // than through [`release_object_callbacks`].
+        objects.retain(|_, callbacks| {
+            let alive = (callbacks.is_alive)();
+            if !alive {
+                released.append(&mut callbacks.handles);
+            }
+            alive
+        });
+        objects
+            .entry(ptr as usize)
+            .or_insert_with(|| ObjectCallbacks {
+                is_alive: Box::new(move || weak.strong_count() > 0),
+                handles: Vec::new(),
+            })
+            .handles
+            .extend(handles);
+    }
+    for handle in released {
+        handle.release();
+    }
+}
+
+/// Drop a reference to an object, which is being freed by the foreign language, and release the
+/// callbacks registered for it if that was the last one.
+pub fn release_object_callbacks<T: ?Sized>(obj: Arc<T>) {
+    let key = Arc::as_ptr(&obj) as *const () as usize;
+    drop(obj);
+    let released = {
+        let mut objects = OBJECT_CALLBACKS.lock().unwrap();
+        match objects.get(&key) {
+            Some(callbacks) if !(callbacks.is_alive)() => objects.remove(&key),
+            _ => None,
+        }
+    };
+    for handle in released.into_iter().flat_map(|callbacks| callbacks.handles) {
+        handle.release();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static INTERNALS: ForeignCallbackInternals = ForeignCallbackInternals::new();
+
+    thread_local! {
+        static FREED: RefCell<Vec<u64>> = RefCell::new(Vec::new());
+    }
+
+    unsafe extern "C" fn callback(handle: u64, method: u32, _args: RustBuffer) -> RustBuffer {
+        if method == IDX_CALLBACK_FREE {
+            FREED.with(|freed| freed.borrow_mut().push(handle));
+        }
+        RustBuffer::new()
+    }
+
+    fn freed() -> Vec<u64> {
+        FREED.with(|freed| freed.borrow().clone())
+    }
+
+    #[test]
+    fn test_release_with_object() {
+        INTERNALS.set_callback(callback);
+        let unowned = ForeignCallbackHandle::new(1, &INTERNALS);
+        let (owned, handles) =
+            collect_foreign_callbacks(|| ForeignCallbackHandle::new(2, &INTERNALS));
+        assert_eq!(handles.len(), 1);
+
+        let obj = Arc::into_raw(Arc::new(0u8));
+        unsafe { register_object_callbacks(obj, handles) };
+        assert!(freed().is_empty());
+
+        // Another handle to the same object, like one from a method returning `Arc<Self>`.
+        let other = unsafe {
+            Arc::increment_strong_count(obj);
+            Arc::from_raw(obj)
+        };
+        release_object_callbacks(other);
+        assert!(freed().is_empty());
+        release_object_callbacks(unsafe { Arc::from_raw(obj) });
+        assert_eq!(freed(), vec![2]);
+
+        // Dropping the proxy's reference doesn't free it a second time.
+        drop(owned);
+        assert_eq!(freed(), vec![2]);
+        drop(unowned);
+        assert_eq!(freed(), vec![2, 1]);
+    }
+
+    type DoubleCallback = unsafe extern "C" fn(handle: u64, value: u32) -> u32;
+
+    static DIRECT_INTERNALS: ForeignCallbackInternals = ForeignCallbackInternals::new();
+    static DOUBLE: ForeignMethodInternals<DoubleCallback> = ForeignMethodInternals::new();
+
+    unsafe extern "C" fn double(handle: u64, value: u32) -> u32 {
+        handle as u32 + value * 2
+    }
+
+    #[test]
+    fn test_direct_callback() {
+        assert!(DOUBLE.get_callback().is_none());
+        DIRECT_INTERNALS.set_callback(callback);
+        DOUBLE.set_callback(double);
+        let double = DOUBLE.get_callback().unwrap();
+
+        let handle = ForeignCallbackHandle::new(100, &DIRECT_INTERNALS);
+        let result = handle.invoke_direct(|handle| unsafe { double(handle, 21) });
+        assert_eq!(result, 142);
+
+        // Freeing the object still goes through the `ForeignCallback`.
+        drop(handle);
+        assert_eq!(freed(), vec![100]);
+    }
+
+    #[test]
+    fn test_ordered_delivery() {
+        let (sender, receiver) = mpsc::channel();
+        let delivery = Arc::new(OrderedDelivery::new("Listener"));
+        let threads: Vec<_> = (0..4)
+            .map(|t| {
+                let delivery = Arc::clone(&delivery);
+                let sender = sender.clone();
+                thread::spawn(move || {
+                    for i in 0..100 {
+                        let sender = sender.clone();
+                        delivery.deliver(move || {
+                            sender
+                                .send((t, i, thread::current().name().map(String::from)))
+                                .unwrap()
+                        });
+                    }
+                })
+            })
+            .collect();
+        for t in threads {
+            t.join().unwrap();
+        }
+        // A panicking call doesn't stop the ones after it, which are still delivered
+        // after the queue is dropped.
+        delivery.deliver(|| panic!("oops"));
+        let last = sender.clone();
+        delivery.deliver(move || last.send((4, 0, None)).unwrap());
+        drop(delivery);
+        drop(sender);
+
+        let calls: Vec<_> = receiver.iter().collect();
+        assert_eq!(calls.len(), 401);
+        assert_eq!(calls[400], (4, 0, None));
+        for (_, _, name) in &calls[..400] {
+            assert_eq!(name.as_deref(), Some("uniffi-Listener"));
+        }
+        // Each thread's calls arrive in the order that thread made them.
+        for t in 0..4 {
+            let order: Vec<_> = calls
+                .iter()
+                .filter(|(thread, _, _)| *thread == t)
+                .map(|(_, i, _)| *i)
+                .collect();
+            assert_eq!(order, (0..100).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_callback_threw() {
+        let returned = [CALLBACK_SUCCESS as u8, 42];
+        let mut buf = &returned[..];
+        assert!(!callback_threw(&mut buf, "Store.get"));
+        assert_eq!(buf, &[42]);
+
+        let threw = [CALLBACK_ERROR as u8, 0, 0, 0, 1];
+        let mut buf = &threw[..];
+        assert!(callback_threw(&mut buf, "Store.get"));
+        assert_eq!(buf, &[0, 0, 0, 1]);
+
+        let failed = catch_unwind(|| callback_threw(&mut &[][..], "Store.get"));
+        assert!(failed.is_err());
+    }
+}
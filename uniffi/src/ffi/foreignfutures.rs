@@ -0,0 +1,269 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Futures for the values that `[Async]` callback interface methods return.
+//!
+//! The method of the Rust trait returns a [`CallbackFuture`] straight away, while the
+//! foreign-language implementation carries on in a coroutine or a task:
+//!
+//! ```
+//! # use uniffi::CallbackFuture;
+//! trait Fetcher: Send {
+//!     fn fetch(&self, url: String) -> CallbackFuture<String>;
+//! }
+//! ```
+//!
+//! The scaffolding registers a completion for each call, and sends its handle to the foreign
+//! language at the end of the method's arguments. When the implementation finishes, the bindings
+//! pass the handle back to the component's `callback_future_complete` FFI function, with what
+//! the method would have returned if it weren't async, and the future is ready.
+//!
+//! The futures can be `.await`ed on any executor, or waited for with [`CallbackFuture::wait`]
+//! by code that doesn't have one. A future that's dropped before it's ready doesn't stop the
+//! foreign implementation, and what it returns is thrown away.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use bytes::Buf;
+use lazy_static::lazy_static;
+
+use crate::RustBuffer;
+
+/// The code that the foreign language completes a future with when the method finished, and
+/// the buffer holds what it returned.
+pub const CALLBACK_FUTURE_DONE: i8 = 0;
+/// The code that the foreign language completes a future with when the method failed with an
+/// exception that it doesn't declare, and the buffer holds its message.
+pub const CALLBACK_FUTURE_FAILED: i8 = 1;
+
+// What a completion has been given, and the task to wake once it has.
+#[derive(Default)]
+struct CompletionState {
+    result: Option<(i8, Vec<u8>)>,
+    waker: Option<Waker>,
+}
+
+#[derive(Default)]
+struct Completion {
+    state: Mutex<CompletionState>,
+    ready: Condvar,
+}
+
+impl Completion {
+    fn complete(&self, code: i8, bytes: Vec<u8>) {
+        let mut state = self.state.lock().unwrap();
+        state.result = Some((code, bytes));
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+        self.ready.notify_all();
+    }
+}
+
+lazy_static! {
+    // The completions that the foreign language hasn't completed yet, by their handle.
+    static ref PENDING: Mutex<HashMap<u64, Arc<Completion>>> = Mutex::new(HashMap::new());
+}
+
+static NEXT_HANDLE: AtomicU64 = AtomicU64::new(1);
+
+type Lift<T> = Box<dyn FnOnce(Vec<u8>) -> T + Send>;
+
+/// The value that an `[Async]` callback interface method will return, once the foreign-language
+/// implementation has finished.
+pub struct CallbackFuture<T> {
+    inner: FutureInner<T>,
+}
+
+enum FutureInner<T> {
+    Ready(Option<T>),
+    Pending {
+        method: &'static str,
+        completion: Arc<Completion>,
+        lift: Option<Lift<T>>,
+    },
+}
+
+// The value is only ever moved out, never pinned.
+impl<T> Unpin for CallbackFuture<T> {}
+
+impl<T> CallbackFuture<T> {
+    /// A future that's ready already.
+    pub fn ready(value: T) -> Self {
+        Self {
+            inner: FutureInner::Ready(Some(value)),
+        }
+    }
+
+    /// Register a future for a call to `method`, and return the handle that completes it.
+    ///
+    /// `lift` turns the bytes that the foreign language completes it with into the value.
+    #[doc(hidden)]
+    pub fn pending(
+        method: &'static str,
+        lift: impl FnOnce(Vec<u8>) -> T + Send + 'static,
+    ) -> (u64, Self) {
+        let handle = NEXT_HANDLE.fetch_add(1, Ordering::SeqCst);
+        let completion = Arc::new(Completion::default());
+        PENDING
+            .lock()
+            .unwrap()
+            .insert(handle, Arc::clone(&completion));
+        let future = Self {
+            inner: FutureInner::Pending {
+                method,
+                completion,
+                lift: Some(Box::new(lift)),
+            },
+        };
+        (handle, future)
+    }
+
+    /// Block the current thread until the foreign implementation has finished, and return the
+    /// value.
+    pub fn wait(self) -> T {
+        match self.inner {
+            FutureInner::Ready(value) => value.expect("CallbackFuture polled after it was ready"),
+            FutureInner::Pending {
+                method,
+                completion,
+                lift,
+            } => {
+                let mut state = completion.state.lock().unwrap();
+                while state.result.is_none() {
+                    state = completion.ready.wait(state).unwrap();
+                }
+                let (code, bytes) = state.result.take().unwrap();
+                finish(
+                    method,
+                    code,
+                    bytes,
+                    lift.expect("CallbackFuture polled after it was ready"),
+                )
+            }
+        }
+    }
+}
+
+impl<T> Future for CallbackFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        match &mut self.get_mut().inner {
+            FutureInner::Ready(value) => Poll::Ready(
+                value
+                    .take()
+                    .expect("CallbackFuture polled after it was ready"),
+            ),
+            FutureInner::Pending {
+                method,
+                completion,
+                lift,
+            } => {
+                let mut state = completion.state.lock().unwrap();
+                match state.result.take() {
+                    Some((code, bytes)) => {
+                        drop(state);
+                        let lift = lift
+                            .take()
+                            .expect("CallbackFuture polled after it was ready");
+                        Poll::Ready(finish(method, code, bytes, lift))
+                    }
+                    None => {
+                        state.waker = Some(cx.waker().clone());
+                        Poll::Pending
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for CallbackFuture<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.inner {
+            FutureInner::Ready(_) => write!(f, "CallbackFuture(ready)"),
+            FutureInner::Pending { method, .. } => write!(f, "CallbackFuture({})", method),
+        }
+    }
+}
+
+fn finish<T>(method: &str, code: i8, bytes: Vec<u8>, lift: Lift<T>) -> T {
+    if code != CALLBACK_FUTURE_DONE {
+        let mut buf = bytes.as_slice();
+        let message = if buf.remaining() >= 4 {
+            let len = buf.get_i32() as usize;
+            String::from_utf8_lossy(&buf[..len.min(buf.len())]).into_owned()
+        } else {
+            String::new()
+        };
+        panic!(
+            "The foreign implementation of {} failed with an error that it doesn't declare: {}",
+            method, message
+        );
+    }
+    lift(bytes)
+}
+
+/// Complete the future that the call with `handle` returned. The scaffolding calls this from
+/// the component's `callback_future_complete` FFI function.
+///
+/// Each future can only be completed once, so this panics if `handle` isn't pending.
+#[doc(hidden)]
+pub fn complete_callback_future(handle: u64, code: i8, result: RustBuffer) {
+    let bytes = result.destroy_into_vec();
+    let completion = PENDING.lock().unwrap().remove(&handle);
+    match completion {
+        Some(completion) => completion.complete(code, bytes),
+        None => panic!(
+            "No callback future is waiting for handle {}, or it was already completed",
+            handle
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::FfiConverter;
+    use std::thread;
+
+    #[test]
+    fn test_callback_futures() {
+        assert_eq!(CallbackFuture::ready(3).wait(), 3);
+
+        let (handle, future) = CallbackFuture::pending("Fetcher.fetch", |bytes: Vec<u8>| {
+            String::try_read(&mut bytes.as_slice()).unwrap()
+        });
+        let completer = thread::spawn(move || {
+            let mut buf = Vec::new();
+            String::write("done".to_string(), &mut buf);
+            complete_callback_future(handle, CALLBACK_FUTURE_DONE, RustBuffer::from_vec(buf));
+        });
+        assert_eq!(future.wait(), "done");
+        completer.join().unwrap();
+
+        // Completing it again is a bug in the bindings.
+        let result = std::panic::catch_unwind(|| {
+            complete_callback_future(handle, CALLBACK_FUTURE_DONE, RustBuffer::new())
+        });
+        assert!(result.is_err());
+
+        let (handle, future) = CallbackFuture::pending("Fetcher.fetch", |_| ());
+        let mut buf = Vec::new();
+        String::write("boom".to_string(), &mut buf);
+        complete_callback_future(handle, CALLBACK_FUTURE_FAILED, RustBuffer::from_vec(buf));
+        let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || future.wait()))
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<String>().unwrap(),
+            "The foreign implementation of Fetcher.fetch failed with an error that it doesn't declare: boom"
+        );
+    }
+}
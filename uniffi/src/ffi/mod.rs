@@ -5,11 +5,15 @@
 pub mod ffidefault;
 pub mod foreignbytes;
 pub mod foreigncallbacks;
+pub mod foreignfutures;
 pub mod rustbuffer;
 pub mod rustcalls;
+pub mod rustfutures;
 
 use ffidefault::FfiDefault;
 pub use foreignbytes::*;
 pub use foreigncallbacks::*;
+pub use foreignfutures::*;
 pub use rustbuffer::*;
 pub use rustcalls::*;
+pub use rustfutures::*;
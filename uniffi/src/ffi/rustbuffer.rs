@@ -105,6 +105,24 @@ impl RustBuffer {
         self.len == 0
     }
 
+    /// View the contents of the buffer as a `&[u8]`, without taking ownership of it.
+    ///
+    /// This is for Rust code that calls a component through its FFI, and so has to copy the
+    /// bytes out of a buffer that the component allocated before giving it back to be freed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on an invalid struct in which the `len` field is negative, or which
+    /// has a null pointer but non-zero length.
+    pub fn as_slice(&self) -> &[u8] {
+        if self.data.is_null() {
+            assert!(self.len == 0, "null RustBuffer had non-zero length");
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(self.data, self.len()) }
+        }
+    }
+
     /// Creates a `RustBuffer` zero-filed to the requested size.
     ///
     /// The resulting vector will not be automatically dropped; you must
@@ -215,6 +233,16 @@ mod test {
         assert_eq!(rbuf.destroy_into_vec().as_slice(), &[0u8; 0]);
     }
 
+    #[test]
+    fn test_rustbuffer_as_slice() {
+        let rbuf = RustBuffer::from_vec(vec![1u8, 2, 3]);
+        assert_eq!(rbuf.as_slice(), &[1u8, 2, 3]);
+        rbuf.destroy();
+
+        let rbuf = unsafe { RustBuffer::from_raw_parts(std::ptr::null_mut(), 0, 0) };
+        assert_eq!(rbuf.as_slice(), &[0u8; 0]);
+    }
+
     #[test]
     fn test_rustbuffer_null_means_empty() {
         // This is how foreign-language code might cheaply indicate an empty buffer.
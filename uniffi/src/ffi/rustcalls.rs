@@ -11,7 +11,7 @@
 //!    - Adapting `Result<>` types into either a return value or an error
 
 use super::FfiDefault;
-use crate::{FfiConverter, RustBuffer, RustBufferFfiConverter};
+use crate::{FfiConverter, InvalidStringError, RustBuffer, RustBufferFfiConverter};
 use anyhow::Result;
 use std::mem::MaybeUninit;
 use std::panic;
@@ -45,11 +45,14 @@ use std::panic;
 ///  - `CALL_SUCCESS` (0) for successful calls
 ///  - `CALL_ERROR` (1) for calls that returned an `Err` value
 ///  - `CALL_PANIC` (2) for calls that panicked
+///  - `CALL_INVALID_STRING` (3) for calls that were passed a string that isn't valid utf8
 ///
 /// #### The `error_buf` field.
 ///
 /// - For `CALL_ERROR` this is a `RustBuffer` with the serialized error.  The consumer code is
 ///   responsible for freeing this `RustBuffer`.
+/// - For `CALL_PANIC` and `CALL_INVALID_STRING` this is a `RustBuffer` with the message, as a
+///   string, or an empty `RustBuffer` if we couldn't construct one.
 #[repr(C)]
 pub struct RustCallStatus {
     pub code: i8,
@@ -70,6 +73,27 @@ pub struct RustCallStatus {
 const CALL_SUCCESS: i8 = 0; // CALL_SUCCESS is set by the calling code
 const CALL_ERROR: i8 = 1;
 const CALL_PANIC: i8 = 2;
+const CALL_INVALID_STRING: i8 = 3;
+
+// What a call unwinds with when one of its arguments was a string that isn't valid utf8, so
+// that `make_call()` can report it as `CALL_INVALID_STRING` rather than as a panic.
+struct InvalidStringArgument(String);
+
+/// Give up on a call because its argument `name` couldn't be lifted.
+///
+/// The generated scaffolding calls this from inside `call_with_output()` or `call_with_result()`,
+/// for errors that the function doesn't declare that it throws. If the argument was, or contained,
+/// a string that isn't valid utf8, then the call status is `CALL_INVALID_STRING`, otherwise this
+/// panics and the call status is `CALL_PANIC`.
+pub fn lift_arg_failed(name: &str, err: anyhow::Error) -> ! {
+    match err.downcast::<InvalidStringError>() {
+        Ok(err) => panic::resume_unwind(Box::new(InvalidStringArgument(format!(
+            "Failed to convert arg '{}': {}",
+            name, err
+        )))),
+        Err(err) => panic!("Failed to convert arg '{}': {}", name, err),
+    }
+}
 
 // A trait for errors that can be thrown to the FFI code
 //
@@ -100,6 +124,22 @@ where
             }
             R::ffi_default()
         }
+        // An argument was a string that isn't valid utf8.
+        Err(cause) if cause.is::<InvalidStringArgument>() => {
+            out_status.code = CALL_INVALID_STRING;
+            if let Ok(arg) = cause.downcast::<InvalidStringArgument>() {
+                log::error!("Invalid string passed to rust code: {:?}", arg.0);
+                unsafe {
+                    // Unsafe because we're setting the `MaybeUninit` value, see above for safety
+                    // invariants.
+                    out_status
+                        .error_buf
+                        .as_mut_ptr()
+                        .write(String::lower(arg.0));
+                }
+            }
+            R::ffi_default()
+        }
         // Callback panicked
         Err(cause) => {
             out_status.code = CALL_PANIC;
@@ -141,6 +181,9 @@ where
 /// - If the function panics:
 ///     - `out_status.code` will be set to `CALL_PANIC`
 ///     - the return value is undefined
+/// - If an argument is a string that isn't valid utf8:
+///     - `out_status.code` will be set to `CALL_INVALID_STRING`
+///     - the return value is undefined
 pub fn call_with_output<F, R>(out_status: &mut RustCallStatus, callback: F) -> R
 where
     F: panic::UnwindSafe + FnOnce() -> R,
@@ -165,6 +208,9 @@ where
 /// - If the function panics:
 ///     - `out_status.code` will be set to `CALL_PANIC`
 ///     - the return value is undefined
+/// - If an argument is a string that isn't valid utf8:
+///     - `out_status.code` will be set to `CALL_INVALID_STRING`
+///     - the return value is undefined
 pub fn call_with_result<F, R>(out_status: &mut RustCallStatus, callback: F) -> R
 where
     F: panic::UnwindSafe + FnOnce() -> Result<R, RustBuffer>,
@@ -267,4 +313,32 @@ mod test {
             );
         }
     }
+
+    fn function_with_string(s: RustBuffer) -> u64 {
+        match String::try_lift(s) {
+            Ok(s) => s.len() as u64,
+            Err(err) => lift_arg_failed("s", err),
+        }
+    }
+
+    #[test]
+    fn test_call_with_invalid_string() {
+        let mut status = create_call_status();
+        let return_value = call_with_output(&mut status, || {
+            function_with_string(String::lower("héllo".to_owned()))
+        });
+        assert_eq!(status.code, CALL_SUCCESS);
+        assert_eq!(return_value, 6);
+
+        call_with_output(&mut status, || {
+            function_with_string(RustBuffer::from_vec(b"h\xe9llo".to_vec()))
+        });
+        assert_eq!(status.code, CALL_INVALID_STRING);
+        unsafe {
+            assert_eq!(
+                String::try_lift(status.error_buf.assume_init()).unwrap(),
+                "Failed to convert arg 's': invalid utf8 in string, after 1 valid bytes"
+            );
+        }
+    }
 }
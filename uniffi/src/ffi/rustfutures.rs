@@ -0,0 +1,452 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Driving the `async fn`s that implement `[Async]` functions and methods.
+//!
+//! The FFI function of an `[Async]` function lifts its arguments, starts the future that the
+//! `async fn` returns on an executor embedded in the library, and returns straight away. Once the
+//! future has finished, the executor passes what it returned to the completion function that the
+//! bindings registered, along with the handle that the call was made with, and the bindings resume
+//! the coroutine, task or `asyncio` future that's waiting for it.
+//!
+//! The executor polls the futures on a small pool of threads of its own, which don't belong to any
+//! async runtime. A future that needs one, like one that uses `tokio`'s sockets or timers, has to
+//! hand that work to a runtime that the component keeps itself, and wait for it there.
+//!
+//! The bindings cancel a call through the component's `uniffi_cancel` FFI function, with the
+//! handle that the call was made with. The executor drops the future of the call, and completes
+//! it as cancelled, unless the function is `[Cancellable]`, in which case it cancels the
+//! [`CancellationToken`] that the function was given, and waits for it to finish.
+//!
+//! The bindings of each component number their calls themselves, so several components built
+//! into the same library can make calls with the same handles. The scaffolding of each one keeps
+//! an [`AsyncCalls`] of its own, which holds the completion that its bindings registered, and
+//! tells its calls apart from those of the other components.
+
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Once};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread;
+
+use lazy_static::lazy_static;
+
+use crate::{CancellationToken, FfiConverter, RustBuffer};
+
+/// The foreign-language function that completes calls to `[Async]` functions and methods. The
+/// bindings register it with the component's `async_init` FFI function.
+pub type AsyncCompletion = unsafe extern "C" fn(handle: u64, code: i8, result: RustBuffer);
+
+/// The code that a call is completed with when it returned, and the buffer holds what it returned.
+pub const ASYNC_CALL_DONE: i8 = 0;
+/// The code that a call is completed with when it failed with the error that it declares, and the
+/// buffer holds the error.
+pub const ASYNC_CALL_ERROR: i8 = 1;
+/// The code that a call is completed with when it panicked, and the buffer holds the message.
+pub const ASYNC_CALL_PANIC: i8 = 2;
+/// The code that a call is completed with when it was cancelled before it finished, and the
+/// buffer is empty.
+pub const ASYNC_CALL_CANCELLED: i8 = 3;
+
+// How many threads poll the futures. They spend most of their time waiting to be woken, so
+// there's no need for one per core.
+const EXECUTOR_THREADS: usize = 4;
+
+static_assertions::assert_eq_size!(usize, AsyncCompletion);
+
+/// The calls to one component's `[Async]` functions and methods. The scaffolding keeps one of
+/// these in a static, and passes it to the functions below.
+#[doc(hidden)]
+pub struct AsyncCalls {
+    completion: AtomicUsize,
+}
+
+impl AsyncCalls {
+    pub const fn new() -> Self {
+        Self {
+            completion: AtomicUsize::new(0),
+        }
+    }
+
+    fn completion(&self) -> Option<AsyncCompletion> {
+        match self.completion.load(Ordering::SeqCst) {
+            0 => None,
+            ptr => Some(unsafe { std::mem::transmute::<usize, AsyncCompletion>(ptr) }),
+        }
+    }
+
+    // The key of the call with `handle` in `CALLS`. Each component's `AsyncCalls` lives in a
+    // static of its own, so its address tells them apart.
+    fn key(&'static self, handle: u64) -> (usize, u64) {
+        (self as *const Self as usize, handle)
+    }
+}
+
+impl Default for AsyncCalls {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Register the function that completes calls to the component's `[Async]` functions and
+/// methods. The scaffolding calls this from the component's `async_init` FFI function.
+///
+/// Registering it again replaces it, for bindings that load the library more than once.
+#[doc(hidden)]
+pub fn init_async_completion(calls: &'static AsyncCalls, completion: AsyncCompletion) {
+    calls
+        .completion
+        .store(completion as usize, Ordering::SeqCst);
+}
+
+type CallFuture = Pin<Box<dyn Future<Output = Result<RustBuffer, RustBuffer>> + Send>>;
+
+// A call whose future the executor is polling, until it's ready and the call completed.
+struct Call {
+    calls: &'static AsyncCalls,
+    handle: u64,
+    completion: AsyncCompletion,
+    future: Mutex<Option<CallFuture>>,
+    // Whether the call is waiting in the queue, so that waking it again doesn't queue it twice.
+    queued: AtomicBool,
+    token: CancellationToken,
+    // Whether cancelling the call drops its future, rather than leaving the future to notice
+    // that its token has been cancelled.
+    drop_on_cancel: bool,
+}
+
+impl Call {
+    fn poll(self: Arc<Self>) {
+        let mut slot = self.future.lock().unwrap();
+        let future = match slot.as_mut() {
+            Some(future) => future,
+            // It was woken again after it had finished.
+            None => return,
+        };
+        let waker = Waker::from(Arc::clone(&self));
+        let mut cx = Context::from_waker(&waker);
+        let polled = if self.drop_on_cancel && self.token.is_cancelled() {
+            None
+        } else {
+            Some(catch_unwind(AssertUnwindSafe(|| {
+                future.as_mut().poll(&mut cx)
+            })))
+        };
+        let (code, result) = match polled {
+            None => (ASYNC_CALL_CANCELLED, RustBuffer::new()),
+            Some(Ok(Poll::Pending)) => return,
+            Some(Ok(Poll::Ready(Ok(value)))) => (ASYNC_CALL_DONE, value),
+            Some(Ok(Poll::Ready(Err(error)))) => (ASYNC_CALL_ERROR, error),
+            Some(Err(cause)) => {
+                let message = if let Some(s) = cause.downcast_ref::<&'static str>() {
+                    (*s).to_string()
+                } else if let Some(s) = cause.downcast_ref::<String>() {
+                    s.clone()
+                } else {
+                    "Unknown panic!".to_string()
+                };
+                log::error!(
+                    "Caught a panic in an async call to rust code: {:?}",
+                    message
+                );
+                let mut buf = Vec::new();
+                String::write(message, &mut buf);
+                (ASYNC_CALL_PANIC, RustBuffer::from_vec(buf))
+            }
+        };
+        // Drop the future before completing the call, so that anything it holds on to is let go
+        // of by the time the foreign language carries on.
+        *slot = None;
+        drop(slot);
+        CALLS.lock().unwrap().remove(&self.calls.key(self.handle));
+        unsafe { (self.completion)(self.handle, code, result) }
+    }
+}
+
+impl Wake for Call {
+    fn wake(self: Arc<Self>) {
+        EXECUTOR.schedule(self)
+    }
+}
+
+struct Executor {
+    queue: Mutex<VecDeque<Arc<Call>>>,
+    ready: Condvar,
+    started: Once,
+}
+
+impl Executor {
+    fn schedule(&'static self, call: Arc<Call>) {
+        if call.queued.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        self.start();
+        self.queue.lock().unwrap().push_back(call);
+        self.ready.notify_one();
+    }
+
+    fn start(&'static self) {
+        self.started.call_once(|| {
+            for _ in 0..EXECUTOR_THREADS {
+                thread::Builder::new()
+                    .name("uniffi-async".into())
+                    .spawn(move || self.run())
+                    .expect("Failed to start a thread for async calls");
+            }
+        });
+    }
+
+    fn run(&self) {
+        loop {
+            let call = {
+                let mut queue = self.queue.lock().unwrap();
+                loop {
+                    match queue.pop_front() {
+                        Some(call) => break call,
+                        None => queue = self.ready.wait(queue).unwrap(),
+                    }
+                }
+            };
+            // Clear the flag before polling, so that a wake-up while it's being polled queues
+            // it again.
+            call.queued.store(false, Ordering::SeqCst);
+            call.poll();
+        }
+    }
+}
+
+lazy_static! {
+    static ref EXECUTOR: Executor = Executor {
+        queue: Mutex::new(VecDeque::new()),
+        ready: Condvar::new(),
+        started: Once::new(),
+    };
+    // The calls that haven't completed yet, by their component and handle, for cancelling them.
+    static ref CALLS: Mutex<HashMap<(usize, u64), Arc<Call>>> = Mutex::new(HashMap::new());
+}
+
+/// Start the threads that run calls to `[Async]` functions and methods, if they haven't been
+/// started yet. The scaffolding calls this when the bindings warm the library up, so that the
+/// first call doesn't have to wait for them.
+#[doc(hidden)]
+pub fn start_async_executor() {
+    lazy_static::initialize(&CALLS);
+    EXECUTOR.start();
+}
+
+/// Start the call with `handle` on the executor, completing it with what `future` returns: the
+/// lowered value, or the lowered error that the call declares. The scaffolding calls this from
+/// the FFI function of an `[Async]` function or method, once it has lifted the arguments.
+///
+/// Cancelling the call drops `future`. This panics if the bindings haven't registered their
+/// completion function with `calls`.
+#[doc(hidden)]
+pub fn spawn_async_call<F>(calls: &'static AsyncCalls, handle: u64, future: F)
+where
+    F: Future<Output = Result<RustBuffer, RustBuffer>> + Send + 'static,
+{
+    spawn(calls, handle, CancellationToken::new(), true, future)
+}
+
+/// Like [`spawn_async_call`], for a `[Cancellable]` function or method, whose future `start`
+/// returns, given the token that cancelling the call cancels. The future isn't dropped, and the
+/// call completes with what it returns.
+#[doc(hidden)]
+pub fn spawn_cancellable_async_call<S, F>(calls: &'static AsyncCalls, handle: u64, start: S)
+where
+    S: FnOnce(CancellationToken) -> F,
+    F: Future<Output = Result<RustBuffer, RustBuffer>> + Send + 'static,
+{
+    let token = CancellationToken::new();
+    let future = start(token.clone());
+    spawn(calls, handle, token, false, future)
+}
+
+fn spawn<F>(
+    calls: &'static AsyncCalls,
+    handle: u64,
+    token: CancellationToken,
+    drop_on_cancel: bool,
+    future: F,
+) where
+    F: Future<Output = Result<RustBuffer, RustBuffer>> + Send + 'static,
+{
+    let completion = calls
+        .completion()
+        .expect("The bindings must register a completion before calling [Async] functions");
+    let call = Arc::new(Call {
+        calls,
+        handle,
+        completion,
+        future: Mutex::new(Some(Box::pin(future))),
+        queued: AtomicBool::new(false),
+        token,
+        drop_on_cancel,
+    });
+    CALLS
+        .lock()
+        .unwrap()
+        .insert(calls.key(handle), Arc::clone(&call));
+    EXECUTOR.schedule(call);
+}
+
+/// Cancel the component's call with `handle`, if it hasn't completed yet. The scaffolding calls
+/// this from the component's `uniffi_cancel` FFI function.
+#[doc(hidden)]
+pub fn cancel_async_call(calls: &'static AsyncCalls, handle: u64) {
+    let call = CALLS.lock().unwrap().get(&calls.key(handle)).cloned();
+    if let Some(call) = call {
+        call.token.cancel();
+        // Poll the call again, which drops its future if that's how it's cancelled.
+        EXECUTOR.schedule(call);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    static CALLS_A: AsyncCalls = AsyncCalls::new();
+    static CALLS_B: AsyncCalls = AsyncCalls::new();
+
+    lazy_static! {
+        static ref COMPLETED: Mutex<HashMap<u64, (i8, Vec<u8>)>> = Mutex::new(HashMap::new());
+        static ref CHANGED: Condvar = Condvar::new();
+    }
+
+    unsafe extern "C" fn complete(handle: u64, code: i8, result: RustBuffer) {
+        let mut completed = COMPLETED.lock().unwrap();
+        completed.insert(handle, (code, result.destroy_into_vec()));
+        CHANGED.notify_all();
+    }
+
+    // `CALLS_B`'s completion marks the handles it completes with this, to tell them apart.
+    const COMPLETED_BY_B: u64 = 1 << 63;
+
+    unsafe extern "C" fn complete_b(handle: u64, code: i8, result: RustBuffer) {
+        complete(COMPLETED_BY_B | handle, code, result)
+    }
+
+    fn wait_for(handle: u64) -> (i8, Vec<u8>) {
+        let mut completed = COMPLETED.lock().unwrap();
+        loop {
+            if let Some(result) = completed.remove(&handle) {
+                return result;
+            }
+            completed = CHANGED
+                .wait_timeout(completed, Duration::from_secs(10))
+                .unwrap()
+                .0;
+        }
+    }
+
+    // A future that's pending the first time it's polled, and wakes itself from another thread.
+    struct WakeLater(bool);
+
+    impl Future for WakeLater {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            let waker = cx.waker().clone();
+            thread::spawn(move || waker.wake());
+            Poll::Pending
+        }
+    }
+
+    fn fail() -> u32 {
+        panic!("the call failed")
+    }
+
+    fn lower(value: u32) -> RustBuffer {
+        let mut buf = Vec::new();
+        u32::write(value, &mut buf);
+        RustBuffer::from_vec(buf)
+    }
+
+    #[test]
+    fn test_async_calls() {
+        init_async_completion(&CALLS_A, complete);
+
+        spawn_async_call(&CALLS_A, 1, async {
+            WakeLater(false).await;
+            Ok(lower(42))
+        });
+        let (code, bytes) = wait_for(1);
+        assert_eq!(code, ASYNC_CALL_DONE);
+        assert_eq!(u32::try_read(&mut bytes.as_slice()).unwrap(), 42);
+
+        spawn_async_call(&CALLS_A, 2, async { Err(lower(7)) });
+        assert_eq!(wait_for(2).0, ASYNC_CALL_ERROR);
+
+        spawn_async_call(&CALLS_A, 3, async {
+            WakeLater(false).await;
+            Ok(lower(fail()))
+        });
+        let (code, bytes) = wait_for(3);
+        assert_eq!(code, ASYNC_CALL_PANIC);
+        assert_eq!(
+            String::try_read(&mut bytes.as_slice()).unwrap(),
+            "the call failed"
+        );
+    }
+
+    #[test]
+    fn test_cancelling_async_calls() {
+        init_async_completion(&CALLS_A, complete);
+
+        spawn_async_call(&CALLS_A, 4, async {
+            std::future::pending::<()>().await;
+            Ok(lower(1))
+        });
+        cancel_async_call(&CALLS_A, 4);
+        let (code, bytes) = wait_for(4);
+        assert_eq!(code, ASYNC_CALL_CANCELLED);
+        assert!(bytes.is_empty());
+        // Cancelling a call that has already completed does nothing.
+        cancel_async_call(&CALLS_A, 4);
+
+        // A cancellable call finishes however it likes.
+        spawn_cancellable_async_call(&CALLS_A, 5, |token| async move {
+            token.cancelled().await;
+            Err(lower(2))
+        });
+        cancel_async_call(&CALLS_A, 5);
+        let (code, bytes) = wait_for(5);
+        assert_eq!(code, ASYNC_CALL_ERROR);
+        assert_eq!(u32::try_read(&mut bytes.as_slice()).unwrap(), 2);
+    }
+
+    // Cancelling one component's call doesn't cancel another's with the same handle, and each
+    // is completed through its own component's completion.
+    #[test]
+    fn test_calls_of_different_components() {
+        init_async_completion(&CALLS_A, complete);
+        init_async_completion(&CALLS_B, complete_b);
+
+        spawn_async_call(&CALLS_A, 6, async {
+            WakeLater(false).await;
+            Ok(lower(1))
+        });
+        spawn_async_call(&CALLS_B, 6, async {
+            std::future::pending::<()>().await;
+            Ok(lower(2))
+        });
+        cancel_async_call(&CALLS_B, 6);
+        let (code, bytes) = wait_for(6);
+        assert_eq!(code, ASYNC_CALL_DONE);
+        assert_eq!(u32::try_read(&mut bytes.as_slice()).unwrap(), 1);
+        let (code, bytes) = wait_for(COMPLETED_BY_B | 6);
+        assert_eq!(code, ASYNC_CALL_CANCELLED);
+        assert!(bytes.is_empty());
+    }
+}
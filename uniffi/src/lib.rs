@@ -30,6 +30,7 @@
 
 use anyhow::bail;
 use bytes::buf::{Buf, BufMut};
+use indexmap::IndexMap;
 use paste::paste;
 use std::{
     collections::HashMap,
@@ -40,9 +41,54 @@ use std::{
 // Make Result<> public to support external impls of FfiConverter
 pub use anyhow::Result;
 
+mod borrowed;
+pub use borrowed::{BorrowedBytes, BorrowedString};
+
+mod cancellation;
+pub use cancellation::{CancellationToken, Cancelled};
+
+mod capabilities;
+pub use capabilities::{CallbackCapabilities, VersionedCallback};
+
+mod deadlocks;
+pub use deadlocks::{
+    deadlock_timeout, enter_foreign_call, enter_rust_call, read_object, set_deadlock_timeout,
+    write_object, CallFrame, ObjectReadGuard, ObjectWriteGuard,
+};
+
+mod events;
+pub use events::{EventSource, Listeners};
+
 pub mod ffi;
 pub use ffi::*;
 
+mod memory_pressure;
+pub use memory_pressure::{on_memory_pressure, run_memory_pressure_hooks, MemoryPressure};
+
+mod observers;
+pub use observers::{Observable, Observers};
+
+mod pagination;
+pub use pagination::paginate;
+
+mod reset;
+pub use reset::{on_reset, run_reset_hooks};
+
+mod scope;
+pub use scope::Scope;
+
+mod service;
+pub use service::{Service, ServiceLifecycle, ServiceState};
+
+mod shutdown;
+pub use shutdown::{check_not_shut_down, is_shut_down, on_shutdown, shut_down};
+
+mod stream;
+pub use stream::Stream;
+
+mod warmup;
+pub use warmup::{on_warmup, run_warmup_hooks};
+
 // It would be nice if this module was behind a cfg(test) guard, but it
 // doesn't work between crates so let's hope LLVM tree-shaking works well.
 pub mod testing;
@@ -52,6 +98,7 @@ pub mod testing;
 pub mod deps {
     pub use anyhow;
     pub use bytes;
+    pub use indexmap;
     pub use log;
     pub use static_assertions;
 }
@@ -127,6 +174,81 @@ macro_rules! assert_compatible_version {
     };
 }
 
+/// Metadata describing a component, generated into its scaffolding as `UNIFFI_COMPONENT_INFO`.
+///
+/// The generated foreign-language bindings can fetch this at runtime, so that support tooling
+/// can check exactly which build of the Rust library an app is running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentInfo {
+    /// The namespace of the component.
+    pub name: &'static str,
+    /// The version of `uniffi_bindgen` that generated the scaffolding.
+    pub uniffi_version: &'static str,
+    /// The checksum of the component interface, as used to guard the FFI function names.
+    pub checksum: u64,
+    /// Either `"debug"` or `"release"`, depending on whether debug assertions are enabled.
+    pub build_profile: &'static str,
+}
+
+impl ComponentInfo {
+    /// Serialize the metadata for the foreign-language bindings.
+    ///
+    /// This is one field per line, in declaration order, with the checksum in hex.
+    pub fn to_ffi_string(&self) -> String {
+        format!(
+            "{}\n{}\n{:016x}\n{}",
+            self.name, self.uniffi_version, self.checksum, self.build_profile
+        )
+    }
+}
+
+/// Collect the messages of an error's chain of `source()`s, outermost first.
+///
+/// This is used by the generated scaffolding for errors declared with `[SourceChain]`,
+/// which send the chain along with the error so that the foreign-language bindings
+/// can surface it as the cause of the exception. The error's own message is not included.
+pub fn error_source_chain(err: &dyn std::error::Error) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut source = err.source();
+    while let Some(err) = source {
+        chain.push(err.to_string());
+        source = err.source();
+    }
+    chain
+}
+
+/// The error for a string from the foreign-language code that isn't valid utf8.
+///
+/// The bindings never send one of these themselves, but a foreign caller can, by working
+/// around them or by corrupting a buffer. Rather than panicking when it fails to lift such
+/// an argument, the scaffolding reports it as a `CALL_INVALID_STRING` status, which the
+/// bindings throw as a typed error of their own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidStringError {
+    /// The number of bytes at the start of the string that are valid utf8.
+    pub valid_up_to: usize,
+}
+
+impl From<std::str::Utf8Error> for InvalidStringError {
+    fn from(err: std::str::Utf8Error) -> Self {
+        Self {
+            valid_up_to: err.valid_up_to(),
+        }
+    }
+}
+
+impl std::fmt::Display for InvalidStringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid utf8 in string, after {} valid bytes",
+            self.valid_up_to
+        )
+    }
+}
+
+impl std::error::Error for InvalidStringError {}
+
 /// Trait defining how to transfer values via the FFI layer.
 ///
 /// The `FfiConverter` trait defines how to pass values of a particular type back-and-forth over
@@ -300,6 +422,38 @@ unsafe impl FfiConverter for bool {
     }
 }
 
+/// Support for passing `char` values via the FFI.
+///
+/// Chars are passed as their Unicode scalar value, in a `u32`. A value that isn't one, like a
+/// surrogate, fails to lift.
+unsafe impl FfiConverter for char {
+    type RustType = Self;
+    type FfiType = u32;
+
+    fn lower(obj: Self::RustType) -> Self::FfiType {
+        obj.into()
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<Self::RustType> {
+        match char::from_u32(v) {
+            Some(c) => Ok(c),
+            None => bail!(
+                "unexpected value {:#x} for char, which isn't a Unicode scalar",
+                v
+            ),
+        }
+    }
+
+    fn write(obj: Self::RustType, buf: &mut Vec<u8>) {
+        buf.put_u32(<char as FfiConverter>::lower(obj));
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Self::RustType> {
+        check_remaining(buf, 4)?;
+        <char as FfiConverter>::try_lift(buf.get_u32())
+    }
+}
+
 /// Support for passing Strings via the FFI.
 ///
 /// Unlike many other implementations of `FfiConverter`, this passes a struct containing
@@ -322,16 +476,12 @@ unsafe impl FfiConverter for String {
         RustBuffer::from_vec(obj.into_bytes())
     }
 
-    // The argument here *must* be a uniquely-owned `RustBuffer` previously obtained
-    // from `lower` above, and hence must be the bytes of a valid rust string.
+    // The argument here *must* be a uniquely-owned `RustBuffer`, but the foreign-language
+    // code may have filled it with anything, so we check that the bytes are valid utf8
+    // before turning them back into a `String`. This doesn't copy the data.
     fn try_lift(v: Self::FfiType) -> Result<Self::RustType> {
         let v = v.destroy_into_vec();
-        // This turns the buffer back into a `String` without copying the data
-        // and without re-checking it for validity of the utf8. If the `RustBuffer`
-        // came from a valid String then there's no point in re-checking the utf8,
-        // and if it didn't then bad things are probably going to happen regardless
-        // of whether we check for valid utf8 data or not.
-        Ok(unsafe { String::from_utf8_unchecked(v) })
+        String::from_utf8(v).map_err(|e| InvalidStringError::from(e.utf8_error()).into())
     }
 
     fn write(obj: Self::RustType, buf: &mut Vec<u8>) {
@@ -350,7 +500,8 @@ unsafe impl FfiConverter for String {
         // But in the specific case of `<&[u8] as Buf>` it returns the full slice,
         // so there is no risk of having less than `len` bytes available here.
         let bytes = &buf.chunk()[..len];
-        let res = String::from_utf8(bytes.to_vec())?;
+        let res = String::from_utf8(bytes.to_vec())
+            .map_err(|e| InvalidStringError::from(e.utf8_error()))?;
         buf.advance(len);
         Ok(res)
     }
@@ -396,6 +547,23 @@ unsafe impl<T: RustBufferFfiConverter> FfiConverter for T {
     }
 }
 
+/// Support for passing `bigint` values via the FFI.
+///
+/// They're passed by serializing to a buffer, as a 128-bit two's complement number in
+/// big-endian order, since not all foreign languages can pass 128-bit integers directly.
+impl RustBufferFfiConverter for i128 {
+    type RustType = Self;
+
+    fn write(obj: Self::RustType, buf: &mut Vec<u8>) {
+        buf.put_i128(obj);
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Self::RustType> {
+        check_remaining(buf, 16)?;
+        Ok(buf.get_i128())
+    }
+}
+
 /// Support for passing timestamp values via the FFI.
 ///
 /// Timestamps values are currently always passed by serializing to a buffer.
@@ -531,22 +699,25 @@ impl<T: FfiConverter> RustBufferFfiConverter for Vec<T> {
 }
 
 /// Support for associative arrays via the FFI.
-/// Note that because of webidl limitations,
-/// the key must always be of the String type.
+/// The key can be a string, an integer or an enum without associated data, which has to
+/// implement `Hash` and `Eq` in Rust.
 ///
 /// HashMaps are currently always passed by serializing to a buffer.
-/// We write a `i32` entries count followed by each entry (string
-/// key followed by the value) in turn.
+/// We write a `i32` entries count followed by each entry (the key
+/// followed by the value) in turn.
 /// (It's a signed type due to limits of the JVM).
-impl<V: FfiConverter> RustBufferFfiConverter for HashMap<String, V> {
-    type RustType = HashMap<String, V::RustType>;
+impl<K: FfiConverter, V: FfiConverter> RustBufferFfiConverter for HashMap<K, V>
+where
+    K::RustType: std::hash::Hash + Eq,
+{
+    type RustType = HashMap<K::RustType, V::RustType>;
 
     fn write(obj: Self::RustType, buf: &mut Vec<u8>) {
         // TODO: would be nice not to panic here :-/
         let len = i32::try_from(obj.len()).unwrap();
         buf.put_i32(len); // We limit HashMaps to i32::MAX entries
         for (key, value) in obj.into_iter() {
-            <String as FfiConverter>::write(key, buf);
+            <K as FfiConverter>::write(key, buf);
             <V as FfiConverter>::write(value, buf);
         }
     }
@@ -555,6 +726,38 @@ impl<V: FfiConverter> RustBufferFfiConverter for HashMap<String, V> {
         check_remaining(buf, 4)?;
         let len = usize::try_from(buf.get_i32())?;
         let mut map = HashMap::with_capacity(len);
+        for _ in 0..len {
+            let key = <K as FfiConverter>::try_read(buf)?;
+            let value = <V as FfiConverter>::try_read(buf)?;
+            map.insert(key, value);
+        }
+        Ok(map)
+    }
+}
+
+/// Support for maps that keep their entries in insertion order, declared with an
+/// `[OrderedMap]` typedef.
+///
+/// These have the same serialization as `HashMap`s, but they're written in the order of the
+/// map, and read back into an `IndexMap` in the order they were written, so that the order
+/// survives the trip across the FFI in both directions.
+impl<V: FfiConverter> RustBufferFfiConverter for IndexMap<String, V> {
+    type RustType = IndexMap<String, V::RustType>;
+
+    fn write(obj: Self::RustType, buf: &mut Vec<u8>) {
+        // TODO: would be nice not to panic here :-/
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len); // We limit IndexMaps to i32::MAX entries
+        for (key, value) in obj.into_iter() {
+            <String as FfiConverter>::write(key, buf);
+            <V as FfiConverter>::write(value, buf);
+        }
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Self::RustType> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        let mut map = IndexMap::with_capacity(len);
         for _ in 0..len {
             let key = String::try_read(buf)?;
             let value = <V as FfiConverter>::try_read(buf)?;
@@ -635,6 +838,45 @@ mod test {
         t.compile_fail("tests/ui/*.rs");
     }
 
+    #[test]
+    fn component_info_ffi_string() {
+        let info = ComponentInfo {
+            name: "example",
+            uniffi_version: "0.15.2",
+            checksum: 0xabcdef,
+            build_profile: "debug",
+        };
+        assert_eq!(
+            info.to_ffi_string(),
+            "example\n0.15.2\n0000000000abcdef\ndebug"
+        );
+    }
+
+    #[test]
+    fn error_source_chain_is_outermost_first() {
+        #[derive(Debug)]
+        struct Chained(&'static str, Option<Box<Chained>>);
+        impl std::fmt::Display for Chained {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.0)
+            }
+        }
+        impl std::error::Error for Chained {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                self.1.as_ref().map(|e| e.as_ref() as _)
+            }
+        }
+        let err = Chained(
+            "outer",
+            Some(Box::new(Chained(
+                "middle",
+                Some(Box::new(Chained("inner", None))),
+            ))),
+        );
+        assert_eq!(error_source_chain(&err), vec!["middle", "inner"]);
+        assert!(error_source_chain(&Chained("alone", None)).is_empty());
+    }
+
     #[test]
     fn timestamp_roundtrip_post_epoch() {
         let expected = SystemTime::UNIX_EPOCH + Duration::new(100, 100);
@@ -651,4 +893,74 @@ mod test {
             "Expected results after lowering and lifting to be equal"
         )
     }
+
+    #[test]
+    fn bigint_roundtrip() {
+        for expected in [i128::MIN, -1, 0, u64::MAX as i128 + 1, i128::MAX].iter() {
+            let result = i128::try_lift(i128::lower(*expected)).expect("Failed to lift!");
+            assert_eq!(*expected, result);
+        }
+        let mut buf = Vec::new();
+        <i128 as FfiConverter>::write(-2, &mut buf);
+        assert_eq!(buf, [[0xff; 15].as_ref(), &[0xfe]].concat());
+    }
+
+    #[test]
+    fn char_roundtrip() {
+        for expected in ['a', 'é', '\u{1f980}', char::MAX].iter() {
+            let result = char::try_lift(char::lower(*expected)).expect("Failed to lift!");
+            assert_eq!(*expected, result);
+        }
+        // Surrogates and values past the last scalar aren't chars.
+        assert!(char::try_lift(0xd800).is_err());
+        assert!(char::try_lift(0x110000).is_err());
+        assert!(char::try_read(&mut [0, 0, 0xdc, 0].as_ref()).is_err());
+    }
+
+    #[test]
+    fn ordered_map_roundtrip_keeps_insertion_order() {
+        let mut expected = IndexMap::new();
+        for (key, value) in [("zebra", 1u32), ("apple", 2), ("mango", 3)].iter() {
+            expected.insert(key.to_string(), *value);
+        }
+        let result = <IndexMap<String, u32> as FfiConverter>::try_lift(
+            <IndexMap<String, u32> as FfiConverter>::lower(expected.clone()),
+        )
+        .expect("Failed to lift!");
+        assert_eq!(
+            result.keys().collect::<Vec<_>>(),
+            vec!["zebra", "apple", "mango"]
+        );
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn map_roundtrip_with_integer_keys() {
+        let mut expected = HashMap::new();
+        expected.insert(7u64, "seven".to_string());
+        expected.insert(u64::MAX, "max".to_string());
+        let result = <HashMap<u64, String> as FfiConverter>::try_lift(
+            <HashMap<u64, String> as FfiConverter>::lower(expected.clone()),
+        )
+        .expect("Failed to lift!");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn invalid_strings_fail_to_lift() {
+        let err = String::try_lift(RustBuffer::from_vec(b"ok\xffno".to_vec())).unwrap_err();
+        assert_eq!(
+            err.downcast::<InvalidStringError>().unwrap(),
+            InvalidStringError { valid_up_to: 2 }
+        );
+        let mut buf = Vec::new();
+        buf.put_i32(2);
+        buf.put_slice(b"\xc3(");
+        let err = String::try_read(&mut buf.as_slice()).unwrap_err();
+        assert!(err.is::<InvalidStringError>());
+        assert_eq!(
+            String::try_lift(RustBuffer::from_vec("héllo".as_bytes().to_vec())).unwrap(),
+            "héllo"
+        );
+    }
 }
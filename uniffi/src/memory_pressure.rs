@@ -0,0 +1,105 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Shrinking caches under memory pressure
+//!
+//! The Kotlin and Swift bindings have a `uniffiOnMemoryPressure()` function, which the app
+//! calls when the operating system says that it's running low on memory, from
+//! `onTrimMemory` on Android or `didReceiveMemoryWarning` on iOS. It runs the hooks
+//! registered here, so that a component which caches things can let some of them go:
+//!
+//! ```
+//! # use std::sync::Mutex;
+//! # lazy_static::lazy_static! {
+//! #     static ref CACHE: Mutex<Vec<String>> = Mutex::new(Vec::new());
+//! # }
+//! uniffi::on_memory_pressure(|pressure| match pressure {
+//!     uniffi::MemoryPressure::Moderate => CACHE.lock().unwrap().truncate(10),
+//!     uniffi::MemoryPressure::Critical => CACHE.lock().unwrap().clear(),
+//! });
+//! ```
+//!
+//! The hooks stay registered, so they run every time, in the order they were registered.
+//! They're shared by all the components that are built into the same library.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+/// How badly the system needs memory back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MemoryPressure {
+    /// Memory is getting low, so caches that are cheap to refill should shrink.
+    Moderate,
+    /// The app is about to be killed unless it frees memory, so caches should be emptied.
+    Critical,
+}
+
+impl MemoryPressure {
+    // The levels that the bindings pass across the FFI.
+    const MODERATE_LEVEL: i32 = 1;
+
+    /// The pressure for a level passed by the bindings, which is `1` for moderate and `2` for
+    /// critical pressure. Levels above that are treated as critical.
+    #[doc(hidden)]
+    pub fn from_level(level: i32) -> Self {
+        if level <= Self::MODERATE_LEVEL {
+            MemoryPressure::Moderate
+        } else {
+            MemoryPressure::Critical
+        }
+    }
+}
+
+type MemoryPressureHook = Box<dyn Fn(MemoryPressure) + Send + Sync>;
+
+lazy_static! {
+    static ref MEMORY_PRESSURE_HOOKS: Mutex<Vec<MemoryPressureHook>> = Mutex::new(Vec::new());
+}
+
+/// Register a hook to run when the foreign-language bindings report memory pressure.
+pub fn on_memory_pressure(hook: impl Fn(MemoryPressure) + Send + Sync + 'static) {
+    MEMORY_PRESSURE_HOOKS.lock().unwrap().push(Box::new(hook));
+}
+
+/// Run the memory pressure hooks. The scaffolding calls this when the bindings report memory
+/// pressure, with the level that they passed.
+///
+/// A hook that panics is logged and skipped, so that the others still get to run.
+#[doc(hidden)]
+pub fn run_memory_pressure_hooks(level: i32) {
+    let pressure = MemoryPressure::from_level(level);
+    let hooks = MEMORY_PRESSURE_HOOKS.lock().unwrap();
+    for hook in hooks.iter() {
+        if catch_unwind(AssertUnwindSafe(|| hook(pressure))).is_err() {
+            log::error!("A memory pressure hook panicked");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_memory_pressure_hooks() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Arc::clone(&seen);
+        on_memory_pressure(move |pressure| recorder.lock().unwrap().push(pressure));
+        on_memory_pressure(|_| panic!("shrinking failed"));
+        run_memory_pressure_hooks(1);
+        run_memory_pressure_hooks(2);
+        run_memory_pressure_hooks(7);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                MemoryPressure::Moderate,
+                MemoryPressure::Critical,
+                MemoryPressure::Critical
+            ]
+        );
+    }
+}
@@ -0,0 +1,187 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Change notifications for `[Observable]` objects
+//!
+//! An interface marked `[Observable=ProfileState]` in the UDL gets an `add_observer` method,
+//! which registers a foreign implementation of the generated `ProfileObserver` callback
+//! interface and returns an id for it, and a `remove_observer` method, which takes that id.
+//! The Rust struct keeps its observers in an [`Observers`] and hands them out through the
+//! [`Observable`] trait:
+//!
+//! ```
+//! # #[derive(Clone)]
+//! # struct ProfileState { name: String }
+//! struct Profile {
+//!     state: std::sync::Mutex<ProfileState>,
+//!     observers: uniffi::Observers<ProfileState>,
+//! }
+//!
+//! impl uniffi::Observable for Profile {
+//!     type Snapshot = ProfileState;
+//!     fn observers(&self) -> &uniffi::Observers<ProfileState> {
+//!         &self.observers
+//!     }
+//! }
+//!
+//! impl Profile {
+//!     fn set_name(&self, name: String) {
+//!         let snapshot = {
+//!             let mut state = self.state.lock().unwrap();
+//!             state.name = name;
+//!             state.clone()
+//!         };
+//!         self.observers.notify_changed("name", snapshot);
+//!     }
+//! }
+//! ```
+//!
+//! Each observer then gets the name of the field that changed and a snapshot of the whole
+//! record, so that a UI can refresh just the parts that show that field.
+
+use std::sync::{Arc, Mutex};
+
+type Observer<T> = Arc<dyn Fn(&str, T) + Send + Sync>;
+
+/// The observers registered with an `[Observable]` object.
+pub struct Observers<T> {
+    inner: Mutex<ObserverList<T>>,
+}
+
+struct ObserverList<T> {
+    next_id: u64,
+    observers: Vec<(u64, Observer<T>)>,
+}
+
+impl<T: Clone> Observers<T> {
+    pub fn new() -> Self {
+        Observers {
+            inner: Mutex::new(ObserverList {
+                next_id: 1,
+                observers: Vec::new(),
+            }),
+        }
+    }
+
+    /// Register an observer, and return the id that removes it again.
+    pub fn add(&self, observer: impl Fn(&str, T) + Send + Sync + 'static) -> u64 {
+        let mut inner = self.inner.lock().unwrap();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.observers.push((id, Arc::new(observer)));
+        id
+    }
+
+    /// Unregister the observer with the given id. Ids that aren't registered are ignored, so
+    /// that removing an observer twice is harmless.
+    pub fn remove(&self, id: u64) {
+        self.inner
+            .lock()
+            .unwrap()
+            .observers
+            .retain(|(observer_id, _)| *observer_id != id);
+    }
+
+    /// The number of observers that are registered.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().observers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Tell each observer that `field` has changed, with a snapshot of the new state.
+    ///
+    /// The observers are called on the current thread, in the order they were added, and
+    /// without holding any lock, so they can add or remove observers themselves.
+    pub fn notify_changed(&self, field: &str, snapshot: T) {
+        let observers: Vec<Observer<T>> = self
+            .inner
+            .lock()
+            .unwrap()
+            .observers
+            .iter()
+            .map(|(_, observer)| Arc::clone(observer))
+            .collect();
+        for observer in observers {
+            observer(field, snapshot.clone());
+        }
+    }
+}
+
+impl<T: Clone> Default for Observers<T> {
+    fn default() -> Self {
+        Observers::new()
+    }
+}
+
+impl<T> std::fmt::Debug for Observers<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Observers")
+            .field("len", &self.inner.lock().unwrap().observers.len())
+            .finish()
+    }
+}
+
+/// Implemented by the Rust structs of `[Observable]` objects, to give the generated
+/// `add_observer` and `remove_observer` methods their [`Observers`].
+pub trait Observable {
+    /// The record that's sent to observers, as named by `[Observable=Snapshot]`.
+    type Snapshot: Clone;
+
+    fn observers(&self) -> &Observers<Self::Snapshot>;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_observers() {
+        let observers = Arc::new(Observers::<u32>::new());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let first = {
+            let seen = Arc::clone(&seen);
+            observers
+                .add(move |field, value| seen.lock().unwrap().push((1, field.to_string(), value)))
+        };
+        let second = {
+            let seen = Arc::clone(&seen);
+            observers
+                .add(move |field, value| seen.lock().unwrap().push((2, field.to_string(), value)))
+        };
+        assert_ne!(first, second);
+        assert_eq!(observers.len(), 2);
+        observers.notify_changed("count", 1);
+        observers.remove(first);
+        observers.remove(first);
+        observers.notify_changed("count", 2);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                (1, "count".to_string(), 1),
+                (2, "count".to_string(), 1),
+                (2, "count".to_string(), 2),
+            ]
+        );
+
+        // An observer can remove itself while it's being notified.
+        let weak = Arc::downgrade(&observers);
+        let once = Arc::new(Mutex::new(0));
+        let id = Arc::new(Mutex::new(0));
+        let third = {
+            let (once, id) = (Arc::clone(&once), Arc::clone(&id));
+            observers.add(move |_, _| {
+                *once.lock().unwrap() += 1;
+                weak.upgrade().unwrap().remove(*id.lock().unwrap());
+            })
+        };
+        *id.lock().unwrap() = third;
+        observers.notify_changed("count", 3);
+        observers.notify_changed("count", 4);
+        assert_eq!(*once.lock().unwrap(), 1);
+        assert!(!observers.is_empty());
+    }
+}
@@ -0,0 +1,45 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Pages of sequences
+//!
+//! A `[Paginated]` method in the UDL, which returns a `sequence<T>`, gets a `{method}_page`
+//! method alongside it, which takes an `offset` and a `limit` and returns a record with a page of
+//! the sequence as its `items`, and the length of the whole sequence as its `total`. The
+//! scaffolding implements it by calling the method and passing what it returns to [`paginate`],
+//! so that only the page is copied across the FFI.
+
+/// Take up to `limit` items from `items`, starting at `offset`, and count how many there are
+/// altogether. An `offset` past the end gives an empty page.
+///
+/// ```
+/// let (page, total) = uniffi::paginate(vec!["a", "b", "c", "d", "e"], 1, 2);
+/// assert_eq!(page, vec!["b", "c"]);
+/// assert_eq!(total, 5);
+/// ```
+pub fn paginate<T>(items: impl IntoIterator<Item = T>, offset: u64, limit: u32) -> (Vec<T>, u64) {
+    let mut page = Vec::new();
+    let mut total = 0;
+    for item in items {
+        if total >= offset && page.len() < limit as usize {
+            page.push(item);
+        }
+        total += 1;
+    }
+    (page, total)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_paginate() {
+        assert_eq!(paginate(0..10, 0, 3), (vec![0, 1, 2], 10));
+        assert_eq!(paginate(0..10, 8, 3), (vec![8, 9], 10));
+        assert_eq!(paginate(0..10, 12, 3), (vec![], 10));
+        assert_eq!(paginate(0..10, 4, 0), (vec![], 10));
+        assert_eq!(paginate(Vec::<u8>::new(), 0, 3), (vec![], 0));
+    }
+}
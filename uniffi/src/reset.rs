@@ -0,0 +1,72 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Resetting a component during development
+//!
+//! The Python and Ruby bindings have a `reset_library()` function, for REPL workflows that
+//! would otherwise need to restart the process to start over. It frees every object that the
+//! bindings hold, then runs the hooks registered here, then reloads the library.
+//!
+//! A component that keeps state outside of its objects, such as a global connection pool or
+//! a cache, registers a hook to flush it:
+//!
+//! ```
+//! # use std::sync::Mutex;
+//! # lazy_static::lazy_static! {
+//! #     static ref CACHE: Mutex<Vec<String>> = Mutex::new(Vec::new());
+//! # }
+//! uniffi::on_reset(|| CACHE.lock().unwrap().clear());
+//! ```
+//!
+//! The hooks stay registered, so they run on every reset, in the order they were registered.
+//! They're shared by all the components that are built into the same library.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+type ResetHook = Box<dyn Fn() + Send + Sync>;
+
+lazy_static! {
+    static ref RESET_HOOKS: Mutex<Vec<ResetHook>> = Mutex::new(Vec::new());
+}
+
+/// Register a hook to run when the foreign-language bindings reset the library.
+pub fn on_reset(hook: impl Fn() + Send + Sync + 'static) {
+    RESET_HOOKS.lock().unwrap().push(Box::new(hook));
+}
+
+/// Run the reset hooks. The scaffolding calls this when the bindings reset the library.
+///
+/// A hook that panics is logged and skipped, so that the others still get to run.
+#[doc(hidden)]
+pub fn run_reset_hooks() {
+    let hooks = RESET_HOOKS.lock().unwrap();
+    for hook in hooks.iter() {
+        if catch_unwind(AssertUnwindSafe(hook)).is_err() {
+            log::error!("A reset hook panicked");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_reset_hooks() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&runs);
+        on_reset(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        on_reset(|| panic!("flushing failed"));
+        run_reset_hooks();
+        run_reset_hooks();
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,208 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Scopes for grouping the work of `[Scoped]` functions and methods
+//!
+//! A function or method marked `[Scoped]` in the UDL takes a [`Scope`], which foreign code
+//! creates and passes as its first argument. The Rust implementation registers the work that
+//! it starts in the background against the scope, so that foreign code can cancel all of it at
+//! once, like when the screen that started it goes away, and wait for it to finish:
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use std::sync::atomic::{AtomicBool, Ordering};
+//! fn prefetch(scope: Arc<uniffi::Scope>, url: String) {
+//!     let stopped = Arc::new(AtomicBool::new(false));
+//!     let flag = Arc::clone(&stopped);
+//!     scope.on_cancel(move || flag.store(true, Ordering::SeqCst));
+//!     scope.spawn(move || {
+//!         while !stopped.load(Ordering::SeqCst) {
+//!             // Fetch the next chunk of `url`...
+//! #           break;
+//!         }
+//!     });
+//! }
+//! # let scope = Arc::new(uniffi::Scope::new());
+//! # prefetch(Arc::clone(&scope), "https://example.com".into());
+//! # scope.cancel();
+//! # scope.join();
+//! ```
+//!
+//! Cancelling is cooperative: it runs the scope's cancel hooks and makes [`Scope::is_cancelled`]
+//! return true, and it's up to the work to notice and stop. Once a scope is cancelled, nothing
+//! more can be spawned on it, and hooks registered afterwards run straight away.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+type CancelHook = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct ScopeState {
+    cancelled: bool,
+    // How many of the threads spawned on the scope are still running.
+    running: usize,
+    hooks: Vec<CancelHook>,
+}
+
+#[derive(Default)]
+struct ScopeInner {
+    state: Mutex<ScopeState>,
+    finished: Condvar,
+}
+
+/// A group of background work that can be cancelled and waited for as a unit. It's declared
+/// in the UDL by uniffi itself, and the scaffolding re-exports it, so components shouldn't
+/// declare it themselves.
+#[derive(Default)]
+pub struct Scope {
+    inner: Arc<ScopeInner>,
+}
+
+// Counts a spawned thread as finished when it's dropped, even if the work panicked.
+struct RunningGuard(Arc<ScopeInner>);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        let mut state = self.0.state.lock().unwrap();
+        state.running -= 1;
+        if state.running == 0 {
+            self.0.finished.notify_all();
+        }
+    }
+}
+
+impl Scope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the scope has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.state.lock().unwrap().cancelled
+    }
+
+    /// Cancel the work registered against the scope, by running its cancel hooks.
+    ///
+    /// Only the first call does anything. A hook that panics is logged and skipped, so that the
+    /// others still get to run.
+    pub fn cancel(&self) {
+        let hooks = {
+            let mut state = self.inner.state.lock().unwrap();
+            if state.cancelled {
+                return;
+            }
+            state.cancelled = true;
+            std::mem::take(&mut state.hooks)
+        };
+        for hook in hooks {
+            if catch_unwind(AssertUnwindSafe(hook)).is_err() {
+                log::error!("A scope's cancel hook panicked");
+            }
+        }
+    }
+
+    /// Register a hook to run when the scope is cancelled, or straight away if it already is.
+    pub fn on_cancel(&self, hook: impl FnOnce() + Send + 'static) {
+        {
+            let mut state = self.inner.state.lock().unwrap();
+            if !state.cancelled {
+                state.hooks.push(Box::new(hook));
+                return;
+            }
+        }
+        hook()
+    }
+
+    /// Run `work` on a thread of its own, which [`Scope::join`] waits for.
+    ///
+    /// Returns false without running it if the scope has been cancelled.
+    pub fn spawn(&self, work: impl FnOnce() + Send + 'static) -> bool {
+        {
+            let mut state = self.inner.state.lock().unwrap();
+            if state.cancelled {
+                return false;
+            }
+            state.running += 1;
+        }
+        let guard = RunningGuard(Arc::clone(&self.inner));
+        thread::spawn(move || {
+            let _guard = guard;
+            work()
+        });
+        true
+    }
+
+    /// Block the current thread until all the threads spawned on the scope have finished.
+    ///
+    /// This doesn't cancel the scope, so call [`Scope::cancel`] first to stop work that would
+    /// otherwise carry on. It mustn't be called from work spawned on the scope itself, which
+    /// would wait for itself forever.
+    pub fn join(&self) {
+        let mut state = self.inner.state.lock().unwrap();
+        while state.running > 0 {
+            state = self.inner.finished.wait(state).unwrap();
+        }
+    }
+}
+
+impl std::fmt::Debug for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.inner.state.lock().unwrap();
+        f.debug_struct("Scope")
+            .field("cancelled", &state.cancelled)
+            .field("running", &state.running)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_scopes() {
+        let scope = Scope::new();
+        let stopped = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&stopped);
+        scope.on_cancel(move || flag.store(true, Ordering::SeqCst));
+        scope.on_cancel(|| panic!("cancelling failed"));
+
+        let (started, is_started) = mpsc::channel();
+        let seen = Arc::new(AtomicBool::new(false));
+        let worker_seen = Arc::clone(&seen);
+        let worker_stopped = Arc::clone(&stopped);
+        assert!(scope.spawn(move || {
+            started.send(()).unwrap();
+            while !worker_stopped.load(Ordering::SeqCst) {
+                thread::yield_now();
+            }
+            worker_seen.store(true, Ordering::SeqCst);
+        }));
+        is_started.recv().unwrap();
+        assert!(!scope.is_cancelled());
+        scope.cancel();
+        scope.cancel();
+        assert!(scope.is_cancelled());
+        scope.join();
+        assert!(seen.load(Ordering::SeqCst));
+
+        // Nothing more can be spawned once it's cancelled, and new hooks run straight away.
+        assert!(!scope.spawn(|| panic!("spawned after cancelling")));
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&runs);
+        scope.on_cancel(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        // Work that panics still counts as finished.
+        let scope = Scope::new();
+        scope.spawn(|| panic!("the work failed"));
+        scope.join();
+    }
+}
@@ -0,0 +1,233 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Lifecycles for `[Service]` objects
+//!
+//! An interface marked `[Service]` in the UDL gets `start`, `stop` and `is_running` methods,
+//! along with `add_state_observer` and `remove_state_observer` methods for a generated
+//! `DownloaderStateObserver` callback interface, which is told each [`ServiceState`] that the
+//! object moves into. The Rust struct keeps its state in a [`ServiceLifecycle`] and does the
+//! actual work of starting and stopping through the [`Service`] trait:
+//!
+//! ```
+//! struct Downloader {
+//!     lifecycle: uniffi::ServiceLifecycle,
+//! }
+//!
+//! impl uniffi::Service for Downloader {
+//!     fn lifecycle(&self) -> &uniffi::ServiceLifecycle {
+//!         &self.lifecycle
+//!     }
+//!     fn on_start(&self) {
+//!         // Spawn the worker thread...
+//!     }
+//!     fn on_stop(&self) {
+//!         // ...and join it again.
+//!     }
+//! }
+//! ```
+//!
+//! Starting a service that isn't stopped, or stopping one that isn't running, does nothing,
+//! so foreign code can call `start` and `stop` without keeping track of the state itself.
+
+use std::sync::{Arc, Mutex};
+
+/// The states that a `[Service]` object moves through. It's declared in the UDL by uniffi
+/// itself, and the scaffolding re-exports it, so components shouldn't declare it themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServiceState {
+    Stopped,
+    Starting,
+    Running,
+    Stopping,
+}
+
+type StateObserver = Arc<dyn Fn(ServiceState) + Send + Sync>;
+
+/// The state of a `[Service]` object, and the observers that are told when it changes.
+pub struct ServiceLifecycle {
+    state: Mutex<ServiceState>,
+    observers: Mutex<StateObserverList>,
+}
+
+struct StateObserverList {
+    next_id: u64,
+    observers: Vec<(u64, StateObserver)>,
+}
+
+impl ServiceLifecycle {
+    /// A lifecycle for a service that hasn't started yet.
+    pub fn new() -> Self {
+        ServiceLifecycle {
+            state: Mutex::new(ServiceState::Stopped),
+            observers: Mutex::new(StateObserverList {
+                next_id: 1,
+                observers: Vec::new(),
+            }),
+        }
+    }
+
+    pub fn state(&self) -> ServiceState {
+        *self.state.lock().unwrap()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.state() == ServiceState::Running
+    }
+
+    /// Register an observer of the state, and return the id that removes it again.
+    pub fn add_observer(&self, observer: impl Fn(ServiceState) + Send + Sync + 'static) -> u64 {
+        let mut inner = self.observers.lock().unwrap();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.observers.push((id, Arc::new(observer)));
+        id
+    }
+
+    /// Unregister the observer with the given id. Ids that aren't registered are ignored.
+    pub fn remove_observer(&self, id: u64) {
+        self.observers
+            .lock()
+            .unwrap()
+            .observers
+            .retain(|(observer_id, _)| *observer_id != id);
+    }
+
+    // Move from `from` to `to`, returning whether the service was in `from`. The observers are
+    // told about the change without holding either lock, so they can ask for the state, or
+    // add and remove observers, themselves.
+    fn transition(&self, from: ServiceState, to: ServiceState) -> bool {
+        {
+            let mut state = self.state.lock().unwrap();
+            if *state != from {
+                return false;
+            }
+            *state = to;
+        }
+        let observers: Vec<StateObserver> = self
+            .observers
+            .lock()
+            .unwrap()
+            .observers
+            .iter()
+            .map(|(_, observer)| Arc::clone(observer))
+            .collect();
+        for observer in observers {
+            observer(to);
+        }
+        true
+    }
+}
+
+impl Default for ServiceLifecycle {
+    fn default() -> Self {
+        ServiceLifecycle::new()
+    }
+}
+
+impl std::fmt::Debug for ServiceLifecycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServiceLifecycle")
+            .field("state", &self.state())
+            .finish()
+    }
+}
+
+/// Implemented by the Rust structs of `[Service]` objects, to give the generated lifecycle
+/// methods their [`ServiceLifecycle`] and the work to do when starting and stopping.
+pub trait Service: Send + Sync {
+    fn lifecycle(&self) -> &ServiceLifecycle;
+
+    /// Called by `start`, while the service is `Starting`.
+    fn on_start(&self);
+
+    /// Called by `stop`, while the service is `Stopping`.
+    fn on_stop(&self);
+
+    /// Start the service, if it's stopped.
+    fn start(&self) {
+        let lifecycle = self.lifecycle();
+        if lifecycle.transition(ServiceState::Stopped, ServiceState::Starting) {
+            self.on_start();
+            lifecycle.transition(ServiceState::Starting, ServiceState::Running);
+        }
+    }
+
+    /// Stop the service, if it's running.
+    fn stop(&self) {
+        let lifecycle = self.lifecycle();
+        if lifecycle.transition(ServiceState::Running, ServiceState::Stopping) {
+            self.on_stop();
+            lifecycle.transition(ServiceState::Stopping, ServiceState::Stopped);
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.lifecycle().is_running()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Default)]
+    struct Counter {
+        lifecycle: ServiceLifecycle,
+        starts: AtomicU32,
+        stops: AtomicU32,
+    }
+
+    impl Service for Counter {
+        fn lifecycle(&self) -> &ServiceLifecycle {
+            &self.lifecycle
+        }
+        fn on_start(&self) {
+            assert_eq!(self.lifecycle.state(), ServiceState::Starting);
+            self.starts.fetch_add(1, Ordering::SeqCst);
+        }
+        fn on_stop(&self) {
+            assert_eq!(self.lifecycle.state(), ServiceState::Stopping);
+            self.stops.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_service_lifecycle() {
+        let service = Counter::default();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let id = {
+            let seen = Arc::clone(&seen);
+            service
+                .lifecycle()
+                .add_observer(move |state| seen.lock().unwrap().push(state))
+        };
+        assert!(!service.is_running());
+        service.stop();
+        service.start();
+        service.start();
+        assert!(service.is_running());
+        service.stop();
+        service.stop();
+        assert!(!service.is_running());
+        assert_eq!(service.starts.load(Ordering::SeqCst), 1);
+        assert_eq!(service.stops.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                ServiceState::Starting,
+                ServiceState::Running,
+                ServiceState::Stopping,
+                ServiceState::Stopped,
+            ]
+        );
+
+        service.lifecycle().remove_observer(id);
+        service.lifecycle().remove_observer(id);
+        service.start();
+        assert!(service.is_running());
+        assert_eq!(seen.lock().unwrap().len(), 4);
+    }
+}
@@ -0,0 +1,129 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Shutting down when the foreign-language runtime exits
+//!
+//! A component's threads can outlive the runtime that's using it. If one of them calls back into
+//! a Python interpreter that's being torn down, or a JVM that's running its shutdown hooks, the
+//! process can crash on its way out. So the Kotlin, Python and Ruby bindings shut the library down
+//! when their runtime starts to exit, from a JVM shutdown hook, `atexit` or `at_exit`, in this
+//! order:
+//!
+//! 1. Callbacks stop being delivered. Calling a method of a callback interface panics rather
+//!    than calling into the foreign language, and releasing one no longer tells it to forget
+//!    the object.
+//! 2. New calls into the component fail, with a panic that says the library has shut down.
+//!    Objects can still be freed.
+//! 3. The hooks that the component registered here run, so that it can stop its own threads:
+//!
+//! ```
+//! # use std::sync::atomic::{AtomicBool, Ordering};
+//! # static RUNNING: AtomicBool = AtomicBool::new(true);
+//! uniffi::on_shutdown(|| RUNNING.store(false, Ordering::SeqCst));
+//! ```
+//!
+//! After that, the bindings let go of the callback interface objects that Rust was holding,
+//! without waiting to be told to. Shutting down can't be undone, and it's shared by all the
+//! components that are built into the same library.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+type ShutdownHook = Box<dyn Fn() + Send + Sync>;
+
+// Whether the library has been shut down, and the hooks to run when it is. There's only ever
+// the one, but the tests make their own, so as not to shut down the library they're running in.
+struct Shutdown {
+    done: AtomicBool,
+    hooks: Mutex<Vec<ShutdownHook>>,
+}
+
+impl Shutdown {
+    fn new() -> Self {
+        Self {
+            done: AtomicBool::new(false),
+            hooks: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn on_shutdown(&self, hook: ShutdownHook) {
+        self.hooks.lock().unwrap().push(hook);
+    }
+
+    fn is_done(&self) -> bool {
+        self.done.load(Ordering::SeqCst)
+    }
+
+    fn run(&self) {
+        if self.done.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let hooks = self.hooks.lock().unwrap();
+        for hook in hooks.iter() {
+            if catch_unwind(AssertUnwindSafe(hook)).is_err() {
+                log::error!("A shutdown hook panicked");
+            }
+        }
+    }
+}
+
+lazy_static! {
+    static ref SHUTDOWN: Shutdown = Shutdown::new();
+}
+
+/// Register a hook to run when the foreign-language runtime shuts the library down.
+pub fn on_shutdown(hook: impl Fn() + Send + Sync + 'static) {
+    SHUTDOWN.on_shutdown(Box::new(hook));
+}
+
+/// Whether the library has been shut down.
+pub fn is_shut_down() -> bool {
+    SHUTDOWN.is_done()
+}
+
+/// Shut the library down. The scaffolding calls this when the bindings shut it down.
+///
+/// Only the first call does anything. A hook that panics is logged and skipped, so that the
+/// others still get to run.
+#[doc(hidden)]
+pub fn shut_down() {
+    SHUTDOWN.run()
+}
+
+/// Refuse to make a call into the component once the library has been shut down.
+///
+/// The scaffolding calls this at the start of every function, constructor and method.
+#[doc(hidden)]
+pub fn check_not_shut_down() {
+    if is_shut_down() {
+        panic!("The library has been shut down, because the foreign-language runtime is exiting");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_shutdown_hooks() {
+        let shutdown = Shutdown::new();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&runs);
+        shutdown.on_shutdown(Box::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+        shutdown.on_shutdown(Box::new(|| panic!("stopping failed")));
+        assert!(!shutdown.is_done());
+        shutdown.run();
+        shutdown.run();
+        assert!(shutdown.is_done());
+        // The hooks only run the first time.
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+}
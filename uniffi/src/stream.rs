@@ -0,0 +1,73 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Streams of values
+//!
+//! A `[Stream]` typedef in the UDL, such as `[Stream] typedef sequence<string> Lines;`, declares
+//! an object that hands out the values of an iterator a few at a time, so that a method can
+//! return them without copying all of them across the FFI at once. The scaffolding implements
+//! the object as a [`Stream`] of the item type, and the Rust implementation of the method
+//! returns one:
+//!
+//! ```
+//! # type Lines = uniffi::Stream<String>;
+//! # use std::sync::Arc;
+//! fn lines(text: &str) -> Arc<Lines> {
+//!     let lines: Vec<String> = text.lines().map(String::from).collect();
+//!     Arc::new(Lines::new(lines))
+//! }
+//! ```
+//!
+//! The foreign-language bindings iterate over the object, asking Rust for the next chunk of
+//! values when they've been through the last one.
+
+use std::sync::Mutex;
+
+/// The values of a `[Stream]` typedef, which foreign code takes a chunk at a time.
+pub struct Stream<T> {
+    items: Mutex<Box<dyn Iterator<Item = T> + Send>>,
+}
+
+impl<T> Stream<T> {
+    /// Make a stream of the values of `items`, which are only taken from it when foreign code
+    /// asks for them.
+    pub fn new<I>(items: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: Send + 'static,
+    {
+        Stream {
+            items: Mutex::new(Box::new(items.into_iter())),
+        }
+    }
+
+    /// Take up to `max` more values from the stream. An empty chunk means that there are no
+    /// more, since foreign code never asks for none.
+    pub fn next_chunk(&self, max: u32) -> Vec<T> {
+        let mut items = self.items.lock().unwrap();
+        items.by_ref().take(max.max(1) as usize).collect()
+    }
+}
+
+impl<T> std::fmt::Debug for Stream<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Stream").finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_next_chunk() {
+        let stream = Stream::new((0..5).map(|n| n * 10));
+        assert_eq!(stream.next_chunk(2), vec![0, 10]);
+        // Asking for none still takes one, so that an empty chunk always means the end.
+        assert_eq!(stream.next_chunk(0), vec![20]);
+        assert_eq!(stream.next_chunk(10), vec![30, 40]);
+        assert_eq!(stream.next_chunk(10), Vec::<i32>::new());
+        assert_eq!(stream.next_chunk(10), Vec::<i32>::new());
+    }
+}
@@ -35,10 +35,14 @@ lazy_static! {
 /// a foreign-language test file that exercises that component's bindings. It ensures that the
 /// component is compiled and available for use and then executes the foreign language script,
 /// returning successfully iff the script exits successfully.
+///
+/// If the bindings for the script's language don't support all of the `required_features`,
+/// the script is skipped (with a note printed to say so) and this returns successfully.
 pub fn run_foreign_language_testcase(
     pkg_dir: &str,
     udl_files: &[&str],
     test_file: &str,
+    required_features: &[&str],
 ) -> Result<()> {
     let cdylib_file = ensure_compiled_cdylib(pkg_dir)?;
     let out_dir = Path::new(cdylib_file.as_str())
@@ -47,7 +51,40 @@ pub fn run_foreign_language_testcase(
         .to_str()
         .unwrap();
     let _lock = UNIFFI_BINDGEN.lock();
-    run_uniffi_bindgen_test(out_dir, udl_files, test_file)?;
+    run_uniffi_bindgen_test(out_dir, udl_files, test_file, required_features)?;
+    Ok(())
+}
+
+/// Compare the code generated for a component with its golden files, as part of a rust test suite.
+///
+/// This function takes the top-level directory of a uniffi component crate, its UDL files, and
+/// the foreign languages whose bindings to compare. It fails if the scaffolding or bindings
+/// generated for any of the UDL files differ from the golden files in `golden/<udl name>/`.
+///
+/// The golden files always live in the crate whose tests run the comparison, even for UDL files
+/// that belong to another crate. It is an error for a UDL file not to have any golden files yet.
+/// If `UNIFFI_UPDATE_GOLDEN` is set in the environment, the golden files are written instead.
+pub fn run_golden_file_testcase(
+    pkg_dir: &str,
+    udl_files: &[&str],
+    languages: &[&str],
+) -> Result<()> {
+    let updating = std::env::var_os("UNIFFI_UPDATE_GOLDEN").is_some();
+    let _lock = UNIFFI_BINDGEN.lock();
+    for udl_file in udl_files {
+        let name = Path::new(udl_file)
+            .file_stem()
+            .ok_or_else(|| anyhow::anyhow!("UDL file has no name"))?;
+        let golden_dir = Path::new(pkg_dir).join("golden").join(name);
+        if !updating && !golden_dir.is_dir() {
+            bail!(
+                "No golden files for {} in {:?}; run the tests with UNIFFI_UPDATE_GOLDEN=1 to create them",
+                udl_file,
+                golden_dir
+            );
+        }
+        run_uniffi_bindgen_golden(udl_file, languages, &golden_dir)?;
+    }
     Ok(())
 }
 
@@ -138,11 +175,19 @@ pub fn ensure_compiled_cdylib(pkg_dir: &str) -> Result<String> {
 /// on the `uniffi_bindgen` crate and execute its methods in-process. This is useful for folks
 /// who are working on uniffi itself and want to test out their changes to the bindings generator.
 #[cfg(not(feature = "builtin-bindgen"))]
-fn run_uniffi_bindgen_test(out_dir: &str, udl_files: &[&str], test_file: &str) -> Result<()> {
+fn run_uniffi_bindgen_test(
+    out_dir: &str,
+    udl_files: &[&str],
+    test_file: &str,
+    required_features: &[&str],
+) -> Result<()> {
     let udl_files = udl_files.join("\n");
-    let status = Command::new("uniffi-bindgen")
-        .args(&["test", out_dir, &udl_files, test_file])
-        .status()?;
+    let mut cmd = Command::new("uniffi-bindgen");
+    cmd.args(&["test", out_dir, &udl_files, test_file]);
+    for feature in required_features {
+        cmd.arg("--requires").arg(feature);
+    }
+    let status = cmd.status()?;
     if !status.success() {
         bail!("Error while running tests: {}", status);
     }
@@ -150,6 +195,38 @@ fn run_uniffi_bindgen_test(out_dir: &str, udl_files: &[&str], test_file: &str) -
 }
 
 #[cfg(feature = "builtin-bindgen")]
-fn run_uniffi_bindgen_test(out_dir: &str, udl_files: &[&str], test_file: &str) -> Result<()> {
-    uniffi_bindgen::run_tests(out_dir, udl_files, vec![test_file], None)
+fn run_uniffi_bindgen_test(
+    out_dir: &str,
+    udl_files: &[&str],
+    test_file: &str,
+    required_features: &[&str],
+) -> Result<()> {
+    uniffi_bindgen::run_tests(out_dir, udl_files, vec![test_file], required_features, None)
+}
+
+/// Execute the `uniffi-bindgen golden` command, in the same way as `run_uniffi_bindgen_test`.
+#[cfg(not(feature = "builtin-bindgen"))]
+fn run_uniffi_bindgen_golden(udl_file: &str, languages: &[&str], golden_dir: &Path) -> Result<()> {
+    let mut cmd = Command::new("uniffi-bindgen");
+    cmd.args(&["golden", udl_file]);
+    cmd.arg("--golden-dir").arg(golden_dir);
+    for language in languages {
+        cmd.arg("--language").arg(language);
+    }
+    let status = cmd.status()?;
+    if !status.success() {
+        bail!("Error while comparing golden files: {}", status);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "builtin-bindgen")]
+fn run_uniffi_bindgen_golden(udl_file: &str, languages: &[&str], golden_dir: &Path) -> Result<()> {
+    uniffi_bindgen::check_golden_files(
+        Path::new(udl_file),
+        None,
+        languages.to_vec(),
+        Some(golden_dir),
+        false,
+    )
 }
@@ -0,0 +1,86 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Warming a component up before it's first used
+//!
+//! The Kotlin, Swift and Python bindings have a `uniffiWarmup()` function (`uniffi_warmup()` in
+//! Python), which an app calls on a background thread while it starts up. It loads the library,
+//! checks it, and registers the bindings' callbacks with it, then runs the hooks registered here,
+//! so that the first real call into the component doesn't have to wait for any of that.
+//!
+//! A component that builds something expensive the first time it's used, such as a lazy static,
+//! registers a hook to build it early:
+//!
+//! ```
+//! # lazy_static::lazy_static! {
+//! #     static ref DICTIONARY: Vec<String> = Vec::new();
+//! # }
+//! uniffi::on_warmup(|| lazy_static::initialize(&DICTIONARY));
+//! ```
+//!
+//! Each hook runs once, the next time the library is warmed up after it's registered, so they
+//! need registering before the app calls `uniffiWarmup()`. They're shared by all the components
+//! that are built into the same library.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+type WarmupHook = Box<dyn FnOnce() + Send>;
+
+lazy_static! {
+    static ref WARMUP_HOOKS: Mutex<Vec<WarmupHook>> = Mutex::new(Vec::new());
+}
+
+/// Register a hook to run when the foreign-language bindings warm the library up.
+pub fn on_warmup(hook: impl FnOnce() + Send + 'static) {
+    WARMUP_HOOKS.lock().unwrap().push(Box::new(hook));
+}
+
+/// Run the warmup hooks that haven't run yet. The scaffolding calls this when the bindings warm
+/// the library up.
+///
+/// The hooks are run without holding the lock, so that they can register more, which run too. A
+/// hook that panics is logged and skipped, so that the others still get to run.
+#[doc(hidden)]
+pub fn run_warmup_hooks() {
+    loop {
+        let hooks = std::mem::take(&mut *WARMUP_HOOKS.lock().unwrap());
+        if hooks.is_empty() {
+            return;
+        }
+        for hook in hooks {
+            if catch_unwind(AssertUnwindSafe(hook)).is_err() {
+                log::error!("A warmup hook panicked");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_warmup_hooks() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&runs);
+        on_warmup(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let counter = Arc::clone(&counter);
+            on_warmup(move || {
+                counter.fetch_add(10, Ordering::SeqCst);
+            });
+        });
+        on_warmup(|| panic!("loading failed"));
+        run_warmup_hooks();
+        assert_eq!(runs.load(Ordering::SeqCst), 11);
+        // Each hook only runs once.
+        run_warmup_hooks();
+        assert_eq!(runs.load(Ordering::SeqCst), 11);
+    }
+}
@@ -0,0 +1,186 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Custom foreign types
+//!
+//! The bindings config in `uniffi.toml` can map a builtin type, or a `[Wrapped]` or `[Custom]`
+//! typedef, onto a different foreign-language type, for example:
+//!
+//! ```toml
+//! [bindings.kotlin.custom_types.timestamp]
+//! type_name = "org.threeten.bp.Instant"
+//! into_custom = "org.threeten.bp.Instant.ofEpochSecond({}.epochSecond, {}.nano.toLong())"
+//! from_custom = "java.time.Instant.ofEpochSecond({}.epochSecond, {}.nano.toLong())"
+//! ```
+//!
+//! Each backend then uses a [`CodeType`](super::CodeType) that converts between the type it
+//! would normally generate and the custom type, by way of the user-supplied snippets, in which
+//! `{}` stands for the value being converted.
+//!
+//! The backends' filters don't have access to the bindings config, so the custom types for
+//! the bindings currently being rendered are kept in a thread-local, installed for the
+//! duration of the render by [`with_custom_types`].
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::interface::{ComponentInterface, Type};
+
+/// How to map a single UDL type onto a custom foreign-language type.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CustomTypeConfig {
+    /// The name of the foreign-language type. This is required for statically-typed
+    /// languages, and ignored for languages such as Python.
+    pub type_name: Option<String>,
+    /// Modules or classes to import for the custom type.
+    #[serde(default)]
+    pub imports: Vec<String>,
+    /// Expression converting the generated type into the custom type.
+    pub into_custom: String,
+    /// Expression converting the custom type back into the generated type.
+    pub from_custom: String,
+}
+
+impl CustomTypeConfig {
+    /// Render the `into_custom` snippet for the given value.
+    pub fn into_custom(&self, value: &str) -> String {
+        self.into_custom.replace("{}", value)
+    }
+
+    /// Render the `from_custom` snippet for the given value.
+    pub fn from_custom(&self, value: &str) -> String {
+        self.from_custom.replace("{}", value)
+    }
+}
+
+/// The custom types configured for one backend, keyed by the UDL name of the type.
+pub type CustomTypesConfig = BTreeMap<String, CustomTypeConfig>;
+
+thread_local! {
+    static CUSTOM_TYPES: RefCell<CustomTypesConfig> = RefCell::new(BTreeMap::new());
+}
+
+/// The name by which a type can be given a custom mapping, if it supports one.
+///
+/// This is the UDL name of a builtin type (e.g. `timestamp`), or the name of a wrapped type.
+pub fn custom_type_key(type_: &Type) -> Option<String> {
+    Some(match type_ {
+        Type::UInt8 => "u8".into(),
+        Type::Int8 => "i8".into(),
+        Type::UInt16 => "u16".into(),
+        Type::Int16 => "i16".into(),
+        Type::UInt32 => "u32".into(),
+        Type::Int32 => "i32".into(),
+        Type::UInt64 => "u64".into(),
+        Type::Int64 => "i64".into(),
+        Type::Float32 => "f32".into(),
+        Type::Float64 => "f64".into(),
+        Type::BigInt => "bigint".into(),
+        Type::Bytes => "bytes".into(),
+        Type::Boolean => "boolean".into(),
+        Type::Char => "char".into(),
+        Type::String => "string".into(),
+        Type::Timestamp => "timestamp".into(),
+        Type::Duration => "duration".into(),
+        Type::Wrapped { name, .. } => name.clone(),
+        _ => return None,
+    })
+}
+
+/// Check that every configured custom type names a type that can be mapped.
+///
+/// `needs_type_name` is set by backends for statically-typed languages.
+pub fn validate_custom_types(
+    ci: &ComponentInterface,
+    custom_types: &CustomTypesConfig,
+    needs_type_name: bool,
+) -> Result<()> {
+    for (key, config) in custom_types.iter() {
+        let is_known = ci
+            .iter_types()
+            .iter()
+            .any(|t| custom_type_key(t).as_ref() == Some(key));
+        if !is_known {
+            bail!(
+                "Invalid custom type `{}`: only builtin types and [Wrapped] or [Custom] types used by the component can be customized",
+                key
+            );
+        }
+        if needs_type_name && config.type_name.is_none() {
+            bail!("Custom type `{}` must specify a `type_name`", key);
+        }
+    }
+    Ok(())
+}
+
+/// Run `f` with the given custom types installed, for use by the backend's code oracle.
+pub fn with_custom_types<T>(custom_types: &CustomTypesConfig, f: impl FnOnce() -> T) -> T {
+    let previous = CUSTOM_TYPES.with(|c| c.replace(custom_types.clone()));
+    let result = f();
+    CUSTOM_TYPES.with(|c| c.replace(previous));
+    result
+}
+
+/// Find the custom mapping for a type, if one is configured for the bindings being rendered.
+pub fn find_custom_type(type_: &Type) -> Option<CustomTypeConfig> {
+    let key = custom_type_key(type_)?;
+    CUSTOM_TYPES.with(|c| c.borrow().get(&key).cloned())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn config(type_name: Option<&str>) -> CustomTypeConfig {
+        CustomTypeConfig {
+            type_name: type_name.map(Into::into),
+            imports: vec![],
+            into_custom: "Wrapper({})".into(),
+            from_custom: "{}.inner".into(),
+        }
+    }
+
+    #[test]
+    fn test_custom_types_are_scoped() {
+        let mut custom_types = CustomTypesConfig::new();
+        custom_types.insert("timestamp".into(), config(Some("Instant")));
+        assert!(find_custom_type(&Type::Timestamp).is_none());
+        with_custom_types(&custom_types, || {
+            let found = find_custom_type(&Type::Timestamp).unwrap();
+            assert_eq!(found.into_custom("v"), "Wrapper(v)");
+            assert_eq!(found.from_custom("v"), "v.inner");
+            assert!(find_custom_type(&Type::Duration).is_none());
+            assert!(find_custom_type(&Type::Record("Timestamp".into())).is_none());
+        });
+        assert!(find_custom_type(&Type::Timestamp).is_none());
+    }
+
+    #[test]
+    fn test_validate_custom_types() {
+        const UDL: &str = r#"
+            namespace test {
+                timestamp now();
+            };
+            dictionary Point {
+                f64 x;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let mut custom_types = CustomTypesConfig::new();
+        custom_types.insert("timestamp".into(), config(None));
+        assert!(validate_custom_types(&ci, &custom_types, false).is_ok());
+        let err = validate_custom_types(&ci, &custom_types, true).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Custom type `timestamp` must specify a `type_name`"
+        );
+
+        custom_types.insert("Point".into(), config(Some("Point")));
+        let err = validate_custom_types(&ci, &custom_types, false).unwrap_err();
+        assert!(err.to_string().starts_with("Invalid custom type `Point`"));
+    }
+}
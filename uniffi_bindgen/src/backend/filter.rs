@@ -0,0 +1,213 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Generating bindings for part of a component
+//!
+//! A consumer that only uses a few of a component's functions and methods can generate
+//! bindings for just those, which keeps the generated code small and leaves less of it to
+//! audit. The filter is given separately for each language in `uniffi.toml`:
+//!
+//! ```toml
+//! [bindings.python.filter]
+//! include = ["open_store", "Store"]
+//! exclude = ["Store.compact"]
+//! ```
+//!
+//! Each entry names a function, such as `open_store` or `sync.engine.start` for one in a
+//! nested namespace, a constructor or method, such as `Store.new` or `Store.compact`, or
+//! everything under a prefix, such as `Store` for all of an object's members or `sync`
+//! for all the functions in a nested namespace. These are the names that the API lockfile
+//! uses. Without an `include` list, everything is included, and `exclude` then removes
+//! entries from what is included.
+//!
+//! The bindings still get the records, enums, errors and objects that the included callables
+//! use, but not the ones that nothing included uses. Only the bindings are affected; the
+//! scaffolding still exposes the whole component, so that bindings for other languages, with
+//! other filters, can use the same library.
+
+use std::borrow::Cow;
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::interface::ComponentInterface;
+use crate::lockfile::exported_callables;
+use crate::MergeWith;
+
+/// A `[bindings.<language>.filter]` table in `uniffi.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiFilter {
+    /// The callables to generate bindings for, or `None` for all of them.
+    #[serde(default)]
+    include: Option<Vec<String>>,
+    /// The callables to leave out, even if they're included.
+    #[serde(default)]
+    exclude: Option<Vec<String>>,
+}
+
+// Whether a filter entry names the callable, or a prefix of its name.
+fn matches(entry: &str, name: &str) -> bool {
+    name == entry || (name.starts_with(entry) && name[entry.len()..].starts_with('.'))
+}
+
+impl ApiFilter {
+    /// A filter that includes just the given entries.
+    pub fn including(entries: Vec<String>) -> Self {
+        ApiFilter {
+            include: Some(entries),
+            exclude: None,
+        }
+    }
+
+    /// A filter that includes everything but the given entries.
+    pub fn excluding(entries: Vec<String>) -> Self {
+        ApiFilter {
+            include: None,
+            exclude: Some(entries),
+        }
+    }
+
+    /// Whether the filter keeps the callable with the given qualified name.
+    pub fn keeps(&self, name: &str) -> bool {
+        let included = match &self.include {
+            None => true,
+            Some(include) => include.iter().any(|entry| matches(entry, name)),
+        };
+        let excluded = match &self.exclude {
+            None => false,
+            Some(exclude) => exclude.iter().any(|entry| matches(entry, name)),
+        };
+        included && !excluded
+    }
+
+    /// The part of the component that the filter keeps.
+    ///
+    /// This fails if an entry doesn't name anything in the component, which is most likely
+    /// a typo, or something that has been renamed or removed since the filter was written.
+    pub fn apply<'a>(&self, ci: &'a ComponentInterface) -> Result<Cow<'a, ComponentInterface>> {
+        if self.include.is_none() && self.exclude.is_none() {
+            return Ok(Cow::Borrowed(ci));
+        }
+        let names: Vec<String> = exported_callables(ci)
+            .iter()
+            .map(|callable| callable.qualified_name())
+            .collect();
+        let lists = [("include", &self.include), ("exclude", &self.exclude)];
+        for (list, entries) in lists.iter() {
+            for entry in entries.iter().flatten() {
+                if !names.iter().any(|name| matches(entry, name)) {
+                    bail!(
+                        "\"{}\" in the filter's `{}` list doesn't name a function, constructor or method of the component",
+                        entry,
+                        list
+                    );
+                }
+            }
+        }
+        Ok(Cow::Owned(ci.subset(|name| self.keeps(name))))
+    }
+}
+
+impl MergeWith for ApiFilter {
+    fn merge_with(&self, other: &Self) -> Self {
+        ApiFilter {
+            include: self.include.merge_with(&other.include),
+            exclude: self.exclude.merge_with(&other.exclude),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn filter(toml: &str) -> ApiFilter {
+        toml::de::from_str(toml).unwrap()
+    }
+
+    const UDL: &str = r#"
+        namespace store {
+            Store open_store(string path);
+        };
+        [Parent=store]
+        namespace sync {
+            void start();
+            void stop();
+        };
+        interface Store {
+            constructor();
+            string? get(string key);
+            void compact();
+        };
+    "#;
+
+    #[test]
+    fn test_entries_match_names_and_prefixes() {
+        let f = filter(r#"include = ["Store", "sync.start"]"#);
+        assert!(f.keeps("Store.new"));
+        assert!(f.keeps("Store.get"));
+        assert!(f.keeps("sync.start"));
+        assert!(!f.keeps("sync.stop"));
+        assert!(!f.keeps("open_store"));
+        // A prefix has to end at a dot.
+        assert!(!f.keeps("StoreBuilder.new"));
+
+        let f = filter(r#"exclude = ["sync", "Store.compact"]"#);
+        assert!(f.keeps("open_store"));
+        assert!(f.keeps("Store.get"));
+        assert!(!f.keeps("Store.compact"));
+        assert!(!f.keeps("sync.start"));
+    }
+
+    #[test]
+    fn test_apply() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert!(matches!(
+            ApiFilter::default().apply(&ci).unwrap(),
+            Cow::Borrowed(_)
+        ));
+        let f = filter(
+            r#"
+            include = ["open_store", "Store"]
+            exclude = ["Store.compact"]
+            "#,
+        );
+        let sub = f.apply(&ci).unwrap();
+        assert_eq!(sub.iter_function_definitions().len(), 1);
+        assert!(sub.iter_module_definitions().is_empty());
+        let store = sub.get_object_definition("Store").unwrap();
+        let methods: Vec<_> = store.methods().iter().map(|m| m.name()).collect();
+        assert_eq!(methods, vec!["get"]);
+        assert_eq!(sub.checksum(), ci.checksum());
+    }
+
+    #[test]
+    fn test_unknown_entries_are_errors() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let err = filter(r#"exclude = ["Store.delete"]"#)
+            .apply(&ci)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "\"Store.delete\" in the filter's `exclude` list doesn't name a function, constructor or method of the component"
+        );
+        assert!(filter(r#"include = ["Stor"]"#).apply(&ci).is_err());
+    }
+
+    #[test]
+    fn test_merge() {
+        let a = filter(r#"include = ["open_store"]"#);
+        let b = filter(r#"exclude = ["Store"]"#);
+        assert_eq!(
+            a.merge_with(&b),
+            filter(
+                r#"
+                include = ["open_store"]
+                exclude = ["Store"]
+                "#
+            )
+        );
+    }
+}
@@ -2,12 +2,19 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+pub mod custom_types;
 mod declarations;
+pub mod filter;
 mod oracle;
+pub mod post_process;
+pub mod renames;
 mod types;
 
+pub use custom_types::{CustomTypeConfig, CustomTypesConfig};
 pub use declarations::CodeDeclaration;
+pub use filter::ApiFilter;
 pub use oracle::CodeOracle;
+pub use renames::{NamingProfile, Renames};
 pub use types::CodeType;
 
 pub type TypeIdentifier = crate::interface::Type;
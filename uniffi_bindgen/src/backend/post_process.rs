@@ -0,0 +1,217 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Post-processing generated files
+//!
+//! Projects often need small additions to the generated code, such as a license header, an
+//! annotation or an extra import, that don't justify forking the templates. Instead, every
+//! generated file can be passed through a list of commands, configured in `uniffi.toml`, before
+//! it's written:
+//!
+//! ```toml
+//! [[post_process]]
+//! command = ["sh", "scripts/add-license-header.sh"]
+//!
+//! [[post_process]]
+//! command = ["python3", "scripts/add-annotations.py"]
+//! extensions = ["kt"]
+//! ```
+//!
+//! Each command is given the generated code on its standard input and must write the code to
+//! use on its standard output, with the path of the file in the `UNIFFI_GENERATED_FILE`
+//! environment variable. A command that exits unsuccessfully fails the generation. Commands
+//! with `extensions` only see the files ending in one of those, and the others see every file,
+//! including the Rust scaffolding.
+//!
+//! Programs that drive `uniffi_bindgen` as a library can also implement [`PostProcessor`]
+//! themselves, and install it with [`with_post_processors`] around the calls that generate
+//! code. Like custom types and renames, the installed post-processors are kept in a
+//! thread-local, where the code writing each file can find them.
+
+use std::cell::RefCell;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::rc::Rc;
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// The environment variable holding the path of the file that a post-processing command is given.
+pub const GENERATED_FILE_ENV_VAR: &str = "UNIFFI_GENERATED_FILE";
+
+/// Something that transforms the generated code before it's written.
+pub trait PostProcessor {
+    /// Transform the contents of a generated file, given the path it's about to be written to.
+    fn process(&self, path: &Path, contents: String) -> Result<String>;
+}
+
+/// A `[[post_process]]` entry in `uniffi.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PostProcessCommand {
+    /// The program to run, followed by its arguments.
+    command: Vec<String>,
+    /// The extensions of the files to pass through the command, or `None` for all of them.
+    #[serde(default)]
+    extensions: Option<Vec<String>>,
+}
+
+impl PostProcessCommand {
+    fn applies_to(&self, path: &Path) -> bool {
+        match &self.extensions {
+            None => true,
+            Some(extensions) => path
+                .extension()
+                .map(|ext| extensions.iter().any(|e| ext.to_str() == Some(e.as_str())))
+                .unwrap_or(false),
+        }
+    }
+}
+
+impl PostProcessor for PostProcessCommand {
+    fn process(&self, path: &Path, contents: String) -> Result<String> {
+        if !self.applies_to(path) {
+            return Ok(contents);
+        }
+        let (program, args) = match self.command.split_first() {
+            Some(split) => split,
+            None => bail!("A `[[post_process]]` entry has an empty `command`"),
+        };
+        let mut child = Command::new(program)
+            .args(args)
+            .env(GENERATED_FILE_ENV_VAR, path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run post-processing command `{}`", program))?;
+        // Write the input from another thread, so that a command that starts writing its
+        // output before it has read all of its input can't fill up its pipes and hang.
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let writer = std::thread::spawn(move || stdin.write_all(contents.as_bytes()));
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            bail!(
+                "Post-processing command `{}` failed for {:?} ({}):\n{}",
+                self.command.join(" "),
+                path,
+                output.status,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        writer
+            .join()
+            .expect("writing to the post-processing command panicked")
+            .with_context(|| format!("Failed to pass {:?} to `{}`", path, program))?;
+        String::from_utf8(output.stdout)
+            .with_context(|| format!("Post-processing command `{}` wrote invalid UTF-8", program))
+    }
+}
+
+thread_local! {
+    static POST_PROCESSORS: RefCell<Vec<Rc<dyn PostProcessor>>> = RefCell::new(Vec::new());
+}
+
+/// Run `f` with the given post-processors installed, after any that are installed already.
+pub fn with_post_processors<T>(processors: Vec<Rc<dyn PostProcessor>>, f: impl FnOnce() -> T) -> T {
+    let previous = POST_PROCESSORS.with(|p| {
+        let mut p = p.borrow_mut();
+        let previous = p.len();
+        p.extend(processors);
+        previous
+    });
+    let result = f();
+    POST_PROCESSORS.with(|p| p.borrow_mut().truncate(previous));
+    result
+}
+
+/// Pass the contents of a generated file through each of the installed post-processors in turn.
+pub fn post_process(path: &Path, contents: String) -> Result<String> {
+    // Clone the list, so that a post-processor can itself generate code with others installed.
+    let processors = POST_PROCESSORS.with(|p| p.borrow().clone());
+    processors.iter().try_fold(contents, |contents, processor| {
+        processor.process(path, contents)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Prepend(&'static str);
+
+    impl PostProcessor for Prepend {
+        fn process(&self, _path: &Path, contents: String) -> Result<String> {
+            Ok(format!("{}{}", self.0, contents))
+        }
+    }
+
+    fn command(toml: &str) -> PostProcessCommand {
+        toml::de::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_post_processors_are_scoped_and_ordered() {
+        let path = Path::new("example.kt");
+        assert_eq!(post_process(path, "code".into()).unwrap(), "code");
+        with_post_processors(vec![Rc::new(Prepend("b "))], || {
+            with_post_processors(vec![Rc::new(Prepend("a "))], || {
+                assert_eq!(post_process(path, "code".into()).unwrap(), "a b code");
+            });
+            assert_eq!(post_process(path, "code".into()).unwrap(), "b code");
+        });
+        assert_eq!(post_process(path, "code".into()).unwrap(), "code");
+    }
+
+    #[test]
+    fn test_command_only_sees_matching_extensions() {
+        // The program doesn't exist, so this would fail if the command were run.
+        let cmd = command(
+            r#"
+            command = ["uniffi-no-such-program"]
+            extensions = ["swift", "h"]
+            "#,
+        );
+        assert!(cmd.applies_to(Path::new("out/example.swift")));
+        assert!(!cmd.applies_to(Path::new("out/example")));
+        assert_eq!(
+            cmd.process(Path::new("out/example.kt"), "code".into())
+                .unwrap(),
+            "code"
+        );
+        assert!(cmd
+            .process(Path::new("out/example.h"), "code".into())
+            .is_err());
+    }
+
+    #[test]
+    fn test_empty_command() {
+        let err = command("command = []")
+            .process(Path::new("example.py"), "code".into())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "A `[[post_process]]` entry has an empty `command`"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_output_replaces_contents() {
+        let cmd = command(r#"command = ["sh", "-c", "echo \"// $UNIFFI_GENERATED_FILE\"; cat"]"#);
+        assert_eq!(
+            cmd.process(Path::new("example.kt"), "fun a()\n".into())
+                .unwrap(),
+            "// example.kt\nfun a()\n"
+        );
+        let failing = command(r#"command = ["sh", "-c", "echo oops >&2; exit 3"]"#);
+        let err = failing
+            .process(Path::new("example.kt"), "fun a()\n".into())
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Post-processing command `sh -c echo oops >&2; exit 3` failed"));
+        assert!(err.ends_with("oops\n"));
+    }
+}
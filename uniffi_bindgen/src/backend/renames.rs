@@ -0,0 +1,402 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Renaming generated identifiers
+//!
+//! A project moving an existing hand-written FFI over to uniffi usually has plenty of
+//! foreign-language code calling the old API, whose names don't always follow the conventions
+//! that the backends would generate. Rather than changing every call site at once, a
+//! `renames.toml` file next to the crate's `Cargo.toml` can map UDL identifiers onto the exact
+//! names the legacy bindings used, separately for each language:
+//!
+//! ```toml
+//! [kotlin.classes]
+//! Counter = "LegacyCounter"
+//!
+//! [kotlin.functions]
+//! get_value = "fetchValue"
+//!
+//! [swift.enum_variants]
+//! Red = "crimson"
+//! ```
+//!
+//! The same tables can instead be given in `uniffi.toml`, as `[bindings.kotlin.renames.classes]`
+//! and so on, which take precedence over the entries in `renames.toml`.
+//!
+//! Renames apply to every use of an identifier across the component, and the renamed name
+//! is used verbatim, without any change of case. Only the foreign-language names are
+//! affected; the Rust scaffolding and the FFI symbols stay the same.
+//!
+//! Identifiers that aren't renamed follow the backend's naming profile, chosen with the
+//! `naming` key of its section in `uniffi.toml`. By default the backends use the idiomatic
+//! case for each kind of identifier in their language, but an existing API might predate
+//! uniffi and follow a convention of its own:
+//!
+//! ```toml
+//! [bindings.python]
+//! naming = "verbatim"    # Use the UDL names exactly as written.
+//!
+//! [bindings.kotlin]
+//! naming = "snake_case"  # Use `snake_case` for functions and variables.
+//! ```
+//!
+//! `camel_case` is the other way around, using `lowerCamelCase` for functions and variables.
+//! The `snake_case` and `camel_case` profiles leave class names and enum variants idiomatic.
+//!
+//! Like custom types, the renames and naming profile for the bindings currently being rendered
+//! are kept in a thread-local, installed for the duration of the render by [`with_renames`],
+//! where the backend's code oracle can find them.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use anyhow::{bail, Result};
+use heck::{MixedCase, SnakeCase};
+use serde::{Deserialize, Serialize};
+
+use crate::interface::ComponentInterface;
+use crate::MergeWith;
+
+/// The default name of the file holding the renames, relative to the crate root.
+pub const DEFAULT_RENAMES_FILE: &str = "renames.toml";
+
+/// The renames configured for one backend, each keyed by the UDL identifier.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Renames {
+    /// Names of interfaces, dictionaries, enums, errors and callback interfaces.
+    #[serde(default)]
+    classes: BTreeMap<String, String>,
+    /// Names of functions, constructors and methods.
+    #[serde(default)]
+    functions: BTreeMap<String, String>,
+    /// Names of arguments and fields.
+    #[serde(default)]
+    variables: BTreeMap<String, String>,
+    /// Names of enum and error variants.
+    #[serde(default)]
+    enum_variants: BTreeMap<String, String>,
+}
+
+impl MergeWith for Renames {
+    fn merge_with(&self, other: &Self) -> Self {
+        Renames {
+            classes: self.classes.merge_with(&other.classes),
+            functions: self.functions.merge_with(&other.functions),
+            variables: self.variables.merge_with(&other.variables),
+            enum_variants: self.enum_variants.merge_with(&other.enum_variants),
+        }
+    }
+}
+
+/// The contents of a `renames.toml` file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RenamesFile {
+    #[serde(default)]
+    pub kotlin: Renames,
+    #[serde(default)]
+    pub swift: Renames,
+    #[serde(default)]
+    pub python: Renames,
+    #[serde(default)]
+    pub csharp: Renames,
+}
+
+/// The kind of identifier being named by the code oracle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RenameKind {
+    Class,
+    Function,
+    Variable,
+    EnumVariant,
+}
+
+/// How a backend names the identifiers that aren't renamed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NamingProfile {
+    /// The conventions of the foreign language, which differ between backends.
+    Idiomatic,
+    /// The names exactly as they're written in the UDL.
+    Verbatim,
+    /// `snake_case` functions and variables, with idiomatic classes and enum variants.
+    SnakeCase,
+    /// `lowerCamelCase` functions and variables, with idiomatic classes and enum variants.
+    CamelCase,
+}
+
+impl Default for NamingProfile {
+    fn default() -> Self {
+        NamingProfile::Idiomatic
+    }
+}
+
+impl NamingProfile {
+    /// Name an identifier of the given kind, where `idiomatic` renders it according to
+    /// the conventions of the language.
+    pub fn apply(
+        self,
+        kind: RenameKind,
+        nm: String,
+        idiomatic: impl FnOnce(String) -> String,
+    ) -> String {
+        let is_member = matches!(kind, RenameKind::Function | RenameKind::Variable);
+        match self {
+            NamingProfile::Verbatim => nm,
+            NamingProfile::SnakeCase if is_member => nm.to_snake_case(),
+            NamingProfile::CamelCase if is_member => nm.to_mixed_case(),
+            _ => idiomatic(nm),
+        }
+    }
+}
+
+impl Renames {
+    fn table(&self, kind: RenameKind) -> &BTreeMap<String, String> {
+        match kind {
+            RenameKind::Class => &self.classes,
+            RenameKind::Function => &self.functions,
+            RenameKind::Variable => &self.variables,
+            RenameKind::EnumVariant => &self.enum_variants,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+            && self.functions.is_empty()
+            && self.variables.is_empty()
+            && self.enum_variants.is_empty()
+    }
+}
+
+thread_local! {
+    static RENAMES: RefCell<(Renames, NamingProfile)> = RefCell::new(Default::default());
+}
+
+/// Check that every renamed identifier exists in the component, so that a typo in a
+/// legacy name is reported rather than silently ignored.
+pub fn validate_renames(ci: &ComponentInterface, renames: &Renames) -> Result<()> {
+    if renames.is_empty() {
+        return Ok(());
+    }
+    let known = identifiers(ci);
+    for (kind, section) in [
+        (RenameKind::Class, "classes"),
+        (RenameKind::Function, "functions"),
+        (RenameKind::Variable, "variables"),
+        (RenameKind::EnumVariant, "enum_variants"),
+    ]
+    .iter()
+    {
+        for key in renames.table(*kind).keys() {
+            if !known.contains(&(*kind, key.clone())) {
+                bail!(
+                    "Invalid rename `{}` in `{}`: the component has no such identifier",
+                    key,
+                    section
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+// Every identifier in the component that a rename could apply to.
+fn identifiers(ci: &ComponentInterface) -> BTreeSet<(RenameKind, String)> {
+    let mut known = BTreeSet::new();
+    let mut add = |kind: RenameKind, name: &str| {
+        known.insert((kind, name.to_string()));
+    };
+    for func in ci.iter_function_definitions() {
+        add(RenameKind::Function, func.name());
+        for arg in func.arguments() {
+            add(RenameKind::Variable, arg.name());
+        }
+    }
+    for obj in ci.iter_object_definitions() {
+        add(RenameKind::Class, obj.name());
+        for cons in obj.constructors() {
+            add(RenameKind::Function, cons.name());
+            for arg in cons.arguments() {
+                add(RenameKind::Variable, arg.name());
+            }
+        }
+        for meth in obj.methods() {
+            add(RenameKind::Function, meth.name());
+            for arg in meth.arguments() {
+                add(RenameKind::Variable, arg.name());
+            }
+        }
+    }
+    for cbi in ci.iter_callback_interface_definitions() {
+        add(RenameKind::Class, cbi.name());
+        for meth in cbi.methods() {
+            add(RenameKind::Function, meth.name());
+            for arg in meth.arguments() {
+                add(RenameKind::Variable, arg.name());
+            }
+        }
+    }
+    for rec in ci.iter_record_definitions() {
+        add(RenameKind::Class, rec.name());
+        for field in rec.fields() {
+            add(RenameKind::Variable, field.name());
+        }
+    }
+    let enums = ci.iter_enum_definitions();
+    let errors = ci.iter_error_definitions();
+    let all_variants = enums
+        .iter()
+        .map(|e| (e.name(), e.variants()))
+        .chain(errors.iter().map(|e| (e.name(), e.variants())));
+    for (name, variants) in all_variants {
+        add(RenameKind::Class, name);
+        for variant in variants {
+            // Variants of enums with fields are generated as nested classes.
+            add(RenameKind::Class, variant.name());
+            add(RenameKind::EnumVariant, variant.name());
+            for field in variant.fields() {
+                add(RenameKind::Variable, field.name());
+            }
+        }
+    }
+    known
+}
+
+/// Run `f` with the given renames and naming profile installed, for use by the backend's
+/// code oracle.
+pub fn with_renames<T>(renames: &Renames, naming: NamingProfile, f: impl FnOnce() -> T) -> T {
+    let previous = RENAMES.with(|r| r.replace((renames.clone(), naming)));
+    let result = f();
+    RENAMES.with(|r| r.replace(previous));
+    result
+}
+
+/// Find the legacy name for an identifier, if one is configured for the bindings being rendered.
+pub fn find_rename(kind: RenameKind, nm: &str) -> Option<String> {
+    RENAMES.with(|r| r.borrow().0.table(kind).get(nm).cloned())
+}
+
+/// The naming profile of the bindings being rendered.
+pub fn naming_profile() -> NamingProfile {
+    RENAMES.with(|r| r.borrow().1)
+}
+
+/// The legacy name for an identifier if there is one, otherwise its name under the naming
+/// profile, where `f` renders it according to the conventions of the language.
+pub fn rename_or(
+    kind: RenameKind,
+    nm: &dyn fmt::Display,
+    f: impl FnOnce(String) -> String,
+) -> String {
+    let nm = nm.to_string();
+    match find_rename(kind, &nm) {
+        Some(renamed) => renamed,
+        None => naming_profile().apply(kind, nm, f),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const UDL: &str = r#"
+        namespace test {
+            u32 get_value(u32 key);
+        };
+        interface Counter {
+            constructor();
+            void increment(u32 by);
+        };
+        enum Color { "Red", "Green" };
+    "#;
+
+    fn renames(toml: &str) -> Renames {
+        toml::de::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_renames_are_scoped() {
+        let renames = renames(
+            r#"
+            [classes]
+            Counter = "LegacyCounter"
+            [functions]
+            get_value = "fetchValue"
+            "#,
+        );
+        assert!(find_rename(RenameKind::Class, "Counter").is_none());
+        with_renames(&renames, NamingProfile::Idiomatic, || {
+            assert_eq!(
+                find_rename(RenameKind::Class, "Counter").as_deref(),
+                Some("LegacyCounter")
+            );
+            assert_eq!(
+                find_rename(RenameKind::Function, "get_value").as_deref(),
+                Some("fetchValue")
+            );
+            assert!(find_rename(RenameKind::Variable, "get_value").is_none());
+        });
+        assert!(find_rename(RenameKind::Function, "get_value").is_none());
+    }
+
+    #[test]
+    fn test_naming_profiles() {
+        let renames = renames("[functions]\nget_value = \"fetchValue\"");
+        let name = |kind, nm: &str| rename_or(kind, &nm, |nm| format!("idiomatic_{}", nm));
+        assert_eq!(
+            name(RenameKind::Variable, "get_value"),
+            "idiomatic_get_value"
+        );
+        with_renames(&renames, NamingProfile::Verbatim, || {
+            assert_eq!(name(RenameKind::Function, "get_value"), "fetchValue");
+            assert_eq!(name(RenameKind::Variable, "get_value"), "get_value");
+            assert_eq!(name(RenameKind::Class, "counter"), "counter");
+        });
+        with_renames(&renames, NamingProfile::CamelCase, || {
+            assert_eq!(name(RenameKind::Variable, "get_value"), "getValue");
+            assert_eq!(name(RenameKind::Class, "counter"), "idiomatic_counter");
+        });
+        with_renames(&renames, NamingProfile::SnakeCase, || {
+            assert_eq!(name(RenameKind::Function, "incrementBy"), "increment_by");
+            assert_eq!(name(RenameKind::EnumVariant, "Red"), "idiomatic_Red");
+        });
+        let config: NamingProfile = toml::Value::String("snake_case".into()).try_into().unwrap();
+        assert_eq!(config, NamingProfile::SnakeCase);
+    }
+
+    #[test]
+    fn test_validate_renames() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let valid = renames(
+            r#"
+            [classes]
+            Counter = "LegacyCounter"
+            [functions]
+            increment = "bump"
+            [variables]
+            by = "amount"
+            [enum_variants]
+            Red = "RED_COLOR"
+            "#,
+        );
+        assert!(validate_renames(&ci, &valid).is_ok());
+
+        let err = validate_renames(&ci, &renames("[functions]\nCounter = \"x\"")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid rename `Counter` in `functions`: the component has no such identifier"
+        );
+    }
+
+    #[test]
+    fn test_merge_prefers_self() {
+        let primary = renames("[functions]\nget_value = \"fromConfig\"");
+        let secondary = renames("[functions]\nget_value = \"fromFile\"\nincrement = \"bump\"");
+        let merged = primary.merge_with(&secondary);
+        assert_eq!(merged.functions["get_value"], "fromConfig");
+        assert_eq!(merged.functions["increment"], "bump");
+    }
+}
@@ -0,0 +1,132 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::fmt;
+
+use crate::backend::{CodeDeclaration, CodeOracle, CodeType, Literal, TypeIdentifier};
+use crate::interface::{CallbackInterface, ComponentInterface};
+use askama::Template;
+
+use super::filters;
+pub struct CallbackInterfaceCodeType {
+    id: String,
+}
+
+impl CallbackInterfaceCodeType {
+    pub fn new(id: String) -> Self {
+        Self { id }
+    }
+}
+
+impl CodeType for CallbackInterfaceCodeType {
+    fn type_label(&self, oracle: &dyn CodeOracle) -> String {
+        oracle.class_name(&self.id)
+    }
+
+    fn canonical_name(&self, oracle: &dyn CodeOracle) -> String {
+        format!("CallbackInterface{}", self.type_label(oracle))
+    }
+
+    fn literal(&self, _oracle: &dyn CodeOracle, _literal: &Literal) -> String {
+        unreachable!();
+    }
+
+    fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Lower({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn write(
+        &self,
+        oracle: &dyn CodeOracle,
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+    ) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Write({}, {})",
+            self.canonical_name(oracle),
+            nm,
+            target
+        )
+    }
+
+    fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Lift({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Read({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn helper_code(&self, oracle: &dyn CodeOracle) -> Option<String> {
+        Some(format!(
+            "// Helper code for {} callback interface is found in CallbackInterfaceTemplate.cs",
+            self.type_label(oracle)
+        ))
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "cs", escape = "none", path = "CallbackInterfaceTemplate.cs")]
+pub struct CSharpCallbackInterface {
+    inner: CallbackInterface,
+}
+
+impl CSharpCallbackInterface {
+    pub fn new(inner: CallbackInterface, _ci: &ComponentInterface) -> Self {
+        Self { inner }
+    }
+    pub fn inner(&self) -> &CallbackInterface {
+        &self.inner
+    }
+}
+
+impl CodeDeclaration for CSharpCallbackInterface {
+    fn initialization_code(&self, oracle: &dyn CodeOracle) -> Option<String> {
+        let code_type = CallbackInterfaceCodeType::new(self.inner.name().into());
+        Some(format!(
+            "FfiConverter{}.INSTANCE.Register();",
+            code_type.canonical_name(oracle)
+        ))
+    }
+
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "cs", escape = "none", path = "CallbackInterfaceRuntime.cs")]
+pub struct CSharpCallbackInterfaceRuntime {
+    is_needed: bool,
+}
+
+impl CSharpCallbackInterfaceRuntime {
+    pub fn new(ci: &ComponentInterface) -> Self {
+        Self {
+            is_needed: !ci.iter_callback_interface_definitions().is_empty(),
+        }
+    }
+}
+
+impl CodeDeclaration for CSharpCallbackInterfaceRuntime {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        if !self.is_needed {
+            None
+        } else {
+            Some(self.render().unwrap())
+        }
+    }
+}
@@ -0,0 +1,192 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::backend::{CodeOracle, CodeType, Literal, TypeIdentifier};
+use askama::Template;
+use paste::paste;
+use std::fmt;
+
+use super::filters;
+
+fn render_literal(
+    oracle: &dyn CodeOracle,
+    literal: &Literal,
+    inner: &TypeIdentifier,
+    outer: &TypeIdentifier,
+) -> String {
+    match literal {
+        Literal::Null => "null".into(),
+        // C# has no literals for collections, so these are only usable where the value doesn't
+        // have to be a compile-time constant.
+        Literal::EmptySequence | Literal::EmptyMap
+            if !matches!(outer, TypeIdentifier::Optional(_)) =>
+        {
+            format!("new {}()", oracle.find(outer).type_label(oracle))
+        }
+
+        // For optionals
+        _ => oracle.find(inner).literal(oracle, literal),
+    }
+}
+
+macro_rules! impl_code_type_for_compound {
+     ($T:ty, $type_label_pattern:literal, $canonical_name_pattern: literal, $template_file:literal) => {
+        paste! {
+            #[derive(Template)]
+            #[template(syntax = "cs", escape = "none", path = $template_file)]
+            pub struct $T {
+                inner: TypeIdentifier,
+                outer: TypeIdentifier,
+            }
+
+            impl $T {
+                pub fn new(inner: TypeIdentifier, outer: TypeIdentifier) -> Self {
+                    Self { inner, outer }
+                }
+                fn inner(&self) -> &TypeIdentifier {
+                    &self.inner
+                }
+                fn outer(&self) -> &TypeIdentifier {
+                    &self.outer
+                }
+            }
+
+            impl CodeType for $T  {
+                fn type_label(&self, oracle: &dyn CodeOracle) -> String {
+                    format!($type_label_pattern, oracle.find(self.inner()).type_label(oracle))
+                }
+
+                fn canonical_name(&self, oracle: &dyn CodeOracle) -> String {
+                    format!($canonical_name_pattern, oracle.find(self.inner()).canonical_name(oracle))
+                }
+
+                fn literal(&self, oracle: &dyn CodeOracle, literal: &Literal) -> String {
+                    render_literal(oracle, &literal, self.inner(), self.outer())
+                }
+
+                fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+                    format!("FfiConverter{}.INSTANCE.Lower({})", self.canonical_name(oracle), nm)
+                }
+
+                fn write(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display, target: &dyn fmt::Display) -> String {
+                    format!("FfiConverter{}.INSTANCE.Write({}, {})", self.canonical_name(oracle), nm, target)
+                }
+
+                fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+                    format!("FfiConverter{}.INSTANCE.Lift({})", self.canonical_name(oracle), nm)
+                }
+
+                fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+                    format!("FfiConverter{}.INSTANCE.Read({})", self.canonical_name(oracle), nm)
+                }
+
+                fn helper_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+                    Some(self.render().unwrap())
+                }
+            }
+        }
+    }
+ }
+
+impl_code_type_for_compound!(OptionalCodeType, "{}?", "Optional{}", "OptionalTemplate.cs");
+impl_code_type_for_compound!(
+    SequenceCodeType,
+    "List<{}>",
+    "Sequence{}",
+    "SequenceTemplate.cs"
+);
+impl_code_type_for_compound!(
+    OrderedMapCodeType,
+    "List<KeyValuePair<string, {}>>",
+    "OrderedMap{}",
+    "OrderedMapTemplate.cs"
+);
+
+// Maps have a key type as well as the value type, so they don't fit the macro above.
+#[derive(Template)]
+#[template(syntax = "cs", escape = "none", path = "MapTemplate.cs")]
+pub struct MapCodeType {
+    key: TypeIdentifier,
+    value: TypeIdentifier,
+    outer: TypeIdentifier,
+}
+
+impl MapCodeType {
+    pub fn new(key: TypeIdentifier, value: TypeIdentifier, outer: TypeIdentifier) -> Self {
+        Self { key, value, outer }
+    }
+    fn key(&self) -> &TypeIdentifier {
+        &self.key
+    }
+    fn inner(&self) -> &TypeIdentifier {
+        &self.value
+    }
+    fn outer(&self) -> &TypeIdentifier {
+        &self.outer
+    }
+}
+
+impl CodeType for MapCodeType {
+    fn type_label(&self, oracle: &dyn CodeOracle) -> String {
+        format!(
+            "Dictionary<{}, {}>",
+            oracle.find(self.key()).type_label(oracle),
+            oracle.find(self.inner()).type_label(oracle)
+        )
+    }
+
+    fn canonical_name(&self, oracle: &dyn CodeOracle) -> String {
+        format!(
+            "Map{}_{}",
+            oracle.find(self.key()).canonical_name(oracle),
+            oracle.find(self.inner()).canonical_name(oracle)
+        )
+    }
+
+    fn literal(&self, oracle: &dyn CodeOracle, literal: &Literal) -> String {
+        render_literal(oracle, literal, self.inner(), self.outer())
+    }
+
+    fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Lower({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn write(
+        &self,
+        oracle: &dyn CodeOracle,
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+    ) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Write({}, {})",
+            self.canonical_name(oracle),
+            nm,
+            target
+        )
+    }
+
+    fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Lift({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Read({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn helper_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+}
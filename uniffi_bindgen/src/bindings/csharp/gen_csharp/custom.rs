@@ -0,0 +1,134 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::backend::{CodeOracle, CodeType, CustomTypeConfig, Literal};
+use crate::interface::FFIType;
+use askama::Template;
+use std::fmt;
+
+// A type that's been mapped onto a custom C# type in `uniffi.toml`.
+//
+// Values are passed over the FFI exactly like the `builtin` type they replace, and are
+// converted to and from the custom type by the helper functions in `CustomTypeTemplate.cs`.
+pub struct CustomCodeType {
+    config: CustomTypeConfig,
+    builtin: Box<dyn CodeType>,
+    ffi_type: FFIType,
+}
+
+impl CustomCodeType {
+    pub fn new(config: CustomTypeConfig, builtin: Box<dyn CodeType>, ffi_type: FFIType) -> Self {
+        CustomCodeType {
+            config,
+            builtin,
+            ffi_type,
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "cs", escape = "none", path = "CustomTypeTemplate.cs")]
+struct CustomTypeHelper {
+    canonical_name: String,
+    type_name: String,
+    builtin_type_name: String,
+    ffi_type_name: String,
+    into_custom: String,
+    from_custom: String,
+    lift_builtin: String,
+    read_builtin: String,
+    lower_builtin: String,
+    write_builtin: String,
+}
+
+impl CodeType for CustomCodeType {
+    fn type_label(&self, oracle: &dyn CodeOracle) -> String {
+        self.config
+            .type_name
+            .clone()
+            .unwrap_or_else(|| self.builtin.type_label(oracle))
+    }
+
+    fn canonical_name(&self, oracle: &dyn CodeOracle) -> String {
+        format!("Custom{}", self.builtin.canonical_name(oracle))
+    }
+
+    fn literal(&self, oracle: &dyn CodeOracle, literal: &Literal) -> String {
+        self.config
+            .into_custom(&self.builtin.literal(oracle, literal))
+    }
+
+    fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Lower({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn write(
+        &self,
+        oracle: &dyn CodeOracle,
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+    ) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Write({}, {})",
+            self.canonical_name(oracle),
+            nm,
+            target
+        )
+    }
+
+    fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Lift({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Read({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn helper_code(&self, oracle: &dyn CodeOracle) -> Option<String> {
+        let helper = CustomTypeHelper {
+            canonical_name: self.canonical_name(oracle),
+            type_name: self.type_label(oracle),
+            builtin_type_name: self.builtin.type_label(oracle),
+            ffi_type_name: oracle.ffi_type_label(&self.ffi_type),
+            into_custom: self.config.into_custom("builtinValue"),
+            from_custom: self.config.from_custom("value"),
+            lift_builtin: self.builtin.lift(oracle, &"value"),
+            read_builtin: self.builtin.read(oracle, &"reader"),
+            lower_builtin: self.builtin.lower(oracle, &"builtinValue"),
+            write_builtin: self.builtin.write(oracle, &"builtinValue", &"buf"),
+        };
+        // The builtin type's own helpers are still needed to do the actual lifting and lowering.
+        Some(
+            self.builtin
+                .helper_code(oracle)
+                .into_iter()
+                .chain(std::iter::once(helper.render().unwrap()))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
+    fn imports(&self, oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        Some(
+            self.builtin
+                .imports(oracle)
+                .unwrap_or_default()
+                .into_iter()
+                .chain(self.config.imports.iter().cloned())
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,107 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::fmt;
+
+use crate::backend::{CodeDeclaration, CodeOracle, CodeType, Literal, TypeIdentifier};
+use crate::interface::{ComponentInterface, Error};
+use askama::Template;
+
+use super::filters;
+pub struct ErrorCodeType {
+    id: String,
+}
+
+impl ErrorCodeType {
+    pub fn new(id: String) -> Self {
+        Self { id }
+    }
+}
+
+impl CodeType for ErrorCodeType {
+    fn type_label(&self, oracle: &dyn CodeOracle) -> String {
+        oracle.error_name(&self.id)
+    }
+
+    fn canonical_name(&self, oracle: &dyn CodeOracle) -> String {
+        format!("Error{}", self.type_label(oracle))
+    }
+
+    fn literal(&self, _oracle: &dyn CodeOracle, _literal: &Literal) -> String {
+        unreachable!();
+    }
+
+    fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Lower({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn write(
+        &self,
+        oracle: &dyn CodeOracle,
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+    ) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Write({}, {})",
+            self.canonical_name(oracle),
+            nm,
+            target
+        )
+    }
+
+    fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Lift({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Read({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn helper_code(&self, oracle: &dyn CodeOracle) -> Option<String> {
+        Some(format!(
+            "// Helper code for {} error is found in ErrorTemplate.cs",
+            self.type_label(oracle)
+        ))
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "cs", escape = "none", path = "ErrorTemplate.cs")]
+pub struct CSharpError {
+    inner: Error,
+    contains_object_references: bool,
+}
+
+impl CSharpError {
+    pub fn new(inner: Error, ci: &ComponentInterface) -> Self {
+        Self {
+            contains_object_references: ci.item_contains_object_references(&inner),
+            inner,
+        }
+    }
+    pub fn inner(&self) -> &Error {
+        &self.inner
+    }
+    pub fn contains_object_references(&self) -> bool {
+        self.contains_object_references
+    }
+}
+
+impl CodeDeclaration for CSharpError {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+}
@@ -0,0 +1,93 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::backend::{CodeDeclaration, CodeOracle};
+use crate::interface::{ComponentInterface, Function, Module};
+use askama::Template;
+use heck::CamelCase;
+
+use super::{filters, CSharpCodeOracle};
+
+#[derive(Template)]
+#[template(syntax = "cs", escape = "none", path = "TopLevelFunctionTemplate.cs")]
+pub struct CSharpFunction {
+    inner: Function,
+}
+
+impl CSharpFunction {
+    pub fn new(inner: Function, _ci: &ComponentInterface) -> Self {
+        Self { inner }
+    }
+    pub fn inner(&self) -> &Function {
+        &self.inner
+    }
+}
+
+impl CodeDeclaration for CSharpFunction {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+}
+
+// C# has no free functions, so each namespace is a static class holding its functions
+// and the classes of any namespaces nested inside it.
+#[derive(Template)]
+#[template(syntax = "cs", escape = "none", path = "ModuleTemplate.cs")]
+pub struct CSharpModule {
+    name: String,
+    functions: Vec<CSharpFunction>,
+    children: Vec<CSharpModule>,
+}
+
+impl CSharpModule {
+    /// The class for the functions declared directly in the component's namespace, such as
+    /// `ArithmeticMethods` for the `arithmetic` namespace.
+    pub fn for_namespace(ci: &ComponentInterface) -> Self {
+        Self {
+            name: format!("{}Methods", ci.namespace().to_camel_case()),
+            functions: ci
+                .iter_namespace_function_definitions()
+                .into_iter()
+                .map(|func| CSharpFunction::new(func, ci))
+                .collect(),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn new(module: Module, ci: &ComponentInterface) -> Self {
+        Self {
+            name: CSharpCodeOracle.class_name(&module.name()),
+            functions: ci
+                .iter_module_function_definitions(&module)
+                .into_iter()
+                .map(|func| CSharpFunction::new(func, ci))
+                .collect(),
+            children: ci
+                .iter_child_module_definitions(&module)
+                .into_iter()
+                .map(|child| CSharpModule::new(child, ci))
+                .collect(),
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn member_code(&self) -> Vec<String> {
+        self.functions
+            .iter()
+            .map(|func| func.render().unwrap())
+            .chain(self.children.iter().map(|module| module.render().unwrap()))
+            .collect()
+    }
+}
+
+impl CodeDeclaration for CSharpModule {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        if self.functions.is_empty() && self.children.is_empty() {
+            None
+        } else {
+            Some(self.render().unwrap())
+        }
+    }
+}
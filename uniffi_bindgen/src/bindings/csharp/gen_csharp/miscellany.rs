@@ -0,0 +1,80 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::backend::{CodeOracle, CodeType, Literal};
+use askama::Template;
+use paste::paste;
+use std::fmt;
+
+#[allow(unused_imports)]
+use super::filters;
+
+macro_rules! impl_code_type_for_miscellany {
+     ($T:ty, $class_name:literal, $canonical_name:literal, $template_file:literal) => {
+         paste! {
+             #[derive(Template)]
+             #[template(syntax = "cs", escape = "none", path = $template_file )]
+             pub struct $T;
+
+             impl CodeType for $T  {
+                 fn type_label(&self, _oracle: &dyn CodeOracle) -> String {
+                     $class_name.into()
+                 }
+
+                 fn canonical_name(&self, _oracle: &dyn CodeOracle) -> String {
+                    $canonical_name.into()
+                }
+
+                 fn literal(&self, _oracle: &dyn CodeOracle, _literal: &Literal) -> String {
+                     unreachable!()
+                 }
+
+                 fn lift(&self, _oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+                     format!("FfiConverter{}.INSTANCE.Lift({})", $canonical_name, nm)
+                 }
+
+                 fn read(&self, _oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+                     format!("FfiConverter{}.INSTANCE.Read({})", $canonical_name, nm)
+                 }
+
+                 fn lower(&self, _oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+                     format!("FfiConverter{}.INSTANCE.Lower({})", $canonical_name, nm)
+                 }
+
+                 fn write(&self, _oracle: &dyn CodeOracle, nm: &dyn fmt::Display, target: &dyn fmt::Display) -> String {
+                     format!("FfiConverter{}.INSTANCE.Write({}, {})", $canonical_name, nm, target)
+                 }
+
+                 fn helper_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+                     Some(self.render().unwrap())
+                 }
+             }
+         }
+     }
+ }
+
+impl_code_type_for_miscellany!(
+    BigIntCodeType,
+    "System.Numerics.BigInteger",
+    "BigInt",
+    "BigIntHelper.cs"
+);
+
+impl_code_type_for_miscellany!(BytesCodeType, "byte[]", "Bytes", "BytesHelper.cs");
+
+impl_code_type_for_miscellany!(CharCodeType, "System.Text.Rune", "Char", "CharHelper.cs");
+
+impl_code_type_for_miscellany!(
+    TimestampCodeType,
+    "DateTimeOffset",
+    "Timestamp",
+    "TimestampHelper.cs"
+);
+
+impl_code_type_for_miscellany!(
+    DurationCodeType,
+    "TimeSpan",
+    "Duration",
+    "DurationHelper.cs"
+);
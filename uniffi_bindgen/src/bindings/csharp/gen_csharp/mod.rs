@@ -0,0 +1,786 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use anyhow::Result;
+use askama::Template;
+use heck::{CamelCase, MixedCase};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::{
+    custom_types, renames, renames::RenameKind, ApiFilter, CodeDeclaration, CodeOracle, CodeType,
+    CustomTypesConfig, NamingProfile, Renames, TypeIdentifier,
+};
+use crate::interface::*;
+use crate::lockfile::type_decl;
+use crate::MergeWith;
+
+mod callback_interface;
+mod compounds;
+mod custom;
+mod enum_;
+mod error;
+mod function;
+mod miscellany;
+mod object;
+mod primitives;
+mod record;
+mod wrapped;
+
+// Some config options for it the caller wants to customize the generated C#.
+// Note that this can only be used to control details of the C# *that do not affect the underlying component*,
+// sine the details of the underlying component are entirely determined by the `ComponentInterface`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Config {
+    namespace: Option<String>,
+    cdylib_name: Option<String>,
+    #[serde(default)]
+    custom_types: CustomTypesConfig,
+    #[serde(default)]
+    renames: Renames,
+    naming: Option<NamingProfile>,
+    #[serde(default)]
+    foreign_extensions: BTreeMap<String, String>,
+    #[serde(default)]
+    filter: ApiFilter,
+}
+
+impl Config {
+    /// The C# namespace that the bindings are declared in.
+    pub fn namespace(&self) -> String {
+        if let Some(namespace) = &self.namespace {
+            namespace.clone()
+        } else {
+            "uniffi".into()
+        }
+    }
+
+    pub fn cdylib_name(&self) -> String {
+        if let Some(cdylib_name) = &self.cdylib_name {
+            cdylib_name.clone()
+        } else {
+            "uniffi".into()
+        }
+    }
+
+    /// Types mapped onto custom C# types, keyed by their UDL name.
+    pub fn custom_types(&self) -> &CustomTypesConfig {
+        &self.custom_types
+    }
+
+    /// Legacy C# names for identifiers in the component, see [`crate::backend::renames`].
+    pub fn renames(&self) -> &Renames {
+        &self.renames
+    }
+
+    /// How to name the identifiers that aren't renamed, see [`crate::backend::renames`].
+    pub fn naming(&self) -> NamingProfile {
+        self.naming.unwrap_or_default()
+    }
+
+    /// The namespace to import a `[ForeignExtension]` interface from, when it isn't in the
+    /// same namespace as the bindings.
+    pub fn foreign_extension_namespace(&self, name: &str) -> Option<&str> {
+        self.foreign_extensions.get(name).map(String::as_str)
+    }
+
+    /// The part of the component to generate bindings for, see [`crate::backend::filter`].
+    pub fn filter(&self) -> &ApiFilter {
+        &self.filter
+    }
+
+    /// Fill in any renames not already configured from those in a `renames.toml` file.
+    pub fn with_renames_from(&self, renames: &Renames) -> Self {
+        Config {
+            renames: self.renames.merge_with(renames),
+            ..self.clone()
+        }
+    }
+}
+
+impl From<&ComponentInterface> for Config {
+    fn from(ci: &ComponentInterface) -> Self {
+        Config {
+            namespace: Some(format!("uniffi.{}", ci.namespace())),
+            cdylib_name: Some(format!("uniffi_{}", ci.namespace())),
+            custom_types: Default::default(),
+            renames: Default::default(),
+            naming: None,
+            foreign_extensions: Default::default(),
+            filter: Default::default(),
+        }
+    }
+}
+
+impl MergeWith for Config {
+    fn merge_with(&self, other: &Self) -> Self {
+        Config {
+            namespace: self.namespace.merge_with(&other.namespace),
+            cdylib_name: self.cdylib_name.merge_with(&other.cdylib_name),
+            custom_types: self.custom_types.merge_with(&other.custom_types),
+            renames: self.renames.merge_with(&other.renames),
+            naming: self.naming.merge_with(&other.naming),
+            foreign_extensions: self
+                .foreign_extensions
+                .merge_with(&other.foreign_extensions),
+            filter: self.filter.merge_with(&other.filter),
+        }
+    }
+}
+
+// Whether the literal can be a C# default value, which must be a compile-time constant.
+fn is_constant(literal: &Literal) -> bool {
+    !matches!(literal, Literal::EmptySequence | Literal::EmptyMap)
+}
+
+/// What the C# backend can generate, apart from the default values it can't.
+pub fn capabilities() -> Capabilities {
+    Capabilities::all().without(&[
+        Feature::Batchable,
+        Feature::Async,
+        Feature::AlsoAsync,
+        Feature::NoCopy,
+        Feature::WeakRefs,
+        Feature::DirectCallbackInterfaces,
+        Feature::ThrowingCallbackMethods,
+        Feature::ExternalTypes,
+    ])
+}
+
+// The things in the component that the C# backend doesn't support yet, to report them all
+// at once.
+pub(super) fn unsupported_features(ci: &ComponentInterface) -> Vec<String> {
+    let mut problems = Vec::new();
+    // C# only lets trailing arguments have defaults, and they must be constants.
+    let mut check_defaults = |what: String, defaults: Vec<(String, Option<Literal>)>| {
+        let mut seen_default = false;
+        for (name, default) in defaults {
+            match default {
+                Some(literal) if !is_constant(&literal) => {
+                    problems.push(format!("the default value of `{}` in {}", name, what))
+                }
+                Some(_) => seen_default = true,
+                None if seen_default => problems.push(format!(
+                    "`{}` in {}, which has no default value but follows one that does",
+                    name, what
+                )),
+                None => (),
+            }
+        }
+    };
+    let arg_defaults = |args: Vec<&Argument>| {
+        args.iter()
+            .map(|arg| (arg.name().to_string(), arg.default_value()))
+            .collect::<Vec<_>>()
+    };
+    for func in ci.iter_function_definitions() {
+        let what = format!("function `{}`", func.qualified_name());
+        check_defaults(what, arg_defaults(func.arguments()));
+    }
+    for obj in ci.iter_object_definitions() {
+        for cons in obj.constructors() {
+            let what = format!("constructor `{}.{}`", obj.name(), cons.name());
+            check_defaults(what, arg_defaults(cons.arguments()));
+        }
+        for meth in obj.methods() {
+            let what = format!("method `{}.{}`", obj.name(), meth.name());
+            check_defaults(what, arg_defaults(meth.arguments()));
+        }
+    }
+    for rec in ci.iter_record_definitions() {
+        let what = format!("dictionary `{}`", rec.name());
+        let defaults = rec
+            .fields()
+            .iter()
+            .map(|field| (field.name().to_string(), field.default_value()))
+            .collect();
+        check_defaults(what, defaults);
+    }
+    for type_ in ci.iter_types() {
+        if let Type::Optional(inner) = &type_ {
+            if matches!(inner.as_ref(), Type::Optional(_)) {
+                problems.push(format!("nested optional type {}", type_decl(&type_)))
+            }
+        }
+    }
+    problems.extend(ci.unsupported_features(&capabilities()));
+    problems
+}
+
+#[derive(Template)]
+#[template(syntax = "cs", escape = "none", path = "wrapper.cs")]
+pub struct CSharpWrapper<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+    oracle: CSharpCodeOracle,
+}
+impl<'a> CSharpWrapper<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self {
+            config,
+            ci,
+            oracle: Default::default(),
+        }
+    }
+
+    /// The version of `uniffi-bindgen` that's generating the bindings.
+    pub fn bindgen_version(&self) -> &'static str {
+        crate::BINDGEN_VERSION
+    }
+
+    pub fn members(&self) -> Vec<Box<dyn CodeDeclaration + 'a>> {
+        let ci = self.ci;
+        vec![
+            Box::new(object::CSharpObjectRuntime::new(ci)) as Box<dyn CodeDeclaration>,
+            Box::new(callback_interface::CSharpCallbackInterfaceRuntime::new(ci))
+                as Box<dyn CodeDeclaration>,
+            Box::new(function::CSharpModule::for_namespace(ci)) as Box<dyn CodeDeclaration>,
+        ]
+        .into_iter()
+        .chain(
+            ci.iter_enum_definitions().into_iter().map(|inner| {
+                Box::new(enum_::CSharpEnum::new(inner, ci)) as Box<dyn CodeDeclaration>
+            }),
+        )
+        .chain(
+            ci.iter_top_level_module_definitions()
+                .into_iter()
+                .map(|inner| {
+                    Box::new(function::CSharpModule::new(inner, ci)) as Box<dyn CodeDeclaration>
+                }),
+        )
+        .chain(ci.iter_object_definitions().into_iter().map(|inner| {
+            if inner.is_opaque_token() {
+                return Box::new(object::CSharpOpaqueToken::new(inner, ci))
+                    as Box<dyn CodeDeclaration>;
+            }
+            let extension_import = inner.foreign_extension().and_then(|ext| {
+                self.config
+                    .foreign_extension_namespace(ext)
+                    .map(String::from)
+            });
+            Box::new(object::CSharpObject::new(inner, ci, extension_import))
+                as Box<dyn CodeDeclaration>
+        }))
+        .chain(ci.iter_record_definitions().into_iter().map(|inner| {
+            Box::new(record::CSharpRecord::new(inner, ci)) as Box<dyn CodeDeclaration>
+        }))
+        .chain(
+            ci.iter_error_definitions().into_iter().map(|inner| {
+                Box::new(error::CSharpError::new(inner, ci)) as Box<dyn CodeDeclaration>
+            }),
+        )
+        .chain(
+            ci.iter_callback_interface_definitions()
+                .into_iter()
+                .map(|inner| {
+                    Box::new(callback_interface::CSharpCallbackInterface::new(inner, ci))
+                        as Box<dyn CodeDeclaration>
+                }),
+        )
+        .collect()
+    }
+
+    pub fn initialization_code(&self) -> Vec<String> {
+        let oracle = &self.oracle;
+        self.members()
+            .into_iter()
+            .filter_map(|member| member.initialization_code(oracle))
+            .collect()
+    }
+
+    pub fn declaration_code(&self) -> Vec<String> {
+        let oracle = &self.oracle;
+        self.members()
+            .into_iter()
+            .filter_map(|member| member.definition_code(oracle))
+            .chain(
+                self.ci
+                    .iter_types()
+                    .into_iter()
+                    .filter_map(|type_| oracle.find(&type_).helper_code(oracle)),
+            )
+            .collect()
+    }
+
+    pub fn imports(&self) -> Vec<String> {
+        let oracle = &self.oracle;
+        // Collecting into a `BTreeSet` removes duplicates and keeps the imports in a stable order.
+        self.members()
+            .into_iter()
+            .filter_map(|member| member.imports(oracle))
+            .flatten()
+            .chain(
+                self.ci
+                    .iter_types()
+                    .into_iter()
+                    .filter_map(|type_| oracle.find(&type_).imports(oracle))
+                    .flatten(),
+            )
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect()
+    }
+}
+
+// The C# keywords, which can only be used as identifiers with an `@` in front.
+const KEYWORDS: &[&str] = &[
+    "abstract",
+    "as",
+    "base",
+    "bool",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "checked",
+    "class",
+    "const",
+    "continue",
+    "decimal",
+    "default",
+    "delegate",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "event",
+    "explicit",
+    "extern",
+    "false",
+    "finally",
+    "fixed",
+    "float",
+    "for",
+    "foreach",
+    "goto",
+    "if",
+    "implicit",
+    "in",
+    "int",
+    "interface",
+    "internal",
+    "is",
+    "lock",
+    "long",
+    "namespace",
+    "new",
+    "null",
+    "object",
+    "operator",
+    "out",
+    "override",
+    "params",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "ref",
+    "return",
+    "sbyte",
+    "sealed",
+    "short",
+    "sizeof",
+    "stackalloc",
+    "static",
+    "string",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "uint",
+    "ulong",
+    "unchecked",
+    "unsafe",
+    "ushort",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+];
+
+#[derive(Default)]
+pub struct CSharpCodeOracle;
+
+impl CSharpCodeOracle {
+    fn create_code_type(&self, type_: TypeIdentifier) -> Box<dyn CodeType> {
+        match custom_types::find_custom_type(&type_) {
+            Some(config) => Box::new(custom::CustomCodeType::new(
+                config,
+                self.create_builtin_code_type(type_.clone()),
+                FFIType::from(&type_),
+            )),
+            None => self.create_builtin_code_type(type_),
+        }
+    }
+
+    fn create_builtin_code_type(&self, type_: TypeIdentifier) -> Box<dyn CodeType> {
+        match type_ {
+            Type::UInt8 => Box::new(primitives::UInt8CodeType),
+            Type::Int8 => Box::new(primitives::Int8CodeType),
+            Type::UInt16 => Box::new(primitives::UInt16CodeType),
+            Type::Int16 => Box::new(primitives::Int16CodeType),
+            Type::UInt32 => Box::new(primitives::UInt32CodeType),
+            Type::Int32 => Box::new(primitives::Int32CodeType),
+            Type::UInt64 => Box::new(primitives::UInt64CodeType),
+            Type::Int64 => Box::new(primitives::Int64CodeType),
+            Type::Float32 => Box::new(primitives::Float32CodeType),
+            Type::Float64 => Box::new(primitives::Float64CodeType),
+            Type::Boolean => Box::new(primitives::BooleanCodeType),
+            Type::String => Box::new(primitives::StringCodeType),
+
+            Type::BigInt => Box::new(miscellany::BigIntCodeType),
+            Type::Bytes => Box::new(miscellany::BytesCodeType),
+            Type::Char => Box::new(miscellany::CharCodeType),
+            Type::Timestamp => Box::new(miscellany::TimestampCodeType),
+            Type::Duration => Box::new(miscellany::DurationCodeType),
+
+            Type::Enum(id) => Box::new(enum_::EnumCodeType::new(id)),
+            Type::Object(id) => Box::new(object::ObjectCodeType::new(id)),
+            Type::Record(id) => Box::new(record::RecordCodeType::new(id)),
+            Type::Error(id) => Box::new(error::ErrorCodeType::new(id)),
+            Type::CallbackInterface(id) => {
+                Box::new(callback_interface::CallbackInterfaceCodeType::new(id))
+            }
+
+            Type::Optional(ref inner) => {
+                let outer = type_.clone();
+                let inner = *inner.to_owned();
+                Box::new(compounds::OptionalCodeType::new(inner, outer))
+            }
+            Type::Sequence(ref inner) => {
+                let outer = type_.clone();
+                let inner = *inner.to_owned();
+                Box::new(compounds::SequenceCodeType::new(inner, outer))
+            }
+            Type::Map(ref key, ref value) => {
+                let outer = type_.clone();
+                let key = *key.to_owned();
+                let value = *value.to_owned();
+                Box::new(compounds::MapCodeType::new(key, value, outer))
+            }
+            Type::OrderedMap(ref inner) => {
+                let outer = type_.clone();
+                let inner = *inner.to_owned();
+                Box::new(compounds::OrderedMapCodeType::new(inner, outer))
+            }
+            Type::External { .. } | Type::ExternalInterface { .. } => {
+                panic!("no support for external types yet")
+            }
+            Type::Wrapped { name, prim } => Box::new(wrapped::WrappedCodeType::new(
+                name,
+                self.create_code_type(prim.as_ref().clone()),
+            )),
+        }
+    }
+}
+
+impl CodeOracle for CSharpCodeOracle {
+    fn find(&self, type_: &TypeIdentifier) -> Box<dyn CodeType> {
+        self.create_code_type(type_.clone())
+    }
+
+    /// Get the idiomatic C# rendering of a class name (for enums, records, errors, etc).
+    fn class_name(&self, nm: &dyn fmt::Display) -> String {
+        renames::rename_or(RenameKind::Class, nm, |nm| nm.to_camel_case())
+    }
+
+    /// Get the idiomatic C# rendering of a function name, which is in `PascalCase`
+    /// like every other member.
+    fn fn_name(&self, nm: &dyn fmt::Display) -> String {
+        renames::rename_or(RenameKind::Function, nm, |nm| nm.to_camel_case())
+    }
+
+    /// Get the idiomatic C# rendering of a variable name, escaping it if it's a keyword.
+    fn var_name(&self, nm: &dyn fmt::Display) -> String {
+        let nm = renames::rename_or(RenameKind::Variable, nm, |nm| nm.to_mixed_case());
+        if KEYWORDS.contains(&nm.as_str()) {
+            format!("@{}", nm)
+        } else {
+            nm
+        }
+    }
+
+    /// Get the idiomatic C# rendering of an individual enum variant.
+    fn enum_variant_name(&self, nm: &dyn fmt::Display) -> String {
+        renames::rename_or(RenameKind::EnumVariant, nm, |nm| nm.to_camel_case())
+    }
+
+    /// Get the idiomatic C# rendering of an exception name
+    ///
+    /// This replaces "Error" at the end of the name with "Exception", which is what .NET
+    /// calls the things that get thrown. A legacy name configured in `renames.toml` is
+    /// used as-is.
+    fn error_name(&self, nm: &dyn fmt::Display) -> String {
+        renames::rename_or(RenameKind::Class, nm, |name| {
+            match name.strip_suffix("Error") {
+                None => name,
+                Some(stripped) => {
+                    let mut cs_exc_name = stripped.to_owned();
+                    cs_exc_name.push_str("Exception");
+                    cs_exc_name
+                }
+            }
+        })
+    }
+
+    fn ffi_type_label(&self, ffi_type: &FFIType) -> String {
+        match ffi_type {
+            FFIType::Int8 => "sbyte".to_string(),
+            FFIType::UInt8 => "byte".to_string(),
+            FFIType::Int16 => "short".to_string(),
+            FFIType::UInt16 => "ushort".to_string(),
+            FFIType::Int32 => "int".to_string(),
+            FFIType::UInt32 => "uint".to_string(),
+            FFIType::Int64 => "long".to_string(),
+            FFIType::UInt64 => "ulong".to_string(),
+            FFIType::Float32 => "float".to_string(),
+            FFIType::Float64 => "double".to_string(),
+            FFIType::RustArcPtr => "IntPtr".to_string(),
+            FFIType::RustBuffer => "RustBuffer".to_string(),
+            FFIType::ForeignBytes => "ForeignBytes".to_string(),
+            FFIType::ForeignCallback => "ForeignCallback".to_string(),
+            FFIType::ForeignMethodCallback(name) => name.clone(),
+            FFIType::AsyncCompletion => unimplemented!("[Async] functions are not implemented"),
+        }
+    }
+}
+
+// The members of `System.Exception`, which the properties of an error's fields would hide.
+const EXCEPTION_MEMBERS: &[&str] = &[
+    "Data",
+    "HelpLink",
+    "HResult",
+    "InnerException",
+    "Message",
+    "Source",
+    "StackTrace",
+    "TargetSite",
+];
+
+pub mod filters {
+    use super::*;
+    use std::fmt;
+
+    fn oracle() -> impl CodeOracle {
+        CSharpCodeOracle
+    }
+
+    pub fn type_name(codetype: &impl CodeType) -> Result<String, askama::Error> {
+        Ok(codetype.type_label(&oracle()))
+    }
+
+    pub fn canonical_name(codetype: &impl CodeType) -> Result<String, askama::Error> {
+        Ok(codetype.canonical_name(&oracle()))
+    }
+
+    // Unlike the other backends, the C# code types use the expressions they're given as they
+    // are, so templates pass variable names through `var_name` themselves. That way they can
+    // also lower and write properties, like `value.Text`.
+
+    pub fn lower_var(
+        nm: &dyn fmt::Display,
+        codetype: &impl CodeType,
+    ) -> Result<String, askama::Error> {
+        Ok(codetype.lower(&oracle(), nm))
+    }
+
+    pub fn write_var(
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+        codetype: &impl CodeType,
+    ) -> Result<String, askama::Error> {
+        Ok(codetype.write(&oracle(), nm, target))
+    }
+
+    pub fn lift_var(
+        nm: &dyn fmt::Display,
+        codetype: &impl CodeType,
+    ) -> Result<String, askama::Error> {
+        Ok(codetype.lift(&oracle(), nm))
+    }
+
+    pub fn read_var(
+        nm: &dyn fmt::Display,
+        codetype: &impl CodeType,
+    ) -> Result<String, askama::Error> {
+        Ok(codetype.read(&oracle(), nm))
+    }
+
+    pub fn render_literal(
+        literal: &Literal,
+        codetype: &impl CodeType,
+    ) -> Result<String, askama::Error> {
+        Ok(codetype.literal(&oracle(), literal))
+    }
+
+    /// The handler for the errors that a call into Rust can throw, which is the converter of
+    /// the error it's declared to throw, if any.
+    pub fn error_handler(throws: &Option<Type>) -> Result<String, askama::Error> {
+        Ok(match throws {
+            Some(error) => format!("FfiConverter{}.INSTANCE", canonical_name(error)?),
+            None => "NullCallStatusErrorHandler.INSTANCE".into(),
+        })
+    }
+
+    /// Get the C# syntax for representing a given low-level `FFIType`.
+    pub fn ffi_type_name(type_: &FFIType) -> Result<String, askama::Error> {
+        Ok(oracle().ffi_type_label(type_))
+    }
+
+    /// Get the idiomatic C# rendering of a class name (for enums, records, errors, etc).
+    pub fn class_name(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(oracle().class_name(nm))
+    }
+
+    /// Get the idiomatic C# rendering of a function name.
+    pub fn fn_name(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(oracle().fn_name(nm))
+    }
+
+    /// Get the idiomatic C# rendering of a variable name.
+    pub fn var_name(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(oracle().var_name(nm))
+    }
+
+    /// Get the idiomatic C# rendering of a property, for the fields of records, enum
+    /// variants and errors.
+    pub fn property_name(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(renames::rename_or(RenameKind::Variable, nm, |nm| {
+            nm.to_camel_case()
+        }))
+    }
+
+    /// Whether the property for an error's field hides one that every `Exception` has, and
+    /// so needs to be declared `new`.
+    pub fn hides_exception_member(nm: &dyn fmt::Display) -> Result<bool, askama::Error> {
+        Ok(EXCEPTION_MEMBERS.contains(&property_name(nm)?.as_str()))
+    }
+
+    /// Get the idiomatic C# rendering of an individual enum variant.
+    pub fn enum_variant(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(oracle().enum_variant_name(nm))
+    }
+
+    /// Get the idiomatic C# rendering of an exception name, see
+    /// [`CSharpCodeOracle::error_name`].
+    pub fn exception_name(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(oracle().error_name(nm))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn generate(udl: &str) -> String {
+        let ci = ComponentInterface::from_webidl(udl).unwrap();
+        super::super::generate_bindings(&Config::from(&ci), &ci).unwrap()
+    }
+
+    #[test]
+    fn test_csharp_bindings() {
+        let bindings = generate(
+            r#"
+            namespace todolist {
+                [Throws=TodoError]
+                TodoEntry get_first(sequence<TodoEntry> entries);
+            };
+            dictionary TodoEntry {
+                string text;
+                u32 priority = 1;
+            };
+            enum Shape { "Circle", "Square" };
+            [Enum]
+            interface Change {
+                Added(string text);
+                Cleared();
+            };
+            [Error]
+            enum TodoError { "EmptyList" };
+            interface TodoList {
+                constructor();
+                [Name=with_entries]
+                constructor(sequence<TodoEntry> entries);
+                void add_entry(TodoEntry entry);
+                string? last_text(string event);
+            };
+            callback interface OnChange {
+                void changed(Change change);
+            };
+        "#,
+        );
+        assert!(bindings.contains("namespace uniffi.todolist;"));
+        assert!(bindings.contains("public static class TodolistMethods"));
+        assert!(bindings.contains("public static TodoEntry GetFirst(List<TodoEntry> entries)"));
+        assert!(bindings.contains("public record TodoEntry("));
+        assert!(bindings.contains("uint Priority = 1u"));
+        assert!(bindings.contains("public enum Shape"));
+        assert!(bindings.contains("public abstract record Change"));
+        assert!(bindings.contains("public sealed record Added(string Text) : Change"));
+        assert!(bindings.contains("public class TodoException : Exception"));
+        assert!(bindings.contains("public class EmptyList : TodoException"));
+        assert!(bindings.contains("public class TodoList : FFIObject, ITodoList"));
+        assert!(bindings.contains("public static TodoList WithEntries(List<TodoEntry> entries)"));
+        assert!(bindings.contains("public string? LastText(string @event)"));
+        assert!(bindings.contains("public interface OnChange"));
+        assert!(bindings.contains("FfiConverterCallbackInterfaceOnChange.INSTANCE.Register();"));
+        assert!(bindings.contains(
+            "[DllImport(\"uniffi_todolist\", CallingConvention = CallingConvention.Cdecl)]"
+        ));
+    }
+
+    #[test]
+    fn test_csharp_unsupported_features() {
+        const UDL: &str = r#"
+            namespace shapes {
+                void draw(optional u32 width = 1, string label);
+                void fill(sequence<string> colors = []);
+            };
+            [External="geometry"]
+            typedef extern Point;
+            dictionary Canvas { Point origin; };
+            [Error]
+            enum PaintError { "OutOfPaint" };
+            [Direct]
+            callback interface Painter {
+                void paint(u32 color);
+                [Throws=PaintError]
+                void mix(u32 color);
+                [Async]
+                void dry();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let err = super::super::generate_bindings(&Config::from(&ci), &ci).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The C# backend doesn't support:
+  `label` in function `draw`, which has no default value but follows one that does
+  the default value of `colors` in function `fill`
+  callback interface `Painter`, which is `[Direct]`
+  callback method `Painter.mix`, which throws
+  callback method `Painter.dry`, which is `[Async]`
+  external type Point"
+        );
+    }
+
+    #[test]
+    fn test_csharp_names() {
+        let oracle = CSharpCodeOracle;
+        assert_eq!(oracle.class_name(&"todo_list"), "TodoList");
+        assert_eq!(oracle.fn_name(&"get_last_entry"), "GetLastEntry");
+        assert_eq!(oracle.var_name(&"entry_count"), "entryCount");
+        assert_eq!(oracle.var_name(&"event"), "@event");
+        assert_eq!(oracle.enum_variant_name(&"DARK_RED"), "DarkRed");
+        assert_eq!(oracle.error_name(&"TodoError"), "TodoException");
+    }
+}
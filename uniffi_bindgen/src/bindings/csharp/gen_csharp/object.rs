@@ -0,0 +1,158 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::fmt;
+
+use crate::backend::{CodeDeclaration, CodeOracle, CodeType, Literal};
+use crate::interface::{ComponentInterface, Object};
+use askama::Template;
+
+// Filters is used by ObjectTemplate.cs, which looks for the filters module here.
+use super::filters;
+pub struct ObjectCodeType {
+    id: String,
+}
+
+impl ObjectCodeType {
+    pub fn new(id: String) -> Self {
+        Self { id }
+    }
+}
+
+impl CodeType for ObjectCodeType {
+    fn type_label(&self, oracle: &dyn CodeOracle) -> String {
+        oracle.class_name(&self.id)
+    }
+
+    fn canonical_name(&self, oracle: &dyn CodeOracle) -> String {
+        format!("Object{}", self.type_label(oracle))
+    }
+
+    fn literal(&self, _oracle: &dyn CodeOracle, _literal: &Literal) -> String {
+        unreachable!();
+    }
+
+    fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Lower({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn write(
+        &self,
+        oracle: &dyn CodeOracle,
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+    ) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Write({}, {})",
+            self.canonical_name(oracle),
+            nm,
+            target
+        )
+    }
+
+    fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Lift({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "FfiConverter{}.INSTANCE.Read({})",
+            self.canonical_name(oracle),
+            nm
+        )
+    }
+
+    fn helper_code(&self, oracle: &dyn CodeOracle) -> Option<String> {
+        Some(format!(
+            "// Helper code for {} class is found in ObjectTemplate.cs",
+            self.type_label(oracle)
+        ))
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "cs", escape = "none", path = "ObjectTemplate.cs")]
+pub struct CSharpObject {
+    inner: Object,
+    // The namespace of the `[ForeignExtension]` interface, if it must be imported.
+    extension_import: Option<String>,
+}
+
+impl CSharpObject {
+    pub fn new(inner: Object, _ci: &ComponentInterface, extension_import: Option<String>) -> Self {
+        Self {
+            inner,
+            extension_import,
+        }
+    }
+    pub fn inner(&self) -> &Object {
+        &self.inner
+    }
+}
+
+impl CodeDeclaration for CSharpObject {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        self.extension_import
+            .clone()
+            .map(|namespace| vec![namespace])
+    }
+}
+
+// An `[OpaqueToken]` has no constructors or methods, so it only needs a small handle class.
+#[derive(Template)]
+#[template(syntax = "cs", escape = "none", path = "OpaqueTokenTemplate.cs")]
+pub struct CSharpOpaqueToken {
+    inner: Object,
+}
+
+impl CSharpOpaqueToken {
+    pub fn new(inner: Object, _ci: &ComponentInterface) -> Self {
+        Self { inner }
+    }
+    pub fn inner(&self) -> &Object {
+        &self.inner
+    }
+}
+
+impl CodeDeclaration for CSharpOpaqueToken {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "cs", escape = "none", path = "ObjectRuntime.cs")]
+pub struct CSharpObjectRuntime {
+    is_needed: bool,
+}
+
+impl CSharpObjectRuntime {
+    pub fn new(ci: &ComponentInterface) -> Self {
+        Self {
+            is_needed: !ci.iter_object_definitions().is_empty(),
+        }
+    }
+}
+
+impl CodeDeclaration for CSharpObjectRuntime {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        if self.is_needed {
+            Some(self.render().unwrap())
+        } else {
+            None
+        }
+    }
+}
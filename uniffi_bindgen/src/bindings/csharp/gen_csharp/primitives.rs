@@ -0,0 +1,124 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::backend::{CodeOracle, CodeType, Literal};
+use crate::interface::{types::Type, Radix};
+use askama::Template;
+use paste::paste;
+use std::fmt;
+
+#[allow(unused_imports)]
+use super::filters;
+
+fn render_literal(_oracle: &dyn CodeOracle, literal: &Literal) -> String {
+    fn typed_number(type_: &Type, num_str: String) -> String {
+        match type_ {
+            // Constant ints are converted to the smaller integer types implicitly.
+            Type::Int8 | Type::Int16 | Type::Int32 | Type::UInt8 | Type::UInt16 => num_str,
+            Type::Int64 => format!("{}L", num_str),
+
+            Type::UInt32 => format!("{}u", num_str),
+            Type::UInt64 => format!("{}UL", num_str),
+
+            Type::Float32 => format!("{}f", num_str),
+            Type::Float64 => format!("{}d", num_str),
+            _ => panic!("Unexpected literal: {} is not a number", num_str),
+        }
+    }
+
+    match literal {
+        Literal::Boolean(v) => format!("{}", v),
+        Literal::String(s) => format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")),
+        Literal::Int(i, radix, type_) => typed_number(
+            type_,
+            match radix {
+                Radix::Octal => format!("{:#x}", i),
+                Radix::Decimal => format!("{}", i),
+                Radix::Hexadecimal => format!("{:#x}", i),
+            },
+        ),
+        Literal::UInt(i, radix, type_) => typed_number(
+            type_,
+            match radix {
+                Radix::Octal => format!("{:#x}", i),
+                Radix::Decimal => format!("{}", i),
+                Radix::Hexadecimal => format!("{:#x}", i),
+            },
+        ),
+        Literal::Float(string, type_) => typed_number(type_, string.clone()),
+
+        _ => unreachable!("Literal"),
+    }
+}
+
+// Each type's helper code is an `FfiConverter` class, with a single instance that the
+// generated code calls.
+macro_rules! impl_code_type_for_primitive {
+    ($T:ty, $type_label:literal, $canonical_name:literal, $template_file:literal) => {
+        paste! {
+            #[derive(Template)]
+            #[template(syntax = "cs", escape = "none", path = $template_file )]
+            pub struct $T;
+
+            impl $T {
+                #[allow(dead_code)]
+                fn type_label_cs(&self) -> &'static str {
+                    $type_label
+                }
+
+                #[allow(dead_code)]
+                fn canonical_name_cs(&self) -> &'static str {
+                    $canonical_name
+                }
+            }
+
+            impl CodeType for $T  {
+                fn type_label(&self, _oracle: &dyn CodeOracle) -> String {
+                    $type_label.into()
+                }
+
+                fn canonical_name(&self, _oracle: &dyn CodeOracle) -> String {
+                    $canonical_name.into()
+                }
+
+                fn literal(&self, oracle: &dyn CodeOracle, literal: &Literal) -> String {
+                    render_literal(oracle, &literal)
+                }
+
+                fn lower(&self, _oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+                    format!("FfiConverter{}.INSTANCE.Lower({})", $canonical_name, nm)
+                }
+
+                fn write(&self, _oracle: &dyn CodeOracle, nm: &dyn fmt::Display, target: &dyn fmt::Display) -> String {
+                    format!("FfiConverter{}.INSTANCE.Write({}, {})", $canonical_name, nm, target)
+                }
+
+                fn lift(&self, _oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+                    format!("FfiConverter{}.INSTANCE.Lift({})", $canonical_name, nm)
+                }
+
+                fn read(&self, _oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+                    format!("FfiConverter{}.INSTANCE.Read({})", $canonical_name, nm)
+                }
+
+                fn helper_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+                    Some(self.render().unwrap())
+                }
+            }
+        }
+    }
+}
+
+impl_code_type_for_primitive!(BooleanCodeType, "bool", "Boolean", "BooleanHelper.cs");
+impl_code_type_for_primitive!(StringCodeType, "string", "String", "StringHelper.cs");
+impl_code_type_for_primitive!(Int8CodeType, "sbyte", "Int8", "NumberHelper.cs");
+impl_code_type_for_primitive!(Int16CodeType, "short", "Int16", "NumberHelper.cs");
+impl_code_type_for_primitive!(Int32CodeType, "int", "Int32", "NumberHelper.cs");
+impl_code_type_for_primitive!(Int64CodeType, "long", "Int64", "NumberHelper.cs");
+impl_code_type_for_primitive!(UInt8CodeType, "byte", "UInt8", "NumberHelper.cs");
+impl_code_type_for_primitive!(UInt16CodeType, "ushort", "UInt16", "NumberHelper.cs");
+impl_code_type_for_primitive!(UInt32CodeType, "uint", "UInt32", "NumberHelper.cs");
+impl_code_type_for_primitive!(UInt64CodeType, "ulong", "UInt64", "NumberHelper.cs");
+impl_code_type_for_primitive!(Float32CodeType, "float", "Float32", "NumberHelper.cs");
+impl_code_type_for_primitive!(Float64CodeType, "double", "Float64", "NumberHelper.cs");
@@ -0,0 +1,139 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use anyhow::{bail, Context, Result};
+use std::{
+    env,
+    ffi::OsString,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+pub mod gen_csharp;
+pub use gen_csharp::{CSharpWrapper, Config};
+
+use super::super::interface::ComponentInterface;
+use crate::backend::{custom_types, post_process, renames};
+
+pub fn write_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Path,
+    try_format_code: bool,
+) -> Result<()> {
+    let mut cs_file = PathBuf::from(out_dir);
+    cs_file.push(format!("{}.cs", ci.namespace()));
+    let mut f = File::create(&cs_file).context("Failed to create .cs file for bindings")?;
+    write!(
+        f,
+        "{}",
+        post_process::post_process(&cs_file, generate_bindings(config, ci)?)?
+    )?;
+    if try_format_code {
+        if let Err(e) = Command::new("dotnet")
+            .arg("format")
+            .arg("whitespace")
+            .arg(out_dir)
+            .arg("--folder")
+            .arg("--include")
+            .arg(&cs_file)
+            .output()
+        {
+            println!(
+                "Warning: Unable to auto-format {} using dotnet format: {:?}",
+                cs_file.file_name().unwrap().to_str().unwrap(),
+                e
+            )
+        }
+    }
+    Ok(())
+}
+
+// Generate C# bindings for the given ComponentInterface, as a string.
+pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
+    use askama::Template;
+    let problems = gen_csharp::unsupported_features(ci);
+    if !problems.is_empty() {
+        bail!(
+            "The C# backend doesn't support:\n  {}",
+            problems.join("\n  ")
+        );
+    }
+    custom_types::validate_custom_types(ci, config.custom_types(), true)?;
+    renames::validate_renames(ci, config.renames())?;
+    custom_types::with_custom_types(config.custom_types(), || {
+        renames::with_renames(config.renames(), config.naming(), || {
+            CSharpWrapper::new(config.clone(), ci).render()
+        })
+    })
+    .map_err(|_| anyhow::anyhow!("failed to render C# bindings"))
+}
+
+/// Execute the specified C# test program, along with the bindings generated in the given
+/// output directory.
+///
+/// C# scripts can't declare namespaces, which the bindings do, so rather than running a
+/// `.csx` script, this compiles the test and the bindings into a throwaway console app
+/// using top-level statements, and runs that with `dotnet run`.
+pub fn run_script(out_dir: &Path, script_file: &Path) -> Result<()> {
+    let script_name = script_file
+        .file_stem()
+        .context("Test script has no name")?
+        .to_string_lossy()
+        .to_string();
+    let project_dir = out_dir.join(format!("{}-csproj", script_name));
+    std::fs::create_dir_all(&project_dir)?;
+    let mut sources = vec![script_file.canonicalize()?];
+    for entry in PathBuf::from(out_dir)
+        .read_dir()
+        .context("Failed to list target directory when running C# script")?
+    {
+        let entry = entry.context("Directory listing failed while running C# script")?;
+        if let Some(ext) = entry.path().extension() {
+            if ext == "cs" {
+                sources.push(entry.path().canonicalize()?);
+            }
+        }
+    }
+    let compile_items: Vec<String> = sources
+        .iter()
+        .map(|path| format!("    <Compile Include=\"{}\" />", path.display()))
+        .collect();
+    let project = format!(
+        r#"<Project Sdk="Microsoft.NET.Sdk">
+  <PropertyGroup>
+    <OutputType>Exe</OutputType>
+    <TargetFramework>net6.0</TargetFramework>
+    <Nullable>enable</Nullable>
+    <EnableDefaultCompileItems>false</EnableDefaultCompileItems>
+    <TreatWarningsAsErrors>true</TreatWarningsAsErrors>
+  </PropertyGroup>
+  <ItemGroup>
+{}
+  </ItemGroup>
+</Project>
+"#,
+        compile_items.join("\n")
+    );
+    std::fs::write(project_dir.join(format!("{}.csproj", script_name)), project)?;
+    let mut cmd = Command::new("dotnet");
+    cmd.arg("run").arg("--project").arg(&project_dir);
+    // This lets .NET find the compiled library for the rust component.
+    for var in &["LD_LIBRARY_PATH", "DYLD_LIBRARY_PATH"] {
+        let paths = env::var_os(var).unwrap_or_else(|| OsString::from(""));
+        let paths = env::join_paths(env::split_paths(&paths).chain(vec![out_dir.to_path_buf()]))?;
+        cmd.env(var, paths);
+    }
+    let status = cmd
+        .spawn()
+        .context("Failed to spawn `dotnet` to run C# script")?
+        .wait()
+        .context("Failed to wait for `dotnet` when running C# script")?;
+    if !status.success() {
+        bail!("running `dotnet` failed")
+    }
+    Ok(())
+}
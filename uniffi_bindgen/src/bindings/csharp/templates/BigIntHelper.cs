@@ -0,0 +1,24 @@
+// Big integers are passed as a 128-bit two's complement number, most significant byte first.
+internal class FfiConverterBigInt : FfiConverterRustBuffer<System.Numerics.BigInteger> {
+    public static readonly FfiConverterBigInt INSTANCE = new FfiConverterBigInt();
+
+    private static readonly System.Numerics.BigInteger MinValue = -System.Numerics.BigInteger.Pow(2, 127);
+    private static readonly System.Numerics.BigInteger MaxValue = System.Numerics.BigInteger.Pow(2, 127) - 1;
+
+    public override System.Numerics.BigInteger Read(BigEndianReader reader) {
+        return new System.Numerics.BigInteger(reader.ReadBytes(16), isUnsigned: false, isBigEndian: true);
+    }
+
+    public override void Write(System.Numerics.BigInteger value, RustBufferBuilder buf) {
+        if (value < MinValue || value > MaxValue) {
+            throw new ArgumentException($"Invalid bigint {value}, must fit in 128 bits");
+        }
+        // `ToByteArray` uses as few bytes as it can, so fill the rest with the sign.
+        var bytes = value.ToByteArray(isUnsigned: false, isBigEndian: true);
+        byte fill = value.Sign < 0 ? (byte)0xff : (byte)0;
+        for (var i = bytes.Length; i < 16; i++) {
+            buf.WriteUInt8(fill);
+        }
+        buf.WriteBytes(bytes);
+    }
+}
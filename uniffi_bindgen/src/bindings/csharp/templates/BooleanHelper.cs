@@ -0,0 +1,19 @@
+internal class FfiConverterBoolean : FfiConverter<bool, sbyte> {
+    public static readonly FfiConverterBoolean INSTANCE = new FfiConverterBoolean();
+
+    public override bool Lift(sbyte value) {
+        return value != 0;
+    }
+
+    public override bool Read(BigEndianReader reader) {
+        return Lift(reader.ReadInt8());
+    }
+
+    public override sbyte Lower(bool value) {
+        return value ? (sbyte)1 : (sbyte)0;
+    }
+
+    public override void Write(bool value, RustBufferBuilder buf) {
+        buf.WriteInt8(Lower(value));
+    }
+}
@@ -0,0 +1,14 @@
+// Bytes are passed as their length, followed by the bytes themselves.
+internal class FfiConverterBytes : FfiConverterRustBuffer<byte[]> {
+    public static readonly FfiConverterBytes INSTANCE = new FfiConverterBytes();
+
+    public override byte[] Read(BigEndianReader reader) {
+        var length = reader.ReadInt32();
+        return reader.ReadBytes(length);
+    }
+
+    public override void Write(byte[] value, RustBufferBuilder buf) {
+        buf.WriteInt32(value.Length);
+        buf.WriteBytes(value);
+    }
+}
@@ -0,0 +1,92 @@
+// The handles that Rust holds for callback interface objects, which map back to the objects.
+// Each object gets a single handle, however many times it's passed to Rust.
+internal class ConcurrentHandleMap<T> where T : class {
+    private readonly Dictionary<ulong, T> leftMap = new Dictionary<ulong, T>();
+    private readonly Dictionary<T, ulong> rightMap = new Dictionary<T, ulong>(ReferenceEqualityComparer.Instance);
+    private readonly object lockObj = new object();
+    private ulong currentHandle = 0;
+
+    public ulong Insert(T obj) {
+        lock (lockObj) {
+            if (rightMap.TryGetValue(obj, out var existing)) {
+                return existing;
+            }
+            var handle = currentHandle++;
+            leftMap[handle] = obj;
+            rightMap[obj] = handle;
+            return handle;
+        }
+    }
+
+    public T? Get(ulong handle) {
+        lock (lockObj) {
+            return leftMap.TryGetValue(handle, out var obj) ? obj : null;
+        }
+    }
+
+    public T? Remove(ulong handle) {
+        lock (lockObj) {
+            if (leftMap.Remove(handle, out var obj)) {
+                rightMap.Remove(obj);
+                return obj;
+            }
+            return null;
+        }
+    }
+}
+
+[UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+internal delegate RustBuffer ForeignCallback(ulong handle, uint method, RustBuffer args);
+
+internal abstract class FfiConverterCallbackInterface<CallbackInterface> : FfiConverter<CallbackInterface, ulong>
+    where CallbackInterface : class
+{
+    // Magic number for the Rust proxy to call using the same mechanism as every other method,
+    // to free the callback once it's dropped by Rust.
+    protected const uint IDX_CALLBACK_FREE = 0;
+
+    // Magic number for the Rust proxy of a `[Versioned]` callback interface to ask which methods
+    // the object has. It's `u32::MAX` in Rust.
+    protected const uint IDX_CALLBACK_CAPABILITIES = uint.MaxValue;
+
+    public readonly ConcurrentHandleMap<CallbackInterface> handleMap = new ConcurrentHandleMap<CallbackInterface>();
+
+    // The delegate that Rust calls. It's kept in a field so that the garbage collector can't free
+    // it, since Rust holds on to it for as long as the library is loaded.
+    protected readonly ForeignCallback foreignCallback;
+
+    protected FfiConverterCallbackInterface() {
+        foreignCallback = Invoke;
+    }
+
+    // Registers the foreign callback with the Rust side.
+    // This method is generated for each callback interface.
+    public abstract void Register();
+
+    protected abstract RustBuffer Invoke(ulong handle, uint method, RustBuffer args);
+
+    protected RustBuffer Drop(ulong handle) {
+        handleMap.Remove(handle);
+        return new RustBuffer();
+    }
+
+    public override CallbackInterface Lift(ulong handle) {
+        var callback = handleMap.Get(handle);
+        if (callback == null) {
+            throw new InternalException("No callback in handlemap; this is a Uniffi bug");
+        }
+        return callback;
+    }
+
+    public override CallbackInterface Read(BigEndianReader reader) {
+        return Lift(reader.ReadUInt64());
+    }
+
+    public override ulong Lower(CallbackInterface value) {
+        return handleMap.Insert(value);
+    }
+
+    public override void Write(CallbackInterface value, RustBufferBuilder buf) {
+        buf.WriteUInt64(Lower(value));
+    }
+}
@@ -0,0 +1,105 @@
+{% import "macros.cs" as cs %}
+{%- let cbi = self.inner() %}
+{%- let type_name = cbi|type_name %}
+{%- let canonical_type_name = cbi|canonical_name %}
+{%- let ffi_converter = format!("FfiConverter{}", canonical_type_name) %}
+
+// Declaration and FfiConverters for {{ type_name }} Callback Interface
+
+public interface {{ type_name }} {
+    {%- for meth in cbi.methods() %}
+    {%- if meth.is_optional() %}
+    // This method is `[Optional]`, so implementations that leave it out get a default
+    // that does nothing, or returns an empty value.
+    {%- endif %}
+    {% match meth.return_type() -%}
+    {%- when Some with (return_type) %}{{ return_type|type_name }} {{ meth.name()|fn_name }}({% call cs::arg_list_decl(meth) %})
+    {%- match meth.default_return_value() %}
+    {%- when Some with (literal) %} => {{ literal|render_literal(return_type) }};
+    {%- when None %};
+    {%- endmatch %}
+    {%- when None %}void {{ meth.name()|fn_name }}({% call cs::arg_list_decl(meth) %}){% if meth.is_optional() %} {}{% else %};{% endif %}
+    {%- endmatch %}
+    {%- endfor %}
+}
+
+// The FfiConverter which transforms the callbacks into handles to pass to Rust, and is called
+// through the `ForeignCallback` that it registers with Rust.
+internal class {{ ffi_converter }} : FfiConverterCallbackInterface<{{ type_name }}> {
+    public static readonly {{ ffi_converter }} INSTANCE = new {{ ffi_converter }}();
+
+    public override void Register() {
+        _UniFFIHelpers.RustCall((ref RustCallStatus status) =>
+            _UniFFILib.{{ cbi.ffi_init_callback().name() }}(foreignCallback, ref status)
+        );
+    }
+
+    protected override RustBuffer Invoke(ulong handle, uint method, RustBuffer args) {
+        switch (method) {
+            case IDX_CALLBACK_FREE:
+                return Drop(handle);
+            {%- if cbi.is_versioned() %}
+            case IDX_CALLBACK_CAPABILITIES:
+                return InvokeCapabilities();
+            {%- endif %}
+            {%- for meth in cbi.methods() %}
+            {%- let method_name = format!("invoke_{}", meth.name())|fn_name %}
+            case {{ loop.index }}:
+                return {{ method_name }}(Lift(handle), args);
+            {%- endfor %}
+            default:
+                // This should never happen, because an out of bounds method index won't
+                // ever be used. Once we can catch errors, we should return an InternalException.
+                // https://github.com/mozilla/uniffi-rs/issues/351
+                return new RustBuffer();
+        }
+    }
+
+    {%- for meth in cbi.methods() %}
+    {%- let method_name = format!("invoke_{}", meth.name())|fn_name %}
+
+    private RustBuffer {{ method_name }}({{ type_name }} callback, RustBuffer args) {
+        try {
+            {%- if meth.arguments().len() != 0 %}
+            var reader = new BigEndianReader(args.ToArray());
+            {%- endif %}
+            {% if meth.return_type().is_some() %}var rval = {% endif -%}
+            callback.{{ meth.name()|fn_name }}(
+                {%- for arg in meth.arguments() %}
+                {{ "reader"|read_var(arg) }}{% if !loop.last %},{% endif %}
+                {%- endfor %}
+            );
+            {%- match meth.return_type() %}
+            {%- when Some with (return_type) %}
+            var buf = new RustBufferBuilder();
+            {{ "rval"|write_var("buf", return_type) }};
+            return buf.ToRustBuffer();
+            {%- when None %}
+            return new RustBuffer();
+            {%- endmatch %}
+            // TODO catch errors and report them back to Rust.
+            // https://github.com/mozilla/uniffi-rs/issues/351
+        } finally {
+            RustBuffer.Free(args);
+        }
+    }
+    {%- endfor %}
+
+    {%- if cbi.is_versioned() %}
+
+    // Tells the Rust proxy which version of `{{ type_name }}` this is, and the names of its
+    // methods in the order of their indexes, so that Rust calls them by name.
+    private RustBuffer InvokeCapabilities() {
+        var methods = new List<string> {
+            {%- for meth in cbi.methods() %}"{{ meth.name() }}"{% if !loop.last %}, {% endif %}{% endfor -%}
+        };
+        var buf = new RustBufferBuilder();
+        buf.WriteUInt64({{ cbi.version() }}UL);
+        buf.WriteInt32(methods.Count);
+        foreach (var name in methods) {
+            {{ "name"|write_var("buf", TypeIdentifier::String) }};
+        }
+        return buf.ToRustBuffer();
+    }
+    {%- endif %}
+}
@@ -0,0 +1,23 @@
+// Chars are passed as their Unicode scalar value, which is what a `Rune` holds.
+internal class FfiConverterChar : FfiConverter<System.Text.Rune, uint> {
+    public static readonly FfiConverterChar INSTANCE = new FfiConverterChar();
+
+    public override System.Text.Rune Lift(uint value) {
+        if (!System.Text.Rune.IsValid(value)) {
+            throw new InternalException($"Unexpected char value {value}");
+        }
+        return new System.Text.Rune(value);
+    }
+
+    public override System.Text.Rune Read(BigEndianReader reader) {
+        return Lift(reader.ReadUInt32());
+    }
+
+    public override uint Lower(System.Text.Rune value) {
+        return (uint)value.Value;
+    }
+
+    public override void Write(System.Text.Rune value, RustBufferBuilder buf) {
+        buf.WriteUInt32(Lower(value));
+    }
+}
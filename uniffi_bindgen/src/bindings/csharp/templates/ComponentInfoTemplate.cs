@@ -0,0 +1,33 @@
+/// <summary>
+/// Metadata about the build of the Rust library that these bindings are using.
+/// </summary>
+/// <remarks>
+/// <c>Checksum</c> identifies the component interface that the library was built from, and
+/// <c>BindingsChecksum</c> the one that these bindings were generated from. If they differ, the
+/// bindings and the library don't match.
+/// </remarks>
+public record UniffiComponentInfo(
+    string ComponentName,
+    string UniffiVersion,
+    string Checksum,
+    string BuildProfile,
+    string BindingsChecksum
+);
+
+public static class UniffiComponent {
+    /// <summary>The version of <c>uniffi-bindgen</c> that generated these bindings.</summary>
+    public const string BindgenVersion = "{{ self.bindgen_version() }}";
+
+    /// <summary>The checksum of the component interface that these bindings were generated from.</summary>
+    /// <remarks>The library is checked against it when it's loaded.</remarks>
+    public const string ComponentChecksum = "{{ "{:016x}"|format(ci.checksum()) }}";
+
+    /// <summary>Fetch metadata about the Rust library, for use by support tooling.</summary>
+    public static UniffiComponentInfo GetInfo() {
+        var info = _UniFFIHelpers.RustCall((ref RustCallStatus status) =>
+            _UniFFILib.{{ ci.ffi_component_info().name() }}(ref status)
+        );
+        var fields = {{ "info"|lift_var(Type::String) }}.Split('\n');
+        return new UniffiComponentInfo(fields[0], fields[1], fields[2], fields[3], ComponentChecksum);
+    }
+}
@@ -0,0 +1,30 @@
+// Conversions for `{{ type_name }}`, which is mapped onto `{{ builtin_type_name }}` by uniffi.toml.
+internal class FfiConverter{{ canonical_name }} : FfiConverter<{{ type_name }}, {{ ffi_type_name }}> {
+    public static readonly FfiConverter{{ canonical_name }} INSTANCE = new FfiConverter{{ canonical_name }}();
+
+    private static {{ type_name }} IntoCustom({{ builtin_type_name }} builtinValue) {
+        return {{ into_custom }};
+    }
+
+    private static {{ builtin_type_name }} FromCustom({{ type_name }} value) {
+        return {{ from_custom }};
+    }
+
+    public override {{ type_name }} Lift({{ ffi_type_name }} value) {
+        return IntoCustom({{ lift_builtin }});
+    }
+
+    public override {{ type_name }} Read(BigEndianReader reader) {
+        return IntoCustom({{ read_builtin }});
+    }
+
+    public override {{ ffi_type_name }} Lower({{ type_name }} value) {
+        var builtinValue = FromCustom(value);
+        return {{ lower_builtin }};
+    }
+
+    public override void Write({{ type_name }} value, RustBufferBuilder buf) {
+        var builtinValue = FromCustom(value);
+        {{ write_builtin }};
+    }
+}
@@ -0,0 +1,29 @@
+// Durations are passed as seconds and nanoseconds. .NET counts time in ticks of 100
+// nanoseconds, so any finer precision is lost.
+internal class FfiConverterDuration : FfiConverterRustBuffer<TimeSpan> {
+    public static readonly FfiConverterDuration INSTANCE = new FfiConverterDuration();
+
+    private const long NanosecondsPerTick = 100;
+
+    public override TimeSpan Read(BigEndianReader reader) {
+        var seconds = reader.ReadUInt64();
+        var nanoseconds = reader.ReadUInt32();
+        if (seconds > (ulong)(TimeSpan.MaxValue.Ticks / TimeSpan.TicksPerSecond)) {
+            throw new InternalException("Duration exceeds the maximum value supported by .NET");
+        }
+        if (nanoseconds >= 1_000_000_000) {
+            throw new InternalException("Duration nanoseconds exceed the maximum supported by uniffi");
+        }
+        return TimeSpan.FromTicks((long)seconds * TimeSpan.TicksPerSecond + nanoseconds / NanosecondsPerTick);
+    }
+
+    public override void Write(TimeSpan value, RustBufferBuilder buf) {
+        if (value < TimeSpan.Zero) {
+            // Rust does not support negative Durations
+            throw new ArgumentException("Invalid duration, must be non-negative");
+        }
+
+        buf.WriteUInt64((ulong)(value.Ticks / TimeSpan.TicksPerSecond));
+        buf.WriteUInt32((uint)(value.Ticks % TimeSpan.TicksPerSecond * NanosecondsPerTick));
+    }
+}
@@ -0,0 +1,104 @@
+{#
+// C#'s `enum` doesn't support variants with associated data, but is a little nicer for
+// consumers than a hierarchy of records. So, we switch here, using `enum` for enums with
+// no associated data and an abstract `record`, with a nested record for each variant, for
+// the general case.
+#}
+{% import "macros.cs" as cs %}
+{%- let e = self.inner() %}
+{%- let type_name = e|type_name %}
+{%- if e.is_flat() %}
+
+public enum {{ type_name }} {
+    {% for variant in e.variants() -%}
+    {{ variant.name()|enum_variant }}{% if !loop.last %},{% endif %}
+    {%- endfor %}
+}
+
+internal class FfiConverter{{ e|canonical_name }} : FfiConverterRustBuffer<{{ type_name }}> {
+    public static readonly FfiConverter{{ e|canonical_name }} INSTANCE = new FfiConverter{{ e|canonical_name }}();
+
+    public override {{ type_name }} Read(BigEndianReader reader) {
+        var value = reader.ReadInt32() - 1;
+        if (Enum.IsDefined(typeof({{ type_name }}), value)) {
+            return ({{ type_name }})value;
+        } else {
+            throw new InternalException($"invalid enum value {value}, something is very wrong!!");
+        }
+    }
+
+    public override void Write({{ type_name }} value, RustBufferBuilder buf) {
+        buf.WriteInt32((int)value + 1);
+    }
+}
+
+{% else %}
+
+public abstract record {{ type_name }}{% if self.contains_object_references() %} : IDisposable{% endif %} {
+    // Only the nested records can derive from this one, so the variants are the only values.
+    private {{ type_name }}() {}
+
+    {% for variant in e.variants() -%}
+    public sealed record {{ variant.name()|class_name }}(
+        {%- for field in variant.fields() -%}
+        {{ field|type_name }} {{ field.name()|property_name }}{% if !loop.last %}, {% endif %}
+        {%- endfor -%}
+    ) : {{ type_name }} {}
+    {% endfor %}
+
+    {%- if self.contains_object_references() %}
+    public void Dispose() {
+        switch (this) {
+            {%- for variant in e.variants() %}
+            {%- if variant.has_fields() %}
+            case {{ type_name }}.{{ variant.name()|class_name }} variant:
+                {% call cs::dispose_fields("variant", variant) %}
+                break;
+            {%- else %}
+            case {{ type_name }}.{{ variant.name()|class_name }}:
+                // Nothing to dispose
+                break;
+            {%- endif %}
+            {%- endfor %}
+        }
+    }
+    {%- endif %}
+}
+
+internal class FfiConverter{{ e|canonical_name }} : FfiConverterRustBuffer<{{ type_name }}> {
+    public static readonly FfiConverter{{ e|canonical_name }} INSTANCE = new FfiConverter{{ e|canonical_name }}();
+
+    public override {{ type_name }} Read(BigEndianReader reader) {
+        var value = reader.ReadInt32();
+        switch (value) {
+            {%- for variant in e.variants() %}
+            case {{ loop.index }}:
+                return new {{ type_name }}.{{ variant.name()|class_name }}(
+                    {%- for field in variant.fields() %}
+                    {{ "reader"|read_var(field) }}{% if !loop.last %},{% endif %}
+                    {%- endfor %}
+                );
+            {%- endfor %}
+            default:
+                throw new InternalException($"invalid enum value {value}, something is very wrong!!");
+        }
+    }
+
+    public override void Write({{ type_name }} value, RustBufferBuilder buf) {
+        switch (value) {
+            {%- for variant in e.variants() %}
+            case {{ type_name }}.{{ variant.name()|class_name }}{% if variant.has_fields() %} variant{% endif %}:
+                buf.WriteInt32({{ loop.index }});
+                {%- for field in variant.fields() %}
+                {%- let property = field.name()|property_name %}
+                {{ "variant.{}"|format(property)|write_var("buf", field) }};
+                {%- endfor %}
+                break;
+            {%- endfor %}
+            default:
+                throw new InternalException("invalid enum value, something is very wrong!!");
+        }
+    }
+}
+
+{% endif %}
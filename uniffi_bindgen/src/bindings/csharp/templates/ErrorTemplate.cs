@@ -0,0 +1,85 @@
+{% import "macros.cs" as cs %}
+{%- let e = self.inner() %}
+{%- let type_name = e|type_name %}
+
+public class {{ type_name }} : Exception{% if self.contains_object_references() %}, IDisposable{% endif %} {
+    // Only the nested classes can derive from this one, so the variants are the only errors.
+    private {{ type_name }}(string message, Exception? innerException) : base(message, innerException) {}
+
+    // Each variant is a nested class
+    {%- for variant in e.variants() %}
+    {% if variant.name()|hides_exception_member %}new {% endif %}public class {{ variant.name()|exception_name }} : {{ type_name }} {
+        {%- if e.is_flat() %}
+        // Flat errors carry a string error message, so no special implementation is necessary.
+        public {{ variant.name()|exception_name }}(string message, Exception? innerException = null) : base(message, innerException) {}
+        {%- else %}
+        {%- for field in variant.fields() %}
+        public {% if field.name()|hides_exception_member %}new {% endif %}{{ field|type_name }} {{ field.name()|property_name }} { get; }
+        {%- endfor %}
+
+        public {{ variant.name()|exception_name }}(
+            {%- for field in variant.fields() -%}
+            {{ field|type_name }} {{ field.name()|var_name }}, {% endfor -%}
+            Exception? innerException = null
+        ) : base("{{ variant.name() }}", innerException) {
+            {%- for field in variant.fields() %}
+            this.{{ field.name()|property_name }} = {{ field.name()|var_name }};
+            {%- endfor %}
+        }
+        {%- endif %}
+    }
+    {%- endfor %}
+
+    {%- if self.contains_object_references() %}
+
+    public void Dispose() {
+        switch (this) {
+            {%- for variant in e.variants() %}
+            {%- if variant.has_fields() %}
+            case {{ type_name }}.{{ variant.name()|exception_name }} variant:
+                {% call cs::dispose_fields("variant", variant) %}
+                break;
+            {%- else %}
+            case {{ type_name }}.{{ variant.name()|exception_name }}:
+                // Nothing to dispose
+                break;
+            {%- endif %}
+            {%- endfor %}
+        }
+    }
+    {%- endif %}
+}
+
+internal class FfiConverter{{ e|canonical_name }} : FfiConverterRustBuffer<{{ type_name }}>, CallStatusErrorHandler<{{ type_name }}> {
+    public static readonly FfiConverter{{ e|canonical_name }} INSTANCE = new FfiConverter{{ e|canonical_name }}();
+
+    public override {{ type_name }} Read(BigEndianReader reader) {
+        var value = reader.ReadInt32();
+        switch (value) {
+            {%- for variant in e.variants() %}
+            case {{ loop.index }}: {
+                {%- if e.is_flat() %}
+                var message = {{ "reader"|read_var(TypeIdentifier::String) }};
+                {%- else %}
+                {%- for field in variant.fields() %}
+                var field{{ loop.index }} = {{ "reader"|read_var(field) }};
+                {%- endfor %}
+                {%- endif %}
+                return new {{ type_name }}.{{ variant.name()|exception_name }}(
+                    {%- if e.is_flat() %}message, {% else %}{% for field in variant.fields() %}field{{ loop.index }}, {% endfor %}{% endif -%}
+                    {%- match e.source_chain_type() %}
+                    {%- when Some with (chain_type) %}UniffiErrorSource.Chain({{ "reader"|read_var(chain_type) }})
+                    {%- when None %}null
+                    {%- endmatch %}
+                );
+            }
+            {%- endfor %}
+            default:
+                throw new InternalException($"invalid error enum value {value}, something is very wrong!!");
+        }
+    }
+
+    public override void Write({{ type_name }} value, RustBufferBuilder buf) {
+        throw new NotSupportedException("Errors are only ever lifted from Rust, never lowered");
+    }
+}
@@ -0,0 +1,124 @@
+// A handful of classes and functions to support the generated data structures.
+// This would be a good candidate for isolating in its own ffi-support lib.
+// Error runtime.
+[StructLayout(LayoutKind.Sequential)]
+internal struct RustCallStatus {
+    public sbyte code;
+    public RustBuffer error_buf;
+
+    public bool IsSuccess() {
+        return code == 0;
+    }
+
+    public bool IsError() {
+        return code == 1;
+    }
+
+    public bool IsPanic() {
+        return code == 2;
+    }
+
+    public bool IsInvalidString() {
+        return code == 3;
+    }
+}
+
+public class InternalException : Exception {
+    public InternalException(string message) : base(message) {}
+}
+
+// Thrown when Rust is passed a string that isn't valid UTF-8, which these bindings never do
+// themselves, but which Rust reports as such rather than panicking.
+public class InvalidStringException : Exception {
+    public InvalidStringException(string message) : base(message) {}
+}
+
+// Errors declared with `[SourceChain]` carry the messages of their Rust `source()`s,
+// which we surface as a chain of `InnerException`s on the thrown exception.
+public class UniffiErrorSource : Exception {
+    public UniffiErrorSource(string message, Exception? innerException) : base(message, innerException) {}
+
+    internal static Exception? Chain(List<string> messages) {
+        Exception? cause = null;
+        for (var i = messages.Count - 1; i >= 0; i--) {
+            cause = new UniffiErrorSource(messages[i], cause);
+        }
+        return cause;
+    }
+}
+
+// Each top-level error class has a converter that can lift the error from the call status's rust buffer.
+internal interface CallStatusErrorHandler<E> where E : Exception {
+    E Lift(RustBuffer error_buf);
+}
+
+// CallStatusErrorHandler implementation for times when we don't expect a CALL_ERROR
+internal class NullCallStatusErrorHandler : CallStatusErrorHandler<InternalException> {
+    public static readonly NullCallStatusErrorHandler INSTANCE = new NullCallStatusErrorHandler();
+
+    public InternalException Lift(RustBuffer error_buf) {
+        RustBuffer.Free(error_buf);
+        return new InternalException("Unexpected CALL_ERROR");
+    }
+}
+
+// The functions that call into Rust, which fill in the `RustCallStatus` that they're given.
+internal delegate U RustCallFunc<U>(ref RustCallStatus status);
+internal delegate void RustCallAction(ref RustCallStatus status);
+
+// Helpers for calling Rust
+// In practice we usually need to be synchronized to call this safely, so it doesn't
+// synchronize itself
+internal static class _UniFFIHelpers {
+    // Call a rust function that returns a Result<>.  Pass in the Error class converter that corresponds to the Err
+    public static U RustCallWithError<U, E>(CallStatusErrorHandler<E> errorHandler, RustCallFunc<U> callback)
+        where E : Exception
+    {
+        _UniFFILib.EnsureInitialized();
+        var status = new RustCallStatus();
+        var return_value = callback(ref status);
+        CheckCallStatus(errorHandler, status);
+        return return_value;
+    }
+
+    public static void RustCallWithError<E>(CallStatusErrorHandler<E> errorHandler, RustCallAction callback)
+        where E : Exception
+    {
+        _UniFFILib.EnsureInitialized();
+        var status = new RustCallStatus();
+        callback(ref status);
+        CheckCallStatus(errorHandler, status);
+    }
+
+    // Call a rust function that returns a plain value
+    public static U RustCall<U>(RustCallFunc<U> callback) {
+        return RustCallWithError(NullCallStatusErrorHandler.INSTANCE, callback);
+    }
+
+    public static void RustCall(RustCallAction callback) {
+        RustCallWithError(NullCallStatusErrorHandler.INSTANCE, callback);
+    }
+
+    private static void CheckCallStatus<E>(CallStatusErrorHandler<E> errorHandler, RustCallStatus status)
+        where E : Exception
+    {
+        if (status.IsSuccess()) {
+            return;
+        } else if (status.IsError()) {
+            throw errorHandler.Lift(status.error_buf);
+        } else if (status.IsPanic()) {
+            // when the rust code sees a panic, it tries to construct a rustbuffer
+            // with the message.  but if that code panics, then it just sends back
+            // an empty buffer.
+            if (status.error_buf.len > 0) {
+                throw new InternalException({{ "status.error_buf"|lift_var(Type::String) }});
+            } else {
+                throw new InternalException("Rust panic");
+            }
+        } else if (status.IsInvalidString()) {
+            throw new InvalidStringException({{ "status.error_buf"|lift_var(Type::String) }});
+        } else {
+            throw new InternalException($"Unknown rust call status: {status.code}");
+        }
+    }
+}
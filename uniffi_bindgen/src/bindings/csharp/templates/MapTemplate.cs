@@ -0,0 +1,29 @@
+{%- let key_type = self.key() %}
+{%- let key_type_name = key_type|type_name %}
+{%- let inner_type = self.inner() %}
+{%- let outer_type = self.outer() %}
+{%- let inner_type_name = inner_type|type_name %}
+{%- let canonical_type_name = outer_type|canonical_name %}
+
+// Helper for passing values of type {{ outer_type|type_name }}
+internal class FfiConverter{{ canonical_type_name }} : FfiConverterRustBuffer<Dictionary<{{ key_type_name }}, {{ inner_type_name }}>> {
+    public static readonly FfiConverter{{ canonical_type_name }} INSTANCE = new FfiConverter{{ canonical_type_name }}();
+
+    public override Dictionary<{{ key_type_name }}, {{ inner_type_name }}> Read(BigEndianReader reader) {
+        var length = reader.ReadInt32();
+        var result = new Dictionary<{{ key_type_name }}, {{ inner_type_name }}>(length);
+        for (var i = 0; i < length; i++) {
+            var key = {{ "reader"|read_var(key_type) }};
+            result[key] = {{ "reader"|read_var(inner_type) }};
+        }
+        return result;
+    }
+
+    public override void Write(Dictionary<{{ key_type_name }}, {{ inner_type_name }}> value, RustBufferBuilder buf) {
+        buf.WriteInt32(value.Count);
+        foreach (var entry in value) {
+            {{ "entry.Key"|write_var("buf", key_type) }};
+            {{ "entry.Value"|write_var("buf", inner_type) }};
+        }
+    }
+}
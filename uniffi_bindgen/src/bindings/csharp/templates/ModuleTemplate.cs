@@ -0,0 +1,6 @@
+
+public static class {{ self.name() }} {
+    {%- for code in self.member_code() %}
+    {{ code|indent(4) }}
+    {%- endfor %}
+}
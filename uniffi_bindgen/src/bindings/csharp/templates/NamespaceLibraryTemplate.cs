@@ -0,0 +1,43 @@
+// The extern-C FFI definitions, which P/Invoke finds in the component's library.
+// This is an implementation detail which will be called internally by the public API.
+internal static class _UniFFILib {
+    static _UniFFILib() {
+        UniffiCheckChecksum();
+        {%- for code in self.initialization_code() %}
+        {{ code }}
+        {%- endfor %}
+    }
+
+    // P/Invoke calls don't run the static constructor, so the helpers that call into Rust
+    // call this first, to make sure that the library has been checked and initialized.
+    public static void EnsureInitialized() {}
+
+    // Check that the library was built from the same component interface as these bindings, before
+    // anything else calls into it. P/Invoke only looks up each function the first time it's called, so
+    // a mismatch would otherwise only show up later, as a confusing missing entry point, or worse.
+    private static void UniffiCheckChecksum() {
+        string checksum;
+        try {
+            var status = new RustCallStatus();
+            checksum = {{ ci.ffi_checksum().name() }}(ref status).ToString("x16");
+        } catch (EntryPointNotFoundException) {
+            throw new InternalException(
+                "The {{ ci.namespace() }} library doesn't report its checksum, so it's older than these bindings, " +
+                $"which uniffi-bindgen {UniffiComponent.BindgenVersion} generated. Regenerate the bindings from the library's UDL."
+            );
+        }
+        if (checksum != UniffiComponent.ComponentChecksum) {
+            throw new InternalException(
+                $"The {{ ci.namespace() }} library was built with checksum {checksum}, but these bindings, " +
+                $"which uniffi-bindgen {UniffiComponent.BindgenVersion} generated, expect {UniffiComponent.ComponentChecksum}. " +
+                "Regenerate the bindings from the library's UDL."
+            );
+        }
+    }
+
+    {% for func in ci.iter_ffi_function_definitions() -%}
+    [DllImport("{{ config.cdylib_name() }}", CallingConvention = CallingConvention.Cdecl)]
+    {%- call cs::ffi_function_definition(func) %}
+
+    {% endfor %}
+}
@@ -0,0 +1,21 @@
+{%- let type_name = self.type_label_cs() %}
+{%- let canonical_name = self.canonical_name_cs() %}
+internal class FfiConverter{{ canonical_name }} : FfiConverter<{{ type_name }}, {{ type_name }}> {
+    public static readonly FfiConverter{{ canonical_name }} INSTANCE = new FfiConverter{{ canonical_name }}();
+
+    public override {{ type_name }} Lift({{ type_name }} value) {
+        return value;
+    }
+
+    public override {{ type_name }} Read(BigEndianReader reader) {
+        return reader.Read{{ canonical_name }}();
+    }
+
+    public override {{ type_name }} Lower({{ type_name }} value) {
+        return value;
+    }
+
+    public override void Write({{ type_name }} value, RustBufferBuilder buf) {
+        buf.Write{{ canonical_name }}(value);
+    }
+}
@@ -0,0 +1,120 @@
+// Helpers for anything that can contain an object reference, such as a record with an
+// object field, which implements `IDisposable` to dispose of the objects in it.
+internal static class FFIObjectUtil {
+    public static void DisposeAll(params object?[] values) {
+        foreach (var value in values) {
+            if (value is IDisposable disposable) {
+                disposable.Dispose();
+            } else if (value is System.Collections.IDictionary dictionary) {
+                foreach (var item in dictionary.Values) {
+                    DisposeAll(item);
+                }
+            } else if (value is System.Collections.IEnumerable items && !(value is string)) {
+                foreach (var item in items) {
+                    DisposeAll(item);
+                }
+            }
+        }
+    }
+}
+
+// The base class for all UniFFI Object types.
+//
+// This class provides core operations for working with the Rust `Arc<T>` pointer to
+// the live Rust struct on the other side of the FFI.
+//
+// We have to be careful not to operate on a Rust struct after it has been dropped, nor to
+// drop it more than once, even when methods are called concurrently with or after a call
+// to `Dispose`, or when the finalizer runs. It works in the same way as the Kotlin bindings
+// (see the comments in `ObjectRuntime.kt` for the details), giving each `FFIObject` a
+// counter of in-flight calls and a flag that says whether `Dispose` has been called:
+//
+//    * The initial value of the counter is 1, indicating a live object with no in-flight calls.
+//
+//    * At the start of each method call, we atomically check the counter. If it is 0 then the
+//      underlying Rust struct has already been freed and the call throws an
+//      `ObjectDisposedException`. Otherwise we atomically increment it and make the call.
+//
+//    * At the end of each method call, we atomically decrement the counter, and if it has
+//      reached zero then we free the underlying Rust struct.
+//
+//    * When `Dispose` is called, we atomically set the flag. If it was already set we silently
+//      do nothing, otherwise we atomically decrement the counter, and if it has reached zero
+//      then we free the underlying Rust struct.
+//
+// Unlike the JVM, .NET runs finalizers reliably enough that objects which aren't disposed are
+// still freed eventually, but `Dispose` (for example with a `using` statement) frees them
+// promptly, and should be preferred.
+//
+// Objects with the `[Closeable]` attribute go one step further and poison their handle once
+// `Dispose` has been called: any later method call throws an `ObjectDisposedException`, even
+// if other calls are still in flight and the Rust struct hasn't been freed yet.
+public abstract class FFIObject : IDisposable {
+    protected readonly IntPtr pointer;
+    private readonly bool poisonOnClose;
+    private int wasDestroyed = 0;
+    private long callCounter = 1;
+
+    protected FFIObject(IntPtr pointer, bool poisonOnClose = false) {
+        this.pointer = pointer;
+        this.poisonOnClose = poisonOnClose;
+    }
+
+    ~FFIObject() {
+        Destroy();
+    }
+
+    protected abstract void FreeRustArcPtr();
+
+    public void Dispose() {
+        Destroy();
+        GC.SuppressFinalize(this);
+    }
+
+    private void Destroy() {
+        // Only allow a single call to this method.
+        if (Interlocked.CompareExchange(ref wasDestroyed, 1, 0) == 0) {
+            // This decrement always matches the initial count of 1 given at creation time.
+            if (Interlocked.Decrement(ref callCounter) == 0) {
+                FreeRustArcPtr();
+            }
+        }
+    }
+
+    internal R CallWithPointer<R>(Func<IntPtr, R> block) {
+        // Check and increment the call counter, to keep the object alive.
+        // This needs a compare-and-set retry loop in case of concurrent updates.
+        long count;
+        do {
+            count = Interlocked.Read(ref callCounter);
+            if (count == 0) {
+                if (poisonOnClose) {
+                    throw new ObjectDisposedException(GetType().Name, "The object has already been closed");
+                }
+                throw new ObjectDisposedException(GetType().Name, "The object has already been destroyed");
+            }
+            if (count == long.MaxValue) {
+                throw new InternalException($"{GetType().Name} call counter would overflow");
+            }
+        } while (Interlocked.CompareExchange(ref callCounter, count + 1, count) != count);
+        // Now we can safely do the method call without the pointer being freed concurrently.
+        try {
+            if (poisonOnClose && Volatile.Read(ref wasDestroyed) != 0) {
+                throw new ObjectDisposedException(GetType().Name, "The object has already been closed");
+            }
+            return block(pointer);
+        } finally {
+            // This decrement always matches the increment we performed above.
+            if (Interlocked.Decrement(ref callCounter) == 0) {
+                FreeRustArcPtr();
+            }
+        }
+    }
+
+    internal void CallWithPointer(Action<IntPtr> block) {
+        CallWithPointer<int>(ptr => {
+            block(ptr);
+            return 0;
+        });
+    }
+}
@@ -0,0 +1,97 @@
+{% import "macros.cs" as cs %}
+{%- let obj = self.inner() %}
+{%- let type_name = obj|type_name %}
+public interface I{{ type_name }} {
+    {%- for meth in obj.methods() %}
+    {% match meth.return_type() -%}
+    {%- when Some with (return_type) %}{{ return_type|type_name }}
+    {%- when None %}void
+    {%- endmatch %} {{ meth.name()|fn_name }}({% call cs::arg_list_decl(meth) %});
+    {%- endfor %}
+}
+
+public class {{ type_name }} : FFIObject, I{{ type_name }}{% if obj.is_cloneable() %}, ICloneable{% endif %}
+{%- match obj.foreign_extension() %}{% when Some with (ext) %}, {{ ext }}{% when None %}{% endmatch %} {
+    internal {{ type_name }}(IntPtr pointer) : base(pointer{% if obj.is_closeable() %}, poisonOnClose: true{% endif %}) {}
+
+    {%- match obj.primary_constructor() %}
+    {%- when Some with (cons) %}
+
+    public {{ type_name }}({% call cs::arg_list_decl(cons) -%}) :
+        this({% call cs::to_ffi_call(cons) %}) {}
+    {%- when None %}
+    {%- endmatch %}
+
+    protected override void FreeRustArcPtr() {
+        _UniFFIHelpers.RustCall((ref RustCallStatus status) =>
+            _UniFFILib.{{ obj.ffi_object_free().name() }}(this.pointer, ref status)
+        );
+    }
+
+    {%- match obj.ffi_object_clone() %}
+    {%- when Some with (ffi_clone) %}
+
+    /// <summary>
+    /// Make a new, independent copy of the underlying Rust object.
+    /// </summary>
+    /// <remarks>
+    /// The copy does not share any state with this object, and must be disposed separately.
+    /// </remarks>
+    public {{ type_name }} Clone() {
+        return new {{ type_name }}(CallWithPointer(thisPtr =>
+            _UniFFIHelpers.RustCall((ref RustCallStatus status) =>
+                _UniFFILib.{{ ffi_clone.name() }}(thisPtr, ref status)
+            )
+        ));
+    }
+
+    object ICloneable.Clone() => Clone();
+    {%- when None %}
+    {%- endmatch %}
+
+    {% for meth in obj.methods() -%}
+    {%- match meth.return_type() -%}
+
+    {%- when Some with (return_type) %}
+    public {{ return_type|type_name }} {{ meth.name()|fn_name }}({% call cs::arg_list_decl(meth) %}) {
+        var _retval = CallWithPointer(thisPtr => {% call cs::to_ffi_call_with_prefix("thisPtr", meth) %});
+        return {{ "_retval"|lift_var(return_type) }};
+    }
+
+    {%- when None %}
+    public void {{ meth.name()|fn_name }}({% call cs::arg_list_decl(meth) %}) {
+        CallWithPointer(thisPtr => {% call cs::to_ffi_call_with_prefix("thisPtr", meth) %});
+    }
+    {% endmatch %}
+    {% endfor %}
+
+    {%- for cons in obj.alternate_constructors() %}
+    public static {{ type_name }} {{ cons.name()|fn_name }}({% call cs::arg_list_decl(cons) %}) {
+        return new {{ type_name }}({% call cs::to_ffi_call(cons) %});
+    }
+    {% endfor %}
+}
+
+internal class FfiConverter{{ obj|canonical_name }} : FfiConverter<{{ type_name }}, IntPtr> {
+    public static readonly FfiConverter{{ obj|canonical_name }} INSTANCE = new FfiConverter{{ obj|canonical_name }}();
+
+    public override {{ type_name }} Lift(IntPtr value) {
+        return new {{ type_name }}(value);
+    }
+
+    public override {{ type_name }} Read(BigEndianReader reader) {
+        // The Rust code always writes pointers as 8 bytes, and will
+        // fail to compile if they don't fit.
+        return Lift(new IntPtr(reader.ReadInt64()));
+    }
+
+    public override IntPtr Lower({{ type_name }} value) {
+        return value.CallWithPointer(thisPtr => thisPtr);
+    }
+
+    public override void Write({{ type_name }} value, RustBufferBuilder buf) {
+        // The Rust code always expects pointers written as 8 bytes,
+        // and will fail to compile if they don't fit.
+        buf.WriteInt64(Lower(value).ToInt64());
+    }
+}
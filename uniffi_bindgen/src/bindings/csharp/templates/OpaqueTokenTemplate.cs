@@ -0,0 +1,53 @@
+{%- let obj = self.inner() %}
+{%- let type_name = obj|type_name %}
+/// <summary>
+/// An opaque handle to a Rust `{{ obj.name() }}`, which can only be passed back to Rust.
+/// </summary>
+/// <remarks>
+/// Two handles are equal if they refer to the same Rust object.
+/// </remarks>
+public class {{ type_name }} : FFIObject, IEquatable<{{ type_name }}> {
+    internal {{ type_name }}(IntPtr pointer) : base(pointer) {}
+
+    protected override void FreeRustArcPtr() {
+        _UniFFIHelpers.RustCall((ref RustCallStatus status) =>
+            _UniFFILib.{{ obj.ffi_object_free().name() }}(this.pointer, ref status)
+        );
+    }
+
+    public bool Equals({{ type_name }}? other) {
+        return other is not null && other.pointer == this.pointer;
+    }
+
+    public override bool Equals(object? obj) {
+        return Equals(obj as {{ type_name }});
+    }
+
+    public override int GetHashCode() {
+        return this.pointer.GetHashCode();
+    }
+}
+
+internal class FfiConverter{{ obj|canonical_name }} : FfiConverter<{{ type_name }}, IntPtr> {
+    public static readonly FfiConverter{{ obj|canonical_name }} INSTANCE = new FfiConverter{{ obj|canonical_name }}();
+
+    public override {{ type_name }} Lift(IntPtr value) {
+        return new {{ type_name }}(value);
+    }
+
+    public override {{ type_name }} Read(BigEndianReader reader) {
+        // The Rust code always writes pointers as 8 bytes, and will
+        // fail to compile if they don't fit.
+        return Lift(new IntPtr(reader.ReadInt64()));
+    }
+
+    public override IntPtr Lower({{ type_name }} value) {
+        return value.CallWithPointer(thisPtr => thisPtr);
+    }
+
+    public override void Write({{ type_name }} value, RustBufferBuilder buf) {
+        // The Rust code always expects pointers written as 8 bytes,
+        // and will fail to compile if they don't fit.
+        buf.WriteInt64(Lower(value).ToInt64());
+    }
+}
@@ -0,0 +1,26 @@
+{%- let inner_type = self.inner() %}
+{%- let outer_type = self.outer() %}
+{%- let inner_type_name = inner_type|type_name %}
+{%- let canonical_type_name = outer_type|canonical_name %}
+
+// Helper for passing values of type {{ outer_type|type_name }}
+internal class FfiConverter{{ canonical_type_name }} : FfiConverterRustBuffer<{{ inner_type_name }}?> {
+    public static readonly FfiConverter{{ canonical_type_name }} INSTANCE = new FfiConverter{{ canonical_type_name }}();
+
+    public override {{ inner_type_name }}? Read(BigEndianReader reader) {
+        if (reader.ReadInt8() == 0) {
+            return null;
+        }
+        return {{ "reader"|read_var(inner_type) }};
+    }
+
+    public override void Write({{ inner_type_name }}? value, RustBufferBuilder buf) {
+        if (value == null) {
+            buf.WriteInt8(0);
+        } else {
+            buf.WriteInt8(1);
+            // The cast unwraps `Nullable<T>` for value types, and is a no-op for reference types.
+            {{ "(({})value)"|format(inner_type_name)|write_var("buf", inner_type) }};
+        }
+    }
+}
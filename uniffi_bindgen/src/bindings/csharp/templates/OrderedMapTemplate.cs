@@ -0,0 +1,30 @@
+{%- let inner_type = self.inner() %}
+{%- let outer_type = self.outer() %}
+{%- let inner_type_name = inner_type|type_name %}
+{%- let canonical_type_name = outer_type|canonical_name %}
+
+// Helper for passing values of type {{ outer_type|type_name }}, which keep their entries in
+// the order they were inserted. .NET has no generic ordered dictionary, so they're a list
+// of key-value pairs.
+internal class FfiConverter{{ canonical_type_name }} : FfiConverterRustBuffer<List<KeyValuePair<string, {{ inner_type_name }}>>> {
+    public static readonly FfiConverter{{ canonical_type_name }} INSTANCE = new FfiConverter{{ canonical_type_name }}();
+
+    public override List<KeyValuePair<string, {{ inner_type_name }}>> Read(BigEndianReader reader) {
+        var length = reader.ReadInt32();
+        var result = new List<KeyValuePair<string, {{ inner_type_name }}>>(length);
+        for (var i = 0; i < length; i++) {
+            var key = {{ "reader"|read_var(TypeIdentifier::String) }};
+            var item = {{ "reader"|read_var(inner_type) }};
+            result.Add(new KeyValuePair<string, {{ inner_type_name }}>(key, item));
+        }
+        return result;
+    }
+
+    public override void Write(List<KeyValuePair<string, {{ inner_type_name }}>> value, RustBufferBuilder buf) {
+        buf.WriteInt32(value.Count);
+        foreach (var entry in value) {
+            {{ "entry.Key"|write_var("buf", TypeIdentifier::String) }};
+            {{ "entry.Value"|write_var("buf", inner_type) }};
+        }
+    }
+}
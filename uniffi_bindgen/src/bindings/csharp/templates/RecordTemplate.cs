@@ -0,0 +1,55 @@
+{% import "macros.cs" as cs %}
+{%- let rec = self.inner() %}
+{%- let type_name = rec|type_name %}
+public record {{ type_name }}(
+    {%- for field in rec.fields() %}
+    {{ field|type_name }} {{ field.name()|property_name -}}
+    {%- match field.default_value() %}
+        {%- when Some with(literal) %} = {{ literal|render_literal(field) }}
+        {%- else %}
+    {%- endmatch -%}
+    {% if !loop.last %}, {% endif %}
+    {%- endfor %}
+){% if self.contains_object_references() %} : IDisposable{% endif %}
+{%- if self.contains_object_references() || rec.has_sensitive_fields() %} {
+    {%- if self.contains_object_references() %}
+    public void Dispose() {
+        {% call cs::dispose_fields("this", rec) %}
+    }
+    {%- endif %}
+    {%- if rec.has_sensitive_fields() %}
+
+    // `[Sensitive]` fields are left out of `ToString()`, so that they don't end up in logs.
+    protected virtual bool PrintMembers(System.Text.StringBuilder builder) {
+        {%- for field in rec.fields() %}
+        builder.Append("{% if !loop.first %}, {% endif %}{{ field.name()|property_name }} = ");
+        {%- if field.sensitive() %}
+        builder.Append("***");
+        {%- else %}
+        builder.Append({{ field.name()|property_name }});
+        {%- endif %}
+        {%- endfor %}
+        return true;
+    }
+    {%- endif %}
+}
+{%- else %};{% endif %}
+
+internal class FfiConverter{{ rec|canonical_name }} : FfiConverterRustBuffer<{{ type_name }}> {
+    public static readonly FfiConverter{{ rec|canonical_name }} INSTANCE = new FfiConverter{{ rec|canonical_name }}();
+
+    public override {{ type_name }} Read(BigEndianReader reader) {
+        return new {{ type_name }}(
+            {%- for field in rec.fields() %}
+            {{ "reader"|read_var(field) }}{% if !loop.last %},{% endif %}
+            {%- endfor %}
+        );
+    }
+
+    public override void Write({{ type_name }} value, RustBufferBuilder buf) {
+        {%- for field in rec.fields() %}
+        {%- let property = field.name()|property_name %}
+        {{ "value.{}"|format(property)|write_var("buf", field) }};
+        {%- endfor %}
+    }
+}
@@ -0,0 +1,179 @@
+// This is a helper for safely working with byte buffers returned from the Rust code.
+// A rust-owned buffer is represented by its capacity, its current length, and a
+// pointer to the underlying data.
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct RustBuffer {
+    public int capacity;
+    public int len;
+    public IntPtr data;
+
+    public static RustBuffer Alloc(int size) {
+        var buffer = _UniFFIHelpers.RustCall((ref RustCallStatus status) =>
+            _UniFFILib.{{ ci.ffi_rustbuffer_alloc().name() }}(size, ref status)
+        );
+        if (buffer.data == IntPtr.Zero) {
+            throw new InternalException($"RustBuffer.Alloc() returned null data pointer (size={size})");
+        }
+        return buffer;
+    }
+
+    public static void Free(RustBuffer buffer) {
+        _UniFFIHelpers.RustCall((ref RustCallStatus status) =>
+            _UniFFILib.{{ ci.ffi_rustbuffer_free().name() }}(buffer, ref status)
+        );
+    }
+
+    // Copy bytes into a new `RustBuffer`, whose length is the number of bytes.
+    public static RustBuffer FromBytes(byte[] bytes) {
+        var buffer = Alloc(bytes.Length);
+        if (bytes.Length > 0) {
+            Marshal.Copy(bytes, 0, buffer.data, bytes.Length);
+        }
+        buffer.len = bytes.Length;
+        return buffer;
+    }
+
+    // Copy the contents of the buffer, which stays owned by Rust, into a managed array.
+    public byte[] ToArray() {
+        var bytes = new byte[len];
+        if (len > 0) {
+            Marshal.Copy(data, bytes, 0, len);
+        }
+        return bytes;
+    }
+}
+
+// This is a helper for safely passing byte references into the rust code.
+// It's not actually used at the moment, because there aren't many things that you
+// can take a direct pointer to in .NET without pinning it, and if we're going to copy
+// something then we might as well copy it into a `RustBuffer`. But it's here for API
+// completeness.
+
+[StructLayout(LayoutKind.Sequential)]
+internal struct ForeignBytes {
+    public int length;
+    public IntPtr data;
+}
+
+// A helper for structured reading of the big-endian data in a `RustBuffer`, once it's been
+// copied into managed memory.
+internal class BigEndianReader {
+    private readonly byte[] data;
+    private int position = 0;
+
+    public BigEndianReader(byte[] data) {
+        this.data = data;
+    }
+
+    public bool HasRemaining() {
+        return position < data.Length;
+    }
+
+    private ReadOnlySpan<byte> Take(int size) {
+        if (size < 0 || position + size > data.Length) {
+            throw new InternalException("read past the end of the buffer, something is very wrong!!");
+        }
+        var span = new ReadOnlySpan<byte>(data, position, size);
+        position += size;
+        return span;
+    }
+
+    public sbyte ReadInt8() => (sbyte)Take(1)[0];
+    public byte ReadUInt8() => Take(1)[0];
+    public short ReadInt16() => BinaryPrimitives.ReadInt16BigEndian(Take(2));
+    public ushort ReadUInt16() => BinaryPrimitives.ReadUInt16BigEndian(Take(2));
+    public int ReadInt32() => BinaryPrimitives.ReadInt32BigEndian(Take(4));
+    public uint ReadUInt32() => BinaryPrimitives.ReadUInt32BigEndian(Take(4));
+    public long ReadInt64() => BinaryPrimitives.ReadInt64BigEndian(Take(8));
+    public ulong ReadUInt64() => BinaryPrimitives.ReadUInt64BigEndian(Take(8));
+    public float ReadFloat32() => BitConverter.Int32BitsToSingle(ReadInt32());
+    public double ReadFloat64() => BitConverter.Int64BitsToDouble(ReadInt64());
+    public byte[] ReadBytes(int length) => Take(length).ToArray();
+}
+
+// A helper for structured writing of data into a `RustBuffer`.
+// The data is built up in managed memory, and copied across in one go at the end.
+internal class RustBufferBuilder {
+    private readonly MemoryStream stream = new MemoryStream();
+
+    public void WriteInt8(sbyte value) => stream.WriteByte((byte)value);
+    public void WriteUInt8(byte value) => stream.WriteByte(value);
+
+    public void WriteInt16(short value) {
+        Span<byte> bytes = stackalloc byte[2];
+        BinaryPrimitives.WriteInt16BigEndian(bytes, value);
+        stream.Write(bytes);
+    }
+
+    public void WriteUInt16(ushort value) {
+        Span<byte> bytes = stackalloc byte[2];
+        BinaryPrimitives.WriteUInt16BigEndian(bytes, value);
+        stream.Write(bytes);
+    }
+
+    public void WriteInt32(int value) {
+        Span<byte> bytes = stackalloc byte[4];
+        BinaryPrimitives.WriteInt32BigEndian(bytes, value);
+        stream.Write(bytes);
+    }
+
+    public void WriteUInt32(uint value) {
+        Span<byte> bytes = stackalloc byte[4];
+        BinaryPrimitives.WriteUInt32BigEndian(bytes, value);
+        stream.Write(bytes);
+    }
+
+    public void WriteInt64(long value) {
+        Span<byte> bytes = stackalloc byte[8];
+        BinaryPrimitives.WriteInt64BigEndian(bytes, value);
+        stream.Write(bytes);
+    }
+
+    public void WriteUInt64(ulong value) {
+        Span<byte> bytes = stackalloc byte[8];
+        BinaryPrimitives.WriteUInt64BigEndian(bytes, value);
+        stream.Write(bytes);
+    }
+
+    public void WriteFloat32(float value) => WriteInt32(BitConverter.SingleToInt32Bits(value));
+    public void WriteFloat64(double value) => WriteInt64(BitConverter.DoubleToInt64Bits(value));
+    public void WriteBytes(byte[] value) => stream.Write(value, 0, value.Length);
+
+    public RustBuffer ToRustBuffer() {
+        return RustBuffer.FromBytes(stream.ToArray());
+    }
+}
+
+// The base class for the helpers that convert values of each type to and from the FFI.
+//
+// `Lift` and `Lower` convert a value that's passed directly as an argument or return value,
+// `Read` and `Write` one that's inside a `RustBuffer`, such as the item of a sequence.
+internal abstract class FfiConverter<CsType, FfiType> {
+    public abstract CsType Lift(FfiType value);
+    public abstract FfiType Lower(CsType value);
+    public abstract CsType Read(BigEndianReader reader);
+    public abstract void Write(CsType value, RustBufferBuilder buf);
+}
+
+// The base class for the helpers of types that are always passed in a `RustBuffer`.
+internal abstract class FfiConverterRustBuffer<CsType> : FfiConverter<CsType, RustBuffer> {
+    public override CsType Lift(RustBuffer value) {
+        try {
+            var reader = new BigEndianReader(value.ToArray());
+            var item = Read(reader);
+            if (reader.HasRemaining()) {
+                throw new InternalException("junk remaining in buffer after lifting, something is very wrong!!");
+            }
+            return item;
+        } finally {
+            RustBuffer.Free(value);
+        }
+    }
+
+    public override RustBuffer Lower(CsType value) {
+        var buf = new RustBufferBuilder();
+        Write(value, buf);
+        return buf.ToRustBuffer();
+    }
+}
@@ -0,0 +1,25 @@
+{%- let inner_type = self.inner() %}
+{%- let outer_type = self.outer() %}
+{%- let inner_type_name = inner_type|type_name %}
+{%- let canonical_type_name = outer_type|canonical_name %}
+
+// Helper for passing values of type {{ outer_type|type_name }}
+internal class FfiConverter{{ canonical_type_name }} : FfiConverterRustBuffer<List<{{ inner_type_name }}>> {
+    public static readonly FfiConverter{{ canonical_type_name }} INSTANCE = new FfiConverter{{ canonical_type_name }}();
+
+    public override List<{{ inner_type_name }}> Read(BigEndianReader reader) {
+        var length = reader.ReadInt32();
+        var result = new List<{{ inner_type_name }}>(length);
+        for (var i = 0; i < length; i++) {
+            result.Add({{ "reader"|read_var(inner_type) }});
+        }
+        return result;
+    }
+
+    public override void Write(List<{{ inner_type_name }}> value, RustBufferBuilder buf) {
+        buf.WriteInt32(value.Count);
+        foreach (var item in value) {
+            {{ "item"|write_var("buf", inner_type) }};
+        }
+    }
+}
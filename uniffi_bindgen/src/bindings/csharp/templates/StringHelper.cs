@@ -0,0 +1,28 @@
+internal class FfiConverterString : FfiConverter<string, RustBuffer> {
+    public static readonly FfiConverterString INSTANCE = new FfiConverterString();
+
+    public override string Lift(RustBuffer value) {
+        try {
+            return Encoding.UTF8.GetString(value.ToArray());
+        } finally {
+            RustBuffer.Free(value);
+        }
+    }
+
+    public override string Read(BigEndianReader reader) {
+        var length = reader.ReadInt32();
+        return Encoding.UTF8.GetString(reader.ReadBytes(length));
+    }
+
+    public override RustBuffer Lower(string value) {
+        // Ideally we'd pass these bytes to `ffi_bytebuffer_from_bytes`, but doing so would require us
+        // to pin them. So we might as well directly copy them into a `RustBuffer`.
+        return RustBuffer.FromBytes(Encoding.UTF8.GetBytes(value));
+    }
+
+    public override void Write(string value, RustBufferBuilder buf) {
+        var bytes = Encoding.UTF8.GetBytes(value);
+        buf.WriteInt32(bytes.Length);
+        buf.WriteBytes(bytes);
+    }
+}
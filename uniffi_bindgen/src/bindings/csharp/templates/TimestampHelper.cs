@@ -0,0 +1,34 @@
+// Timestamps are passed as seconds and nanoseconds either side of the Unix epoch. .NET
+// counts time in ticks of 100 nanoseconds, so any finer precision is lost.
+internal class FfiConverterTimestamp : FfiConverterRustBuffer<DateTimeOffset> {
+    public static readonly FfiConverterTimestamp INSTANCE = new FfiConverterTimestamp();
+
+    private const long NanosecondsPerTick = 100;
+
+    public override DateTimeOffset Read(BigEndianReader reader) {
+        var seconds = reader.ReadInt64();
+        var nanoseconds = reader.ReadUInt32();
+        if (nanoseconds >= 1_000_000_000) {
+            throw new InternalException("Timestamp nanoseconds exceed the maximum supported by uniffi");
+        }
+        var offset = TimeSpan.FromTicks(Math.Abs(seconds) * TimeSpan.TicksPerSecond + nanoseconds / NanosecondsPerTick);
+        if (seconds >= 0) {
+            return DateTimeOffset.UnixEpoch + offset;
+        } else {
+            return DateTimeOffset.UnixEpoch - offset;
+        }
+    }
+
+    public override void Write(DateTimeOffset value, RustBufferBuilder buf) {
+        var epochOffset = value - DateTimeOffset.UnixEpoch;
+
+        long sign = 1;
+        if (epochOffset < TimeSpan.Zero) {
+            sign = -1;
+            epochOffset = epochOffset.Negate();
+        }
+
+        buf.WriteInt64(sign * (epochOffset.Ticks / TimeSpan.TicksPerSecond));
+        buf.WriteUInt32((uint)(epochOffset.Ticks % TimeSpan.TicksPerSecond * NanosecondsPerTick));
+    }
+}
@@ -0,0 +1,16 @@
+{% import "macros.cs" as cs %}
+{%- let func = self.inner() %}
+{%- match func.return_type() -%}
+{%- when Some with (return_type) %}
+
+public static {{ return_type|type_name }} {{ func.name()|fn_name }}({%- call cs::arg_list_decl(func) -%}) {
+    var _retval = {% call cs::to_ffi_call(func) %};
+    return {{ "_retval"|lift_var(return_type) }};
+}
+
+{% when None -%}
+
+public static void {{ func.name()|fn_name }}({% call cs::arg_list_decl(func) %}) {
+    {% call cs::to_ffi_call(func) %};
+}
+{% endmatch %}
@@ -0,0 +1,64 @@
+{#
+// Template to call into rust. Used in several places.
+// Variable names in `arg_list_decl` should match up with arg lists
+// passed to rust via `_arg_list_ffi_call` (we use `var_name` in both)
+#}
+
+{%- macro to_ffi_call(func) -%}
+    _UniFFIHelpers.RustCallWithError({{ func.throws_type()|error_handler }}, (ref RustCallStatus _status) =>
+    _UniFFILib.{{ func.ffi_func().name() }}({% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %}, {% endif %}ref _status)
+)
+{%- endmacro -%}
+
+{%- macro to_ffi_call_with_prefix(prefix, func) -%}
+    _UniFFIHelpers.RustCallWithError({{ func.throws_type()|error_handler }}, (ref RustCallStatus _status) =>
+    _UniFFILib.{{ func.ffi_func().name() }}(
+        {{- prefix }}, {% call _arg_list_ffi_call(func) %}{% if func.arguments().len() > 0 %}, {% endif %}ref _status)
+)
+{%- endmacro %}
+
+{%- macro _arg_list_ffi_call(func) %}
+    {%- for arg in func.arguments() %}
+        {{- arg.name()|var_name|lower_var(arg) }}
+        {%- if !loop.last %}, {% endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{#-
+// Arglist as used in C# declarations of methods, functions and constructors.
+// Note the var_name and type_name filters.
+-#}
+
+{% macro arg_list_decl(func) %}
+    {%- for arg in func.arguments() -%}
+        {{ arg|type_name }} {{ arg.name()|var_name -}}
+        {%- match arg.default_value() %}
+        {%- when Some with(literal) %} = {{ literal|render_literal(arg) }}
+        {%- else %}
+        {%- endmatch %}
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+{%- endmacro %}
+
+{#-
+// Arglist as used in the _UniFFILib function declations.
+// Note unfiltered name but ffi_type_name filters, and the `@` that lets the names be keywords.
+-#}
+{%- macro arg_list_ffi_decl(func) %}
+    {%- for arg in func.arguments() %}{{ arg.type_()|ffi_type_name }} @{{ arg.name() }}, {% endfor -%}
+    ref RustCallStatus _uniffi_out_err
+{%- endmacro -%}
+
+// Macro for disposing of the objects in fields
+{%- macro dispose_fields(prefix, member) %}
+    FFIObjectUtil.DisposeAll(
+    {%- for field in member.fields() %}
+        {{ prefix }}.{{ field.name()|property_name }}{%- if !loop.last %}, {% endif -%}
+    {% endfor -%});
+{%- endmacro -%}
+
+{%- macro ffi_function_definition(func) %}
+    public static extern {% match func.return_type() -%}{%- when Some with (type_) %}{{ type_|ffi_type_name }}{% when None %}void{% endmatch %} {{ func.name() }}(
+    {%- call arg_list_ffi_decl(func) -%}
+);
+{%- endmacro %}
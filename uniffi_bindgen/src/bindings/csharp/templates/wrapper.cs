@@ -0,0 +1,48 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+// Common helper code.
+//
+// Ideally this would live in a separate .cs file where it can be unittested etc
+// in isolation, and perhaps even published as a re-useable package.
+//
+// However, it's important that the details of how this helper code works (e.g. the
+// way that different builtin types are passed across the FFI) exactly match what's
+// expected by the Rust code on the other side of the interface. In practice right
+// now that means coming from the exact some version of `uniffi` that was used to
+// compile the Rust component. The easiest way to ensure this is to bundle the C#
+// helpers directly inline like we're doing here.
+
+#nullable enable
+
+using System;
+using System.Buffers.Binary;
+using System.Collections.Generic;
+using System.IO;
+using System.Runtime.InteropServices;
+using System.Text;
+using System.Threading;
+
+{%- for imported_namespace in self.imports() %}
+using {{ imported_namespace }};
+{%- endfor %}
+
+namespace {{ config.namespace() }};
+
+// The RustBuffer struct, and the helpers for reading and writing its contents.
+{% include "RustBufferTemplate.cs" %}
+
+{% include "Helpers.cs" %}
+
+// Contains loading, initialization code,
+// and the FFI Function declarations in a static class.
+{% include "NamespaceLibraryTemplate.cs" %}
+
+{% include "ComponentInfoTemplate.cs" %}
+
+// Public interface members begin here.
+{% for code in self.declaration_code() %}
+{{ code }}
+{%- endfor %}
+
+{% import "macros.cs" as cs %}
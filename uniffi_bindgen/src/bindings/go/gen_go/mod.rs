@@ -0,0 +1,388 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use anyhow::Result;
+use askama::Template;
+use heck::{CamelCase, MixedCase};
+use serde::{Deserialize, Serialize};
+
+use crate::backend::ApiFilter;
+use crate::interface::*;
+use crate::lockfile::type_decl;
+use crate::MergeWith;
+
+// https://go.dev/ref/spec#Keywords, along with the predeclared identifiers and packages that
+// the generated code uses, which an argument mustn't shadow.
+const RESERVED_WORDS: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+    "append",
+    "len",
+    "make",
+    "nil",
+    "panic",
+    "atomic",
+    "binary",
+    "bytes",
+    "fmt",
+    "io",
+    "runtime",
+    "time",
+    "unsafe",
+];
+
+fn is_reserved_word(word: &str) -> bool {
+    RESERVED_WORDS.contains(&word)
+}
+
+// Some config options for it the caller wants to customize the generated Go.
+// Note that this can only be used to control details of the Go *that do not affect the underlying component*,
+// since the details of the underlying component are entirely determined by the `ComponentInterface`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    package_name: Option<String>,
+    cdylib_name: Option<String>,
+    #[serde(default)]
+    filter: ApiFilter,
+}
+
+impl Config {
+    /// The name of the Go package that the bindings are declared in.
+    pub fn package_name(&self) -> String {
+        self.package_name
+            .clone()
+            .unwrap_or_else(|| "uniffi".to_string())
+    }
+
+    pub fn cdylib_name(&self) -> String {
+        self.cdylib_name
+            .clone()
+            .unwrap_or_else(|| "uniffi".to_string())
+    }
+
+    /// The part of the component to generate bindings for, see [`crate::backend::filter`].
+    pub fn filter(&self) -> &ApiFilter {
+        &self.filter
+    }
+}
+
+impl From<&ComponentInterface> for Config {
+    fn from(ci: &ComponentInterface) -> Self {
+        Config {
+            package_name: Some(ci.namespace().to_string()),
+            cdylib_name: Some(format!("uniffi_{}", ci.namespace())),
+            filter: Default::default(),
+        }
+    }
+}
+
+impl MergeWith for Config {
+    fn merge_with(&self, other: &Self) -> Self {
+        Config {
+            package_name: self.package_name.merge_with(&other.package_name),
+            cdylib_name: self.cdylib_name.merge_with(&other.cdylib_name),
+            filter: self.filter.merge_with(&other.filter),
+        }
+    }
+}
+
+/// What the Go backend can generate.
+pub fn capabilities() -> Capabilities {
+    Capabilities::all().without(&[
+        Feature::NestedNamespaces,
+        Feature::Batchable,
+        Feature::Async,
+        Feature::AlsoAsync,
+        Feature::NoCopy,
+        Feature::WeakRefs,
+        Feature::ForeignExtensions,
+        Feature::Observable,
+        Feature::Events,
+        Feature::Services,
+        Feature::SourceChains,
+        Feature::CallbackInterfaces,
+        Feature::ExternalTypes,
+        Feature::CustomTypes,
+        Feature::OrderedMaps,
+        Feature::BigInt,
+        Feature::Bytes,
+        Feature::Char,
+    ])
+}
+
+// The things in the component that the Go backend doesn't support yet, to report them all
+// at once.
+pub(super) fn unsupported_features(ci: &ComponentInterface) -> Vec<String> {
+    ci.unsupported_features(&capabilities())
+}
+
+#[derive(Template)]
+#[template(syntax = "go", escape = "none", path = "wrapper.go")]
+pub struct GoWrapper<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+impl<'a> GoWrapper<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+
+    /// The version of `uniffi-bindgen` that's generating the bindings.
+    pub fn bindgen_version(&self) -> &'static str {
+        crate::BINDGEN_VERSION
+    }
+
+    /// The packages that the bindings import, besides `C`. Go refuses to compile a file
+    /// that imports a package it doesn't use, so these depend on what's in the component.
+    pub fn imports(&self) -> Vec<&'static str> {
+        let types = self.ci.iter_types();
+        let mut imports = vec!["bytes", "encoding/binary", "fmt", "io"];
+        if !self.ci.iter_object_definitions().is_empty() {
+            imports.extend(&["runtime", "sync/atomic"]);
+        }
+        if types
+            .iter()
+            .any(|t| matches!(t, Type::Timestamp | Type::Duration))
+        {
+            imports.push("time");
+        }
+        imports.push("unsafe");
+        imports
+    }
+
+    /// Whether the Go value passed for an argument has to be kept alive until the call
+    /// returns, because the finalizer of an object in it would free the Rust object.
+    pub fn keeps_alive(&self, arg: &Argument) -> bool {
+        self.ci.item_contains_object_references(arg)
+    }
+}
+
+mod filters {
+    use super::*;
+    use std::fmt;
+
+    /// The C type of a low-level `FFIType`, for declaring the FFI functions to cgo.
+    pub fn ffi_type_c(type_: &FFIType) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            FFIType::Int8 => "int8_t".to_string(),
+            FFIType::UInt8 => "uint8_t".to_string(),
+            FFIType::Int16 => "int16_t".to_string(),
+            FFIType::UInt16 => "uint16_t".to_string(),
+            FFIType::Int32 => "int32_t".to_string(),
+            FFIType::UInt32 => "uint32_t".to_string(),
+            FFIType::Int64 => "int64_t".to_string(),
+            FFIType::UInt64 => "uint64_t".to_string(),
+            FFIType::Float32 => "float".to_string(),
+            FFIType::Float64 => "double".to_string(),
+            FFIType::RustArcPtr => "void *".to_string(),
+            FFIType::RustBuffer => "RustBuffer".to_string(),
+            FFIType::ForeignBytes => "ForeignBytes".to_string(),
+            FFIType::ForeignCallback | FFIType::ForeignMethodCallback(_) => {
+                unimplemented!("Callback interfaces are not implemented")
+            }
+            FFIType::AsyncCompletion => unimplemented!("[Async] functions are not implemented"),
+        })
+    }
+
+    pub fn class_name_go(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(nm.to_string().to_camel_case())
+    }
+
+    /// Functions and methods are exported, so they're capitalized.
+    pub fn fn_name_go(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(nm.to_string().to_camel_case())
+    }
+
+    /// The fields of records, enum variants and errors are exported too.
+    pub fn field_name_go(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(nm.to_string().to_camel_case())
+    }
+
+    /// A field of a record, enum variant or error, as a member of the Go value `owner`.
+    pub fn member_go(nm: &dyn fmt::Display, owner: &str) -> Result<String, askama::Error> {
+        Ok(format!("{}.{}", owner, field_name_go(nm)?))
+    }
+
+    pub fn var_name_go(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        let nm = nm.to_string().to_mixed_case();
+        let suffix = if is_reserved_word(&nm) { "_" } else { "" };
+        Ok(format!("{}{}", nm, suffix))
+    }
+
+    pub fn type_go(type_: &Type) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Type::Int8 => "int8".into(),
+            Type::UInt8 => "uint8".into(),
+            Type::Int16 => "int16".into(),
+            Type::UInt16 => "uint16".into(),
+            Type::Int32 => "int32".into(),
+            Type::UInt32 => "uint32".into(),
+            Type::Int64 => "int64".into(),
+            Type::UInt64 => "uint64".into(),
+            Type::Float32 => "float32".into(),
+            Type::Float64 => "float64".into(),
+            Type::Boolean => "bool".into(),
+            Type::String => "string".into(),
+            Type::Timestamp => "time.Time".into(),
+            Type::Duration => "time.Duration".into(),
+            Type::Object(name) => format!("*{}", class_name_go(name)?),
+            Type::Record(name) | Type::Enum(name) | Type::Error(name) => class_name_go(name)?,
+            // Objects are already pointers, which can be nil.
+            Type::Optional(t) if is_object(t)? => type_go(t)?,
+            Type::Optional(t) => format!("*{}", type_go(t)?),
+            Type::Sequence(t) => format!("[]{}", type_go(t)?),
+            Type::Map(k, v) => format!("map[{}]{}", type_go(k)?, type_go(v)?),
+            _ => panic!("No support for {} in Go, yet", type_decl(type_)),
+        })
+    }
+
+    /// Whether the Go type is a pointer to an object.
+    pub fn is_object(type_: &Type) -> Result<bool, askama::Error> {
+        Ok(matches!(type_, Type::Object(_)))
+    }
+
+    /// The suffix of the names of the helper functions that convert values of the type.
+    pub fn helper_name_go(type_: &Type) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Type::Int8 => "Int8".into(),
+            Type::UInt8 => "Uint8".into(),
+            Type::Int16 => "Int16".into(),
+            Type::UInt16 => "Uint16".into(),
+            Type::Int32 => "Int32".into(),
+            Type::UInt32 => "Uint32".into(),
+            Type::Int64 => "Int64".into(),
+            Type::UInt64 => "Uint64".into(),
+            Type::Float32 => "Float32".into(),
+            Type::Float64 => "Float64".into(),
+            Type::Boolean => "Bool".into(),
+            Type::String => "String".into(),
+            Type::Timestamp => "Timestamp".into(),
+            Type::Duration => "Duration".into(),
+            Type::Object(name) | Type::Record(name) | Type::Enum(name) | Type::Error(name) => {
+                format!("Type{}", class_name_go(name)?)
+            }
+            Type::Optional(t) => format!("Optional{}", helper_name_go(t)?),
+            Type::Sequence(t) => format!("Sequence{}", helper_name_go(t)?),
+            Type::Map(k, v) => format!("Map{}_{}", helper_name_go(k)?, helper_name_go(v)?),
+            _ => panic!("No support for {} in Go, yet", type_decl(type_)),
+        })
+    }
+
+    pub fn lower_go(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+        Ok(format!("uniffiLower{}({})", helper_name_go(type_)?, nm))
+    }
+
+    pub fn lift_go(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+        Ok(format!("uniffiLift{}({})", helper_name_go(type_)?, nm))
+    }
+
+    pub fn read_go(type_: &Type) -> Result<String, askama::Error> {
+        Ok(format!("uniffiRead{}(reader)", helper_name_go(type_)?))
+    }
+
+    pub fn write_go(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+        Ok(format!(
+            "uniffiWrite{}(writer, {})",
+            helper_name_go(type_)?,
+            nm
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_go_bindings() {
+        const UDL: &str = r#"
+            namespace todolist {
+                [Throws=TodoError]
+                TodoEntry get_first(sequence<TodoEntry> entries);
+                record<DOMString, u32>? counts(timestamp since);
+            };
+            dictionary TodoEntry {
+                string text;
+                TodoList? list;
+            };
+            enum Shape { "Circle", "Square" };
+            [Enum]
+            interface Change {
+                Added(string text);
+                Cleared();
+            };
+            [Error]
+            enum TodoError { "EmptyList" };
+            interface TodoList {
+                constructor();
+                [Name=with_entries]
+                constructor(sequence<TodoEntry> entries);
+                void add_entry(TodoEntry entry);
+                string? last_text(string type);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let bindings = super::super::generate_go_bindings(&Config::from(&ci), &ci).unwrap();
+        assert!(bindings.contains("package todolist"));
+        assert!(bindings.contains("#cgo LDFLAGS: -luniffi_todolist"));
+        assert!(bindings.contains("\t\"time\"\n"));
+        assert!(bindings.contains("func GetFirst(entries []TodoEntry) (TodoEntry, error) {"));
+        assert!(bindings.contains("func Counts(since time.Time) *map[string]uint32 {"));
+        assert!(bindings.contains("type TodoEntry struct {"));
+        assert!(bindings.contains("\tList *TodoList\n"));
+        assert!(bindings.contains("\tShapeCircle Shape = 1\n"));
+        assert!(bindings.contains("type ChangeAdded struct {"));
+        assert!(bindings.contains("func (*TodoErrorEmptyList) isTodoError() {}"));
+        assert!(bindings.contains("func NewTodoList() *TodoList {"));
+        assert!(bindings.contains("func NewTodoListWithEntries(entries []TodoEntry) *TodoList {"));
+        assert!(bindings.contains("func (_self *TodoList) LastText(type_ string) *string {"));
+        assert!(bindings.contains("runtime.KeepAlive(entries)"));
+    }
+
+    #[test]
+    fn test_go_unsupported_features() {
+        const UDL: &str = r#"
+            namespace shapes {
+                void draw(Canvas canvas);
+            };
+            [External="geometry"]
+            typedef extern Point;
+            dictionary Canvas { Point origin; };
+            callback interface Painter {
+                void paint(u32 color);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let err = super::super::generate_go_bindings(&Config::from(&ci), &ci).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The Go backend doesn't support:
+  callback interface `Painter`
+  external type Point"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::{
+    env,
+    ffi::OsString,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{bail, Context, Result};
+
+pub mod gen_go;
+pub use gen_go::{Config, GoWrapper};
+
+use super::super::interface::ComponentInterface;
+use crate::backend::post_process;
+
+// Generate Go bindings for the given ComponentInterface, in the given output directory.
+
+pub fn write_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Path,
+    try_format_code: bool,
+) -> Result<()> {
+    let mut go_file = PathBuf::from(out_dir);
+    go_file.push(format!("{}.go", ci.namespace()));
+    let mut f = File::create(&go_file).context("Failed to create .go file for bindings")?;
+    write!(
+        f,
+        "{}",
+        post_process::post_process(&go_file, generate_go_bindings(config, ci)?)?
+    )?;
+
+    if try_format_code {
+        if let Err(e) = Command::new("gofmt").arg("-w").arg(&go_file).output() {
+            println!(
+                "Warning: Unable to auto-format {} using gofmt: {:?}",
+                go_file.file_name().unwrap().to_str().unwrap(),
+                e
+            )
+        }
+    }
+
+    Ok(())
+}
+
+// Generate Go bindings for the given ComponentInterface, as a string.
+
+pub fn generate_go_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
+    use askama::Template;
+    let problems = gen_go::unsupported_features(ci);
+    if !problems.is_empty() {
+        bail!(
+            "The Go backend doesn't support:\n  {}",
+            problems.join("\n  ")
+        );
+    }
+    GoWrapper::new(config.clone(), ci)
+        .render()
+        .map_err(|_| anyhow::anyhow!("failed to render Go bindings"))
+}
+
+/// Execute the specified Go test program, along with the bindings generated in the given
+/// output directory.
+///
+/// Go only builds packages, so this sets up a throwaway `uniffi` module with the bindings for
+/// each component in a package of their own, which the test imports as `uniffi/{namespace}`,
+/// and the test as its `main` package, then runs that with `go run`.
+pub fn run_script(out_dir: &Path, script_file: &Path) -> Result<()> {
+    let script_name = script_file
+        .file_stem()
+        .context("Test script has no name")?
+        .to_string_lossy()
+        .to_string();
+    let module_dir = out_dir.join(format!("{}-gomod", script_name));
+    std::fs::create_dir_all(&module_dir)?;
+    std::fs::write(module_dir.join("go.mod"), "module uniffi\n\ngo 1.17\n")?;
+    std::fs::copy(script_file, module_dir.join("main.go"))?;
+    for entry in PathBuf::from(out_dir)
+        .read_dir()
+        .context("Failed to list target directory when running Go script")?
+    {
+        let entry = entry.context("Directory listing failed while running Go script")?;
+        let path = entry.path();
+        if path.extension().map_or(false, |ext| ext == "go") {
+            let package_dir = module_dir.join(path.file_stem().unwrap());
+            std::fs::create_dir_all(&package_dir)?;
+            std::fs::copy(&path, package_dir.join(path.file_name().unwrap()))?;
+        }
+    }
+    let mut cmd = Command::new("go");
+    cmd.current_dir(&module_dir).arg("run").arg(".");
+    // This lets cgo link against the compiled library for the rust component...
+    let mut ldflags = OsString::from(format!("-L{}", out_dir.display()));
+    if let Some(flags) = env::var_os("CGO_LDFLAGS") {
+        ldflags.push(" ");
+        ldflags.push(flags);
+    }
+    cmd.env("CGO_LDFLAGS", ldflags);
+    // ...and the test program find it when it runs.
+    for var in &["LD_LIBRARY_PATH", "DYLD_LIBRARY_PATH"] {
+        let paths = env::var_os(var).unwrap_or_else(|| OsString::from(""));
+        let paths = env::join_paths(env::split_paths(&paths).chain(vec![out_dir.to_path_buf()]))?;
+        cmd.env(var, paths);
+    }
+    let status = cmd
+        .spawn()
+        .context("Failed to spawn `go` to run Go script")?
+        .wait()
+        .context("Failed to wait for `go` when running Go script")?;
+    if !status.success() {
+        bail!("running `go` failed")
+    }
+    Ok(())
+}
@@ -0,0 +1,23 @@
+// Durations are serialized as seconds and nanoseconds.
+
+func uniffiReadDuration(reader *bytes.Reader) time.Duration {
+	seconds := uniffiReadUint64(reader)
+	nanoseconds := uniffiReadUint32(reader)
+	if seconds > uint64(1<<63-1)/uint64(time.Second) {
+		panic(&InternalError{Message: "Duration exceeds the maximum value supported by Go"})
+	}
+	if nanoseconds >= 1000000000 {
+		panic(&InternalError{Message: "Duration nanoseconds exceed the maximum supported by uniffi"})
+	}
+	return time.Duration(seconds)*time.Second + time.Duration(nanoseconds)
+}
+
+func uniffiWriteDuration(writer *bytes.Buffer, value time.Duration) {
+	if value < 0 {
+		// Rust does not support negative Durations
+		panic(fmt.Errorf("invalid duration %v, must be non-negative", value))
+	}
+	uniffiWriteUint64(writer, uint64(value/time.Second))
+	uniffiWriteUint32(writer, uint32(value%time.Second))
+}
+{% call go::lift_lower_buffer(type_) %}
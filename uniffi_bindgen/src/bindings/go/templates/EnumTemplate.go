@@ -0,0 +1,72 @@
+{% let name = e.type_()|helper_name_go -%}
+{%- let enum_name = e.name()|class_name_go -%}
+{% if e.is_flat() -%}
+type {{ enum_name }} int32
+
+const (
+	{%- for variant in e.variants() %}
+	{{ enum_name }}{{ variant.name()|class_name_go }} {{ enum_name }} = {{ loop.index }}
+	{%- endfor %}
+)
+
+func uniffiRead{{ name }}(reader *bytes.Reader) {{ enum_name }} {
+	value := {{ enum_name }}(uniffiReadInt32(reader))
+	if value < 1 || value > {{ e.variants().len() }} {
+		panic(&InternalError{Message: fmt.Sprintf("invalid {{ enum_name }} enum value %d", value)})
+	}
+	return value
+}
+
+func uniffiWrite{{ name }}(writer *bytes.Buffer, value {{ enum_name }}) {
+	uniffiWriteInt32(writer, int32(value))
+}
+{%- else -%}
+// {{ enum_name }} is implemented by a struct type for each of its variants.
+type {{ enum_name }} interface {
+	is{{ enum_name }}()
+}
+{%- for variant in e.variants() %}
+{%- let variant_name = variant.name()|class_name_go %}
+
+type {{ enum_name }}{{ variant_name }} struct {
+	{%- for field in variant.fields() %}
+	{{ field.name()|field_name_go }} {{ field.type_()|type_go }}
+	{%- endfor %}
+}
+
+func ({{ enum_name }}{{ variant_name }}) is{{ enum_name }}() {}
+{%- endfor %}
+
+func uniffiRead{{ name }}(reader *bytes.Reader) {{ enum_name }} {
+	switch index := uniffiReadInt32(reader); index {
+	{%- for variant in e.variants() %}
+	case {{ loop.index }}:
+		return {{ enum_name }}{{ variant.name()|class_name_go }}{
+			{%- for field in variant.fields() %}
+			{{ field.name()|field_name_go }}: {{ field.type_()|read_go }},
+			{%- endfor %}
+		}
+	{%- endfor %}
+	default:
+		panic(&InternalError{Message: fmt.Sprintf("invalid {{ enum_name }} enum index %d", index)})
+	}
+}
+
+func uniffiWrite{{ name }}(writer *bytes.Buffer, value {{ enum_name }}) {
+	switch value.(type) {
+	{%- for variant in e.variants() %}
+	case {{ enum_name }}{{ variant.name()|class_name_go }}:
+		uniffiWriteInt32(writer, {{ loop.index }})
+		{%- if variant.has_fields() %}
+		variant := value.({{ enum_name }}{{ variant.name()|class_name_go }})
+		{%- for field in variant.fields() %}
+		{{ field.name()|member_go("variant")|write_go(field.type_()) }}
+		{%- endfor %}
+		{%- endif %}
+	{%- endfor %}
+	default:
+		panic(fmt.Errorf("unexpected {{ enum_name }} value %v", value))
+	}
+}
+{%- endif %}
+{% call go::lift_lower_buffer(e.type_()) %}
@@ -0,0 +1,57 @@
+{% let name = e.type_()|helper_name_go -%}
+{%- let error_name = e.name()|class_name_go -%}
+// {{ error_name }} is the error that functions declared to throw it return, which is a pointer
+// to the struct type for one of its variants.
+type {{ error_name }} interface {
+	error
+	is{{ error_name }}()
+}
+{%- for variant in e.variants() %}
+{%- let variant_name = variant.name()|class_name_go %}
+
+type {{ error_name }}{{ variant_name }} struct {
+	{%- if e.is_flat() %}
+	Message string
+	{%- else %}
+	{%- for field in variant.fields() %}
+	{{ field.name()|field_name_go }} {{ field.type_()|type_go }}
+	{%- endfor %}
+	{%- endif %}
+}
+
+func (e *{{ error_name }}{{ variant_name }}) Error() string {
+	{%- if e.is_flat() %}
+	return fmt.Sprintf("{{ error_name }}.{{ variant_name }}: %s", e.Message)
+	{%- else %}
+	return fmt.Sprintf("{{ error_name }}.{{ variant_name }}: %+v", *e)
+	{%- endif %}
+}
+
+func (*{{ error_name }}{{ variant_name }}) is{{ error_name }}() {}
+{%- endfor %}
+
+func uniffiRead{{ name }}(reader *bytes.Reader) {{ error_name }} {
+	switch index := uniffiReadInt32(reader); index {
+	{%- for variant in e.variants() %}
+	case {{ loop.index }}:
+		return &{{ error_name }}{{ variant.name()|class_name_go }}{
+			{%- if e.is_flat() %}
+			Message: uniffiReadString(reader),
+			{%- else %}
+			{%- for field in variant.fields() %}
+			{{ field.name()|field_name_go }}: {{ field.type_()|read_go }},
+			{%- endfor %}
+			{%- endif %}
+		}
+	{%- endfor %}
+	default:
+		panic(&InternalError{Message: fmt.Sprintf("invalid {{ error_name }} error index %d", index)})
+	}
+}
+
+func uniffiLiftError{{ name }}(rb C.RustBuffer) error {
+	reader := uniffiRustBufferReader(rb)
+	err := uniffiRead{{ name }}(reader)
+	uniffiCheckFullyRead(reader)
+	return err
+}
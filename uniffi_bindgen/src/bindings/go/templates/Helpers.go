@@ -0,0 +1,56 @@
+// InternalError is what the bindings panic with when something goes wrong in the rust code
+// that it doesn't report as an error the function throws, such as a rust panic.
+type InternalError struct {
+	Message string
+}
+
+func (e *InternalError) Error() string {
+	return e.Message
+}
+
+// InvalidStringError is what the bindings panic with when rust is passed a string that isn't
+// valid UTF-8, which go strings can hold, and which rust reports as such rather than panicking.
+type InvalidStringError struct {
+	Message string
+}
+
+func (e *InvalidStringError) Error() string {
+	return e.Message
+}
+
+// These match the values from the uniffi::rustcalls module
+const (
+	uniffiCallSuccess       = 0
+	uniffiCallError         = 1
+	uniffiCallPanic         = 2
+	uniffiCallInvalidString = 3
+)
+
+// uniffiCheckCallStatus checks how a call into rust went. It returns the error that the
+// function threw, lifted by liftError, and panics with an `*InternalError` if rust
+// panicked, or an `*InvalidStringError` if a string argument wasn't valid UTF-8.
+func uniffiCheckCallStatus(status *C.RustCallStatus, liftError func(C.RustBuffer) error) error {
+	switch status.code {
+	case uniffiCallSuccess:
+		return nil
+	case uniffiCallError:
+		if liftError != nil {
+			return liftError(status.errorBuf)
+		}
+		uniffiRustBufferFree(status.errorBuf)
+		panic(&InternalError{Message: "unexpected error from a function that doesn't throw"})
+	case uniffiCallPanic:
+		// When the rust code sees a panic, it tries to construct a RustBuffer
+		// with the message. But if that code panics, then it just sends back
+		// an empty buffer.
+		if status.errorBuf.len > 0 {
+			panic(&InternalError{Message: uniffiLiftString(status.errorBuf)})
+		}
+		uniffiRustBufferFree(status.errorBuf)
+		panic(&InternalError{Message: "rust panic"})
+	case uniffiCallInvalidString:
+		panic(&InvalidStringError{Message: uniffiLiftString(status.errorBuf)})
+	default:
+		panic(&InternalError{Message: fmt.Sprintf("unknown rust call status code: %d", status.code)})
+	}
+}
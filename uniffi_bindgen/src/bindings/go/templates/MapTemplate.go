@@ -0,0 +1,19 @@
+{% let name = type_|helper_name_go -%}
+func uniffiRead{{ name }}(reader *bytes.Reader) {{ type_|type_go }} {
+	length := uniffiReadLength(reader)
+	value := make({{ type_|type_go }}, length)
+	for i := 0; i < length; i++ {
+		key := {{ key_type|read_go }}
+		value[key] = {{ inner|read_go }}
+	}
+	return value
+}
+
+func uniffiWrite{{ name }}(writer *bytes.Buffer, value {{ type_|type_go }}) {
+	uniffiWriteInt32(writer, int32(len(value)))
+	for key, item := range value {
+		{{ "key"|write_go(key_type) }}
+		{{ "item"|write_go(inner) }}
+	}
+}
+{% call go::lift_lower_buffer(type_) %}
@@ -0,0 +1,19 @@
+// The version of `uniffi-bindgen` that generated these bindings.
+const uniffiBindgenVersion = "{{ self.bindgen_version() }}"
+
+// The checksum of the component interface that these bindings were generated from. The library
+// is checked against it when the package is initialized.
+const uniffiComponentChecksum = "{{ "{:016x}"|format(ci.checksum()) }}"
+
+func init() {
+	var status C.RustCallStatus
+	checksum := fmt.Sprintf("%016x", uint64(C.{{ ci.ffi_checksum().name() }}(&status)))
+	uniffiCheckCallStatus(&status, nil)
+	if checksum != uniffiComponentChecksum {
+		panic(&InternalError{Message: fmt.Sprintf(
+			"The {{ ci.namespace() }} library was built with checksum %s, but these bindings, which uniffi-bindgen %s "+
+				"generated, expect %s. Regenerate the bindings from the library's UDL.",
+			checksum, uniffiBindgenVersion, uniffiComponentChecksum,
+		)})
+	}
+}
@@ -0,0 +1,54 @@
+// uniffiFFIObject holds the pointer to a rust object, and frees it once the Go object that
+// wraps it is destroyed, or garbage-collected, and no calls into rust are still using it.
+type uniffiFFIObject struct {
+	pointer unsafe.Pointer
+	// The number of calls using the pointer, plus one until the object is destroyed.
+	callCounter int64
+	destroyed   int32
+	free        func(unsafe.Pointer, *C.RustCallStatus)
+}
+
+func newUniffiFFIObject(pointer unsafe.Pointer, free func(unsafe.Pointer, *C.RustCallStatus)) uniffiFFIObject {
+	return uniffiFFIObject{
+		pointer:     pointer,
+		callCounter: 1,
+		free:        free,
+	}
+}
+
+// incrementPointer hands out the pointer for a call into rust, which must call
+// decrementPointer once it's done with it.
+func (o *uniffiFFIObject) incrementPointer(name string) unsafe.Pointer {
+	for {
+		counter := atomic.LoadInt64(&o.callCounter)
+		if counter <= 0 {
+			panic(fmt.Errorf("%s object has already been destroyed", name))
+		}
+		if atomic.CompareAndSwapInt64(&o.callCounter, counter, counter+1) {
+			return o.pointer
+		}
+	}
+}
+
+func (o *uniffiFFIObject) decrementPointer() {
+	if atomic.AddInt64(&o.callCounter, -1) == 0 {
+		var status C.RustCallStatus
+		o.free(o.pointer, &status)
+		uniffiCheckCallStatus(&status, nil)
+	}
+}
+
+// lowerPointer hands out the pointer to pass the object as an argument, which the calling
+// code keeps alive until the call returns.
+func (o *uniffiFFIObject) lowerPointer(name string) unsafe.Pointer {
+	if atomic.LoadInt64(&o.callCounter) <= 0 {
+		panic(fmt.Errorf("%s object has already been destroyed", name))
+	}
+	return o.pointer
+}
+
+func (o *uniffiFFIObject) destroy() {
+	if atomic.CompareAndSwapInt32(&o.destroyed, 0, 1) {
+		o.decrementPointer()
+	}
+}
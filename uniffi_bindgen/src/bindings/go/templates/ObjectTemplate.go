@@ -0,0 +1,89 @@
+{% let name = obj.type_()|helper_name_go -%}
+{%- let object_name = obj.name()|class_name_go -%}
+// {{ object_name }} wraps a rust object, which is freed once the {{ object_name }} is
+// garbage-collected, or sooner by calling its Destroy method. Its methods can be called from
+// any goroutine.
+type {{ object_name }} struct {
+	ffiObject uniffiFFIObject
+}
+
+func uniffiLift{{ name }}(pointer unsafe.Pointer) *{{ object_name }} {
+	value := &{{ object_name }}{
+		ffiObject: newUniffiFFIObject(pointer, func(pointer unsafe.Pointer, status *C.RustCallStatus) {
+			C.{{ obj.ffi_object_free().name() }}(pointer, status)
+		}),
+	}
+	runtime.SetFinalizer(value, (*{{ object_name }}).Destroy)
+	return value
+}
+
+func uniffiLower{{ name }}(value *{{ object_name }}) unsafe.Pointer {
+	return value.ffiObject.lowerPointer("{{ object_name }}")
+}
+
+func uniffiRead{{ name }}(reader *bytes.Reader) *{{ object_name }} {
+	return uniffiLift{{ name }}(unsafe.Pointer(uintptr(uniffiReadUint64(reader))))
+}
+
+func uniffiWrite{{ name }}(writer *bytes.Buffer, value *{{ object_name }}) {
+	uniffiWriteUint64(writer, uint64(uintptr(uniffiLower{{ name }}(value))))
+}
+
+{%- for cons in obj.constructors() %}
+
+func New{{ object_name }}{% if !cons.is_primary_constructor() %}{{ cons.name()|fn_name_go }}{% endif %}({% call go::arg_list_decl(cons) %}) {% if cons.throws().is_some() %}(*{{ object_name }}, error){% else %}*{{ object_name }}{% endif %} {
+	var _uniffiStatus C.RustCallStatus
+	_uniffiRV := {% call go::to_ffi_call(cons, "") %}
+	{%- call go::keep_alive(cons) %}
+	{%- match cons.throws_type() %}
+	{%- when Some with (error_type) %}
+	if err := uniffiCheckCallStatus(&_uniffiStatus, uniffiLiftError{{ error_type|helper_name_go }}); err != nil {
+		return nil, err
+	}
+	return uniffiLift{{ name }}(_uniffiRV), nil
+	{%- when None %}
+	uniffiCheckCallStatus(&_uniffiStatus, nil)
+	return uniffiLift{{ name }}(_uniffiRV)
+	{%- endmatch %}
+}
+{%- endfor %}
+
+// Destroy frees the rust object without waiting for the {{ object_name }} to be
+// garbage-collected. It can be called more than once, but once it's called, calling any other
+// method panics.
+func (_self *{{ object_name }}) Destroy() {
+	runtime.SetFinalizer(_self, nil)
+	_self.ffiObject.destroy()
+}
+
+{%- match obj.ffi_object_clone() %}
+{%- when Some with (ffi_clone) %}
+
+// Clone returns a new {{ object_name }}, with an independent copy of the rust object.
+func (_self *{{ object_name }}) Clone() *{{ object_name }} {
+	_pointer := _self.ffiObject.incrementPointer("{{ object_name }}")
+	defer _self.ffiObject.decrementPointer()
+	var _uniffiStatus C.RustCallStatus
+	_uniffiRV := C.{{ ffi_clone.name() }}(_pointer, &_uniffiStatus)
+	uniffiCheckCallStatus(&_uniffiStatus, nil)
+	return uniffiLift{{ name }}(_uniffiRV)
+}
+{%- when None %}
+{%- endmatch %}
+
+{%- if obj.is_opaque_token() %}
+
+// Equals reports whether the two handles are for the same rust value.
+func (_self *{{ object_name }}) Equals(other *{{ object_name }}) bool {
+	return _self.ffiObject.pointer == other.ffiObject.pointer
+}
+{%- endif %}
+
+{%- for meth in obj.methods() %}
+
+func (_self *{{ object_name }}) {{ meth.name()|fn_name_go }}({% call go::arg_list_decl(meth) %}){% call go::return_decl(meth) %} {
+	_pointer := _self.ffiObject.incrementPointer("{{ object_name }}")
+	defer _self.ffiObject.decrementPointer()
+	{%- call go::call_body(meth, "_pointer, ") %}
+}
+{%- endfor %}
@@ -0,0 +1,26 @@
+{% let name = type_|helper_name_go -%}
+func uniffiRead{{ name }}(reader *bytes.Reader) {{ type_|type_go }} {
+	if uniffiReadInt8(reader) == 0 {
+		return nil
+	}
+	{%- if inner|is_object %}
+	return {{ inner|read_go }}
+	{%- else %}
+	value := {{ inner|read_go }}
+	return &value
+	{%- endif %}
+}
+
+func uniffiWrite{{ name }}(writer *bytes.Buffer, value {{ type_|type_go }}) {
+	if value == nil {
+		uniffiWriteInt8(writer, 0)
+		return
+	}
+	uniffiWriteInt8(writer, 1)
+	{%- if inner|is_object %}
+	{{ "value"|write_go(inner) }}
+	{%- else %}
+	{{ "*value"|write_go(inner) }}
+	{%- endif %}
+}
+{% call go::lift_lower_buffer(type_) %}
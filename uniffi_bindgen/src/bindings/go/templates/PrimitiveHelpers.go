@@ -0,0 +1,227 @@
+// Numbers are passed as the C type of the same size, and serialized in big-endian order.
+
+func uniffiLowerInt8(value int8) C.int8_t {
+	return C.int8_t(value)
+}
+
+func uniffiLiftInt8(value C.int8_t) int8 {
+	return int8(value)
+}
+
+func uniffiReadInt8(reader *bytes.Reader) int8 {
+	var value int8
+	uniffiReadValue(reader, &value)
+	return value
+}
+
+func uniffiWriteInt8(writer *bytes.Buffer, value int8) {
+	uniffiWriteValue(writer, value)
+}
+
+func uniffiLowerUint8(value uint8) C.uint8_t {
+	return C.uint8_t(value)
+}
+
+func uniffiLiftUint8(value C.uint8_t) uint8 {
+	return uint8(value)
+}
+
+func uniffiReadUint8(reader *bytes.Reader) uint8 {
+	var value uint8
+	uniffiReadValue(reader, &value)
+	return value
+}
+
+func uniffiWriteUint8(writer *bytes.Buffer, value uint8) {
+	uniffiWriteValue(writer, value)
+}
+
+func uniffiLowerInt16(value int16) C.int16_t {
+	return C.int16_t(value)
+}
+
+func uniffiLiftInt16(value C.int16_t) int16 {
+	return int16(value)
+}
+
+func uniffiReadInt16(reader *bytes.Reader) int16 {
+	var value int16
+	uniffiReadValue(reader, &value)
+	return value
+}
+
+func uniffiWriteInt16(writer *bytes.Buffer, value int16) {
+	uniffiWriteValue(writer, value)
+}
+
+func uniffiLowerUint16(value uint16) C.uint16_t {
+	return C.uint16_t(value)
+}
+
+func uniffiLiftUint16(value C.uint16_t) uint16 {
+	return uint16(value)
+}
+
+func uniffiReadUint16(reader *bytes.Reader) uint16 {
+	var value uint16
+	uniffiReadValue(reader, &value)
+	return value
+}
+
+func uniffiWriteUint16(writer *bytes.Buffer, value uint16) {
+	uniffiWriteValue(writer, value)
+}
+
+func uniffiLowerInt32(value int32) C.int32_t {
+	return C.int32_t(value)
+}
+
+func uniffiLiftInt32(value C.int32_t) int32 {
+	return int32(value)
+}
+
+func uniffiReadInt32(reader *bytes.Reader) int32 {
+	var value int32
+	uniffiReadValue(reader, &value)
+	return value
+}
+
+func uniffiWriteInt32(writer *bytes.Buffer, value int32) {
+	uniffiWriteValue(writer, value)
+}
+
+func uniffiLowerUint32(value uint32) C.uint32_t {
+	return C.uint32_t(value)
+}
+
+func uniffiLiftUint32(value C.uint32_t) uint32 {
+	return uint32(value)
+}
+
+func uniffiReadUint32(reader *bytes.Reader) uint32 {
+	var value uint32
+	uniffiReadValue(reader, &value)
+	return value
+}
+
+func uniffiWriteUint32(writer *bytes.Buffer, value uint32) {
+	uniffiWriteValue(writer, value)
+}
+
+func uniffiLowerInt64(value int64) C.int64_t {
+	return C.int64_t(value)
+}
+
+func uniffiLiftInt64(value C.int64_t) int64 {
+	return int64(value)
+}
+
+func uniffiReadInt64(reader *bytes.Reader) int64 {
+	var value int64
+	uniffiReadValue(reader, &value)
+	return value
+}
+
+func uniffiWriteInt64(writer *bytes.Buffer, value int64) {
+	uniffiWriteValue(writer, value)
+}
+
+func uniffiLowerUint64(value uint64) C.uint64_t {
+	return C.uint64_t(value)
+}
+
+func uniffiLiftUint64(value C.uint64_t) uint64 {
+	return uint64(value)
+}
+
+func uniffiReadUint64(reader *bytes.Reader) uint64 {
+	var value uint64
+	uniffiReadValue(reader, &value)
+	return value
+}
+
+func uniffiWriteUint64(writer *bytes.Buffer, value uint64) {
+	uniffiWriteValue(writer, value)
+}
+
+func uniffiLowerFloat32(value float32) C.float {
+	return C.float(value)
+}
+
+func uniffiLiftFloat32(value C.float) float32 {
+	return float32(value)
+}
+
+func uniffiReadFloat32(reader *bytes.Reader) float32 {
+	var value float32
+	uniffiReadValue(reader, &value)
+	return value
+}
+
+func uniffiWriteFloat32(writer *bytes.Buffer, value float32) {
+	uniffiWriteValue(writer, value)
+}
+
+func uniffiLowerFloat64(value float64) C.double {
+	return C.double(value)
+}
+
+func uniffiLiftFloat64(value C.double) float64 {
+	return float64(value)
+}
+
+func uniffiReadFloat64(reader *bytes.Reader) float64 {
+	var value float64
+	uniffiReadValue(reader, &value)
+	return value
+}
+
+func uniffiWriteFloat64(writer *bytes.Buffer, value float64) {
+	uniffiWriteValue(writer, value)
+}
+
+// Booleans are passed as an `int8_t` that's `1` for true and `0` for false.
+
+func uniffiLowerBool(value bool) C.int8_t {
+	if value {
+		return 1
+	}
+	return 0
+}
+
+func uniffiLiftBool(value C.int8_t) bool {
+	return value != 0
+}
+
+func uniffiReadBool(reader *bytes.Reader) bool {
+	return uniffiReadInt8(reader) != 0
+}
+
+func uniffiWriteBool(writer *bytes.Buffer, value bool) {
+	uniffiWriteInt8(writer, int8(uniffiLowerBool(value)))
+}
+
+// Strings are passed as a `RustBuffer` of their UTF-8 bytes, and serialized with their
+// length first.
+
+func uniffiLowerString(value string) C.RustBuffer {
+	return uniffiRustBufferFromBytes([]byte(value))
+}
+
+func uniffiLiftString(rb C.RustBuffer) string {
+	return string(uniffiRustBufferToBytes(rb))
+}
+
+func uniffiReadString(reader *bytes.Reader) string {
+	length := uniffiReadLength(reader)
+	value := make([]byte, length)
+	if _, err := io.ReadFull(reader, value); err != nil {
+		panic(&InternalError{Message: fmt.Sprintf("failed to read a string from a RustBuffer: %v", err)})
+	}
+	return string(value)
+}
+
+func uniffiWriteString(writer *bytes.Buffer, value string) {
+	uniffiWriteInt32(writer, int32(len(value)))
+	writer.WriteString(value)
+}
@@ -0,0 +1,35 @@
+{% let name = rec.type_()|helper_name_go -%}
+{%- let record_name = rec.name()|class_name_go -%}
+type {{ record_name }} struct {
+	{%- for field in rec.fields() %}
+	{{ field.name()|field_name_go }} {{ field.type_()|type_go }}
+	{%- endfor %}
+}
+{%- if rec.has_sensitive_fields() %}
+
+// String leaves out the `[Sensitive]` fields, so that they don't end up in logs.
+func (r {{ record_name }}) String() string {
+	return fmt.Sprintf("{{ record_name }}{ {%- for field in rec.fields() %}{{ field.name()|field_name_go }}:{% if field.sensitive() %}***{% else %}%v{% endif %}{% if !loop.last %} {% endif %}{%- endfor %}}"
+		{%- for field in rec.fields() %}{% if !field.sensitive() %}, r.{{ field.name()|field_name_go }}{% endif %}{% endfor %})
+}
+
+// GoString is used for `%#v`, which would otherwise print the `[Sensitive]` fields too.
+func (r {{ record_name }}) GoString() string {
+	return r.String()
+}
+{%- endif %}
+
+func uniffiRead{{ name }}(reader *bytes.Reader) {{ record_name }} {
+	return {{ record_name }}{
+		{%- for field in rec.fields() %}
+		{{ field.name()|field_name_go }}: {{ field.type_()|read_go }},
+		{%- endfor %}
+	}
+}
+
+func uniffiWrite{{ name }}(writer *bytes.Buffer, value {{ record_name }}) {
+	{%- for field in rec.fields() %}
+	{{ field.name()|member_go("value")|write_go(field.type_()) }}
+	{%- endfor %}
+}
+{% call go::lift_lower_buffer(rec.type_()) %}
@@ -0,0 +1,70 @@
+// uniffiRustBufferFromBytes copies bytes into a new `RustBuffer`, which the rust code is
+// responsible for freeing once it's passed across the FFI.
+func uniffiRustBufferFromBytes(b []byte) C.RustBuffer {
+	// cgo doesn't let C code hold on to Go memory, so the bytes are copied into C memory
+	// first, and from there into the `RustBuffer`.
+	var data unsafe.Pointer
+	if len(b) > 0 {
+		data = C.CBytes(b)
+		defer C.free(data)
+	}
+	foreignBytes := C.ForeignBytes{
+		len:  C.int32_t(len(b)),
+		data: (*C.uint8_t)(data),
+	}
+	var status C.RustCallStatus
+	rb := C.{{ ci.ffi_rustbuffer_from_bytes().name() }}(foreignBytes, &status)
+	uniffiCheckCallStatus(&status, nil)
+	return rb
+}
+
+// uniffiRustBufferToBytes copies the bytes out of a `RustBuffer` that the rust code returned,
+// and frees it.
+func uniffiRustBufferToBytes(rb C.RustBuffer) []byte {
+	defer uniffiRustBufferFree(rb)
+	if rb.data == nil {
+		return nil
+	}
+	return C.GoBytes(unsafe.Pointer(rb.data), C.int(rb.len))
+}
+
+func uniffiRustBufferFree(rb C.RustBuffer) {
+	var status C.RustCallStatus
+	C.{{ ci.ffi_rustbuffer_free().name() }}(rb, &status)
+	uniffiCheckCallStatus(&status, nil)
+}
+
+// uniffiRustBufferReader reads the values serialized into a `RustBuffer` that the rust code
+// returned, which it frees.
+func uniffiRustBufferReader(rb C.RustBuffer) *bytes.Reader {
+	return bytes.NewReader(uniffiRustBufferToBytes(rb))
+}
+
+func uniffiCheckFullyRead(reader *bytes.Reader) {
+	if reader.Len() != 0 {
+		panic(&InternalError{Message: "junk remaining in buffer after lifting"})
+	}
+}
+
+// uniffiReadValue reads a fixed-size value, such as a number, in big-endian order.
+func uniffiReadValue(reader *bytes.Reader, value interface{}) {
+	if err := binary.Read(reader, binary.BigEndian, value); err != nil {
+		panic(&InternalError{Message: fmt.Sprintf("failed to read from a RustBuffer: %v", err)})
+	}
+}
+
+// uniffiWriteValue writes a fixed-size value, such as a number, in big-endian order.
+func uniffiWriteValue(writer *bytes.Buffer, value interface{}) {
+	// Writing to a bytes.Buffer can't fail.
+	_ = binary.Write(writer, binary.BigEndian, value)
+}
+
+// uniffiReadLength reads the length of a string, sequence or map.
+func uniffiReadLength(reader *bytes.Reader) int {
+	var length int32
+	uniffiReadValue(reader, &length)
+	if length < 0 {
+		panic(&InternalError{Message: "unexpected negative length in a RustBuffer"})
+	}
+	return int(length)
+}
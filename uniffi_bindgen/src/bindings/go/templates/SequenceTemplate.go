@@ -0,0 +1,17 @@
+{% let name = type_|helper_name_go -%}
+func uniffiRead{{ name }}(reader *bytes.Reader) {{ type_|type_go }} {
+	length := uniffiReadLength(reader)
+	value := make({{ type_|type_go }}, 0, length)
+	for i := 0; i < length; i++ {
+		value = append(value, {{ inner|read_go }})
+	}
+	return value
+}
+
+func uniffiWrite{{ name }}(writer *bytes.Buffer, value {{ type_|type_go }}) {
+	uniffiWriteInt32(writer, int32(len(value)))
+	for _, item := range value {
+		{{ "item"|write_go(inner) }}
+	}
+}
+{% call go::lift_lower_buffer(type_) %}
@@ -0,0 +1,27 @@
+// Timestamps are serialized as seconds and nanoseconds either side of the Unix epoch.
+
+func uniffiReadTimestamp(reader *bytes.Reader) time.Time {
+	seconds := uniffiReadInt64(reader)
+	nanoseconds := uniffiReadUint32(reader)
+	if nanoseconds >= 1000000000 {
+		panic(&InternalError{Message: "Timestamp nanoseconds exceed the maximum supported by uniffi"})
+	}
+	if seconds >= 0 {
+		return time.Unix(seconds, int64(nanoseconds)).UTC()
+	}
+	return time.Unix(seconds, -int64(nanoseconds)).UTC()
+}
+
+func uniffiWriteTimestamp(writer *bytes.Buffer, value time.Time) {
+	// Go counts the nanoseconds forward from the second, where they're serialized
+	// away from the epoch.
+	seconds := value.Unix()
+	nanoseconds := int64(value.Nanosecond())
+	if seconds < 0 && nanoseconds > 0 {
+		seconds++
+		nanoseconds = 1000000000 - nanoseconds
+	}
+	uniffiWriteInt64(writer, seconds)
+	uniffiWriteUint32(writer, uint32(nanoseconds))
+}
+{% call go::lift_lower_buffer(type_) %}
@@ -0,0 +1,3 @@
+func {{ func.name()|fn_name_go }}({% call go::arg_list_decl(func) %}){% call go::return_decl(func) %} {
+	{%- call go::call_body(func, "") %}
+}
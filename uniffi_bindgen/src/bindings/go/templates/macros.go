@@ -0,0 +1,113 @@
+{#
+// Template to call into rust. Used in several places.
+// Variable names in `arg_list_decl` should match up with arg lists
+// passed to rust via `_arg_list_ffi_call` (we use `var_name_go` in `lower_go`)
+#}
+
+{%- macro to_ffi_call(func, prefix) -%}
+C.{{ func.ffi_func().name() }}({{ prefix }}{% call _arg_list_ffi_call(func) %}&_uniffiStatus)
+{%- endmacro -%}
+
+{%- macro _arg_list_ffi_call(func) %}
+    {%- for arg in func.arguments() %}
+        {{- arg.name()|var_name_go|lower_go(arg.type_()) }}, {% endfor %}
+{%- endmacro -%}
+
+{#-
+// The finalizer of an object that's passed to rust mustn't free it until
+// the call returns.
+-#}
+{%- macro keep_alive(func) %}
+    {%- for arg in func.arguments() %}
+    {%- if self.keeps_alive(arg) %}
+	runtime.KeepAlive({{ arg.name()|var_name_go }})
+    {%- endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{#-
+// The body of a function or method, which calls into rust, and returns what
+// it returned, or the error it threw.
+-#}
+{%- macro call_body(func, prefix) %}
+	var _uniffiStatus C.RustCallStatus
+	{% if func.return_type().is_some() %}_uniffiRV := {% endif %}{% call to_ffi_call(func, prefix) %}
+	{%- call keep_alive(func) %}
+	{%- match func.throws_type() %}
+	{%- when Some with (error_type) %}
+	if err := uniffiCheckCallStatus(&_uniffiStatus, uniffiLiftError{{ error_type|helper_name_go }}); err != nil {
+		{%- match func.return_type() %}
+		{%- when Some with (return_type) %}
+		var _uniffiZero {{ return_type|type_go }}
+		return _uniffiZero, err
+		{%- when None %}
+		return err
+		{%- endmatch %}
+	}
+	{%- match func.return_type() %}
+	{%- when Some with (return_type) %}
+	return {{ "_uniffiRV"|lift_go(return_type) }}, nil
+	{%- when None %}
+	return nil
+	{%- endmatch %}
+	{%- when None %}
+	uniffiCheckCallStatus(&_uniffiStatus, nil)
+	{%- match func.return_type() %}
+	{%- when Some with (return_type) %}
+	return {{ "_uniffiRV"|lift_go(return_type) }}
+	{%- when None %}
+	{%- endmatch %}
+	{%- endmatch %}
+{%- endmacro -%}
+
+{#-
+// Arglist as used in Go declarations of functions, methods and constructors.
+// Note the var_name_go and type_go filters.
+-#}
+{%- macro arg_list_decl(func) %}
+    {%- for arg in func.arguments() -%}
+        {{ arg.name()|var_name_go }} {{ arg.type_()|type_go }}
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+{%- endmacro -%}
+
+{#-
+// What a function or method returns, with an `error` if it throws.
+-#}
+{%- macro return_decl(func) %}
+    {%- match func.return_type() %}
+    {%- when Some with (return_type) %}
+    {%- if func.throws().is_some() %} ({{ return_type|type_go }}, error){% else %} {{ return_type|type_go }}{% endif %}
+    {%- when None %}
+    {%- if func.throws().is_some() %} error{% endif %}
+    {%- endmatch %}
+{%- endmacro -%}
+
+{#-
+// Arglist as used in the cgo declarations of the FFI functions.
+// Note unfiltered name but ffi_type_c filters.
+-#}
+{%- macro arg_list_ffi_decl(func) %}
+    {%- for arg in func.arguments() %}{{ arg.type_()|ffi_type_c }} {{ arg.name() }}, {% endfor -%}
+    RustCallStatus *out_status
+{%- endmacro -%}
+
+{#-
+// The functions that lower values into, and lift them out of, a `RustBuffer`, for
+// the types that are passed across the FFI serialized into one.
+-#}
+{%- macro lift_lower_buffer(type_) %}
+{%- let name = type_|helper_name_go %}
+func uniffiLower{{ name }}(value {{ type_|type_go }}) C.RustBuffer {
+	var writer bytes.Buffer
+	uniffiWrite{{ name }}(&writer, value)
+	return uniffiRustBufferFromBytes(writer.Bytes())
+}
+
+func uniffiLift{{ name }}(rb C.RustBuffer) {{ type_|type_go }} {
+	reader := uniffiRustBufferReader(rb)
+	value := uniffiRead{{ name }}(reader)
+	uniffiCheckFullyRead(reader)
+	return value
+}
+{%- endmacro -%}
@@ -0,0 +1,113 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+// Package {{ config.package_name() }} is the Go interface to the `{{ ci.namespace() }}` component,
+// which it calls through cgo.
+//
+// Ideally the helper code in here would live in a separate package where it could be unittested
+// etc in isolation. However, it's important that the details of how this helper code works (e.g.
+// the way that different builtin types are passed across the FFI) exactly match what's expected
+// by the rust code on the other side of the interface. In practice right now that means coming
+// from the exact same version of `uniffi` that was used to compile the rust component. The easiest
+// way to ensure this is to bundle the Go helpers directly inline like we're doing here.
+package {{ config.package_name() }}
+
+/*
+#cgo LDFLAGS: -l{{ config.cdylib_name() }}
+
+#include <stdint.h>
+#include <stdlib.h>
+
+// The following structs are used to implement the lowest level
+// of the FFI, and must match the rust code's layout of them.
+
+typedef struct RustBuffer {
+	int32_t capacity;
+	int32_t len;
+	uint8_t *data;
+} RustBuffer;
+
+typedef struct ForeignBytes {
+	int32_t len;
+	const uint8_t *data;
+} ForeignBytes;
+
+typedef struct RustCallStatus {
+	int8_t code;
+	RustBuffer errorBuf;
+} RustCallStatus;
+
+{% for func in ci.iter_ffi_function_definitions() -%}
+{% match func.return_type() %}{% when Some with (type_) %}{{ type_|ffi_type_c }}{% when None %}void{% endmatch %} {{ func.name() }}({% call go::arg_list_ffi_decl(func) %});
+{% endfor -%}
+*/
+import "C"
+
+import (
+	{%- for package in self.imports() %}
+	"{{ package }}"
+	{%- endfor %}
+)
+
+{% include "RustBufferTemplate.go" %}
+
+{% include "Helpers.go" %}
+
+{% include "PrimitiveHelpers.go" %}
+
+{% include "NamespaceLibraryTemplate.go" %}
+
+{%- if !ci.iter_object_definitions().is_empty() %}
+
+{% include "ObjectRuntime.go" %}
+{%- endif %}
+
+{%- for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::Timestamp %}
+
+{% include "TimestampHelper.go" %}
+{%- when Type::Duration %}
+
+{% include "DurationHelper.go" %}
+{%- when Type::Optional with (inner) %}
+
+{% include "OptionalTemplate.go" %}
+{%- when Type::Sequence with (inner) %}
+
+{% include "SequenceTemplate.go" %}
+{%- when Type::Map with (key_type, inner) %}
+
+{% include "MapTemplate.go" %}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+// Public interface members begin here.
+
+{%- for e in ci.iter_enum_definitions() %}
+
+{% include "EnumTemplate.go" %}
+{%- endfor %}
+
+{%- for e in ci.iter_error_definitions() %}
+
+{% include "ErrorTemplate.go" %}
+{%- endfor %}
+
+{%- for rec in ci.iter_record_definitions() %}
+
+{% include "RecordTemplate.go" %}
+{%- endfor %}
+
+{%- for func in ci.iter_function_definitions() %}
+
+{% include "TopLevelFunctionTemplate.go" %}
+{%- endfor %}
+
+{%- for obj in ci.iter_object_definitions() %}
+
+{% include "ObjectTemplate.go" %}
+{%- endfor %}
+
+{% import "macros.go" as go %}
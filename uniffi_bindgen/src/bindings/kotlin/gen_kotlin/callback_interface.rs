@@ -4,8 +4,8 @@
 
 use std::fmt;
 
-use crate::backend::{CodeDeclaration, CodeOracle, CodeType, Literal};
-use crate::interface::{CallbackInterface, ComponentInterface};
+use crate::backend::{CodeDeclaration, CodeOracle, CodeType, Literal, TypeIdentifier};
+use crate::interface::{CallbackInterface, ComponentInterface, FFIFunction};
 use askama::Template;
 
 use super::filters;
@@ -119,12 +119,15 @@ impl CodeDeclaration for KotlinCallbackInterface {
 #[template(syntax = "kt", escape = "none", path = "CallbackInterfaceRuntime.kt")]
 pub struct KotlinCallbackInterfaceRuntime {
     is_needed: bool,
+    // The function that completes the futures of `[Async]` methods, if there are any.
+    ffi_callback_future_complete: Option<FFIFunction>,
 }
 
 impl KotlinCallbackInterfaceRuntime {
     pub fn new(ci: &ComponentInterface) -> Self {
         Self {
             is_needed: !ci.iter_callback_interface_definitions().is_empty(),
+            ffi_callback_future_complete: ci.ffi_callback_future_complete(),
         }
     }
 }
@@ -137,4 +140,14 @@ impl CodeDeclaration for KotlinCallbackInterfaceRuntime {
             Some(self.render().unwrap())
         }
     }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        self.ffi_callback_future_complete.as_ref().map(|_| {
+            vec![
+                "kotlin.coroutines.Continuation".into(),
+                "kotlin.coroutines.EmptyCoroutineContext".into(),
+                "kotlin.coroutines.startCoroutine".into(),
+            ]
+        })
+    }
 }
@@ -9,10 +9,18 @@ use std::fmt;
 
 use super::filters;
 
-fn render_literal(oracle: &dyn CodeOracle, literal: &Literal, inner: &TypeIdentifier) -> String {
+fn render_literal(
+    oracle: &dyn CodeOracle,
+    literal: &Literal,
+    inner: &TypeIdentifier,
+    outer: &TypeIdentifier,
+) -> String {
     match literal {
         Literal::Null => "null".into(),
         Literal::EmptySequence => "listOf()".into(),
+        Literal::EmptyMap if matches!(outer, TypeIdentifier::OrderedMap(_)) => {
+            "linkedMapOf()".into()
+        }
         Literal::EmptyMap => "mapOf()".into(),
 
         // For optionals
@@ -52,7 +60,7 @@ macro_rules! impl_code_type_for_compound {
                 }
 
                 fn literal(&self, oracle: &dyn CodeOracle, literal: &Literal) -> String {
-                    render_literal(oracle, &literal, self.inner())
+                    render_literal(oracle, &literal, self.inner(), self.outer())
                 }
 
                 fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
@@ -86,4 +94,89 @@ impl_code_type_for_compound!(
     "Sequence{}",
     "SequenceTemplate.kt"
 );
-impl_code_type_for_compound!(MapCodeType, "Map<String, {}>", "Map{}", "MapTemplate.kt");
+impl_code_type_for_compound!(
+    OrderedMapCodeType,
+    "LinkedHashMap<String, {}>",
+    "OrderedMap{}",
+    "OrderedMapTemplate.kt"
+);
+
+// Maps have a key type as well as the value type, so they don't fit the macro above.
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "MapTemplate.kt")]
+pub struct MapCodeType {
+    key: TypeIdentifier,
+    value: TypeIdentifier,
+    outer: TypeIdentifier,
+}
+
+impl MapCodeType {
+    pub fn new(key: TypeIdentifier, value: TypeIdentifier, outer: TypeIdentifier) -> Self {
+        Self { key, value, outer }
+    }
+    fn key(&self) -> &TypeIdentifier {
+        &self.key
+    }
+    fn inner(&self) -> &TypeIdentifier {
+        &self.value
+    }
+    fn outer(&self) -> &TypeIdentifier {
+        &self.outer
+    }
+}
+
+impl CodeType for MapCodeType {
+    fn type_label(&self, oracle: &dyn CodeOracle) -> String {
+        format!(
+            "Map<{}, {}>",
+            oracle.find(self.key()).type_label(oracle),
+            oracle.find(self.inner()).type_label(oracle)
+        )
+    }
+
+    fn canonical_name(&self, oracle: &dyn CodeOracle) -> String {
+        format!(
+            "Map{}_{}",
+            oracle.find(self.key()).canonical_name(oracle),
+            oracle.find(self.inner()).canonical_name(oracle)
+        )
+    }
+
+    fn literal(&self, oracle: &dyn CodeOracle, literal: &Literal) -> String {
+        render_literal(oracle, literal, self.inner(), self.outer())
+    }
+
+    fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "lower{}({})",
+            self.canonical_name(oracle),
+            oracle.var_name(nm)
+        )
+    }
+
+    fn write(
+        &self,
+        oracle: &dyn CodeOracle,
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+    ) -> String {
+        format!(
+            "write{}({}, {})",
+            self.canonical_name(oracle),
+            oracle.var_name(nm),
+            target
+        )
+    }
+
+    fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("lift{}({})", self.canonical_name(oracle), nm)
+    }
+
+    fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("read{}({})", self.canonical_name(oracle), nm)
+    }
+
+    fn helper_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+}
@@ -74,12 +74,17 @@ impl CodeType for EnumCodeType {
 pub struct KotlinEnum {
     inner: Enum,
     contains_object_references: bool,
+    is_parcelable: bool,
 }
 
 impl KotlinEnum {
-    pub fn new(inner: Enum, ci: &ComponentInterface) -> Self {
+    pub fn new(inner: Enum, ci: &ComponentInterface, parcelable_records: bool) -> Self {
+        let contains_object_references = ci.item_contains_object_references(&inner);
         Self {
-            contains_object_references: ci.item_contains_object_references(&inner),
+            // Kotlin's `enum class` is already parcelable, so only the `sealed class`
+            // form of enums needs any help.
+            is_parcelable: parcelable_records && !contains_object_references && !inner.is_flat(),
+            contains_object_references,
             inner,
         }
     }
@@ -89,10 +94,21 @@ impl KotlinEnum {
     pub fn contains_object_references(&self) -> bool {
         self.contains_object_references
     }
+    pub fn is_parcelable(&self) -> bool {
+        self.is_parcelable
+    }
 }
 
 impl CodeDeclaration for KotlinEnum {
     fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
         Some(self.render().unwrap())
     }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        if self.is_parcelable {
+            Some(super::record::parcelable_imports())
+        } else {
+            None
+        }
+    }
 }
@@ -4,7 +4,7 @@
 
 use std::fmt;
 
-use crate::backend::{CodeDeclaration, CodeOracle, CodeType, Literal};
+use crate::backend::{CodeDeclaration, CodeOracle, CodeType, Literal, TypeIdentifier};
 use crate::interface::{ComponentInterface, Error};
 use askama::Template;
 
@@ -0,0 +1,258 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::fmt;
+
+use crate::backend::{CodeDeclaration, CodeOracle, CodeType, Literal};
+use crate::interface::{ComponentInterface, Type};
+use askama::Template;
+use heck::CamelCase;
+
+use super::filters;
+
+/// The package that the bindings of the component built from `crate_name` are expected to be in.
+pub fn external_package_name(crate_name: &str) -> String {
+    format!("uniffi.{}", crate_name.replace('-', "_"))
+}
+
+/// The name of the function that checks the component built from `crate_name` against this one.
+fn check_component_fn_name(crate_name: &str) -> String {
+    format!(
+        "uniffiCheck{}Component",
+        crate_name.replace('-', "_").to_camel_case()
+    )
+}
+
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "ExternalTemplate.kt")]
+pub struct ExternalCodeType {
+    name: String,
+    crate_name: String,
+}
+
+impl ExternalCodeType {
+    pub fn new(name: String, crate_name: String) -> Self {
+        Self { name, crate_name }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn canonical_type_name(&self) -> String {
+        format!("External{}", self.name)
+    }
+
+    pub fn package_name(&self) -> String {
+        external_package_name(&self.crate_name)
+    }
+}
+
+impl CodeType for ExternalCodeType {
+    fn type_label(&self, _oracle: &dyn CodeOracle) -> String {
+        self.name.clone()
+    }
+
+    fn canonical_name(&self, _oracle: &dyn CodeOracle) -> String {
+        self.canonical_type_name()
+    }
+
+    fn literal(&self, _oracle: &dyn CodeOracle, _literal: &Literal) -> String {
+        unreachable!("Can't have a literal of an external type");
+    }
+
+    fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "lower{}({})",
+            self.canonical_name(oracle),
+            oracle.var_name(nm)
+        )
+    }
+
+    fn write(
+        &self,
+        oracle: &dyn CodeOracle,
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+    ) -> String {
+        format!(
+            "write{}({}, {})",
+            self.canonical_name(oracle),
+            oracle.var_name(nm),
+            target
+        )
+    }
+
+    fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("lift{}({})", self.canonical_name(oracle), nm)
+    }
+
+    fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("read{}({})", self.canonical_name(oracle), nm)
+    }
+
+    fn helper_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        Some(vec![format!("{}.{}", self.package_name(), self.name)])
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "ExternalInterfaceTemplate.kt")]
+pub struct ExternalInterfaceCodeType {
+    name: String,
+    crate_name: String,
+}
+
+impl ExternalInterfaceCodeType {
+    pub fn new(name: String, crate_name: String) -> Self {
+        Self { name, crate_name }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn canonical_type_name(&self) -> String {
+        format!("External{}", self.name)
+    }
+
+    pub fn package_name(&self) -> String {
+        external_package_name(&self.crate_name)
+    }
+
+    pub fn check_component_fn_name(&self) -> String {
+        check_component_fn_name(&self.crate_name)
+    }
+}
+
+impl CodeType for ExternalInterfaceCodeType {
+    fn type_label(&self, _oracle: &dyn CodeOracle) -> String {
+        self.name.clone()
+    }
+
+    fn canonical_name(&self, _oracle: &dyn CodeOracle) -> String {
+        self.canonical_type_name()
+    }
+
+    fn literal(&self, _oracle: &dyn CodeOracle, _literal: &Literal) -> String {
+        unreachable!();
+    }
+
+    fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "lower{}({})",
+            self.canonical_name(oracle),
+            oracle.var_name(nm)
+        )
+    }
+
+    fn write(
+        &self,
+        oracle: &dyn CodeOracle,
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+    ) -> String {
+        format!(
+            "write{}({}, {})",
+            self.canonical_name(oracle),
+            oracle.var_name(nm),
+            target
+        )
+    }
+
+    fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("lift{}({})", self.canonical_name(oracle), nm)
+    }
+
+    fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("read{}({})", self.canonical_name(oracle), nm)
+    }
+
+    fn helper_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        Some(vec![format!("{}.{}", self.package_name(), self.name)])
+    }
+}
+
+/// A type of this component that the bindings of other components can use as an external type.
+pub struct ExportedType {
+    name: String,
+    type_: Type,
+    is_object: bool,
+}
+
+/// A component that this one takes `[ExternalInterface]` objects from.
+pub struct ExternalComponent {
+    package_name: String,
+    check_component_fn_name: String,
+    checksum_fn_name: String,
+}
+
+// The helpers that other components' bindings use for our types, since those that the types
+// have themselves are `internal`, and the checks of the components that we use objects from.
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "ExternalTypesTemplate.kt")]
+pub struct KotlinExternalTypes {
+    namespace: String,
+    exported_types: Vec<ExportedType>,
+    external_components: Vec<ExternalComponent>,
+}
+
+impl KotlinExternalTypes {
+    pub fn new(ci: &ComponentInterface) -> Self {
+        let exported_types = ci
+            .iter_types()
+            .into_iter()
+            .filter_map(|type_| {
+                let (name, is_object) = match &type_ {
+                    Type::Record(name)
+                    | Type::Enum(name)
+                    | Type::Error(name)
+                    | Type::Wrapped { name, .. } => (name.clone(), false),
+                    Type::Object(name) => match ci.get_object_definition(name) {
+                        Some(obj) if !obj.is_opaque_token() => (name.clone(), true),
+                        _ => return None,
+                    },
+                    _ => return None,
+                };
+                Some(ExportedType {
+                    name,
+                    type_,
+                    is_object,
+                })
+            })
+            .collect();
+        let external_components = ci
+            .iter_external_interface_crates()
+            .into_iter()
+            .map(|crate_name| ExternalComponent {
+                package_name: external_package_name(&crate_name),
+                check_component_fn_name: check_component_fn_name(&crate_name),
+                checksum_fn_name: ci.ffi_external_checksum(&crate_name).name().to_string(),
+            })
+            .collect();
+        Self {
+            namespace: ci.namespace().to_string(),
+            exported_types,
+            external_components,
+        }
+    }
+}
+
+impl CodeDeclaration for KotlinExternalTypes {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        if self.exported_types.is_empty() && self.external_components.is_empty() {
+            None
+        } else {
+            Some(self.render().unwrap())
+        }
+    }
+}
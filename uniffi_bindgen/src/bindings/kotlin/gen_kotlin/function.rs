@@ -3,7 +3,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use crate::backend::{CodeDeclaration, CodeOracle};
-use crate::interface::{ComponentInterface, Function};
+use crate::interface::{ComponentInterface, FFIFunction, Function, Module, Type};
 use askama::Template;
 
 use super::filters;
@@ -12,15 +12,38 @@ use super::filters;
 #[template(syntax = "kt", escape = "none", path = "TopLevelFunctionTemplate.kt")]
 pub struct KotlinFunction {
     inner: Function,
+    java_interop: bool,
+    errors_as_values: bool,
+    // Whether the function is a member of the `object` for a nested namespace.
+    in_module: bool,
 }
 
 impl KotlinFunction {
-    pub fn new(inner: Function, _ci: &ComponentInterface) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: Function,
+        _ci: &ComponentInterface,
+        java_interop: bool,
+        errors_as_values: bool,
+    ) -> Self {
+        Self {
+            inner,
+            java_interop,
+            errors_as_values,
+            in_module: false,
+        }
     }
     pub fn inner(&self) -> &Function {
         &self.inner
     }
+    pub fn java_interop(&self) -> bool {
+        self.java_interop
+    }
+    pub fn errors_as_values(&self) -> bool {
+        self.errors_as_values
+    }
+    pub fn in_module(&self) -> bool {
+        self.in_module
+    }
 }
 
 impl CodeDeclaration for KotlinFunction {
@@ -28,3 +51,101 @@ impl CodeDeclaration for KotlinFunction {
         Some(self.render().unwrap())
     }
 }
+
+// A namespace nested inside the component's namespace, holding its functions
+// and any namespaces nested further inside it.
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "ModuleTemplate.kt")]
+pub struct KotlinModule {
+    inner: Module,
+    functions: Vec<KotlinFunction>,
+    children: Vec<KotlinModule>,
+}
+
+impl KotlinModule {
+    pub fn new(
+        inner: Module,
+        ci: &ComponentInterface,
+        java_interop: bool,
+        errors_as_values: bool,
+    ) -> Self {
+        Self {
+            functions: ci
+                .iter_module_function_definitions(&inner)
+                .into_iter()
+                .map(|func| KotlinFunction {
+                    in_module: true,
+                    ..KotlinFunction::new(func, ci, java_interop, errors_as_values)
+                })
+                .collect(),
+            children: ci
+                .iter_child_module_definitions(&inner)
+                .into_iter()
+                .map(|module| KotlinModule::new(module, ci, java_interop, errors_as_values))
+                .collect(),
+            inner,
+        }
+    }
+    pub fn inner(&self) -> &Module {
+        &self.inner
+    }
+    pub fn member_code(&self) -> Vec<String> {
+        self.functions
+            .iter()
+            .map(|func| func.render().unwrap())
+            .chain(self.children.iter().map(|module| module.render().unwrap()))
+            .collect()
+    }
+}
+
+impl CodeDeclaration for KotlinModule {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+}
+
+// The calls to `[Async]` functions and methods that are waiting for Rust to complete them, and
+// the completion that Rust calls once they're done.
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "AsyncRuntime.kt")]
+pub struct KotlinAsyncRuntime {
+    ffi_init: Option<FFIFunction>,
+    // Only when cancelling a coroutine should cancel the Rust call.
+    ffi_cancel: Option<FFIFunction>,
+}
+
+impl KotlinAsyncRuntime {
+    pub fn new(ci: &ComponentInterface, cancellable: bool) -> Self {
+        Self {
+            ffi_init: ci.ffi_async_init(),
+            ffi_cancel: if cancellable {
+                ci.ffi_async_cancel()
+            } else {
+                None
+            },
+        }
+    }
+}
+
+impl CodeDeclaration for KotlinAsyncRuntime {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        self.ffi_init.as_ref().map(|_| self.render().unwrap())
+    }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        self.ffi_init.as_ref().map(|_| {
+            vec![
+                "java.util.concurrent.CancellationException".into(),
+                "java.util.concurrent.ConcurrentHashMap".into(),
+                "java.util.concurrent.atomic.AtomicLong".into(),
+                "kotlin.coroutines.Continuation".into(),
+                "kotlin.coroutines.resume".into(),
+                if self.ffi_cancel.is_some() {
+                    "kotlinx.coroutines.suspendCancellableCoroutine".into()
+                } else {
+                    "kotlin.coroutines.suspendCoroutine".into()
+                },
+            ]
+        })
+    }
+}
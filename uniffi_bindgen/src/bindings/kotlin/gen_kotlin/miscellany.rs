@@ -54,6 +54,17 @@ macro_rules! impl_code_type_for_miscellany {
      }
  }
 
+impl_code_type_for_miscellany!(
+    BigIntCodeType,
+    "java.math.BigInteger",
+    "BigInt",
+    "BigIntHelper.kt"
+);
+
+impl_code_type_for_miscellany!(BytesCodeType, "ByteArray", "Bytes", "BytesHelper.kt");
+
+impl_code_type_for_miscellany!(CharCodeType, "Int", "Char", "CharHelper.kt");
+
 impl_code_type_for_miscellany!(
     TimestampCodeType,
     "java.time.Instant",
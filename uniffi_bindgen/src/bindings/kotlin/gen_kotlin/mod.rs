@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 
 use anyhow::Result;
@@ -10,16 +10,24 @@ use askama::Template;
 use heck::{CamelCase, MixedCase, ShoutySnakeCase};
 use serde::{Deserialize, Serialize};
 
-use crate::backend::{CodeDeclaration, CodeOracle, CodeType, TypeIdentifier};
+use crate::backend::{
+    custom_types, renames, renames::RenameKind, ApiFilter, CodeDeclaration, CodeOracle, CodeType,
+    CustomTypesConfig, NamingProfile, Renames, TypeIdentifier,
+};
 use crate::interface::*;
 use crate::MergeWith;
+pub use multiplatform::KotlinFlavor;
 
 mod callback_interface;
 mod compounds;
+mod custom;
 mod enum_;
 mod error;
+mod exports;
+mod external;
 mod function;
 mod miscellany;
+pub mod multiplatform;
 mod object;
 mod primitives;
 mod record;
@@ -32,6 +40,23 @@ mod wrapped;
 pub struct Config {
     package_name: Option<String>,
     cdylib_name: Option<String>,
+    inline_single_field_records: Option<bool>,
+    parcelable_records: Option<bool>,
+    java_interop: Option<bool>,
+    #[serde(default)]
+    custom_types: CustomTypesConfig,
+    #[serde(default)]
+    renames: Renames,
+    naming: Option<NamingProfile>,
+    #[serde(default)]
+    foreign_extensions: BTreeMap<String, String>,
+    #[serde(default)]
+    filter: ApiFilter,
+    flavor: Option<KotlinFlavor>,
+    observable_models: Option<bool>,
+    cancellable_coroutines: Option<bool>,
+    event_flows: Option<bool>,
+    errors_as_values: Option<bool>,
 }
 
 impl Config {
@@ -50,6 +75,85 @@ impl Config {
             "uniffi".into()
         }
     }
+
+    /// Whether records with a single field are generated as value classes.
+    pub fn inline_single_field_records(&self) -> bool {
+        self.inline_single_field_records.unwrap_or(false)
+    }
+
+    /// Whether records, and the enums they might contain, implement `android.os.Parcelable`.
+    pub fn parcelable_records(&self) -> bool {
+        self.parcelable_records.unwrap_or(false)
+    }
+
+    /// Whether to annotate the generated code so that Java can call it naturally, with
+    /// `@JvmOverloads` for default arguments and `@JvmStatic` for the members of objects.
+    pub fn java_interop(&self) -> bool {
+        self.java_interop.unwrap_or(false)
+    }
+
+    /// Types mapped onto custom Kotlin types, keyed by their UDL name.
+    pub fn custom_types(&self) -> &CustomTypesConfig {
+        &self.custom_types
+    }
+
+    /// Legacy Kotlin names for identifiers in the component, see [`crate::backend::renames`].
+    pub fn renames(&self) -> &Renames {
+        &self.renames
+    }
+
+    /// How to name the identifiers that aren't renamed, see [`crate::backend::renames`].
+    pub fn naming(&self) -> NamingProfile {
+        self.naming.unwrap_or_default()
+    }
+
+    /// The package to import a `[ForeignExtension]` interface from, when it isn't in the
+    /// same package as the bindings.
+    pub fn foreign_extension_package(&self, name: &str) -> Option<&str> {
+        self.foreign_extensions.get(name).map(String::as_str)
+    }
+
+    /// The part of the component to generate bindings for, see [`crate::backend::filter`].
+    pub fn filter(&self) -> &ApiFilter {
+        &self.filter
+    }
+
+    /// The platforms to generate the bindings for, see [`multiplatform`].
+    pub fn flavor(&self) -> KotlinFlavor {
+        self.flavor.unwrap_or_default()
+    }
+
+    /// Whether each `[Observable]` object also gets a class holding its latest snapshot in a
+    /// `StateFlow`, which needs `kotlinx-coroutines-core`.
+    pub fn observable_models(&self) -> bool {
+        self.observable_models.unwrap_or(false)
+    }
+
+    /// Whether cancelling the coroutine that's waiting for an `[Async]` function or method also
+    /// cancels the Rust call, which needs `kotlinx-coroutines-core`.
+    pub fn cancellable_coroutines(&self) -> bool {
+        self.cancellable_coroutines.unwrap_or(false)
+    }
+
+    /// Whether each object with `[Events]` also gets an `events` property, which is a `Flow` of
+    /// the events that it emits and needs `kotlinx-coroutines-core`.
+    pub fn event_flows(&self) -> bool {
+        self.event_flows.unwrap_or(false)
+    }
+
+    /// Whether the functions and methods that throw return a `Result` instead, which holds
+    /// either what they returned or the exception that they'd otherwise throw.
+    pub fn errors_as_values(&self) -> bool {
+        self.errors_as_values.unwrap_or(false)
+    }
+
+    /// Fill in any renames not already configured from those in a `renames.toml` file.
+    pub fn with_renames_from(&self, renames: &Renames) -> Self {
+        Config {
+            renames: self.renames.merge_with(renames),
+            ..self.clone()
+        }
+    }
 }
 
 impl From<&ComponentInterface> for Config {
@@ -57,6 +161,19 @@ impl From<&ComponentInterface> for Config {
         Config {
             package_name: Some(format!("uniffi.{}", ci.namespace())),
             cdylib_name: Some(format!("uniffi_{}", ci.namespace())),
+            inline_single_field_records: None,
+            parcelable_records: None,
+            java_interop: None,
+            custom_types: Default::default(),
+            renames: Default::default(),
+            naming: None,
+            foreign_extensions: Default::default(),
+            filter: Default::default(),
+            flavor: None,
+            observable_models: None,
+            cancellable_coroutines: None,
+            event_flows: None,
+            errors_as_values: None,
         }
     }
 }
@@ -66,6 +183,27 @@ impl MergeWith for Config {
         Config {
             package_name: self.package_name.merge_with(&other.package_name),
             cdylib_name: self.cdylib_name.merge_with(&other.cdylib_name),
+            inline_single_field_records: self
+                .inline_single_field_records
+                .merge_with(&other.inline_single_field_records),
+            parcelable_records: self
+                .parcelable_records
+                .merge_with(&other.parcelable_records),
+            java_interop: self.java_interop.merge_with(&other.java_interop),
+            custom_types: self.custom_types.merge_with(&other.custom_types),
+            renames: self.renames.merge_with(&other.renames),
+            naming: self.naming.merge_with(&other.naming),
+            foreign_extensions: self
+                .foreign_extensions
+                .merge_with(&other.foreign_extensions),
+            filter: self.filter.merge_with(&other.filter),
+            flavor: self.flavor.merge_with(&other.flavor),
+            observable_models: self.observable_models.merge_with(&other.observable_models),
+            cancellable_coroutines: self
+                .cancellable_coroutines
+                .merge_with(&other.cancellable_coroutines),
+            event_flows: self.event_flows.merge_with(&other.event_flows),
+            errors_as_values: self.errors_as_values.merge_with(&other.errors_as_values),
         }
     }
 }
@@ -86,27 +224,101 @@ impl<'a> KotlinWrapper<'a> {
         }
     }
 
+    /// The version of `uniffi-bindgen` that's generating the bindings.
+    pub fn bindgen_version(&self) -> &'static str {
+        crate::BINDGEN_VERSION
+    }
+
     pub fn members(&self) -> Vec<Box<dyn CodeDeclaration + 'a>> {
         let ci = self.ci;
+        let inline_records = self.config.inline_single_field_records();
+        let parcelable = self.config.parcelable_records();
+        let java_interop = self.config.java_interop();
+        let errors_as_values = self.config.errors_as_values();
         vec![
             Box::new(object::KotlinObjectRuntime::new(ci)) as Box<dyn CodeDeclaration>,
             Box::new(callback_interface::KotlinCallbackInterfaceRuntime::new(ci))
                 as Box<dyn CodeDeclaration>,
+            Box::new(function::KotlinAsyncRuntime::new(
+                ci,
+                self.config.cancellable_coroutines(),
+            )) as Box<dyn CodeDeclaration>,
+            Box::new(exports::KotlinExports::new(ci)) as Box<dyn CodeDeclaration>,
+            Box::new(external::KotlinExternalTypes::new(ci)) as Box<dyn CodeDeclaration>,
         ]
         .into_iter()
+        .chain(ci.iter_enum_definitions().into_iter().map(|inner| {
+            Box::new(enum_::KotlinEnum::new(inner, ci, parcelable)) as Box<dyn CodeDeclaration>
+        }))
         .chain(
-            ci.iter_enum_definitions().into_iter().map(|inner| {
-                Box::new(enum_::KotlinEnum::new(inner, ci)) as Box<dyn CodeDeclaration>
-            }),
+            ci.iter_namespace_function_definitions()
+                .into_iter()
+                .map(|inner| {
+                    Box::new(function::KotlinFunction::new(
+                        inner,
+                        ci,
+                        java_interop,
+                        errors_as_values,
+                    )) as Box<dyn CodeDeclaration>
+                }),
+        )
+        .chain(
+            ci.iter_top_level_module_definitions()
+                .into_iter()
+                .map(|inner| {
+                    Box::new(function::KotlinModule::new(
+                        inner,
+                        ci,
+                        java_interop,
+                        errors_as_values,
+                    )) as Box<dyn CodeDeclaration>
+                }),
         )
-        .chain(ci.iter_function_definitions().into_iter().map(|inner| {
-            Box::new(function::KotlinFunction::new(inner, ci)) as Box<dyn CodeDeclaration>
-        }))
         .chain(ci.iter_object_definitions().into_iter().map(|inner| {
-            Box::new(object::KotlinObject::new(inner, ci)) as Box<dyn CodeDeclaration>
+            if inner.is_opaque_token() {
+                return Box::new(object::KotlinOpaqueToken::new(inner, ci))
+                    as Box<dyn CodeDeclaration>;
+            }
+            let extension_import = inner.foreign_extension().and_then(|ext| {
+                self.config
+                    .foreign_extension_package(ext)
+                    .map(|package| format!("{}.{}", package, ext))
+            });
+            Box::new(object::KotlinObject::new(
+                inner,
+                ci,
+                extension_import,
+                java_interop,
+                errors_as_values,
+            )) as Box<dyn CodeDeclaration>
         }))
+        .chain(
+            ci.iter_object_definitions()
+                .into_iter()
+                .filter(|inner| {
+                    self.config.observable_models() && inner.observable_model().is_some()
+                })
+                .map(|inner| {
+                    Box::new(object::KotlinObservableModel::new(inner, ci))
+                        as Box<dyn CodeDeclaration>
+                }),
+        )
+        .chain(
+            ci.iter_object_definitions()
+                .into_iter()
+                .filter(|inner| self.config.event_flows() && inner.events_type().is_some())
+                .map(|inner| {
+                    Box::new(object::KotlinEventFlow::new(inner, ci)) as Box<dyn CodeDeclaration>
+                }),
+        )
         .chain(ci.iter_record_definitions().into_iter().map(|inner| {
-            Box::new(record::KotlinRecord::new(inner, ci)) as Box<dyn CodeDeclaration>
+            Box::new(record::KotlinRecord::new(
+                inner,
+                ci,
+                inline_records,
+                parcelable,
+                java_interop,
+            )) as Box<dyn CodeDeclaration>
         }))
         .chain(
             ci.iter_error_definitions().into_iter().map(|inner| {
@@ -148,8 +360,8 @@ impl<'a> KotlinWrapper<'a> {
 
     pub fn imports(&self) -> Vec<String> {
         let oracle = &self.oracle;
-        let mut imports: Vec<String> = self
-            .members()
+        // Collecting into a `BTreeSet` removes duplicates and keeps the imports in a stable order.
+        self.members()
             .into_iter()
             .filter_map(|member| member.imports(oracle))
             .flatten()
@@ -160,12 +372,9 @@ impl<'a> KotlinWrapper<'a> {
                     .filter_map(|type_| oracle.find(&type_).imports(oracle))
                     .flatten(),
             )
-            .collect::<HashSet<String>>()
+            .collect::<BTreeSet<String>>()
             .into_iter()
-            .collect();
-
-        imports.sort();
-        imports
+            .collect()
     }
 }
 
@@ -174,6 +383,17 @@ pub struct KotlinCodeOracle;
 
 impl KotlinCodeOracle {
     fn create_code_type(&self, type_: TypeIdentifier) -> Box<dyn CodeType> {
+        match custom_types::find_custom_type(&type_) {
+            Some(config) => Box::new(custom::CustomCodeType::new(
+                config,
+                self.create_builtin_code_type(type_.clone()),
+                FFIType::from(&type_),
+            )),
+            None => self.create_builtin_code_type(type_),
+        }
+    }
+
+    fn create_builtin_code_type(&self, type_: TypeIdentifier) -> Box<dyn CodeType> {
         // I really want access to the ComponentInterface here so I can look up the interface::{Enum, Record, Error, Object, etc}
         // However, there's some violence and gore I need to do to (temporarily) make the oracle usable from filters.
 
@@ -192,6 +412,9 @@ impl KotlinCodeOracle {
             Type::Boolean => Box::new(primitives::BooleanCodeType),
             Type::String => Box::new(primitives::StringCodeType),
 
+            Type::BigInt => Box::new(miscellany::BigIntCodeType),
+            Type::Bytes => Box::new(miscellany::BytesCodeType),
+            Type::Char => Box::new(miscellany::CharCodeType),
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
 
@@ -213,12 +436,23 @@ impl KotlinCodeOracle {
                 let inner = *inner.to_owned();
                 Box::new(compounds::SequenceCodeType::new(inner, outer))
             }
-            Type::Map(ref inner) => {
+            Type::Map(ref key, ref value) => {
+                let outer = type_.clone();
+                let key = *key.to_owned();
+                let value = *value.to_owned();
+                Box::new(compounds::MapCodeType::new(key, value, outer))
+            }
+            Type::OrderedMap(ref inner) => {
                 let outer = type_.clone();
                 let inner = *inner.to_owned();
-                Box::new(compounds::MapCodeType::new(inner, outer))
+                Box::new(compounds::OrderedMapCodeType::new(inner, outer))
+            }
+            Type::External { name, crate_name } => {
+                Box::new(external::ExternalCodeType::new(name, crate_name))
+            }
+            Type::ExternalInterface { name, crate_name } => {
+                Box::new(external::ExternalInterfaceCodeType::new(name, crate_name))
             }
-            Type::External { .. } => panic!("no support for external types yet"),
             Type::Wrapped { name, prim } => Box::new(wrapped::WrappedCodeType::new(
                 name,
                 self.create_code_type(prim.as_ref().clone()),
@@ -234,39 +468,41 @@ impl CodeOracle for KotlinCodeOracle {
 
     /// Get the idiomatic Kotlin rendering of a class name (for enums, records, errors, etc).
     fn class_name(&self, nm: &dyn fmt::Display) -> String {
-        nm.to_string().to_camel_case()
+        renames::rename_or(RenameKind::Class, nm, |nm| nm.to_camel_case())
     }
 
     /// Get the idiomatic Kotlin rendering of a function name.
     fn fn_name(&self, nm: &dyn fmt::Display) -> String {
-        nm.to_string().to_mixed_case()
+        renames::rename_or(RenameKind::Function, nm, |nm| nm.to_mixed_case())
     }
 
     /// Get the idiomatic Kotlin rendering of a variable name.
     fn var_name(&self, nm: &dyn fmt::Display) -> String {
-        nm.to_string().to_mixed_case()
+        renames::rename_or(RenameKind::Variable, nm, |nm| nm.to_mixed_case())
     }
 
     /// Get the idiomatic Kotlin rendering of an individual enum variant.
     fn enum_variant_name(&self, nm: &dyn fmt::Display) -> String {
-        nm.to_string().to_shouty_snake_case()
+        renames::rename_or(RenameKind::EnumVariant, nm, |nm| nm.to_shouty_snake_case())
     }
 
     /// Get the idiomatic Kotlin rendering of an exception name
     ///
     /// This replaces "Error" at the end of the name with "Exception".  Rust code typically uses
     /// "Error" for any type of error but in the Java world, "Error" means a non-recoverable error
-    /// and is distinguished from an "Exception".
+    /// and is distinguished from an "Exception". A legacy name configured in `renames.toml`
+    /// is used as-is.
     fn error_name(&self, nm: &dyn fmt::Display) -> String {
-        let name = nm.to_string();
-        match name.strip_suffix("Error") {
-            None => name,
-            Some(stripped) => {
-                let mut kt_exc_name = stripped.to_owned();
-                kt_exc_name.push_str("Exception");
-                kt_exc_name
+        renames::rename_or(RenameKind::Class, nm, |name| {
+            match name.strip_suffix("Error") {
+                None => name,
+                Some(stripped) => {
+                    let mut kt_exc_name = stripped.to_owned();
+                    kt_exc_name.push_str("Exception");
+                    kt_exc_name
+                }
             }
-        }
+        })
     }
 
     fn ffi_type_label(&self, ffi_type: &FFIType) -> String {
@@ -284,6 +520,8 @@ impl CodeOracle for KotlinCodeOracle {
             FFIType::RustBuffer => "RustBuffer.ByValue".to_string(),
             FFIType::ForeignBytes => "ForeignBytes.ByValue".to_string(),
             FFIType::ForeignCallback => "ForeignCallback".to_string(),
+            FFIType::AsyncCompletion => "UniFFIAsyncCompletion".to_string(),
+            FFIType::ForeignMethodCallback(name) => name.clone(),
         }
     }
 }
@@ -373,4 +611,10 @@ pub mod filters {
     pub fn exception_name(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
         Ok(oracle().error_name(nm))
     }
+
+    /// Whether any of the arguments have default values, and so need `@JvmOverloads` for
+    /// Java to be able to leave them out.
+    pub fn has_default_values(args: &[&Argument]) -> Result<bool, askama::Error> {
+        Ok(args.iter().any(|arg| arg.default_value().is_some()))
+    }
 }
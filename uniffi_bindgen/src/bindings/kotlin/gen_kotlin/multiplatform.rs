@@ -0,0 +1,350 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Kotlin Multiplatform bindings
+//!
+//! With `flavor = "multiplatform"` in `[bindings.kotlin]`, the Kotlin backend generates bindings
+//! for a Kotlin Multiplatform module rather than for the JVM. They're in three files, arranged like
+//! the module's `src` directory:
+//!
+//!  * `commonMain/kotlin/{package}/{namespace}.kt` holds the records, enums and errors, and `expect`
+//!    declarations of the functions and objects, for the code shared between platforms;
+//!  * `nativeMain/kotlin/{package}/{namespace}.native.kt` holds their `actual` implementations for
+//!    Kotlin/Native, which call the Rust library directly, without JNA;
+//!  * `nativeInterop/cinterop/{namespace}.def` declares the library's C functions, for the cinterop
+//!    tool to generate the Kotlin declarations that the implementations call, in the
+//!    `{package}.cinterop` package.
+//!
+//! The flavor supports a part of the component interface for now: functions, objects, records,
+//! flat enums and flat errors, made of numbers, booleans, strings, optionals, sequences and maps.
+//! Generating it for a component that uses anything else fails, listing what isn't supported.
+
+use anyhow::Result;
+use askama::Template;
+use serde::{Deserialize, Serialize};
+
+use super::Config;
+use crate::interface::*;
+use crate::lockfile::type_decl;
+
+/// The platforms that the Kotlin bindings are generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KotlinFlavor {
+    /// The JVM and Android, calling the Rust library through JNA.
+    Jvm,
+    /// A Kotlin Multiplatform module, calling the Rust library through cinterop on Kotlin/Native.
+    Multiplatform,
+}
+
+impl Default for KotlinFlavor {
+    fn default() -> Self {
+        KotlinFlavor::Jvm
+    }
+}
+
+/// What the multiplatform flavor can generate.
+pub fn capabilities() -> Capabilities {
+    Capabilities::all().without(&[
+        Feature::NestedNamespaces,
+        Feature::Batchable,
+        Feature::Async,
+        Feature::AlsoAsync,
+        Feature::Borrowed,
+        Feature::NoCopy,
+        Feature::Clone,
+        Feature::Closeable,
+        Feature::WeakRefs,
+        Feature::ReleaseCallbacks,
+        Feature::OpaqueTokens,
+        Feature::ForeignExtensions,
+        Feature::Observable,
+        Feature::Events,
+        Feature::Services,
+        Feature::Streams,
+        Feature::EnumsWithData,
+        Feature::ErrorsWithData,
+        Feature::SourceChains,
+        Feature::CallbackInterfaces,
+        Feature::ExternalTypes,
+        Feature::CustomTypes,
+        Feature::OrderedMaps,
+        Feature::BigInt,
+        Feature::Bytes,
+        Feature::Char,
+        Feature::Time,
+    ])
+}
+
+// The things in the component that the multiplatform flavor doesn't support yet, to report them
+// all at once.
+pub fn unsupported_features(config: &Config, ci: &ComponentInterface) -> Vec<String> {
+    let mut problems = ci.unsupported_features(&capabilities());
+    for name in config.custom_types().keys() {
+        problems.push(format!("custom type {}, in `custom_types`", name));
+    }
+    problems
+}
+
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "multiplatform/common.kt")]
+pub struct KotlinMultiplatformCommon<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> KotlinMultiplatformCommon<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+
+    /// The version of `uniffi-bindgen` that's generating the bindings.
+    pub fn bindgen_version(&self) -> &'static str {
+        crate::BINDGEN_VERSION
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "multiplatform/native.kt")]
+pub struct KotlinMultiplatformNative<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> KotlinMultiplatformNative<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+
+    /// The version of `uniffi-bindgen` that's generating the bindings.
+    pub fn bindgen_version(&self) -> &'static str {
+        crate::BINDGEN_VERSION
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "multiplatform/cinterop.def")]
+pub struct KotlinMultiplatformCinterop<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> KotlinMultiplatformCinterop<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+}
+
+/// The generated files of the multiplatform bindings, as source code.
+#[derive(Debug)]
+pub struct MultiplatformBindings {
+    /// The shared declarations, for `commonMain`.
+    pub common: String,
+    /// The Kotlin/Native implementations, for `nativeMain`.
+    pub native: String,
+    /// The cinterop definition of the Rust library's C functions.
+    pub cinterop: String,
+}
+
+pub mod filters {
+    use super::*;
+    use std::fmt;
+
+    pub use super::super::filters::{
+        class_name, enum_variant, exception_name, fn_name, render_literal, type_name, var_name,
+    };
+
+    /// The C type of a low-level `FFIType`, for declaring the FFI functions to cinterop.
+    pub fn ffi_type_c(type_: &FFIType) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            FFIType::Int8 => "int8_t".to_string(),
+            FFIType::UInt8 => "uint8_t".to_string(),
+            FFIType::Int16 => "int16_t".to_string(),
+            FFIType::UInt16 => "uint16_t".to_string(),
+            FFIType::Int32 => "int32_t".to_string(),
+            FFIType::UInt32 => "uint32_t".to_string(),
+            FFIType::Int64 => "int64_t".to_string(),
+            FFIType::UInt64 => "uint64_t".to_string(),
+            FFIType::Float32 => "float".to_string(),
+            FFIType::Float64 => "double".to_string(),
+            FFIType::RustArcPtr => "void *".to_string(),
+            FFIType::RustBuffer => "RustBuffer".to_string(),
+            FFIType::ForeignBytes => "ForeignBytes".to_string(),
+            FFIType::ForeignCallback | FFIType::ForeignMethodCallback(_) => {
+                unimplemented!("Callback interfaces are not implemented")
+            }
+            FFIType::AsyncCompletion => unimplemented!("[Async] functions are not implemented"),
+        })
+    }
+
+    /// A field of a record, as a member of the Kotlin value `owner`.
+    pub fn member_kt(nm: &dyn fmt::Display, owner: &str) -> Result<String, askama::Error> {
+        Ok(format!("{}.{}", owner, var_name(nm)?))
+    }
+
+    // Numbers are passed to cinterop as the Kotlin type of the same size, so they don't need
+    // lifting or lowering.
+    fn is_number(type_: &Type) -> bool {
+        matches!(
+            type_,
+            Type::UInt8
+                | Type::Int8
+                | Type::UInt16
+                | Type::Int16
+                | Type::UInt32
+                | Type::Int32
+                | Type::UInt64
+                | Type::Int64
+                | Type::Float32
+                | Type::Float64
+        )
+    }
+
+    /// The suffix of the names of the helper functions that convert values of the type.
+    pub fn helper_name_kt(type_: &Type) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Type::Int8 => "Int8".into(),
+            Type::UInt8 => "UInt8".into(),
+            Type::Int16 => "Int16".into(),
+            Type::UInt16 => "UInt16".into(),
+            Type::Int32 => "Int32".into(),
+            Type::UInt32 => "UInt32".into(),
+            Type::Int64 => "Int64".into(),
+            Type::UInt64 => "UInt64".into(),
+            Type::Float32 => "Float32".into(),
+            Type::Float64 => "Float64".into(),
+            Type::Boolean => "Bool".into(),
+            Type::String => "String".into(),
+            Type::Object(name) | Type::Record(name) | Type::Enum(name) | Type::Error(name) => {
+                format!("Type{}", class_name(name)?)
+            }
+            Type::Optional(t) => format!("Optional{}", helper_name_kt(t)?),
+            Type::Sequence(t) => format!("Sequence{}", helper_name_kt(t)?),
+            Type::Map(k, v) => format!("Map{}_{}", helper_name_kt(k)?, helper_name_kt(v)?),
+            _ => panic!(
+                "No support for {} in Kotlin Multiplatform, yet",
+                type_decl(type_)
+            ),
+        })
+    }
+
+    pub fn lower_kt(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+        Ok(if is_number(type_) {
+            nm.to_string()
+        } else {
+            format!("uniffiLower{}({})", helper_name_kt(type_)?, nm)
+        })
+    }
+
+    pub fn lift_kt(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+        Ok(if is_number(type_) {
+            nm.to_string()
+        } else {
+            format!("uniffiLift{}({})", helper_name_kt(type_)?, nm)
+        })
+    }
+
+    pub fn read_kt(type_: &Type) -> Result<String, askama::Error> {
+        Ok(format!("uniffiRead{}(reader)", helper_name_kt(type_)?))
+    }
+
+    pub fn write_kt(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+        Ok(format!(
+            "uniffiWrite{}(writer, {})",
+            helper_name_kt(type_)?,
+            nm
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::super::generate_multiplatform_bindings;
+    use super::*;
+
+    #[test]
+    fn test_multiplatform_bindings() {
+        const UDL: &str = r#"
+            namespace todolist {
+                [Throws=TodoError]
+                TodoEntry get_first(sequence<TodoEntry> entries);
+                record<DOMString, u32>? counts(boolean all = false);
+            };
+            dictionary TodoEntry {
+                string text;
+                TodoList? list;
+            };
+            enum Shape { "Circle", "Square" };
+            [Error]
+            enum TodoError { "EmptyList" };
+            interface TodoList {
+                constructor();
+                [Name=with_entries]
+                constructor(sequence<TodoEntry> entries);
+                void add_entry(TodoEntry entry);
+                string? last_text();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let bindings = generate_multiplatform_bindings(&Config::from(&ci), &ci).unwrap();
+
+        let common = &bindings.common;
+        assert!(common.contains("package uniffi.todolist"));
+        assert!(!common.contains("com.sun.jna"));
+        assert!(common.contains("@Throws(TodoException::class)"));
+        assert!(common.contains("expect fun getFirst(entries: List<TodoEntry>): TodoEntry"));
+        assert!(common.contains("expect fun counts(all: Boolean = false): Map<String, UInt>?"));
+        assert!(common.contains("data class TodoEntry("));
+        assert!(common.contains("enum class Shape {"));
+        assert!(common.contains("class EmptyList(message: String) : TodoException(message)"));
+        assert!(common.contains("expect class TodoList {"));
+        assert!(common.contains("    constructor()\n"));
+        assert!(common.contains("        fun withEntries(entries: List<TodoEntry>): TodoList\n"));
+
+        let native = &bindings.native;
+        assert!(native.contains("import uniffi.todolist.cinterop.*"));
+        assert!(!native.contains("com.sun.jna"));
+        assert!(native.contains("actual fun counts(all: Boolean): Map<String, UInt>? {"));
+        assert!(native.contains("uniffiLowerBool(all)"));
+        assert!(native.contains("actual class TodoList internal constructor("));
+        assert!(native.contains("actual fun lastText(): String? {"));
+        assert!(native
+            .contains("internal fun uniffiReadTypeTodoEntry(reader: UniffiReader): TodoEntry {"));
+        assert!(native.contains("::uniffiLiftErrorTodoException"));
+
+        let cinterop = &bindings.cinterop;
+        assert!(cinterop.contains("package = uniffi.todolist.cinterop"));
+        assert!(cinterop.contains("linkerOpts = -luniffi_todolist"));
+        let func = ci.get_function_definition("counts").unwrap();
+        assert!(cinterop.contains(&format!(
+            "RustBuffer {}(int8_t all, RustCallStatus *out_status);",
+            func.ffi_func().name()
+        )));
+    }
+
+    #[test]
+    fn test_multiplatform_unsupported_features() {
+        const UDL: &str = r#"
+            namespace shapes {
+                void draw(Drawing drawing, timestamp when);
+            };
+            [Enum]
+            interface Drawing {
+                Line(f64 length);
+            };
+            callback interface Painter {
+                void paint(u32 color);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let err = generate_multiplatform_bindings(&Config::from(&ci), &ci).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The Kotlin Multiplatform flavor doesn't support:
+  enum `Drawing`, which has associated data
+  callback interface `Painter`
+  timestamp"
+        );
+    }
+}
@@ -66,15 +66,36 @@ impl CodeType for ObjectCodeType {
 #[template(syntax = "kt", escape = "none", path = "ObjectTemplate.kt")]
 pub struct KotlinObject {
     inner: Object,
+    // The fully-qualified name of the `[ForeignExtension]` interface, if it must be imported.
+    extension_import: Option<String>,
+    java_interop: bool,
+    errors_as_values: bool,
 }
 
 impl KotlinObject {
-    pub fn new(inner: Object, _ci: &ComponentInterface) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: Object,
+        _ci: &ComponentInterface,
+        extension_import: Option<String>,
+        java_interop: bool,
+        errors_as_values: bool,
+    ) -> Self {
+        Self {
+            inner,
+            extension_import,
+            java_interop,
+            errors_as_values,
+        }
     }
     pub fn inner(&self) -> &Object {
         &self.inner
     }
+    pub fn java_interop(&self) -> bool {
+        self.java_interop
+    }
+    pub fn errors_as_values(&self) -> bool {
+        self.errors_as_values
+    }
 }
 
 impl CodeDeclaration for KotlinObject {
@@ -90,21 +111,153 @@ impl CodeDeclaration for KotlinObject {
             ]
             .into_iter()
             .map(|s| s.into())
+            .chain(self.extension_import.clone())
             .collect(),
         )
     }
 }
 
+// With `observable_models` on, each `[Observable]` object also gets a class holding its latest
+// snapshot in a `StateFlow`, which it keeps up to date as an observer of the object.
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "ObservableModelTemplate.kt")]
+pub struct KotlinObservableModel {
+    inner: Object,
+}
+
+impl KotlinObservableModel {
+    pub fn new(inner: Object, _ci: &ComponentInterface) -> Self {
+        Self { inner }
+    }
+    pub fn inner(&self) -> &Object {
+        &self.inner
+    }
+    pub fn model(&self) -> String {
+        self.inner.observable_model().unwrap()
+    }
+    pub fn snapshot(&self) -> &str {
+        self.inner.observable_snapshot().unwrap()
+    }
+    pub fn observer(&self) -> String {
+        self.inner.observer_interface().unwrap()
+    }
+}
+
+impl CodeDeclaration for KotlinObservableModel {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        Some(
+            vec![
+                "java.util.concurrent.atomic.AtomicBoolean",
+                "kotlinx.coroutines.flow.MutableStateFlow",
+                "kotlinx.coroutines.flow.StateFlow",
+                "kotlinx.coroutines.flow.asStateFlow",
+            ]
+            .into_iter()
+            .map(|s| s.into())
+            .collect(),
+        )
+    }
+}
+
+// With `event_flows` on, each object with `[Events]` also gets an extension property that
+// collects its events into a `Flow`, as a listener of the object.
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "EventFlowTemplate.kt")]
+pub struct KotlinEventFlow {
+    inner: Object,
+}
+
+impl KotlinEventFlow {
+    pub fn new(inner: Object, _ci: &ComponentInterface) -> Self {
+        Self { inner }
+    }
+    pub fn inner(&self) -> &Object {
+        &self.inner
+    }
+    pub fn event(&self) -> &str {
+        self.inner.events_type().unwrap()
+    }
+    pub fn listener(&self) -> String {
+        self.inner.listener_interface().unwrap()
+    }
+}
+
+impl CodeDeclaration for KotlinEventFlow {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        Some(
+            vec![
+                "kotlinx.coroutines.channels.Channel",
+                "kotlinx.coroutines.channels.awaitClose",
+                "kotlinx.coroutines.flow.Flow",
+                "kotlinx.coroutines.flow.buffer",
+                "kotlinx.coroutines.flow.callbackFlow",
+            ]
+            .into_iter()
+            .map(|s| s.into())
+            .collect(),
+        )
+    }
+}
+
+// An `[OpaqueToken]` has no constructors or methods, so it only needs a small handle class.
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "OpaqueTokenTemplate.kt")]
+pub struct KotlinOpaqueToken {
+    inner: Object,
+}
+
+impl KotlinOpaqueToken {
+    pub fn new(inner: Object, _ci: &ComponentInterface) -> Self {
+        Self { inner }
+    }
+    pub fn inner(&self) -> &Object {
+        &self.inner
+    }
+}
+
+impl CodeDeclaration for KotlinOpaqueToken {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        Some(vec![
+            "java.util.concurrent.atomic.AtomicLong".into(),
+            "java.util.concurrent.atomic.AtomicBoolean".into(),
+        ])
+    }
+}
+
 #[derive(Template)]
 #[template(syntax = "kt", escape = "none", path = "ObjectRuntime.kt")]
 pub struct KotlinObjectRuntime {
     is_needed: bool,
+    // Whether any object has `[AlsoAsync]` methods, whose `suspend` variants need a thread pool.
+    has_also_async: bool,
+    // Whether any object has `[NoCopy]` methods, which return `OwnedBytes`.
+    has_no_copy: bool,
 }
 
 impl KotlinObjectRuntime {
     pub fn new(ci: &ComponentInterface) -> Self {
         Self {
             is_needed: !ci.iter_object_definitions().is_empty(),
+            has_also_async: ci
+                .iter_object_definitions()
+                .iter()
+                .any(|obj| obj.methods().iter().any(|meth| meth.is_also_async())),
+            has_no_copy: ci
+                .iter_object_definitions()
+                .iter()
+                .any(|obj| obj.methods().iter().any(|meth| meth.is_no_copy())),
         }
     }
 }
@@ -117,4 +270,238 @@ impl CodeDeclaration for KotlinObjectRuntime {
             None
         }
     }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        if self.has_also_async {
+            Some(vec![
+                "java.util.concurrent.Executors".into(),
+                "kotlin.coroutines.suspendCoroutine".into(),
+            ])
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::super::generate_bindings;
+    use super::super::Config;
+    use crate::interface::ComponentInterface;
+
+    #[test]
+    fn test_async_functions_and_methods_suspend() {
+        const UDL: &str = r#"
+            namespace test {
+                [Async, Throws=FetchError]
+                string fetch(string url);
+            };
+            [Error]
+            enum FetchError { "Offline" };
+            interface Client {
+                constructor();
+                [Async]
+                void flush();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let bindings = generate_bindings(&Config::from(&ci), &ci).unwrap();
+        assert!(bindings.contains("suspend fun fetch(url: String): String ="));
+        assert!(bindings.contains("override suspend fun flush() ="));
+        assert!(bindings.contains("uniffiAsyncCall(FetchException,"));
+        assert!(bindings.contains("import kotlin.coroutines.suspendCoroutine"));
+        let init = ci.ffi_async_init().unwrap();
+        assert!(bindings.contains(&format!(
+            "_UniFFILib.INSTANCE.{}(completion, status)",
+            init.name()
+        )));
+        assert!(!bindings.contains("invokeOnCancellation"));
+
+        let config = Config {
+            cancellable_coroutines: Some(true),
+            ..Config::from(&ci)
+        };
+        let bindings = generate_bindings(&config, &ci).unwrap();
+        assert!(bindings.contains("import kotlinx.coroutines.suspendCancellableCoroutine"));
+        assert!(!bindings.contains("import kotlin.coroutines.suspendCoroutine"));
+        let cancel = ci.ffi_async_cancel().unwrap();
+        assert!(bindings.contains(&format!(
+            "_UniFFILib.INSTANCE.{}(handle, status)",
+            cancel.name()
+        )));
+
+        let ci = ComponentInterface::from_webidl("namespace test { void ping(); };").unwrap();
+        let bindings = generate_bindings(&Config::from(&ci), &ci).unwrap();
+        assert!(!bindings.contains("UniFFIAsyncCalls"));
+    }
+
+    #[test]
+    fn test_observable_models() {
+        const UDL: &str = r#"
+            namespace test {};
+            dictionary ProfileState {
+                string name;
+            };
+            [Observable=ProfileState]
+            interface Profile {
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let bindings = generate_bindings(&Config::from(&ci), &ci).unwrap();
+        assert!(!bindings.contains("class ProfileModel("));
+        assert!(!bindings.contains("kotlinx.coroutines"));
+
+        let config = Config {
+            observable_models: Some(true),
+            ..Config::from(&ci)
+        };
+        let bindings = generate_bindings(&config, &ci).unwrap();
+        assert!(bindings.contains("import kotlinx.coroutines.flow.StateFlow"));
+        assert!(bindings.contains("class ProfileModel(\n    val source: Profile,\n    initial: ProfileState\n) : AutoCloseable {"));
+        assert!(
+            bindings.contains("val state: StateFlow<ProfileState> = mutableState.asStateFlow()")
+        );
+        assert!(bindings
+            .contains("private val observerId = source.addObserver(object : ProfileObserver {"));
+        assert!(bindings.contains("source.removeObserver(observerId)"));
+
+        let ci = ComponentInterface::from_webidl(&format!("{} interface ProfileModel {{}};", UDL))
+            .unwrap();
+        let err = generate_bindings(&config, &ci).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Observable] interface \"Profile\" gets a \"ProfileModel\" class with `observable_models`, which conflicts with a type of the same name"
+        );
+    }
+
+    #[test]
+    fn test_streams_are_sequences() {
+        const UDL: &str = r#"
+            namespace test {};
+            [Stream]
+            typedef sequence<string> Lines;
+            interface Document {
+                constructor();
+                Lines lines();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let bindings = generate_bindings(&Config::from(&ci), &ci).unwrap();
+        assert!(bindings.contains(
+            "class Lines(\n    pointer: Pointer\n) : FFIObject(pointer), LinesInterface, Sequence<String> {"
+        ));
+        assert!(bindings.contains("override fun iterator(): Iterator<String> = iterator {"));
+        assert!(bindings.contains("val chunk = nextChunk(64u)"));
+        assert!(!bindings.contains("Sequence<Lines>"));
+    }
+
+    #[test]
+    fn test_event_flows() {
+        const UDL: &str = r#"
+            namespace test {};
+            dictionary Tick {
+                u64 at;
+            };
+            [Events=Tick]
+            interface Clock {
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let bindings = generate_bindings(&Config::from(&ci), &ci).unwrap();
+        assert!(bindings.contains("fun addListener(listener: ClockListener): ULong"));
+        assert!(!bindings.contains("val Clock.events"));
+        assert!(!bindings.contains("kotlinx.coroutines"));
+
+        let config = Config {
+            event_flows: Some(true),
+            ..Config::from(&ci)
+        };
+        let bindings = generate_bindings(&config, &ci).unwrap();
+        assert!(bindings.contains("import kotlinx.coroutines.flow.callbackFlow"));
+        assert!(bindings.contains("val Clock.events: Flow<Tick>"));
+        assert!(bindings.contains("val listenerId = addListener(object : ClockListener {"));
+        assert!(bindings.contains("awaitClose { removeListener(listenerId) }"));
+    }
+
+    #[test]
+    fn test_errors_as_values() {
+        const UDL: &str = r#"
+            namespace test {
+                [Throws=FetchError]
+                string fetch(string url);
+            };
+            [Error]
+            enum FetchError { "Offline" };
+            interface Client {
+                constructor();
+                [Throws=FetchError]
+                void flush();
+                u32 count();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let bindings = generate_bindings(&Config::from(&ci), &ci).unwrap();
+        assert!(bindings.contains("@Throws(FetchException::class)"));
+        assert!(bindings.contains("fun fetch(url: String): String {"));
+        assert!(bindings.contains("override fun flush() ="));
+
+        let config = Config {
+            errors_as_values: Some(true),
+            ..Config::from(&ci)
+        };
+        let bindings = generate_bindings(&config, &ci).unwrap();
+        assert!(!bindings.contains("@Throws(FetchException::class)"));
+        assert!(bindings.contains("fun fetch(url: String): Result<String> {"));
+        assert!(bindings.contains("return uniffiResultOf(FetchException::class) {"));
+        assert!(bindings.contains("fun flush(): Result<Unit>\n"));
+        assert!(bindings.contains("override fun flush(): Result<Unit> ="));
+        // Methods that don't throw return what they did before.
+        assert!(bindings.contains("override fun count(): UInt ="));
+    }
+
+    #[test]
+    fn test_external_types() {
+        const UDL: &str = r#"
+            namespace test {
+                DemoSession resume_session(DemoDict dict);
+            };
+            [External="demo-crate"]
+            typedef extern DemoDict;
+            [ExternalInterface="demo-crate"]
+            typedef extern DemoSession;
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let bindings = generate_bindings(&Config::from(&ci), &ci).unwrap();
+        assert!(bindings.contains("import uniffi.demo_crate.DemoDict\n"));
+        assert!(bindings.contains("import uniffi.demo_crate.DemoSession\n"));
+        assert!(bindings.contains("return uniffi.demo_crate.UniffiExternalTypes.readDemoDict(buf)"));
+        assert!(
+            bindings.contains("return uniffi.demo_crate.UniffiExternalTypes.liftDemoSession(ptr)")
+        );
+        assert!(bindings.contains(&format!(
+            "_UniFFILib.INSTANCE.{}(status)",
+            ci.ffi_external_checksum("demo-crate").name()
+        )));
+
+        // And the other way around: the component that defines the types lets others use them.
+        const DEMO_UDL: &str = r#"
+            namespace demo_crate {};
+            dictionary DemoDict {
+                boolean bool_val;
+                DemoToken token;
+            };
+            interface DemoSession {};
+            [Wrapped]
+            typedef string DemoToken;
+        "#;
+        let ci = ComponentInterface::from_webidl(DEMO_UDL).unwrap();
+        let bindings = generate_bindings(&Config::from(&ci), &ci).unwrap();
+        assert!(bindings.contains("object UniffiExternalTypes {"));
+        assert!(bindings.contains("fun readDemoDict(buf: ByteBuffer): DemoDict {"));
+        assert!(bindings.contains("fun writeDemoDict(value: DemoDict): ByteArray {"));
+        assert!(bindings.contains("fun liftDemoSession(ptr: Pointer): DemoSession {"));
+        assert!(bindings.contains("typealias DemoToken = String\n"));
+    }
 }
@@ -66,12 +66,26 @@ impl CodeType for RecordCodeType {
 pub struct KotlinRecord {
     inner: Record,
     contains_object_references: bool,
+    is_inline: bool,
+    is_parcelable: bool,
+    java_interop: bool,
 }
 
 impl KotlinRecord {
-    pub fn new(inner: Record, ci: &ComponentInterface) -> Self {
+    pub fn new(
+        inner: Record,
+        ci: &ComponentInterface,
+        inline_single_field_records: bool,
+        parcelable_records: bool,
+        java_interop: bool,
+    ) -> Self {
+        let contains_object_references = ci.item_contains_object_references(&inner);
         Self {
-            contains_object_references: ci.item_contains_object_references(&inner),
+            contains_object_references,
+            is_inline: inline_single_field_records && inner.fields().len() == 1,
+            // Objects can't be parcelled, so neither can records that contain them.
+            is_parcelable: parcelable_records && !contains_object_references,
+            java_interop,
             inner,
         }
     }
@@ -81,10 +95,41 @@ impl KotlinRecord {
     pub fn contains_object_references(&self) -> bool {
         self.contains_object_references
     }
+    pub fn is_inline(&self) -> bool {
+        self.is_inline
+    }
+    pub fn is_parcelable(&self) -> bool {
+        self.is_parcelable
+    }
+    // Java can only leave out the fields with defaults if there are overloads of the constructor.
+    pub fn has_jvm_overloads(&self) -> bool {
+        self.java_interop
+            && !self.is_inline
+            && self
+                .inner
+                .fields()
+                .iter()
+                .any(|field| field.default_value().is_some())
+    }
 }
 
 impl CodeDeclaration for KotlinRecord {
     fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
         Some(self.render().unwrap())
     }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        if self.is_parcelable {
+            Some(parcelable_imports())
+        } else {
+            None
+        }
+    }
+}
+
+pub(super) fn parcelable_imports() -> Vec<String> {
+    vec![
+        "android.os.Parcelable".into(),
+        "kotlinx.parcelize.Parcelize".into(),
+    ]
 }
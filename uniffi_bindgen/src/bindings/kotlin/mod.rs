@@ -4,6 +4,7 @@
 
 use anyhow::{bail, Context, Result};
 use std::{
+    collections::BTreeSet,
     env,
     ffi::OsString,
     fs::File,
@@ -13,9 +14,12 @@ use std::{
 };
 
 pub mod gen_kotlin;
-pub use gen_kotlin::{Config, KotlinWrapper};
+pub use gen_kotlin::{Config, KotlinFlavor, KotlinWrapper};
+
+use gen_kotlin::multiplatform::{self, MultiplatformBindings};
 
 use super::super::interface::ComponentInterface;
+use crate::backend::{custom_types, post_process, renames};
 
 pub fn write_bindings(
     config: &Config,
@@ -23,27 +27,82 @@ pub fn write_bindings(
     out_dir: &Path,
     try_format_code: bool,
 ) -> Result<()> {
+    if config.flavor() == KotlinFlavor::Multiplatform {
+        return write_multiplatform_bindings(config, ci, out_dir, try_format_code);
+    }
     let mut kt_file = full_bindings_path(config, out_dir)?;
     std::fs::create_dir_all(&kt_file)?;
     kt_file.push(format!("{}.kt", ci.namespace()));
     let mut f = File::create(&kt_file).context("Failed to create .kt file for bindings")?;
-    write!(f, "{}", generate_bindings(config, ci)?)?;
+    write!(
+        f,
+        "{}",
+        post_process::post_process(&kt_file, generate_bindings(config, ci)?)?
+    )?;
     if try_format_code {
-        if let Err(e) = Command::new("ktlint")
-            .arg("-F")
-            .arg(kt_file.to_str().unwrap())
-            .output()
-        {
-            println!(
-                "Warning: Unable to auto-format {} using ktlint: {:?}",
-                kt_file.file_name().unwrap().to_str().unwrap(),
-                e
-            )
+        format_code(&kt_file);
+    }
+    Ok(())
+}
+
+// Write the multiplatform bindings into `out_dir` as if it's the `src` directory of the module,
+// see [`gen_kotlin::multiplatform`].
+fn write_multiplatform_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Path,
+    try_format_code: bool,
+) -> Result<()> {
+    let bindings = generate_multiplatform_bindings(config, ci)?;
+    let package_path: PathBuf = config.package_name().split('.').collect();
+    let files = vec![
+        (
+            out_dir
+                .join("commonMain/kotlin")
+                .join(&package_path)
+                .join(format!("{}.kt", ci.namespace())),
+            bindings.common,
+        ),
+        (
+            out_dir
+                .join("nativeMain/kotlin")
+                .join(&package_path)
+                .join(format!("{}.native.kt", ci.namespace())),
+            bindings.native,
+        ),
+        (
+            out_dir
+                .join("nativeInterop/cinterop")
+                .join(format!("{}.def", ci.namespace())),
+            bindings.cinterop,
+        ),
+    ];
+    for (path, source) in files {
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        let mut f = File::create(&path)
+            .with_context(|| format!("Failed to create {} for bindings", path.display()))?;
+        write!(f, "{}", post_process::post_process(&path, source)?)?;
+        if try_format_code && path.extension().map_or(false, |ext| ext == "kt") {
+            format_code(&path);
         }
     }
     Ok(())
 }
 
+fn format_code(kt_file: &Path) {
+    if let Err(e) = Command::new("ktlint")
+        .arg("-F")
+        .arg(kt_file.to_str().unwrap())
+        .output()
+    {
+        println!(
+            "Warning: Unable to auto-format {} using ktlint: {:?}",
+            kt_file.file_name().unwrap().to_str().unwrap(),
+            e
+        )
+    }
+}
+
 fn full_bindings_path(config: &Config, out_dir: &Path) -> Result<PathBuf> {
     let package_path: PathBuf = config.package_name().split('.').collect();
     Ok(PathBuf::from(out_dir).join(package_path))
@@ -52,24 +111,83 @@ fn full_bindings_path(config: &Config, out_dir: &Path) -> Result<PathBuf> {
 // Generate kotlin bindings for the given ComponentInterface, as a string.
 pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
     use askama::Template;
-    KotlinWrapper::new(config.clone(), ci)
-        .render()
-        .map_err(|_| anyhow::anyhow!("failed to render kotlin bindings"))
+    custom_types::validate_custom_types(ci, config.custom_types(), true)?;
+    renames::validate_renames(ci, config.renames())?;
+    if config.observable_models() {
+        for obj in ci.iter_object_definitions() {
+            if let Some(model) = obj.observable_model() {
+                if ci.get_type(&model).is_some() {
+                    bail!(
+                        "[Observable] interface \"{}\" gets a \"{}\" class with `observable_models`, which conflicts with a type of the same name",
+                        obj.name(),
+                        model
+                    );
+                }
+            }
+        }
+    }
+    custom_types::with_custom_types(config.custom_types(), || {
+        renames::with_renames(config.renames(), config.naming(), || {
+            KotlinWrapper::new(config.clone(), ci).render()
+        })
+    })
+    .map_err(|_| anyhow::anyhow!("failed to render kotlin bindings"))
+}
+
+// Generate the Kotlin Multiplatform bindings for the given ComponentInterface, as strings.
+pub fn generate_multiplatform_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+) -> Result<MultiplatformBindings> {
+    use askama::Template;
+    let problems = multiplatform::unsupported_features(config, ci);
+    if !problems.is_empty() {
+        bail!(
+            "The Kotlin Multiplatform flavor doesn't support:\n  {}",
+            problems.join("\n  ")
+        );
+    }
+    renames::validate_renames(ci, config.renames())?;
+    renames::with_renames(config.renames(), config.naming(), || {
+        Ok(MultiplatformBindings {
+            common: multiplatform::KotlinMultiplatformCommon::new(config.clone(), ci).render()?,
+            native: multiplatform::KotlinMultiplatformNative::new(config.clone(), ci).render()?,
+            cinterop: multiplatform::KotlinMultiplatformCinterop::new(config.clone(), ci)
+                .render()?,
+        })
+    })
+    .map_err(|_: askama::Error| anyhow::anyhow!("failed to render Kotlin Multiplatform bindings"))
 }
 
 /// Generate kotlin bindings for the given namespace, then use the kotlin
 /// command-line tools to compile them into a .jar file.
 pub fn compile_bindings(config: &Config, ci: &ComponentInterface, out_dir: &Path) -> Result<()> {
+    if config.flavor() == KotlinFlavor::Multiplatform {
+        bail!("The Kotlin Multiplatform bindings can't be compiled by themselves, build the module that they're in with Gradle instead");
+    }
     let mut kt_file = full_bindings_path(config, out_dir)?;
     kt_file.push(format!("{}.kt", ci.namespace()));
     let mut jar_file = PathBuf::from(out_dir);
     jar_file.push(format!("{}.jar", ci.namespace()));
+    // Reflect $CLASSPATH from the environment, to help find `jna.jar`.
+    let mut classpath = env::var_os("CLASSPATH").unwrap_or_else(|| OsString::from(""));
+    // The bindings of the components that we use external types from must have been compiled
+    // first, into the jars named after their crates.
+    let external_crates = ci
+        .iter_external_types()
+        .into_iter()
+        .chain(ci.iter_external_interfaces())
+        .map(|(_, crate_name)| crate_name)
+        .collect::<BTreeSet<_>>();
+    for crate_name in external_crates {
+        classpath.push(":");
+        classpath.push(out_dir.join(format!("{}.jar", crate_name.replace('-', "_"))));
+    }
     let status = Command::new("kotlinc")
         // Our generated bindings should not produce any warnings; fail tests if they do.
         .arg("-Werror")
-        // Reflect $CLASSPATH from the environment, to help find `jna.jar`.
         .arg("-classpath")
-        .arg(env::var("CLASSPATH").unwrap_or_else(|_| "".to_string()))
+        .arg(classpath)
         .arg(&kt_file)
         .arg("-d")
         .arg(jar_file)
@@ -0,0 +1,92 @@
+// How Rust completes a call to an `[Async]` function or method: either it returned, and the
+// buffer holds what it returned, or it failed with the error that it declares, or it panicked,
+// and the buffer holds the message, or it was cancelled before it finished.
+internal const val ASYNC_CALL_DONE: Byte = 0
+internal const val ASYNC_CALL_ERROR: Byte = 1
+internal const val ASYNC_CALL_PANIC: Byte = 2
+internal const val ASYNC_CALL_CANCELLED: Byte = 3
+
+interface UniFFIAsyncCompletion : com.sun.jna.Callback {
+    public fun invoke(handle: Long, code: Byte, result: RustBuffer.ByValue)
+}
+
+// The calls to `[Async]` functions and methods that Rust hasn't completed yet, by the handle that
+// each one was made with. The completion is registered with Rust the first time it's used, and
+// held here so that JNA doesn't let go of it while Rust can still call it.
+internal object UniFFIAsyncCalls {
+    private val pending = ConcurrentHashMap<Long, Continuation<Pair<Byte, RustBuffer.ByValue>>>()
+    private val nextHandle = AtomicLong(1L)
+
+    private val completion = object : UniFFIAsyncCompletion {
+        override fun invoke(handle: Long, code: Byte, result: RustBuffer.ByValue) {
+            // Nothing is waiting for a call whose coroutine was cancelled.
+            pending.remove(handle)?.resume(Pair(code, result)) ?: RustBuffer.free(result)
+        }
+    }
+
+    init {
+        rustCall() { status ->
+            _UniFFILib.INSTANCE.{{ ffi_init.name() }}(completion, status)
+        }
+    }
+
+    // Called by `uniffiWarmup()`, so that the completion is registered before the first call.
+    fun warmUp() = Unit
+
+    fun register(continuation: Continuation<Pair<Byte, RustBuffer.ByValue>>): Long =
+        nextHandle.getAndIncrement().also { pending[it] = continuation }
+
+    fun forget(handle: Long) {
+        pending.remove(handle)
+    }
+}
+
+// Makes a call to an `[Async]` function or method with `start`, which passes Rust the handle
+// that it's given, and suspends until Rust has completed it. `lift` reads what the call returned,
+// and `errorHandler` the error that it declares.
+//
+// The coroutine carries on from the thread that Rust completed the call on, unless its context
+// has a dispatcher.
+{%- match ffi_cancel %}
+{%- when Some with (cancel) %} Cancelling it cancels the Rust call too.
+{%- when None %} Cancelling it doesn't stop the Rust call, and what the call returns is thrown
+// away.
+{%- endmatch %}
+internal suspend fun <T, E: Exception> uniffiAsyncCall(
+    errorHandler: CallStatusErrorHandler<E>,
+    start: (Long) -> Unit,
+    lift: (RustBuffer.ByValue) -> T
+): T {
+    {%- match ffi_cancel %}
+    {%- when Some with (cancel) %}
+    val (code, result) = suspendCancellableCoroutine<Pair<Byte, RustBuffer.ByValue>> { continuation ->
+        val handle = UniFFIAsyncCalls.register(continuation)
+        continuation.invokeOnCancellation {
+            UniFFIAsyncCalls.forget(handle)
+            rustCall() { status ->
+                _UniFFILib.INSTANCE.{{ cancel.name() }}(handle, status)
+            }
+        }
+    {%- when None %}
+    val (code, result) = suspendCoroutine<Pair<Byte, RustBuffer.ByValue>> { continuation ->
+        val handle = UniFFIAsyncCalls.register(continuation)
+    {%- endmatch %}
+        try {
+            start(handle)
+        } catch (e: Throwable) {
+            // Rust didn't start the call, so it won't complete it either.
+            UniFFIAsyncCalls.forget(handle)
+            throw e
+        }
+    }
+    when (code) {
+        ASYNC_CALL_DONE -> return lift(result)
+        ASYNC_CALL_ERROR -> throw errorHandler.lift(result)
+        ASYNC_CALL_PANIC -> throw InternalException(liftFromRustBuffer(result) { buf -> {{ "buf"|read_var(Type::String) }} })
+        ASYNC_CALL_CANCELLED -> {
+            RustBuffer.free(result)
+            throw CancellationException("The call was cancelled")
+        }
+        else -> throw InternalException("Unknown async call status: $code")
+    }
+}
@@ -0,0 +1,77 @@
+{% import "macros.kt" as kt %}
+{%- match ci.ffi_batch() %}
+{%- when Some with (ffi_batch) %}
+/**
+ * The result of a call that was queued up in a [batch], which [get] returns once the batch
+ * has run, or throws the error that the call failed with.
+ */
+class BatchResult<T> internal constructor() {
+    private var result: Result<T>? = null
+
+    internal fun complete(result: Result<T>) {
+        this.result = result
+    }
+
+    fun get(): T {
+        val result = this.result ?: throw IllegalStateException("The batch that this call was queued up in hasn't run yet")
+        return result.getOrThrow()
+    }
+}
+
+/**
+ * The calls queued up in a [batch]. It has a method for each `[Batchable]` function, which
+ * takes the function's arguments, but returns a [BatchResult] rather than calling it.
+ */
+class Batch internal constructor() {
+    internal val calls = RustBufferBuilder()
+    internal val results = mutableListOf<(ByteBuffer) -> Unit>()
+    {% for func in ci.iter_batchable_function_definitions() %}
+    fun {{ func.name()|fn_name }}({% call kt::arg_list_decl(func) %}): BatchResult<{% call kt::batch_result_type(func) %}> {
+        val result = BatchResult<{% call kt::batch_result_type(func) %}>()
+        calls.putInt({{ loop.index }})
+        {%- for arg in func.arguments() %}
+        {{ arg.name()|write_var("calls", arg) }}
+        {%- endfor %}
+        results.add { buf ->
+            when (buf.get().toInt()) {
+                {%- match func.return_type() %}
+                {%- when Some with (return_type) %}
+                0 -> result.complete(Result.success({{ "buf"|read_var(return_type) }}))
+                {%- when None %}
+                0 -> result.complete(Result.success(Unit))
+                {%- endmatch %}
+                {%- match func.throws_type() %}
+                {%- when Some with (e) %}
+                1 -> result.complete(Result.failure({{ "buf"|read_var(e) }}))
+                {%- when None %}
+                {%- endmatch %}
+                else -> throw RuntimeException("invalid batch result, something is very wrong!!")
+            }
+        }
+        return result
+    }
+    {% endfor %}
+}
+
+/**
+ * Queue up calls to `[Batchable]` functions in [block], then make all of them with a single
+ * call into the Rust library, which is much cheaper than calling each function on its own when
+ * there are a lot of small calls to make. Their results are available once this returns.
+ */
+fun batch(block: Batch.() -> Unit) {
+    val batch = Batch()
+    try {
+        batch.block()
+    } catch (e: Throwable) {
+        batch.calls.discard()
+        throw e
+    }
+    val results = rustCall() { status ->
+        _UniFFILib.INSTANCE.{{ ffi_batch.name() }}(batch.calls.finalize(), status)
+    }
+    liftFromRustBuffer(results) { buf ->
+        batch.results.forEach { it(buf) }
+    }
+}
+{%- when None %}
+{%- endmatch %}
@@ -0,0 +1,30 @@
+internal fun liftBigInt(rbuf: RustBuffer.ByValue): java.math.BigInteger {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readBigInt(buf)
+    }
+}
+
+// Big integers are passed as a 128-bit two's complement number, most significant byte first,
+// which is also how `BigInteger` reads and writes bytes.
+internal fun readBigInt(buf: ByteBuffer): java.math.BigInteger {
+    val bytes = ByteArray(16)
+    buf.get(bytes)
+    return java.math.BigInteger(bytes)
+}
+
+internal fun lowerBigInt(v: java.math.BigInteger): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeBigInt(v, buf)
+    }
+}
+
+internal fun writeBigInt(v: java.math.BigInteger, buf: RustBufferBuilder) {
+    if (v.bitLength() > 127) {
+        throw IllegalArgumentException("Invalid bigint $v, must fit in 128 bits")
+    }
+    // `toByteArray` uses as few bytes as it can, so fill the rest with the sign.
+    val bytes = v.toByteArray()
+    val fill: Byte = if (v.signum() < 0) -1 else 0
+    repeat(16 - bytes.size) { buf.putByte(fill) }
+    buf.put(bytes)
+}
@@ -0,0 +1,25 @@
+internal fun liftBytes(rbuf: RustBuffer.ByValue): ByteArray {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readBytes(buf)
+    }
+}
+
+// Bytes are passed as their length, followed by the bytes themselves, which are copied in and out
+// of the buffer all at once rather than one at a time.
+internal fun readBytes(buf: ByteBuffer): ByteArray {
+    val len = buf.getInt()
+    val byteArr = ByteArray(len)
+    buf.get(byteArr)
+    return byteArr
+}
+
+internal fun lowerBytes(v: ByteArray): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeBytes(v, buf)
+    }
+}
+
+internal fun writeBytes(v: ByteArray, buf: RustBufferBuilder) {
+    buf.putInt(v.size)
+    buf.put(v)
+}
@@ -32,6 +32,12 @@ internal class ConcurrentHandleMap<T>(
                 obj
             }
         }
+
+    fun clear() =
+        lock.withLock {
+            leftMap.clear()
+            rightMap.clear()
+        }
 }
 
 interface ForeignCallback : com.sun.jna.Callback {
@@ -42,11 +48,67 @@ interface ForeignCallback : com.sun.jna.Callback {
 // to free the callback once it's dropped by Rust.
 internal const val IDX_CALLBACK_FREE = 0
 
+// Magic number for the Rust proxy of a `[Versioned]` callback interface to ask which methods
+// the object has. It's `u32::MAX` in Rust, which is `-1` as a Kotlin `Int`.
+internal const val IDX_CALLBACK_CAPABILITIES = -1
+
+// The first byte of what a method that throws returns to Rust, which says whether the rest is
+// the value that it returned, or the error that it threw.
+internal const val CALLBACK_SUCCESS: Byte = 0
+internal const val CALLBACK_ERROR: Byte = 1
+{%- match ffi_callback_future_complete %}
+{%- when Some with (ffi_complete) %}
+
+// What the future of an `[Async]` method is completed with: either the method finished, and the
+// buffer holds what it returned, or it failed with an exception that it doesn't declare, and
+// the buffer holds its message.
+internal const val CALLBACK_FUTURE_DONE: Byte = 0
+internal const val CALLBACK_FUTURE_FAILED: Byte = 1
+
+// Starts the `suspend` implementation of an `[Async]` method, and once it has finished, completes
+// the Rust future that's waiting for it. `lower` packs what it returned, and `lowerError` the
+// error that it threw, or returns `null` if the method doesn't declare it.
+//
+// The coroutine runs on the thread that Rust called from until it first suspends.
+internal fun <R> uniffiStartAsyncCallback(
+    completionHandle: Long,
+    block: suspend () -> R,
+    lower: (R) -> RustBuffer.ByValue,
+    lowerError: (Throwable) -> RustBuffer.ByValue? = { null }
+) {
+    block.startCoroutine(Continuation(EmptyCoroutineContext) { result ->
+        val failure = result.exceptionOrNull()
+        val lowered = if (failure == null) lower(result.getOrThrow()) else lowerError(failure)
+        val (code, rbuf) = if (lowered != null) {
+            Pair(CALLBACK_FUTURE_DONE, lowered)
+        } else {
+            val message = failure.toString().toByteArray(Charsets.UTF_8)
+            val builder = RustBufferBuilder()
+            builder.putInt(message.size)
+            builder.put(message)
+            Pair(CALLBACK_FUTURE_FAILED, builder.finalize())
+        }
+        rustCall() { status ->
+            _UniFFILib.INSTANCE.{{ ffi_complete.name() }}(completionHandle, code, rbuf, status)
+        }
+    })
+}
+{%- when None %}
+{%- endmatch %}
+
+// The callback interfaces that have been registered with Rust, so that the objects it holds can
+// all be let go of when the library is shut down.
+internal val uniffiCallbackInterfaces = java.util.concurrent.CopyOnWriteArrayList<FfiConverterCallbackInterface<*>>()
+
 internal abstract class FfiConverterCallbackInterface<CallbackInterface>(
     protected val foreignCallback: ForeignCallback
 ) {
     val handleMap = ConcurrentHandleMap<CallbackInterface>()
 
+    init {
+        uniffiCallbackInterfaces.add(this)
+    }
+
     // Registers the foreign callback with the Rust side.
     // This method is generated for each callback interface.
     abstract fun register(lib: _UniFFILib)
@@ -9,10 +9,22 @@
 
 public interface {{ type_name }} {
     {% for meth in cbi.methods() -%}
-    fun {{ meth.name()|fn_name }}({% call kt::arg_list_decl(meth) %})
+    {%- if meth.is_optional() %}
+    // This method is `[Optional]`, so implementations that leave it out get a default
+    // that does nothing, or returns an empty value.
+    {%- endif %}
+    {%- if meth.is_async() %}
+    // This method is `[Async]`, so Rust gets a future for what it returns, and doesn't wait.
+    {%- endif %}
+    {% if meth.is_async() %}suspend {% endif %}fun {{ meth.name()|fn_name }}({% call kt::arg_list_decl(meth) %})
     {%- match meth.return_type() -%}
     {%- when Some with (return_type) %}: {{ return_type|type_name -}}
+    {%- match meth.default_return_value() %}
+    {%- when Some with (literal) %} = {{ literal|render_literal(return_type) }}
+    {%- when None %}
+    {%- endmatch %}
     {%- else -%}
+    {%- if meth.is_optional() %} {}{% endif %}
     {%- endmatch %}
     {% endfor %}
 }
@@ -24,6 +36,9 @@ internal class {{ foreign_callback }} : ForeignCallback {
         val cb = {{ ffi_converter }}.lift(handle) ?: throw InternalException("No callback in handlemap; this is a Uniffi bug")
         return when (method) {
             IDX_CALLBACK_FREE -> {{ ffi_converter }}.drop(handle)
+            {%- if cbi.is_versioned() %}
+            IDX_CALLBACK_CAPABILITIES -> this.invokeCapabilities()
+            {%- endif %}
             {% for meth in cbi.methods() -%}
             {% let method_name = format!("invoke_{}", meth.name())|fn_name -%}
             {{ loop.index }} -> this.{{ method_name }}(cb, args)
@@ -37,6 +52,56 @@ internal class {{ foreign_callback }} : ForeignCallback {
 
     {% for meth in cbi.methods() -%}
     {% let method_name = format!("invoke_{}", meth.name())|fn_name %}
+    {%- if meth.is_async() %}
+    // The arguments are followed by the handle of the future that Rust is waiting on, which is
+    // completed once the coroutine has finished.
+    private fun {{ method_name }}(kotlinCallbackInterface: {{ type_name }}, args: RustBuffer.ByValue): RustBuffer.ByValue =
+        try {
+            val buf = args.asByteBuffer() ?: throw InternalException("No ByteBuffer in RustBuffer; this is a Uniffi bug")
+            {%- for arg in meth.arguments() %}
+            val uniffiArg{{ loop.index }} = {{ "buf"|read_var(arg) }}
+            {%- endfor %}
+            val completionHandle = buf.getLong()
+            uniffiStartAsyncCallback(
+                completionHandle,
+                {
+                    kotlinCallbackInterface.{{ meth.name()|fn_name }}(
+                        {%- for arg in meth.arguments() %}uniffiArg{{ loop.index }}{% if !loop.last %}, {% endif %}{% endfor -%}
+                    )
+                },
+                {% match meth.return_type() %}{% when Some with (return_type) %}{ rval ->{% when None %}{ _ ->{% endmatch %}
+                    val rbuf = RustBufferBuilder()
+                    {%- if meth.throws().is_some() %}
+                    rbuf.putByte(CALLBACK_SUCCESS)
+                    {%- endif %}
+                    {%- match meth.return_type() %}
+                    {%- when Some with (return_type) %}
+                    {{ "rval"|write_var("rbuf", return_type) }}
+                    {%- when None %}
+                    {%- endmatch %}
+                    rbuf.finalize()
+                }
+                {%- match meth.throws_type() %}
+                {%- when Some with (e) %},
+                { e ->
+                    if (e is {{ e|type_name }}) {
+                        // The error that the method declares is sent back to Rust, to be returned there.
+                        val rbuf = RustBufferBuilder()
+                        rbuf.putByte(CALLBACK_ERROR)
+                        e.write(rbuf)
+                        rbuf.finalize()
+                    } else {
+                        null
+                    }
+                }
+                {%- when None %}
+                {%- endmatch %}
+            )
+            RustBuffer.ByValue()
+        } finally {
+            RustBuffer.free(args)
+        }
+    {%- else %}
     private fun {{ method_name }}(kotlinCallbackInterface: {{ type_name }}, args: RustBuffer.ByValue): RustBuffer.ByValue =
         try {
         {#- Unpacking args from the RustBuffer #}
@@ -54,6 +119,31 @@ internal class {{ foreign_callback }} : ForeignCallback {
             {% endif -%}
 
         {#- Packing up the return value into a RustBuffer #}
+                {%- match meth.throws_type() -%}
+                {%- when Some with (e) -%}
+                {#- Methods that throw say whether they returned or threw first #}
+                {%- match meth.return_type() -%}
+                {%- when Some with (return_type) -%}
+                .let { rval ->
+                    val rbuf = RustBufferBuilder()
+                    rbuf.putByte(CALLBACK_SUCCESS)
+                    {{ "rval"|write_var("rbuf", return_type) }}
+                    rbuf.finalize()
+                }
+                {%- else -%}
+                .let {
+                    val rbuf = RustBufferBuilder()
+                    rbuf.putByte(CALLBACK_SUCCESS)
+                    rbuf.finalize()
+                }
+                {% endmatch %}
+        } catch (e: {{ e|type_name }}) {
+            // The error that the method declares is sent back to Rust, to be returned there.
+            val rbuf = RustBufferBuilder()
+            rbuf.putByte(CALLBACK_ERROR)
+            e.write(rbuf)
+            rbuf.finalize()
+                {%- when None -%}
                 {%- match meth.return_type() -%}
                 {%- when Some with (return_type) -%}
                 .let { rval ->
@@ -64,15 +154,67 @@ internal class {{ foreign_callback }} : ForeignCallback {
                 {%- else -%}
                 .let { RustBuffer.ByValue() }
                 {% endmatch -%}
-                // TODO catch errors and report them back to Rust.
+                {%- endmatch %}
+                // TODO catch other errors and report them back to Rust.
                 // https://github.com/mozilla/uniffi-rs/issues/351
         } finally {
             RustBuffer.free(args)
         }
+    {%- endif %}
 
     {% endfor %}
+    {%- if cbi.is_versioned() %}
+    // Tells the Rust proxy which version of `{{ type_name }}` this is, and the names of its
+    // methods in the order of their indexes, so that Rust calls them by name.
+    private fun invokeCapabilities(): RustBuffer.ByValue {
+        val methods = listOf<String>(
+            {%- for meth in cbi.methods() %}"{{ meth.name() }}"{% if !loop.last %}, {% endif %}{% endfor -%}
+        )
+        val rbuf = RustBufferBuilder()
+        rbuf.putLong({{ cbi.version() }}UL.toLong())
+        rbuf.putInt(methods.size)
+        methods.forEach { name ->
+            {{ "name"|write_var("rbuf", TypeIdentifier::String) }}
+        }
+        return rbuf.finalize()
+    }
+    {%- endif %}
 }
 
+{%- for meth in cbi.direct_methods() %}
+{%- match cbi.ffi_direct_callback(meth) %}
+{%- when Some with (callback) %}
+
+// The function that Rust calls for `{{ meth.name()|fn_name }}`, passing the arguments as they are
+// rather than in a RustBuffer, because the callback interface is `[Direct]`.
+internal interface {{ callback.name() }} : com.sun.jna.Callback {
+    fun invoke(
+        {%- for arg in callback.arguments() %}{{ arg.name()|var_name }}: {{ arg.type_()|ffi_type_name }}{% if !loop.last %}, {% endif %}{% endfor -%}
+    ){% match callback.return_type() %}{% when Some with (return_type) %}: {{ return_type|ffi_type_name }}{% when None %}{% endmatch %}
+}
+
+internal object {{ callback.name() }}Impl : {{ callback.name() }} {
+    override fun invoke(
+        {%- for arg in callback.arguments() %}{{ arg.name()|var_name }}: {{ arg.type_()|ffi_type_name }}{% if !loop.last %}, {% endif %}{% endfor -%}
+    ){% match callback.return_type() %}{% when Some with (return_type) %}: {{ return_type|ffi_type_name }}{% when None %}{% endmatch %} {
+        val cb = {{ ffi_converter }}.lift(handle) ?: throw InternalException("No callback in handlemap; this is a Uniffi bug")
+        {% match meth.return_type() -%}
+        {%- when Some with (return_type) -%}
+        val rval = cb.{{ meth.name()|fn_name }}(
+            {%- for arg in meth.arguments() %}{{ arg.name()|var_name|lift_var(arg) }}{% if !loop.last %}, {% endif %}{% endfor -%}
+        )
+        return {{ "rval"|lower_var(return_type) }}
+        {%- when None -%}
+        cb.{{ meth.name()|fn_name }}(
+            {%- for arg in meth.arguments() %}{{ arg.name()|var_name|lift_var(arg) }}{% if !loop.last %}, {% endif %}{% endfor -%}
+        )
+        {%- endmatch %}
+    }
+}
+{%- when None %}
+{%- endmatch %}
+{%- endfor %}
+
 // The ffiConverter which transforms the Callbacks in to Handles to pass to Rust.
 internal object {{ ffi_converter }}: FfiConverterCallbackInterface<{{ type_name }}>(
     foreignCallback = {{ foreign_callback }}()
@@ -81,5 +223,15 @@ internal object {{ ffi_converter }}: FfiConverterCallbackInterface<{{ type_name
         rustCall() { status ->
             lib.{{ cbi.ffi_init_callback().name() }}(this.foreignCallback, status)
         }
+        {%- match cbi.ffi_init_direct_callbacks() %}
+        {%- when Some with (ffi_init_direct) %}
+        rustCall() { status ->
+            lib.{{ ffi_init_direct.name() }}(
+                {%- for callback in cbi.ffi_direct_callbacks() %}{{ callback.name() }}Impl, {% endfor -%}
+                status
+            )
+        }
+        {%- when None %}
+        {%- endmatch %}
     }
 }
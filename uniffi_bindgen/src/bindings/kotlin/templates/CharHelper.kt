@@ -0,0 +1,24 @@
+// Chars are `Int` code points, which are passed as their value, and must be Unicode scalar
+// values: code points that aren't surrogates.
+internal fun checkChar(v: Int): Int {
+    if (!Character.isValidCodePoint(v) || v in 0xD800..0xDFFF) {
+        throw IllegalArgumentException("Invalid char $v, must be a Unicode scalar value")
+    }
+    return v
+}
+
+internal fun liftChar(v: Int): Int {
+    return checkChar(v)
+}
+
+internal fun readChar(buf: ByteBuffer): Int {
+    return liftChar(buf.getInt())
+}
+
+internal fun lowerChar(v: Int): Int {
+    return checkChar(v)
+}
+
+internal fun writeChar(v: Int, buf: RustBufferBuilder) {
+    buf.putInt(lowerChar(v))
+}
@@ -0,0 +1,40 @@
+/** The version of `uniffi-bindgen` that generated these bindings. */
+const val UNIFFI_BINDGEN_VERSION = "{{ self.bindgen_version() }}"
+
+/**
+ * The checksum of the component interface that these bindings were generated from.
+ *
+ * The library is checked against it when it's loaded.
+ */
+const val UNIFFI_COMPONENT_CHECKSUM = "{{ "{:016x}"|format(ci.checksum()) }}"
+
+/**
+ * Metadata about the build of the Rust library that these bindings are using.
+ *
+ * [checksum] identifies the component interface that the library was built from, and
+ * [bindingsChecksum] the one that these bindings were generated from. If they differ, the
+ * bindings and the library don't match.
+ */
+data class UniffiComponentInfo(
+    val componentName: String,
+    val uniffiVersion: String,
+    val checksum: String,
+    val buildProfile: String,
+    val bindingsChecksum: String
+)
+
+/**
+ * Fetch metadata about the Rust library, for use by support tooling.
+ */
+fun uniffiComponentInfo(): UniffiComponentInfo {
+    val fields = rustCall() { status ->
+        _UniFFILib.INSTANCE.{{ ci.ffi_component_info().name() }}(status)
+    }.let { {{ "it"|lift_var(Type::String) }} }.split("\n")
+    return UniffiComponentInfo(
+        componentName = fields[0],
+        uniffiVersion = fields[1],
+        checksum = fields[2],
+        buildProfile = fields[3],
+        bindingsChecksum = UNIFFI_COMPONENT_CHECKSUM
+    )
+}
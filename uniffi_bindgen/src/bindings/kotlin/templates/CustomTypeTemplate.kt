@@ -0,0 +1,27 @@
+// Conversions for `{{ type_name }}`, which is mapped onto `{{ builtin_type_name }}` by uniffi.toml.
+
+internal fun intoCustom{{ canonical_name }}(builtinValue: {{ builtin_type_name }}): {{ type_name }} {
+    return {{ into_custom }}
+}
+
+internal fun fromCustom{{ canonical_name }}(value: {{ type_name }}): {{ builtin_type_name }} {
+    return {{ from_custom }}
+}
+
+internal fun lift{{ canonical_name }}(v: {{ ffi_type_name }}): {{ type_name }} {
+    return intoCustom{{ canonical_name }}({{ lift_builtin }})
+}
+
+internal fun read{{ canonical_name }}(buf: ByteBuffer): {{ type_name }} {
+    return intoCustom{{ canonical_name }}({{ read_builtin }})
+}
+
+internal fun lower{{ canonical_name }}(value: {{ type_name }}): {{ ffi_type_name }} {
+    val builtinValue = fromCustom{{ canonical_name }}(value)
+    return {{ lower_builtin }}
+}
+
+internal fun write{{ canonical_name }}(value: {{ type_name }}, buf: RustBufferBuilder) {
+    val builtinValue = fromCustom{{ canonical_name }}(value)
+    {{ write_builtin }}
+}
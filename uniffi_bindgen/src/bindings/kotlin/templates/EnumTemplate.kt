@@ -36,8 +36,11 @@ enum class {{ e|type_name }} {
 
 {% else %}
 
-sealed class {{ e|type_name }}{% if self.contains_object_references() %}: Disposable {% endif %} {
+sealed class {{ e|type_name }}{% if self.contains_object_references() %}: Disposable {% else if self.is_parcelable() %}: Parcelable {% endif %} {
     {% for variant in e.variants() -%}
+    {% if self.is_parcelable() -%}
+    @Parcelize
+    {% endif -%}
     {% if !variant.has_fields() -%}
     object {{ variant.name()|class_name }} : {{ e|type_name }}()
     {% else -%}
@@ -26,6 +26,33 @@ sealed class {{ e|type_name }}: Exception(){% if self.contains_object_references
 
 {%- endif %}
 
+    // Callback interfaces write the errors that they throw, to send them back to Rust.
+    internal fun write(buf: RustBufferBuilder) {
+        {%- if e.is_flat() %}
+        val message = this.message ?: ""
+        {%- endif %}
+        when(this) {
+            {%- for variant in e.variants() %}
+            is {{ e|type_name }}.{{ variant.name()|exception_name }} -> {
+                buf.putInt({{ loop.index }})
+                {%- if e.is_flat() %}
+                {{ "message"|write_var("buf", TypeIdentifier::String) }}
+                {%- else %}
+                {%- for field in variant.fields() %}
+                {{ field.name()|write_var("buf", field) }}
+                {%- endfor %}
+                {%- endif %}
+            }
+            {%- endfor %}
+        }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
+        {%- match e.source_chain_type() %}
+        {%- when Some with (chain_type) %}
+        val sourceChain = generateSequence(this.cause) { it.cause }.map { it.message ?: "" }.toList()
+        {{ "sourceChain"|write_var("buf", chain_type) }}
+        {%- when None %}
+        {%- endmatch %}
+    }
+
     companion object ErrorHandler : CallStatusErrorHandler<{{ e|type_name }}> {
         override fun lift(error_buf: RustBuffer.ByValue): {{ e|type_name }} {
             return liftFromRustBuffer(error_buf) { error_buf -> read(error_buf) }
@@ -33,7 +60,7 @@ sealed class {{ e|type_name }}: Exception(){% if self.contains_object_references
 
         fun read(error_buf: ByteBuffer): {{ e|type_name }} {
             {% if e.is_flat() %}
-                return when(error_buf.getInt()) {
+            val error = when(error_buf.getInt()) {
                 {%- for variant in e.variants() %}
                 {{ loop.index }} -> {{ e|type_name }}.{{ variant.name()|exception_name }}(String.read(error_buf))
                 {%- endfor %}
@@ -41,7 +68,7 @@ sealed class {{ e|type_name }}: Exception(){% if self.contains_object_references
             }
             {% else %}
 
-            return when(error_buf.getInt()) {
+            val error = when(error_buf.getInt()) {
                 {%- for variant in e.variants() %}
                 {{ loop.index }} -> {{ e|type_name }}.{{ variant.name()|exception_name }}({% if variant.has_fields() %}
                     {% for field in variant.fields() -%}
@@ -52,6 +79,12 @@ sealed class {{ e|type_name }}: Exception(){% if self.contains_object_references
                 else -> throw RuntimeException("invalid error enum value, something is very wrong!!")
             }
             {%- endif %}
+            {%- match e.source_chain_type() %}
+            {%- when Some with (chain_type) %}
+            uniffiErrorSourceChain({{ "error_buf"|read_var(chain_type) }})?.let { error.initCause(it) }
+            {%- when None %}
+            {%- endmatch %}
+            return error
         }
     }
 
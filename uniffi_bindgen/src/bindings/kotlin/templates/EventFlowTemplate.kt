@@ -0,0 +1,16 @@
+{%- let obj = self.inner() %}
+{%- let event = self.event()|class_name %}
+/**
+ * The [{{ event }}]s that a [{{ obj|type_name }}] emits, for as long as the flow is collected. Each
+ * collector adds a listener to the object, and removes it again once it stops collecting. The
+ * flow doesn't destroy the object itself.
+ */
+val {{ obj|type_name }}.events: Flow<{{ event }}>
+    get() = callbackFlow {
+        val listenerId = {{ "add_listener"|fn_name }}(object : {{ self.listener()|class_name }} {
+            override fun {{ "on_event"|fn_name }}({{ "event"|var_name }}: {{ event }}) {
+                trySend({{ "event"|var_name }})
+            }
+        })
+        awaitClose { {{ "remove_listener"|fn_name }}(listenerId) }
+    }.buffer(Channel.UNLIMITED)
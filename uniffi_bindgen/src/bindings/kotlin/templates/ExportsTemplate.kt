@@ -0,0 +1,17 @@
+{%- let type_name = format!("{}Export", self.namespace)|class_name %}
+/**
+ * Every function, constructor and method exported by this component.
+ *
+ * This lets routing layers map names onto calls without keeping their own list. The
+ * `signatureHash` of an entry changes whenever its signature does.
+ */
+enum class {{ type_name }}(val kind: String, val qualifiedName: String, val signatureHash: String) {
+    {%- for export in self.exports %}
+    {{ export.qualified_name()|enum_variant }}("{{ export.kind() }}", "{{ export.qualified_name() }}", "{{ export.signature_hash() }}"){% if loop.last %};{% else %},{% endif %}
+    {%- endfor %}
+
+    companion object {
+        /** Look up an export by its qualified name, e.g. `"Counter.get"` for a method. */
+        fun fromQualifiedName(name: String): {{ type_name }}? = values().find { it.qualifiedName == name }
+    }
+}
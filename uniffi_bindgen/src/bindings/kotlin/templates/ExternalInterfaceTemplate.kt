@@ -0,0 +1,27 @@
+{%- let name = self.name() %}
+{%- let package = self.package_name() %}
+{%- let canonical_type_name = self.canonical_type_name() %}
+
+// `{{ name }}` objects belong to the `{{ package }}` package. They're passed to and from this
+// library as pointers to the same Rust object, rather than being copied.
+internal fun lower{{ canonical_type_name }}(v: {{ name }}): Pointer {
+    {{ self.check_component_fn_name() }}()
+    return {{ package }}.UniffiExternalTypes.lower{{ name }}(v)
+}
+
+internal fun write{{ canonical_type_name }}(v: {{ name }}, buf: RustBufferBuilder) {
+    // The Rust code always expects pointers written as 8 bytes,
+    // and will fail to compile if they don't fit.
+    buf.putLong(Pointer.nativeValue(lower{{ canonical_type_name }}(v)))
+}
+
+internal fun lift{{ canonical_type_name }}(ptr: Pointer): {{ name }} {
+    {{ self.check_component_fn_name() }}()
+    return {{ package }}.UniffiExternalTypes.lift{{ name }}(ptr)
+}
+
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ name }} {
+    // The Rust code always writes pointers as 8 bytes, and will
+    // fail to compile if they don't fit.
+    return lift{{ canonical_type_name }}(Pointer(buf.getLong()))
+}
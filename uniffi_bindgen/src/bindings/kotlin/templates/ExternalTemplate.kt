@@ -0,0 +1,24 @@
+{%- let name = self.name() %}
+{%- let package = self.package_name() %}
+{%- let canonical_type_name = self.canonical_type_name() %}
+
+// `{{ name }}` belongs to the `{{ package }}` package, whose bindings read and write it for us.
+internal fun lower{{ canonical_type_name }}(v: {{ name }}): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        write{{ canonical_type_name }}(v, buf)
+    }
+}
+
+internal fun write{{ canonical_type_name }}(v: {{ name }}, buf: RustBufferBuilder) {
+    buf.put({{ package }}.UniffiExternalTypes.write{{ name }}(v))
+}
+
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ name }} {
+    return liftFromRustBuffer(rbuf) { buf ->
+        read{{ canonical_type_name }}(buf)
+    }
+}
+
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ name }} {
+    return {{ package }}.UniffiExternalTypes.read{{ name }}(buf)
+}
@@ -0,0 +1,66 @@
+{%- if !self.exported_types.is_empty() %}
+/**
+ * Reads, writes, lifts and lowers the types of this component for the bindings of other components
+ * that declare them as `[External]` or `[ExternalInterface]`, which can't use their `internal`
+ * helpers. It isn't meant to be used by anything else.
+ */
+object UniffiExternalTypes {
+    {%- for exported in self.exported_types %}
+    {%- let type_name = exported.type_|type_name %}
+    {%- if exported.is_object %}
+
+    fun lift{{ exported.name }}(ptr: Pointer): {{ type_name }} {
+        return {{ "ptr"|lift_var(exported.type_) }}
+    }
+
+    fun lower{{ exported.name }}(value: {{ type_name }}): Pointer {
+        return {{ "value"|lower_var(exported.type_) }}
+    }
+    {%- else %}
+
+    fun read{{ exported.name }}(buf: ByteBuffer): {{ type_name }} {
+        return {{ "buf"|read_var(exported.type_) }}
+    }
+
+    fun write{{ exported.name }}(value: {{ type_name }}): ByteArray {
+        val rbuf = lowerIntoRustBuffer(value) { v, buf ->
+            {{ "v"|write_var("buf", exported.type_) }}
+        }
+        try {
+            return rbuf.data?.getByteArray(0, rbuf.len) ?: ByteArray(0)
+        } finally {
+            RustBuffer.free(rbuf)
+        }
+    }
+    {%- endif %}
+    {%- endfor %}
+}
+{%- for exported in self.exported_types %}
+{%- let type_name = exported.type_|type_name %}
+{%- if type_name != exported.name %}
+
+// The other components' bindings refer to the type by the name it has in the UDL.
+typealias {{ exported.name }} = {{ type_name }}
+{%- endif %}
+{%- endfor %}
+{%- endif %}
+{%- for component in self.external_components %}
+
+// The objects of the `{{ component.package_name }}` package are passed to this library as pointers
+// to the Rust structs behind them, so both libraries have to be built from the same interface.
+private val {{ component.check_component_fn_name }}Checked: Unit by lazy {
+    val checksum = "%016x".format(rustCall() { status ->
+        _UniFFILib.INSTANCE.{{ component.checksum_fn_name }}(status)
+    })
+    val expected = {{ component.package_name }}.UNIFFI_COMPONENT_CHECKSUM
+    if (checksum != expected) {
+        throw InternalException(
+            "The {{ self.namespace }} library was built against the {{ component.package_name }} component with checksum $checksum, " +
+            "but the {{ component.package_name }} bindings expect $expected. " +
+            "Build both libraries from the same source."
+        )
+    }
+}
+
+internal fun {{ component.check_component_fn_name }}() = {{ component.check_component_fn_name }}Checked
+{%- endfor %}
@@ -17,10 +17,26 @@ internal open class RustCallStatus : Structure() {
     fun isPanic(): Boolean {
         return code == 2
     }
+
+    fun isInvalidString(): Boolean {
+        return code == 3
+    }
 }
 
 class InternalException(message: String) : Exception(message)
 
+// Thrown when Rust is passed a string that isn't valid UTF-8, which these bindings never do
+// themselves, but which Rust reports as such rather than panicking.
+class InvalidStringException(message: String) : Exception(message)
+
+// Errors declared with `[SourceChain]` carry the messages of their Rust `source()`s,
+// which we surface as a chain of `cause`s on the thrown exception.
+class UniffiErrorSource(message: String, cause: Throwable?) : Exception(message, cause)
+
+internal fun uniffiErrorSourceChain(messages: List<String>): Throwable? {
+    return messages.foldRight(null as Throwable?) { message, cause -> UniffiErrorSource(message, cause) }
+}
+
 // Each top-level error class has a companion object that can lift the error from the call status's rust buffer
 interface CallStatusErrorHandler<E> {
     fun lift(error_buf: RustBuffer.ByValue): E;
@@ -47,6 +63,8 @@ private inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusE
         } else {
             throw InternalException("Rust panic")
         }
+    } else if (status.isInvalidString()) {
+        throw InvalidStringException({{ "status.error_buf"|lift_var(Type::String) }})
     } else {
         throw InternalException("Unknown rust call status: $status.code")
     }
@@ -64,3 +82,13 @@ object NullCallStatusErrorHandler: CallStatusErrorHandler<InternalException> {
 private inline fun <U> rustCall(callback: (RustCallStatus) -> U): U {
     return rustCallWithError(NullCallStatusErrorHandler, callback);
 }
+
+// With `errors_as_values`, the functions and methods that throw return a `Result` instead, which
+// holds what they returned or the exception that they declare. Anything else, such as the
+// `InternalException` for a panic, is still thrown.
+private inline fun <T, E: Exception> uniffiResultOf(errorClass: kotlin.reflect.KClass<E>, block: () -> T): Result<T> =
+    try {
+        Result.success(block())
+    } catch (e: Exception) {
+        if (errorClass.isInstance(e)) Result.failure(e) else throw e
+    }
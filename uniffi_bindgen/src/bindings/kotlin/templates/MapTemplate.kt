@@ -1,39 +1,41 @@
 {%- import "macros.kt" as kt -%}
+{%- let key_type = self.key() %}
+{%- let key_type_name = key_type|type_name %}
 {%- let inner_type = self.inner() %}
 {%- let outer_type = self.outer() %}
 {%- let inner_type_name = inner_type|type_name %}
 {%- let canonical_type_name = outer_type|canonical_name %}
 
 // Helper functions for passing values of type {{ outer_type|type_name }}
-internal fun lower{{ canonical_type_name }}(m: Map<String, {{ inner_type_name }}>): RustBuffer.ByValue {
+internal fun lower{{ canonical_type_name }}(m: Map<{{ key_type_name }}, {{ inner_type_name }}>): RustBuffer.ByValue {
     return lowerIntoRustBuffer(m) { m, buf ->
         write{{ canonical_type_name }}(m, buf)
     }
 }
 
-internal fun write{{ canonical_type_name }}(v: Map<String, {{ inner_type_name }}>, buf: RustBufferBuilder) {
+internal fun write{{ canonical_type_name }}(v: Map<{{ key_type_name }}, {{ inner_type_name }}>, buf: RustBufferBuilder) {
     buf.putInt(v.size)
     // The parens on `(k, v)` here ensure we're calling the right method,
     // which is important for compatibility with older android devices.
     // Ref https://blog.danlew.net/2017/03/16/kotlin-puzzler-whose-line-is-it-anyways/
     v.forEach { (k, v) ->
-        {{ "k"|write_var("buf", TypeIdentifier::String) }}
+        {{ "k"|write_var("buf", key_type) }}
         {{ "v"|write_var("buf", inner_type) }}
     }
 }
 
-internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): Map<String, {{ inner_type_name }}> {
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): Map<{{ key_type_name }}, {{ inner_type_name }}> {
     return liftFromRustBuffer(rbuf) { buf ->
         read{{ canonical_type_name }}(buf)
     }
 }
 
-internal fun read{{ canonical_type_name }}(buf: ByteBuffer): Map<String, {{ inner_type_name }}> {
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): Map<{{ key_type_name }}, {{ inner_type_name }}> {
     // TODO: Once Kotlin's `buildMap` API is stabilized we should use it here.
-    val items : MutableMap<String, {{ inner_type_name }}> = mutableMapOf()
+    val items : MutableMap<{{ key_type_name }}, {{ inner_type_name }}> = mutableMapOf()
     val len = buf.getInt()
     repeat(len) {
-        val k = {{ "buf"|read_var(TypeIdentifier::String) }}
+        val k = {{ "buf"|read_var(key_type) }}
         val v = {{ "buf"|read_var(inner_type) }}
         items[k] = v
     }
@@ -0,0 +1,17 @@
+/**
+ * Tell the Rust library that the system is running low on memory, so that it can shrink its
+ * caches.
+ *
+ * Call it from `onTrimMemory`, passing the [level] that it was given. The hooks that the
+ * component registered with `uniffi::on_memory_pressure` are told that the pressure is
+ * critical for `TRIM_MEMORY_RUNNING_CRITICAL` and `TRIM_MEMORY_COMPLETE`, and moderate
+ * for the other levels.
+ */
+fun uniffiOnMemoryPressure(level: Int) {
+    // The values of `ComponentCallbacks2.TRIM_MEMORY_RUNNING_CRITICAL` and `TRIM_MEMORY_COMPLETE`,
+    // which aren't available outside of Android.
+    val pressure = if (level == 15 || level >= 80) 2 else 1
+    rustCall() { status ->
+        _UniFFILib.INSTANCE.{{ ci.ffi_on_memory_pressure().name() }}(pressure, status)
+    }
+}
@@ -0,0 +1,7 @@
+{%- let module = self.inner() %}
+
+object {{ module.name()|class_name }} {
+    {%- for code in self.member_code() %}
+    {{ code|indent(4) }}
+    {%- endfor %}
+}
@@ -1,5 +1,10 @@
+// Set by `{{ ci.namespace()|class_name }}Library.load()`, and guarded by `libraryLock`.
+private var explicitLibraryPath: String? = null
+private val libraryLock = Any()
+
 @Synchronized
 private fun findLibraryName(componentName: String): String {
+    explicitLibraryPath?.let { return it }
     val libOverride = System.getProperty("uniffi.component.$componentName.libraryOverride")
     if (libOverride != null) {
         return libOverride
@@ -13,13 +18,51 @@ private inline fun <reified Lib : Library> loadIndirect(
     return Native.load<Lib>(findLibraryName(componentName), Lib::class.java)
 }
 
+// Check that the library was built from the same component interface as these bindings, before
+// anything else calls into it. JNA only looks up each function the first time it's called, so
+// a mismatch would otherwise only show up later, as a confusing missing symbol, or worse.
+private fun uniffiCheckChecksum(lib: _UniFFILib) {
+    val checksum = try {
+        "%016x".format(lib.{{ ci.ffi_checksum().name() }}(RustCallStatus()))
+    } catch (e: UnsatisfiedLinkError) {
+        throw InternalException(
+            "The {{ ci.namespace() }} library doesn't report its checksum, so it's older than these bindings, " +
+            "which uniffi-bindgen $UNIFFI_BINDGEN_VERSION generated. Regenerate the bindings from the library's UDL."
+        )
+    }
+    if (checksum != UNIFFI_COMPONENT_CHECKSUM) {
+        throw InternalException(
+            "The {{ ci.namespace() }} library was built with checksum $checksum, but these bindings, " +
+            "which uniffi-bindgen $UNIFFI_BINDGEN_VERSION generated, expect $UNIFFI_COMPONENT_CHECKSUM. " +
+            "Regenerate the bindings from the library's UDL."
+        )
+    }
+}
+
+// Shut the library down when the JVM starts to exit, so that the component stops calling back into
+// it while it's torn down. Calls into the component throw an `InternalException` after this, and
+// the callback interface objects that Rust was holding are let go of, without waiting for Rust
+// to release them.
+private fun uniffiRegisterShutdownHook(lib: _UniFFILib) {
+    Runtime.getRuntime().addShutdownHook(Thread({
+        rustCall() { status ->
+            lib.{{ ci.ffi_shutdown().name() }}(status)
+        }
+        {%- if !ci.iter_callback_interface_definitions().is_empty() %}
+        uniffiCallbackInterfaces.forEach { it.handleMap.clear() }
+        {%- endif %}
+    }, "uniffi-shutdown"))
+}
+
 // A JNA Library to expose the extern-C FFI definitions.
 // This is an implementation detail which will be called internally by the public API.
 
 internal interface _UniFFILib : Library {
     companion object {
-        internal val INSTANCE: _UniFFILib by lazy {
+        internal val lazyInstance: Lazy<_UniFFILib> = lazy(libraryLock) {
             loadIndirect<_UniFFILib>(componentName = "{{ ci.namespace() }}")
+            .also { lib: _UniFFILib -> uniffiCheckChecksum(lib) }
+            .also { lib: _UniFFILib -> uniffiRegisterShutdownHook(lib) }
             {% let initialization_code = self.initialization_code() %}
             {%- if !initialization_code.is_empty() -%}
             .also { lib: _UniFFILib ->
@@ -29,6 +72,8 @@ internal interface _UniFFILib : Library {
             }
             {% endif %}
         }
+
+        internal val INSTANCE: _UniFFILib by lazyInstance
     }
 
     {% for func in ci.iter_ffi_function_definitions() -%}
@@ -38,3 +83,27 @@ internal interface _UniFFILib : Library {
 
     {% endfor %}
 }
+
+/**
+ * Controls where the native library for this component is loaded from.
+ *
+ * By default the library is looked up by name, the first time it's needed.
+ */
+object {{ ci.namespace()|class_name }}Library {
+    /**
+     * Load the native library from an explicit `path`, such as the location of a library
+     * extracted from a split APK or built for a test environment.
+     *
+     * This must be called before anything else in the component is used, and throws an
+     * [IllegalStateException] if the library has already been loaded.
+     */
+    fun load(path: String) {
+        synchronized(libraryLock) {
+            if (_UniFFILib.lazyInstance.isInitialized()) {
+                throw IllegalStateException("The {{ ci.namespace() }} library has already been loaded")
+            }
+            explicitLibraryPath = path
+            _UniFFILib.INSTANCE
+        }
+    }
+}
@@ -109,8 +109,15 @@ inline fun <T : Disposable?, R> T.use(block: (T) -> R) =
 //
 // [1] https://stackoverflow.com/questions/24376768/can-java-finalize-an-object-when-it-is-still-in-scope/24380219
 //
+// Objects with the `[Closeable]` attribute go one step further and poison their handle once
+// `destroy` or `close` has been called: any later method call throws an `IllegalStateException`,
+// even if other calls are still in flight and the Rust struct hasn't been freed yet. The check
+// happens after the call counter has been incremented, so a call either sees the object as closed,
+// or is guaranteed to run before the Rust struct is freed.
+//
 abstract class FFIObject(
-    protected val pointer: Pointer
+    protected val pointer: Pointer,
+    private val poisonOnClose: Boolean = false
 ): Disposable, AutoCloseable {
 
     private val wasDestroyed = AtomicBoolean(false)
@@ -142,6 +149,9 @@ abstract class FFIObject(
         do {
             val c = this.callCounter.get()
             if (c == 0L) {
+                if (this.poisonOnClose) {
+                    throw IllegalStateException("${this.javaClass.simpleName} object has already been closed")
+                }
                 throw IllegalStateException("${this.javaClass.simpleName} object has already been destroyed")
             }
             if (c == Long.MAX_VALUE) {
@@ -150,6 +160,9 @@ abstract class FFIObject(
         } while (! this.callCounter.compareAndSet(c, c + 1L))
         // Now we can safely do the method call without the pointer being freed concurrently.
         try {
+            if (this.poisonOnClose && this.wasDestroyed.get()) {
+                throw IllegalStateException("${this.javaClass.simpleName} object has already been closed")
+            }
             return block(this.pointer)
         } finally {
             // This decrement aways matches the increment we performed above.
@@ -159,3 +172,94 @@ abstract class FFIObject(
         }
     }
 }
+{%- if has_also_async %}
+
+// The threads that the `suspend` variants of `[AlsoAsync]` methods call the blocking method on,
+// so that the coroutine's own thread isn't blocked while the Rust code runs. They're daemon
+// threads, which don't keep the process alive once everything else is done.
+//
+// Cancelling the coroutine doesn't stop the Rust call, which always runs to completion.
+internal object UniFFIAsync {
+    private val executor = Executors.newCachedThreadPool { runnable ->
+        Thread(runnable, "uniffi-async").apply { isDaemon = true }
+    }
+
+    internal suspend fun <R> call(block: () -> R): R = suspendCoroutine { continuation ->
+        executor.execute { continuation.resumeWith(runCatching(block)) }
+    }
+}
+{%- endif %}
+{%- if has_no_copy %}
+
+// The bytes that a `[NoCopy]` method returned, which are left where the Rust code put them
+// rather than copied into a `ByteArray`. They should be freed once done with, by calling `free()`
+// or `close()`; if they aren't, they're freed some time after the `OwnedBytes` is unreachable.
+class OwnedBytes internal constructor(buf: RustBuffer.ByValue) : AutoCloseable {
+    private val state = State(buf)
+
+    init {
+        Cleaner.register(this, state)
+    }
+
+    val size: Int = buf.len
+
+    // Call `block` with a read-only view of the bytes, which doesn't copy them. They can't be
+    // freed while it runs, and the `ByteBuffer` mustn't be kept after it returns.
+    fun <R> useByteBuffer(block: (ByteBuffer) -> R): R = synchronized(state) {
+        state.checkNotFreed()
+        block(state.buf.data?.getByteBuffer(0, state.buf.len.toLong())?.asReadOnlyBuffer() ?: ByteBuffer.allocate(0))
+    }
+
+    // A copy of the bytes, which can still be used after they're freed.
+    fun toByteArray(): ByteArray = synchronized(state) {
+        state.checkNotFreed()
+        state.buf.data?.getByteArray(0, state.buf.len) ?: ByteArray(0)
+    }
+
+    // Give the bytes back to the Rust code. It can be called more than once.
+    fun free() = state.free()
+
+    override fun close() = free()
+
+    // The buffer, kept apart from the `OwnedBytes` so that the cleaner can free it without
+    // keeping the `OwnedBytes` reachable.
+    private class State(val buf: RustBuffer.ByValue) {
+        private var freed = false
+
+        fun checkNotFreed() = check(!freed) { "OwnedBytes has already been freed" }
+
+        @Synchronized
+        fun free() {
+            if (!freed) {
+                freed = true
+                RustBuffer.free(buf)
+            }
+        }
+    }
+
+    // Frees the buffers of `OwnedBytes` that were never freed, once they're collected. We don't
+    // use `java.lang.ref.Cleaner`, because Android only has it from API level 33.
+    private object Cleaner {
+        private val queue = java.lang.ref.ReferenceQueue<OwnedBytes>()
+        // The references must stay reachable themselves until they're enqueued.
+        private val refs = java.util.concurrent.ConcurrentHashMap.newKeySet<Ref>()
+
+        private class Ref(owner: OwnedBytes, val state: State) :
+            java.lang.ref.PhantomReference<OwnedBytes>(owner, queue)
+
+        init {
+            Thread({
+                while (true) {
+                    val ref = queue.remove() as Ref
+                    refs.remove(ref)
+                    ref.state.free()
+                }
+            }, "uniffi-owned-bytes-cleaner").apply { isDaemon = true }.start()
+        }
+
+        fun register(owner: OwnedBytes, state: State) {
+            refs.add(Ref(owner, state))
+        }
+    }
+}
+{%- endif %}
@@ -2,25 +2,42 @@
 {%- let obj = self.inner() %}
 public interface {{ obj|type_name }}Interface {
     {% for meth in obj.methods() -%}
+    {%- let as_result = self.errors_as_values() && meth.throws().is_some() %}
     {%- match meth.throws() -%}
     {%- when Some with (throwable) %}
+    {%- if !as_result %}
     @Throws({{ throwable|exception_name }}::class)
+    {%- endif %}
     {%- else -%}
     {%- endmatch %}
-    fun {{ meth.name()|fn_name }}({% call kt::arg_list_decl(meth) %})
-    {%- match meth.return_type() -%}
-    {%- when Some with (return_type) %}: {{ return_type|type_name -}}
+    {% if meth.is_async() %}suspend {% endif %}fun {{ meth.name()|fn_name }}({% call kt::arg_list_decl(meth) %})
+    {%- if meth.is_no_copy() %}: {% if as_result %}Result<OwnedBytes>{% else %}OwnedBytes{% endif %}
+    {%- else %}{% call kt::return_type(meth) %}
+    {%- endif %}
+    {%- if meth.is_also_async() %}
+    {%- match meth.throws() -%}
+    {%- when Some with (throwable) %}
+    {%- if !as_result %}
+    @Throws({{ throwable|exception_name }}::class)
+    {%- endif %}
     {%- else -%}
     {%- endmatch %}
+    suspend fun {{ meth.async_method_name()|fn_name }}({% call kt::arg_list_decl(meth) %}){% call kt::return_type(meth) %}
+    {%- endif %}
     {% endfor %}
 }
 
 class {{ obj|type_name }}(
     pointer: Pointer
-) : FFIObject(pointer), {{ obj|type_name }}Interface {
+) : FFIObject(pointer{% if obj.is_closeable() %}, poisonOnClose = true{% endif %}), {{ obj|type_name }}Interface{% if obj.is_cloneable() %}, Cloneable{% endif %}
+{%- match obj.foreign_extension() %}{% when Some with (ext) %}, {{ ext }}{% when None %}{% endmatch %}
+{%- match obj.stream_item() %}{% when Some with (item) %}, Sequence<{{ item|type_name }}>{% when None %}{% endmatch %} {
 
     {%- match obj.primary_constructor() %}
     {%- when Some with (cons) %}
+    {%- if self.java_interop() %}
+    {%- call kt::jvm_constructor_annotations(cons) %}
+    {%- endif %}
     constructor({% call kt::arg_list_decl(cons) -%}) :
         this({% call kt::to_ffi_call(cons) %})
     {%- when None %}
@@ -40,6 +57,44 @@ class {{ obj|type_name }}(
         }
     }
 
+    {%- match obj.ffi_object_clone() %}
+    {%- when Some with (ffi_clone) %}
+
+    /**
+     * Make a new, independent copy of the underlying Rust object.
+     *
+     * The copy does not share any state with this object, and must be destroyed separately.
+     */
+    public override fun clone(): {{ obj|type_name }} =
+        callWithPointer {
+            rustCall() { status ->
+                _UniFFILib.INSTANCE.{{ ffi_clone.name() }}(it, status)
+            }
+        }.let {
+            {{ obj|type_name }}(it)
+        }
+    {%- when None %}
+    {%- endmatch %}
+
+    {%- match obj.ffi_object_downgrade() %}
+    {%- when Some with (ffi_downgrade) %}
+
+    /**
+     * Make a weak reference to the underlying Rust object, which doesn't keep it alive.
+     *
+     * The weak reference must be destroyed separately.
+     */
+    fun downgrade(): {{ obj|type_name }}WeakRef =
+        callWithPointer {
+            rustCall() { status ->
+                _UniFFILib.INSTANCE.{{ ffi_downgrade.name() }}(it, status)
+            }
+        }.let {
+            {{ obj|type_name }}WeakRef(it)
+        }
+    {%- when None %}
+    {%- endmatch %}
+
     internal fun lower(): Pointer = callWithPointer { it }
 
     internal fun write(buf: RustBufferBuilder) {
@@ -49,30 +104,118 @@ class {{ obj|type_name }}(
     }
 
     {% for meth in obj.methods() -%}
+    {%- let as_result = self.errors_as_values() && meth.throws().is_some() %}
     {%- match meth.throws() -%}
     {%- when Some with (throwable) %}
+    {%- if !as_result %}
     @Throws({{ throwable|exception_name }}::class)
+    {%- endif %}
     {%- else -%}
     {%- endmatch %}
+    {%- if meth.is_async() %}
+    override suspend fun {{ meth.name()|fn_name }}({% call kt::arg_list_protocol(meth) %}){% call kt::return_type(meth) %} =
+        {% if as_result %}{% call kt::result_of(meth) %} {
+        {% endif -%}
+        {% call kt::to_async_method_call(meth) %}
+        {%- if as_result %}
+        }
+        {%- endif %}
+    {%- else %}
     {%- match meth.return_type() -%}
 
     {%- when Some with (return_type) -%}
-    override fun {{ meth.name()|fn_name }}({% call kt::arg_list_protocol(meth) %}): {{ return_type|type_name }} =
+    {%- if meth.is_cached() %}
+    override fun {{ meth.name()|fn_name }}(){% call kt::return_type(meth) %} =
+        {%- if as_result %} {% call kt::result_of(meth) %} { {{ meth.name()|fn_name }}Cache }
+        {%- else %} {{ meth.name()|fn_name }}Cache
+        {%- endif %}
+
+    // `[Cached]`: the value is lifted the first time it's asked for, and kept after that. If the
+    // call throws, nothing is kept, and the next call tries again.
+    private val {{ meth.name()|fn_name }}Cache: {{ return_type|type_name }} by lazy {
         callWithPointer {
             {%- call kt::to_ffi_call_with_prefix("it", meth) %}
         }.let {
             {{ "it"|lift_var(return_type) }}
         }
+    }
+    {%- else if meth.is_no_copy() %}
+    override fun {{ meth.name()|fn_name }}({% call kt::arg_list_protocol(meth) %}): {% if as_result %}Result<OwnedBytes>{% else %}OwnedBytes{% endif %} =
+        {% if as_result %}{% call kt::result_of(meth) %} {
+        {% endif -%}
+        callWithPointer {
+            {%- call kt::to_ffi_call_with_prefix("it", meth) %}
+        }.let {
+            OwnedBytes(it)
+        }
+        {%- if as_result %}
+        }
+        {%- endif %}
+    {%- else %}
+    override fun {{ meth.name()|fn_name }}({% call kt::arg_list_protocol(meth) %}){% call kt::return_type(meth) %} =
+        {% if as_result %}{% call kt::result_of(meth) %} {
+        {% endif -%}
+        callWithPointer {
+            {%- call kt::to_ffi_call_with_prefix("it", meth) %}
+        }.let {
+            {{ "it"|lift_var(return_type) }}
+        }
+        {%- if as_result %}
+        }
+        {%- endif %}
+    {%- endif %}
 
     {%- when None -%}
-    override fun {{ meth.name()|fn_name }}({% call kt::arg_list_protocol(meth) %}) =
+    override fun {{ meth.name()|fn_name }}({% call kt::arg_list_protocol(meth) %}){% call kt::return_type(meth) %} =
+        {% if as_result %}{% call kt::result_of(meth) %} {
+        {% endif -%}
         callWithPointer {
             {%- call kt::to_ffi_call_with_prefix("it", meth) %}
         }
+        {%- if as_result %}
+        }
+        {%- endif %}
     {% endmatch %}
+    {%- endif %}
+    {%- if meth.is_also_async() %}
+    {%- match meth.throws() -%}
+    {%- when Some with (throwable) %}
+    {%- if !as_result %}
+    @Throws({{ throwable|exception_name }}::class)
+    {%- endif %}
+    {%- else -%}
+    {%- endmatch %}
+    override suspend fun {{ meth.async_method_name()|fn_name }}({% call kt::arg_list_protocol(meth) %}){% call kt::return_type(meth) %} =
+        UniFFIAsync.call { {{ meth.name()|fn_name }}({% call kt::arg_names(meth) %}) }
+    {% endif %}
     {% endfor %}
 
+    {%- match obj.stream_item() %}
+    {%- when Some with (item) %}
+
+    /**
+     * Iterate over the rest of the stream, taking its items from Rust a chunk at a time.
+     *
+     * A stream can only be iterated over once: iterating again only gets the items that no
+     * earlier iteration has taken.
+     */
+    override fun iterator(): Iterator<{{ item|type_name }}> = iterator {
+        while (true) {
+            val chunk = nextChunk(64u)
+            if (chunk.isEmpty()) {
+                break
+            }
+            yieldAll(chunk)
+        }
+    }
+    {%- when None %}
+    {%- endmatch %}
+
     companion object {
+        // The threads that this object can be used from, for tools that check the callers:
+        // "threadsafe", "main-thread-only" or "blocking".
+        const val UNIFFI_THREADING = "{{ obj.threading().as_str() }}"
+
         internal fun lift(ptr: Pointer): {{ obj|type_name }} {
             return {{ obj|type_name }}(ptr)
         }
@@ -84,8 +227,45 @@ class {{ obj|type_name }}(
         }
 
         {% for cons in obj.alternate_constructors() -%}
+        {%- if self.java_interop() %}
+        @JvmStatic
+        {%- call kt::jvm_constructor_annotations(cons) %}
+        {%- endif %}
         fun {{ cons.name()|fn_name }}({% call kt::arg_list_decl(cons) %}): {{ obj|type_name }} =
             {{ obj|type_name }}({% call kt::to_ffi_call(cons) %})
         {% endfor %}
     }
 }
+{%- match obj.ffi_object_upgrade() %}
+{%- when Some with (ffi_upgrade) %}
+
+/**
+ * A weak reference to a [{{ obj|type_name }}], which doesn't keep the underlying Rust object alive,
+ * for caches that would rather not hold on to it.
+ *
+ * Clients **must** call `destroy()` once done with the weak reference, or cause a memory leak.
+ */
+class {{ obj|type_name }}WeakRef(
+    pointer: Pointer
+) : FFIObject(pointer) {
+    /**
+     * Get the object back, as a new reference that must be destroyed separately, or `null` if
+     * the Rust object is gone.
+     */
+    fun upgrade(): {{ obj|type_name }}? =
+        callWithPointer {
+            rustCall() { status ->
+                _UniFFILib.INSTANCE.{{ ffi_upgrade.name() }}(it, status)
+            }
+        }.let {
+            if (it == 0L) null else {{ obj|type_name }}(Pointer(it))
+        }
+
+    override protected fun freeRustArcPtr() {
+        rustCall() { status ->
+            _UniFFILib.INSTANCE.{{ obj.ffi_object_weak_free().unwrap().name() }}(this.pointer, status)
+        }
+    }
+}
+{%- when None %}
+{%- endmatch %}
@@ -0,0 +1,32 @@
+{%- let obj = self.inner() %}
+{%- let model = self.model()|class_name %}
+{%- let snapshot = self.snapshot()|class_name %}
+/**
+ * Holds the latest [{{ snapshot }}] of a [{{ obj|type_name }}] in a [StateFlow], for a view model to
+ * expose to the UI. It starts from `initial`, and observes the object until it's closed, which
+ * a view model would do in `onCleared()`. It doesn't destroy the object itself.
+ */
+class {{ model }}(
+    val source: {{ obj|type_name }},
+    initial: {{ snapshot }}
+) : AutoCloseable {
+    private val mutableState = MutableStateFlow(initial)
+    private val closed = AtomicBoolean(false)
+
+    /**
+     * The latest snapshot of the object, which changes on whichever thread changed the object.
+     */
+    val state: StateFlow<{{ snapshot }}> = mutableState.asStateFlow()
+
+    private val observerId = source.{{ "add_observer"|fn_name }}(object : {{ self.observer()|class_name }} {
+        override fun {{ "on_changed"|fn_name }}({{ "field"|var_name }}: String, {{ "snapshot"|var_name }}: {{ snapshot }}) {
+            mutableState.value = {{ "snapshot"|var_name }}
+        }
+    })
+
+    override fun close() {
+        if (closed.compareAndSet(false, true)) {
+            source.{{ "remove_observer"|fn_name }}(observerId)
+        }
+    }
+}
@@ -0,0 +1,42 @@
+{%- let obj = self.inner() %}
+/**
+ * An opaque handle to a Rust `{{ obj.name() }}`, which can only be passed back to Rust.
+ *
+ * Two handles are equal if they refer to the same Rust object. Clients **must** call
+ * `destroy()` once done with the handle, or cause a memory leak.
+ */
+class {{ obj|type_name }}(
+    pointer: Pointer
+) : FFIObject(pointer) {
+
+    override protected fun freeRustArcPtr() {
+        rustCall() { status ->
+            _UniFFILib.INSTANCE.{{ obj.ffi_object_free().name() }}(this.pointer, status)
+        }
+    }
+
+    override fun equals(other: Any?): Boolean =
+        other is {{ obj|type_name }} && other.pointer == this.pointer
+
+    override fun hashCode(): Int = this.pointer.hashCode()
+
+    internal fun lower(): Pointer = callWithPointer { it }
+
+    internal fun write(buf: RustBufferBuilder) {
+        // The Rust code always expects pointers written as 8 bytes,
+        // and will fail to compile if they don't fit.
+        buf.putLong(Pointer.nativeValue(this.lower()))
+    }
+
+    companion object {
+        internal fun lift(ptr: Pointer): {{ obj|type_name }} {
+            return {{ obj|type_name }}(ptr)
+        }
+
+        internal fun read(buf: ByteBuffer): {{ obj|type_name }} {
+            // The Rust code always writes pointers as 8 bytes, and will
+            // fail to compile if they don't fit.
+            return {{ obj|type_name }}.lift(Pointer(buf.getLong()))
+        }
+    }
+}
@@ -0,0 +1,41 @@
+{%- import "macros.kt" as kt -%}
+{%- let inner_type = self.inner() %}
+{%- let outer_type = self.outer() %}
+{%- let inner_type_name = inner_type|type_name %}
+{%- let canonical_type_name = outer_type|canonical_name %}
+
+// Helper functions for passing values of type {{ outer_type|type_name }}, which keep their
+// entries in the order they were inserted.
+internal fun lower{{ canonical_type_name }}(m: LinkedHashMap<String, {{ inner_type_name }}>): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(m) { m, buf ->
+        write{{ canonical_type_name }}(m, buf)
+    }
+}
+
+internal fun write{{ canonical_type_name }}(v: LinkedHashMap<String, {{ inner_type_name }}>, buf: RustBufferBuilder) {
+    buf.putInt(v.size)
+    // The parens on `(k, v)` here ensure we're calling the right method,
+    // which is important for compatibility with older android devices.
+    // Ref https://blog.danlew.net/2017/03/16/kotlin-puzzler-whose-line-is-it-anyways/
+    v.forEach { (k, v) ->
+        {{ "k"|write_var("buf", TypeIdentifier::String) }}
+        {{ "v"|write_var("buf", inner_type) }}
+    }
+}
+
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): LinkedHashMap<String, {{ inner_type_name }}> {
+    return liftFromRustBuffer(rbuf) { buf ->
+        read{{ canonical_type_name }}(buf)
+    }
+}
+
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): LinkedHashMap<String, {{ inner_type_name }}> {
+    val len = buf.getInt()
+    val items = LinkedHashMap<String, {{ inner_type_name }}>(len)
+    repeat(len) {
+        val k = {{ "buf"|read_var(TypeIdentifier::String) }}
+        val v = {{ "buf"|read_var(inner_type) }}
+        items[k] = v
+    }
+    return items
+}
@@ -1,15 +1,23 @@
 {% import "macros.kt" as kt %}
 {%- let rec = self.inner() %}
-data class {{ rec|type_name }} (
+{%- if self.is_parcelable() %}
+@Parcelize
+{%- endif %}
+{%- if self.is_inline() %}
+@JvmInline
+value class {{ rec|type_name }} (
+{%- else %}
+data class {{ rec|type_name }} {% if self.has_jvm_overloads() %}@JvmOverloads constructor{% endif %}(
+{%- endif %}
     {%- for field in rec.fields() %}
-    var {{ field.name()|var_name }}: {{ field|type_name -}}
+    {% if self.is_inline() %}val{% else %}var{% endif %} {{ field.name()|var_name }}: {{ field|type_name -}}
     {%- match field.default_value() %}
         {%- when Some with(literal) %} = {{ literal|render_literal(field) }}
         {%- else %}
     {%- endmatch -%}
     {% if !loop.last %}, {% endif %}
     {%- endfor %}
-) {% if self.contains_object_references() %}: Disposable {% endif %}{
+) {% if self.contains_object_references() %}: Disposable {% else if self.is_parcelable() %}: Parcelable {% endif %}{
     companion object {
         internal fun lift(rbuf: RustBuffer.ByValue): {{ rec|type_name }} {
             return liftFromRustBuffer(rbuf) { buf -> {{ rec|type_name }}.read(buf) }
@@ -33,6 +41,12 @@ data class {{ rec|type_name }} (
             {{ "this.{}"|format(field.name())|write_var("buf", field) }}
         {% endfor %}
     }
+    {%- if rec.has_sensitive_fields() %}
+
+    // `[Sensitive]` fields are left out, so that they don't end up in logs.
+    override fun toString(): String =
+        "{{ rec|type_name }}({% for field in rec.fields() %}{{ field.name()|var_name }}={% if field.sensitive() %}***{% else %}${{ "{" }}this.{{ field.name()|var_name }}{{ "}" }}{% endif %}{% if !loop.last %}, {% endif %}{% endfor %})"
+    {%- endif %}
 
     {% if self.contains_object_references() %}
     @Suppress("UNNECESSARY_SAFE_CALL") // codegen is much simpler if we unconditionally emit safe calls here
@@ -1,20 +1,54 @@
 {% import "macros.kt" as kt %}
 {%- let func = self.inner() %}
+{%- let as_result = self.errors_as_values() && func.throws().is_some() %}
 {%- match func.throws() -%}
 {%- when Some with (throwable) %}
+{%- if !as_result %}
 @Throws({{ throwable|exception_name }}::class)
+{%- endif %}
 {%- else -%}
 {%- endmatch %}
+{%- if self.java_interop() %}
+{%- if self.in_module() %}
+@JvmStatic
+{%- endif %}
+{%- if func.arguments()|has_default_values %}
+@JvmOverloads
+{%- endif %}
+{%- endif %}
+{%- if func.is_async() %}
+
+suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% call kt::return_type(func) %} =
+    {% if as_result %}{% call kt::result_of(func) %} {
+    {% endif -%}
+    {% call kt::to_async_ffi_call(func) %}
+    {%- if as_result %}
+    }
+    {%- endif %}
+{% else %}
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
 
-fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}): {{ return_type|type_name }} {
+fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% call kt::return_type(func) %} {
+    {%- if as_result %}
+    return {% call kt::result_of(func) %} {
+        val _retval = {% call kt::to_ffi_call(func) %}
+        {{ "_retval"|lift_var(return_type) }}
+    }
+    {%- else %}
     val _retval = {% call kt::to_ffi_call(func) %}
     return {{ "_retval"|lift_var(return_type) }}
+    {%- endif %}
 }
 
 {% when None -%}
 
-fun {{ func.name()|fn_name }}({% call kt::arg_list_decl(func) %}) =
+fun {{ func.name()|fn_name }}({% call kt::arg_list_decl(func) %}){% call kt::return_type(func) %} =
+    {% if as_result %}{% call kt::result_of(func) %} {
+    {% endif -%}
     {% call kt::to_ffi_call(func) %}
+    {%- if as_result %}
+    }
+    {%- endif %}
 {% endmatch %}
+{%- endif %}
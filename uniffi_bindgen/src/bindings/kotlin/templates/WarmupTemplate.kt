@@ -0,0 +1,25 @@
+/**
+ * Get the Rust library ready on a background thread, so that the first call into it is as quick
+ * as the ones after it.
+ *
+ * Call it while the app starts up. It loads the library and checks that it matches these
+ * bindings, registers the callbacks that Rust needs, and runs the hooks that the component
+ * registered with `uniffi::on_warmup`. It returns the thread that's doing that, which can be
+ * joined to wait for it. If anything fails, the first call into the component fails the same way.
+ */
+fun uniffiWarmup(): Thread = Thread({
+    try {
+        val lib = _UniFFILib.INSTANCE
+        {%- if ci.ffi_async_init().is_some() %}
+        UniFFIAsyncCalls.warmUp()
+        {%- endif %}
+        rustCall() { status ->
+            lib.{{ ci.ffi_warmup().name() }}(status)
+        }
+    } catch (e: Throwable) {
+        // Reported by the first call into the component instead, where the app expects it.
+    }
+}, "uniffi-warmup").apply {
+    isDaemon = true
+    start()
+}
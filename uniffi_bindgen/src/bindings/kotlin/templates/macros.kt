@@ -27,6 +27,38 @@
 }
 {%- endmacro %}
 
+{#-
+// Call an `[Async]` function or method, suspending until Rust has completed the call. The handle
+// of the call follows the arguments, and the value that it returns comes back in a buffer. A
+// method is started inside `callWithPointer`, which keeps the object alive until Rust has taken
+// its own reference to it.
+-#}
+{%- macro to_async_ffi_call(func) %}
+    {%- call _async_ffi_call(false, func) %}
+{%- endmacro %}
+
+{%- macro to_async_method_call(meth) %}
+    {%- call _async_ffi_call(true, meth) %}
+{%- endmacro %}
+
+{%- macro _async_ffi_call(is_method, func) -%}
+    uniffiAsyncCall(
+        {%- match func.throws() %}{% when Some with (e) %}{{ e|exception_name }}{% else %}NullCallStatusErrorHandler{% endmatch %},
+        { uniffiHandle ->
+            {% if is_method %}callWithPointer { {% endif -%}
+            {% match func.throws() %}{% when Some with (e) %}rustCallWithError({{ e|exception_name }}){% else %}rustCall(){% endmatch %} { status ->
+                _UniFFILib.INSTANCE.{{ func.ffi_func().name() }}({% if is_method %}it, {% endif %}{% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %}, {% endif %}uniffiHandle, status)
+            }{% if is_method %} }{% endif %}
+        },
+        {%- match func.return_type() %}
+        {%- when Some with (return_type) %}
+        { rbuf -> liftFromRustBuffer(rbuf) { buf -> {{ "buf"|read_var(return_type) }} } }
+        {%- when None %}
+        { rbuf -> RustBuffer.free(rbuf) }
+        {%- endmatch %}
+    )
+{%- endmacro %}
+
 {%- macro _arg_list_ffi_call(func) %}
     {%- for arg in func.arguments() %}
         {{- arg.name()|lower_var(arg) }}
@@ -50,12 +82,57 @@
     {%- endfor %}
 {%- endmacro %}
 
+{#-
+// With `java_interop`, constructors declare the exception that they throw, like methods and
+// functions always do, and get overloads for Java to leave out the arguments with defaults.
+-#}
+{%- macro jvm_constructor_annotations(cons) %}
+    {%- match cons.throws() %}
+    {%- when Some with (throwable) %}
+    @Throws({{ throwable|exception_name }}::class)
+    {%- else %}
+    {%- endmatch %}
+    {%- if cons.arguments()|has_default_values %}
+    @JvmOverloads
+    {%- endif %}
+{%- endmacro %}
+
 {% macro arg_list_protocol(func) %}
     {%- for arg in func.arguments() -%}
         {{ arg.name()|var_name }}: {{ arg|type_name -}}
         {%- if !loop.last %}, {% endif -%}
     {%- endfor %}
 {%- endmacro %}
+{#-
+// The arguments of a method, passed on as they are to a call of another one.
+-#}
+{%- macro arg_names(func) %}
+    {%- for arg in func.arguments() -%}
+        {{ arg.name()|var_name }}
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+{%- endmacro %}
+
+{#-
+// The return type of a method or function, as it follows the argument list in a declaration.
+// With `errors_as_values`, one that throws returns a `Result` of it instead.
+-#}
+{%- macro return_type(func) %}
+    {%- if self.errors_as_values() && func.throws().is_some() %}
+    {%- match func.return_type() %}{% when Some with (return_type) %}: Result<{{ return_type|type_name }}>{% when None %}: Result<Unit>{% endmatch %}
+    {%- else %}
+    {%- match func.return_type() %}{% when Some with (return_type) %}: {{ return_type|type_name }}{% when None %}{% endmatch %}
+    {%- endif %}
+{%- endmacro %}
+
+{#-
+// Starts the block that turns the exception a function or method throws into a failed `Result`,
+// for the functions and methods that return one with `errors_as_values`.
+-#}
+{%- macro result_of(func) %}
+    {%- match func.throws() %}{% when Some with (e) %}uniffiResultOf({{ e|exception_name }}::class){% when None %}{% endmatch %}
+{%- endmacro %}
+
 {#-
 // Arglist as used in the _UniFFILib function declations.
 // Note unfiltered name but ffi_type_name filters.
@@ -80,3 +157,10 @@ fun {{ func.name() }}(
     {%- call arg_list_ffi_decl(func) %}
 ){%- match func.return_type() -%}{%- when Some with (type_) %}: {{ type_|ffi_type_name }}{% when None %}: Unit{% endmatch %}
 {% endmacro %}
+
+{#-
+// The type of the value that a `[Batchable]` function's `BatchResult` holds.
+-#}
+{%- macro batch_result_type(func) -%}
+{%- match func.return_type() %}{% when Some with (return_type) %}{{ return_type|type_name }}{% when None %}Unit{% endmatch -%}
+{%- endmacro -%}
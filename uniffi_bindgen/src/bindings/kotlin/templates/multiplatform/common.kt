@@ -0,0 +1,96 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+//
+// The declarations of the `{{ ci.namespace() }}` component that are shared between the platforms
+// of a Kotlin Multiplatform module. The functions and objects are implemented by the `actual`
+// declarations that were generated alongside this file, in `{{ ci.namespace() }}.native.kt`.
+//
+// Generated by uniffi-bindgen {{ self.bindgen_version() }}.
+
+@file:Suppress("NAME_SHADOWING")
+
+package {{ config.package_name() }}
+
+{% import "multiplatform/macros.kt" as mpp %}
+
+class InternalException(message: String) : Exception(message)
+
+// Thrown when Rust is passed a string that isn't valid UTF-8, which these bindings never do
+// themselves, but which Rust reports as such rather than panicking.
+class InvalidStringException(message: String) : Exception(message)
+{%- for e in ci.iter_enum_definitions() %}
+
+enum class {{ e|type_name }} {
+    {%- for variant in e.variants() %}
+    {{ variant.name()|enum_variant }}{% if loop.last %};{% else %},{% endif %}
+    {%- endfor %}
+}
+{%- endfor %}
+
+{%- for e in ci.iter_error_definitions() %}
+
+sealed class {{ e|type_name }}(message: String) : Exception(message) {
+    {%- for variant in e.variants() %}
+    class {{ variant.name()|exception_name }}(message: String) : {{ e|type_name }}(message)
+    {%- endfor %}
+}
+{%- endfor %}
+
+{%- for rec in ci.iter_record_definitions() %}
+
+data class {{ rec|type_name }}(
+    {%- for field in rec.fields() %}
+    var {{ field.name()|var_name }}: {{ field|type_name -}}
+    {%- match field.default_value() %}
+    {%- when Some with(literal) %} = {{ literal|render_literal(field) }}
+    {%- else %}
+    {%- endmatch %}{% if !loop.last %},{% endif %}
+    {%- endfor %}
+){% if rec.has_sensitive_fields() %} {
+    // `[Sensitive]` fields are left out, so that they don't end up in logs.
+    override fun toString(): String =
+        "{{ rec|type_name }}({% for field in rec.fields() %}{{ field.name()|var_name }}={% if field.sensitive() %}***{% else %}${{ "{" }}this.{{ field.name()|var_name }}{{ "}" }}{% endif %}{% if !loop.last %}, {% endif %}{% endfor %})"
+}{% endif %}
+{%- endfor %}
+
+{%- for func in ci.iter_function_definitions() %}
+{% if func.throws().is_some() %}
+{% call mpp::throws_annotation(func) %}
+{%- endif %}
+expect fun {{ func.name()|fn_name }}({% call mpp::arg_list_decl(func) %}){% call mpp::return_type(func) %}
+{%- endfor %}
+
+{%- for obj in ci.iter_object_definitions() %}
+{%- let class = obj.name()|class_name %}
+
+expect class {{ class }} {
+    {%- match obj.primary_constructor() %}
+    {%- when Some with (cons) %}
+    {%- if cons.throws().is_some() %}
+    {% call mpp::throws_annotation(cons) %}
+    {%- endif %}
+    constructor({% call mpp::arg_list_decl(cons) %})
+    {%- when None %}
+    {%- endmatch %}
+    {%- for meth in obj.methods() %}
+    {%- if meth.throws().is_some() %}
+    {% call mpp::throws_annotation(meth) %}
+    {%- endif %}
+    fun {{ meth.name()|fn_name }}({% call mpp::arg_list_decl(meth) %}){% call mpp::return_type(meth) %}
+    {%- endfor %}
+
+    // Free the Rust object, which mustn't be used afterwards.
+    fun destroy()
+    {%- if !obj.alternate_constructors().is_empty() %}
+
+    companion object {
+        {%- for cons in obj.alternate_constructors() %}
+        {%- if cons.throws().is_some() %}
+        {% call mpp::throws_annotation(cons) %}
+        {%- endif %}
+        fun {{ cons.name()|fn_name }}({% call mpp::arg_list_decl(cons) %}): {{ class }}
+        {%- endfor %}
+    }
+    {%- endif %}
+}
+{%- endfor %}
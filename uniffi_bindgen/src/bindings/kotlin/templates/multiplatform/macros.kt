@@ -0,0 +1,78 @@
+{#
+// Macros for the Kotlin Multiplatform bindings. The arguments are named the same in the
+// `expect` and the `actual` declarations, which Kotlin requires.
+#}
+
+{%- macro arg_list_decl(func) %}
+    {%- for arg in func.arguments() -%}
+        {{ arg.name()|var_name }}: {{ arg|type_name -}}
+        {%- match arg.default_value() %}
+        {%- when Some with(literal) %} = {{ literal|render_literal(arg) }}
+        {%- else %}
+        {%- endmatch %}
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+{%- endmacro %}
+
+{#-
+// The `actual` declarations can't repeat the default values of the arguments.
+-#}
+{%- macro arg_list_impl(func) %}
+    {%- for arg in func.arguments() -%}
+        {{ arg.name()|var_name }}: {{ arg|type_name -}}
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+{%- endmacro %}
+
+{%- macro return_type(func) %}
+    {%- match func.return_type() %}
+    {%- when Some with (return_type) %}: {{ return_type|type_name }}
+    {%- when None %}
+    {%- endmatch %}
+{%- endmacro %}
+
+{%- macro throws_annotation(func) %}
+    {%- match func.throws() %}
+    {%- when Some with (e) %}@Throws({{ e|exception_name }}::class)
+    {%- when None %}
+    {%- endmatch %}
+{%- endmacro %}
+
+{%- macro error_lifter(func) %}
+    {%- match func.throws() %}
+    {%- when Some with (e) %}::uniffiLiftError{{ e|exception_name }}
+    {%- when None %}::uniffiUnexpectedError
+    {%- endmatch %}
+{%- endmacro %}
+
+{%- macro _arg_list_ffi_call(func) %}
+    {%- for arg in func.arguments() %}
+        {{- arg.name()|var_name|lower_kt(arg.type_()) }}, {# space #}
+    {%- endfor %}
+{%- endmacro -%}
+
+{%- macro to_ffi_call(func) -%}
+uniffiRustCall({% call error_lifter(func) %}) { status ->
+        {{ func.ffi_func().name() }}({% call _arg_list_ffi_call(func) %}status)
+    }
+{%- endmacro -%}
+
+{%- macro to_ffi_call_with_prefix(prefix, func) -%}
+uniffiRustCall({% call error_lifter(func) %}) { status ->
+        {{ func.ffi_func().name() }}({{ prefix }}, {% call _arg_list_ffi_call(func) %}status)
+    }
+{%- endmacro -%}
+
+{#-
+// The functions that lower values into, and lift them out of, a `RustBuffer`, for
+// the types that are passed across the FFI serialized into one.
+-#}
+{%- macro lift_lower_buffer(type_) %}
+{%- let name = type_|helper_name_kt %}
+
+internal fun uniffiLift{{ name }}(rbuf: CValue<RustBuffer>): {{ type_|type_name }} =
+    uniffiLiftFromBuffer(rbuf) { reader -> uniffiRead{{ name }}(reader) }
+
+internal fun uniffiLower{{ name }}(value: {{ type_|type_name }}): CValue<RustBuffer> =
+    uniffiLowerIntoBuffer { writer -> uniffiWrite{{ name }}(writer, value) }
+{%- endmacro %}
@@ -0,0 +1,385 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+//
+// The Kotlin/Native implementations of the `expect` declarations for the `{{ ci.namespace() }}`
+// component, which call the Rust library through the declarations that cinterop generates from
+// `{{ ci.namespace() }}.def`, in `{{ config.package_name() }}.cinterop`.
+//
+// Like the JVM bindings, these bundle the helpers that pass values across the FFI, whose details
+// have to match the Rust scaffolding generated by the same version of `uniffi`.
+//
+// Generated by uniffi-bindgen {{ self.bindgen_version() }}.
+
+@file:Suppress("NAME_SHADOWING")
+@file:OptIn(kotlinx.cinterop.ExperimentalForeignApi::class)
+
+package {{ config.package_name() }}
+
+{% import "multiplatform/macros.kt" as mpp %}
+
+import kotlinx.cinterop.*
+import {{ config.package_name() }}.cinterop.*
+
+// Values in a `RustBuffer` are serialized in big-endian order, as the JVM's `ByteBuffer` does.
+internal class UniffiReader(private val bytes: ByteArray) {
+    private var position = 0
+
+    fun hasRemaining(): Boolean = position < bytes.size
+
+    fun readByte(): Byte = bytes[position++]
+
+    fun readShort(): Short = ((readByte().toInt() and 0xff shl 8) or (readByte().toInt() and 0xff)).toShort()
+
+    fun readInt(): Int {
+        var value = 0
+        repeat(4) { value = (value shl 8) or (readByte().toInt() and 0xff) }
+        return value
+    }
+
+    fun readLong(): Long {
+        var value = 0L
+        repeat(8) { value = (value shl 8) or (readByte().toLong() and 0xff) }
+        return value
+    }
+
+    fun readBytes(len: Int): ByteArray {
+        val value = bytes.copyOfRange(position, position + len)
+        position += len
+        return value
+    }
+}
+
+internal class UniffiWriter {
+    private var bytes = ByteArray(64)
+    private var size = 0
+
+    fun writeByte(value: Byte) {
+        if (size == bytes.size) {
+            bytes = bytes.copyOf(size * 2)
+        }
+        bytes[size++] = value
+    }
+
+    fun writeShort(value: Short) {
+        writeByte((value.toInt() shr 8).toByte())
+        writeByte(value.toByte())
+    }
+
+    fun writeInt(value: Int) {
+        for (shift in 24 downTo 0 step 8) {
+            writeByte((value shr shift).toByte())
+        }
+    }
+
+    fun writeLong(value: Long) {
+        for (shift in 56 downTo 0 step 8) {
+            writeByte((value shr shift).toByte())
+        }
+    }
+
+    fun writeBytes(value: ByteArray) {
+        value.forEach { writeByte(it) }
+    }
+
+    fun toByteArray(): ByteArray = bytes.copyOf(size)
+}
+
+// Take the contents of a `RustBuffer` that Rust handed over, and free it.
+internal fun uniffiLiftBytes(rbuf: CValue<RustBuffer>): ByteArray {
+    val bytes = rbuf.useContents { data?.readBytes(len) ?: ByteArray(0) }
+    uniffiRustCall(::uniffiUnexpectedError) { status ->
+        {{ ci.ffi_rustbuffer_free().name() }}(rbuf, status)
+    }
+    return bytes
+}
+
+// Copy bytes into a new `RustBuffer`, for Rust to take over.
+internal fun uniffiLowerBytes(bytes: ByteArray): CValue<RustBuffer> = bytes.usePinned { pinned ->
+    val foreign = cValue<ForeignBytes> {
+        len = bytes.size
+        data = if (bytes.isEmpty()) null else pinned.addressOf(0).reinterpret()
+    }
+    uniffiRustCall(::uniffiUnexpectedError) { status ->
+        {{ ci.ffi_rustbuffer_from_bytes().name() }}(foreign, status)
+    }
+}
+
+internal fun <T> uniffiLiftFromBuffer(rbuf: CValue<RustBuffer>, read: (UniffiReader) -> T): T {
+    val reader = UniffiReader(uniffiLiftBytes(rbuf))
+    val value = read(reader)
+    if (reader.hasRemaining()) {
+        throw RuntimeException("junk remaining in buffer after lifting, something is very wrong!!")
+    }
+    return value
+}
+
+internal fun uniffiLowerIntoBuffer(write: (UniffiWriter) -> Unit): CValue<RustBuffer> {
+    val writer = UniffiWriter()
+    write(writer)
+    return uniffiLowerBytes(writer.toByteArray())
+}
+
+// The error that a call which isn't expected to fail with one throws, if it does.
+internal fun uniffiUnexpectedError(@Suppress("UNUSED_PARAMETER") bytes: ByteArray): Exception =
+    InternalException("Unexpected CALL_ERROR")
+
+// Call a Rust function, throwing the error that `liftError` lifts from the call status if it
+// returned one, an `InternalException` if it panicked, or an `InvalidStringException` if it was
+// passed a string that isn't valid UTF-8.
+internal inline fun <T> uniffiRustCall(liftError: (ByteArray) -> Exception, callback: (CPointer<RustCallStatus>) -> T): T = memScoped {
+    val status = alloc<RustCallStatus>()
+    status.code = 0
+    val result = callback(status.ptr)
+    when (status.code.toInt()) {
+        0 -> result
+        1 -> throw liftError(uniffiLiftBytes(status.errorBuf.readValue()))
+        2 -> {
+            // When the Rust code sees a panic, it tries to construct a `RustBuffer` with the
+            // message, but if that code panics, then it just sends back an empty buffer.
+            val message = uniffiLiftBytes(status.errorBuf.readValue()).decodeToString()
+            throw InternalException(if (message.isEmpty()) "Rust panic" else message)
+        }
+        3 -> throw InvalidStringException(uniffiLiftBytes(status.errorBuf.readValue()).decodeToString())
+        else -> throw InternalException("Unknown rust call status: ${status.code}")
+    }
+}
+
+// Numbers are passed to the C functions as they are, and only need reading and writing.
+
+internal fun uniffiReadInt8(reader: UniffiReader): Byte = reader.readByte()
+
+internal fun uniffiWriteInt8(writer: UniffiWriter, value: Byte) = writer.writeByte(value)
+
+internal fun uniffiReadUInt8(reader: UniffiReader): UByte = reader.readByte().toUByte()
+
+internal fun uniffiWriteUInt8(writer: UniffiWriter, value: UByte) = writer.writeByte(value.toByte())
+
+internal fun uniffiReadInt16(reader: UniffiReader): Short = reader.readShort()
+
+internal fun uniffiWriteInt16(writer: UniffiWriter, value: Short) = writer.writeShort(value)
+
+internal fun uniffiReadUInt16(reader: UniffiReader): UShort = reader.readShort().toUShort()
+
+internal fun uniffiWriteUInt16(writer: UniffiWriter, value: UShort) = writer.writeShort(value.toShort())
+
+internal fun uniffiReadInt32(reader: UniffiReader): Int = reader.readInt()
+
+internal fun uniffiWriteInt32(writer: UniffiWriter, value: Int) = writer.writeInt(value)
+
+internal fun uniffiReadUInt32(reader: UniffiReader): UInt = reader.readInt().toUInt()
+
+internal fun uniffiWriteUInt32(writer: UniffiWriter, value: UInt) = writer.writeInt(value.toInt())
+
+internal fun uniffiReadInt64(reader: UniffiReader): Long = reader.readLong()
+
+internal fun uniffiWriteInt64(writer: UniffiWriter, value: Long) = writer.writeLong(value)
+
+internal fun uniffiReadUInt64(reader: UniffiReader): ULong = reader.readLong().toULong()
+
+internal fun uniffiWriteUInt64(writer: UniffiWriter, value: ULong) = writer.writeLong(value.toLong())
+
+internal fun uniffiReadFloat32(reader: UniffiReader): Float = Float.fromBits(reader.readInt())
+
+internal fun uniffiWriteFloat32(writer: UniffiWriter, value: Float) = writer.writeInt(value.toRawBits())
+
+internal fun uniffiReadFloat64(reader: UniffiReader): Double = Double.fromBits(reader.readLong())
+
+internal fun uniffiWriteFloat64(writer: UniffiWriter, value: Double) = writer.writeLong(value.toRawBits())
+
+// Booleans are passed as an `int8_t`.
+
+internal fun uniffiLiftBool(value: Byte): Boolean = value.toInt() != 0
+
+internal fun uniffiLowerBool(value: Boolean): Byte = if (value) 1 else 0
+
+internal fun uniffiReadBool(reader: UniffiReader): Boolean = uniffiLiftBool(reader.readByte())
+
+internal fun uniffiWriteBool(writer: UniffiWriter, value: Boolean) = writer.writeByte(uniffiLowerBool(value))
+
+// Strings are passed as a `RustBuffer` of their UTF-8 bytes, and serialized with their length.
+
+internal fun uniffiLiftString(rbuf: CValue<RustBuffer>): String = uniffiLiftBytes(rbuf).decodeToString()
+
+internal fun uniffiLowerString(value: String): CValue<RustBuffer> = uniffiLowerBytes(value.encodeToByteArray())
+
+internal fun uniffiReadString(reader: UniffiReader): String = reader.readBytes(reader.readInt()).decodeToString()
+
+internal fun uniffiWriteString(writer: UniffiWriter, value: String) {
+    val bytes = value.encodeToByteArray()
+    writer.writeInt(bytes.size)
+    writer.writeBytes(bytes)
+}
+
+{%- for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::Object with (name) %}
+{%- let class = name|class_name %}
+
+// `{{ class }}` objects are passed as the pointer to the Rust object, which they own a reference to.
+
+internal fun uniffiLiftType{{ class }}(value: COpaquePointer?): {{ class }} = {{ class }}(value)
+
+internal fun uniffiLowerType{{ class }}(value: {{ class }}): COpaquePointer? = value.pointer
+
+internal fun uniffiReadType{{ class }}(reader: UniffiReader): {{ class }} = {{ class }}(reader.readLong().toCPointer())
+
+internal fun uniffiWriteType{{ class }}(writer: UniffiWriter, value: {{ class }}) = writer.writeLong(value.pointer.toLong())
+{%- when Type::Optional with (inner) %}
+
+internal fun uniffiRead{{ type_|helper_name_kt }}(reader: UniffiReader): {{ type_|type_name }} =
+    if (reader.readByte().toInt() == 0) null else {{ inner|read_kt }}
+
+internal fun uniffiWrite{{ type_|helper_name_kt }}(writer: UniffiWriter, value: {{ type_|type_name }}) {
+    if (value == null) {
+        writer.writeByte(0)
+    } else {
+        writer.writeByte(1)
+        {{ "value"|write_kt(inner) }}
+    }
+}
+{%- call mpp::lift_lower_buffer(type_) %}
+{%- when Type::Sequence with (inner) %}
+
+internal fun uniffiRead{{ type_|helper_name_kt }}(reader: UniffiReader): {{ type_|type_name }} =
+    List(reader.readInt()) { {{ inner|read_kt }} }
+
+internal fun uniffiWrite{{ type_|helper_name_kt }}(writer: UniffiWriter, value: {{ type_|type_name }}) {
+    writer.writeInt(value.size)
+    value.forEach { {{ "it"|write_kt(inner) }} }
+}
+{%- call mpp::lift_lower_buffer(type_) %}
+{%- when Type::Map with (key_type, inner) %}
+
+internal fun uniffiRead{{ type_|helper_name_kt }}(reader: UniffiReader): {{ type_|type_name }} {
+    val len = reader.readInt()
+    val items = LinkedHashMap<{{ key_type|type_name }}, {{ inner|type_name }}>(len)
+    repeat(len) {
+        val key = {{ key_type|read_kt }}
+        items[key] = {{ inner|read_kt }}
+    }
+    return items
+}
+
+internal fun uniffiWrite{{ type_|helper_name_kt }}(writer: UniffiWriter, value: {{ type_|type_name }}) {
+    writer.writeInt(value.size)
+    value.forEach { (key, item) ->
+        {{ "key"|write_kt(key_type) }}
+        {{ "item"|write_kt(inner) }}
+    }
+}
+{%- call mpp::lift_lower_buffer(type_) %}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+{%- for e in ci.iter_enum_definitions() %}
+{%- let type_ = e.type_() %}
+
+internal fun uniffiRead{{ type_|helper_name_kt }}(reader: UniffiReader): {{ e|type_name }} =
+    try {
+        {{ e|type_name }}.values()[reader.readInt() - 1]
+    } catch (e: IndexOutOfBoundsException) {
+        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+    }
+
+internal fun uniffiWrite{{ type_|helper_name_kt }}(writer: UniffiWriter, value: {{ e|type_name }}) = writer.writeInt(value.ordinal + 1)
+{%- call mpp::lift_lower_buffer(type_) %}
+{%- endfor %}
+
+{%- for e in ci.iter_error_definitions() %}
+
+// Flat errors carry the variant, then the Rust error's message.
+internal fun uniffiLiftError{{ e|type_name }}(bytes: ByteArray): {{ e|type_name }} {
+    val reader = UniffiReader(bytes)
+    return when (reader.readInt()) {
+        {%- for variant in e.variants() %}
+        {{ loop.index }} -> {{ e|type_name }}.{{ variant.name()|exception_name }}(uniffiReadString(reader))
+        {%- endfor %}
+        else -> throw RuntimeException("invalid error enum value, something is very wrong!!")
+    }
+}
+{%- endfor %}
+
+{%- for rec in ci.iter_record_definitions() %}
+{%- let type_ = rec.type_() %}
+
+internal fun uniffiRead{{ type_|helper_name_kt }}(reader: UniffiReader): {{ rec|type_name }} {
+    return {{ rec|type_name }}(
+        {%- for field in rec.fields() %}
+        {{ field.type_()|read_kt }}{% if !loop.last %},{% endif %}
+        {%- endfor %}
+    )
+}
+
+internal fun uniffiWrite{{ type_|helper_name_kt }}(writer: UniffiWriter, value: {{ rec|type_name }}) {
+    {%- for field in rec.fields() %}
+    {{ field.name()|member_kt("value")|write_kt(field.type_()) }}
+    {%- endfor %}
+}
+{%- call mpp::lift_lower_buffer(type_) %}
+{%- endfor %}
+
+{%- for func in ci.iter_function_definitions() %}
+
+actual fun {{ func.name()|fn_name }}({% call mpp::arg_list_impl(func) %}){% call mpp::return_type(func) %} {
+    {%- match func.return_type() %}
+    {%- when Some with (return_type) %}
+    val ret = {% call mpp::to_ffi_call(func) %}
+    return {{ "ret"|lift_kt(return_type) }}
+    {%- when None %}
+    {% call mpp::to_ffi_call(func) %}
+    {%- endmatch %}
+}
+{%- endfor %}
+
+{%- for obj in ci.iter_object_definitions() %}
+{%- let class = obj.name()|class_name %}
+
+actual class {{ class }} internal constructor(
+    internal val pointer: COpaquePointer?
+) {
+    {%- match obj.primary_constructor() %}
+    {%- when Some with (cons) %}
+
+    actual constructor({% call mpp::arg_list_impl(cons) %}) : this(
+        {% call mpp::to_ffi_call(cons) %}
+    )
+    {%- when None %}
+    {%- endmatch %}
+
+    private var destroyed = false
+    {%- for meth in obj.methods() %}
+
+    actual fun {{ meth.name()|fn_name }}({% call mpp::arg_list_impl(meth) %}){% call mpp::return_type(meth) %} {
+        {%- match meth.return_type() %}
+        {%- when Some with (return_type) %}
+        val ret = {% call mpp::to_ffi_call_with_prefix("pointer", meth) %}
+        return {{ "ret"|lift_kt(return_type) }}
+        {%- when None %}
+        {% call mpp::to_ffi_call_with_prefix("pointer", meth) %}
+        {%- endmatch %}
+    }
+    {%- endfor %}
+
+    // There's no finalizer on Kotlin/Native, so the object has to be destroyed explicitly. It
+    // mustn't be destroyed while another thread is calling one of its methods.
+    actual fun destroy() {
+        if (!destroyed) {
+            destroyed = true
+            uniffiRustCall(::uniffiUnexpectedError) { status ->
+                {{ obj.ffi_object_free().name() }}(pointer, status)
+            }
+        }
+    }
+    {%- if !obj.alternate_constructors().is_empty() %}
+
+    actual companion object {
+        {%- for cons in obj.alternate_constructors() %}
+
+        actual fun {{ cons.name()|fn_name }}({% call mpp::arg_list_impl(cons) %}): {{ class }} =
+            {{ class }}({% call mpp::to_ffi_call(cons) %})
+        {%- endfor %}
+    }
+    {%- endif %}
+}
+{%- endfor %}
@@ -37,6 +37,14 @@ import {{ imported_class }}
 // and the FFI Function declarations in a com.sun.jna.Library.
 {% include "NamespaceLibraryTemplate.kt" %}
 
+{% include "ComponentInfoTemplate.kt" %}
+
+{% include "MemoryPressureTemplate.kt" %}
+
+{% include "WarmupTemplate.kt" %}
+
+{% include "BatchTemplate.kt" %}
+
 // Public interface members begin here.
 {% for code in self.declaration_code() %}
 {{ code }}
@@ -12,12 +12,16 @@ use serde::{Deserialize, Serialize};
 use std::convert::{TryFrom, TryInto};
 use std::path::Path;
 
-use crate::interface::ComponentInterface;
+use crate::backend::renames::RenamesFile;
+use crate::interface::{Capabilities, ComponentInterface, Feature};
 use crate::MergeWith;
 
+pub mod csharp;
+pub mod go;
 pub mod kotlin;
 pub mod python;
 pub mod ruby;
+pub mod rust;
 pub mod swift;
 
 /// Enumeration of all foreign language targets currently supported by this crate.
@@ -26,12 +30,59 @@ pub mod swift;
 /// on the provided `TargetLanguage`. For convenience of calling code we also provide
 /// a few `TryFrom` implementations to help guess the correct target language from
 /// e.g. a file extension of command-line argument.
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum TargetLanguage {
     Kotlin,
     Swift,
     Python,
     Ruby,
+    CSharp,
+    Go,
+    Rust,
+}
+
+/// The optional features that a component's tests can declare they depend on.
+///
+/// Not every backend supports every feature of the `ComponentInterface` yet, so
+/// the test harness uses these to skip test scripts that a backend can't run.
+pub const OPTIONAL_FEATURES: &[&str] = &[
+    "async_functions",
+    "callback_interfaces",
+    "external_types",
+    "wrapped_types",
+];
+
+impl TargetLanguage {
+    /// The features of the `ComponentInterface` that the backend for this language can generate.
+    pub fn capabilities(&self) -> Capabilities {
+        match self {
+            TargetLanguage::Kotlin | TargetLanguage::Swift => Capabilities::all(),
+            TargetLanguage::Python => python::capabilities(),
+            TargetLanguage::Ruby => ruby::capabilities(),
+            TargetLanguage::CSharp => csharp::gen_csharp::capabilities(),
+            TargetLanguage::Go => go::gen_go::capabilities(),
+            TargetLanguage::Rust => rust::gen_rust::capabilities(),
+        }
+    }
+
+    /// Check whether the backend for this language supports the named optional feature,
+    /// according to its [`TargetLanguage::capabilities`].
+    ///
+    /// Errors out if the feature isn't one of the known [`OPTIONAL_FEATURES`].
+    pub fn supports_feature(&self, feature: &str) -> Result<bool> {
+        let feature = match feature {
+            "async_functions" => Feature::Async,
+            "callback_interfaces" => Feature::CallbackInterfaces,
+            "external_types" => Feature::ExternalTypes,
+            "wrapped_types" => Feature::CustomTypes,
+            _ => bail!(
+                "Unknown feature: \"{}\" (expected one of: {})",
+                feature,
+                OPTIONAL_FEATURES.join(", ")
+            ),
+        };
+        Ok(self.capabilities().supports(feature))
+    }
 }
 
 impl TryFrom<&str> for TargetLanguage {
@@ -42,6 +93,9 @@ impl TryFrom<&str> for TargetLanguage {
             "swift" => TargetLanguage::Swift,
             "python" | "py" => TargetLanguage::Python,
             "ruby" | "rb" => TargetLanguage::Ruby,
+            "csharp" | "cs" | "c#" => TargetLanguage::CSharp,
+            "go" | "golang" => TargetLanguage::Go,
+            "rust" | "rs" => TargetLanguage::Rust,
             _ => bail!("Unknown or unsupported target language: \"{}\"", value),
         })
     }
@@ -74,6 +128,12 @@ pub struct Config {
     python: python::Config,
     #[serde(default)]
     ruby: ruby::Config,
+    #[serde(default)]
+    csharp: csharp::Config,
+    #[serde(default)]
+    go: go::Config,
+    #[serde(default)]
+    rust: rust::Config,
 }
 
 impl From<&ComponentInterface> for Config {
@@ -83,6 +143,33 @@ impl From<&ComponentInterface> for Config {
             swift: ci.into(),
             python: ci.into(),
             ruby: ci.into(),
+            csharp: ci.into(),
+            go: ci.into(),
+            rust: ci.into(),
+        }
+    }
+}
+
+impl Config {
+    /// Fill in the renames from a `renames.toml` file, for any identifiers that the
+    /// bindings config doesn't already rename.
+    pub fn with_renames_from(&self, file: &RenamesFile) -> Self {
+        Config {
+            kotlin: self.kotlin.with_renames_from(&file.kotlin),
+            swift: self.swift.with_renames_from(&file.swift),
+            python: self.python.with_renames_from(&file.python),
+            ruby: self.ruby.clone(),
+            csharp: self.csharp.with_renames_from(&file.csharp),
+            go: self.go.clone(),
+            rust: self.rust.clone(),
+        }
+    }
+
+    /// Generate the Python bindings with the given backend, whatever the config says.
+    pub fn with_python_backend(&self, backend: python::PythonBackend) -> Self {
+        Config {
+            python: self.python.with_backend(backend),
+            ..self.clone()
         }
     }
 }
@@ -94,6 +181,9 @@ impl MergeWith for Config {
             swift: self.swift.merge_with(&other.swift),
             python: self.python.merge_with(&other.python),
             ruby: self.ruby.merge_with(&other.ruby),
+            csharp: self.csharp.merge_with(&other.csharp),
+            go: self.go.merge_with(&other.go),
+            rust: self.rust.merge_with(&other.rust),
         }
     }
 }
@@ -110,17 +200,36 @@ where
     P: AsRef<Path>,
 {
     let out_dir = out_dir.as_ref();
+    // Each language's bindings only get the part of the component that its filter keeps.
     match language {
         TargetLanguage::Kotlin => {
-            kotlin::write_bindings(&config.kotlin, ci, out_dir, try_format_code)?
+            let ci = config.kotlin.filter().apply(ci)?;
+            kotlin::write_bindings(&config.kotlin, &ci, out_dir, try_format_code)?
         }
         TargetLanguage::Swift => {
-            swift::write_bindings(&config.swift, ci, out_dir, try_format_code)?
+            let ci = config.swift.filter().apply(ci)?;
+            swift::write_bindings(&config.swift, &ci, out_dir, try_format_code)?
         }
         TargetLanguage::Python => {
-            python::write_bindings(&config.python, ci, out_dir, try_format_code)?
+            let ci = config.python.filter().apply(ci)?;
+            python::write_bindings(&config.python, &ci, out_dir, try_format_code)?
+        }
+        TargetLanguage::Ruby => {
+            let ci = config.ruby.filter().apply(ci)?;
+            ruby::write_bindings(&config.ruby, &ci, out_dir, try_format_code)?
+        }
+        TargetLanguage::CSharp => {
+            let ci = config.csharp.filter().apply(ci)?;
+            csharp::write_bindings(&config.csharp, &ci, out_dir, try_format_code)?
+        }
+        TargetLanguage::Go => {
+            let ci = config.go.filter().apply(ci)?;
+            go::write_bindings(&config.go, &ci, out_dir, try_format_code)?
+        }
+        TargetLanguage::Rust => {
+            let ci = config.rust.filter().apply(ci)?;
+            rust::write_bindings(&config.rust, &ci, out_dir, try_format_code)?
         }
-        TargetLanguage::Ruby => ruby::write_bindings(&config.ruby, ci, out_dir, try_format_code)?,
     }
     Ok(())
 }
@@ -141,6 +250,9 @@ where
         TargetLanguage::Swift => swift::compile_bindings(&config.swift, ci, out_dir)?,
         TargetLanguage::Python => (),
         TargetLanguage::Ruby => (),
+        TargetLanguage::CSharp => (),
+        TargetLanguage::Go => (),
+        TargetLanguage::Rust => (),
     }
     Ok(())
 }
@@ -158,6 +270,87 @@ where
         TargetLanguage::Swift => swift::run_script(out_dir, script_file)?,
         TargetLanguage::Python => python::run_script(out_dir, script_file)?,
         TargetLanguage::Ruby => ruby::run_script(out_dir, script_file)?,
+        TargetLanguage::CSharp => csharp::run_script(out_dir, script_file)?,
+        TargetLanguage::Go => go::run_script(out_dir, script_file)?,
+        TargetLanguage::Rust => rust::run_script(out_dir, script_file)?,
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::interface::FEATURE_EXAMPLES;
+
+    // The backends with a list of what they can't generate, by name.
+    fn limited_backends() -> Vec<(&'static str, Capabilities)> {
+        vec![
+            (
+                "Kotlin Multiplatform",
+                kotlin::gen_kotlin::multiplatform::capabilities(),
+            ),
+            ("Python", python::capabilities()),
+            ("PyO3", python::gen_pyo3::capabilities()),
+            ("Ruby", ruby::capabilities()),
+            ("C#", csharp::gen_csharp::capabilities()),
+            ("Go", go::gen_go::capabilities()),
+            ("Rust", rust::gen_rust::capabilities()),
+            ("WebAssembly", crate::scaffolding::wasm::capabilities()),
+        ]
+    }
+
+    #[test]
+    fn test_backends_report_unsupported_features() {
+        for (feature, udl) in FEATURE_EXAMPLES {
+            let ci = ComponentInterface::from_webidl(udl).unwrap();
+            for (backend, capabilities) in limited_backends() {
+                if !capabilities.supports(*feature) {
+                    assert!(
+                        !ci.unsupported_features(&capabilities).is_empty(),
+                        "{} doesn't support {:?}, but doesn't report it",
+                        backend,
+                        feature
+                    );
+                }
+            }
+        }
+    }
+
+    // A backend that doesn't report anything it can't generate in a component generates it,
+    // or fails with an error, rather than panicking.
+    #[test]
+    fn test_supported_features_are_generated() {
+        for (_, udl) in FEATURE_EXAMPLES {
+            let ci = ComponentInterface::from_webidl(udl).unwrap();
+            if ci.unsupported_features(&python::capabilities()).is_empty() {
+                let _ = python::generate_python_bindings(&python::Config::from(&ci), &ci);
+            }
+            if ci.unsupported_features(&ruby::capabilities()).is_empty() {
+                let _ = ruby::generate_ruby_bindings(&ruby::Config::from(&ci), &ci);
+            }
+        }
+    }
+
+    #[test]
+    fn test_optional_features_follow_capabilities() {
+        assert!(TargetLanguage::Kotlin
+            .supports_feature("callback_interfaces")
+            .unwrap());
+        assert!(TargetLanguage::Python
+            .supports_feature("async_functions")
+            .unwrap());
+        assert!(!TargetLanguage::Python
+            .supports_feature("callback_interfaces")
+            .unwrap());
+        assert!(!TargetLanguage::Ruby
+            .supports_feature("external_types")
+            .unwrap());
+        assert!(TargetLanguage::CSharp
+            .supports_feature("wrapped_types")
+            .unwrap());
+        assert!(!TargetLanguage::Go
+            .supports_feature("wrapped_types")
+            .unwrap());
+        assert!(TargetLanguage::Swift.supports_feature("unknown").is_err());
+    }
+}
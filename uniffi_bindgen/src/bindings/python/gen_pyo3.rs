@@ -0,0 +1,293 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # PyO3 extension modules
+//!
+//! With `--python-backend=pyo3`, or `backend = "pyo3"` in `[bindings.python]`, the Python
+//! bindings are a PyO3 extension module written in Rust, rather than a Python module that
+//! uses `ctypes` to call the component's cdylib. The module is `include!`d into the
+//! component's crate after the scaffolding, and calls the Rust implementation directly,
+//! without lowering the arguments into a `RustBuffer` and lifting them again, so calls are
+//! much cheaper, and there's no separate `.py` file or library to package.
+//!
+//! It only supports part of what the ctypes bindings do: functions and objects, whose
+//! arguments and return values are primitives, strings, and optionals, sequences and maps
+//! of those, or objects, along with flat errors. Generating it for a component that uses
+//! anything else fails, listing what isn't supported.
+
+use anyhow::{bail, Result};
+use askama::Template;
+
+use super::gen_python::Config;
+use crate::backend::{custom_types, renames};
+use crate::interface::*;
+use crate::lockfile::type_decl;
+
+#[derive(Template)]
+#[template(syntax = "rs", escape = "none", path = "Pyo3Module.rs")]
+pub struct Pyo3Module<'a> {
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> Pyo3Module<'a> {
+    pub fn new(ci: &'a ComponentInterface) -> Self {
+        Self { ci }
+    }
+}
+
+// Whether PyO3 can convert values of this type as it is. Objects are wrapped in a class of
+// their own, so they're only supported as arguments and return values, not inside others.
+fn is_convertible(type_: &Type) -> bool {
+    match type_ {
+        Type::UInt8
+        | Type::Int8
+        | Type::UInt16
+        | Type::Int16
+        | Type::UInt32
+        | Type::Int32
+        | Type::UInt64
+        | Type::Int64
+        | Type::Float32
+        | Type::Float64
+        | Type::Boolean
+        | Type::String => true,
+        Type::Optional(t) | Type::Sequence(t) => is_convertible(t),
+        Type::Map(k, v) => is_convertible(k) && is_convertible(v),
+        _ => false,
+    }
+}
+
+fn is_supported(type_: &Type) -> bool {
+    matches!(type_, Type::Object(_)) || is_convertible(type_)
+}
+
+/// What the PyO3 backend can generate, apart from the types that it can't convert.
+pub fn capabilities() -> Capabilities {
+    Capabilities::all().without(&[
+        Feature::NestedNamespaces,
+        Feature::Batchable,
+        Feature::Scoped,
+        Feature::Async,
+        Feature::AlsoAsync,
+        Feature::MutMethods,
+        Feature::Borrowed,
+        Feature::NoCopy,
+        Feature::Paginated,
+        Feature::Clone,
+        Feature::Closeable,
+        Feature::WeakRefs,
+        Feature::ReleaseCallbacks,
+        Feature::OpaqueTokens,
+        Feature::ForeignExtensions,
+        Feature::Observable,
+        Feature::Events,
+        Feature::Services,
+        Feature::Streams,
+        Feature::Traits,
+        Feature::ErrorsWithData,
+        Feature::CallbackInterfaces,
+    ])
+}
+
+// The things in the component that the PyO3 backend doesn't support yet, to report them all
+// at once.
+fn unsupported_features(ci: &ComponentInterface) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut check_callable = |what: String, args: Vec<&Argument>, ret: Option<&Type>| {
+        for arg in args {
+            if !is_supported(&arg.type_()) {
+                problems.push(format!(
+                    "argument `{}` of {}, of type {}",
+                    arg.name(),
+                    what,
+                    type_decl(&arg.type_())
+                ));
+            } else if arg.default_value().is_some() {
+                problems.push(format!("the default value of `{}` in {}", arg.name(), what));
+            }
+        }
+        if let Some(ret) = ret {
+            if !is_supported(ret) {
+                problems.push(format!("the return type of {}, {}", what, type_decl(ret)));
+            }
+        }
+    };
+    for func in ci.iter_function_definitions() {
+        let what = format!("function `{}`", func.qualified_name());
+        check_callable(what, func.arguments(), func.return_type());
+    }
+    for obj in ci.iter_object_definitions() {
+        for cons in obj.constructors() {
+            let what = format!("constructor `{}.{}`", obj.name(), cons.name());
+            check_callable(what, cons.arguments(), None);
+        }
+        for meth in obj.methods() {
+            let what = format!("method `{}.{}`", obj.name(), meth.name());
+            check_callable(what, meth.arguments(), meth.return_type());
+        }
+    }
+    problems.extend(ci.unsupported_features(&capabilities()));
+    problems
+}
+
+/// Generate a PyO3 extension module for the component, as Rust source.
+pub fn generate_pyo3_module(config: &Config, ci: &ComponentInterface) -> Result<String> {
+    let problems = unsupported_features(ci);
+    if !problems.is_empty() {
+        bail!(
+            "The PyO3 Python backend doesn't support:\n  {}",
+            problems.join("\n  ")
+        );
+    }
+    custom_types::validate_custom_types(ci, config.custom_types(), false)?;
+    renames::validate_renames(ci, config.renames())?;
+    renames::with_renames(config.renames(), config.naming(), || {
+        Pyo3Module::new(ci).render()
+    })
+    .map_err(|_| anyhow::anyhow!("failed to render PyO3 module"))
+}
+
+mod filters {
+    use super::*;
+    use crate::bindings::python::gen_python::filters as py;
+    use crate::scaffolding::filters as rs;
+
+    pub use py::{class_name, fn_name, var_name};
+    pub use rs::object_inner_rs;
+
+    /// The Rust name of the class that wraps an object.
+    pub fn pyclass_rs(name: &str) -> Result<String, askama::Error> {
+        Ok(format!("Py{}", name))
+    }
+
+    // The Rust type that a `#[pyfunction]` or `#[pymethods]` method takes for an argument.
+    fn arg_type_pyo3(arg: &Argument) -> Result<String, askama::Error> {
+        match arg.type_() {
+            Type::Object(name) => Ok(format!("PyRef<{}>", pyclass_rs(&name)?)),
+            t => rs::type_rs(&t),
+        }
+    }
+
+    // How to pass an argument on to the function that checks the UDL signature.
+    fn arg_value_pyo3(arg: &Argument) -> Result<String, askama::Error> {
+        let name = var_name(&arg.name())?;
+        Ok(match (arg.type_(), arg.by_ref()) {
+            (Type::Object(_), true) => format!("&{}.0", name),
+            (Type::Object(_), false) => format!("std::sync::Arc::clone(&{}.0)", name),
+            (_, true) => format!("&{}", name),
+            (_, false) => name,
+        })
+    }
+
+    /// The arguments of a `#[pyfunction]` or `#[pymethods]` method, which PyO3 converts from
+    /// Python values, and takes the names of the Python keyword arguments from.
+    pub fn arg_list_pyo3(args: &[&Argument]) -> Result<String, askama::Error> {
+        let args: Result<Vec<String>, askama::Error> = args
+            .iter()
+            .map(|arg| {
+                Ok(format!(
+                    "{}: {}",
+                    var_name(&arg.name())?,
+                    arg_type_pyo3(arg)?
+                ))
+            })
+            .collect();
+        Ok(args?.join(", "))
+    }
+
+    /// The arguments to pass on to the function that checks the UDL signature.
+    pub fn arg_values_pyo3(args: &[&Argument]) -> Result<String, askama::Error> {
+        let values: Result<Vec<String>, askama::Error> =
+            args.iter().map(|arg| arg_value_pyo3(arg)).collect();
+        Ok(values?.join(", "))
+    }
+
+    /// Raise the exception for an error that the Rust implementation returns, if it can.
+    pub fn map_err_pyo3(throws: &Option<&str>) -> Result<String, askama::Error> {
+        Ok(match throws {
+            Some(e) => format!(".map_err(|e| {}::new_err(e.to_string()))?", class_name(e)?),
+            None => "".into(),
+        })
+    }
+
+    /// The Rust type that a `#[pyfunction]` or `#[pymethods]` method returns, inside `PyResult`.
+    pub fn return_type_pyo3(type_: &Option<&Type>) -> Result<String, askama::Error> {
+        match type_ {
+            Some(Type::Object(name)) => pyclass_rs(name),
+            Some(t) => rs::type_rs(t),
+            None => Ok("()".into()),
+        }
+    }
+
+    /// Wrap a value returned by the Rust implementation for PyO3.
+    pub fn return_value_pyo3(type_: &Option<&Type>) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Some(Type::Object(name)) => format!("{}(ret)", pyclass_rs(name)?),
+            _ => "ret".into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pyo3_module() {
+        const UDL: &str = r#"
+            namespace geometry {
+                [Throws=GeometryError]
+                f64 gradient(Line line);
+                sequence<string>? labels(record<DOMString, u32> counts);
+            };
+            [Error]
+            enum GeometryError { "Vertical" };
+            interface Line {
+                constructor(f64 slope);
+                [Name=flat]
+                constructor();
+                Line rotated([ByRef] Line other);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let module = generate_pyo3_module(&Config::from(&ci), &ci).unwrap();
+        assert!(module.contains("#[pyo3(name = \"geometry\")]"));
+        assert!(module.contains("create_exception!(geometry, GeometryError"));
+        assert!(module.contains("fn gradient(line: PyRef<PyLine>) -> PyResult<f64>"));
+        assert!(module.contains(".map_err(|e| GeometryError::new_err(e.to_string()))?"));
+        assert!(module.contains("#[pyclass(name = \"Line\")]"));
+        assert!(module.contains("#[staticmethod]"));
+        assert!(module.contains("fn rotated(&self, other: PyRef<PyLine>) -> PyResult<PyLine>"));
+        assert!(module.contains("(&self.0, &other.0)"));
+    }
+
+    #[test]
+    fn test_pyo3_unsupported_features() {
+        const UDL: &str = r#"
+            namespace geometry {
+                Point origin();
+                void draw(sequence<Line> lines, optional u32 width = 1);
+            };
+            dictionary Point { f64 x; f64 y; };
+            interface Line {
+                [AlsoAsync]
+                f64 length();
+            };
+            callback interface Canvas {
+                void clear();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let err = generate_pyo3_module(&Config::from(&ci), &ci).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The PyO3 Python backend doesn't support:
+  the return type of function `origin`, Point
+  argument `lines` of function `draw`, of type sequence<Line>
+  the default value of `width` in function `draw`
+  method `Line.length`, which is `[AlsoAsync]`
+  callback interface `Canvas`"
+        );
+    }
+}
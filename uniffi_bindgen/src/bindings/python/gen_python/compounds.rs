@@ -125,12 +125,100 @@ fn sequence_coerce(
         nm
     )
 }
-impl_code_type_for_compound!(MapCodeType, "Map{}", "MapTemplate.py", map_coerce);
-fn map_coerce(this: &MapCodeType, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
-    format!(
-        "dict(({}, {}) for (k, v) in {}.items())",
-        oracle.find(&Type::String).coerce(oracle, &"k".to_string()),
-        oracle.find(this.inner()).coerce(oracle, &"v".to_string()),
-        nm
-    )
+// Maps have a key type as well as the value type, so they don't fit the macro above.
+// Python's dicts keep their insertion order, so ordered maps are plain dicts too, with
+// string keys.
+#[derive(Template)]
+#[template(syntax = "py", escape = "none", path = "MapTemplate.py")]
+pub struct MapCodeType {
+    key: TypeIdentifier,
+    value: TypeIdentifier,
+    outer: TypeIdentifier,
+}
+
+impl MapCodeType {
+    pub fn new(key: TypeIdentifier, value: TypeIdentifier, outer: TypeIdentifier) -> Self {
+        Self { key, value, outer }
+    }
+
+    fn key(&self) -> &TypeIdentifier {
+        &self.key
+    }
+
+    fn inner(&self) -> &TypeIdentifier {
+        &self.value
+    }
+
+    fn outer(&self) -> &TypeIdentifier {
+        &self.outer
+    }
+
+    fn ffi_converter_name(&self, oracle: &dyn CodeOracle) -> String {
+        format!("FfiConverter{}", self.canonical_name(oracle))
+    }
+}
+
+impl CodeType for MapCodeType {
+    fn type_label(&self, oracle: &dyn CodeOracle) -> String {
+        oracle.find(self.inner()).type_label(oracle)
+    }
+
+    fn canonical_name(&self, oracle: &dyn CodeOracle) -> String {
+        let value = oracle.find(self.inner()).canonical_name(oracle);
+        match self.outer() {
+            Type::OrderedMap(_) => format!("OrderedMap{}", value),
+            _ => format!(
+                "Map{}_{}",
+                oracle.find(self.key()).canonical_name(oracle),
+                value
+            ),
+        }
+    }
+
+    fn literal(&self, oracle: &dyn CodeOracle, literal: &Literal) -> String {
+        render_literal(oracle, literal, self.inner())
+    }
+
+    fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "{}._lower({})",
+            self.ffi_converter_name(oracle),
+            oracle.var_name(nm)
+        )
+    }
+
+    fn write(
+        &self,
+        oracle: &dyn CodeOracle,
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+    ) -> String {
+        format!(
+            "{}._write({}, {})",
+            self.ffi_converter_name(oracle),
+            oracle.var_name(nm),
+            target
+        )
+    }
+
+    fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("{}._lift({})", self.ffi_converter_name(oracle), nm)
+    }
+
+    fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("{}._read({})", self.ffi_converter_name(oracle), nm)
+    }
+
+    fn helper_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+
+    fn coerce(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "dict(({}, {}) for (k, v) in {}.items())",
+            oracle.find(self.key()).coerce(oracle, &"k".to_string()),
+            oracle.find(self.inner()).coerce(oracle, &"v".to_string()),
+            nm
+        )
+    }
 }
@@ -0,0 +1,124 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::backend::{CodeOracle, CodeType, CustomTypeConfig, Literal};
+use askama::Template;
+use std::fmt;
+
+// A type that's been mapped onto a custom Python type in `uniffi.toml`.
+//
+// Values are passed over the FFI exactly like the `builtin` type they replace, and are
+// converted to and from the custom type by the `FfiConverter` in `CustomTypeTemplate.py`.
+pub struct CustomCodeType {
+    config: CustomTypeConfig,
+    builtin: Box<dyn CodeType>,
+}
+
+impl CustomCodeType {
+    pub fn new(config: CustomTypeConfig, builtin: Box<dyn CodeType>) -> Self {
+        CustomCodeType { config, builtin }
+    }
+
+    fn ffi_converter_name(&self, oracle: &dyn CodeOracle) -> String {
+        format!("FfiConverter{}", self.canonical_name(oracle))
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "py", escape = "none", path = "CustomTypeTemplate.py")]
+struct CustomTypeHelper {
+    ffi_converter_name: String,
+    into_custom: String,
+    from_custom: String,
+    lift_builtin: String,
+    read_builtin: String,
+    lower_builtin: String,
+    write_builtin: String,
+}
+
+impl CodeType for CustomCodeType {
+    fn type_label(&self, oracle: &dyn CodeOracle) -> String {
+        self.config
+            .type_name
+            .clone()
+            .unwrap_or_else(|| self.builtin.type_label(oracle))
+    }
+
+    fn canonical_name(&self, oracle: &dyn CodeOracle) -> String {
+        format!("Custom{}", self.builtin.canonical_name(oracle))
+    }
+
+    fn literal(&self, oracle: &dyn CodeOracle, literal: &Literal) -> String {
+        self.config
+            .into_custom(&self.builtin.literal(oracle, literal))
+    }
+
+    fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "{}._lower({})",
+            self.ffi_converter_name(oracle),
+            oracle.var_name(nm)
+        )
+    }
+
+    fn write(
+        &self,
+        oracle: &dyn CodeOracle,
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+    ) -> String {
+        format!(
+            "{}._write({}, {})",
+            self.ffi_converter_name(oracle),
+            oracle.var_name(nm),
+            target
+        )
+    }
+
+    fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("{}._lift({})", self.ffi_converter_name(oracle), nm)
+    }
+
+    fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("{}._read({})", self.ffi_converter_name(oracle), nm)
+    }
+
+    fn helper_code(&self, oracle: &dyn CodeOracle) -> Option<String> {
+        let helper = CustomTypeHelper {
+            ffi_converter_name: self.ffi_converter_name(oracle),
+            into_custom: self.config.into_custom("builtin_value"),
+            from_custom: self.config.from_custom("value"),
+            lift_builtin: self.builtin.lift(oracle, &"value"),
+            read_builtin: self.builtin.read(oracle, &"buf"),
+            lower_builtin: self.builtin.lower(oracle, &"builtin_value"),
+            write_builtin: self.builtin.write(oracle, &"builtin_value", &"buf"),
+        };
+        // The builtin type's own helpers are still needed to do the actual lifting and lowering.
+        Some(
+            self.builtin
+                .helper_code(oracle)
+                .into_iter()
+                .chain(std::iter::once(helper.render().unwrap()))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+
+    fn imports(&self, oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        Some(
+            self.builtin
+                .imports(oracle)
+                .unwrap_or_default()
+                .into_iter()
+                .chain(self.config.imports.iter().cloned())
+                .collect(),
+        )
+    }
+
+    fn coerce(&self, _oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        // We can't know what values are acceptable for a custom type, so leave it to the
+        // `from_custom` conversion to complain.
+        nm.to_string()
+    }
+}
@@ -76,7 +76,81 @@ impl CodeType for ExternalCodeType {
         Some(self.render().unwrap())
     }
 
-    fn coerce(&self, _oracle: &dyn CodeOracle, _nm: &dyn fmt::Display) -> String {
-        panic!("should not be necessary to coerce External types");
+    // External types are left to the converters of the component that they come from.
+    fn coerce(&self, _oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        nm.to_string()
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "py", escape = "none", path = "ExternalInterfaceTemplate.py")]
+pub struct ExternalInterfaceCodeType {
+    name: String,
+    crate_name: String,
+}
+
+impl ExternalInterfaceCodeType {
+    pub fn new(name: String, crate_name: String) -> Self {
+        Self { name, crate_name }
+    }
+
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn crate_name(&self) -> String {
+        self.crate_name.clone()
+    }
+
+    fn ffi_converter_name(&self, oracle: &dyn CodeOracle) -> String {
+        format!("FfiConverter{}", self.canonical_name(oracle))
+    }
+}
+
+impl CodeType for ExternalInterfaceCodeType {
+    fn type_label(&self, _oracle: &dyn CodeOracle) -> String {
+        self.name.clone()
+    }
+
+    fn canonical_name(&self, _oracle: &dyn CodeOracle) -> String {
+        self.name.clone()
+    }
+
+    fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "{}._lower({})",
+            self.ffi_converter_name(oracle),
+            oracle.var_name(nm)
+        )
+    }
+
+    fn write(
+        &self,
+        oracle: &dyn CodeOracle,
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+    ) -> String {
+        format!(
+            "{}._write({}, {})",
+            self.ffi_converter_name(oracle),
+            oracle.var_name(nm),
+            target
+        )
+    }
+
+    fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("{}._lift({})", self.ffi_converter_name(oracle), nm)
+    }
+
+    fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("{}._read({})", self.ffi_converter_name(oracle), nm)
+    }
+
+    fn helper_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+
+    fn coerce(&self, _oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        nm.to_string()
     }
 }
@@ -3,7 +3,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use crate::backend::{CodeDeclaration, CodeOracle};
-use crate::interface::{ComponentInterface, Function};
+use crate::interface::{ComponentInterface, FFIFunction, Function, Module, Type};
 use askama::Template;
 
 use super::filters;
@@ -12,15 +12,22 @@ use super::filters;
 #[template(syntax = "py", escape = "none", path = "TopLevelFunctionTemplate.py")]
 pub struct PythonFunction {
     inner: Function,
+    errors_as_values: bool,
 }
 
 impl PythonFunction {
-    pub fn new(inner: Function, _ci: &ComponentInterface) -> Self {
-        Self { inner }
+    pub fn new(inner: Function, _ci: &ComponentInterface, errors_as_values: bool) -> Self {
+        Self {
+            inner,
+            errors_as_values,
+        }
     }
     pub fn inner(&self) -> &Function {
         &self.inner
     }
+    pub fn errors_as_values(&self) -> bool {
+        self.errors_as_values
+    }
 }
 
 impl CodeDeclaration for PythonFunction {
@@ -28,3 +35,62 @@ impl CodeDeclaration for PythonFunction {
         Some(self.render().unwrap())
     }
 }
+
+// A namespace nested inside the component's namespace, exposed as a submodule.
+#[derive(Template)]
+#[template(syntax = "py", escape = "none", path = "ModuleTemplate.py")]
+pub struct PythonModule {
+    inner: Module,
+    functions: Vec<Function>,
+}
+
+impl PythonModule {
+    pub fn new(inner: Module, ci: &ComponentInterface) -> Self {
+        Self {
+            functions: ci.iter_module_function_definitions(&inner),
+            inner,
+        }
+    }
+    pub fn inner(&self) -> &Module {
+        &self.inner
+    }
+    pub fn functions(&self) -> &[Function] {
+        &self.functions
+    }
+}
+
+impl CodeDeclaration for PythonModule {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        Some(vec!["types".into()])
+    }
+}
+
+// The calls to `[Async]` functions and methods that are waiting for Rust to complete them, and
+// the completion that Rust calls once they're done.
+#[derive(Template)]
+#[template(syntax = "py", escape = "none", path = "AsyncRuntime.py")]
+pub struct PythonAsyncRuntime {
+    ffi_cancel: Option<FFIFunction>,
+}
+
+impl PythonAsyncRuntime {
+    pub fn new(ci: &ComponentInterface) -> Self {
+        Self {
+            ffi_cancel: ci.ffi_async_cancel(),
+        }
+    }
+}
+
+impl CodeDeclaration for PythonAsyncRuntime {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        self.ffi_cancel.as_ref().map(|_| self.render().unwrap())
+    }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        self.ffi_cancel.as_ref().map(|_| vec!["asyncio".into()])
+    }
+}
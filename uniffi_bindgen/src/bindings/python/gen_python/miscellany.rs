@@ -64,6 +64,12 @@ macro_rules! impl_code_type_for_miscellany {
      }
  }
 
+impl_code_type_for_miscellany!(BigIntCodeType, "BigInt", "BigIntHelper.py");
+
+impl_code_type_for_miscellany!(BytesCodeType, "Bytes", "BytesHelper.py");
+
+impl_code_type_for_miscellany!(CharCodeType, "Char", "CharHelper.py");
+
 impl_code_type_for_miscellany!(TimestampCodeType, "Timestamp", "TimestampHelper.py");
 
 impl_code_type_for_miscellany!(DurationCodeType, "Duration", "DurationHelper.py");
@@ -2,21 +2,26 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use askama::Template;
 use heck::{CamelCase, ShoutySnakeCase, SnakeCase};
 use serde::{Deserialize, Serialize};
 
-use crate::backend::{CodeDeclaration, CodeOracle, CodeType, TypeIdentifier};
+use crate::backend::{
+    custom_types, renames, renames::RenameKind, ApiFilter, CodeDeclaration, CodeOracle, CodeType,
+    CustomTypesConfig, NamingProfile, Renames, TypeIdentifier,
+};
 use crate::interface::*;
 use crate::MergeWith;
 
 mod compounds;
+mod custom;
 mod enum_;
 mod error;
+mod exports;
 mod external;
 mod function;
 mod miscellany;
@@ -31,6 +36,41 @@ mod wrapped;
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     cdylib_name: Option<String>,
+    backend: Option<PythonBackend>,
+    #[serde(default)]
+    custom_types: CustomTypesConfig,
+    #[serde(default)]
+    renames: Renames,
+    naming: Option<NamingProfile>,
+    #[serde(default)]
+    foreign_extensions: BTreeMap<String, String>,
+    #[serde(default)]
+    filter: ApiFilter,
+    errors_as_values: Option<bool>,
+}
+
+/// How the Python bindings call into Rust.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PythonBackend {
+    /// A Python module that loads the component's cdylib with `ctypes`, which is the default.
+    Ctypes,
+    /// A PyO3 extension module, written in Rust, that's built into the component's crate.
+    Pyo3,
+}
+
+impl std::str::FromStr for PythonBackend {
+    type Err = anyhow::Error;
+    fn from_str(value: &str) -> Result<Self> {
+        Ok(match value {
+            "ctypes" => PythonBackend::Ctypes,
+            "pyo3" => PythonBackend::Pyo3,
+            _ => bail!(
+                "Unknown Python backend: \"{}\" (expected \"ctypes\" or \"pyo3\")",
+                value
+            ),
+        })
+    }
 }
 
 impl Config {
@@ -41,12 +81,87 @@ impl Config {
             "uniffi".into()
         }
     }
+
+    /// How the bindings call into Rust, from `backend` in the config or `--python-backend`.
+    pub fn backend(&self) -> PythonBackend {
+        self.backend.unwrap_or(PythonBackend::Ctypes)
+    }
+
+    /// Use the given backend, whatever the config says.
+    pub fn with_backend(&self, backend: PythonBackend) -> Self {
+        Config {
+            backend: Some(backend),
+            ..self.clone()
+        }
+    }
+
+    /// Types mapped onto custom Python types, keyed by their UDL name.
+    pub fn custom_types(&self) -> &CustomTypesConfig {
+        &self.custom_types
+    }
+
+    /// Legacy Python names for identifiers in the component, see [`crate::backend::renames`].
+    pub fn renames(&self) -> &Renames {
+        &self.renames
+    }
+
+    /// How to name the identifiers that aren't renamed, see [`crate::backend::renames`].
+    pub fn naming(&self) -> NamingProfile {
+        self.naming.unwrap_or_default()
+    }
+
+    /// The module to import a `[ForeignExtension]` class from.
+    pub fn foreign_extension_module(&self, name: &str) -> Option<&str> {
+        self.foreign_extensions.get(name).map(String::as_str)
+    }
+
+    /// Check that there's a module to import each `[ForeignExtension]` class from, since
+    /// Python bindings have no other way to find it.
+    pub fn validate_foreign_extensions(&self, ci: &ComponentInterface) -> Result<()> {
+        for obj in ci.iter_object_definitions() {
+            if let Some(ext) = obj.foreign_extension() {
+                if self.foreign_extension_module(ext).is_none() {
+                    bail!(
+                        "The [ForeignExtension] `{}` of `{}` needs a module to import it from, in `[bindings.python.foreign_extensions]`",
+                        ext,
+                        obj.name()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The part of the component to generate bindings for, see [`crate::backend::filter`].
+    pub fn filter(&self) -> &ApiFilter {
+        &self.filter
+    }
+
+    /// Whether the functions and methods that raise return the error that they declare instead.
+    pub fn errors_as_values(&self) -> bool {
+        self.errors_as_values.unwrap_or(false)
+    }
+
+    /// Fill in any renames not already configured from those in a `renames.toml` file.
+    pub fn with_renames_from(&self, renames: &Renames) -> Self {
+        Config {
+            renames: self.renames.merge_with(renames),
+            ..self.clone()
+        }
+    }
 }
 
 impl From<&ComponentInterface> for Config {
     fn from(ci: &ComponentInterface) -> Self {
         Config {
             cdylib_name: Some(format!("uniffi_{}", ci.namespace())),
+            backend: None,
+            custom_types: Default::default(),
+            renames: Default::default(),
+            naming: None,
+            foreign_extensions: Default::default(),
+            filter: Default::default(),
+            errors_as_values: None,
         }
     }
 }
@@ -55,6 +170,15 @@ impl MergeWith for Config {
     fn merge_with(&self, other: &Self) -> Self {
         Config {
             cdylib_name: self.cdylib_name.merge_with(&other.cdylib_name),
+            backend: self.backend.merge_with(&other.backend),
+            custom_types: self.custom_types.merge_with(&other.custom_types),
+            renames: self.renames.merge_with(&other.renames),
+            naming: self.naming.merge_with(&other.naming),
+            foreign_extensions: self
+                .foreign_extensions
+                .merge_with(&other.foreign_extensions),
+            filter: self.filter.merge_with(&other.filter),
+            errors_as_values: self.errors_as_values.merge_with(&other.errors_as_values),
         }
     }
 }
@@ -75,26 +199,57 @@ impl<'a> PythonWrapper<'a> {
         }
     }
 
+    /// The version of `uniffi-bindgen` that's generating the bindings.
+    pub fn bindgen_version(&self) -> &'static str {
+        crate::BINDGEN_VERSION
+    }
+
     pub fn members(&self) -> Vec<Box<dyn CodeDeclaration + 'a>> {
         let ci = self.ci;
-        vec![]
-            .into_iter()
-            .chain(ci.iter_enum_definitions().into_iter().map(|inner| {
+        let errors_as_values = self.config.errors_as_values();
+        vec![
+            Box::new(function::PythonAsyncRuntime::new(ci)) as Box<dyn CodeDeclaration>,
+            Box::new(exports::PythonExports::new(ci)) as Box<dyn CodeDeclaration>,
+        ]
+        .into_iter()
+        .chain(
+            ci.iter_enum_definitions().into_iter().map(|inner| {
                 Box::new(enum_::PythonEnum::new(inner, ci)) as Box<dyn CodeDeclaration>
-            }))
-            .chain(ci.iter_function_definitions().into_iter().map(|inner| {
-                Box::new(function::PythonFunction::new(inner, ci)) as Box<dyn CodeDeclaration>
-            }))
-            .chain(ci.iter_object_definitions().into_iter().map(|inner| {
-                Box::new(object::PythonObject::new(inner, ci)) as Box<dyn CodeDeclaration>
-            }))
-            .chain(ci.iter_record_definitions().into_iter().map(|inner| {
-                Box::new(record::PythonRecord::new(inner, ci)) as Box<dyn CodeDeclaration>
-            }))
-            .chain(ci.iter_error_definitions().into_iter().map(|inner| {
+            }),
+        )
+        .chain(ci.iter_function_definitions().into_iter().map(|inner| {
+            Box::new(function::PythonFunction::new(inner, ci, errors_as_values))
+                as Box<dyn CodeDeclaration>
+        }))
+        // Nested namespaces come after the functions, which they refer to.
+        .chain(ci.iter_module_definitions().into_iter().map(|inner| {
+            Box::new(function::PythonModule::new(inner, ci)) as Box<dyn CodeDeclaration>
+        }))
+        .chain(ci.iter_object_definitions().into_iter().map(|inner| {
+            if inner.is_opaque_token() {
+                return Box::new(object::PythonOpaqueToken::new(inner, ci))
+                    as Box<dyn CodeDeclaration>;
+            }
+            let extension_module = inner
+                .foreign_extension()
+                .and_then(|ext| self.config.foreign_extension_module(ext))
+                .map(Into::into);
+            Box::new(object::PythonObject::new(
+                inner,
+                ci,
+                extension_module,
+                errors_as_values,
+            )) as Box<dyn CodeDeclaration>
+        }))
+        .chain(ci.iter_record_definitions().into_iter().map(|inner| {
+            Box::new(record::PythonRecord::new(inner, ci)) as Box<dyn CodeDeclaration>
+        }))
+        .chain(
+            ci.iter_error_definitions().into_iter().map(|inner| {
                 Box::new(error::PythonError::new(inner, ci)) as Box<dyn CodeDeclaration>
-            }))
-            .collect()
+            }),
+        )
+        .collect()
     }
 
     pub fn initialization_code(&self) -> Vec<String> {
@@ -119,10 +274,29 @@ impl<'a> PythonWrapper<'a> {
             .collect()
     }
 
+    /// Whether any object has `[NoCopy]` methods, which return `OwnedBytes`.
+    pub fn has_no_copy_methods(&self) -> bool {
+        self.ci
+            .iter_object_definitions()
+            .iter()
+            .any(|obj| obj.methods().iter().any(|meth| meth.is_no_copy()))
+    }
+
+    /// The name of the enum listing the component's exports, if it has any.
+    pub fn exports_enum_name(&self) -> Option<String> {
+        if crate::lockfile::exported_callables(self.ci).is_empty() {
+            None
+        } else {
+            Some(
+                self.oracle
+                    .class_name(&format!("{}Export", self.ci.namespace())),
+            )
+        }
+    }
+
     pub fn imports(&self) -> Vec<String> {
         let oracle = &self.oracle;
-        let mut imports: Vec<String> = self
-            .members()
+        self.members()
             .into_iter()
             .filter_map(|member| member.imports(oracle))
             .flatten()
@@ -133,12 +307,9 @@ impl<'a> PythonWrapper<'a> {
                     .filter_map(|type_| oracle.find(&type_).imports(oracle))
                     .flatten(),
             )
-            .collect::<HashSet<String>>()
+            .collect::<BTreeSet<String>>()
             .into_iter()
-            .collect();
-
-        imports.sort();
-        imports
+            .collect()
     }
 }
 
@@ -147,6 +318,16 @@ pub struct PythonCodeOracle;
 
 impl PythonCodeOracle {
     fn create_code_type(&self, type_: TypeIdentifier) -> Box<dyn CodeType> {
+        match custom_types::find_custom_type(&type_) {
+            Some(config) => Box::new(custom::CustomCodeType::new(
+                config,
+                self.create_builtin_code_type(type_),
+            )),
+            None => self.create_builtin_code_type(type_),
+        }
+    }
+
+    fn create_builtin_code_type(&self, type_: TypeIdentifier) -> Box<dyn CodeType> {
         // I really want access to the ComponentInterface here so I can look up the interface::{Enum, Record, Error, Object, etc}
         // However, there's some violence and gore I need to do to (temporarily) make the oracle usable from filters.
 
@@ -165,6 +346,9 @@ impl PythonCodeOracle {
             Type::Boolean => Box::new(primitives::BooleanCodeType),
             Type::String => Box::new(primitives::StringCodeType),
 
+            Type::BigInt => Box::new(miscellany::BigIntCodeType),
+            Type::Bytes => Box::new(miscellany::BytesCodeType),
+            Type::Char => Box::new(miscellany::CharCodeType),
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
 
@@ -186,14 +370,23 @@ impl PythonCodeOracle {
                 let inner = *inner.to_owned();
                 Box::new(compounds::SequenceCodeType::new(inner, outer))
             }
-            Type::Map(ref inner) => {
+            Type::Map(ref key, ref value) => {
+                let outer = type_.clone();
+                let key = *key.to_owned();
+                let value = *value.to_owned();
+                Box::new(compounds::MapCodeType::new(key, value, outer))
+            }
+            Type::OrderedMap(ref inner) => {
                 let outer = type_.clone();
                 let inner = *inner.to_owned();
-                Box::new(compounds::MapCodeType::new(inner, outer))
+                Box::new(compounds::MapCodeType::new(Type::String, inner, outer))
             }
             Type::External { name, crate_name } => {
                 Box::new(external::ExternalCodeType::new(name, crate_name))
             }
+            Type::ExternalInterface { name, crate_name } => {
+                Box::new(external::ExternalInterfaceCodeType::new(name, crate_name))
+            }
             Type::Wrapped { ref prim, .. } => {
                 let outer = type_.clone();
                 let inner = *prim.to_owned();
@@ -210,39 +403,41 @@ impl CodeOracle for PythonCodeOracle {
 
     /// Get the idiomatic Python rendering of a class name (for enums, records, errors, etc).
     fn class_name(&self, nm: &dyn fmt::Display) -> String {
-        nm.to_string().to_camel_case()
+        renames::rename_or(RenameKind::Class, nm, |nm| nm.to_camel_case())
     }
 
     /// Get the idiomatic Python rendering of a function name.
     fn fn_name(&self, nm: &dyn fmt::Display) -> String {
-        nm.to_string().to_snake_case()
+        renames::rename_or(RenameKind::Function, nm, |nm| nm.to_snake_case())
     }
 
     /// Get the idiomatic Python rendering of a variable name.
     fn var_name(&self, nm: &dyn fmt::Display) -> String {
-        nm.to_string().to_snake_case()
+        renames::rename_or(RenameKind::Variable, nm, |nm| nm.to_snake_case())
     }
 
     /// Get the idiomatic Python rendering of an individual enum variant.
     fn enum_variant_name(&self, nm: &dyn fmt::Display) -> String {
-        nm.to_string().to_shouty_snake_case()
+        renames::rename_or(RenameKind::EnumVariant, nm, |nm| nm.to_shouty_snake_case())
     }
 
     /// Get the idiomatic Python rendering of an exception name
     ///
     /// This replaces "Error" at the end of the name with "Exception".  Rust code typically uses
     /// "Error" for any type of error but in the Java world, "Error" means a non-recoverable error
-    /// and is distinguished from an "Exception".
+    /// and is distinguished from an "Exception". A legacy name configured in `renames.toml`
+    /// is used as-is.
     fn error_name(&self, nm: &dyn fmt::Display) -> String {
-        let name = nm.to_string();
-        match name.strip_suffix("Error") {
-            None => name,
-            Some(stripped) => {
-                let mut py_exc_name = stripped.to_owned();
-                py_exc_name.push_str("Exception");
-                py_exc_name
+        renames::rename_or(RenameKind::Class, nm, |name| {
+            match name.strip_suffix("Error") {
+                None => name,
+                Some(stripped) => {
+                    let mut py_exc_name = stripped.to_owned();
+                    py_exc_name.push_str("Exception");
+                    py_exc_name
+                }
             }
-        }
+        })
     }
 
     fn ffi_type_label(&self, ffi_type: &FFIType) -> String {
@@ -260,7 +455,10 @@ impl CodeOracle for PythonCodeOracle {
             FFIType::RustArcPtr => "ctypes.c_void_p".to_string(),
             FFIType::RustBuffer => "RustBuffer".to_string(),
             FFIType::ForeignBytes => "ForeignBytes".to_string(),
-            FFIType::ForeignCallback => unimplemented!("Callback interfaces are not implemented"),
+            FFIType::ForeignCallback | FFIType::ForeignMethodCallback(_) => {
+                unimplemented!("Callback interfaces are not implemented")
+            }
+            FFIType::AsyncCompletion => "_UNIFFI_ASYNC_COMPLETION_T".to_string(),
         }
     }
 }
@@ -362,4 +560,62 @@ pub mod filters {
         let oracle = oracle();
         Ok(oracle.find(type_).coerce(&oracle, nm))
     }
+
+    /// Get the check that an argument of the given type is run through before it's coerced and
+    /// lowered, or an empty string if there's nothing to check.
+    ///
+    /// Objects already raise a `TypeError` when they're lowered, and custom, external and
+    /// callback interface types are left to their own conversions.
+    pub fn arg_check_py(type_: &Type) -> Result<String, askama::Error> {
+        let integer = |low: &str, high: &str| format!("_uniffi_check_integer({}, {})", low, high);
+        let instance =
+            |class: &str, what: &str| format!("_uniffi_check_instance({}, \"{}\")", class, what);
+        Ok(match type_ {
+            Type::Int8 => integer("-2 ** 7", "2 ** 7 - 1"),
+            Type::UInt8 => integer("0", "2 ** 8 - 1"),
+            Type::Int16 => integer("-2 ** 15", "2 ** 15 - 1"),
+            Type::UInt16 => integer("0", "2 ** 16 - 1"),
+            Type::Int32 => integer("-2 ** 31", "2 ** 31 - 1"),
+            Type::UInt32 => integer("0", "2 ** 32 - 1"),
+            Type::Int64 => integer("-2 ** 63", "2 ** 63 - 1"),
+            Type::UInt64 => integer("0", "2 ** 64 - 1"),
+            Type::BigInt => integer("-2 ** 127", "2 ** 127 - 1"),
+            Type::Float32 | Type::Float64 => "_uniffi_check_float".into(),
+            Type::Boolean => "_uniffi_check_not_none".into(),
+            Type::String => instance("str", "a string"),
+            Type::Bytes => instance("(bytes, bytearray)", "bytes"),
+            Type::Char => "_uniffi_check_char".into(),
+            Type::Timestamp => instance("datetime.datetime", "a datetime"),
+            Type::Duration => instance("datetime.timedelta", "a timedelta"),
+            Type::Enum(_) | Type::Record(_) => {
+                let oracle = oracle();
+                let class = oracle.find(type_).type_label(&oracle);
+                instance(&class, &format!("a {}", class))
+            }
+            Type::Optional(t) => match arg_check_py(t)?.as_str() {
+                "" => "".into(),
+                check => format!("_uniffi_check_optional({})", check),
+            },
+            Type::Sequence(t) => format!("_uniffi_check_sequence({})", inner_check_py(t)?),
+            Type::Map(k, v) => format!(
+                "_uniffi_check_map({}, {})",
+                inner_check_py(k)?,
+                inner_check_py(v)?
+            ),
+            Type::OrderedMap(v) => format!(
+                "_uniffi_check_map({}, {})",
+                inner_check_py(&Type::String)?,
+                inner_check_py(v)?
+            ),
+            _ => "".into(),
+        })
+    }
+
+    // The check for the items in a sequence or map, where `None` means that they aren't checked.
+    fn inner_check_py(type_: &Type) -> Result<String, askama::Error> {
+        Ok(match arg_check_py(type_)?.as_str() {
+            "" => "None".into(),
+            check => check.into(),
+        })
+    }
 }
@@ -34,7 +34,11 @@ impl CodeType for ObjectCodeType {
     }
 
     fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
-        format!("{}._lower()", oracle.var_name(nm))
+        format!(
+            "{}._check_lower({})",
+            self.type_label(oracle),
+            oracle.var_name(nm)
+        )
     }
 
     fn write(
@@ -75,9 +79,58 @@ impl CodeType for ObjectCodeType {
 #[template(syntax = "py", escape = "none", path = "ObjectTemplate.py")]
 pub struct PythonObject {
     inner: Object,
+    // The module to import the `[ForeignExtension]` class from.
+    extension_module: Option<String>,
+    errors_as_values: bool,
 }
 
 impl PythonObject {
+    pub fn new(
+        inner: Object,
+        _ci: &ComponentInterface,
+        extension_module: Option<String>,
+        errors_as_values: bool,
+    ) -> Self {
+        Self {
+            inner,
+            extension_module,
+            errors_as_values,
+        }
+    }
+    pub fn inner(&self) -> &Object {
+        &self.inner
+    }
+    pub fn errors_as_values(&self) -> bool {
+        self.errors_as_values
+    }
+
+    /// The class that the generated class inherits from.
+    pub fn base_class(&self) -> String {
+        match (&self.extension_module, self.inner.foreign_extension()) {
+            (Some(module), Some(ext)) => format!("{}.{}", module, ext),
+            _ => "object".into(),
+        }
+    }
+}
+
+impl CodeDeclaration for PythonObject {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        self.extension_module.clone().map(|module| vec![module])
+    }
+}
+
+// An `[OpaqueToken]` has no constructors or methods, so it only needs a small handle class.
+#[derive(Template)]
+#[template(syntax = "py", escape = "none", path = "OpaqueTokenTemplate.py")]
+pub struct PythonOpaqueToken {
+    inner: Object,
+}
+
+impl PythonOpaqueToken {
     pub fn new(inner: Object, _ci: &ComponentInterface) -> Self {
         Self { inner }
     }
@@ -86,7 +139,7 @@ impl PythonObject {
     }
 }
 
-impl CodeDeclaration for PythonObject {
+impl CodeDeclaration for PythonOpaqueToken {
     fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
         Some(self.render().unwrap())
     }
@@ -5,7 +5,7 @@
 use std::fmt;
 
 use crate::backend::{CodeDeclaration, CodeOracle, CodeType, Literal};
-use crate::interface::{ComponentInterface, Record};
+use crate::interface::{ComponentInterface, Field, Record};
 use askama::Template;
 
 use super::filters;
@@ -80,6 +80,18 @@ impl PythonRecord {
     pub fn inner(&self) -> &Record {
         &self.inner
     }
+
+    // The default value of a field that's an empty list or dict. Each record needs its own,
+    // rather than sharing the one that Python would evaluate for the argument's default, so
+    // the argument defaults to `_UNIFFI_DEFAULT`, which the constructor replaces.
+    fn collection_default(&self, field: &Field) -> Option<Literal> {
+        match self.inner.trailing_default_value(field) {
+            Some(literal @ Literal::EmptySequence) | Some(literal @ Literal::EmptyMap) => {
+                Some(literal)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl CodeDeclaration for PythonRecord {
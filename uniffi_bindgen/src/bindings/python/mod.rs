@@ -13,10 +13,12 @@ use std::{
 
 use anyhow::{bail, Context, Result};
 
+pub mod gen_pyo3;
 pub mod gen_python;
-pub use gen_python::{Config, PythonWrapper};
+pub use gen_python::{Config, PythonBackend, PythonWrapper};
 
-use super::super::interface::ComponentInterface;
+use super::super::interface::{Capabilities, ComponentInterface, Feature};
+use crate::backend::{custom_types, post_process, renames};
 
 // Generate python bindings for the given ComponentInterface, in the given output directory.
 
@@ -26,10 +28,17 @@ pub fn write_bindings(
     out_dir: &Path,
     try_format_code: bool,
 ) -> Result<()> {
+    if config.backend() == PythonBackend::Pyo3 {
+        return write_pyo3_module(config, ci, out_dir, try_format_code);
+    }
     let mut py_file = PathBuf::from(out_dir);
     py_file.push(format!("{}.py", ci.namespace()));
     let mut f = File::create(&py_file).context("Failed to create .py file for bindings")?;
-    write!(f, "{}", generate_python_bindings(config, ci)?)?;
+    write!(
+        f,
+        "{}",
+        post_process::post_process(&py_file, generate_python_bindings(config, ci)?)?
+    )?;
 
     if try_format_code {
         if let Err(e) = Command::new("yapf").arg(py_file.to_str().unwrap()).output() {
@@ -44,13 +53,62 @@ pub fn write_bindings(
     Ok(())
 }
 
+// Generate a PyO3 extension module for the given ComponentInterface, in the given output
+// directory, for the component's crate to `include!`.
+
+fn write_pyo3_module(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Path,
+    try_format_code: bool,
+) -> Result<()> {
+    let mut rs_file = PathBuf::from(out_dir);
+    rs_file.push(format!("{}.pyo3.rs", ci.namespace()));
+    let mut f = File::create(&rs_file).context("Failed to create .rs file for PyO3 module")?;
+    write!(
+        f,
+        "{}",
+        post_process::post_process(&rs_file, gen_pyo3::generate_pyo3_module(config, ci)?)?
+    )?;
+
+    if try_format_code {
+        if let Err(e) = Command::new("rustfmt").arg(&rs_file).output() {
+            println!(
+                "Warning: Unable to auto-format {} using rustfmt: {:?}",
+                rs_file.file_name().unwrap().to_str().unwrap(),
+                e
+            )
+        }
+    }
+
+    Ok(())
+}
+
+/// What the Python bindings can generate.
+pub fn capabilities() -> Capabilities {
+    Capabilities::all().without(&[Feature::AlsoAsync, Feature::CallbackInterfaces])
+}
+
 // Generate python bindings for the given ComponentInterface, as a string.
 
 pub fn generate_python_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
     use askama::Template;
-    PythonWrapper::new(config.clone(), ci)
-        .render()
-        .map_err(|_| anyhow::anyhow!("failed to render python bindings"))
+    let problems = ci.unsupported_features(&capabilities());
+    if !problems.is_empty() {
+        bail!(
+            "The Python bindings don't support:\n  {}",
+            problems.join("\n  ")
+        );
+    }
+    custom_types::validate_custom_types(ci, config.custom_types(), false)?;
+    renames::validate_renames(ci, config.renames())?;
+    config.validate_foreign_extensions(ci)?;
+    custom_types::with_custom_types(config.custom_types(), || {
+        renames::with_renames(config.renames(), config.naming(), || {
+            PythonWrapper::new(config.clone(), ci).render()
+        })
+    })
+    .map_err(|_| anyhow::anyhow!("failed to render python bindings"))
 }
 
 /// Execute the specifed python script, with environment based on the generated
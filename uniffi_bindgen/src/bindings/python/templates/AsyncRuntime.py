@@ -0,0 +1,81 @@
+# How Rust completes a call to an `[Async]` function or method: either it returned, and the
+# buffer holds what it returned, or it failed with the error that it declares, or it panicked,
+# and the buffer holds the message, or it was cancelled before it finished.
+_ASYNC_CALL_DONE = 0
+_ASYNC_CALL_ERROR = 1
+_ASYNC_CALL_PANIC = 2
+_ASYNC_CALL_CANCELLED = 3
+
+_UNIFFI_ASYNC_COMPLETION_T = ctypes.CFUNCTYPE(None, ctypes.c_uint64, ctypes.c_int8, RustBuffer)
+
+# The calls to `[Async]` functions and methods that Rust hasn't completed yet, by the handle that
+# each one was made with, along with the event loop that's waiting for it.
+_uniffi_async_calls = {}
+_uniffi_async_lock = threading.Lock()
+_uniffi_async_next_handle = 1
+
+def _uniffi_resolve_async_call(future, code, result):
+    if future.cancelled():
+        # Nothing is waiting for what it returned any more.
+        result.free()
+    else:
+        future.set_result((code, result))
+
+def _uniffi_complete_async_call(handle, code, result):
+    # Rust calls this on one of its own threads, so the future is resolved on its event loop.
+    with _uniffi_async_lock:
+        pending = _uniffi_async_calls.pop(handle, None)
+    if pending is None:
+        return
+    (loop, future) = pending
+    try:
+        loop.call_soon_threadsafe(_uniffi_resolve_async_call, future, code, result)
+    except RuntimeError:
+        # The event loop has been closed.
+        result.free()
+
+# Registered with Rust when the library is loaded, and kept here so that ctypes doesn't free it
+# while Rust can still call it.
+_uniffi_async_completion = _UNIFFI_ASYNC_COMPLETION_T(_uniffi_complete_async_call)
+
+async def _uniffi_async_call(error_class, start, read):
+    # Make a call to an `[Async]` function or method with `start`, which passes Rust the handle
+    # that it's given, and wait until Rust has completed it. `read` reads what the call returned,
+    # or is None if it doesn't return anything.
+    #
+    # Cancelling the task cancels the Rust call too.
+    global _uniffi_async_next_handle
+    loop = asyncio.get_running_loop()
+    future = loop.create_future()
+    with _uniffi_async_lock:
+        handle = _uniffi_async_next_handle
+        _uniffi_async_next_handle += 1
+        _uniffi_async_calls[handle] = (loop, future)
+    try:
+        start(handle)
+    except BaseException:
+        # Rust didn't start the call, so it won't complete it either.
+        with _uniffi_async_lock:
+            _uniffi_async_calls.pop(handle, None)
+        raise
+    try:
+        (code, result) = await future
+    except asyncio.CancelledError:
+        # Rust still completes the call once it's cancelled, and what it returns is thrown away.
+        rust_call(_UniFFILib.{{ ffi_cancel.as_ref().unwrap().name() }}, handle)
+        raise
+    if code == _ASYNC_CALL_DONE:
+        with result.consumeWithStream() as stream:
+            return None if read is None else read(stream)
+    elif code == _ASYNC_CALL_ERROR:
+        raise error_class._lift(result)
+    elif code == _ASYNC_CALL_PANIC:
+        with result.consumeWithStream() as stream:
+            message = {{ "stream"|read_var(Type::String) }}
+        raise InternalError(message)
+    elif code == _ASYNC_CALL_CANCELLED:
+        result.free()
+        raise asyncio.CancelledError()
+    else:
+        result.free()
+        raise InternalError("Invalid async call status: {}".format(code))
@@ -0,0 +1,85 @@
+{% import "macros.py" as py %}
+{%- match ci.ffi_batch() %}
+{%- when Some with (ffi_batch) %}
+
+class BatchResult:
+    """The result of a call that was queued up in a `batch()`, which `get()` returns once the
+    batch has run, or raises the error that the call failed with.
+    """
+
+    def __init__(self):
+        self._done = False
+        self._value = None
+        self._error = None
+
+    def _complete(self, value=None, error=None):
+        self._done = True
+        self._value = value
+        self._error = error
+
+    def get(self):
+        if not self._done:
+            raise RuntimeError("The batch that this call was queued up in hasn't run yet")
+        if self._error is not None:
+            raise self._error
+        return self._value
+
+
+class Batch:
+    """The calls queued up in a `batch()`. It has a method for each `[Batchable]` function, which
+    takes the function's arguments, but returns a `BatchResult` rather than calling it.
+    """
+
+    def __init__(self):
+        self._calls = RustBufferBuilder()
+        self._results = []
+    {% for func in ci.iter_batchable_function_definitions() %}
+    def {{ func.name()|fn_name }}(self{% if !func.arguments().is_empty() %}, {% call py::arg_list_decl(func) %}{% endif %}):
+        {%- call py::coerce_args_extra_indent(func) %}
+        result = BatchResult()
+        self._calls.writeI32({{ loop.index }})
+        {%- for arg in func.arguments() %}
+        {{ arg.name()|write_var("self._calls", arg.type_()) }}
+        {%- endfor %}
+
+        def read(buf):
+            status = buf.readI8()
+            if status == 0:
+                {%- match func.return_type() %}
+                {%- when Some with (return_type) %}
+                result._complete(value={{ "buf"|read_var(return_type) }})
+                {%- when None %}
+                result._complete()
+                {%- endmatch %}
+            {%- match func.throws_type() %}
+            {%- when Some with (e) %}
+            elif status == 1:
+                result._complete(error={{ "buf"|read_var(e) }})
+            {%- when None %}
+            {%- endmatch %}
+            else:
+                raise InternalError("invalid batch result, something is very wrong!!")
+
+        self._results.append(read)
+        return result
+    {% endfor %}
+
+@contextlib.contextmanager
+def batch():
+    """Queue up calls to `[Batchable]` functions on the `Batch` that this gives to a `with` block,
+    then make all of them with a single call into the Rust library when the block ends, which is
+    much cheaper than calling each function on its own when there are a lot of small calls to
+    make. Their results are available once the block has ended.
+    """
+    b = Batch()
+    try:
+        yield b
+    except:
+        b._calls.discard()
+        raise
+    results = rust_call(_UniFFILib.{{ ffi_batch.name() }}, b._calls.finalize())
+    with results.consumeWithStream() as stream:
+        for read in b._results:
+            read(stream)
+{%- when None %}
+{%- endmatch %}
@@ -0,0 +1,12 @@
+# The BigInt type, which is a Python int that fits in 128 bits.
+# It's passed as a two's complement number, most significant byte first.
+class FfiConverterBigInt(FfiConverterUsingByteBuffer):
+    @staticmethod
+    def _read(buf):
+        return int.from_bytes(bytes(buf.read(16)), "big", signed=True)
+
+    @staticmethod
+    def _write(value, buf):
+        if not -(2 ** 127) <= value < 2 ** 127:
+            raise ValueError("Invalid bigint {}, must fit in 128 bits".format(value))
+        buf.write(value.to_bytes(16, "big", signed=True))
@@ -0,0 +1,14 @@
+# The Bytes type, which is a Python `bytes` object.
+# It's passed as its length, followed by the bytes themselves.
+class FfiConverterBytes(FfiConverterUsingByteBuffer):
+    @staticmethod
+    def _read(buf):
+        size = buf.readI32()
+        if size < 0:
+            raise InternalError("Unexpected negative byte string length")
+        return bytes(buf.read(size))
+
+    @staticmethod
+    def _write(value, buf):
+        buf.writeI32(len(value))
+        buf.write(value)
@@ -0,0 +1,23 @@
+# The Char type, which is a Python `str` of one character.
+# It's passed as the character's Unicode scalar value.
+class FfiConverterChar:
+    @staticmethod
+    def _read(buf):
+        return FfiConverterChar._lift(buf.readU32())
+
+    @staticmethod
+    def _write(value, buf):
+        buf.writeU32(FfiConverterChar._lower(value))
+
+    @staticmethod
+    def _lift(value):
+        if 0xD800 <= value <= 0xDFFF or value > 0x10FFFF:
+            raise InternalError("Unexpected char value {:#x}".format(value))
+        return chr(value)
+
+    @staticmethod
+    def _lower(value):
+        problem = _uniffi_check_char(value)
+        if problem is not None:
+            raise ValueError(problem)
+        return ord(value)
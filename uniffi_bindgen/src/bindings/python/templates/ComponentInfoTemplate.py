@@ -0,0 +1,29 @@
+class UniffiComponentInfo(object):
+    """
+    Metadata about the build of the Rust library that these bindings are using.
+
+    `checksum` identifies the component interface that the library was built from, and
+    `bindings_checksum` the one that these bindings were generated from. If they differ, the
+    bindings and the library don't match.
+    """
+    def __init__(self, component_name, uniffi_version, checksum, build_profile, bindings_checksum):
+        self.component_name = component_name
+        self.uniffi_version = uniffi_version
+        self.checksum = checksum
+        self.build_profile = build_profile
+        self.bindings_checksum = bindings_checksum
+
+    def __repr__(self):
+        return "UniffiComponentInfo(component_name={}, uniffi_version={}, checksum={}, build_profile={}, bindings_checksum={})".format(
+            self.component_name,
+            self.uniffi_version,
+            self.checksum,
+            self.build_profile,
+            self.bindings_checksum,
+        )
+
+def uniffi_component_info():
+    """Fetch metadata about the Rust library, for use by support tooling."""
+    info = FfiConverterString._lift(rust_call(_UniFFILib.{{ ci.ffi_component_info().name() }}))
+    fields = info.split("\n")
+    return UniffiComponentInfo(fields[0], fields[1], fields[2], fields[3], UNIFFI_COMPONENT_CHECKSUM)
@@ -0,0 +1,27 @@
+# Conversions for a type that's mapped onto a custom Python type by uniffi.toml.
+class {{ ffi_converter_name }}:
+    @staticmethod
+    def _into_custom(builtin_value):
+        return {{ into_custom }}
+
+    @staticmethod
+    def _from_custom(value):
+        return {{ from_custom }}
+
+    @staticmethod
+    def _lift(value):
+        return {{ ffi_converter_name }}._into_custom({{ lift_builtin }})
+
+    @staticmethod
+    def _read(buf):
+        return {{ ffi_converter_name }}._into_custom({{ read_builtin }})
+
+    @staticmethod
+    def _lower(value):
+        builtin_value = {{ ffi_converter_name }}._from_custom(value)
+        return {{ lower_builtin }}
+
+    @staticmethod
+    def _write(value, buf):
+        builtin_value = {{ ffi_converter_name }}._from_custom(value)
+        {{ write_builtin }}
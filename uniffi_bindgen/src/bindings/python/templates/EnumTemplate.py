@@ -12,21 +12,17 @@ class {{ e|type_name }}(ViaFfiUsingByteBuffer, enum.Enum):
     {{ variant.name()|enum_variant_py }} = {{ loop.index }}
     {% endfor %}
 
+    # The value of each variant is its index on the wire, so we can let `enum` look it up.
     @staticmethod
     def _read(buf):
         variant = buf.readI32()
-        {% for variant in e.variants() -%}
-        if variant == {{ loop.index }}:
-            return {{ e|type_name }}.{{ variant.name()|enum_variant_py }}
-        {% endfor %}
-        raise InternalError("Raw enum value doesn't match any cases")
+        try:
+            return {{ e|type_name }}(variant)
+        except ValueError:
+            raise InternalError("Raw enum value doesn't match any cases")
 
     def _write(self, buf):
-        {% for variant in e.variants() -%}
-        if self is {{ e|type_name }}.{{ variant.name()|enum_variant_py }}:
-            i = {{loop.index}}
-            buf.writeI32({{ loop.index }})
-        {% endfor %}
+        buf.writeI32(self.value)
 {% else %}
 
 class {{ e|type_name }}(ViaFfiUsingByteBuffer, object):
@@ -56,6 +52,12 @@ class {{ e|type_name }}(ViaFfiUsingByteBuffer, object):
                 return False
             {%- endfor %}
             return True
+
+        def _write(self, buf):
+            buf.writeI32({{ loop.index }})
+            {%- for field in variant.fields() %}
+            {{ "self.{}"|format(field.name())|write_var("buf", field.type_()) }}
+            {%- endfor %}
     {% endfor %}
 
     # For each variant, we have an `is_NAME` method for easily checking
@@ -65,28 +67,14 @@ class {{ e|type_name }}(ViaFfiUsingByteBuffer, object):
         return isinstance(self, {{ e|type_name }}.{{ variant.name()|enum_variant_py }})
     {% endfor %}
 
+    # Each variant writes itself, and we look up the reader for a variant by its index,
+    # so that lifting enums with many variants doesn't have to test for each one in turn.
     @classmethod
     def _read(cls, buf):
         variant = buf.readI32()
-
-        {% for variant in e.variants() -%}
-        if variant == {{ loop.index }}:
-            return cls.{{variant.name()|enum_variant_py}}(
-                {%- for field in variant.fields() %}
-                {{ field.name()|var_name }}={{ "buf"|read_var(field.type_()) }},
-                {%- endfor %}
-            )
-        {% endfor %}
-        raise InternalError("Raw enum value doesn't match any cases")
-
-    def _write(self, buf):
-        {% for variant in e.variants() -%}
-        if self.is_{{ variant.name()|var_name }}():
-            buf.writeI32({{ loop.index }})
-            {%- for field in variant.fields() %}
-            {{ "self.{}"|format(field.name())|write_var("buf", field.type_()) }}
-            {%- endfor %}
-        {% endfor %}
+        if not 1 <= variant <= len(cls._variant_readers):
+            raise InternalError("Raw enum value doesn't match any cases")
+        return cls._variant_readers[variant - 1](buf)
 
 # Now, a little trick - we make each nested variant class be a subclass of the main
 # enum class, so that method calls and instance checks etc will work intuitively.
@@ -95,4 +83,14 @@ class {{ e|type_name }}(ViaFfiUsingByteBuffer, object):
 {{ e|type_name }}.{{ variant.name()|enum_variant_py }} = type("{{ e|type_name }}.{{ variant.name()|enum_variant_py }}", ({{ e|type_name }}.{{variant.name()|enum_variant_py}}, {{ e|type_name }},), {})
 {% endfor %}
 
+{{ e|type_name }}._variant_readers = (
+    {%- for variant in e.variants() %}
+    lambda buf: {{ e|type_name }}.{{ variant.name()|enum_variant_py }}(
+        {%- for field in variant.fields() %}
+        {{ field.name()|var_name }}={{ "buf"|read_var(field.type_()) }},
+        {%- endfor %}
+    ),
+    {%- endfor %}
+)
+
 {% endif %}
@@ -11,16 +11,33 @@ class {{ e|type_name }}(ViaFfiUsingByteBuffer):
             buf.writeI32({{ loop.index }})
             message = str(self)
             {{ "message"|write_var("buf", Type::String) }}
+            {%- match e.source_chain_type() %}
+            {%- when Some with (chain_type) %}
+            {{ "_uniffi_error_source_messages(self)"|write_var("buf", chain_type) }}
+            {%- when None %}
+            {%- endmatch %}
     {%- endfor %}
 
+    # Look up the class for a variant by its index, so that lifting errors with many
+    # variants doesn't have to test for each one in turn.
+    _variant_classes = (
+        {%- for variant in e.variants() %}
+        {{ variant.name()|class_name }},
+        {%- endfor %}
+    )
+
     @classmethod
     def _read(cls, buf):
         variant = buf.readI32()
-        {% for variant in e.variants() -%}
-        if variant == {{ loop.index }}:
-            return cls.{{ variant.name()|class_name }}({{ "buf"|read_var(Type::String) }})
-        {% endfor %}
-        raise InternalError("Raw enum value doesn't match any cases")
+        if not 1 <= variant <= len(cls._variant_classes):
+            raise InternalError("Raw enum value doesn't match any cases")
+        error = cls._variant_classes[variant - 1]({{ "buf"|read_var(Type::String) }})
+        {%- match e.source_chain_type() %}
+        {%- when Some with (chain_type) %}
+        error.__cause__ = _uniffi_error_source_chain({{ "buf"|read_var(chain_type) }})
+        {%- when None %}
+        {%- endmatch %}
+        return error
 
     {%- else %}
 
@@ -53,19 +70,36 @@ class {{ e|type_name }}(ViaFfiUsingByteBuffer):
             {%- for field in variant.fields() %}
             {{ "self.{}"|format(field.name()) |write_var("buf", field.type_()) }}
             {%- endfor %}
+            {%- match e.source_chain_type() %}
+            {%- when Some with (chain_type) %}
+            {{ "_uniffi_error_source_messages(self)"|write_var("buf", chain_type) }}
+            {%- when None %}
+            {%- endmatch %}
     {%- endfor %}
 
+    # Look up the reader for a variant by its index, so that lifting errors with many
+    # variants doesn't have to test for each one in turn.
     @classmethod
     def _read(cls, buf):
         variant = buf.readI32()
-        {% for variant in e.variants() -%}
-        if variant == {{ loop.index }}:
-            return cls.{{ variant.name()|class_name }}(
-                {% for field in variant.fields() -%}
-                {{ field.name()|var_name }}={{ "buf"|read_var(field.type_()) }}{% if loop.last %}{% else %},{% endif %}
-                {% endfor -%}
-            )
-        {% endfor %}
-        raise InternalError("Raw enum value doesn't match any cases")
+        if not 1 <= variant <= len(cls._variant_readers):
+            raise InternalError("Raw enum value doesn't match any cases")
+        error = cls._variant_readers[variant - 1](buf)
+        {%- match e.source_chain_type() %}
+        {%- when Some with (chain_type) %}
+        error.__cause__ = _uniffi_error_source_chain({{ "buf"|read_var(chain_type) }})
+        {%- when None %}
+        {%- endmatch %}
+        return error
+
+{{ e|type_name }}._variant_readers = (
+    {%- for variant in e.variants() %}
+    lambda buf: {{ e|type_name }}.{{ variant.name()|class_name }}(
+        {%- for field in variant.fields() %}
+        {{ field.name()|var_name }}={{ "buf"|read_var(field.type_()) }},
+        {%- endfor %}
+    ),
+    {%- endfor %}
+)
 
     {%- endif %}
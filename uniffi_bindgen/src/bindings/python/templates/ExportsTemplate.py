@@ -0,0 +1,24 @@
+{%- let type_name = format!("{}Export", self.namespace)|class_name %}
+class {{ type_name }}(enum.Enum):
+    """
+    Every function, constructor and method exported by this component.
+
+    This lets routing layers map names onto calls without keeping their own list. The
+    `signature_hash` of an entry changes whenever its signature does.
+    """
+    {%- for export in self.exports %}
+    {{ export.qualified_name()|enum_variant_py }} = ("{{ export.kind() }}", "{{ export.qualified_name() }}", "{{ export.signature_hash() }}")
+    {%- endfor %}
+
+    def __init__(self, kind, qualified_name, signature_hash):
+        self.kind = kind
+        self.qualified_name = qualified_name
+        self.signature_hash = signature_hash
+
+    @classmethod
+    def from_qualified_name(cls, name):
+        """Look up an export by its qualified name, e.g. `"Counter.get"` for a method."""
+        for export in cls:
+            if export.qualified_name == name:
+                return export
+        return None
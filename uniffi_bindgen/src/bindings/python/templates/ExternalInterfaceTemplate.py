@@ -0,0 +1,30 @@
+{%- let name = self.name() %}
+{%- let module = self.crate_name()|fn_name %}
+
+class FfiConverter{{ name }}:
+    # `{{ name }}` objects belong to the `{{ module }}` module. They're passed to and from this
+    # library as pointers to the same Rust object, rather than being copied.
+    @staticmethod
+    def _class():
+        import {{ module }}
+        _check_external_component("{{ module }}", {{ module }})
+        return {{ module }}.{{ name }}
+
+    @staticmethod
+    def _lower(value):
+        cls = FfiConverter{{ name }}._class()
+        if not isinstance(value, cls):
+            raise TypeError("Expected {{ name }} instance, {} found".format(value.__class__.__name__))
+        return value._lower()
+
+    @staticmethod
+    def _lift(pointer):
+        return FfiConverter{{ name }}._class()._lift(pointer)
+
+    @staticmethod
+    def _write(value, buf):
+        FfiConverter{{ name }}._class()._write(value, buf)
+
+    @staticmethod
+    def _read(buf):
+        return FfiConverter{{ name }}._class()._read(buf)
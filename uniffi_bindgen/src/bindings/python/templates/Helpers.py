@@ -4,6 +4,190 @@
 class InternalError(Exception):
     pass
 
+class InvalidStringError(Exception):
+    """Raised when Rust is passed a string that isn't valid UTF-8, which Rust reports as such rather than panicking."""
+    pass
+
+# What the constructor of a record takes for a field whose default is an empty list or dict,
+# so that it can make a new one for each record.
+_UNIFFI_DEFAULT = object()
+
+class UniffiErrorSource(Exception):
+    """One of the sources of a Rust error, for errors declared with `[SourceChain]`."""
+    pass
+
+def _uniffi_error_source_chain(messages):
+    # Build the chain innermost first, so that each source is the `__cause__` of the one before.
+    cause = None
+    for message in reversed(messages):
+        source = UniffiErrorSource(message)
+        source.__cause__ = cause
+        cause = source
+    return cause
+
+def _uniffi_error_source_messages(error):
+    messages = []
+    cause = error.__cause__
+    while cause is not None:
+        messages.append(str(cause))
+        cause = cause.__cause__
+    return messages
+
+def _uniffi_module(path):
+    # Each nested namespace is a submodule of this one, registered in `sys.modules`
+    # so that it can be imported by name.
+    module = types.ModuleType("{}.{}".format(__name__, path))
+    sys.modules[module.__name__] = module
+    return module
+
+class _UniffiCloseState:
+    """
+    Tracks whether a `[Closeable]` object has been closed, and how many calls are using its
+    pointer, so that the underlying Rust struct is freed exactly once and only after the last
+    in-flight call has finished.
+    """
+
+    def __init__(self):
+        self._lock = threading.Lock()
+        self._closed = False
+        self._calls = 0
+
+    # Claim the pointer for a call, returning False if the object has been closed.
+    def enter(self):
+        with self._lock:
+            if self._closed:
+                return False
+            self._calls += 1
+            return True
+
+    # Finish a call, returning True if the pointer should now be freed.
+    def exit(self):
+        with self._lock:
+            self._calls -= 1
+            return self._closed and self._calls == 0
+
+    # Mark the object as closed, returning True if the pointer should now be freed.
+    def close(self):
+        with self._lock:
+            if self._closed:
+                return False
+            self._closed = True
+            return self._calls == 0
+
+# The `[Closeable]` objects lowered into the arguments of each call that's in progress on this
+# thread, which can't be freed until Rust has taken them.
+_uniffi_claimed_args = threading.local()
+
+def _uniffi_call_claiming_args(call):
+    # Make a call into Rust, during which any `[Closeable]` objects lowered into its
+    # arguments are claimed, so that closing them on another thread waits for it to finish.
+    stack = getattr(_uniffi_claimed_args, "stack", None)
+    if stack is None:
+        stack = _uniffi_claimed_args.stack = []
+    stack.append([])
+    try:
+        return call()
+    finally:
+        for obj in stack.pop():
+            obj._uniffi_release_pointer()
+
+def _uniffi_claim_arg(obj):
+    # Claim the pointer of a `[Closeable]` object that's being lowered, until the call that it's
+    # an argument of has finished.
+    pointer = obj._uniffi_claim_pointer()
+    stack = getattr(_uniffi_claimed_args, "stack", None)
+    if stack:
+        stack[-1].append(obj)
+    else:
+        # It's being returned to Rust rather than passed to it, so there's no call to wait for.
+        obj._uniffi_release_pointer()
+    return pointer
+
+# Checks for the arguments of the generated functions and methods, which run before they're
+# lowered, so that a value that Rust can't take raises a `ValueError` naming the argument, rather
+# than an opaque error from deep inside the code that writes it into a buffer. Each check returns
+# what's wrong with a value, or None if there's nothing wrong with it.
+def _uniffi_check_arg(name, value, check):
+    problem = check(value)
+    if problem is not None:
+        raise ValueError("Invalid arg '{}': {}".format(name, problem))
+
+def _uniffi_check_not_none(value):
+    if value is None:
+        return "expected a value, got None"
+    return None
+
+def _uniffi_check_integer(low, high):
+    def check(value):
+        if value is None:
+            return "expected an integer, got None"
+        try:
+            number = int(value)
+        except (TypeError, ValueError):
+            return "expected an integer, got {!r}".format(value)
+        if not low <= number <= high:
+            return "{} is out of range, it must be between {} and {}".format(number, low, high)
+        return None
+    return check
+
+def _uniffi_check_float(value):
+    if value is None:
+        return "expected a number, got None"
+    try:
+        float(value)
+    except (TypeError, ValueError):
+        return "expected a number, got {!r}".format(value)
+    return None
+
+def _uniffi_check_char(value):
+    if not isinstance(value, str) or len(value) != 1:
+        return "expected a string of one character, got {!r}".format(value)
+    if 0xD800 <= ord(value) <= 0xDFFF:
+        return "expected a Unicode scalar value, got the surrogate {!r}".format(value)
+    return None
+
+def _uniffi_check_instance(cls, description):
+    def check(value):
+        if not isinstance(value, cls):
+            return "expected {}, got {!r}".format(description, value)
+        return None
+    return check
+
+def _uniffi_check_optional(inner):
+    def check(value):
+        if value is None:
+            return None
+        return inner(value)
+    return check
+
+def _uniffi_check_sequence(inner):
+    def check(value):
+        if value is None or isinstance(value, (str, bytes)) or not hasattr(value, "__iter__"):
+            return "expected a sequence, got {!r}".format(value)
+        # Other iterables could be used up by checking them, so only lists and tuples have their
+        # items checked.
+        if inner is not None and isinstance(value, (list, tuple)):
+            for (index, item) in enumerate(value):
+                problem = inner(item)
+                if problem is not None:
+                    return "item {}: {}".format(index, problem)
+        return None
+    return check
+
+def _uniffi_check_map(key_check, value_check):
+    def check(value):
+        if not hasattr(value, "items"):
+            return "expected a dict, got {!r}".format(value)
+        for (k, v) in value.items():
+            problem = None if key_check is None else key_check(k)
+            if problem is not None:
+                return "key {!r}: {}".format(k, problem)
+            problem = None if value_check is None else value_check(v)
+            if problem is not None:
+                return "value for key {!r}: {}".format(k, problem)
+        return None
+    return check
+
 class RustCallStatus(ctypes.Structure):
     """
     Error runtime.
@@ -17,6 +201,7 @@ class RustCallStatus(ctypes.Structure):
     CALL_SUCCESS = 0
     CALL_ERROR = 1
     CALL_PANIC = 2
+    CALL_INVALID_STRING = 3
 
     def __str__(self):
         if self.code == RustCallStatus.CALL_SUCCESS:
@@ -24,10 +209,45 @@ class RustCallStatus(ctypes.Structure):
         elif self.code == RustCallStatus.CALL_ERROR:
             return "RustCallStatus(CALL_ERROR)"
         elif self.code == RustCallStatus.CALL_PANIC:
-            return "RustCallStatus(CALL_SUCCESS)"
+            return "RustCallStatus(CALL_PANIC)"
+        elif self.code == RustCallStatus.CALL_INVALID_STRING:
+            return "RustCallStatus(CALL_INVALID_STRING)"
         else:
             return "RustCallStatus(<invalid code>)"
 
+# With `errors_as_values`, the functions and methods that raise return the error that they
+# declare instead, for the caller to check for with `isinstance`. Any other exception, such as
+# the `InternalError` for a panic, is still raised. The variants of the error are nested classes
+# of it, since the error class itself isn't an exception.
+def _uniffi_is_error_variant(error_class, error):
+    return any(variant is type(error) for variant in vars(error_class).values())
+
+def _uniffi_errors_as_values(error_class):
+    def decorator(func):
+        @functools.wraps(func)
+        def wrapper(*args, **kwargs):
+            try:
+                return func(*args, **kwargs)
+            except Exception as error:
+                if _uniffi_is_error_variant(error_class, error):
+                    return error
+                raise
+        return wrapper
+    return decorator
+
+def _uniffi_async_errors_as_values(error_class):
+    def decorator(func):
+        @functools.wraps(func)
+        async def wrapper(*args, **kwargs):
+            try:
+                return await func(*args, **kwargs)
+            except Exception as error:
+                if _uniffi_is_error_variant(error_class, error):
+                    return error
+                raise
+        return wrapper
+    return decorator
+
 def rust_call(fn, *args):
     # Call a rust function
     return rust_call_with_error(None, fn, *args)
@@ -45,7 +265,7 @@ def rust_call_with_error(error_class, fn, *args):
         return result
     elif call_status.code == RustCallStatus.CALL_ERROR:
         if error_class is None:
-            call_status.err_buf.contents.free()
+            call_status.error_buf.free()
             raise InternalError("rust_call_with_error: CALL_ERROR, but no error class set")
         else:
             raise error_class._lift(call_status.error_buf)
@@ -58,6 +278,8 @@ def rust_call_with_error(error_class, fn, *args):
         else:
             msg = "Unknown rust panic"
         raise InternalError(msg)
+    elif call_status.code == RustCallStatus.CALL_INVALID_STRING:
+        raise InvalidStringError(FfiConverterString._lift(call_status.error_buf))
     else:
         raise InternalError("Invalid RustCallStatus code: {}".format(
             call_status.code))
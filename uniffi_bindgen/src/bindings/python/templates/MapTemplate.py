@@ -1,3 +1,4 @@
+{%- let key_type = self.key() %}
 {%- let inner_type = self.inner() %}
 {%- let outer_type = self.outer() %}
 {%- let canonical_type_name = outer_type|canonical_name %}
@@ -6,7 +7,7 @@ class FfiConverter{{ canonical_type_name }}(FfiConverterUsingByteBuffer):
     @staticmethod
     def _write(value, buf):
         def inner_write(key, value, buf):
-            {{ "key"|write_var("buf", Type::String) }}
+            {{ "key"|write_var("buf", key_type) }}
             {{ "value"|write_var("buf", inner_type) }}
 
         FfiConverterDictionary._write(value, buf, inner_write)
@@ -14,7 +15,7 @@ class FfiConverter{{ canonical_type_name }}(FfiConverterUsingByteBuffer):
     @staticmethod
     def _read(buf):
         def inner_read(buf):
-            key = {{ "buf"|read_var(TypeIdentifier::String) }}
+            key = {{ "buf"|read_var(key_type) }}
             value = {{ "buf"|read_var(inner_type) }}
             return (key, value)
 
@@ -0,0 +1,7 @@
+{% import "macros.py" as py %}
+{%- let module = self.inner() %}
+
+{{ module.path()|join(".") }} = _uniffi_module("{{ module.qualified_name() }}")
+{%- for func in self.functions() %}
+{{ module.path()|join(".") }}.{{ func.name()|fn_name }} = {% call py::fn_def_name(func) %}
+{%- endfor %}
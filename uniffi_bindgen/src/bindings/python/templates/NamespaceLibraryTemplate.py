@@ -21,13 +21,183 @@ def loadIndirect():
         )
     return getattr(ctypes.cdll, libname.format("{{ config.cdylib_name() }}"))
 
+# The version of `uniffi-bindgen` that generated these bindings.
+UNIFFI_BINDGEN_VERSION = "{{ self.bindgen_version() }}"
+
+# The checksum of the component interface that these bindings were generated from. The library
+# is checked against it when it's loaded.
+UNIFFI_COMPONENT_CHECKSUM = "{{ "{:016x}"|format(ci.checksum()) }}"
+
+def _check_checksum(lib):
+    # Check that the library was built from the same component interface as these bindings,
+    # before declaring its other functions, which would fail on the first one that's missing.
+    {%- let func = ci.ffi_checksum() %}
+    try:
+        checksum_fn = lib.{{ func.name() }}
+    except AttributeError:
+        raise InternalError(
+            "The {{ ci.namespace() }} library doesn't report its checksum, so it's older than these bindings, "
+            "which uniffi-bindgen {} generated. Regenerate the bindings from the library's UDL.".format(UNIFFI_BINDGEN_VERSION)
+        )
+    checksum_fn.argtypes = (
+        {%- call py::arg_list_ffi_decl(func) -%}
+    )
+    checksum_fn.restype = ctypes.c_uint64
+    call_status = RustCallStatus(code=RustCallStatus.CALL_SUCCESS, error_buf=RustBuffer(0, 0, None))
+    checksum = "{:016x}".format(checksum_fn(ctypes.byref(call_status)))
+    if checksum != UNIFFI_COMPONENT_CHECKSUM:
+        raise InternalError(
+            "The {{ ci.namespace() }} library was built with checksum {}, but these bindings, "
+            "which uniffi-bindgen {} generated, expect {}. Regenerate the bindings from the library's UDL.".format(
+                checksum, UNIFFI_BINDGEN_VERSION, UNIFFI_COMPONENT_CHECKSUM
+            )
+        )
+
+{% let external_crates = ci.iter_external_interface_crates() %}
+{%- if !external_crates.is_empty() -%}
+# The functions that report the checksums of the other components whose objects this library
+# takes and returns, as it was built against them.
+_uniffi_external_checksum_fns = {
+    {%- for crate_name in external_crates %}
+    "{{ crate_name|fn_name }}": "{{ ci.ffi_external_checksum(crate_name).name() }}",
+    {%- endfor %}
+}
+_uniffi_checked_components = set()
+
+def _check_external_component(module_name, module):
+    # The objects of another component are passed to this library as pointers to the Rust
+    # structs behind them, so both libraries have to be built from the same interface for it.
+    if module_name in _uniffi_checked_components:
+        return
+    checksum_fn = getattr(_UniFFILib, _uniffi_external_checksum_fns[module_name])
+    checksum = "{:016x}".format(rust_call(checksum_fn))
+    if checksum != module.UNIFFI_COMPONENT_CHECKSUM:
+        raise InternalError(
+            "The {{ ci.namespace() }} library was built against the {} component with checksum {}, "
+            "but the {} bindings expect {}. Build both libraries from the same source.".format(
+                module_name, checksum, module_name, module.UNIFFI_COMPONENT_CHECKSUM
+            )
+        )
+    _uniffi_checked_components.add(module_name)
+
+{% endif -%}
+def _declare_ffi_functions(lib):
+    {%- for func in ci.iter_ffi_function_definitions() %}
+    lib.{{ func.name() }}.argtypes = (
+        {%- call py::arg_list_ffi_decl(func) -%}
+    )
+    lib.{{ func.name() }}.restype = {% match func.return_type() %}{% when Some with (type_) %}{{ type_|ffi_type_name }}{% when None %}None{% endmatch %}
+    {%- endfor %}
+
+# The objects that hold a pointer into the library, so that `reset_library()` can free them
+# before it unloads the library. They're keyed by `id()` rather than kept in a `WeakSet`,
+# because `[OpaqueToken]` handles to the same Rust object compare equal.
+_uniffi_live_objects = weakref.WeakValueDictionary()
+
+# Set once the library has been shut down, after which objects no longer free their Rust structs
+# when they're garbage-collected, because the interpreter might be too far gone to call it.
+_uniffi_shut_down = False
+
+def _unload_library(lib):
+    # ctypes doesn't unload libraries itself. The operating system only really unloads it once
+    # nothing else is using it, and a library that has thread-local destructors pending may
+    # never be unloaded at all, in which case loading it again gives back the same code.
+    import _ctypes
+    if sys.platform.startswith("win"):
+        _ctypes.FreeLibrary(lib._handle)
+    else:
+        _ctypes.dlclose(lib._handle)
+
+class _UniFFILibLoader(object):
+    # Loads the library the first time one of its functions is needed, unless `load_library()`
+    # has already loaded it from an explicit path. The lock makes sure that only one thread
+    # ever loads it.
+    def __init__(self):
+        self._lock = threading.Lock()
+        self._lib = None
+
+    def _load(self, path=None):
+        with self._lock:
+            if self._lib is not None:
+                if path is not None:
+                    raise InternalError("load_library: the library has already been loaded")
+                return self._lib
+            return self._open(path)
+
+    def _open(self, path):
+        # Called with the lock held.
+        if path is None:
+            lib = loadIndirect()
+        else:
+            lib = ctypes.CDLL(path)
+        _check_checksum(lib)
+        _declare_ffi_functions(lib)
+        {%- match ci.ffi_async_init() %}
+        {%- when Some with (ffi_init) %}
+        # Rust completes the calls to `[Async]` functions and methods through this.
+        rust_call(lib.{{ ffi_init.name() }}, _uniffi_async_completion)
+        {%- when None %}
+        {%- endmatch %}
+        self._lib = lib
+        return lib
+
+    def _reset(self):
+        with self._lock:
+            lib = self._lib
+            if lib is None:
+                # Nothing has used the library yet, so there's nothing to reset.
+                return
+            for obj in list(_uniffi_live_objects.values()):
+                obj._uniffi_reset()
+            rust_call(lib.{{ ci.ffi_reset().name() }})
+            self._lib = None
+            _unload_library(lib)
+            # Load it again from the same file, even if that was found by name.
+            self._open(lib._name)
+
+    def _shutdown(self):
+        global _uniffi_shut_down
+        with self._lock:
+            lib = self._lib
+            if lib is None:
+                return
+            rust_call(lib.{{ ci.ffi_shutdown().name() }})
+            _uniffi_shut_down = True
+            _uniffi_live_objects.clear()
+
+    def __getattr__(self, name):
+        lib = self._lib
+        if lib is None:
+            lib = self._load()
+        return getattr(lib, name)
+
 # A ctypes library to expose the extern-C FFI definitions.
 # This is an implementation detail which will be called internally by the public API.
 
-_UniFFILib = loadIndirect()
-{%- for func in ci.iter_ffi_function_definitions() %}
-_UniFFILib.{{ func.name() }}.argtypes = (
-    {%- call py::arg_list_ffi_decl(func) -%}
-)
-_UniFFILib.{{ func.name() }}.restype = {% match func.return_type() %}{% when Some with (type_) %}{{ type_|ffi_type_name }}{% when None %}None{% endmatch %}
-{%- endfor %}
+_UniFFILib = _UniFFILibLoader()
+
+# Shut the library down when the interpreter starts to exit, while it can still run Python code,
+# so that the component stops calling back into it while it's torn down. Calls into the component
+# raise an `InternalError` after this.
+atexit.register(_UniFFILib._shutdown)
+
+def load_library(path):
+    """
+    Load the component's native library from an explicit path, rather than looking it up by
+    name the first time it's needed. This must be called before anything else in the module
+    is used, and raises `InternalError` if the library has already been loaded.
+    """
+    _UniFFILib._load(path)
+
+def reset_library():
+    """
+    Start over with a fresh copy of the component's native library, without restarting the
+    process, for use during development.
+
+    This frees the Rust object behind every object of the component that's still alive, after
+    which calling their methods raises a `ValueError`. It then runs the reset hooks that the
+    component registered with `uniffi::on_reset`, and reloads the library from the same file,
+    which picks up a library that has been rebuilt in the meantime if the operating system
+    could unload the old one. It mustn't be called while other threads are using the component.
+    """
+    _UniFFILib._reset()
@@ -1,28 +1,129 @@
 {% import "macros.py" as py %}
 {%- let obj = self.inner() %}
 
-class {{ obj|type_name }}(object):
+class {{ obj|type_name }}({{ self.base_class() }}):
+    # The threads that this object can be used from, for tools that check the callers:
+    # "threadsafe", "main-thread-only" or "blocking".
+    UNIFFI_THREADING = "{{ obj.threading().as_str() }}"
+
     {%- match obj.primary_constructor() %}
     {%- when Some with (cons) %}
     def __init__(self, {% call py::arg_list_decl(cons) -%}):
         {%- call py::coerce_args_extra_indent(cons) %}
+        {%- if obj.is_closeable() %}
+        self._close_state = _UniffiCloseState()
+        {%- endif %}
         self._pointer = {% call py::to_ffi_call(cons) %}
+        _uniffi_live_objects[id(self)] = self
     {%- when None %}
     {%- endmatch %}
 
     def __del__(self):
+        if _uniffi_shut_down:
+            return
         # In case of partial initialization of instances.
         pointer = getattr(self, "_pointer", None)
+        {%- if obj.is_closeable() %}
+        if pointer is not None and self._close_state.close():
+        {%- else %}
         if pointer is not None:
+        {%- endif %}
             rust_call(_UniFFILib.{{ obj.ffi_object_free().name() }}, pointer)
 
+    # Called by `reset_library()`, to free the Rust object before the library is unloaded.
+    def _uniffi_reset(self):
+        {%- if obj.is_closeable() %}
+        self.close()
+        {%- else %}
+        pointer, self._pointer = self._pointer, None
+        if pointer is not None:
+            rust_call(_UniFFILib.{{ obj.ffi_object_free().name() }}, pointer)
+
+    def _uniffi_pointer(self):
+        if self._pointer is None:
+            raise ValueError("{{ obj|type_name }} object was freed by reset_library()")
+        return self._pointer
+        {%- endif %}
+
+    {%- if obj.is_closeable() %}
+
+    def close(self):
+        """
+        Free the underlying Rust object without waiting to be garbage-collected.
+
+        It can be called more than once, but once called, calling any other method
+        on the object raises a `ValueError`.
+        """
+        if self._close_state.close():
+            rust_call(_UniFFILib.{{ obj.ffi_object_free().name() }}, self._pointer)
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, exc_type, exc_value, traceback):
+        self.close()
+
+    def _uniffi_claim_pointer(self):
+        if not self._close_state.enter():
+            raise ValueError("{{ obj|type_name }} object has already been closed")
+        return self._pointer
+
+    def _uniffi_release_pointer(self):
+        if self._close_state.exit():
+            rust_call(_UniFFILib.{{ obj.ffi_object_free().name() }}, self._pointer)
+    {%- endif %}
+
+    {%- match obj.ffi_object_clone() %}
+    {%- when Some with (ffi_clone) %}
+
+    # Support for `copy.copy()` and `copy.deepcopy()`, which make a new,
+    # independent copy of the underlying Rust object.
+    def __copy__(self):
+        {%- if obj.is_closeable() %}
+        pointer = self._uniffi_claim_pointer()
+        try:
+            return self._make_instance_(rust_call(_UniFFILib.{{ ffi_clone.name() }}, pointer))
+        finally:
+            self._uniffi_release_pointer()
+        {%- else %}
+        return self._make_instance_(rust_call(_UniFFILib.{{ ffi_clone.name() }}, self._uniffi_pointer()))
+        {%- endif %}
+
+    def __deepcopy__(self, memo):
+        return self.__copy__()
+    {%- when None %}
+    {%- endmatch %}
+
+    {%- match obj.ffi_object_downgrade() %}
+    {%- when Some with (ffi_downgrade) %}
+
+    def downgrade(self):
+        """
+        Make a weak reference to the underlying Rust object, which doesn't keep it alive.
+        """
+        {%- if obj.is_closeable() %}
+        pointer = self._uniffi_claim_pointer()
+        try:
+            return {{ obj|type_name }}WeakRef(rust_call(_UniFFILib.{{ ffi_downgrade.name() }}, pointer))
+        finally:
+            self._uniffi_release_pointer()
+        {%- else %}
+        return {{ obj|type_name }}WeakRef(rust_call(_UniFFILib.{{ ffi_downgrade.name() }}, self._uniffi_pointer()))
+        {%- endif %}
+    {%- when None %}
+    {%- endmatch %}
+
     # Used by alternative constructors or any methods which return this type.
     @classmethod
     def _make_instance_(cls, pointer):
         # Lightly yucky way to bypass the usual __init__ logic
         # and just create a new instance with the required pointer.
         inst = cls.__new__(cls)
+        {%- if obj.is_closeable() %}
+        inst._close_state = _UniffiCloseState()
+        {%- endif %}
         inst._pointer = pointer
+        _uniffi_live_objects[id(inst)] = inst
         return inst
 
     {% for cons in obj.alternate_constructors() -%}
@@ -35,21 +136,82 @@ class {{ obj|type_name }}(object):
     {% endfor %}
 
     {% for meth in obj.methods() -%}
+    {%- let as_result = self.errors_as_values() && meth.throws().is_some() %}
+    {%- if meth.is_async() %}
+    {% if as_result %}@_uniffi_async_errors_as_values({{ meth.throws().unwrap()|class_name }})
+    {% endif -%}
+    async def {{ meth.name()|fn_name }}(self, {% call py::arg_list_decl(meth) %}):
+        {%- call py::coerce_args_extra_indent(meth) %}
+        {%- if obj.is_closeable() %}
+        pointer = self._uniffi_claim_pointer()
+        try:
+            return {% call py::to_async_ffi_call_with_prefix("pointer", meth) %}
+        finally:
+            self._uniffi_release_pointer()
+        {%- else %}
+        return {% call py::to_async_ffi_call_with_prefix("self._uniffi_pointer()", meth) %}
+        {%- endif %}
+    {% else %}
     {%- match meth.return_type() -%}
 
     {%- when Some with (return_type) -%}
+    {% if as_result %}@_uniffi_errors_as_values({{ meth.throws().unwrap()|class_name }})
+    {% endif -%}
     def {{ meth.name()|fn_name }}(self, {% call py::arg_list_decl(meth) %}):
         {%- call py::coerce_args_extra_indent(meth) %}
-        _retval = {% call py::to_ffi_call_with_prefix("self._pointer", meth) %}
+        {%- if meth.is_cached() %}
+        # `[Cached]`: keep the value from the first call that succeeds.
+        if "_uniffi_cached_{{ meth.name() }}" in self.__dict__:
+            return self._uniffi_cached_{{ meth.name() }}
+        {%- endif %}
+        {%- if obj.is_closeable() %}
+        pointer = self._uniffi_claim_pointer()
+        try:
+            _retval = {% call py::to_ffi_call_with_prefix("pointer", meth) %}
+        finally:
+            self._uniffi_release_pointer()
+        {%- else %}
+        _retval = {% call py::to_ffi_call_with_prefix("self._uniffi_pointer()", meth) %}
+        {%- endif %}
+        {%- if meth.is_no_copy() %}
+        return OwnedBytes(_retval)
+        {%- else if meth.is_cached() %}
+        self._uniffi_cached_{{ meth.name() }} = {{ "_retval"|lift_var(return_type) }}
+        return self._uniffi_cached_{{ meth.name() }}
+        {%- else %}
         return {{ "_retval"|lift_var(return_type) }}
+        {%- endif %}
 
     {%- when None -%}
+    {% if as_result %}@_uniffi_errors_as_values({{ meth.throws().unwrap()|class_name }})
+    {% endif -%}
     def {{ meth.name()|fn_name }}(self, {% call py::arg_list_decl(meth) %}):
         {%- call py::coerce_args_extra_indent(meth) %}
-        {% call py::to_ffi_call_with_prefix("self._pointer", meth) %}
+        {%- if obj.is_closeable() %}
+        pointer = self._uniffi_claim_pointer()
+        try:
+            {% call py::to_ffi_call_with_prefix("pointer", meth) %}
+        finally:
+            self._uniffi_release_pointer()
+        {%- else %}
+        {% call py::to_ffi_call_with_prefix("self._uniffi_pointer()", meth) %}
+        {%- endif %}
     {% endmatch %}
+    {%- endif %}
     {% endfor %}
 
+    {%- if obj.stream_item().is_some() %}
+
+    # Iterating over a `[Stream]` takes its items from Rust a chunk at a time. A stream can only be
+    # iterated over once: iterating again only gets the items that no earlier iteration has taken.
+    def __iter__(self):
+        while True:
+            chunk = self.next_chunk(64)
+            if not chunk:
+                return
+            yield from chunk
+    {%- endif %}
+
     @classmethod
     def _read(cls, buf):
         ptr = buf.readU64()
@@ -59,13 +221,52 @@ class {{ obj|type_name }}(object):
 
     @classmethod
     def _write(cls, value, buf):
+        buf.writeU64(cls._check_lower(value))
+
+    # Lower an argument, which might not be one of these at all, or the pointer of the wrong
+    # object would be passed to Rust.
+    @classmethod
+    def _check_lower(cls, value):
         if not isinstance(value, {{ obj|type_name }}):
             raise TypeError("Expected {{ obj|type_name }} instance, {} found".format(value.__class__.__name__))
-        buf.writeU64(value._lower())
+        return value._lower()
 
     @classmethod
     def _lift(cls, pointer):
         return cls._make_instance_(pointer)
 
     def _lower(self):
-        return self._pointer
+        {%- if obj.is_closeable() %}
+        return _uniffi_claim_arg(self)
+        {%- else %}
+        return self._uniffi_pointer()
+        {%- endif %}
+{%- match obj.ffi_object_upgrade() %}
+{%- when Some with (ffi_upgrade) %}
+
+class {{ obj|type_name }}WeakRef:
+    """
+    A weak reference to a `{{ obj|type_name }}`, which doesn't keep the underlying Rust object
+    alive, for caches that would rather not hold on to it.
+    """
+
+    def __init__(self, pointer):
+        self._pointer = pointer
+
+    def __del__(self):
+        if _uniffi_shut_down:
+            return
+        pointer = getattr(self, "_pointer", None)
+        if pointer is not None:
+            rust_call(_UniFFILib.{{ obj.ffi_object_weak_free().unwrap().name() }}, pointer)
+
+    def upgrade(self):
+        """
+        Get the object back, or `None` if the Rust object is gone.
+        """
+        pointer = rust_call(_UniFFILib.{{ ffi_upgrade.name() }}, self._pointer)
+        if pointer == 0:
+            return None
+        return {{ obj|type_name }}._make_instance_(pointer)
+{%- when None %}
+{%- endmatch %}
@@ -0,0 +1,70 @@
+{%- let obj = self.inner() %}
+
+class {{ obj|type_name }}(object):
+    """
+    An opaque handle to a Rust `{{ obj.name() }}`, which can only be passed back to Rust.
+
+    Two handles are equal if they refer to the same Rust object.
+    """
+
+    def __del__(self):
+        if _uniffi_shut_down:
+            return
+        # In case of partial initialization of instances.
+        pointer = getattr(self, "_pointer", None)
+        if pointer is not None:
+            rust_call(_UniFFILib.{{ obj.ffi_object_free().name() }}, pointer)
+
+    # Called by `reset_library()`, to free the Rust object before the library is unloaded.
+    def _uniffi_reset(self):
+        pointer, self._pointer = self._pointer, None
+        if pointer is not None:
+            rust_call(_UniFFILib.{{ obj.ffi_object_free().name() }}, pointer)
+
+    def _uniffi_pointer(self):
+        if self._pointer is None:
+            raise ValueError("{{ obj|type_name }} object was freed by reset_library()")
+        return self._pointer
+
+    # The address stays the same after `reset_library()`, so that the hash does too.
+    def __eq__(self, other):
+        if not isinstance(other, {{ obj|type_name }}):
+            return NotImplemented
+        return self._address == other._address
+
+    def __hash__(self):
+        return hash(self._address)
+
+    @classmethod
+    def _make_instance_(cls, pointer):
+        inst = cls.__new__(cls)
+        inst._pointer = pointer
+        inst._address = pointer
+        _uniffi_live_objects[id(inst)] = inst
+        return inst
+
+    @classmethod
+    def _read(cls, buf):
+        ptr = buf.readU64()
+        if ptr == 0:
+            raise InternalError("Raw pointer value was null")
+        return cls._lift(ptr)
+
+    @classmethod
+    def _write(cls, value, buf):
+        buf.writeU64(cls._check_lower(value))
+
+    # Lower an argument, which might not be one of these at all, or the pointer of the wrong
+    # object would be passed to Rust.
+    @classmethod
+    def _check_lower(cls, value):
+        if not isinstance(value, {{ obj|type_name }}):
+            raise TypeError("Expected {{ obj|type_name }} instance, {} found".format(value.__class__.__name__))
+        return value._lower()
+
+    @classmethod
+    def _lift(cls, pointer):
+        return cls._make_instance_(pointer)
+
+    def _lower(self):
+        return self._uniffi_pointer()
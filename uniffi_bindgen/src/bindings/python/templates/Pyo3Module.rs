@@ -0,0 +1,72 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+//
+// A PyO3 extension module for the `{{ ci.namespace() }}` component. It's `include!`d into the
+// component's crate after the scaffolding, and calls the Rust implementation through the
+// functions that the scaffolding generates to check its signatures, without going through the FFI.
+
+#[allow(non_snake_case, clippy::too_many_arguments, clippy::unused_unit, clippy::let_unit_value)]
+mod uniffi_pyo3 {
+    use pyo3::prelude::*;
+    {%- for e in ci.iter_error_definitions() %}
+
+    pyo3::create_exception!({{ ci.namespace() }}, {{ e.name()|class_name }}, pyo3::exceptions::PyException);
+    {%- endfor %}
+    {%- for func in ci.iter_function_definitions() %}
+
+    #[pyfunction]
+    #[pyo3(name = "{{ func.name()|fn_name }}")]
+    fn {{ func.name() }}({{ func.arguments()|arg_list_pyo3 }}) -> PyResult<{{ func.return_type()|return_type_pyo3 }}> {
+        let ret = super::uniffi_check_{{ func.ffi_func().name() }}({{ func.arguments()|arg_values_pyo3 }}){{ func.throws()|map_err_pyo3 }};
+        Ok({{ func.return_type()|return_value_pyo3 }})
+    }
+    {%- endfor %}
+    {%- for obj in ci.iter_object_definitions() %}
+    {%- let pyclass = obj.name()|pyclass_rs %}
+
+    #[pyclass(name = "{{ obj.name()|class_name }}")]
+    pub struct {{ pyclass }}(std::sync::Arc<super::{{ obj.name()|object_inner_rs }}>);
+
+    #[pymethods]
+    impl {{ pyclass }} {
+        {%- for cons in obj.constructors() %}
+
+        {%- if cons.is_primary_constructor() %}
+        #[new]
+        {%- else %}
+        #[staticmethod]
+        #[pyo3(name = "{{ cons.name()|fn_name }}")]
+        {%- endif %}
+        fn {{ cons.name() }}({{ cons.arguments()|arg_list_pyo3 }}) -> PyResult<Self> {
+            let obj = super::uniffi_check_{{ cons.ffi_func().name() }}({{ cons.arguments()|arg_values_pyo3 }}){{ cons.throws()|map_err_pyo3 }};
+            Ok(Self(std::sync::Arc::new(obj)))
+        }
+        {%- endfor %}
+        {%- for meth in obj.methods() %}
+
+        #[pyo3(name = "{{ meth.name()|fn_name }}")]
+        fn {{ meth.name() }}(&self{% if !meth.arguments().is_empty() %}, {% endif %}{{ meth.arguments()|arg_list_pyo3 }}) -> PyResult<{{ meth.return_type()|return_type_pyo3 }}> {
+            let ret = super::uniffi_check_{{ meth.ffi_func().name() }}(
+                {%- if meth.takes_self_by_arc() %}std::sync::Arc::clone(&self.0){% else %}&self.0{% endif %}
+                {%- if !meth.arguments().is_empty() %}, {% endif %}{{ meth.arguments()|arg_values_pyo3 }}){{ meth.throws()|map_err_pyo3 }};
+            Ok({{ meth.return_type()|return_value_pyo3 }})
+        }
+        {%- endfor %}
+    }
+    {%- endfor %}
+
+    #[pymodule]
+    #[pyo3(name = "{{ ci.namespace() }}")]
+    fn uniffi_pyo3_module(_py: Python, m: &PyModule) -> PyResult<()> {
+        {%- for func in ci.iter_function_definitions() %}
+        m.add_function(wrap_pyfunction!({{ func.name() }}, m)?)?;
+        {%- endfor %}
+        {%- for obj in ci.iter_object_definitions() %}
+        m.add_class::<{{ obj.name()|pyclass_rs }}>()?;
+        {%- endfor %}
+        {%- for e in ci.iter_error_definitions() %}
+        m.add("{{ e.name()|class_name }}", _py.get_type::<{{ e.name()|class_name }}>())?;
+        {%- endfor %}
+        Ok(())
+    }
+}
@@ -1,12 +1,23 @@
 {%- let rec = self.inner() %}
 class {{ rec|type_name }}(ViaFfiUsingByteBuffer, object):
-    def __init__(self,{% for field in rec.fields() %}{{ field.name()|var_name }}{% if loop.last %}{% else %}, {% endif %}{% endfor %}):
+    def __init__(self,{% for field in rec.fields() %}{{ field.name()|var_name }}
+        {%- match rec.trailing_default_value(field) %}
+        {%- when Some with(literal) %}={% if self.collection_default(field).is_some() %}_UNIFFI_DEFAULT{% else %}{{ literal|literal_py(field.type_()) }}{% endif %}
+        {%- else %}
+        {%- endmatch %}{% if loop.last %}{% else %}, {% endif %}{% endfor %}):
         {%- for field in rec.fields() %}
+        {%- match self.collection_default(field) %}
+        {%- when Some with(literal) %}
+        if {{ field.name()|var_name }} is _UNIFFI_DEFAULT:
+            {{ field.name()|var_name }} = {{ literal|literal_py(field.type_()) }}
+        {%- else %}
+        {%- endmatch %}
         self.{{ field.name()|var_name }} = {{ field.name()|var_name }}
         {%- endfor %}
 
+    # `[Sensitive]` fields are left out, so that they don't end up in logs.
     def __str__(self):
-        return "{{ rec|type_name }}({% for field in rec.fields() %}{{ field.name() }}={}{% if loop.last %}{% else %}, {% endif %}{% endfor %})".format({% for field in rec.fields() %}self.{{ field.name() }}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
+        return "{{ rec|type_name }}({% for field in rec.fields() %}{{ field.name() }}={% if field.sensitive() %}***{% else %}{}{% endif %}{% if loop.last %}{% else %}, {% endif %}{% endfor %})".format({% for field in rec.fields() %}{% if !field.sensitive() %}self.{{ field.name() }}{% if loop.last %}{% else %}, {% endif %}{% endif %}{% endfor %})
 
     def __eq__(self, other):
         {%- for field in rec.fields() %}
@@ -53,6 +53,67 @@ class RustBuffer(ctypes.Structure):
         finally:
             self.free()
 
+{%- if self.has_no_copy_methods() %}
+
+
+class OwnedBytes:
+    """The bytes that a `[NoCopy]` method returned, which are left where the Rust code put them
+    rather than copied into a `bytes`.
+
+    They're freed when this is garbage-collected, which a `memoryview()` of them prevents for as
+    long as it's in use, or earlier by calling `free()` or leaving a `with` block, after which no
+    `memoryview()` of them must be used.
+    """
+
+    def __init__(self, rbuf):
+        self._rbuf = rbuf
+        self._lock = threading.Lock()
+        self._freed = False
+
+    def __len__(self):
+        return self._rbuf.len
+
+    def _check_not_freed(self):
+        if self._freed:
+            raise RuntimeError("OwnedBytes has already been freed")
+
+    def memoryview(self):
+        """A read-only view of the bytes, which doesn't copy them."""
+        self._check_not_freed()
+        if self._rbuf.len == 0:
+            return memoryview(b"")
+        array = ctypes.cast(self._rbuf.data, ctypes.POINTER(ctypes.c_ubyte * self._rbuf.len)).contents
+        # The view holds on to the array, which holds on to this, so that the bytes aren't freed
+        # when this is garbage-collected while the view is still in use.
+        array._uniffi_owner = self
+        return memoryview(array).cast("B").toreadonly()
+
+    def tobytes(self):
+        """A copy of the bytes, which can still be used after they're freed."""
+        self._check_not_freed()
+        if self._rbuf.len == 0:
+            return b""
+        return ctypes.string_at(self._rbuf.data, self._rbuf.len)
+
+    def free(self):
+        """Give the bytes back to the Rust code. It can be called more than once."""
+        with self._lock:
+            if self._freed:
+                return
+            self._freed = True
+        self._rbuf.free()
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *exc_info):
+        self.free()
+
+    def __del__(self):
+        if not _uniffi_shut_down:
+            self.free()
+{%- endif %}
+
 
 class ForeignBytes(ctypes.Structure):
     _fields_ = [
@@ -1,16 +1,30 @@
 {% import "macros.py" as py %}
 {%- let func = self.inner() %}
+{%- let as_result = self.errors_as_values() && func.throws().is_some() %}
+{%- if func.is_async() %}
+
+{% if as_result %}@_uniffi_async_errors_as_values({{ func.throws().unwrap()|class_name }})
+{% endif -%}
+async def {% call py::fn_def_name(func) %}({%- call py::arg_list_decl(func) -%}):
+    {%- call py::coerce_args(func) %}
+    return {% call py::to_async_ffi_call(func) %}
+{% else %}
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
 
-def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}):
+{% if as_result %}@_uniffi_errors_as_values({{ func.throws().unwrap()|class_name }})
+{% endif -%}
+def {% call py::fn_def_name(func) %}({%- call py::arg_list_decl(func) -%}):
     {%- call py::coerce_args(func) %}
     _retval = {% call py::to_ffi_call(func) %}
     return {{ "_retval"|lift_var(return_type) }}
 
 {% when None -%}
 
-def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}):
+{% if as_result %}@_uniffi_errors_as_values({{ func.throws().unwrap()|class_name }})
+{% endif -%}
+def {% call py::fn_def_name(func) %}({%- call py::arg_list_decl(func) -%}):
     {%- call py::coerce_args(func) %}
     {% call py::to_ffi_call(func) %}
 {% endmatch %}
+{%- endif %}
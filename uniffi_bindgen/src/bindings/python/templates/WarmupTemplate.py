@@ -0,0 +1,20 @@
+def uniffi_warmup():
+    """
+    Get the Rust library ready on a background thread, so that the first call into it is as
+    quick as the ones after it.
+
+    Call it while the app starts up. It loads the library and checks that it matches these
+    bindings, and runs the hooks that the component registered with `uniffi::on_warmup`. It
+    returns the thread that's doing that, which can be joined to wait for it. If anything fails,
+    the first call into the component fails the same way.
+    """
+    def warm_up():
+        try:
+            rust_call(_UniFFILib.{{ ci.ffi_warmup().name() }})
+        except Exception:
+            # Reported by the first call into the component instead, where the app expects it.
+            pass
+
+    thread = threading.Thread(target=warm_up, name="uniffi-warmup", daemon=True)
+    thread.start()
+    return thread
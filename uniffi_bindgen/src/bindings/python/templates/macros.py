@@ -5,6 +5,7 @@
 #}
 
 {%- macro to_ffi_call(func) -%}
+    {%- call _claiming_args_start(func) -%}
     {%- match func.throws() -%}
     {%- when Some with (e) -%}
 rust_call_with_error({{ e|class_name }},
@@ -13,10 +14,35 @@ rust_call(
     {%- endmatch -%}
     _UniFFILib.{{ func.ffi_func().name() }},
     {%- call _arg_list_ffi_call(func) -%}
-)
+){% call _claiming_args_end(func) %}
+{%- endmacro -%}
+
+{#-
+// Any `[Closeable]` objects in the arguments are claimed while they're lowered, and released
+// when the call returns, so the call is made in a lambda that lowers them.
+#}
+{%- macro _claiming_args_start(func) -%}
+{%- if func.arguments().len() > 0 %}_uniffi_call_claiming_args(lambda: {% endif -%}
+{%- endmacro -%}
+
+{%- macro _claiming_args_end(func) -%}
+{%- if func.arguments().len() > 0 %}){% endif -%}
+{%- endmacro -%}
+
+{#-
+// Functions in nested namespaces are defined under a private name, and then
+// added to their namespace's submodule.
+#}
+{%- macro fn_def_name(func) -%}
+{%- if func.module_path().is_empty() -%}
+{{ func.name()|fn_name }}
+{%- else -%}
+_uniffi_{{ func.module_path()|join("_") }}_{{ func.name()|fn_name }}
+{%- endif -%}
 {%- endmacro -%}
 
 {%- macro to_ffi_call_with_prefix(prefix, func) -%}
+    {%- call _claiming_args_start(func) -%}
     {%- match func.throws() -%}
     {%- when Some with (e) -%}
 rust_call_with_error(
@@ -27,6 +53,37 @@ rust_call(
     _UniFFILib.{{ func.ffi_func().name() }},
     {{- prefix }},
     {%- call _arg_list_ffi_call(func) -%}
+){% call _claiming_args_end(func) %}
+{%- endmacro -%}
+
+{#-
+// Call an `[Async]` function or method, waiting until Rust has completed the call. The handle of
+// the call follows the arguments, and the value that it returns comes back in a buffer.
+#}
+{%- macro to_async_ffi_call(func) -%}
+    {%- call _async_ffi_call("", func) -%}
+{%- endmacro -%}
+
+{%- macro to_async_ffi_call_with_prefix(prefix, func) -%}
+    {%- call _async_ffi_call(format!("{},", prefix), func) -%}
+{%- endmacro -%}
+
+{%- macro _async_ffi_call(prefix, func) -%}
+await _uniffi_async_call(
+    {%- match func.throws() %}{% when Some with (e) %}{{ e|class_name }}{% else %}None{% endmatch %},
+    lambda uniffi_handle: {% call _claiming_args_start(func) %}rust_call_with_error(
+        {%- match func.throws() %}{% when Some with (e) %}{{ e|class_name }}{% else %}None{% endmatch %},
+        _UniFFILib.{{ func.ffi_func().name() }},
+        {{- prefix }}
+        {%- call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %},{% endif %}
+        uniffi_handle,
+    ){% call _claiming_args_end(func) %},
+    {%- match func.return_type() %}
+    {%- when Some with (return_type) %}
+    lambda stream: {{ "stream"|read_var(return_type) }},
+    {%- when None %}
+    None,
+    {%- endmatch %}
 )
 {%- endmacro -%}
 
@@ -64,14 +121,26 @@ rust_call(
     ctypes.POINTER(RustCallStatus),
 {% endmacro -%}
 
+{#-
+// Check each argument, so that a value Rust can't take raises a `ValueError` that names it,
+// and then coerce it to the Python type that it's lowered from.
+#}
 {%- macro coerce_args(func) %}
     {%- for arg in func.arguments() %}
+    {%- let check = arg.type_()|arg_check_py %}
+    {%- if !check.is_empty() %}
+    _uniffi_check_arg("{{ arg.name() }}", {{ arg.name() }}, {{ check }})
+    {%- endif %}
     {{ arg.name() }} = {{ arg.name()|coerce_py(arg.type_()) -}}
     {% endfor -%}
 {%- endmacro -%}
 
 {%- macro coerce_args_extra_indent(func) %}
         {%- for arg in func.arguments() %}
+        {%- let check = arg.type_()|arg_check_py %}
+        {%- if !check.is_empty() %}
+        _uniffi_check_arg("{{ arg.name() }}", {{ arg.name() }}, {{ check }})
+        {%- endif %}
         {{ arg.name() }} = {{ arg.name()|coerce_py(arg.type_()) }}
         {%- endfor %}
 {%- endmacro -%}
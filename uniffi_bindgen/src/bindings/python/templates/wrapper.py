@@ -13,13 +13,20 @@
 # compile the rust component. The easiest way to ensure this is to bundle the Python
 # helpers directly inline like we're doing here.
 
+import atexit
 import os
 import sys
 import ctypes
 import enum
+import functools
 import struct
+import threading
+import weakref
 import contextlib
 import datetime
+{%- for module in self.imports() %}
+import {{ module }}
+{%- endfor %}
 
 {% include "RustBufferTemplate.py" %}
 {% include "Helpers.py" %}
@@ -29,6 +36,12 @@ import datetime
 # and the FFI Function declarations in a com.sun.jna.Library.
 {% include "NamespaceLibraryTemplate.py" %}
 
+{% include "ComponentInfoTemplate.py" %}
+
+{% include "WarmupTemplate.py" %}
+
+{% include "BatchTemplate.py" %}
+
 # Public interface members begin here.
 {% for code in self.declaration_code() %}
 {{ code }}
@@ -36,21 +49,39 @@ import datetime
 
 __all__ = [
     "InternalError",
+    "InvalidStringError",
+    "UniffiErrorSource",
+    {%- if self.has_no_copy_methods() %}
+    "OwnedBytes",
+    {%- endif %}
+    {%- if ci.ffi_batch().is_some() %}
+    "Batch",
+    "BatchResult",
+    "batch",
+    {%- endif %}
     {%- for e in ci.iter_enum_definitions() %}
     "{{ e|type_name }}",
     {%- endfor %}
     {%- for record in ci.iter_record_definitions() %}
     "{{ record|type_name }}",
     {%- endfor %}
-    {%- for func in ci.iter_function_definitions() %}
+    {%- for func in ci.iter_namespace_function_definitions() %}
     "{{ func.name()|fn_name }}",
     {%- endfor %}
+    {%- for module in ci.iter_top_level_module_definitions() %}
+    "{{ module.name() }}",
+    {%- endfor %}
     {%- for obj in ci.iter_object_definitions() %}
     "{{ obj|type_name }}",
     {%- endfor %}
     {%- for e in ci.iter_error_definitions() %}
     "{{ e|type_name }}",
     {%- endfor %}
+    {%- match self.exports_enum_name() %}
+    {%- when Some with (name) %}
+    "{{ name }}",
+    {%- when None %}
+    {%- endmatch %}
 ]
 
 {% import "macros.py" as py %}
@@ -7,6 +7,7 @@ use askama::Template;
 use heck::{CamelCase, ShoutySnakeCase, SnakeCase};
 use serde::{Deserialize, Serialize};
 
+use crate::backend::ApiFilter;
 use crate::interface::*;
 use crate::MergeWith;
 
@@ -28,6 +29,8 @@ fn is_reserved_word(word: &str) -> bool {
 pub struct Config {
     cdylib_name: Option<String>,
     cdylib_path: Option<String>,
+    #[serde(default)]
+    filter: ApiFilter,
 }
 
 impl Config {
@@ -44,6 +47,11 @@ impl Config {
     pub fn cdylib_path(&self) -> String {
         self.cdylib_path.clone().unwrap_or_default()
     }
+
+    /// The part of the component to generate bindings for, see [`crate::backend::filter`].
+    pub fn filter(&self) -> &ApiFilter {
+        &self.filter
+    }
 }
 
 impl From<&ComponentInterface> for Config {
@@ -51,6 +59,7 @@ impl From<&ComponentInterface> for Config {
         Config {
             cdylib_name: Some(format!("uniffi_{}", ci.namespace())),
             cdylib_path: None,
+            filter: Default::default(),
         }
     }
 }
@@ -60,6 +69,7 @@ impl MergeWith for Config {
         Config {
             cdylib_name: self.cdylib_name.merge_with(&other.cdylib_name),
             cdylib_path: self.cdylib_path.merge_with(&other.cdylib_path),
+            filter: self.filter.merge_with(&other.filter),
         }
     }
 }
@@ -74,6 +84,11 @@ impl<'a> RubyWrapper<'a> {
     pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
         Self { config, ci }
     }
+
+    /// The version of `uniffi-bindgen` that's generating the bindings.
+    pub fn bindgen_version(&self) -> &'static str {
+        crate::BINDGEN_VERSION
+    }
 }
 
 mod filters {
@@ -95,7 +110,11 @@ mod filters {
             FFIType::RustArcPtr => ":pointer".to_string(),
             FFIType::RustBuffer => "RustBuffer.by_value".to_string(),
             FFIType::ForeignBytes => "ForeignBytes".to_string(),
-            FFIType::ForeignCallback => unimplemented!("Callback interfaces are not implemented"),
+            FFIType::ForeignCallback | FFIType::ForeignMethodCallback(_) => {
+                unimplemented!("Callback interfaces are not implemented")
+            }
+            // `generate_ruby_bindings` refuses components with `[Async]` functions.
+            FFIType::AsyncCompletion => unreachable!("[Async] functions are not supported"),
         })
     }
 
@@ -165,6 +184,9 @@ mod filters {
             Type::Boolean => format!("{} ? true : false", nm),
             Type::Object(_) | Type::Enum(_) | Type::Error(_) | Type::Record(_) => nm.to_string(),
             Type::String => format!("{}.to_s", nm),
+            Type::BigInt => panic!("No support for bigints in Ruby, yet"),
+            Type::Bytes => panic!("No support for bytes in Ruby, yet"),
+            Type::Char => panic!("No support for chars in Ruby, yet"),
             Type::Timestamp => panic!("No support for timestamps in Ruby, yet"),
             Type::Duration => panic!("No support for durations in Ruby, yet"),
             Type::CallbackInterface(_) => panic!("No support for coercing callback interfaces yet"),
@@ -177,24 +199,33 @@ mod filters {
                     format!("{}.map {{ |v| {} }}", nm, coerce_code)
                 }
             }
-            Type::Map(t) => {
-                let k_coerce_code = coerce_rb(&"k", &Type::String)?;
-                let v_coerce_code = coerce_rb(&"v", t)?;
-
-                if k_coerce_code == "k" && v_coerce_code == "v" {
-                    nm.to_string()
-                } else {
-                    format!(
-                        "{}.each.with_object({{}}) {{ |(k, v), res| res[{}] = {} }}",
-                        nm, k_coerce_code, v_coerce_code,
-                    )
-                }
+            Type::Map(k, v) => coerce_map_rb(nm, k, v)?,
+            Type::OrderedMap(v) => coerce_map_rb(nm, &Type::String, v)?,
+            Type::External { .. } | Type::ExternalInterface { .. } => {
+                panic!("No support for external types, yet")
             }
-            Type::External { .. } => panic!("No support for external types, yet"),
             Type::Wrapped { .. } => panic!("No support for wrapped types, yet"),
         })
     }
 
+    fn coerce_map_rb(
+        nm: &dyn fmt::Display,
+        key_type: &Type,
+        value_type: &Type,
+    ) -> Result<String, askama::Error> {
+        let k_coerce_code = coerce_rb(&"k", key_type)?;
+        let v_coerce_code = coerce_rb(&"v", value_type)?;
+
+        Ok(if k_coerce_code == "k" && v_coerce_code == "v" {
+            nm.to_string()
+        } else {
+            format!(
+                "{}.each.with_object({{}}) {{ |(k, v), res| res[{}] = {} }}",
+                nm, k_coerce_code, v_coerce_code,
+            )
+        })
+    }
+
     pub fn lower_rb(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
         Ok(match type_ {
             Type::Int8
@@ -209,6 +240,9 @@ mod filters {
             | Type::Float64 => nm.to_string(),
             Type::Boolean => format!("({} ? 1 : 0)", nm),
             Type::String => format!("RustBuffer.allocFromString({})", nm),
+            Type::BigInt => panic!("No support for bigints in Ruby, yet"),
+            Type::Bytes => panic!("No support for bytes in Ruby, yet"),
+            Type::Char => panic!("No support for chars in Ruby, yet"),
             Type::Timestamp => panic!("No support for timestamps in Ruby, yet"),
             Type::Duration => panic!("No support for durations in Ruby, yet"),
             Type::Object(name) => format!("({}._uniffi_lower {})", class_name_rb(name)?, nm),
@@ -218,12 +252,15 @@ mod filters {
             | Type::Record(_)
             | Type::Optional(_)
             | Type::Sequence(_)
-            | Type::Map(_) => format!(
+            | Type::Map(..)
+            | Type::OrderedMap(_) => format!(
                 "RustBuffer.alloc_from_{}({})",
                 class_name_rb(&type_.canonical_name())?,
                 nm
             ),
-            Type::External { .. } => panic!("No support for lowering external types, yet"),
+            Type::External { .. } | Type::ExternalInterface { .. } => {
+                panic!("No support for lowering external types, yet")
+            }
             Type::Wrapped { .. } => panic!("No support for lowering wrapped types, yet"),
         })
     }
@@ -241,6 +278,9 @@ mod filters {
             Type::Float32 | Type::Float64 => format!("{}.to_f", nm),
             Type::Boolean => format!("1 == {}", nm),
             Type::String => format!("{}.consumeIntoString", nm),
+            Type::BigInt => panic!("No support for bigints in Ruby, yet"),
+            Type::Bytes => panic!("No support for bytes in Ruby, yet"),
+            Type::Char => panic!("No support for chars in Ruby, yet"),
             Type::Timestamp => panic!("No support for timestamps in Ruby, yet"),
             Type::Duration => panic!("No support for durations in Ruby, yet"),
             Type::Object(name) => format!("{}._uniffi_allocate({})", class_name_rb(name)?, nm),
@@ -250,12 +290,15 @@ mod filters {
             | Type::Record(_)
             | Type::Optional(_)
             | Type::Sequence(_)
-            | Type::Map(_) => format!(
+            | Type::Map(..)
+            | Type::OrderedMap(_) => format!(
                 "{}.consumeInto{}",
                 nm,
                 class_name_rb(&type_.canonical_name())?
             ),
-            Type::External { .. } => panic!("No support for lifting external types, yet"),
+            Type::External { .. } | Type::ExternalInterface { .. } => {
+                panic!("No support for lifting external types, yet")
+            }
             Type::Wrapped { .. } => panic!("No support for lifting wrapped types, yet"),
         })
     }
@@ -45,3 +45,37 @@ fn cdylib_path() {
     assert_eq!("/foo/bar", config.cdylib_path());
     assert!(config.custom_cdylib_path());
 }
+
+#[test]
+fn async_functions_are_rejected() {
+    let ci = crate::interface::ComponentInterface::from_webidl(
+        "namespace test { [Async] u32 later(); };",
+    )
+    .unwrap();
+    let config = Config {
+        cdylib_name: None,
+        cdylib_path: None,
+    };
+    let err = super::super::generate_ruby_bindings(&config, &ci).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The Ruby bindings don't support:\n  function `later`, which is `[Async]`"
+    );
+}
+
+#[test]
+fn also_async_methods_are_rejected() {
+    let ci = crate::interface::ComponentInterface::from_webidl(
+        "namespace test {}; interface Library { [AlsoAsync] u32 count(); };",
+    )
+    .unwrap();
+    let config = Config {
+        cdylib_name: None,
+        cdylib_path: None,
+    };
+    let err = super::super::generate_ruby_bindings(&config, &ci).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The Ruby bindings don't support:\n  method `Library.count`, which is `[AlsoAsync]`"
+    );
+}
@@ -16,7 +16,8 @@ use anyhow::{bail, Context, Result};
 pub mod gen_ruby;
 pub use gen_ruby::{Config, RubyWrapper};
 
-use super::super::interface::ComponentInterface;
+use super::super::interface::{Capabilities, ComponentInterface, Feature};
+use crate::backend::post_process;
 
 // Generate ruby bindings for the given ComponentInterface, in the given output directory.
 
@@ -29,7 +30,11 @@ pub fn write_bindings(
     let mut rb_file = PathBuf::from(out_dir);
     rb_file.push(format!("{}.rb", ci.namespace()));
     let mut f = File::create(&rb_file).context("Failed to create .rb file for bindings")?;
-    write!(f, "{}", generate_ruby_bindings(config, ci)?)?;
+    write!(
+        f,
+        "{}",
+        post_process::post_process(&rb_file, generate_ruby_bindings(config, ci)?)?
+    )?;
 
     if try_format_code {
         if let Err(e) = Command::new("rubocop")
@@ -48,10 +53,33 @@ pub fn write_bindings(
     Ok(())
 }
 
+/// What the Ruby bindings can generate.
+pub fn capabilities() -> Capabilities {
+    Capabilities::all().without(&[
+        Feature::Batchable,
+        Feature::Async,
+        Feature::AlsoAsync,
+        Feature::CallbackInterfaces,
+        Feature::ExternalTypes,
+        Feature::CustomTypes,
+        Feature::BigInt,
+        Feature::Bytes,
+        Feature::Char,
+        Feature::Time,
+    ])
+}
+
 // Generate ruby bindings for the given ComponentInterface, as a string.
 
 pub fn generate_ruby_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
     use askama::Template;
+    let problems = ci.unsupported_features(&capabilities());
+    if !problems.is_empty() {
+        bail!(
+            "The Ruby bindings don't support:\n  {}",
+            problems.join("\n  ")
+        );
+    }
     RubyWrapper::new(config.clone(), ci)
         .render()
         .map_err(|_| anyhow::anyhow!("failed to render ruby bindings"))
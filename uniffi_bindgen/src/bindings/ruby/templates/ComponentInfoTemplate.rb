@@ -0,0 +1,14 @@
+# Metadata about the build of the Rust library that these bindings are using.
+#
+# `checksum` identifies the component interface that the library was built from, and
+# `bindings_checksum` the one that these bindings were generated from. If they differ, the
+# bindings and the library don't match.
+UniffiComponentInfo = Struct.new(
+  :component_name, :uniffi_version, :checksum, :build_profile, :bindings_checksum
+)
+
+# Fetch metadata about the Rust library, for use by support tooling.
+def self.uniffi_component_info
+  fields = rust_call(:{{ ci.ffi_component_info().name() }}).consumeIntoString.split("\n")
+  UniffiComponentInfo.new(fields[0], fields[1], fields[2], fields[3], UNIFFI_COMPONENT_CHECKSUM)
+end
@@ -19,6 +19,7 @@ end
 CALL_SUCCESS = 0
 CALL_ERROR = 1
 CALL_PANIC = 2
+CALL_INVALID_STRING = 3
 {%- for e in ci.iter_error_definitions() %}
 {% if e.is_flat() %}
 class {{ e.name()|class_name_rb }}
@@ -55,7 +56,7 @@ ERROR_MODULE_TO_READER_METHOD = {
 }
 
 private_constant :ERROR_MODULE_TO_READER_METHOD, :CALL_SUCCESS, :CALL_ERROR, :CALL_PANIC,
-                 :RustCallStatus
+                 :CALL_INVALID_STRING, :RustCallStatus
 
 def self.consume_buffer_into_error(error_module, rust_buffer)
   rust_buffer.consumeWithStream do |stream|
@@ -67,6 +68,21 @@ end
 class InternalError < StandardError
 end
 
+# Raised when Rust is passed a string that isn't valid UTF-8, which Rust reports as such
+# rather than panicking.
+class InvalidStringError < StandardError
+end
+
+# One of the sources of a Rust error, for errors declared with `[SourceChain]`.
+class UniffiErrorSource < StandardError
+  attr_reader :cause
+
+  def initialize(message, cause)
+    super(message)
+    @cause = cause
+  end
+end
+
 def self.rust_call(fn_name, *args)
   # Call a rust function
   rust_call_with_error(nil, fn_name, *args)
@@ -84,6 +100,7 @@ def self.rust_call_with_error(error_module, fn_name, *args)
   status = RustCallStatus.new
   args << status
 
+  UniFFILib.load_from(nil) unless UniFFILib.loaded?
   result = UniFFILib.public_send(fn_name, *args)
 
   case status.code
@@ -94,7 +111,11 @@ def self.rust_call_with_error(error_module, fn_name, *args)
       status.error_buf.free
       raise InternalError, "CALL_ERROR with no error_module set"
     else
-      raise consume_buffer_into_error(error_module, status.error_buf)
+      error = consume_buffer_into_error(error_module, status.error_buf)
+      cause = error.instance_variable_get(:@uniffi_source_chain)
+      raise error if cause.nil?
+
+      raise error, cause: cause
     end
   when CALL_PANIC
     # When the rust code sees a panic, it tries to construct a RustBuffer
@@ -105,6 +126,8 @@ def self.rust_call_with_error(error_module, fn_name, *args)
     else
       raise InternalError, "Rust panic"
     end
+  when CALL_INVALID_STRING
+    raise InvalidStringError, status.error_buf.consumeIntoString()
   else
     raise InternalError, "Unknown call status: #{status.code}"
   end
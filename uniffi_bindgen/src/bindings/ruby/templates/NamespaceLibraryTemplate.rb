@@ -1,17 +1,124 @@
 # This is how we find and load the dynamic library provided by the component.
-# For now we just look it up by name.
+# By default we look it up by name the first time it's needed, but `load_library` can be
+# used to load it from an explicit path before then.
+
+# The version of `uniffi-bindgen` that generated these bindings.
+UNIFFI_BINDGEN_VERSION = '{{ self.bindgen_version() }}'
+
+# The checksum of the component interface that these bindings were generated from. The library
+# is checked against it when it's loaded.
+UNIFFI_COMPONENT_CHECKSUM = '{{ "{:016x}"|format(ci.checksum()) }}'
+
 module UniFFILib
   extend FFI::Library
 
-  {% if config.custom_cdylib_path() %}
-  ffi_lib {{ config.cdylib_path() }}
-  {% else %}
-  ffi_lib '{{ config.cdylib_name() }}'
-  {% endif %}
-
-  {% for func in ci.iter_ffi_function_definitions() -%}
-  attach_function :{{ func.name() }},
-    {%- call rb::arg_list_ffi_decl(func) %},
-    {% match func.return_type() %}{% when Some with (type_) %}{{ type_|type_ffi }}{% when None %}:void{% endmatch %}
-  {% endfor %}
+  LOAD_MUTEX = Mutex.new
+  @loaded = false
+  @path = nil
+
+  # The objects that hold a pointer into the library, so that `reset_library` can free them.
+  LIVE_OBJECTS = ObjectSpace::WeakMap.new
+
+  def self.loaded?
+    @loaded
+  end
+
+  def self.load_from(path)
+    LOAD_MUTEX.synchronize do
+      if @loaded
+        raise InternalError, 'load_library: the library has already been loaded' unless path.nil?
+        return
+      end
+
+      open_library(path)
+    end
+  end
+
+  def self.reset
+    LOAD_MUTEX.synchronize do
+      # Nothing has used the library yet, so there's nothing to reset.
+      next unless @loaded
+
+      LIVE_OBJECTS.keys.each(&:_uniffi_reset)
+      {{ ci.namespace()|class_name_rb }}.rust_call(:{{ ci.ffi_reset().name() }})
+      @loaded = false
+      open_library(@path)
+    end
+  end
+
+  # Shut the library down when the interpreter starts to exit, so that the component stops
+  # calling back into it while it's torn down. Calls into the component raise an `InternalError`
+  # after this, but objects can still be freed.
+  def self.shutdown
+    LOAD_MUTEX.synchronize do
+      {{ ci.namespace()|class_name_rb }}.rust_call(:{{ ci.ffi_shutdown().name() }}) if @loaded
+    end
+  end
+
+  # Called with the lock held.
+  def self.open_library(path)
+    if path.nil?
+      {% if config.custom_cdylib_path() -%}
+      ffi_lib {{ config.cdylib_path() }}
+      {%- else -%}
+      ffi_lib '{{ config.cdylib_name() }}'
+      {%- endif %}
+    else
+      ffi_lib path
+    end
+
+    # Check that the library was built from the same component interface as these bindings,
+    # before attaching its other functions, which would fail on the first one that's missing.
+    {% let checksum_func = ci.ffi_checksum() -%}
+    begin
+      attach_function :{{ checksum_func.name() }},
+        {%- call rb::arg_list_ffi_decl(checksum_func) %},
+        :uint64
+    rescue FFI::NotFoundError
+      raise InternalError, "The {{ ci.namespace() }} library doesn't report its checksum, so it's older than these " \
+                           "bindings, which uniffi-bindgen #{UNIFFI_BINDGEN_VERSION} generated. " \
+                           "Regenerate the bindings from the library's UDL."
+    end
+    checksum = format('%016x', {{ checksum_func.name() }}(RustCallStatus.new))
+    if checksum != UNIFFI_COMPONENT_CHECKSUM
+      raise InternalError, "The {{ ci.namespace() }} library was built with checksum #{checksum}, but these " \
+                           "bindings, which uniffi-bindgen #{UNIFFI_BINDGEN_VERSION} generated, expect " \
+                           "#{UNIFFI_COMPONENT_CHECKSUM}. Regenerate the bindings from the library's UDL."
+    end
+
+    {% for func in ci.iter_ffi_function_definitions() -%}
+    {% if func.name() != checksum_func.name() -%}
+    attach_function :{{ func.name() }},
+      {%- call rb::arg_list_ffi_decl(func) %},
+      {% match func.return_type() %}{% when Some with (type_) %}{{ type_|type_ffi }}{% when None %}:void{% endmatch %}
+    {% endif -%}
+    {% endfor %}
+    @path = path
+    @loaded = true
+    # `reset_library` opens the library again, but it only needs shutting down once.
+    return if @shutdown_registered
+
+    at_exit { shutdown }
+    @shutdown_registered = true
+  end
+  private_class_method :open_library
 end
+
+# Load the component's native library from an explicit path, rather than looking it up by
+# name the first time it's needed. This must be called before anything else in the module is
+# used, and raises `InternalError` if the library has already been loaded.
+def self.load_library(path)
+  UniFFILib.load_from(path)
+end
+
+
+# Start over with the component's native library, without restarting the process, for use
+# during development. This frees the Rust object behind every object of the component that's
+# still alive, after which calling their methods raises a `RuntimeError`, and then runs the
+# reset hooks that the component registered with `uniffi::on_reset`, and attaches the library's
+# functions again. Ruby FFI never unloads a library, so this doesn't pick up a library that has
+# been rebuilt in the meantime; that still needs a new process. It mustn't be called while other
+# threads are using the component.
+def self.reset_library
+  UniFFILib.reset
+end
\ No newline at end of file
@@ -0,0 +1,40 @@
+# Tracks whether a `[Closeable]` object has been closed, and how many calls are using its
+# pointer, so that the underlying Rust struct is freed exactly once and only after the last
+# in-flight call has finished.
+class UniffiCloseState
+  def initialize
+    @lock = Mutex.new
+    @closed = false
+    @calls = 0
+  end
+
+  def closed?
+    @lock.synchronize { @closed }
+  end
+
+  # Claim the pointer for a call, returning false if the object has been closed.
+  def enter
+    @lock.synchronize do
+      next false if @closed
+      @calls += 1
+      true
+    end
+  end
+
+  # Finish a call, returning true if the pointer should now be freed.
+  def exit
+    @lock.synchronize do
+      @calls -= 1
+      @closed && @calls == 0
+    end
+  end
+
+  # Mark the object as closed, returning true if the pointer should now be freed.
+  def close
+    @lock.synchronize do
+      next false if @closed
+      @closed = true
+      @calls == 0
+    end
+  end
+end
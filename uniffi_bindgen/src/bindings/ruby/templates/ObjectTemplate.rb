@@ -1,4 +1,14 @@
 class {{ obj.name()|class_name_rb }}
+{%- match obj.foreign_extension() %}
+{%- when Some with (ext) %}
+  # Defined by the application, which must load it before these bindings.
+  include {{ ext }}
+{%- when None %}
+{%- endmatch %}
+
+  # The threads that this object can be used from, for tools that check the callers:
+  # 'threadsafe', 'main-thread-only' or 'blocking'.
+  UNIFFI_THREADING = '{{ obj.threading().as_str() }}'
 
   # A private helper for initializing instances of the class from a raw pointer,
   # bypassing any initialization logic and ensuring they are GC'd properly.
@@ -6,7 +16,11 @@ class {{ obj.name()|class_name_rb }}
     pointer.autorelease = false
     inst = allocate
     inst.instance_variable_set :@pointer, pointer
+    {%- if obj.is_closeable() %}
+    inst.instance_variable_set :@uniffi_close_state, UniffiCloseState.new
+    {%- endif %}
     ObjectSpace.define_finalizer(inst, _uniffi_define_finalizer_by_pointer(pointer, inst.object_id))
+    UniFFILib::LIVE_OBJECTS[inst] = true
     return inst
   end
 
@@ -29,8 +43,78 @@ class {{ obj.name()|class_name_rb }}
     if not inst.is_a? self
       raise TypeError.new "Expected a {{ obj.name()|class_name_rb }} intance, got #{inst}"
     end
+    {%- if obj.is_closeable() %}
+    if inst.instance_variable_get(:@uniffi_close_state).closed?
+      raise RuntimeError.new "{{ obj.name()|class_name_rb }} object has already been closed"
+    end
     return inst.instance_variable_get :@pointer
+    {%- else %}
+    return inst._uniffi_pointer
+    {%- endif %}
+  end
+
+  # Called by `reset_library`, to free the Rust object before the library is reset.
+  def _uniffi_reset
+    {%- if obj.is_closeable() %}
+    close
+    {%- else %}
+    ObjectSpace.undefine_finalizer(self)
+    pointer, @pointer = @pointer, nil
+    {{ ci.namespace()|class_name_rb }}.rust_call(:{{ obj.ffi_object_free().name() }}, pointer) unless pointer.nil?
+    {%- endif %}
+  end
+  {%- if !obj.is_closeable() %}
+
+  # A private helper for handing out the pointer, which `reset_library` frees.
+  def _uniffi_pointer
+    raise RuntimeError.new "{{ obj.name()|class_name_rb }} object was freed by reset_library" if @pointer.nil?
+    @pointer
+  end
+  {%- endif %}
+
+  {%- match obj.ffi_object_clone() %}
+  {%- when Some with (ffi_clone) %}
+
+  # Called by `dup` and `clone`, to give the copy a new, independent copy
+  # of the underlying Rust object rather than sharing this one's pointer.
+  def initialize_copy(other)
+    super
+    # `super` copies the finalizer for `other`'s pointer, which would free it a second time.
+    ObjectSpace.undefine_finalizer(self)
+    {%- if obj.is_closeable() %}
+    pointer = other._uniffi_with_pointer do |other_pointer|
+      {{ ci.namespace()|class_name_rb }}.rust_call(:{{ ffi_clone.name() }}, other_pointer)
+    end
+    @uniffi_close_state = UniffiCloseState.new
+    {%- else %}
+    pointer = {{ ci.namespace()|class_name_rb }}.rust_call(
+      :{{ ffi_clone.name() }},
+      other._uniffi_pointer
+    )
+    {%- endif %}
+    @pointer = pointer
+    ObjectSpace.define_finalizer(self, self.class._uniffi_define_finalizer_by_pointer(pointer, self.object_id))
+    UniFFILib::LIVE_OBJECTS[self] = true
   end
+  {%- when None %}
+  {%- endmatch %}
+
+  {%- match obj.ffi_object_downgrade() %}
+  {%- when Some with (ffi_downgrade) %}
+
+  # Make a weak reference to the underlying Rust object, which doesn't keep it alive.
+  def downgrade
+    {%- if obj.is_closeable() %}
+    pointer = _uniffi_with_pointer do |this_pointer|
+      {{ ci.namespace()|class_name_rb }}.rust_call(:{{ ffi_downgrade.name() }}, this_pointer)
+    end
+    {%- else %}
+    pointer = {{ ci.namespace()|class_name_rb }}.rust_call(:{{ ffi_downgrade.name() }}, _uniffi_pointer)
+    {%- endif %}
+    {{ obj.name()|class_name_rb }}WeakRef._uniffi_allocate(pointer)
+  end
+  {%- when None %}
+  {%- endmatch %}
 
   {%- match obj.primary_constructor() %}
   {%- when Some with (cons) %}
@@ -38,7 +122,11 @@ class {{ obj.name()|class_name_rb }}
     {%- call rb::coerce_args_extra_indent(cons) %}
     pointer = {% call rb::to_ffi_call(cons) %}
     @pointer = pointer
+    {%- if obj.is_closeable() %}
+    @uniffi_close_state = UniffiCloseState.new
+    {%- endif %}
     ObjectSpace.define_finalizer(self, self.class._uniffi_define_finalizer_by_pointer(pointer, self.object_id))
+    UniFFILib::LIVE_OBJECTS[self] = true
   end
   {%- when None %}
   {%- endmatch %}
@@ -53,21 +141,97 @@ class {{ obj.name()|class_name_rb }}
   end
   {% endfor %}
 
+  {%- if obj.is_closeable() %}
+
+  # Free the underlying Rust object without waiting to be garbage-collected.
+  # It can be called more than once, but once called, calling any other method
+  # on the object raises a `RuntimeError`.
+  def close
+    ObjectSpace.undefine_finalizer(self)
+    if @uniffi_close_state.close
+      {{ ci.namespace()|class_name_rb }}.rust_call(:{{ obj.ffi_object_free().name() }}, @pointer)
+    end
+  end
+
+  # A private helper for keeping the pointer alive, and refusing to hand it out once closed.
+  def _uniffi_with_pointer
+    if not @uniffi_close_state.enter
+      raise RuntimeError.new "{{ obj.name()|class_name_rb }} object has already been closed"
+    end
+    begin
+      yield @pointer
+    ensure
+      if @uniffi_close_state.exit
+        {{ ci.namespace()|class_name_rb }}.rust_call(:{{ obj.ffi_object_free().name() }}, @pointer)
+      end
+    end
+  end
+  {%- endif %}
+
   {% for meth in obj.methods() -%}
   {%- match meth.return_type() -%}
 
   {%- when Some with (return_type) -%}
   def {{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
     {%- call rb::coerce_args_extra_indent(meth) %}
-    result = {% call rb::to_ffi_call_with_prefix("@pointer", meth) %}
+    {%- if obj.is_closeable() %}
+    result = _uniffi_with_pointer do |pointer|
+      {% call rb::to_ffi_call_with_prefix("pointer", meth) %}
+    end
+    {%- else %}
+    result = {% call rb::to_ffi_call_with_prefix("_uniffi_pointer", meth) %}
+    {%- endif %}
+    {%- if meth.is_no_copy() %}
+    return result.consumeIntoOwnedBytes
+    {%- else %}
     return {{ "result"|lift_rb(return_type) }}
+    {%- endif %}
   end
 
   {%- when None -%}
   def {{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
       {%- call rb::coerce_args_extra_indent(meth) %}
-      {% call rb::to_ffi_call_with_prefix("@pointer", meth) %}
+      {%- if obj.is_closeable() %}
+      _uniffi_with_pointer do |pointer|
+        {% call rb::to_ffi_call_with_prefix("pointer", meth) %}
+      end
+      {%- else %}
+      {% call rb::to_ffi_call_with_prefix("_uniffi_pointer", meth) %}
+      {%- endif %}
   end
   {% endmatch %}
   {% endfor %}
 end
+{%- match obj.ffi_object_upgrade() %}
+{%- when Some with (ffi_upgrade) %}
+
+# A weak reference to a {{ obj.name()|class_name_rb }}, which doesn't keep the underlying Rust
+# object alive, for caches that would rather not hold on to it.
+class {{ obj.name()|class_name_rb }}WeakRef
+  def self._uniffi_allocate(pointer)
+    pointer.autorelease = false
+    inst = allocate
+    inst.instance_variable_set :@pointer, pointer
+    ObjectSpace.define_finalizer(inst, _uniffi_define_finalizer_by_pointer(pointer, inst.object_id))
+    return inst
+  end
+
+  # As for the objects themselves, this mustn't capture a reference to the instance.
+  def self._uniffi_define_finalizer_by_pointer(pointer, object_id)
+    Proc.new do |_id|
+      {{ ci.namespace()|class_name_rb }}.rust_call(
+        :{{ obj.ffi_object_weak_free().unwrap().name() }},
+        pointer
+      )
+    end
+  end
+
+  # Get the object back, or nil if the Rust object is gone.
+  def upgrade
+    value = {{ ci.namespace()|class_name_rb }}.rust_call(:{{ ffi_upgrade.name() }}, @pointer)
+    return nil if value.zero?
+    {{ obj.name()|class_name_rb }}._uniffi_allocate(FFI::Pointer.new(value))
+  end
+end
+{%- when None %}
+{%- endmatch %}
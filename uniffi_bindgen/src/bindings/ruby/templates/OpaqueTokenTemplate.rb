@@ -0,0 +1,61 @@
+# An opaque handle to a Rust `{{ obj.name() }}`, which can only be passed back to Rust.
+# Two handles are equal if they refer to the same Rust object.
+class {{ obj.name()|class_name_rb }}
+  # A private helper for initializing instances of the class from a raw pointer,
+  # ensuring they are GC'd properly.
+  def self._uniffi_allocate(pointer)
+    pointer.autorelease = false
+    inst = allocate
+    inst.instance_variable_set :@pointer, pointer
+    inst.instance_variable_set :@uniffi_address, pointer.address
+    ObjectSpace.define_finalizer(inst, _uniffi_define_finalizer_by_pointer(pointer, inst.object_id))
+    UniFFILib::LIVE_OBJECTS[inst] = true
+    return inst
+  end
+
+  # A private helper for registering an object finalizer.
+  # N.B. it's important that this does not capture a reference
+  # to the actual instance, only its underlying pointer.
+  def self._uniffi_define_finalizer_by_pointer(pointer, object_id)
+    Proc.new do |_id|
+      {{ ci.namespace()|class_name_rb }}.rust_call(
+        :{{ obj.ffi_object_free().name() }},
+        pointer
+      )
+    end
+  end
+
+  # A private helper for lowering instances into a raw pointer.
+  # This does an explicit typecheck, because accidentally lowering a different type of
+  # object in a place where this type is expected, could lead to memory unsafety.
+  def self._uniffi_lower(inst)
+    if not inst.is_a? self
+      raise TypeError.new "Expected a {{ obj.name()|class_name_rb }} intance, got #{inst}"
+    end
+    return inst._uniffi_pointer
+  end
+
+  # Called by `reset_library`, to free the Rust object before the library is reset.
+  def _uniffi_reset
+    ObjectSpace.undefine_finalizer(self)
+    pointer, @pointer = @pointer, nil
+    {{ ci.namespace()|class_name_rb }}.rust_call(:{{ obj.ffi_object_free().name() }}, pointer) unless pointer.nil?
+  end
+
+  # A private helper for handing out the pointer, which `reset_library` frees.
+  def _uniffi_pointer
+    raise RuntimeError.new "{{ obj.name()|class_name_rb }} object was freed by reset_library" if @pointer.nil?
+    @pointer
+  end
+
+  # The address stays the same after `reset_library`, so that the hash does too.
+  def ==(other)
+    other.is_a?(self.class) && other.instance_variable_get(:@uniffi_address) == @uniffi_address
+  end
+
+  alias eql? ==
+
+  def hash
+    @uniffi_address.hash
+  end
+end
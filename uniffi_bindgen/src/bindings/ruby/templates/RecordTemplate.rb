@@ -2,7 +2,11 @@
 class {{ rec.name()|class_name_rb }}
   attr_reader {% for field in rec.fields() %}:{{ field.name()|var_name_rb }}{% if loop.last %}{% else %}, {% endif %}{%- endfor %}
 
-  def initialize({% for field in rec.fields() %}{{ field.name()|var_name_rb }}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
+  def initialize({% for field in rec.fields() %}{{ field.name()|var_name_rb }}
+    {%- match rec.trailing_default_value(field) %}
+    {%- when Some with(literal) %} = {{ literal|literal_rb }}
+    {%- else %}
+    {%- endmatch %}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
     {%- for field in rec.fields() %}
     @{{ field.name()|var_name_rb }} = {{ field.name()|var_name_rb }}
     {%- endfor %}
@@ -17,4 +21,13 @@ class {{ rec.name()|class_name_rb }}
 
     true
   end
+  {%- if rec.has_sensitive_fields() %}
+
+  # `[Sensitive]` fields are left out, so that they don't end up in logs.
+  def inspect
+    "#<#{self.class.name} {% for field in rec.fields() %}{{ field.name()|var_name_rb }}={% if field.sensitive() %}***{% else %}#{@{{ field.name()|var_name_rb }}.inspect}{% endif %}{% if !loop.last %}, {% endif %}{% endfor %}>"
+  end
+
+  alias to_s inspect
+  {%- endif %}
 end
@@ -165,8 +165,20 @@ class RustBufferBuilder
     end
   end
 
-  {% when Type::Map with (inner_type) -%}
-  # The Map<T> type for {{ inner_type.canonical_name() }}.
+  {% when Type::Map with (key_type, inner_type) -%}
+  # The Map<K, T> type for {{ key_type.canonical_name() }} and {{ inner_type.canonical_name() }}.
+
+  def write_{{ canonical_type_name }}(items)
+    pack_into(4, 'l>', items.size)
+
+    items.each do |k, v|
+      self.write_{{ key_type.canonical_name()|class_name_rb }}(k)
+      self.write_{{ inner_type.canonical_name()|class_name_rb }}(v)
+    end
+  end
+
+  {% when Type::OrderedMap with (inner_type) -%}
+  # The OrderedMap<T> type for {{ inner_type.canonical_name() }}. Ruby hashes keep their insertion order.
 
   def write_{{ canonical_type_name }}(items)
     pack_into(4, 'l>', items.size)
@@ -121,65 +121,75 @@ class RustBufferStream
   def read{{ canonical_type_name }}
     variant = unpack_from 4, 'l>'
     {% if e.is_flat() -%}
-    {%- for variant in e.variants() %}
-    if variant == {{ loop.index }}
-      return {{ enum_name|class_name_rb }}::{{ variant.name()|enum_name_rb }}
-    end
-    {%- endfor %}
+    # The value of each variant is its index on the wire, so there's nothing to look up.
+    return variant if variant.between?(1, {{ e.variants().len() }})
 
     raise InternalError, 'Unexpected variant tag for {{ canonical_type_name }}'
     {%- else -%}
+    # A `case` over integer literals is compiled into a single table lookup, so this
+    # doesn't have to test for each variant in turn.
+    case variant
     {%- for variant in e.variants() %}
-    if variant == {{ loop.index }}
+    when {{ loop.index }}
         {%- if variant.has_fields() %}
-        return {{ enum_name|class_name_rb }}::{{ variant.name()|enum_name_rb }}.new(
+        {{ enum_name|class_name_rb }}::{{ variant.name()|enum_name_rb }}.new(
             {%- for field in variant.fields() %}
             self.read{{ field.type_().canonical_name()|class_name_rb }}(){% if loop.last %}{% else %},{% endif %}
             {%- endfor %}
         )
         {%- else %}
-        return {{ enum_name|class_name_rb }}::{{ variant.name()|enum_name_rb }}.new
-        {% endif %}
-    end
+        {{ enum_name|class_name_rb }}::{{ variant.name()|enum_name_rb }}.new
+        {%- endif %}
     {%- endfor %}
-    raise InternalError, 'Unexpected variant tag for {{ canonical_type_name }}'
+    else
+      raise InternalError, 'Unexpected variant tag for {{ canonical_type_name }}'
+    end
     {%- endif %}
   end
 
   {% when Type::Error with (error_name) -%}
-  {%- let e = ci.get_error_definition(error_name).unwrap().wrapped_enum() %}
+  {%- let error = ci.get_error_definition(error_name).unwrap() %}
+  {%- let e = error.wrapped_enum() %}
 
   # The Error type {{ error_name }}
 
   def read{{ canonical_type_name }}
     variant = unpack_from 4, 'l>'
-    {% if e.is_flat() -%}
+    # A `case` over integer literals is compiled into a single table lookup, so this
+    # doesn't have to test for each variant in turn.
+    error = case variant
     {%- for variant in e.variants() %}
-    if variant == {{ loop.index }}
-      return {{ error_name|class_name_rb }}::{{ variant.name()|class_name_rb }}.new(
+    when {{ loop.index }}
+      {%- if e.is_flat() %}
+      {{ error_name|class_name_rb }}::{{ variant.name()|class_name_rb }}.new(
         readString()
       )
-    end
+      {%- else if variant.has_fields() %}
+      {{ error_name|class_name_rb }}::{{ variant.name()|class_name_rb }}.new(
+        {%- for field in variant.fields() %}
+        read{{ field.type_().canonical_name()|class_name_rb }}(){% if loop.last %}{% else %},{% endif %}
+        {%- endfor %}
+      )
+      {%- else %}
+      {{ error_name|class_name_rb }}::{{ variant.name()|class_name_rb }}.new
+      {%- endif %}
     {%- endfor %}
-
-    raise InternalError, 'Unexpected variant tag for {{ canonical_type_name }}'
-    {%- else -%}
-    {%- for variant in e.variants() %}
-    if variant == {{ loop.index }}
-        {%- if variant.has_fields() %}
-        return {{ error_name|class_name_rb }}::{{ variant.name()|class_name_rb }}.new(
-            {%- for field in variant.fields() %}
-            read{{ field.type_().canonical_name()|class_name_rb }}(){% if loop.last %}{% else %},{% endif %}
-            {%- endfor %}
-        )
-        {%- else %}
-        return {{ error_name|class_name_rb }}::{{ variant.name()|class_name_rb }}.new
-        {%- endif %}
+    else
+      raise InternalError, 'Unexpected variant tag for {{ canonical_type_name }}'
     end
-    {%- endfor %}
-
-    raise InternalError, 'Unexpected variant tag for {{ canonical_type_name }}'
-    {%- endif %}
+    {%- match error.source_chain_type() %}
+    {%- when Some with (chain_type) %}
+
+    # The messages of the Rust error's sources follow the variant data. We keep them
+    # around so that `rust_call_with_error` can raise the error with them as its `cause`.
+    cause = nil
+    read{{ chain_type.canonical_name()|class_name_rb }}.reverse_each do |message|
+      cause = UniffiErrorSource.new(message, cause)
+    end
+    error.instance_variable_set(:@uniffi_source_chain, cause)
+    {%- when None %}
+    {%- endmatch %}
+    error
   end
 
   {% when Type::Record with (record_name) -%}
@@ -226,8 +236,24 @@ class RustBufferStream
     items
   end
 
-  {% when Type::Map with (inner_type) -%}
-  # The Map<T> type for {{ inner_type.canonical_name() }}.
+  {% when Type::Map with (key_type, inner_type) -%}
+  # The Map<K, T> type for {{ key_type.canonical_name() }} and {{ inner_type.canonical_name() }}.
+
+  def read{{ canonical_type_name }}
+    count = unpack_from 4, 'l>'
+    raise InternalError, 'Unexpected negative map size' if count.negative?
+
+    items = {}
+    count.times do
+      key = read{{ key_type.canonical_name()|class_name_rb }}
+      items[key] = read{{ inner_type.canonical_name()|class_name_rb }}
+    end
+
+    items
+  end
+
+  {% when Type::OrderedMap with (inner_type) -%}
+  # The OrderedMap<T> type for {{ inner_type.canonical_name() }}. Ruby hashes keep their insertion order.
 
   def read{{ canonical_type_name }}
     count = unpack_from 4, 'l>'
@@ -60,6 +60,16 @@ class RustBuffer < FFI::Struct
     free
   end
 
+  # The bytes that a `[NoCopy]` method returned, which are the whole of the buffer, rather than
+  # serialized into it. Ruby can't view them in place, so they're copied out once.
+  def consumeIntoOwnedBytes
+    return [] if len.zero?
+
+    data.read_bytes(len).bytes
+  ensure
+    free
+  end
+
   {%- for typ in ci.iter_types() -%}
   {%- let canonical_type_name = typ.canonical_name() -%}
   {%- match typ -%}
@@ -146,8 +156,24 @@ class RustBuffer < FFI::Struct
     end
   end
 
-  {% when Type::Map with (inner_type) -%}
-  # The Map<T> type for {{ inner_type.canonical_name() }}.
+  {% when Type::Map with (key_type, inner_type) -%}
+  # The Map<K, T> type for {{ key_type.canonical_name() }} and {{ inner_type.canonical_name() }}.
+
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_{{ canonical_type_name }}(v)
+      return builder.finalize
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read{{ canonical_type_name }}
+    end
+  end
+
+  {% when Type::OrderedMap with (inner_type) -%}
+  # The OrderedMap<T> type for {{ inner_type.canonical_name() }}. Ruby hashes keep their insertion order.
 
   def self.alloc_from_{{ canonical_type_name }}(v)
     RustBuffer.allocWithBuilder do |builder|
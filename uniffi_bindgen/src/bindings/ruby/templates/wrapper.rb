@@ -25,6 +25,10 @@ module {{ ci.namespace()|class_name_rb }}
 
   {% include "NamespaceLibraryTemplate.rb" %}
 
+  {% include "ComponentInfoTemplate.rb" %}
+
+  {% include "ObjectRuntime.rb" %}
+
   # Public interface members begin here.
 
   {% for e in ci.iter_enum_definitions() %}
@@ -35,12 +39,26 @@ module {{ ci.namespace()|class_name_rb }}
   {% include "RecordTemplate.rb" %}
   {% endfor %}
 
-  {% for func in ci.iter_function_definitions() %}
+  {% for func in ci.iter_namespace_function_definitions() %}
   {% include "TopLevelFunctionTemplate.rb" %}
   {% endfor %}
 
+  {%- for module in ci.iter_module_definitions() %}
+
+  # The `{{ module.qualified_name() }}` namespace.
+  module {% for name in module.path() %}{{ name|class_name_rb }}{% if !loop.last %}::{% endif %}{% endfor %}
+    {%- for func in ci.iter_module_function_definitions(module) %}
+    {% include "TopLevelFunctionTemplate.rb" %}
+    {%- endfor %}
+  end
+  {%- endfor %}
+
   {% for obj in ci.iter_object_definitions() %}
+  {%- if obj.is_opaque_token() %}
+  {% include "OpaqueTokenTemplate.rb" %}
+  {%- else %}
   {% include "ObjectTemplate.rb" %}
+  {%- endif %}
   {% endfor %}
 end
 
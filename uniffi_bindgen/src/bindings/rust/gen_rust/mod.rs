@@ -0,0 +1,299 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use anyhow::Result;
+use askama::Template;
+use serde::{Deserialize, Serialize};
+
+use crate::backend::ApiFilter;
+use crate::interface::*;
+use crate::lockfile::type_decl;
+use crate::MergeWith;
+
+// Some config options for the caller to customize the generated Rust client.
+// Note that this can only be used to control details *that do not affect the underlying component*,
+// since the details of the underlying component are entirely determined by the `ComponentInterface`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    filter: ApiFilter,
+}
+
+impl Config {
+    /// The part of the component to generate the client for, see [`crate::backend::filter`].
+    pub fn filter(&self) -> &ApiFilter {
+        &self.filter
+    }
+}
+
+impl From<&ComponentInterface> for Config {
+    fn from(_ci: &ComponentInterface) -> Self {
+        Config {
+            filter: Default::default(),
+        }
+    }
+}
+
+impl MergeWith for Config {
+    fn merge_with(&self, other: &Self) -> Self {
+        Config {
+            filter: self.filter.merge_with(&other.filter),
+        }
+    }
+}
+
+// Whether the type is, or holds, an object. The client only passes objects as the arguments and
+// return values of calls, because it would have to take them out of any other value that the
+// component lifts or lowers, to manage their references itself.
+fn contains_object(type_: &Type) -> bool {
+    match type_ {
+        Type::Object(_) => true,
+        Type::Optional(t) | Type::Sequence(t) | Type::OrderedMap(t) => contains_object(t),
+        Type::Map(k, v) => contains_object(k) || contains_object(v),
+        _ => false,
+    }
+}
+
+/// What the Rust client can generate, apart from records and enums that hold objects.
+pub fn capabilities() -> Capabilities {
+    Capabilities::all().without(&[
+        Feature::NestedNamespaces,
+        Feature::Batchable,
+        Feature::Async,
+        Feature::AlsoAsync,
+        Feature::Borrowed,
+        Feature::Clone,
+        Feature::Closeable,
+        Feature::WeakRefs,
+        Feature::ReleaseCallbacks,
+        Feature::OpaqueTokens,
+        Feature::ForeignExtensions,
+        Feature::Observable,
+        Feature::Events,
+        Feature::Services,
+        Feature::Streams,
+        Feature::SourceChains,
+        Feature::CallbackInterfaces,
+        Feature::ExternalTypes,
+        Feature::CustomTypes,
+    ])
+}
+
+// The things in the component that the Rust client doesn't support yet, to report them all
+// at once.
+pub(super) fn unsupported_features(ci: &ComponentInterface) -> Vec<String> {
+    let mut problems = Vec::new();
+    for rec in ci.iter_record_definitions() {
+        for field in rec.fields() {
+            if matches!(field.type_(), Type::Object(_)) {
+                problems.push(format!(
+                    "field `{}` of `{}`, which is an object",
+                    field.name(),
+                    rec.name()
+                ));
+            }
+        }
+    }
+    for e in ci.iter_enum_definitions() {
+        for variant in e.variants() {
+            for field in variant.fields() {
+                if matches!(field.type_(), Type::Object(_)) {
+                    problems.push(format!(
+                        "field `{}` of `{}.{}`, which is an object",
+                        field.name(),
+                        e.name(),
+                        variant.name()
+                    ));
+                }
+            }
+        }
+    }
+    for type_ in ci.iter_types() {
+        if !matches!(type_, Type::Object(_)) && contains_object(&type_) {
+            problems.push(format!("{}, which holds objects", type_decl(&type_)))
+        }
+    }
+    problems.extend(ci.unsupported_features(&capabilities()));
+    problems
+}
+
+#[derive(Template)]
+#[template(syntax = "rs", escape = "none", path = "rust_client_template.rs")]
+pub struct RustWrapper<'a> {
+    ci: &'a ComponentInterface,
+}
+impl<'a> RustWrapper<'a> {
+    pub fn new(ci: &'a ComponentInterface) -> Self {
+        Self { ci }
+    }
+
+    /// The version of `uniffi` that the component has to be built with.
+    pub fn uniffi_version(&self) -> &'static str {
+        crate::BINDGEN_VERSION
+    }
+
+    /// The FFI functions that the client calls, which it looks up in the library when it's
+    /// loaded. It doesn't look up the rest, so that they can be left out of a filtered client.
+    pub fn ffi_functions(&self) -> Vec<FFIFunction> {
+        let mut functions = vec![
+            self.ci.ffi_checksum(),
+            self.ci.ffi_rustbuffer_from_bytes(),
+            self.ci.ffi_rustbuffer_free(),
+        ];
+        for func in self.ci.iter_function_definitions() {
+            functions.push(func.ffi_func().clone());
+        }
+        for obj in self.ci.iter_object_definitions() {
+            functions.push(obj.ffi_object_free().clone());
+            for cons in obj.constructors() {
+                functions.push(cons.ffi_func().clone());
+            }
+            for meth in obj.methods() {
+                functions.push(meth.ffi_func().clone());
+            }
+        }
+        functions
+    }
+}
+
+mod filters {
+    use super::*;
+    use crate::scaffolding::filters as rs;
+
+    pub use rs::{ffi_converter, ffi_converter_name, type_ffi, type_rs};
+
+    /// The type of an argument of a function or method. Objects are borrowed, since the
+    /// component only takes another reference to them.
+    pub fn arg_type_client(arg: &Argument) -> Result<String, askama::Error> {
+        match arg.type_() {
+            Type::Object(name) => Ok(format!("&{}", name)),
+            t => type_rs(&t),
+        }
+    }
+
+    /// The return type of a function or method, including any error, or nothing if it
+    /// doesn't return anything.
+    pub fn return_type_client(
+        type_: &Option<&Type>,
+        throws: &Option<&str>,
+    ) -> Result<String, askama::Error> {
+        let ret = match type_ {
+            Some(Type::Object(name)) => name.clone(),
+            Some(t) => type_rs(t)?,
+            None => "()".into(),
+        };
+        Ok(match throws {
+            Some(e) => format!(" -> Result<{}, {}>", ret, e),
+            None if type_.is_none() => "".into(),
+            None => format!(" -> {}", ret),
+        })
+    }
+
+    /// Lower an argument into the value to pass to the FFI function, as `ffi`'s component.
+    pub fn lower_client(arg: &Argument) -> Result<String, askama::Error> {
+        Ok(match arg.type_() {
+            Type::Object(_) => format!("{}.pointer", arg.name()),
+            t => format!("ffi.lower::<{}>({})", ffi_converter_name(&t)?, arg.name()),
+        })
+    }
+
+    /// Lift the value `ret` that an FFI function of `ffi`'s component returned, or nothing if
+    /// it doesn't return one.
+    pub fn lift_client(type_: &Option<&Type>) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Some(Type::Object(name)) => format!(
+                "{} {{ pointer: ret, ffi: std::sync::Arc::clone(ffi) }}",
+                name
+            ),
+            Some(t) => format!("ffi.lift::<{}>(ret)", ffi_converter_name(t)?),
+            None => "".into(),
+        })
+    }
+
+    /// Lift the value `ret` that a method returned. The bytes that a `[NoCopy]` method
+    /// returns are in a buffer of their own, which is copied out here, once, since the client
+    /// can't free the component's memory itself.
+    pub fn lift_method_client(meth: &Method) -> Result<String, askama::Error> {
+        if meth.is_no_copy() {
+            return Ok("ffi.take_buffer(ret).destroy_into_vec()".into());
+        }
+        lift_client(&meth.return_type())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rust_client() {
+        const UDL: &str = r#"
+            namespace todolist {
+                [Throws=TodoError]
+                TodoEntry get_first(sequence<TodoEntry> entries);
+                void clear(TodoList list);
+            };
+            dictionary TodoEntry {
+                string text;
+                timestamp? due;
+            };
+            [Enum]
+            interface Change {
+                Added(string text);
+                Cleared();
+            };
+            [Error]
+            enum TodoError { "EmptyList" };
+            interface TodoList {
+                constructor();
+                [Throws=TodoError]
+                TodoEntry last_entry();
+                TodoList copy();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let client = super::super::generate_rust_bindings(&Config::from(&ci), &ci).unwrap();
+        assert!(client.contains("pub unsafe fn load(path: impl AsRef<std::path::Path>)"));
+        assert!(client.contains(&format!(
+            "{}: unsafe extern \"C\" fn(uniffi::RustBuffer, &mut uniffi::RustCallStatus) -> uniffi::RustBuffer,",
+            ci.get_function_definition("get_first").unwrap().ffi_func().name()
+        )));
+        assert!(client.contains(&format!("if ffi.checksum() != {} {{", ci.checksum())));
+        assert!(client.contains(
+            "pub fn get_first(&self, entries: std::vec::Vec<TodoEntry>) -> Result<TodoEntry, TodoError> {"
+        ));
+        assert!(client.contains("pub fn clear(&self, list: &TodoList) {"));
+        assert!(client.contains("let list = list.pointer;"));
+        assert!(client.contains("pub due: std::option::Option<std::time::SystemTime>,"));
+        assert!(client.contains("Added { text: String },"));
+        assert!(client.contains("EmptyList { message: String },"));
+        assert!(client.contains("pub fn new(component: &Component) -> Self {"));
+        assert!(client.contains("pub fn copy(&self) -> TodoList {"));
+        assert!(client.contains("TodoList { pointer: ret, ffi: std::sync::Arc::clone(ffi) }"));
+        assert!(client.contains("impl Drop for TodoList {"));
+    }
+
+    #[test]
+    fn test_rust_unsupported_features() {
+        const UDL: &str = r#"
+            namespace shapes {
+                sequence<Shape> shapes();
+            };
+            dictionary Canvas { Shape background; };
+            interface Shape {};
+            callback interface Painter {
+                void paint(u32 color);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let err = super::super::generate_rust_bindings(&Config::from(&ci), &ci).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The Rust backend doesn't support:
+  field `background` of `Canvas`, which is an object
+  sequence<Shape>, which holds objects
+  callback interface `Painter`"
+        );
+    }
+}
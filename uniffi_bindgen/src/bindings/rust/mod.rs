@@ -0,0 +1,84 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Rust client
+//!
+//! With `--language rust`, `uniffi-bindgen` generates a Rust module that loads the component's
+//! cdylib at runtime, with `libloading`, and calls it through the same FFI as the
+//! foreign-language bindings. This lets a Rust application use a component as a plugin, without
+//! linking it in or depending on its crate: its functions are methods of a `Component` that
+//! `Component::load()` returns, and its dictionaries, enums, errors and objects are Rust types of
+//! their own.
+
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{bail, Context, Result};
+
+pub mod gen_rust;
+pub use gen_rust::{Config, RustWrapper};
+
+use super::super::interface::ComponentInterface;
+use crate::backend::post_process;
+
+// Generate the Rust client for the given ComponentInterface, in the given output directory.
+
+pub fn write_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Path,
+    try_format_code: bool,
+) -> Result<()> {
+    let mut rs_file = PathBuf::from(out_dir);
+    rs_file.push(format!("{}.rs", ci.namespace()));
+    let mut f = File::create(&rs_file).context("Failed to create .rs file for bindings")?;
+    write!(
+        f,
+        "{}",
+        post_process::post_process(&rs_file, generate_rust_bindings(config, ci)?)?
+    )?;
+
+    if try_format_code {
+        if let Err(e) = Command::new("rustfmt")
+            .arg("--edition")
+            .arg("2018")
+            .arg(&rs_file)
+            .output()
+        {
+            println!(
+                "Warning: Unable to auto-format {} using rustfmt: {:?}",
+                rs_file.file_name().unwrap().to_str().unwrap(),
+                e
+            )
+        }
+    }
+
+    Ok(())
+}
+
+// Generate the Rust client for the given ComponentInterface, as a string.
+
+pub fn generate_rust_bindings(_config: &Config, ci: &ComponentInterface) -> Result<String> {
+    use askama::Template;
+    let problems = gen_rust::unsupported_features(ci);
+    if !problems.is_empty() {
+        bail!(
+            "The Rust backend doesn't support:\n  {}",
+            problems.join("\n  ")
+        );
+    }
+    RustWrapper::new(ci)
+        .render()
+        .map_err(|_| anyhow::anyhow!("failed to render Rust client"))
+}
+
+/// Running test scripts against the Rust client would mean building a crate for each of them,
+/// which the test harness doesn't do yet.
+pub fn run_script(_out_dir: &Path, _script_file: &Path) -> Result<()> {
+    bail!("Running Rust test scripts isn't supported yet")
+}
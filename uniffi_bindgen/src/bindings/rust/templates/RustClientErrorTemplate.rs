@@ -0,0 +1,69 @@
+{#
+// Errors are enums, like the component's own. The variants of flat errors only have the message
+// that the component's error displays, since that's all it sends, while the variants of rich
+// errors have the fields declared in the UDL.
+#}
+
+/// The `{{ e.name() }}` error of the component.
+#[derive(Debug, Clone, PartialEq)]
+pub enum {{ e.name() }} {
+    {%- for variant in e.variants() %}
+    {%- if e.is_flat() %}
+    {{ variant.name() }} { message: String },
+    {%- else if variant.has_fields() %}
+    {{ variant.name() }} { {% for field in variant.fields() %}{{ field.name() }}: {{ field.type_()|type_rs }}{% if !loop.last %}, {% endif %}{% endfor %} },
+    {%- else %}
+    {{ variant.name() }},
+    {%- endif %}
+    {%- endfor %}
+}
+
+impl std::fmt::Display for {{ e.name() }} {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            {%- for variant in e.variants() %}
+            {%- if e.is_flat() %}
+            Self::{{ variant.name() }} { message } => f.write_str(message),
+            {%- else %}
+            Self::{{ variant.name() }} { .. } => f.write_str("{{ variant.name() }}"),
+            {%- endif %}
+            {%- endfor %}
+        }
+    }
+}
+
+impl std::error::Error for {{ e.name() }} {}
+
+#[doc(hidden)]
+pub struct {{ e.type_()|ffi_converter_name }};
+
+#[doc(hidden)]
+impl uniffi::RustBufferFfiConverter for {{ e.type_()|ffi_converter_name }} {
+    type RustType = {{ e.name() }};
+
+    fn write(_obj: {{ e.name() }}, _buf: &mut std::vec::Vec<u8>) {
+        // Errors only ever come back from the component, they're never sent to it.
+        panic!("write not supported for errors");
+    }
+
+    fn try_read(buf: &mut &[u8]) -> uniffi::deps::anyhow::Result<{{ e.name() }}> {
+        use uniffi::deps::bytes::Buf;
+        uniffi::check_remaining(buf, 4)?;
+        Ok(match buf.get_i32() {
+            {%- for variant in e.variants() %}
+            {%- if e.is_flat() %}
+            {{ loop.index }} => {{ e.name() }}::{{ variant.name() }} {
+                message: <String as uniffi::FfiConverter>::try_read(buf)?,
+            },
+            {%- else %}
+            {{ loop.index }} => {{ e.name() }}::{{ variant.name() }}{% if variant.has_fields() %} {
+                {%- for field in variant.fields() %}
+                {{ field.name() }}: {{ field.type_()|ffi_converter }}::try_read(buf)?,
+                {%- endfor %}
+            }{% endif %},
+            {%- endif %}
+            {%- endfor %}
+            v => uniffi::deps::anyhow::bail!("Invalid {{ e.name() }} enum value: {}", v),
+        })
+    }
+}
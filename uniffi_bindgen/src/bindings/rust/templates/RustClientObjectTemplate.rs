@@ -0,0 +1,40 @@
+{#
+// Objects hold the pointer that the component gave out for them, which is a reference to its
+// `Arc`, and release it when they're dropped. They share the loaded library with the component,
+// so that it stays loaded for as long as they need it.
+#}
+
+/// The `{{ obj.name() }}` object of the component.
+pub struct {{ obj.name() }} {
+    pointer: *const std::os::raw::c_void,
+    ffi: std::sync::Arc<UniffiFfi>,
+}
+
+// The component's objects are `Send` and `Sync`, so their pointers can be shared between threads.
+unsafe impl Send for {{ obj.name() }} {}
+unsafe impl Sync for {{ obj.name() }} {}
+
+impl {{ obj.name() }} {
+    {%- for cons in obj.constructors() %}
+
+    pub fn {{ cons.name() }}(component: &Component{% if !cons.arguments().is_empty() %}, {% endif %}{% call rs::arg_list_client(cons) %}) -> {% if cons.throws().is_some() %}Result<Self, {{ cons.throws().unwrap() }}>{% else %}Self{% endif %} {
+        let ffi = &component.ffi;
+        {%- call rs::call_body(cons, "", "Self { pointer: ret, ffi: std::sync::Arc::clone(ffi) }") %}
+    }
+    {%- endfor %}
+    {%- for meth in obj.methods() %}
+    {%- let lifted = meth|lift_method_client %}
+
+    pub fn {{ meth.name() }}(&self{% if !meth.arguments().is_empty() %}, {% endif %}{% call rs::arg_list_client(meth) %}){{ meth.return_type()|return_type_client(meth.throws()) }} {
+        let ffi = &self.ffi;
+        {%- call rs::call_body(meth, "self.pointer", lifted) %}
+    }
+    {%- endfor %}
+}
+
+impl Drop for {{ obj.name() }} {
+    fn drop(&mut self) {
+        let pointer = self.pointer;
+        self.ffi.call(|status| unsafe { (self.ffi.{{ obj.ffi_object_free().name() }})(pointer, status) });
+    }
+}
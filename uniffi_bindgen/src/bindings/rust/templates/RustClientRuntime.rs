@@ -0,0 +1,151 @@
+{#
+// The FFI functions of the component, looked up in its library, and the helpers that call them.
+//
+// The client and the component each have their own copy of the `uniffi` runtime, and might not
+// share an allocator, so each side frees the buffers that it allocated itself. Buffers are copied
+// into one that the other side allocates whenever they cross the FFI.
+#}
+
+#[allow(non_snake_case, clippy::type_complexity)]
+struct UniffiFfi {
+    {%- for func in self.ffi_functions() %}
+    {{ func.name() }}: unsafe extern "C" fn({% for arg in func.arguments() %}{{ arg.type_()|type_ffi }}, {% endfor %}&mut uniffi::RustCallStatus){% match func.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_ffi }}{% when None %}{% endmatch %},
+    {%- endfor %}
+    // Kept loaded for as long as the functions above might be called.
+    _library: libloading::Library,
+}
+
+// Look up a function in the library, copying the pointer to it out of the `Symbol` that
+// borrows the library.
+unsafe fn uniffi_symbol<T: Copy>(library: &libloading::Library, name: &[u8]) -> Result<T, libloading::Error> {
+    Ok(*library.get::<T>(name)?)
+}
+
+// Not every component needs all of these helpers.
+#[allow(dead_code)]
+impl UniffiFfi {
+    unsafe fn load(path: &std::path::Path) -> Result<Self, libloading::Error> {
+        let library = libloading::Library::new(path)?;
+        Ok(Self {
+            {%- for func in self.ffi_functions() %}
+            {{ func.name() }}: uniffi_symbol(&library, b"{{ func.name() }}\0")?,
+            {%- endfor %}
+            _library: library,
+        })
+    }
+
+    fn checksum(&self) -> u64 {
+        self.call(|status| unsafe { (self.{{ ci.ffi_checksum().name() }})(status) })
+    }
+
+    // Copy a buffer that the client allocated into one that the component allocates, to pass
+    // it to the component.
+    fn give_buffer(&self, buf: uniffi::RustBuffer) -> uniffi::RustBuffer {
+        let bytes = buf.destroy_into_vec();
+        // `RustBuffer::from_vec` already checked that the length fits.
+        let foreign = unsafe { uniffi::ForeignBytes::from_raw_parts(bytes.as_ptr(), bytes.len() as i32) };
+        self.call(|status| unsafe { (self.{{ ci.ffi_rustbuffer_from_bytes().name() }})(foreign, status) })
+    }
+
+    // Copy a buffer that the component returned into one that the client owns, and give the
+    // component's back to it to be freed.
+    fn take_buffer(&self, buf: uniffi::RustBuffer) -> uniffi::RustBuffer {
+        let copy = uniffi::RustBuffer::from_vec(buf.as_slice().to_vec());
+        self.call(|status| unsafe { (self.{{ ci.ffi_rustbuffer_free().name() }})(buf, status) });
+        copy
+    }
+
+    fn lower<FC>(&self, value: FC::RustType) -> FC::FfiType
+    where
+        FC: uniffi::FfiConverter,
+        FC::FfiType: UniffiFfiValue,
+    {
+        FC::lower(value).give(self)
+    }
+
+    fn lift<FC>(&self, value: FC::FfiType) -> FC::RustType
+    where
+        FC: uniffi::FfiConverter,
+        FC::FfiType: UniffiFfiValue,
+    {
+        FC::try_lift(value.take(self))
+            .unwrap_or_else(|err| panic!("Failed to lift a value from the component: {}", err))
+    }
+
+    // Call one of the component's FFI functions. If it returns an error, that's left in its
+    // buffer, for the caller to lift as the error that the function throws, and if it panics,
+    // so does this.
+    fn call_status<R>(&self, call: impl FnOnce(&mut uniffi::RustCallStatus) -> R) -> Result<R, uniffi::RustBuffer> {
+        let mut status = uniffi::RustCallStatus {
+            code: 0,
+            error_buf: std::mem::MaybeUninit::new(uniffi::RustBuffer::new()),
+        };
+        let ret = call(&mut status);
+        // This is either our empty buffer, or the one the component put there instead.
+        let error_buf = unsafe { status.error_buf.assume_init() };
+        match status.code {
+            0 => Ok(ret),
+            1 => Err(error_buf),
+            _ => {
+                let message = self.lift::<String>(error_buf);
+                if message.is_empty() {
+                    panic!("The `{{ ci.namespace() }}` component panicked");
+                }
+                panic!("The `{{ ci.namespace() }}` component panicked: {}", message)
+            }
+        }
+    }
+
+    // Call an FFI function that doesn't throw.
+    fn call<R>(&self, call: impl FnOnce(&mut uniffi::RustCallStatus) -> R) -> R {
+        match self.call_status(call) {
+            Ok(ret) => ret,
+            Err(buf) => {
+                self.take_buffer(buf).destroy();
+                panic!("The `{{ ci.namespace() }}` component returned an error that the function doesn't throw")
+            }
+        }
+    }
+
+    // Call an FFI function that throws errors lifted by `E`.
+    fn call_with_error<E, R, F>(&self, call: F) -> Result<R, E::RustType>
+    where
+        E: uniffi::FfiConverter<FfiType = uniffi::RustBuffer>,
+        F: FnOnce(&mut uniffi::RustCallStatus) -> R,
+    {
+        self.call_status(call).map_err(|buf| self.lift::<E>(buf))
+    }
+}
+
+// How the low-level values of the FFI are passed between the client and the component. Buffers
+// are copied across, and everything else is passed as it is.
+trait UniffiFfiValue: Sized {
+    fn give(self, _ffi: &UniffiFfi) -> Self {
+        self
+    }
+
+    fn take(self, _ffi: &UniffiFfi) -> Self {
+        self
+    }
+}
+
+impl UniffiFfiValue for i8 {}
+impl UniffiFfiValue for u8 {}
+impl UniffiFfiValue for i16 {}
+impl UniffiFfiValue for u16 {}
+impl UniffiFfiValue for i32 {}
+impl UniffiFfiValue for u32 {}
+impl UniffiFfiValue for i64 {}
+impl UniffiFfiValue for u64 {}
+impl UniffiFfiValue for f32 {}
+impl UniffiFfiValue for f64 {}
+
+impl UniffiFfiValue for uniffi::RustBuffer {
+    fn give(self, ffi: &UniffiFfi) -> Self {
+        ffi.give_buffer(self)
+    }
+
+    fn take(self, ffi: &UniffiFfi) -> Self {
+        ffi.take_buffer(self)
+    }
+}
@@ -0,0 +1,43 @@
+{#
+// Templates for calling into the component through its FFI.
+#}
+
+{#-
+// The arguments of a function, constructor or method, as the client declares them.
+-#}
+{%- macro arg_list_client(func) %}
+    {%- for arg in func.arguments() %}
+        {{- arg.name() }}: {{ arg|arg_type_client }}{% if !loop.last %}, {% endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{#-
+// The body of a function, constructor or method, which lowers its arguments, calls its FFI
+// function through `ffi`, and returns `lifted`, the value that it returned lifted from `ret`,
+// or nothing if `lifted` is empty. `receiver` is the pointer to the object that a method is
+// called on, or empty for functions and constructors.
+-#}
+{%- macro call_body(func, receiver, lifted) %}
+        {%- for arg in func.arguments() %}
+        let {{ arg.name() }} = {{ arg|lower_client }};
+        {%- endfor %}
+        {%- match func.throws_type() %}
+        {%- when Some with (e) %}
+        {% if !lifted.is_empty() %}let ret = {% endif %}ffi.call_with_error::<{{ e|ffi_converter_name }}, _, _>(|status| unsafe {
+            (ffi.{{ func.ffi_func().name() }})({% call _ffi_args(func, receiver) %})
+        })?;
+        Ok({% if lifted.is_empty() %}(){% else %}{{ lifted }}{% endif %})
+        {%- when None %}
+        {% if !lifted.is_empty() %}let ret = {% endif %}ffi.call(|status| unsafe {
+            (ffi.{{ func.ffi_func().name() }})({% call _ffi_args(func, receiver) %})
+        });
+        {%- if !lifted.is_empty() %}
+        {{ lifted }}
+        {%- endif %}
+        {%- endmatch %}
+{%- endmacro -%}
+
+{%- macro _ffi_args(func, receiver) %}
+    {%- if !receiver.is_empty() %}{{ receiver }}, {% endif %}
+    {%- for arg in func.arguments() %}{{ arg.name() }}, {% endfor %}status
+{%- endmacro -%}
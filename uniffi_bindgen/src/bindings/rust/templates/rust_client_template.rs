@@ -0,0 +1,108 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+//
+// The Rust client for the `{{ ci.namespace() }}` component, which loads the component's cdylib at
+// runtime and calls it through its FFI, just as the foreign-language bindings do. The crate that
+// uses it depends on `libloading`, and on the same version of `uniffi` as the component.
+{% import "rust_client_macros.rs" as rs %}
+
+// Check for compatibility between `uniffi` and `uniffi_bindgen` versions.
+// Note that we have an error message on the same line as the assertion.
+// This is important, because if the assertion fails, the compiler only
+// seems to show that single line as context for the user.
+uniffi::assert_compatible_version!("{{ self.uniffi_version() }}"); // Please check that you depend on version {{ self.uniffi_version() }} of the `uniffi` crate.
+
+{% include "RustClientRuntime.rs" %}
+
+/// The `{{ ci.namespace() }}` component, loaded from its cdylib.
+///
+/// Its namespace functions are methods, and the objects that it creates keep the library loaded
+/// for as long as they're alive.
+#[derive(Clone)]
+pub struct Component {
+    ffi: std::sync::Arc<UniffiFfi>,
+}
+
+impl Component {
+    /// Load the component from the cdylib at `path`.
+    ///
+    /// This fails if the library isn't a build of the component that the client was generated
+    /// for, with the same interface.
+    ///
+    /// # Safety
+    ///
+    /// Loading the library runs its initialization code, and the client trusts the functions
+    /// that it exports to be the component's, which may not be the case if it's been swapped
+    /// for another one with the same symbols. See [`libloading::Library::new`].
+    pub unsafe fn load(path: impl AsRef<std::path::Path>) -> uniffi::deps::anyhow::Result<Self> {
+        let ffi = UniffiFfi::load(path.as_ref())?;
+        if ffi.checksum() != {{ ci.checksum() }} {
+            uniffi::deps::anyhow::bail!(
+                "{} isn't a build of the `{{ ci.namespace() }}` component that the client was generated for",
+                path.as_ref().display()
+            );
+        }
+        Ok(Self { ffi: std::sync::Arc::new(ffi) })
+    }
+    {%- for func in ci.iter_function_definitions() %}
+    {%- let lifted = func.return_type()|lift_client %}
+
+    pub fn {{ func.name() }}(&self{% if !func.arguments().is_empty() %}, {% endif %}{% call rs::arg_list_client(func) %}){{ func.return_type()|return_type_client(func.throws()) }} {
+        let ffi = &self.ffi;
+        {%- call rs::call_body(func, "", lifted) %}
+    }
+    {%- endfor %}
+}
+
+{%- for rec in ci.iter_record_definitions() %}
+
+/// The `{{ rec.name() }}` dictionary of the component.
+#[derive({% if !rec.has_sensitive_fields() %}Debug, {% endif %}Clone, PartialEq)]
+pub struct {{ rec.name() }} {
+    {%- for field in rec.fields() %}
+    pub {{ field.name() }}: {{ field.type_()|type_rs }},
+    {%- endfor %}
+}
+{%- if rec.has_sensitive_fields() %}
+
+// `[Sensitive]` fields are left out, so that they don't end up in logs.
+impl std::fmt::Debug for {{ rec.name() }} {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("{{ rec.name() }}")
+            {%- for field in rec.fields() %}
+            {%- if field.sensitive() %}
+            .field("{{ field.name() }}", &format_args!("***"))
+            {%- else %}
+            .field("{{ field.name() }}", &self.{{ field.name() }})
+            {%- endif %}
+            {%- endfor %}
+            .finish()
+    }
+}
+{%- endif %}
+{% include "RecordTemplate.rs" %}
+{%- endfor %}
+
+{%- for e in ci.iter_enum_definitions() %}
+
+/// The `{{ e.name() }}` enum of the component.
+#[derive(Debug, Clone, PartialEq)]
+pub enum {{ e.name() }} {
+    {%- for variant in e.variants() %}
+    {%- if variant.has_fields() %}
+    {{ variant.name() }} { {% for field in variant.fields() %}{{ field.name() }}: {{ field.type_()|type_rs }}{% if !loop.last %}, {% endif %}{% endfor %} },
+    {%- else %}
+    {{ variant.name() }},
+    {%- endif %}
+    {%- endfor %}
+}
+{% include "EnumTemplate.rs" %}
+{%- endfor %}
+
+{%- for e in ci.iter_error_definitions() %}
+{% include "RustClientErrorTemplate.rs" %}
+{%- endfor %}
+
+{%- for obj in ci.iter_object_definitions() %}
+{% include "RustClientObjectTemplate.rs" %}
+{%- endfor %}
@@ -5,7 +5,7 @@
 use std::fmt;
 
 use crate::backend::{CodeDeclaration, CodeOracle, CodeType, Literal};
-use crate::interface::{CallbackInterface, ComponentInterface};
+use crate::interface::{CallbackInterface, ComponentInterface, FFIFunction, Type};
 use askama::Template;
 
 use super::filters;
@@ -112,12 +112,15 @@ impl CodeDeclaration for SwiftCallbackInterface {
 )]
 pub struct SwiftCallbackInterfaceRuntime {
     is_needed: bool,
+    // The function that completes the futures of `[Async]` methods, if there are any.
+    ffi_callback_future_complete: Option<FFIFunction>,
 }
 
 impl SwiftCallbackInterfaceRuntime {
     pub fn new(ci: &ComponentInterface) -> Self {
         Self {
             is_needed: !ci.iter_callback_interface_definitions().is_empty(),
+            ffi_callback_future_complete: ci.ffi_callback_future_complete(),
         }
     }
 }
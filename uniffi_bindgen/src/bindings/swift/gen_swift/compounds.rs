@@ -2,8 +2,9 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use crate::backend::{CodeOracle, CodeType, Literal, TypeIdentifier};
+use crate::backend::{CodeDeclaration, CodeOracle, CodeType, Literal, TypeIdentifier};
 use crate::interface::types::Type;
+use crate::interface::ComponentInterface;
 use askama::Template;
 use paste::paste;
 use std::fmt;
@@ -100,8 +101,122 @@ impl_code_type_for_compound!(
 );
 
 impl_code_type_for_compound!(
-    MapCodeType,
-    "[String: {}]",
-    "Dictionary{}",
-    "MapTemplate.swift"
+    OrderedMapCodeType,
+    "OrderedMap<{}>",
+    "OrderedMap{}",
+    "OrderedMapTemplate.swift"
 );
+
+// Maps have a key type as well as the value type, so they don't fit the macro above.
+#[derive(Template)]
+#[template(syntax = "swift", escape = "none", path = "MapTemplate.swift")]
+pub struct MapCodeType {
+    key: TypeIdentifier,
+    value: TypeIdentifier,
+    outer: TypeIdentifier,
+}
+
+impl MapCodeType {
+    pub fn new(key: TypeIdentifier, value: TypeIdentifier, outer: TypeIdentifier) -> Self {
+        Self { key, value, outer }
+    }
+    fn key(&self) -> &TypeIdentifier {
+        &self.key
+    }
+    fn inner(&self) -> &TypeIdentifier {
+        &self.value
+    }
+    fn outer(&self) -> &TypeIdentifier {
+        &self.outer
+    }
+
+    fn ffi_converter_name(&self, oracle: &dyn CodeOracle) -> String {
+        format!("FfiConverter{}", self.canonical_name(oracle))
+    }
+}
+
+impl CodeType for MapCodeType {
+    fn type_label(&self, oracle: &dyn CodeOracle) -> String {
+        format!(
+            "[{}: {}]",
+            oracle.find(self.key()).type_label(oracle),
+            oracle.find(self.inner()).type_label(oracle)
+        )
+    }
+
+    fn canonical_name(&self, oracle: &dyn CodeOracle) -> String {
+        format!(
+            "Dictionary{}_{}",
+            oracle.find(self.key()).canonical_name(oracle),
+            oracle.find(self.inner()).canonical_name(oracle)
+        )
+    }
+
+    fn literal(&self, oracle: &dyn CodeOracle, literal: &Literal) -> String {
+        render_literal(oracle, literal, self.inner())
+    }
+
+    fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "{}.lower({})",
+            self.ffi_converter_name(oracle),
+            oracle.var_name(nm)
+        )
+    }
+
+    fn write(
+        &self,
+        oracle: &dyn CodeOracle,
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+    ) -> String {
+        format!(
+            "{}.write({}, into: {})",
+            self.ffi_converter_name(oracle),
+            oracle.var_name(nm),
+            target
+        )
+    }
+
+    fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("{}.lift({})", self.ffi_converter_name(oracle), nm)
+    }
+
+    fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("{}.read(from: {})", self.ffi_converter_name(oracle), nm)
+    }
+
+    fn helper_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+}
+
+/// The `OrderedMap` struct that `[OrderedMap]` typedefs are lifted into, since Swift
+/// dictionaries don't keep their insertion order. It's only emitted for components that
+/// have ordered maps.
+#[derive(Template)]
+#[template(syntax = "swift", escape = "none", path = "OrderedMapRuntime.swift")]
+pub struct SwiftOrderedMapRuntime {
+    is_needed: bool,
+}
+
+impl SwiftOrderedMapRuntime {
+    pub fn new(ci: &ComponentInterface) -> Self {
+        Self {
+            is_needed: ci
+                .iter_types()
+                .iter()
+                .any(|t| matches!(t, Type::OrderedMap(_))),
+        }
+    }
+}
+
+impl CodeDeclaration for SwiftOrderedMapRuntime {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        if self.is_needed {
+            Some(self.render().unwrap())
+        } else {
+            None
+        }
+    }
+}
@@ -70,12 +70,15 @@ impl CodeType for EnumCodeType {
 pub struct SwiftEnum {
     inner: Enum,
     contains_object_references: bool,
+    is_codable: bool,
 }
 
 impl SwiftEnum {
-    pub fn new(inner: Enum, ci: &ComponentInterface) -> Self {
+    pub fn new(inner: Enum, ci: &ComponentInterface, codable_records: bool) -> Self {
+        let contains_object_references = ci.item_contains_object_references(&inner);
         Self {
-            contains_object_references: ci.item_contains_object_references(&inner),
+            contains_object_references,
+            is_codable: codable_records && !contains_object_references,
             inner,
         }
     }
@@ -85,6 +88,9 @@ impl SwiftEnum {
     pub fn contains_object_references(&self) -> bool {
         self.contains_object_references
     }
+    pub fn is_codable(&self) -> bool {
+        self.is_codable
+    }
 }
 
 impl CodeDeclaration for SwiftEnum {
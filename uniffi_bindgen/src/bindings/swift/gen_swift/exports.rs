@@ -0,0 +1,38 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::backend::{CodeDeclaration, CodeOracle};
+use crate::interface::ComponentInterface;
+use crate::lockfile::{exported_callables, ExportedCallable};
+use askama::Template;
+
+#[allow(unused_imports)]
+use super::filters;
+
+// An enum listing every function, constructor and method exported by the component.
+#[derive(Template)]
+#[template(syntax = "swift", escape = "none", path = "ExportsTemplate.swift")]
+pub struct SwiftExports {
+    namespace: String,
+    exports: Vec<ExportedCallable>,
+}
+
+impl SwiftExports {
+    pub fn new(ci: &ComponentInterface) -> Self {
+        Self {
+            namespace: ci.namespace().to_string(),
+            exports: exported_callables(ci),
+        }
+    }
+}
+
+impl CodeDeclaration for SwiftExports {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        if self.exports.is_empty() {
+            None
+        } else {
+            Some(self.render().unwrap())
+        }
+    }
+}
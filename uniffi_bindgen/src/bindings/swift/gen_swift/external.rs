@@ -0,0 +1,296 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::fmt;
+
+use crate::backend::{CodeDeclaration, CodeOracle, CodeType, Literal};
+use crate::interface::{ComponentInterface, Type};
+use askama::Template;
+use heck::CamelCase;
+
+use super::filters;
+
+/// The module that the bindings of the component built from `crate_name` are expected to be in.
+pub fn external_module_name(crate_name: &str) -> String {
+    crate_name.replace('-', "_")
+}
+
+/// The name of the enum with the helpers that a component's bindings have for other components.
+///
+/// It's named after the component, rather than qualified with the module name, because Swift
+/// can't qualify a name with a module that has a type of the same name.
+fn external_types_enum_name(namespace: &str) -> String {
+    format!("{}ExternalTypes", namespace.to_camel_case())
+}
+
+/// The name of the function that checks the component built from `crate_name` against this one.
+fn check_component_fn_name(crate_name: &str) -> String {
+    format!(
+        "uniffiCheck{}Component",
+        external_module_name(crate_name).to_camel_case()
+    )
+}
+
+#[derive(Template)]
+#[template(syntax = "swift", escape = "none", path = "ExternalTemplate.swift")]
+pub struct ExternalCodeType {
+    name: String,
+    crate_name: String,
+}
+
+impl ExternalCodeType {
+    pub fn new(name: String, crate_name: String) -> Self {
+        Self { name, crate_name }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn canonical_type_name(&self) -> String {
+        format!("External{}", self.name)
+    }
+
+    pub fn module_name(&self) -> String {
+        external_module_name(&self.crate_name)
+    }
+
+    pub fn external_types_enum_name(&self) -> String {
+        external_types_enum_name(&self.module_name())
+    }
+
+    fn ffi_converter_name(&self, oracle: &dyn CodeOracle) -> String {
+        format!("FfiConverter{}", self.canonical_name(oracle))
+    }
+}
+
+impl CodeType for ExternalCodeType {
+    fn type_label(&self, _oracle: &dyn CodeOracle) -> String {
+        self.name.clone()
+    }
+
+    fn canonical_name(&self, _oracle: &dyn CodeOracle) -> String {
+        self.canonical_type_name()
+    }
+
+    fn literal(&self, _oracle: &dyn CodeOracle, _literal: &Literal) -> String {
+        unreachable!("Can't have a literal of an external type");
+    }
+
+    fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "{}.lower({})",
+            self.ffi_converter_name(oracle),
+            oracle.var_name(nm)
+        )
+    }
+
+    fn write(
+        &self,
+        oracle: &dyn CodeOracle,
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+    ) -> String {
+        format!(
+            "{}.write({}, into: {})",
+            self.ffi_converter_name(oracle),
+            oracle.var_name(nm),
+            target
+        )
+    }
+
+    fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("{}.lift({})", self.ffi_converter_name(oracle), nm)
+    }
+
+    fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("{}.read(from: {})", self.ffi_converter_name(oracle), nm)
+    }
+
+    fn helper_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        Some(vec![self.module_name()])
+    }
+}
+
+#[derive(Template)]
+#[template(
+    syntax = "swift",
+    escape = "none",
+    path = "ExternalInterfaceTemplate.swift"
+)]
+pub struct ExternalInterfaceCodeType {
+    name: String,
+    crate_name: String,
+}
+
+impl ExternalInterfaceCodeType {
+    pub fn new(name: String, crate_name: String) -> Self {
+        Self { name, crate_name }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn canonical_type_name(&self) -> String {
+        format!("External{}", self.name)
+    }
+
+    pub fn module_name(&self) -> String {
+        external_module_name(&self.crate_name)
+    }
+
+    pub fn external_types_enum_name(&self) -> String {
+        external_types_enum_name(&self.module_name())
+    }
+
+    pub fn check_component_fn_name(&self) -> String {
+        check_component_fn_name(&self.crate_name)
+    }
+
+    fn ffi_converter_name(&self, oracle: &dyn CodeOracle) -> String {
+        format!("FfiConverter{}", self.canonical_name(oracle))
+    }
+}
+
+impl CodeType for ExternalInterfaceCodeType {
+    fn type_label(&self, _oracle: &dyn CodeOracle) -> String {
+        self.name.clone()
+    }
+
+    fn canonical_name(&self, _oracle: &dyn CodeOracle) -> String {
+        self.canonical_type_name()
+    }
+
+    fn literal(&self, _oracle: &dyn CodeOracle, _literal: &Literal) -> String {
+        unreachable!();
+    }
+
+    fn lower(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!(
+            "{}.lower({})",
+            self.ffi_converter_name(oracle),
+            oracle.var_name(nm)
+        )
+    }
+
+    fn write(
+        &self,
+        oracle: &dyn CodeOracle,
+        nm: &dyn fmt::Display,
+        target: &dyn fmt::Display,
+    ) -> String {
+        format!(
+            "{}.write({}, into: {})",
+            self.ffi_converter_name(oracle),
+            oracle.var_name(nm),
+            target
+        )
+    }
+
+    fn lift(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("{}.lift({})", self.ffi_converter_name(oracle), nm)
+    }
+
+    fn read(&self, oracle: &dyn CodeOracle, nm: &dyn fmt::Display) -> String {
+        format!("{}.read(from: {})", self.ffi_converter_name(oracle), nm)
+    }
+
+    fn helper_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+
+    fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
+        Some(vec![self.module_name()])
+    }
+}
+
+/// A type of this component that the bindings of other components can use as an external type.
+pub struct ExportedType {
+    name: String,
+    type_: Type,
+    is_object: bool,
+}
+
+/// A component that this one takes `[ExternalInterface]` objects from.
+pub struct ExternalComponent {
+    module_name: String,
+    external_types_enum_name: String,
+    check_component_fn_name: String,
+    checksum_fn_name: String,
+}
+
+// The helpers that other components' bindings use for our types, since those that the types
+// have themselves are `fileprivate`, and the checks of the components that we use objects from.
+#[derive(Template)]
+#[template(
+    syntax = "swift",
+    escape = "none",
+    path = "ExternalTypesTemplate.swift"
+)]
+pub struct SwiftExternalTypes {
+    namespace: String,
+    external_types_enum_name: String,
+    exported_types: Vec<ExportedType>,
+    external_components: Vec<ExternalComponent>,
+}
+
+impl SwiftExternalTypes {
+    pub fn new(ci: &ComponentInterface) -> Self {
+        let exported_types = ci
+            .iter_types()
+            .into_iter()
+            .filter_map(|type_| {
+                let (name, is_object) = match &type_ {
+                    Type::Record(name)
+                    | Type::Enum(name)
+                    | Type::Error(name)
+                    | Type::Wrapped { name, .. } => (name.clone(), false),
+                    Type::Object(name) => match ci.get_object_definition(name) {
+                        Some(obj) if !obj.is_opaque_token() => (name.clone(), true),
+                        _ => return None,
+                    },
+                    _ => return None,
+                };
+                Some(ExportedType {
+                    name,
+                    type_,
+                    is_object,
+                })
+            })
+            .collect();
+        let external_components = ci
+            .iter_external_interface_crates()
+            .into_iter()
+            .map(|crate_name| ExternalComponent {
+                module_name: external_module_name(&crate_name),
+                external_types_enum_name: external_types_enum_name(&external_module_name(
+                    &crate_name,
+                )),
+                check_component_fn_name: check_component_fn_name(&crate_name),
+                checksum_fn_name: ci.ffi_external_checksum(&crate_name).name().to_string(),
+            })
+            .collect();
+        Self {
+            namespace: ci.namespace().to_string(),
+            external_types_enum_name: external_types_enum_name(ci.namespace()),
+            exported_types,
+            external_components,
+        }
+    }
+}
+
+impl CodeDeclaration for SwiftExternalTypes {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        if self.exported_types.is_empty() && self.external_components.is_empty() {
+            None
+        } else {
+            Some(self.render().unwrap())
+        }
+    }
+}
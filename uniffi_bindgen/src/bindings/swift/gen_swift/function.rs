@@ -3,7 +3,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use crate::backend::{CodeDeclaration, CodeOracle};
-use crate::interface::{ComponentInterface, Function};
+use crate::interface::{ComponentInterface, FFIFunction, Function, Module, Type};
 use askama::Template;
 
 use super::filters;
@@ -16,15 +16,22 @@ use super::filters;
 )]
 pub struct SwiftFunction {
     inner: Function,
+    errors_as_values: bool,
 }
 
 impl SwiftFunction {
-    pub fn new(inner: Function, _ci: &ComponentInterface) -> Self {
-        Self { inner }
+    pub fn new(inner: Function, _ci: &ComponentInterface, errors_as_values: bool) -> Self {
+        Self {
+            inner,
+            errors_as_values,
+        }
     }
     pub fn inner(&self) -> &Function {
         &self.inner
     }
+    pub fn errors_as_values(&self) -> bool {
+        self.errors_as_values
+    }
 }
 
 impl CodeDeclaration for SwiftFunction {
@@ -32,3 +39,71 @@ impl CodeDeclaration for SwiftFunction {
         Some(self.render().unwrap())
     }
 }
+
+// A namespace nested inside the component's namespace, holding its functions
+// and any namespaces nested further inside it.
+#[derive(Template)]
+#[template(syntax = "swift", escape = "none", path = "ModuleTemplate.swift")]
+pub struct SwiftModule {
+    inner: Module,
+    functions: Vec<SwiftFunction>,
+    children: Vec<SwiftModule>,
+}
+
+impl SwiftModule {
+    pub fn new(inner: Module, ci: &ComponentInterface, errors_as_values: bool) -> Self {
+        Self {
+            functions: ci
+                .iter_module_function_definitions(&inner)
+                .into_iter()
+                .map(|func| SwiftFunction::new(func, ci, errors_as_values))
+                .collect(),
+            children: ci
+                .iter_child_module_definitions(&inner)
+                .into_iter()
+                .map(|module| SwiftModule::new(module, ci, errors_as_values))
+                .collect(),
+            inner,
+        }
+    }
+    pub fn inner(&self) -> &Module {
+        &self.inner
+    }
+    pub fn member_code(&self) -> Vec<String> {
+        self.functions
+            .iter()
+            .map(|func| func.render().unwrap())
+            .chain(self.children.iter().map(|module| module.render().unwrap()))
+            .collect()
+    }
+}
+
+impl CodeDeclaration for SwiftModule {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+}
+
+// The calls to `[Async]` functions and methods that are waiting for Rust to complete them, and
+// the completion that Rust calls once they're done.
+#[derive(Template)]
+#[template(syntax = "swift", escape = "none", path = "AsyncRuntime.swift")]
+pub struct SwiftAsyncRuntime {
+    ffi_init: Option<FFIFunction>,
+    ffi_cancel: Option<FFIFunction>,
+}
+
+impl SwiftAsyncRuntime {
+    pub fn new(ci: &ComponentInterface) -> Self {
+        Self {
+            ffi_init: ci.ffi_async_init(),
+            ffi_cancel: ci.ffi_async_cancel(),
+        }
+    }
+}
+
+impl CodeDeclaration for SwiftAsyncRuntime {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        self.ffi_init.as_ref().map(|_| self.render().unwrap())
+    }
+}
@@ -65,6 +65,24 @@ macro_rules! impl_code_type_for_miscellany {
      }
  }
 
+impl_code_type_for_miscellany!(
+    BigIntCodeType,
+    "BigInt",
+    "BigInt",
+    vec![],
+    "BigIntHelper.swift"
+);
+
+impl_code_type_for_miscellany!(BytesCodeType, "Data", "Bytes", vec![], "BytesHelper.swift");
+
+impl_code_type_for_miscellany!(
+    CharCodeType,
+    "Unicode.Scalar",
+    "Char",
+    vec![],
+    "CharHelper.swift"
+);
+
 impl_code_type_for_miscellany!(
     TimestampCodeType,
     "Date",
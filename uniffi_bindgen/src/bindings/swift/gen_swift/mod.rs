@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 
 use anyhow::Result;
@@ -10,14 +10,20 @@ use askama::Template;
 use heck::{CamelCase, MixedCase};
 use serde::{Deserialize, Serialize};
 
-use crate::backend::{CodeDeclaration, CodeOracle, CodeType, TypeIdentifier};
+use crate::backend::{
+    custom_types, renames, renames::RenameKind, ApiFilter, CodeDeclaration, CodeOracle, CodeType,
+    CustomTypesConfig, NamingProfile, Renames, TypeIdentifier,
+};
 use crate::interface::*;
 use crate::MergeWith;
 
 mod callback_interface;
 mod compounds;
+mod custom;
 mod enum_;
 mod error;
+mod exports;
+mod external;
 mod function;
 mod miscellany;
 mod object;
@@ -36,6 +42,19 @@ pub struct Config {
     ffi_module_name: Option<String>,
     ffi_module_filename: Option<String>,
     generate_module_map: Option<bool>,
+    inline_single_field_records: Option<bool>,
+    codable_records: Option<bool>,
+    #[serde(default)]
+    custom_types: CustomTypesConfig,
+    #[serde(default)]
+    renames: Renames,
+    naming: Option<NamingProfile>,
+    #[serde(default)]
+    foreign_extensions: BTreeMap<String, String>,
+    #[serde(default)]
+    filter: ApiFilter,
+    observable_models: Option<bool>,
+    errors_as_values: Option<bool>,
 }
 
 impl Config {
@@ -86,6 +105,62 @@ impl Config {
     pub fn generate_module_map(&self) -> bool {
         self.generate_module_map.unwrap_or(true)
     }
+
+    /// Whether records with a single field are generated as `@frozen` structs.
+    pub fn inline_single_field_records(&self) -> bool {
+        self.inline_single_field_records.unwrap_or(false)
+    }
+
+    /// Whether records, and the enums they might contain, conform to `Codable`.
+    pub fn codable_records(&self) -> bool {
+        self.codable_records.unwrap_or(false)
+    }
+
+    /// Types mapped onto custom Swift types, keyed by their UDL name.
+    pub fn custom_types(&self) -> &CustomTypesConfig {
+        &self.custom_types
+    }
+
+    /// Legacy Swift names for identifiers in the component, see [`crate::backend::renames`].
+    pub fn renames(&self) -> &Renames {
+        &self.renames
+    }
+
+    /// How to name the identifiers that aren't renamed, see [`crate::backend::renames`].
+    pub fn naming(&self) -> NamingProfile {
+        self.naming.unwrap_or_default()
+    }
+
+    /// The module to import a `[ForeignExtension]` protocol from, when it isn't in the
+    /// same module as the bindings.
+    pub fn foreign_extension_module(&self, name: &str) -> Option<&str> {
+        self.foreign_extensions.get(name).map(String::as_str)
+    }
+
+    /// The part of the component to generate bindings for, see [`crate::backend::filter`].
+    pub fn filter(&self) -> &ApiFilter {
+        &self.filter
+    }
+
+    /// Whether each `[Observable]` object also gets an `ObservableObject` publishing its latest
+    /// snapshot, on the platforms that have Combine.
+    pub fn observable_models(&self) -> bool {
+        self.observable_models.unwrap_or(false)
+    }
+
+    /// Whether the blocking functions and methods that throw return a `Result` instead, of
+    /// either what they returned or the error that they'd otherwise throw.
+    pub fn errors_as_values(&self) -> bool {
+        self.errors_as_values.unwrap_or(false)
+    }
+
+    /// Fill in any renames not already configured from those in a `renames.toml` file.
+    pub fn with_renames_from(&self, renames: &Renames) -> Self {
+        Config {
+            renames: self.renames.merge_with(renames),
+            ..self.clone()
+        }
+    }
 }
 
 impl From<&ComponentInterface> for Config {
@@ -110,6 +185,19 @@ impl MergeWith for Config {
             generate_module_map: self
                 .generate_module_map
                 .merge_with(&other.generate_module_map),
+            inline_single_field_records: self
+                .inline_single_field_records
+                .merge_with(&other.inline_single_field_records),
+            codable_records: self.codable_records.merge_with(&other.codable_records),
+            custom_types: self.custom_types.merge_with(&other.custom_types),
+            renames: self.renames.merge_with(&other.renames),
+            naming: self.naming.merge_with(&other.naming),
+            foreign_extensions: self
+                .foreign_extensions
+                .merge_with(&other.foreign_extensions),
+            filter: self.filter.merge_with(&other.filter),
+            observable_models: self.observable_models.merge_with(&other.observable_models),
+            errors_as_values: self.errors_as_values.merge_with(&other.errors_as_values),
         }
     }
 }
@@ -170,32 +258,85 @@ impl<'a> SwiftWrapper<'a> {
         }
     }
 
+    /// The version of `uniffi-bindgen` that's generating the bindings.
+    pub fn bindgen_version(&self) -> &'static str {
+        crate::BINDGEN_VERSION
+    }
+
     pub fn members(&self) -> Vec<Box<dyn CodeDeclaration + 'a>> {
         let ci = self.ci;
+        let inline_records = self.config.inline_single_field_records();
+        let codable = self.config.codable_records();
+        let errors_as_values = self.config.errors_as_values();
         vec![
             Box::new(object::SwiftObjectRuntime::new(ci)) as Box<dyn CodeDeclaration>,
             Box::new(callback_interface::SwiftCallbackInterfaceRuntime::new(ci))
                 as Box<dyn CodeDeclaration>,
+            Box::new(function::SwiftAsyncRuntime::new(ci)) as Box<dyn CodeDeclaration>,
+            Box::new(exports::SwiftExports::new(ci)) as Box<dyn CodeDeclaration>,
+            Box::new(compounds::SwiftOrderedMapRuntime::new(ci)) as Box<dyn CodeDeclaration>,
+            Box::new(external::SwiftExternalTypes::new(ci)) as Box<dyn CodeDeclaration>,
         ]
         .into_iter()
+        .chain(ci.iter_enum_definitions().into_iter().map(|inner| {
+            Box::new(enum_::SwiftEnum::new(inner, ci, codable)) as Box<dyn CodeDeclaration>
+        }))
         .chain(
-            ci.iter_enum_definitions().into_iter().map(|inner| {
-                Box::new(enum_::SwiftEnum::new(inner, ci)) as Box<dyn CodeDeclaration>
-            }),
+            ci.iter_namespace_function_definitions()
+                .into_iter()
+                .map(|inner| {
+                    Box::new(function::SwiftFunction::new(inner, ci, errors_as_values))
+                        as Box<dyn CodeDeclaration>
+                }),
         )
-        .chain(ci.iter_function_definitions().into_iter().map(|inner| {
-            Box::new(function::SwiftFunction::new(inner, ci)) as Box<dyn CodeDeclaration>
+        .chain(
+            ci.iter_top_level_module_definitions()
+                .into_iter()
+                .map(|inner| {
+                    Box::new(function::SwiftModule::new(inner, ci, errors_as_values))
+                        as Box<dyn CodeDeclaration>
+                }),
+        )
+        .chain(ci.iter_object_definitions().into_iter().map(|inner| {
+            if inner.is_opaque_token() {
+                return Box::new(object::SwiftOpaqueToken::new(inner, ci))
+                    as Box<dyn CodeDeclaration>;
+            }
+            let extension_module = inner
+                .foreign_extension()
+                .and_then(|ext| self.config.foreign_extension_module(ext))
+                .map(Into::into);
+            Box::new(object::SwiftObject::new(
+                inner,
+                ci,
+                extension_module,
+                errors_as_values,
+            )) as Box<dyn CodeDeclaration>
         }))
         .chain(
-            ci.iter_object_definitions().into_iter().map(|inner| {
-                Box::new(object::SwiftObject::new(inner, ci)) as Box<dyn CodeDeclaration>
-            }),
+            ci.iter_object_definitions()
+                .into_iter()
+                .filter(|inner| {
+                    self.config.observable_models() && inner.observable_model().is_some()
+                })
+                .map(|inner| {
+                    Box::new(object::SwiftObservableModel::new(inner, ci))
+                        as Box<dyn CodeDeclaration>
+                }),
         )
         .chain(
-            ci.iter_record_definitions().into_iter().map(|inner| {
-                Box::new(record::SwiftRecord::new(inner, ci)) as Box<dyn CodeDeclaration>
-            }),
+            ci.iter_object_definitions()
+                .into_iter()
+                .filter(|inner| inner.events_type().is_some())
+                .map(|inner| {
+                    Box::new(object::SwiftEventPublisher::new(inner, ci))
+                        as Box<dyn CodeDeclaration>
+                }),
         )
+        .chain(ci.iter_record_definitions().into_iter().map(|inner| {
+            Box::new(record::SwiftRecord::new(inner, ci, inline_records, codable))
+                as Box<dyn CodeDeclaration>
+        }))
         .chain(
             ci.iter_error_definitions().into_iter().map(|inner| {
                 Box::new(error::SwiftError::new(inner, ci)) as Box<dyn CodeDeclaration>
@@ -236,8 +377,7 @@ impl<'a> SwiftWrapper<'a> {
 
     pub fn imports(&self) -> Vec<String> {
         let oracle = &self.oracle;
-        let mut imports: Vec<String> = self
-            .members()
+        self.members()
             .into_iter()
             .filter_map(|member| member.imports(oracle))
             .flatten()
@@ -248,12 +388,9 @@ impl<'a> SwiftWrapper<'a> {
                     .filter_map(|type_| oracle.find(&type_).imports(oracle))
                     .flatten(),
             )
-            .collect::<HashSet<String>>()
+            .collect::<BTreeSet<String>>()
             .into_iter()
-            .collect();
-
-        imports.sort();
-        imports
+            .collect()
     }
 }
 
@@ -262,6 +399,17 @@ pub struct SwiftCodeOracle;
 
 impl SwiftCodeOracle {
     fn create_code_type(&self, type_: TypeIdentifier) -> Box<dyn CodeType> {
+        match custom_types::find_custom_type(&type_) {
+            Some(config) => Box::new(custom::CustomCodeType::new(
+                config,
+                self.create_builtin_code_type(type_.clone()),
+                FFIType::from(&type_),
+            )),
+            None => self.create_builtin_code_type(type_),
+        }
+    }
+
+    fn create_builtin_code_type(&self, type_: TypeIdentifier) -> Box<dyn CodeType> {
         // I really want access to the ComponentInterface here so I can look up the interface::{Enum, Record, Error, Object, etc}
         // However, there's some violence and gore I need to do to (temporarily) make the oracle usable from filters.
 
@@ -280,6 +428,9 @@ impl SwiftCodeOracle {
             Type::Boolean => Box::new(primitives::BooleanCodeType),
             Type::String => Box::new(primitives::StringCodeType),
 
+            Type::BigInt => Box::new(miscellany::BigIntCodeType),
+            Type::Bytes => Box::new(miscellany::BytesCodeType),
+            Type::Char => Box::new(miscellany::CharCodeType),
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
 
@@ -301,12 +452,23 @@ impl SwiftCodeOracle {
                 let inner = *inner.to_owned();
                 Box::new(compounds::SequenceCodeType::new(inner, outer))
             }
-            Type::Map(ref inner) => {
+            Type::Map(ref key, ref value) => {
+                let outer = type_.clone();
+                let key = *key.to_owned();
+                let value = *value.to_owned();
+                Box::new(compounds::MapCodeType::new(key, value, outer))
+            }
+            Type::OrderedMap(ref inner) => {
                 let outer = type_.clone();
                 let inner = *inner.to_owned();
-                Box::new(compounds::MapCodeType::new(inner, outer))
+                Box::new(compounds::OrderedMapCodeType::new(inner, outer))
+            }
+            Type::External { name, crate_name } => {
+                Box::new(external::ExternalCodeType::new(name, crate_name))
+            }
+            Type::ExternalInterface { name, crate_name } => {
+                Box::new(external::ExternalInterfaceCodeType::new(name, crate_name))
             }
-            Type::External { .. } => panic!("no support for external types yet"),
             Type::Wrapped { name, prim } => Box::new(wrapped::WrappedCodeType::new(
                 name,
                 self.create_code_type(prim.as_ref().clone()),
@@ -322,22 +484,22 @@ impl CodeOracle for SwiftCodeOracle {
 
     /// Get the idiomatic Swift rendering of a class name (for enums, records, errors, etc).
     fn class_name(&self, nm: &dyn fmt::Display) -> String {
-        nm.to_string().to_camel_case()
+        renames::rename_or(RenameKind::Class, nm, |nm| nm.to_camel_case())
     }
 
     /// Get the idiomatic Swift rendering of a function name.
     fn fn_name(&self, nm: &dyn fmt::Display) -> String {
-        nm.to_string().to_mixed_case()
+        renames::rename_or(RenameKind::Function, nm, |nm| nm.to_mixed_case())
     }
 
     /// Get the idiomatic Swift rendering of a variable name.
     fn var_name(&self, nm: &dyn fmt::Display) -> String {
-        nm.to_string().to_mixed_case()
+        renames::rename_or(RenameKind::Variable, nm, |nm| nm.to_mixed_case())
     }
 
     /// Get the idiomatic Swift rendering of an individual enum variant.
     fn enum_variant_name(&self, nm: &dyn fmt::Display) -> String {
-        nm.to_string().to_mixed_case()
+        renames::rename_or(RenameKind::EnumVariant, nm, |nm| nm.to_mixed_case())
     }
 
     /// Get the idiomatic Swift rendering of an exception name.
@@ -361,6 +523,8 @@ impl CodeOracle for SwiftCodeOracle {
             FFIType::RustBuffer => "RustBuffer".into(),
             FFIType::ForeignBytes => "ForeignBytes".into(),
             FFIType::ForeignCallback => "ForeignCallback  _Nonnull".to_string(),
+            FFIType::AsyncCompletion => "UniFfiAsyncCompletion _Nonnull".to_string(),
+            FFIType::ForeignMethodCallback(name) => format!("{} _Nonnull", name),
         }
     }
 }
@@ -65,15 +65,30 @@ impl CodeType for ObjectCodeType {
 #[template(syntax = "swift", escape = "none", path = "ObjectTemplate.swift")]
 pub struct SwiftObject {
     inner: Object,
+    // The module to import the `[ForeignExtension]` protocol from, if it's defined elsewhere.
+    extension_module: Option<String>,
+    errors_as_values: bool,
 }
 
 impl SwiftObject {
-    pub fn new(inner: Object, _ci: &ComponentInterface) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: Object,
+        _ci: &ComponentInterface,
+        extension_module: Option<String>,
+        errors_as_values: bool,
+    ) -> Self {
+        Self {
+            inner,
+            extension_module,
+            errors_as_values,
+        }
     }
     pub fn inner(&self) -> &Object {
         &self.inner
     }
+    pub fn errors_as_values(&self) -> bool {
+        self.errors_as_values
+    }
 }
 
 impl CodeDeclaration for SwiftObject {
@@ -82,7 +97,98 @@ impl CodeDeclaration for SwiftObject {
     }
 
     fn imports(&self, _oracle: &dyn CodeOracle) -> Option<Vec<String>> {
-        None
+        self.extension_module.clone().map(|module| vec![module])
+    }
+}
+
+// With `observable_models` on, each `[Observable]` object also gets an `ObservableObject`
+// publishing its latest snapshot, which it keeps up to date as an observer of the object.
+#[derive(Template)]
+#[template(
+    syntax = "swift",
+    escape = "none",
+    path = "ObservableModelTemplate.swift"
+)]
+pub struct SwiftObservableModel {
+    inner: Object,
+}
+
+impl SwiftObservableModel {
+    pub fn new(inner: Object, _ci: &ComponentInterface) -> Self {
+        Self { inner }
+    }
+    pub fn inner(&self) -> &Object {
+        &self.inner
+    }
+    pub fn model(&self) -> String {
+        self.inner.observable_model().unwrap()
+    }
+    pub fn snapshot(&self) -> &str {
+        self.inner.observable_snapshot().unwrap()
+    }
+    pub fn observer(&self) -> String {
+        self.inner.observer_interface().unwrap()
+    }
+}
+
+impl CodeDeclaration for SwiftObservableModel {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+}
+
+// Each object with `[Events]` also gets a Combine publisher of its events, on the platforms that
+// have Combine, which adds a listener to the object for each subscription.
+#[derive(Template)]
+#[template(
+    syntax = "swift",
+    escape = "none",
+    path = "EventPublisherTemplate.swift"
+)]
+pub struct SwiftEventPublisher {
+    inner: Object,
+}
+
+impl SwiftEventPublisher {
+    pub fn new(inner: Object, _ci: &ComponentInterface) -> Self {
+        Self { inner }
+    }
+    pub fn inner(&self) -> &Object {
+        &self.inner
+    }
+    pub fn event(&self) -> &str {
+        self.inner.events_type().unwrap()
+    }
+    pub fn listener(&self) -> String {
+        self.inner.listener_interface().unwrap()
+    }
+}
+
+impl CodeDeclaration for SwiftEventPublisher {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
+    }
+}
+
+// An `[OpaqueToken]` has no constructors or methods, so it only needs a small handle class.
+#[derive(Template)]
+#[template(syntax = "swift", escape = "none", path = "OpaqueTokenTemplate.swift")]
+pub struct SwiftOpaqueToken {
+    inner: Object,
+}
+
+impl SwiftOpaqueToken {
+    pub fn new(inner: Object, _ci: &ComponentInterface) -> Self {
+        Self { inner }
+    }
+    pub fn inner(&self) -> &Object {
+        &self.inner
+    }
+}
+
+impl CodeDeclaration for SwiftOpaqueToken {
+    fn definition_code(&self, _oracle: &dyn CodeOracle) -> Option<String> {
+        Some(self.render().unwrap())
     }
 }
 
@@ -90,12 +196,27 @@ impl CodeDeclaration for SwiftObject {
 #[template(syntax = "swift", escape = "none", path = "ObjectRuntime.swift")]
 pub struct SwiftObjectRuntime {
     is_needed: bool,
+    has_closeable_objects: bool,
+    has_cached_methods: bool,
+    has_no_copy_methods: bool,
 }
 
 impl SwiftObjectRuntime {
     pub fn new(ci: &ComponentInterface) -> Self {
         Self {
             is_needed: !ci.iter_object_definitions().is_empty(),
+            has_closeable_objects: ci
+                .iter_object_definitions()
+                .iter()
+                .any(|obj| obj.is_closeable()),
+            has_cached_methods: ci
+                .iter_object_definitions()
+                .iter()
+                .any(|obj| obj.methods().iter().any(|meth| meth.is_cached())),
+            has_no_copy_methods: ci
+                .iter_object_definitions()
+                .iter()
+                .any(|obj| obj.methods().iter().any(|meth| meth.is_no_copy())),
         }
     }
 }
@@ -66,12 +66,23 @@ impl CodeType for RecordCodeType {
 pub struct SwiftRecord {
     inner: Record,
     contains_object_references: bool,
+    is_inline: bool,
+    is_codable: bool,
 }
 
 impl SwiftRecord {
-    pub fn new(inner: Record, ci: &ComponentInterface) -> Self {
+    pub fn new(
+        inner: Record,
+        ci: &ComponentInterface,
+        inline_single_field_records: bool,
+        codable_records: bool,
+    ) -> Self {
+        let contains_object_references = ci.item_contains_object_references(&inner);
         Self {
-            contains_object_references: ci.item_contains_object_references(&inner),
+            contains_object_references,
+            is_inline: inline_single_field_records && inner.fields().len() == 1,
+            // Objects can't be encoded, so neither can records that contain them.
+            is_codable: codable_records && !contains_object_references,
             inner,
         }
     }
@@ -81,6 +92,12 @@ impl SwiftRecord {
     pub fn contains_object_references(&self) -> bool {
         self.contains_object_references
     }
+    pub fn is_inline(&self) -> bool {
+        self.is_inline
+    }
+    pub fn is_codable(&self) -> bool {
+        self.is_codable
+    }
 }
 
 impl CodeDeclaration for SwiftRecord {
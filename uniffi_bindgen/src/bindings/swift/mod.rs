@@ -31,6 +31,7 @@
 
 use anyhow::{anyhow, bail, Context, Result};
 use std::{
+    collections::BTreeSet,
     ffi::OsString,
     fs::File,
     io::Write,
@@ -42,6 +43,7 @@ pub mod gen_swift;
 pub use gen_swift::{BridgingHeader, Config, ModuleMap, SwiftWrapper};
 
 use super::super::interface::ComponentInterface;
+use crate::backend::{custom_types, post_process, renames};
 
 /// The Swift bindings generated from a [`ComponentInterface`].
 ///
@@ -76,19 +78,23 @@ pub fn write_bindings(
     let mut source_file = out_path.clone();
     source_file.push(format!("{}.swift", config.module_name()));
     let mut l = File::create(&source_file).context("Failed to create .swift file for bindings")?;
-    write!(l, "{}", library)?;
+    write!(l, "{}", post_process::post_process(&source_file, library)?)?;
 
     let mut header_file = out_path.clone();
     header_file.push(config.header_filename());
     let mut h = File::create(&header_file).context("Failed to create .h file for bindings")?;
-    write!(h, "{}", header)?;
+    write!(h, "{}", post_process::post_process(&header_file, header)?)?;
 
     if let Some(modulemap) = modulemap {
         let mut modulemap_file = out_path;
         modulemap_file.push(config.modulemap_filename());
         let mut m = File::create(&modulemap_file)
             .context("Failed to create .modulemap file for bindings")?;
-        write!(m, "{}", modulemap)?;
+        write!(
+            m,
+            "{}",
+            post_process::post_process(&modulemap_file, modulemap)?
+        )?;
     }
 
     if try_format_code {
@@ -114,9 +120,27 @@ pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<Bin
     let header = BridgingHeader::new(config, ci)
         .render()
         .map_err(|_| anyhow!("failed to render Swift bridging header"))?;
-    let library = SwiftWrapper::new(config.clone(), ci)
-        .render()
-        .map_err(|_| anyhow!("failed to render Swift library"))?;
+    custom_types::validate_custom_types(ci, config.custom_types(), true)?;
+    renames::validate_renames(ci, config.renames())?;
+    if config.observable_models() {
+        for obj in ci.iter_object_definitions() {
+            if let Some(model) = obj.observable_model() {
+                if ci.get_type(&model).is_some() {
+                    bail!(
+                        "[Observable] interface \"{}\" gets a \"{}\" class with `observable_models`, which conflicts with a type of the same name",
+                        obj.name(),
+                        model
+                    );
+                }
+            }
+        }
+    }
+    let library = custom_types::with_custom_types(config.custom_types(), || {
+        renames::with_renames(config.renames(), config.naming(), || {
+            SwiftWrapper::new(config.clone(), ci).render()
+        })
+    })
+    .map_err(|_| anyhow!("failed to render Swift library"))?;
     let modulemap = if config.generate_module_map() {
         Some(
             ModuleMap::new(config, ci)
@@ -164,8 +188,8 @@ pub fn compile_bindings(config: &Config, ci: &ComponentInterface, out_dir: &Path
     // symbols" when we try to import the module.
     // See https://bugs.swift.org/browse/SR-1191.
 
-    let status = Command::new("swiftc")
-        .arg("-module-name")
+    let mut cmd = Command::new("swiftc");
+    cmd.arg("-module-name")
         .arg(ci.namespace())
         .arg("-emit-library")
         .arg("-o")
@@ -174,11 +198,29 @@ pub fn compile_bindings(config: &Config, ci: &ComponentInterface, out_dir: &Path
         .arg("-emit-module-path")
         .arg(&out_path)
         .arg("-parse-as-library")
+        .arg("-I")
+        .arg(&out_path)
         .arg("-L")
         .arg(&out_path)
         .arg(format!("-l{}", config.cdylib_name()))
         .arg("-Xcc")
-        .arg(module_map_file_option)
+        .arg(module_map_file_option);
+    // The bindings of the components that we use external types from must have been compiled
+    // first, into the modules named after their crates.
+    let external_modules = ci
+        .iter_external_types()
+        .into_iter()
+        .chain(ci.iter_external_interfaces())
+        .map(|(_, crate_name)| crate_name.replace('-', "_"))
+        .collect::<BTreeSet<_>>();
+    for module in external_modules {
+        let mut option = OsString::from("-fmodule-map-file=");
+        option.push(out_path.join(format!("{}FFI.modulemap", module)));
+        let mut library = OsString::from("-l");
+        library.push(out_path.join(format!("lib{}.dylib", module)));
+        cmd.arg("-Xcc").arg(option).arg(library);
+    }
+    let status = cmd
         .arg(source_file)
         .spawn()
         .context("Failed to spawn `swiftc` when compiling bindings")?
@@ -0,0 +1,130 @@
+// How Rust completes a call to an `[Async]` function or method: either it returned, and the
+// buffer holds what it returned, or it failed with the error that it declares, or it panicked,
+// and the buffer holds the message, or it was cancelled before it finished.
+private let ASYNC_CALL_DONE: Int8 = 0
+private let ASYNC_CALL_ERROR: Int8 = 1
+private let ASYNC_CALL_PANIC: Int8 = 2
+private let ASYNC_CALL_CANCELLED: Int8 = 3
+
+// The calls to `[Async]` functions and methods that Rust hasn't completed yet, by the handle that
+// each one was made with.
+fileprivate class UniffiAsyncCalls {
+    private let lock = NSLock()
+    private var pending: [UInt64: (Int8, RustBuffer) -> Void] = [:]
+    private var nextHandle: UInt64 = 1
+
+    func allocate() -> UInt64 {
+        lock.lock()
+        defer { lock.unlock() }
+        let handle = nextHandle
+        nextHandle += 1
+        return handle
+    }
+
+    func register(_ handle: UInt64, _ resume: @escaping (Int8, RustBuffer) -> Void) {
+        lock.lock()
+        defer { lock.unlock() }
+        pending[handle] = resume
+    }
+
+    func complete(_ handle: UInt64, _ code: Int8, _ result: RustBuffer) {
+        lock.lock()
+        let resume = pending.removeValue(forKey: handle)
+        lock.unlock()
+        if let resume = resume {
+            resume(code, result)
+        } else {
+            result.deallocate()
+        }
+    }
+
+    func forget(_ handle: UInt64) {
+        lock.lock()
+        defer { lock.unlock() }
+        pending.removeValue(forKey: handle)
+    }
+}
+
+// Rust calls this on one of its own threads once a call has finished.
+private func uniffiCompleteAsyncCall(_ handle: UInt64, _ code: Int8, _ result: RustBuffer) {
+    uniffiAsyncCalls.complete(handle, code, result)
+}
+
+// The completion is registered with Rust the first time that an `[Async]` function or method is
+// called.
+private let uniffiAsyncCalls: UniffiAsyncCalls = {
+    try! rustCall { {{ ffi_init.name() }}(uniffiCompleteAsyncCall, $0) }
+    return UniffiAsyncCalls()
+}()
+
+// Rust still completes a call once it's cancelled, and ignores a handle that it doesn't know.
+private func uniffiCancelAsyncCall(_ handle: UInt64) {
+    try? rustCall { {{ ffi_cancel.as_ref().unwrap().name() }}(handle, $0) }
+}
+
+// Makes a call to an `[Async]` function or method with `start`, which passes Rust the handle that
+// it's given, and suspends until Rust has completed it. `lift` reads what the call returned, and
+// `liftError` the error that it declares.
+//
+// Cancelling the task cancels the Rust call too if it's `cancellable`. Calls to functions that
+// don't throw aren't, unless they're `[Cancellable]` and so still return something, since a
+// cancelled call throws `CancellationError`.
+@available(iOS 13, macOS 10.15, tvOS 13, watchOS 6, *)
+fileprivate func uniffiAsyncCall<T>(
+    start: (UInt64) throws -> Void,
+    cancellable: Bool,
+    lift: (Reader) throws -> T,
+    liftError: (RustBuffer) throws -> Error = { $0.deallocate(); return UniffiInternalError.unexpectedRustCallError }
+) async throws -> T {
+    let calls = uniffiAsyncCalls
+    let handle = calls.allocate()
+    let (code, result): (Int8, RustBuffer) = try await withTaskCancellationHandler {
+        try await withCheckedThrowingContinuation { continuation in
+            calls.register(handle) { code, result in
+                continuation.resume(returning: (code, result))
+            }
+            do {
+                try start(handle)
+            } catch {
+                // Rust didn't start the call, so it won't complete it either.
+                calls.forget(handle)
+                continuation.resume(throwing: error)
+                return
+            }
+            // The task may have been cancelled before Rust knew about the call.
+            if cancellable && Task.isCancelled {
+                uniffiCancelAsyncCall(handle)
+            }
+        }
+    } onCancel: {
+        if cancellable {
+            uniffiCancelAsyncCall(handle)
+        }
+    }
+    switch code {
+        case ASYNC_CALL_DONE:
+            let reader = Reader(data: Data(rustBuffer: result))
+            result.deallocate()
+            let value = try lift(reader)
+            if reader.hasRemaining() {
+                throw UniffiInternalError.incompleteData
+            }
+            return value
+
+        case ASYNC_CALL_ERROR:
+            throw try liftError(result)
+
+        case ASYNC_CALL_PANIC:
+            let reader = Reader(data: Data(rustBuffer: result))
+            result.deallocate()
+            throw UniffiInternalError.rustPanic(try {{ "reader"|read_var(Type::String) }})
+
+        case ASYNC_CALL_CANCELLED:
+            result.deallocate()
+            throw CancellationError()
+
+        default:
+            result.deallocate()
+            throw UniffiInternalError.unexpectedRustCallStatusCode
+    }
+}
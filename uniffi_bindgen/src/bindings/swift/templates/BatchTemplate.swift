@@ -0,0 +1,74 @@
+{% import "macros.swift" as swift %}
+{%- match ci.ffi_batch() %}
+{%- when Some with (ffi_batch) %}
+/// The result of a call that was queued up in a `batch`, which `get()` returns once the batch
+/// has run, or throws the error that the call failed with.
+public final class BatchResult<T> {
+    fileprivate var result: Result<T, Error>?
+
+    fileprivate init() {}
+
+    public func get() throws -> T {
+        guard let result = result else {
+            fatalError("The batch that this call was queued up in hasn't run yet")
+        }
+        return try result.get()
+    }
+}
+
+/// The calls queued up in a `batch`. It has a method for each `[Batchable]` function, which
+/// takes the function's arguments, but returns a `BatchResult` rather than calling it.
+public final class Batch {
+    fileprivate let calls = Writer()
+    fileprivate var results: [(Reader) throws -> Void] = []
+
+    fileprivate init() {}
+    {% for func in ci.iter_batchable_function_definitions() %}
+    public func {{ func.name()|fn_name }}({% call swift::arg_list_decl(func) %}) -> BatchResult<{% call swift::batch_result_type(func) %}> {
+        let result = BatchResult<{% call swift::batch_result_type(func) %}>()
+        calls.writeInt(Int32({{ loop.index }}))
+        {%- for arg in func.arguments() %}
+        {{ arg.name()|write_var("calls", arg) }}
+        {%- endfor %}
+        results.append { buf in
+            let status: Int8 = try buf.readInt()
+            switch status {
+            {%- match func.return_type() %}
+            {%- when Some with (return_type) %}
+            case 0: result.result = .success(try {{ "buf"|read_var(return_type) }})
+            {%- when None %}
+            case 0: result.result = .success(())
+            {%- endmatch %}
+            {%- match func.throws_type() %}
+            {%- when Some with (e) %}
+            case 1: result.result = .failure(try {{ "buf"|read_var(e) }})
+            {%- when None %}
+            {%- endmatch %}
+            default: throw UniffiInternalError.unexpectedEnumCase
+            }
+        }
+        return result
+    }
+    {% endfor %}
+}
+
+/// Queue up calls to `[Batchable]` functions in `block`, then make all of them with a single
+/// call into the Rust library, which is much cheaper than calling each function on its own when
+/// there are a lot of small calls to make. Their results are available once this returns.
+public func batch(_ block: (Batch) throws -> Void) rethrows {
+    let batch = Batch()
+    try block(batch)
+    let results = try! rustCall {
+        {{ ffi_batch.name() }}(RustBuffer(bytes: batch.calls.bytes), $0)
+    }
+    let reader = Reader(data: Data(rustBuffer: results))
+    results.deallocate()
+    for read in batch.results {
+        try! read(reader)
+    }
+    if reader.hasRemaining() {
+        fatalError("junk remaining in the batch results, something is very wrong!!")
+    }
+}
+{%- when None %}
+{%- endmatch %}
@@ -0,0 +1,59 @@
+/// A 128-bit signed integer, for values that don't fit in an `Int64`. It's stored as its
+/// most and least significant 64 bits, in two's complement.
+public struct BigInt: Hashable, CustomStringConvertible, ExpressibleByIntegerLiteral {
+    public var high: Int64
+    public var low: UInt64
+
+    public init(high: Int64, low: UInt64) {
+        self.high = high
+        self.low = low
+    }
+
+    public init(_ value: Int64) {
+        self.init(high: value < 0 ? -1 : 0, low: UInt64(bitPattern: value))
+    }
+
+    public init(integerLiteral value: Int64) {
+        self.init(value)
+    }
+
+    public var description: String {
+        let negative = high < 0
+        var high = UInt64(bitPattern: self.high)
+        var low = self.low
+        if negative {
+            // Negate the two's complement bits, to get the magnitude.
+            let (sum, overflow) = (~low).addingReportingOverflow(1)
+            high = ~high &+ (overflow ? 1 : 0)
+            low = sum
+        }
+        // Divide the magnitude into chunks of 19 decimal digits, least significant first.
+        let divisor: UInt64 = 10_000_000_000_000_000_000
+        var chunks: [UInt64] = []
+        repeat {
+            let (quotient, remainder) = divisor.dividingFullWidth((high: high % divisor, low: low))
+            chunks.append(remainder)
+            high /= divisor
+            low = quotient
+        } while high != 0 || low != 0
+        var digits = String(chunks.removeLast())
+        for chunk in chunks.reversed() {
+            let chunkDigits = String(chunk)
+            digits += String(repeating: "0", count: 19 - chunkDigits.count) + chunkDigits
+        }
+        return negative ? "-" + digits : digits
+    }
+}
+
+extension BigInt: ViaFfiUsingByteBuffer, ViaFfi {
+    fileprivate static func read(from buf: Reader) throws -> Self {
+        let high: Int64 = try buf.readInt()
+        let low: UInt64 = try buf.readInt()
+        return BigInt(high: high, low: low)
+    }
+
+    fileprivate func write(into buf: Writer) {
+        buf.writeInt(high)
+        buf.writeInt(low)
+    }
+}
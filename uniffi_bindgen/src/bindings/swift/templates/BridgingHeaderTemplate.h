@@ -46,6 +46,16 @@ typedef struct RustCallStatus {
 // ⚠️ increment the version suffix in all instances of UNIFFI_SHARED_HEADER_V3 in this file.           ⚠️
 #endif // def UNIFFI_SHARED_H
 
+{% if ci.ffi_async_init().is_some() -%}
+typedef void (*UniFfiAsyncCompletion)(uint64_t, int8_t, RustBuffer);
+{% endif -%}
+{% for cbi in ci.iter_callback_interface_definitions() -%}
+{%- for callback in cbi.ffi_direct_callbacks() -%}
+typedef {% match callback.return_type() %}{% when Some with (type_) %}{{ type_|ffi_type_name }}{% when None %}void{% endmatch %} (*{{ callback.name() }})(
+    {%- for arg in callback.arguments() %}{{ arg.type_()|ffi_type_name }}{% if !loop.last %}, {% endif %}{% endfor -%}
+);
+{% endfor -%}
+{%- endfor %}
 {% for func in ci.iter_ffi_function_definitions() -%}
     {%- match func.return_type() -%}{%- when Some with (type_) %}{{ type_|ffi_type_name }}{% when None %}void{% endmatch %} {{ func.name() }}(
       {% call swift::arg_list_ffi_decl(func) %}
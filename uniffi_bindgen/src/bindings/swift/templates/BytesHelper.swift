@@ -0,0 +1,13 @@
+// Bytes are passed as their length, followed by the bytes themselves.
+extension Data: ViaFfiUsingByteBuffer, ViaFfi {
+    fileprivate static func read(from buf: Reader) throws -> Self {
+        let len: Int32 = try buf.readInt()
+        return Data(try buf.readBytes(count: Int(len)))
+    }
+
+    fileprivate func write(into buf: Writer) {
+        let len = Int32(self.count)
+        buf.writeInt(len)
+        buf.writeBytes(self)
+    }
+}
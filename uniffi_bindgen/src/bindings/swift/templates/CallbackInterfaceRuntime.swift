@@ -59,6 +59,40 @@ fileprivate class ConcurrentHandleMap<T> {
 // to free the callback once it's dropped by Rust.
 private let IDX_CALLBACK_FREE: Int32 = 0
 
+// Magic number for the Rust proxy of a `[Versioned]` callback interface to ask which methods
+// the object has. It's `u32::MAX` in Rust, which is `-1` as an `Int32`.
+private let IDX_CALLBACK_CAPABILITIES: Int32 = -1
+
+// The first byte of what a method that throws returns to Rust, which says whether the rest is
+// the value that it returned, or the error that it threw.
+private let CALLBACK_SUCCESS: Int8 = 0
+private let CALLBACK_ERROR: Int8 = 1
+{%- match ffi_callback_future_complete %}
+{%- when Some with (ffi_complete) %}
+
+// What the future of an `[Async]` method is completed with: either the method finished, and the
+// buffer holds what it returned, or it failed with an error that it doesn't declare, and the
+// buffer holds its message.
+private let CALLBACK_FUTURE_DONE: Int8 = 0
+private let CALLBACK_FUTURE_FAILED: Int8 = 1
+
+// Completes the Rust future of an `[Async]` method with what the method returned.
+fileprivate func uniffiCompleteCallbackFuture(_ handle: UInt64, _ writer: Writer) {
+    try! rustCall { {{ ffi_complete.name() }}(handle, CALLBACK_FUTURE_DONE, RustBuffer(bytes: writer.bytes), $0) }
+}
+
+// Completes the Rust future of an `[Async]` method with an error that it doesn't declare, which
+// Rust panics with.
+fileprivate func uniffiFailCallbackFuture(_ handle: UInt64, _ error: Error) {
+    let message = Array(String(describing: error).utf8)
+    let writer = Writer()
+    writer.writeInt(Int32(message.count))
+    writer.writeBytes(message)
+    try! rustCall { {{ ffi_complete.name() }}(handle, CALLBACK_FUTURE_FAILED, RustBuffer(bytes: writer.bytes), $0) }
+}
+{%- when None %}
+{%- endmatch %}
+
 fileprivate class FfiConverterCallbackInterface<CallbackInterface> {
     fileprivate let handleMap = ConcurrentHandleMap<CallbackInterface>()
 
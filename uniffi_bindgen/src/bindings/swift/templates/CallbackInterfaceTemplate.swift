@@ -9,13 +9,38 @@
 
 public protocol {{ type_name }} : AnyObject {
     {% for meth in cbi.methods() -%}
-    func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% call swift::throws(meth) -%}
+    {%- if meth.is_async() %}
+    // This method is `[Async]`, so Rust gets a future for what it returns, and doesn't wait.
+    @available(iOS 13, macOS 10.15, tvOS 13, watchOS 6, *)
+    {% endif -%}
+    func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% if meth.is_async() %}async {% endif %}{% call swift::throws(meth) -%}
     {%- match meth.return_type() -%}
     {%- when Some with (return_type) %} -> {{ return_type|type_name -}}
     {%- else -%}
     {%- endmatch %}
     {% endfor %}
 }
+{%- if cbi.has_optional_methods() %}
+
+// Default implementations of the `[Optional]` methods, so that conforming types can leave them out.
+public extension {{ type_name }} {
+    {%- for meth in cbi.methods() %}
+    {%- if meth.is_optional() %}
+    func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% call swift::throws(meth) -%}
+    {%- match meth.return_type() -%}
+    {%- when Some with (return_type) %} -> {{ return_type|type_name }} {
+        {%- match meth.default_return_value() %}
+        {%- when Some with (literal) %}
+        return {{ literal|literal_swift(return_type) }}
+        {%- when None %}
+        {%- endmatch %}
+    }
+    {%- else %} {}
+    {%- endmatch %}
+    {%- endif %}
+    {%- endfor %}
+}
+{%- endif %}
 
 // The ForeignCallback that is passed to Rust.
 fileprivate let {{ foreign_callback }} : ForeignCallback =
@@ -25,45 +50,72 @@ fileprivate let {{ foreign_callback }} : ForeignCallback =
 
     func {{ method_name }}(_ swiftCallbackInterface: {{ type_name }}, _ args: RustBuffer) throws -> RustBuffer {
         defer { args.deallocate() }
-        {#- Unpacking args from the RustBuffer #}
-            {%- if meth.arguments().len() != 0 -%}
-            {#- Calling the concrete callback object #}
-
-            let reader = Reader(data: Data(rustBuffer: args))
-            {% if meth.return_type().is_some() %}let result = {% endif -%}
-            {% if meth.throws().is_some() %}try {% endif -%}
-            swiftCallbackInterface.{{ meth.name()|fn_name }}(
-                    {% for arg in meth.arguments() -%}
-                    {{ arg.name() }}: try {{ "reader"|read_var(arg) }}
-                    {%- if !loop.last %}, {% endif %}
-                    {% endfor -%}
-                )
-            {% else %}
-            {% if meth.return_type().is_some() %}let result = {% endif -%}
-            {% if meth.throws().is_some() %}try {% endif -%}
-            swiftCallbackInterface.{{ meth.name()|fn_name }}()
-            {% endif -%}
-
+        {%- if meth.is_async() %}
+        {%- call swift::start_async_callback_method(meth) %}
+        {%- else %}
+        {%- match meth.throws_type() %}
+        {%- when Some with (e) %}
+        do {
+            {%- call swift::invoke_callback_method(meth) %}
+            {#- Methods that throw say whether they returned or threw first #}
+            let writer = Writer()
+            writer.writeInt(CALLBACK_SUCCESS)
+            {%- match meth.return_type() %}
+            {%- when Some with (return_type) %}
+            {{ "result"|write_var("writer", return_type) }}
+            {%- when None %}
+            {%- endmatch %}
+            return RustBuffer(bytes: writer.bytes)
+        } catch let error as {{ e|type_name }} {
+            // The error that the method declares is sent back to Rust, to be returned there.
+            let writer = Writer()
+            writer.writeInt(CALLBACK_ERROR)
+            error.write(into: writer)
+            return RustBuffer(bytes: writer.bytes)
+        }
+        {%- when None %}
+        {%- call swift::invoke_callback_method(meth) %}
         {#- Packing up the return value into a RustBuffer #}
-                {%- match meth.return_type() -%}
-                {%- when Some with (return_type) -%}
-                let writer = Writer()
-                {{ "result"|write_var("writer", return_type) }}
-                return RustBuffer(bytes: writer.bytes)
-                {%- else -%}
-                return RustBuffer()
-                {% endmatch -%}
-                // TODO catch errors and report them back to Rust.
-                // https://github.com/mozilla/uniffi-rs/issues/351
-
+        {%- match meth.return_type() %}
+        {%- when Some with (return_type) %}
+        let writer = Writer()
+        {{ "result"|write_var("writer", return_type) }}
+        return RustBuffer(bytes: writer.bytes)
+        {%- when None %}
+        return RustBuffer()
+        {%- endmatch %}
+        {%- endmatch %}
+        {%- endif %}
+        // TODO catch other errors and report them back to Rust.
+        // https://github.com/mozilla/uniffi-rs/issues/351
     }
     {% endfor %}
+    {%- if cbi.is_versioned() %}
+
+    // Tells the Rust proxy which version of `{{ type_name }}` this is, and the names of its
+    // methods in the order of their indexes, so that Rust calls them by name.
+    func invokeCapabilities() -> RustBuffer {
+        let methods: [String] = [
+            {%- for meth in cbi.methods() %}"{{ meth.name() }}"{% if !loop.last %}, {% endif %}{% endfor -%}
+        ]
+        let writer = Writer()
+        writer.writeInt(UInt64({{ cbi.version() }}))
+        writer.writeInt(Int32(methods.count))
+        for name in methods {
+            {{ "name"|write_var("writer", Type::String) }}
+        }
+        return RustBuffer(bytes: writer.bytes)
+    }
+    {%- endif %}
 
         let cb = try! {{ ffi_converter }}.lift(handle)
         switch method {
             case IDX_CALLBACK_FREE:
                 {{ ffi_converter }}.drop(handle: handle)
                 return RustBuffer()
+            {%- if cbi.is_versioned() %}
+            case IDX_CALLBACK_CAPABILITIES: return invokeCapabilities()
+            {%- endif %}
             {% for meth in cbi.methods() -%}
             {% let method_name = format!("invoke_{}", meth.name())|fn_name -%}
             case {{ loop.index }}: return try! {{ method_name }}(cb, args)
@@ -75,10 +127,43 @@ fileprivate let {{ foreign_callback }} : ForeignCallback =
         }
     }
 
+{%- for meth in cbi.direct_methods() %}
+{%- match cbi.ffi_direct_callback(meth) %}
+{%- when Some with (callback) %}
+
+// The function that Rust calls for `{{ meth.name()|fn_name }}`, passing the arguments as they are
+// rather than in a RustBuffer, because the callback interface is `[Direct]`.
+fileprivate let {{ callback.name() }}Impl : {{ callback.name() }} =
+    { ({% for arg in callback.arguments() %}{{ arg.name()|var_name }}{% if !loop.last %}, {% endif %}{% endfor %}) in
+        let cb = try! {{ ffi_converter }}.lift(handle)
+        {% if meth.return_type().is_some() %}let result = {% endif -%}
+        cb.{{ meth.name()|fn_name }}(
+            {%- for arg in meth.arguments() %}{{ arg.name()|var_name }}: try! {{ arg.name()|var_name|lift_var(arg) }}{% if !loop.last %}, {% endif %}{% endfor -%}
+        )
+        {%- match meth.return_type() %}
+        {%- when Some with (return_type) %}
+        return {{ "result"|lower_var(return_type) }}
+        {%- when None %}
+        {%- endmatch %}
+    }
+{%- when None %}
+{%- endmatch %}
+{%- endfor %}
+
 // The ffiConverter which transforms the Callbacks in to Handles to pass to Rust.
 private let {{ ffi_converter }}: FfiConverterCallbackInterface<{{ type_name }}> = {
     try! rustCall { (err: UnsafeMutablePointer<RustCallStatus>) in
             {{ cbi.ffi_init_callback().name() }}({{ foreign_callback }}, err)
     }
+    {%- match cbi.ffi_init_direct_callbacks() %}
+    {%- when Some with (ffi_init_direct) %}
+    try! rustCall { (err: UnsafeMutablePointer<RustCallStatus>) in
+            {{ ffi_init_direct.name() }}(
+                {%- for callback in cbi.ffi_direct_callbacks() %}{{ callback.name() }}Impl, {% endfor -%}
+                err
+            )
+    }
+    {%- when None %}
+    {%- endmatch %}
     return FfiConverterCallbackInterface<{{ type_name }}>()
 }()
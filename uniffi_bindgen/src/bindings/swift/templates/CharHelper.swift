@@ -0,0 +1,23 @@
+// Chars are passed as their Unicode scalar value.
+extension Unicode.Scalar: ViaFfi {
+    fileprivate typealias FfiType = UInt32
+
+    fileprivate static func read(from buf: Reader) throws -> Self {
+        return try self.lift(buf.readInt())
+    }
+
+    fileprivate func write(into buf: Writer) {
+        buf.writeInt(self.lower())
+    }
+
+    fileprivate static func lift(_ v: FfiType) throws -> Self {
+        guard let scalar = Unicode.Scalar(v) else {
+            throw UniffiInternalError.unexpectedCharValue
+        }
+        return scalar
+    }
+
+    fileprivate func lower() -> FfiType {
+        return self.value
+    }
+}
@@ -0,0 +1,35 @@
+/// The version of `uniffi-bindgen` that generated these bindings.
+public let uniffiBindgenVersion = "{{ self.bindgen_version() }}"
+
+/// The checksum of the component interface that these bindings were generated from.
+///
+/// The library is checked against it before it's first called.
+public let uniffiComponentChecksum = "{{ "{:016x}"|format(ci.checksum()) }}"
+
+/// Metadata about the build of the Rust library that these bindings are using.
+///
+/// `checksum` identifies the component interface that the library was built from, and
+/// `bindingsChecksum` the one that these bindings were generated from. If they differ, the
+/// bindings and the library don't match.
+public struct UniffiComponentInfo: Equatable {
+    public let componentName: String
+    public let uniffiVersion: String
+    public let checksum: String
+    public let buildProfile: String
+    public let bindingsChecksum: String
+}
+
+/// Fetch metadata about the Rust library, for use by support tooling.
+public func uniffiComponentInfo() -> UniffiComponentInfo {
+    let info = try! String.lift(try! rustCall() {
+        {{ ci.ffi_component_info().name() }}($0)
+    })
+    let fields = info.components(separatedBy: "\n")
+    return UniffiComponentInfo(
+        componentName: fields[0],
+        uniffiVersion: fields[1],
+        checksum: fields[2],
+        buildProfile: fields[3],
+        bindingsChecksum: uniffiComponentChecksum
+    )
+}
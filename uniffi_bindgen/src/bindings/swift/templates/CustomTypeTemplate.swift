@@ -0,0 +1,31 @@
+// Conversions for `{{ type_name }}`, which is mapped onto `{{ builtin_type_name }}` by uniffi.toml.
+fileprivate enum FfiConverter{{ canonical_name }}: FfiConverter {
+    typealias SwiftType = {{ type_name }}
+    typealias FfiType = {{ ffi_type_name }}
+
+    static func intoCustom(_ builtinValue: {{ builtin_type_name }}) -> SwiftType {
+        return {{ into_custom }}
+    }
+
+    static func fromCustom(_ value: SwiftType) -> {{ builtin_type_name }} {
+        return {{ from_custom }}
+    }
+
+    static func lift(_ v: FfiType) throws -> SwiftType {
+        return intoCustom(try {{ lift_builtin }})
+    }
+
+    static func read(from buf: Reader) throws -> SwiftType {
+        return intoCustom(try {{ read_builtin }})
+    }
+
+    static func lower(_ value: SwiftType) -> FfiType {
+        let builtinValue = fromCustom(value)
+        return {{ lower_builtin }}
+    }
+
+    static func write(_ value: SwiftType, into buf: Writer) {
+        let builtinValue = fromCustom(value)
+        {{ write_builtin }}
+    }
+}
@@ -45,3 +45,7 @@ extension {{ e|type_name }}: ViaFfiUsingByteBuffer, ViaFfi {
 {% if ! self.contains_object_references() %}
 extension {{ e|type_name }}: Equatable, Hashable {}
 {% endif %}
+
+{%- if self.is_codable() %}
+extension {{ e|type_name }}: Codable {}
+{% endif %}
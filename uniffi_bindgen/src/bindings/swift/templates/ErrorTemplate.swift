@@ -5,12 +5,18 @@ public enum {{ e|type_name }} {
     {% if e.is_flat() %}
     {% for variant in e.variants() %}
     // Simple error enums only carry a message
-    case {{ variant.name()|class_name }}(message: String)
+    case {{ variant.name()|class_name }}(message: String{%- match e.source_chain_type() %}{%- when Some with (chain_type) %}, sourceChain: {{ chain_type|type_name }}{%- when None %}{%- endmatch %})
     {% endfor %}
 
     {%- else %}
     {% for variant in e.variants() %}
+    {%- match e.source_chain_type() %}
+    {%- when Some with (chain_type) %}
+    // The messages of the Rust error's chain of sources follow any fields
+    case {{ variant.name()|class_name }}({% if variant.fields().len() > 0 %}{% call swift::field_list_decl(variant) %}, {% endif %}sourceChain: {{ chain_type|type_name }})
+    {%- when None %}
     case {{ variant.name()|class_name }}{% if variant.fields().len() > 0 %}({% call swift::field_list_decl(variant) %}){% endif -%}
+    {%- endmatch %}
     {% endfor %}
 
     {%- endif %}
@@ -25,18 +31,29 @@ extension {{ e|type_name }}: ViaFfiUsingByteBuffer, ViaFfi {
 
         {% for variant in e.variants() %}
         case {{ loop.index }}: return .{{ variant.name()|class_name }}(
-            message: try {{ "buf"|read_var(Type::String) }}
+            message: try {{ "buf"|read_var(Type::String) }}{%- match e.source_chain_type() %}{%- when Some with (chain_type) %},
+            sourceChain: try {{ "buf"|read_var(chain_type) }}{%- when None %}{%- endmatch %}
         )
         {% endfor %}
 
        {% else %}
 
         {% for variant in e.variants() %}
+        {%- match e.source_chain_type() %}
+        {%- when Some with (chain_type) %}
+        case {{ loop.index }}: return .{{ variant.name()|class_name }}(
+            {% for field in variant.fields() -%}
+            {{ field.name()|var_name }}: try {{ "buf"|read_var(field) }},
+            {% endfor -%}
+            sourceChain: try {{ "buf"|read_var(chain_type) }}
+        )
+        {%- when None %}
         case {{ loop.index }}: return .{{ variant.name()|class_name }}{% if variant.has_fields() -%}(
             {% for field in variant.fields() -%}
             {{ field.name()|var_name }}: try {{ "buf"|read_var(field) }}{% if loop.last %}{% else %},{% endif %}
             {% endfor -%}
         ){% endif -%}
+        {%- endmatch %}
         {% endfor %}
 
          {% endif -%}
@@ -50,14 +67,28 @@ extension {{ e|type_name }}: ViaFfiUsingByteBuffer, ViaFfi {
         {% if e.is_flat() %}
 
         {% for variant in e.variants() %}
-        case let .{{ variant.name()|class_name }}(message):
+        case let .{{ variant.name()|class_name }}(message{% if e.has_source_chain() %}, sourceChain{% endif %}):
             buf.writeInt(Int32({{ loop.index }}))
             {{ "message"|write_var("buf", Type::String) }}
+            {%- match e.source_chain_type() %}
+            {%- when Some with (chain_type) %}
+            {{ "sourceChain"|write_var("buf", chain_type) }}
+            {%- when None %}
+            {%- endmatch %}
         {%- endfor %}
 
         {% else %}
 
         {% for variant in e.variants() %}
+        {%- match e.source_chain_type() %}
+        {%- when Some with (chain_type) %}
+        case let .{{ variant.name()|class_name }}({% for field in variant.fields() %}{{ field.name()|var_name }}, {% endfor %}sourceChain):
+            buf.writeInt(Int32({{ loop.index }}))
+            {% for field in variant.fields() -%}
+            {{ field.name()|write_var("buf", field) }}
+            {% endfor -%}
+            {{ "sourceChain"|write_var("buf", chain_type) }}
+        {%- when None %}
         {% if variant.has_fields() %}
         case let .{{ variant.name()|class_name }}({% for field in variant.fields() %}{{ field.name()|var_name }}{%- if loop.last -%}{%- else -%},{%- endif -%}{% endfor %}):
             buf.writeInt(Int32({{ loop.index }}))
@@ -68,6 +99,7 @@ extension {{ e|type_name }}: ViaFfiUsingByteBuffer, ViaFfi {
         case .{{ variant.name()|class_name }}:
             buf.writeInt(Int32({{ loop.index }}))
         {% endif %}
+        {%- endmatch %}
         {%- endfor %}
 
         {%- endif %}
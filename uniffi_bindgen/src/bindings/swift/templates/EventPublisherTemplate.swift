@@ -0,0 +1,38 @@
+{%- let obj = self.inner() %}
+{%- let event = self.event()|class_name %}
+{%- let publisher = format!("Uniffi{}Publisher", obj.name())|class_name %}
+#if canImport(Combine)
+import Combine
+
+@available(iOS 13, macOS 10.15, tvOS 13, watchOS 6, *)
+extension {{ obj|type_name }} {
+    /// Publishes the `{{ event }}`s that the object emits, on whichever thread emits them.
+    ///
+    /// Each subscription adds a listener to the object, and removes it again when it's cancelled.
+    /// The subscription keeps the object alive until then.
+    public var eventPublisher: AnyPublisher<{{ event }}, Never> {
+        Deferred { () -> AnyPublisher<{{ event }}, Never> in
+            let subject = PassthroughSubject<{{ event }}, Never>()
+            let listenerId = self.{{ "add_listener"|fn_name }}({{ "listener"|var_name }}: {{ publisher }}(subject))
+            return subject
+                .handleEvents(receiveCancel: { self.{{ "remove_listener"|fn_name }}({{ "id"|var_name }}: listenerId) })
+                .eraseToAnyPublisher()
+        }
+        .eraseToAnyPublisher()
+    }
+}
+
+// The listener that each subscription to `eventPublisher` adds, which sends the events on.
+@available(iOS 13, macOS 10.15, tvOS 13, watchOS 6, *)
+fileprivate final class {{ publisher }}: {{ self.listener()|class_name }} {
+    private let subject: PassthroughSubject<{{ event }}, Never>
+
+    init(_ subject: PassthroughSubject<{{ event }}, Never>) {
+        self.subject = subject
+    }
+
+    func {{ "on_event"|fn_name }}({{ "event"|var_name }}: {{ event }}) {
+        subject.send({{ "event"|var_name }})
+    }
+}
+#endif
@@ -0,0 +1,25 @@
+{%- let type_name = format!("{}Export", self.namespace)|class_name %}
+// Every function, constructor and method exported by this component, keyed by its qualified
+// name (e.g. "Counter.get" for a method), so that routing layers can map names onto calls
+// without keeping their own list. The `signatureHash` of a case changes whenever its signature does.
+public enum {{ type_name }}: String, CaseIterable {
+    {%- for export in self.exports %}
+    case {{ export.qualified_name()|enum_variant_swift }} = "{{ export.qualified_name() }}"
+    {%- endfor %}
+
+    public var kind: String {
+        switch self {
+        {%- for export in self.exports %}
+        case .{{ export.qualified_name()|enum_variant_swift }}: return "{{ export.kind() }}"
+        {%- endfor %}
+        }
+    }
+
+    public var signatureHash: String {
+        switch self {
+        {%- for export in self.exports %}
+        case .{{ export.qualified_name()|enum_variant_swift }}: return "{{ export.signature_hash() }}"
+        {%- endfor %}
+        }
+    }
+}
@@ -0,0 +1,37 @@
+{%- let name = self.name() %}
+{%- let external_types = self.external_types_enum_name() %}
+{%- let canonical_type_name = self.canonical_type_name() %}
+
+// `{{ name }}` objects belong to the `{{ self.module_name() }}` module. They're passed to and from
+// this library as pointers to the same Rust object, rather than being copied.
+fileprivate enum FfiConverter{{ canonical_type_name }}: FfiConverter {
+    typealias SwiftType = {{ name }}
+    typealias FfiType = UnsafeMutableRawPointer
+
+    static func lift(_ pointer: UnsafeMutableRawPointer) throws -> SwiftType {
+        try {{ self.check_component_fn_name() }}()
+        return try {{ external_types }}.lift{{ name }}(pointer)
+    }
+
+    static func lower(_ value: SwiftType) -> UnsafeMutableRawPointer {
+        try! {{ self.check_component_fn_name() }}()
+        return {{ external_types }}.lower{{ name }}(value)
+    }
+
+    static func read(from buf: Reader) throws -> SwiftType {
+        let v: UInt64 = try buf.readInt()
+        // The Rust code won't compile if a pointer won't fit in a UInt64.
+        // We have to go via `UInt` because that's the thing that's the size of a pointer.
+        let ptr = UnsafeMutableRawPointer(bitPattern: UInt(truncatingIfNeeded: v))
+        if (ptr == nil) {
+            throw UniffiInternalError.unexpectedNullPointer
+        }
+        return try lift(ptr!)
+    }
+
+    static func write(_ value: SwiftType, into buf: Writer) {
+        // This fiddling is because `Int` is the thing that's the same size as a pointer.
+        // The Rust code won't compile if a pointer won't fit in a `UInt64`.
+        buf.writeInt(UInt64(bitPattern: Int64(Int(bitPattern: lower(value)))))
+    }
+}
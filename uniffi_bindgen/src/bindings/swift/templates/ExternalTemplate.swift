@@ -0,0 +1,16 @@
+{%- let name = self.name() %}
+{%- let external_types = self.external_types_enum_name() %}
+{%- let canonical_type_name = self.canonical_type_name() %}
+
+// `{{ name }}` belongs to the `{{ self.module_name() }}` module, whose bindings read and write it for us.
+fileprivate enum FfiConverter{{ canonical_type_name }}: FfiConverterUsingByteBuffer {
+    typealias SwiftType = {{ name }}
+
+    static func write(_ value: SwiftType, into buf: Writer) {
+        {{ external_types }}.write{{ name }}(value, into: &buf.bytes)
+    }
+
+    static func read(from buf: Reader) throws -> SwiftType {
+        return try {{ external_types }}.read{{ name }}(from: buf.data, at: &buf.offset)
+    }
+}
@@ -0,0 +1,66 @@
+{%- if !self.exported_types.is_empty() %}
+/// Reads, writes, lifts and lowers the types of this component for the bindings of other components
+/// that declare them as `[External]` or `[ExternalInterface]`, which can't use their `fileprivate`
+/// helpers. It isn't meant to be used by anything else.
+public enum {{ self.external_types_enum_name }} {
+    /// The checksum of the component interface that these bindings were generated from.
+    public static let componentChecksum = uniffiComponentChecksum
+    {%- for exported in self.exported_types %}
+    {%- let type_name = exported.type_|type_name %}
+    {%- if exported.is_object %}
+
+    public static func lift{{ exported.name }}(_ pointer: UnsafeMutableRawPointer) throws -> {{ type_name }} {
+        return try {{ "pointer"|lift_var(exported.type_) }}
+    }
+
+    public static func lower{{ exported.name }}(_ value: {{ type_name }}) -> UnsafeMutableRawPointer {
+        return {{ "value"|lower_var(exported.type_) }}
+    }
+    {%- else %}
+
+    public static func read{{ exported.name }}(from data: Data, at offset: inout Int) throws -> {{ type_name }} {
+        let reader = Reader(data: data)
+        reader.offset = offset
+        let value = try {{ "reader"|read_var(exported.type_) }}
+        offset = reader.offset
+        return value
+    }
+
+    public static func write{{ exported.name }}(_ value: {{ type_name }}, into bytes: inout [UInt8]) {
+        let writer = Writer()
+        {{ "value"|write_var("writer", exported.type_) }}
+        bytes.append(contentsOf: writer.bytes)
+    }
+    {%- endif %}
+    {%- endfor %}
+}
+{%- for exported in self.exported_types %}
+{%- let type_name = exported.type_|type_name %}
+{%- if type_name != exported.name %}
+
+// The other components' bindings refer to the type by the name it has in the UDL.
+public typealias {{ exported.name }} = {{ type_name }}
+{%- endif %}
+{%- endfor %}
+{%- endif %}
+{%- for component in self.external_components %}
+
+// The objects of the `{{ component.module_name }}` module are passed to this library as pointers
+// to the Rust structs behind them, so both libraries have to be built from the same interface.
+// This is worked out the first time that one of them is passed.
+private let {{ component.check_component_fn_name }}Mismatch: String? = {
+    let checksum = String(format: "%016llx", try! rustCall { {{ component.checksum_fn_name }}($0) })
+    let expected = {{ component.external_types_enum_name }}.componentChecksum
+    if checksum == expected {
+        return nil
+    }
+    return "The {{ self.namespace }} library was built against the {{ component.module_name }} component with checksum \(checksum), " +
+        "but the {{ component.module_name }} bindings expect \(expected). Build both libraries from the same source."
+}()
+
+fileprivate func {{ component.check_component_fn_name }}() throws {
+    if let message = {{ component.check_component_fn_name }}Mismatch {
+        throw UniffiInternalError.checksumMismatch(message)
+    }
+}
+{%- endfor %}
@@ -5,11 +5,13 @@ fileprivate enum UniffiInternalError: LocalizedError {
     case incompleteData
     case unexpectedOptionalTag
     case unexpectedEnumCase
+    case unexpectedCharValue
     case unexpectedNullPointer
     case unexpectedRustCallStatusCode
     case unexpectedRustCallError
     case unexpectedStaleHandle
     case rustPanic(_ message: String)
+    case checksumMismatch(_ message: String)
 
     public var errorDescription: String? {
         switch self {
@@ -17,18 +19,31 @@ fileprivate enum UniffiInternalError: LocalizedError {
         case .incompleteData: return "The buffer still has data after lifting its containing value"
         case .unexpectedOptionalTag: return "Unexpected optional tag; should be 0 or 1"
         case .unexpectedEnumCase: return "Raw enum value doesn't match any cases"
+        case .unexpectedCharValue: return "Raw char value isn't a Unicode scalar value"
         case .unexpectedNullPointer: return "Raw pointer value was null"
         case .unexpectedRustCallStatusCode: return "Unexpected RustCallStatus code"
         case .unexpectedRustCallError: return "CALL_ERROR but no errorClass specified"
         case .unexpectedStaleHandle: return "The object in the handle map has been dropped already"
         case let .rustPanic(message): return message
+        case let .checksumMismatch(message): return message
         }
     }
 }
 
+// Thrown when Rust is passed a string that isn't valid UTF-8, which these bindings never do
+// themselves, but which Rust reports as such rather than panicking.
+public struct InvalidStringError: LocalizedError {
+    public let message: String
+
+    public var errorDescription: String? {
+        return message
+    }
+}
+
 fileprivate let CALL_SUCCESS: Int8 = 0
 fileprivate let CALL_ERROR: Int8 = 1
 fileprivate let CALL_PANIC: Int8 = 2
+fileprivate let CALL_INVALID_STRING: Int8 = 3
 
 fileprivate extension RustCallStatus {
     init() {
@@ -43,6 +58,20 @@ fileprivate extension RustCallStatus {
     }
 }
 
+// Whether the library was built from a different component interface than these bindings, and
+// if so, what to tell the user. This is worked out the first time Rust is called, and checked
+// before every call after that.
+private let uniffiChecksumMismatch: String? = {
+    var callStatus = RustCallStatus.init()
+    let checksum = String(format: "%016llx", {{ ci.ffi_checksum().name() }}(&callStatus))
+    if checksum == uniffiComponentChecksum {
+        return nil
+    }
+    return "The {{ ci.namespace() }} library was built with checksum \(checksum), but these bindings, " +
+        "which uniffi-bindgen \(uniffiBindgenVersion) generated, expect \(uniffiComponentChecksum). " +
+        "Regenerate the bindings from the library's UDL."
+}()
+
 private func rustCall<T>(_ callback: (UnsafeMutablePointer<RustCallStatus>) -> T) throws -> T {
     try makeRustCall(callback, errorHandler: {
         $0.deallocate()
@@ -55,6 +84,9 @@ private func rustCallWithError<T, E: ViaFfiUsingByteBuffer & Error>(_ errorClass
 }
 
 private func makeRustCall<T>(_ callback: (UnsafeMutablePointer<RustCallStatus>) -> T, errorHandler: (RustBuffer) throws -> Error) throws -> T {
+    if let message = uniffiChecksumMismatch {
+        throw UniffiInternalError.checksumMismatch(message)
+    }
     var callStatus = RustCallStatus.init()
     let returnedVal = callback(&callStatus)
     switch callStatus.code {
@@ -75,7 +107,23 @@ private func makeRustCall<T>(_ callback: (UnsafeMutablePointer<RustCallStatus>)
                 throw UniffiInternalError.rustPanic("Rust panic")
             }
 
+        case CALL_INVALID_STRING:
+            throw InvalidStringError(message: try String.lift(callStatus.errorBuf))
+
         default:
             throw UniffiInternalError.unexpectedRustCallStatusCode
     }
 }
+
+// With `errors_as_values`, the blocking functions and methods that throw return a `Result`
+// instead, which holds what they returned or the error that they declare. Any other error, such
+// as a Rust panic, is fatal, as it is for the functions that don't throw.
+private func uniffiResultOf<T, E: Error>(_ errorClass: E.Type, _ body: () throws -> T) -> Result<T, E> {
+    do {
+        return .success(try body())
+    } catch let error as E {
+        return .failure(error)
+    } catch {
+        fatalError("\(error)")
+    }
+}
@@ -1,4 +1,5 @@
 {%- import "macros.swift" as swift -%}
+{%- let key_type = self.key() %}
 {%- let inner_type = self.inner() %}
 {%- let outer_type = self.outer() %}
 {%- let inner_type_name = inner_type|type_name %}
@@ -8,14 +9,14 @@ fileprivate enum FfiConverter{{ canonical_type_name }}: FfiConverterUsingByteBuf
 
     static func write(_ value: SwiftType, into buf: Writer) {
         FfiConverterDictionary.write(value, into: buf) { (key, value, buf) in
-            {{ "key"|write_var("buf", Type::String) }}
+            {{ "key"|write_var("buf", key_type) }}
             {{ "value"|write_var("buf", inner_type) }}
         }
     }
 
     static func read(from buf: Reader) throws -> SwiftType {
         try FfiConverterDictionary.read(from: buf) { buf in
-            (try {{ "buf"|read_var(Type::String) }},
+            (try {{ "buf"|read_var(key_type) }},
             try {{ "buf"|read_var(inner_type) }})
         }
     }
@@ -0,0 +1,13 @@
+/// Tell the Rust library that the system is running low on memory, so that it can shrink its
+/// caches.
+///
+/// Call it from `didReceiveMemoryWarning`, whose warnings are critical, or from a
+/// `DispatchSource.makeMemoryPressureSource` handler, passing the event that it was given.
+/// The hooks that the component registered with `uniffi::on_memory_pressure` are told whether
+/// the pressure is critical.
+public func uniffiOnMemoryPressure(_ level: DispatchSource.MemoryPressureEvent = .critical) {
+    let pressure: Int32 = level.contains(.critical) ? 2 : 1
+    try! rustCall {
+        {{ ci.ffi_on_memory_pressure().name() }}(pressure, $0)
+    }
+}
@@ -0,0 +1,119 @@
+{%- if has_closeable_objects %}
+// Tracks whether a `[Closeable]` object has been closed, and how many calls are using its
+// pointer, so that the underlying Rust struct is freed exactly once and only after the last
+// in-flight call has finished. Once closed, the object refuses to hand out its pointer again.
+fileprivate final class UniffiCloseState {
+    private let lock = NSLock()
+    private var closed = false
+    private var callCount = 0
+
+    var isClosed: Bool {
+        lock.lock()
+        defer { lock.unlock() }
+        return closed
+    }
+
+    // Claim the pointer for a call, returning false if the object has been closed.
+    func enter() -> Bool {
+        lock.lock()
+        defer { lock.unlock() }
+        if closed {
+            return false
+        }
+        callCount += 1
+        return true
+    }
+
+    // Finish a call, returning true if the pointer should now be freed.
+    func exit() -> Bool {
+        lock.lock()
+        defer { lock.unlock() }
+        callCount -= 1
+        return closed && callCount == 0
+    }
+
+    // Mark the object as closed, returning true if the pointer should now be freed.
+    func close() -> Bool {
+        lock.lock()
+        defer { lock.unlock() }
+        if closed {
+            return false
+        }
+        closed = true
+        return callCount == 0
+    }
+}
+{%- endif %}
+{%- if has_cached_methods %}
+
+// Keeps the value that a `[Cached]` method returned the first time it was called. The value is
+// only kept if the call succeeds, so a call that throws is tried again the next time.
+fileprivate final class UniffiCachedValue<T> {
+    private let lock = NSLock()
+    private var value: T?
+
+    func get(_ load: () throws -> T) rethrows -> T {
+        lock.lock()
+        defer { lock.unlock() }
+        if let value = value {
+            return value
+        }
+        let loaded = try load()
+        value = loaded
+        return loaded
+    }
+}
+{%- endif %}
+{%- if has_no_copy_methods %}
+
+/// The bytes that a `[NoCopy]` method returned, which are left where the Rust code put them
+/// rather than copied into a `Data`.
+///
+/// They're freed when this is deinitialized, or earlier by calling `free()`, after which
+/// they can't be read any more.
+public final class OwnedBytes {
+    private let lock = NSLock()
+    private let buf: RustBuffer
+    private var freed = false
+
+    fileprivate init(_ buf: RustBuffer) {
+        self.buf = buf
+    }
+
+    deinit {
+        free()
+    }
+
+    /// The number of bytes.
+    public var count: Int {
+        return Int(buf.len)
+    }
+
+    /// Call `body` with a view of the bytes, which doesn't copy them. The view must not be
+    /// used after `body` returns.
+    public func withUnsafeBytes<R>(_ body: (UnsafeRawBufferPointer) throws -> R) rethrows -> R {
+        lock.lock()
+        let isFreed = freed
+        lock.unlock()
+        if isFreed {
+            fatalError("OwnedBytes has already been freed")
+        }
+        return try body(UnsafeRawBufferPointer(start: buf.data, count: Int(buf.len)))
+    }
+
+    /// A copy of the bytes, which can still be used after they're freed.
+    public var data: Data {
+        return withUnsafeBytes { Data($0) }
+    }
+
+    /// Give the bytes back to the Rust code. It can be called more than once.
+    public func free() {
+        lock.lock()
+        defer { lock.unlock() }
+        if !freed {
+            freed = true
+            buf.deallocate()
+        }
+    }
+}
+{%- endif %}
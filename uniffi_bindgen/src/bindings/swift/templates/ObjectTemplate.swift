@@ -3,16 +3,54 @@
 {%- let obj = self.inner() %}
 public protocol {{ obj.name() }}Protocol {
     {% for meth in obj.methods() -%}
-    func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% call swift::throws(meth) -%}
+    {%- let as_result = self.errors_as_values() && meth.throws().is_some() && !meth.is_async() %}
+    {%- if meth.is_async() %}
+    @available(iOS 13, macOS 10.15, tvOS 13, watchOS 6, *)
+    {% endif -%}
+    {%- if as_result %}
     {%- match meth.return_type() -%}
-    {%- when Some with (return_type) %} -> {{ return_type|type_name -}}
+    {%- when Some with (return_type) %}
+    {%- if meth.is_no_copy() %}
+    func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) -> {% call swift::result_type(meth, "OwnedBytes") %}
+    {%- else %}
+    {%- let value_type = return_type|type_name %}
+    func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) -> {% call swift::result_type(meth, value_type) %}
+    {%- endif %}
+    {%- when None %}
+    func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) -> {% call swift::result_type(meth, "Void") %}
+    {%- endmatch %}
+    {%- else %}
+    func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% if meth.is_async() %}async {% endif %}{% call swift::throws(meth) -%}
+    {%- match meth.return_type() -%}
+    {%- when Some with (return_type) %} -> {% if meth.is_no_copy() %}OwnedBytes{% else %}{{ return_type|type_name }}{% endif -%}
     {%- else -%}
     {%- endmatch %}
+    {%- endif %}
+    {%- if meth.is_also_async() %}
+    {%- call swift::async_method_decl(meth) %}
+    {%- endif %}
     {% endfor %}
 }
 
-public class {{ obj|type_name }}: {{ obj.name() }}Protocol {
+{# A `[ForeignExtension]` comes first, in case it's a base class rather than a protocol. -#}
+public class {{ obj|type_name }}: {% match obj.foreign_extension() %}{% when Some with (ext) %}{{ ext }}, {% when None %}{% endmatch %}{{ obj.name() }}Protocol {
+    // The threads that this object can be used from, for tools that check the callers:
+    // "threadsafe", "main-thread-only" or "blocking".
+    public static let uniffiThreading = "{{ obj.threading().as_str() }}"
+
     fileprivate let pointer: UnsafeMutableRawPointer
+    {%- if obj.is_closeable() %}
+    fileprivate let closeState = UniffiCloseState()
+    {%- endif %}
+    {%- for meth in obj.methods() %}
+    {%- if meth.is_cached() %}
+    {%- match meth.return_type() %}
+    {%- when Some with (return_type) %}
+    private let {{ meth.name()|fn_name }}Cache = UniffiCachedValue<{{ return_type|type_name }}>()
+    {%- when None %}
+    {%- endmatch %}
+    {%- endif %}
+    {%- endfor %}
 
     // TODO: We'd like this to be `private` but for Swifty reasons,
     // we can't implement `ViaFfi` without making this `required` and we can't
@@ -29,9 +67,74 @@ public class {{ obj|type_name }}: {{ obj.name() }}Protocol {
     {%- when None %}
     {%- endmatch %}
 
+    {%- if obj.is_closeable() %}
+
+    deinit {
+        if closeState.close() {
+            try! rustCall { {{ obj.ffi_object_free().name() }}(pointer, $0) }
+        }
+    }
+
+    /// Free the underlying Rust object without waiting to be deinitialized.
+    ///
+    /// It can be called more than once, but once called, calling any other method on the
+    /// object is a fatal error.
+    public func close() {
+        if closeState.close() {
+            try! rustCall { {{ obj.ffi_object_free().name() }}(pointer, $0) }
+        }
+    }
+
+    fileprivate func uniffiClaimPointer() -> UnsafeMutableRawPointer {
+        guard closeState.enter() else {
+            fatalError("{{ obj|type_name }} object has already been closed")
+        }
+        return pointer
+    }
+
+    fileprivate func uniffiReleasePointer() {
+        if closeState.exit() {
+            try! rustCall { {{ obj.ffi_object_free().name() }}(pointer, $0) }
+        }
+    }
+    {%- else %}
+
     deinit {
         try! rustCall { {{ obj.ffi_object_free().name() }}(pointer, $0) }
     }
+    {%- endif %}
+
+    {%- match obj.ffi_object_clone() %}
+    {%- when Some with (ffi_clone) %}
+
+    /// Make a new, independent copy of the underlying Rust object.
+    public func copy() -> {{ obj|type_name }} {
+        {%- if obj.is_closeable() %}
+        let _pointer = uniffiClaimPointer()
+        defer { uniffiReleasePointer() }
+        return {{ obj|type_name }}(unsafeFromRawPointer: try! rustCall { {{ ffi_clone.name() }}(_pointer, $0) })
+        {%- else %}
+        return {{ obj|type_name }}(unsafeFromRawPointer: try! rustCall { {{ ffi_clone.name() }}(self.pointer, $0) })
+        {%- endif %}
+    }
+    {%- when None %}
+    {%- endmatch %}
+
+    {%- match obj.ffi_object_downgrade() %}
+    {%- when Some with (ffi_downgrade) %}
+
+    /// Make a weak reference to the underlying Rust object, which doesn't keep it alive.
+    public func downgrade() -> {{ obj|type_name }}WeakRef {
+        {%- if obj.is_closeable() %}
+        let _pointer = uniffiClaimPointer()
+        defer { uniffiReleasePointer() }
+        return {{ obj|type_name }}WeakRef(pointer: try! rustCall { {{ ffi_downgrade.name() }}(_pointer, $0) })
+        {%- else %}
+        return {{ obj|type_name }}WeakRef(pointer: try! rustCall { {{ ffi_downgrade.name() }}(self.pointer, $0) })
+        {%- endif %}
+    }
+    {%- when None %}
+    {%- endmatch %}
 
     {% for cons in obj.alternate_constructors() %}
     public static func {{ cons.name()|fn_name }}({% call swift::arg_list_decl(cons) %}) {% call swift::throws(cons) %} -> {{ obj|type_name }} {
@@ -41,19 +144,77 @@ public class {{ obj|type_name }}: {{ obj.name() }}Protocol {
 
     {# // TODO: Maybe merge the two templates (i.e the one with a return type and the one without) #}
     {% for meth in obj.methods() -%}
+    {%- if meth.is_async() %}
+    @available(iOS 13, macOS 10.15, tvOS 13, watchOS 6, *)
+    public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) async {% call swift::throws(meth) -%}
+    {%- match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
+        {%- if obj.is_closeable() %}
+        let _pointer = uniffiClaimPointer()
+        defer { uniffiReleasePointer() }
+        return {% call swift::to_async_ffi_call_with_prefix("_pointer", meth) %}
+        {%- else %}
+        return {% call swift::to_async_ffi_call_with_prefix("self.pointer", meth) %}
+        {%- endif %}
+    }
+    {%- else %}
+    {%- let as_result = self.errors_as_values() && meth.throws().is_some() %}
     {%- match meth.return_type() -%}
 
     {%- when Some with (return_type) -%}
-    public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_name }} {
+    {%- if as_result %}
+    {%- let value_type = return_type|type_name %}
+    public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) -> {% if meth.is_no_copy() %}{% call swift::result_type(meth, "OwnedBytes") %}{% else %}{% call swift::result_type(meth, value_type) %}{% endif %} {
+        return {% if meth.is_no_copy() %}{% call swift::result_of(meth, "OwnedBytes") %}{% else %}{% call swift::result_of(meth, value_type) %}{% endif %}
+    {%- else %}
+    public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {% if meth.is_no_copy() %}OwnedBytes{% else %}{{ return_type|type_name }}{% endif %} {
+    {%- endif %}
+        {%- if meth.is_cached() %}
+        return {% if meth.throws().is_some() %}try {% endif %}{{ meth.name()|fn_name }}Cache.get {
+        {%- endif %}
+        {%- if obj.is_closeable() %}
+        let _pointer = uniffiClaimPointer()
+        defer { uniffiReleasePointer() }
+        let _retval = {% call swift::to_ffi_call_with_prefix("_pointer", meth) %}
+        {%- else %}
         let _retval = {% call swift::to_ffi_call_with_prefix("self.pointer", meth) %}
+        {%- endif %}
+        {%- if meth.is_no_copy() %}
+        return OwnedBytes(_retval)
+        {%- else %}
         return {% call swift::try(meth) %} {{ "_retval"|lift_var(return_type) }}
+        {%- endif %}
+        {%- if meth.is_cached() %}
+        }
+        {%- endif %}
+        {%- if as_result %}
+        }
+        {%- endif %}
     }
 
     {%- when None -%}
+    {%- if as_result %}
+    public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) -> {% call swift::result_type(meth, "Void") %} {
+        return {% call swift::result_of(meth, "Void") %}
+    {%- else %}
     public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} {
+    {%- endif %}
+        {%- if obj.is_closeable() %}
+        let _pointer = uniffiClaimPointer()
+        defer { uniffiReleasePointer() }
+        {% call swift::to_ffi_call_with_prefix("_pointer", meth) %}
+        {%- else %}
         {% call swift::to_ffi_call_with_prefix("self.pointer", meth) %}
+        {%- endif %}
+        {%- if as_result %}
+        }
+        {%- endif %}
     }
     {%- endmatch %}
+    {%- endif %}
+    {%- if meth.is_also_async() %}
+
+    {%- call swift::async_method(meth) %}
+    {%- endif %}
     {% endfor %}
 }
 
@@ -83,6 +244,11 @@ fileprivate extension {{ obj|type_name }} {
     }
 
     func lower() -> UnsafeMutableRawPointer {
+        {%- if obj.is_closeable() %}
+        if closeState.isClosed {
+            fatalError("{{ obj|type_name }} object has already been closed")
+        }
+        {%- endif %}
         return self.pointer
     }
 }
@@ -92,3 +258,55 @@ fileprivate extension {{ obj|type_name }} {
 // 'private' modifier cannot be used with extensions that declare protocol conformances
 // """
 extension {{ obj|type_name }} : ViaFfi, Serializable {}
+{%- match obj.stream_item() %}
+{%- when Some with (item) %}
+
+// Iterating over a `[Stream]` takes its items from Rust a chunk at a time. A stream can only be
+// iterated over once: iterating again only gets the items that no earlier iteration has taken.
+extension {{ obj|type_name }} : Sequence {
+    public func makeIterator() -> AnyIterator<{{ item|type_name }}> {
+        var chunk: [{{ item|type_name }}] = []
+        var index = 0
+        return AnyIterator {
+            if index == chunk.count {
+                chunk = self.nextChunk(max: 64)
+                index = 0
+                if chunk.isEmpty {
+                    return nil
+                }
+            }
+            index += 1
+            return chunk[index - 1]
+        }
+    }
+}
+{%- when None %}
+{%- endmatch %}
+{%- match obj.ffi_object_upgrade() %}
+{%- when Some with (ffi_upgrade) %}
+
+/// A weak reference to a `{{ obj|type_name }}`, which doesn't keep the underlying Rust object alive,
+/// for caches that would rather not hold on to it.
+public class {{ obj|type_name }}WeakRef {
+    fileprivate let pointer: UnsafeMutableRawPointer
+
+    fileprivate init(pointer: UnsafeMutableRawPointer) {
+        self.pointer = pointer
+    }
+
+    deinit {
+        try! rustCall { {{ obj.ffi_object_weak_free().unwrap().name() }}(pointer, $0) }
+    }
+
+    /// Get the object back, or `nil` if the Rust object is gone.
+    public func upgrade() -> {{ obj|type_name }}? {
+        let value = try! rustCall { {{ ffi_upgrade.name() }}(self.pointer, $0) }
+        // We have to go via `UInt` because that's the thing that's the size of a pointer.
+        guard let ptr = UnsafeMutableRawPointer(bitPattern: UInt(truncatingIfNeeded: value)) else {
+            return nil
+        }
+        return {{ obj|type_name }}(unsafeFromRawPointer: ptr)
+    }
+}
+{%- when None %}
+{%- endmatch %}
@@ -0,0 +1,49 @@
+{%- let obj = self.inner() %}
+{%- let model = self.model()|class_name %}
+{%- let snapshot = self.snapshot()|class_name %}
+#if canImport(Combine)
+import Combine
+
+/// Publishes the latest `{{ snapshot }}` of a `{{ obj|type_name }}`, for SwiftUI views to observe.
+///
+/// It starts from `initial`, and observes the object for as long as it's alive. Changes made
+/// on other threads are published on the main one.
+@available(iOS 13, macOS 10.15, tvOS 13, watchOS 6, *)
+public final class {{ model }}: ObservableObject {
+    @Published public private(set) var state: {{ snapshot }}
+    public let source: {{ obj|type_name }}
+    private var observerId: UInt64 = 0
+
+    public init(_ source: {{ obj|type_name }}, initial: {{ snapshot }}) {
+        self.source = source
+        self.state = initial
+        self.observerId = source.{{ "add_observer"|fn_name }}({{ "observer"|var_name }}: Observer(self))
+    }
+
+    deinit {
+        source.{{ "remove_observer"|fn_name }}({{ "id"|var_name }}: observerId)
+    }
+
+    // Holds the model weakly, so that the object's observers don't keep it alive.
+    private final class Observer: {{ self.observer()|class_name }} {
+        weak var model: {{ model }}?
+
+        init(_ model: {{ model }}) {
+            self.model = model
+        }
+
+        func {{ "on_changed"|fn_name }}({{ "field"|var_name }}: String, {{ "snapshot"|var_name }}: {{ snapshot }}) {
+            guard let model = model else {
+                return
+            }
+            if Thread.isMainThread {
+                model.state = {{ "snapshot"|var_name }}
+            } else {
+                DispatchQueue.main.async { [weak model] in
+                    model?.state = {{ "snapshot"|var_name }}
+                }
+            }
+        }
+    }
+}
+#endif
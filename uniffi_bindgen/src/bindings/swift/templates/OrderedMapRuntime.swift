@@ -0,0 +1,79 @@
+/**
+ * A dictionary with `String` keys that keeps its entries in the order they were inserted,
+ * for the `[OrderedMap]` typedefs of the component.
+ *
+ * Setting the value of a key that's already present keeps it in its place, and setting it
+ * to `nil` removes it. Iterating over the map gives `(key, value)` pairs, in order.
+ */
+public struct OrderedMap<Value> {
+    public private(set) var keys: [String] = []
+    private var values: [String: Value] = [:]
+
+    public init() {}
+
+    public init<S: Sequence>(_ entries: S) where S.Element == (String, Value) {
+        for (key, value) in entries {
+            self[key] = value
+        }
+    }
+
+    public var count: Int { keys.count }
+
+    public var isEmpty: Bool { keys.isEmpty }
+
+    public subscript(key: String) -> Value? {
+        get { values[key] }
+        set {
+            if let newValue = newValue {
+                if values.updateValue(newValue, forKey: key) == nil {
+                    keys.append(key)
+                }
+            } else if values.removeValue(forKey: key) != nil {
+                keys.removeAll { $0 == key }
+            }
+        }
+    }
+}
+
+extension OrderedMap: Sequence {
+    public func makeIterator() -> AnyIterator<(key: String, value: Value)> {
+        var index = 0
+        return AnyIterator {
+            guard index < keys.count else { return nil }
+            let key = keys[index]
+            index += 1
+            return (key: key, value: values[key]!)
+        }
+    }
+}
+
+extension OrderedMap: ExpressibleByDictionaryLiteral {
+    public init(dictionaryLiteral elements: (String, Value)...) {
+        self.init(elements)
+    }
+}
+
+extension OrderedMap: Equatable where Value: Equatable {}
+
+extension OrderedMap: Hashable where Value: Hashable {}
+
+// Encoded as a list of alternating keys and values, like Swift encodes dictionaries whose keys
+// aren't strings, so that decoding it again keeps the order.
+extension OrderedMap: Codable where Value: Codable {
+    public init(from decoder: Decoder) throws {
+        self.init()
+        var container = try decoder.unkeyedContainer()
+        while !container.isAtEnd {
+            let key = try container.decode(String.self)
+            self[key] = try container.decode(Value.self)
+        }
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.unkeyedContainer()
+        for (key, value) in self {
+            try container.encode(key)
+            try container.encode(value)
+        }
+    }
+}
@@ -0,0 +1,26 @@
+{%- import "macros.swift" as swift -%}
+{%- let inner_type = self.inner() %}
+{%- let outer_type = self.outer() %}
+{%- let canonical_type_name = outer_type|canonical_name %}
+fileprivate enum FfiConverter{{ canonical_type_name }}: FfiConverterUsingByteBuffer {
+    typealias SwiftType = {{ outer_type|type_name }}
+
+    static func write(_ value: SwiftType, into buf: Writer) {
+        let len = Int32(value.count)
+        buf.writeInt(len)
+        for (key, value) in value {
+            {{ "key"|write_var("buf", Type::String) }}
+            {{ "value"|write_var("buf", inner_type) }}
+        }
+    }
+
+    static func read(from buf: Reader) throws -> SwiftType {
+        let len: Int32 = try buf.readInt()
+        var map = SwiftType()
+        for _ in 0..<len {
+            let key = try {{ "buf"|read_var(Type::String) }}
+            map[key] = try {{ "buf"|read_var(inner_type) }}
+        }
+        return map
+    }
+}
@@ -1,5 +1,8 @@
 {% import "macros.swift" as swift %}
 {%- let rec = self.inner() %}
+{%- if self.is_inline() %}
+@frozen
+{%- endif %}
 public struct {{ rec|type_name }} {
     {%- for field in rec.fields() %}
     public var {{ field.name()|var_name }}: {{ field|type_name }}
@@ -33,6 +36,34 @@ extension {{ rec|type_name }}: Equatable, Hashable {
 }
 {% endif %}
 
+{%- if rec.has_sensitive_fields() %}
+
+// `[Sensitive]` fields are left out when the record is printed, dumped or inspected in the
+// debugger, so that they don't end up in logs.
+extension {{ rec|type_name }}: CustomStringConvertible, CustomDebugStringConvertible, CustomReflectable {
+    public var description: String {
+        return "{{ rec|type_name }}({% for field in rec.fields() %}{{ field.name()|var_name }}: {% if field.sensitive() %}***{% else %}\(String(reflecting: self.{{ field.name()|var_name }})){% endif %}{% if !loop.last %}, {% endif %}{% endfor %})"
+    }
+
+    public var debugDescription: String {
+        return description
+    }
+
+    public var customMirror: Mirror {
+        return Mirror(self, children: [
+            {%- for field in rec.fields() %}
+            "{{ field.name()|var_name }}": {% if field.sensitive() %}"***"{% else %}self.{{ field.name()|var_name }}{% endif %},
+            {%- endfor %}
+        ])
+    }
+}
+{%- endif %}
+
+{%- if self.is_codable() %}
+
+extension {{ rec|type_name }}: Codable {}
+{%- endif %}
+
 fileprivate extension {{ rec|type_name }} {
     static func read(from buf: Reader) throws -> {{ rec|type_name }} {
         return try {{ rec|type_name }}(
@@ -75,7 +75,7 @@ fileprivate enum FfiConverterOptional {
 }
 
 fileprivate enum FfiConverterDictionary {
-    static func write<T>(_ value: [String: T], into buf: Writer, writeItem: (String, T, Writer) -> Void) {
+    static func write<K, T>(_ value: [K: T], into buf: Writer, writeItem: (K, T, Writer) -> Void) {
         let len = Int32(value.count)
         buf.writeInt(len)
         for (key, value) in value {
@@ -83,9 +83,9 @@ fileprivate enum FfiConverterDictionary {
         }
     }
 
-    static func read<T>(from buf: Reader, readItem: (Reader) throws -> (String, T)) throws -> [String: T] {
+    static func read<K: Hashable, T>(from buf: Reader, readItem: (Reader) throws -> (K, T)) throws -> [K: T] {
         let len: Int32 = try buf.readInt()
-        var dict = [String: T]()
+        var dict = [K: T]()
         dict.reserveCapacity(Int(len))
         for _ in 0..<len {
             let (key, value) = try readItem(buf)
@@ -1,16 +1,48 @@
 {% import "macros.swift" as swift %}
 {%- let func = self.inner() %}
+{#- Functions in nested namespaces are static members of their namespace's enum. #}
+{%- if func.is_async() %}
+
+@available(iOS 13, macOS 10.15, tvOS 13, watchOS 6, *)
+public {% if !func.module_path().is_empty() %}static {% endif %}func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) async {% call swift::throws(func) %}
+{%- match func.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
+    return {% call swift::to_async_ffi_call(func) %}
+}
+{% else %}
+{%- let as_result = self.errors_as_values() && func.throws().is_some() %}
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
+{%- let value_type = return_type|type_name %}
 
-public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) {% call swift::throws(func) %} -> {{ return_type|type_name }} {
+{%- if as_result %}
+
+public {% if !func.module_path().is_empty() %}static {% endif %}func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) -> {% call swift::result_type(func, value_type) %} {
+    return {% call swift::result_of(func, value_type) %}
+        let _retval = {% call swift::to_ffi_call(func) %}
+        return try {{ "_retval"|lift_var(return_type) }}
+    }
+}
+{%- else %}
+
+public {% if !func.module_path().is_empty() %}static {% endif %}func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) {% call swift::throws(func) %} -> {{ value_type }} {
     let _retval = {% call swift::to_ffi_call(func) %}
     return {% call swift::try(func) %} {{ "_retval"|lift_var(return_type) }}
 }
+{%- endif %}
 
 {% when None -%}
+{%- if as_result %}
+
+public {% if !func.module_path().is_empty() %}static {% endif %}func {{ func.name()|fn_name }}({% call swift::arg_list_decl(func) %}) -> {% call swift::result_type(func, "Void") %} {
+    return {% call swift::result_of(func, "Void") %}
+        {% call swift::to_ffi_call(func) %}
+    }
+}
+{%- else %}
 
-public func {{ func.name()|fn_name }}({% call swift::arg_list_decl(func) %}) {% call swift::throws(func) %} {
+public {% if !func.module_path().is_empty() %}static {% endif %}func {{ func.name()|fn_name }}({% call swift::arg_list_decl(func) %}) {% call swift::throws(func) %} {
     {% call swift::to_ffi_call(func) %}
 }
+{%- endif %}
 {% endmatch %}
+{%- endif %}
@@ -0,0 +1,21 @@
+/// Get the Rust library ready on a background queue, so that the first call into it is as quick
+/// as the ones after it.
+///
+/// Call it while the app starts up. It checks that the library matches these bindings, registers
+/// the callbacks that Rust needs, and runs the hooks that the component registered with
+/// `uniffi::on_warmup`, then calls `completion`. If anything fails, the first call into the
+/// component fails the same way.
+public func uniffiWarmup(completion: (() -> Void)? = nil) {
+    DispatchQueue.global(qos: .utility).async {
+        {%- for cbi in ci.iter_callback_interface_definitions() %}
+        _ = ffiConverterCallbackInterface{{ cbi.name()|class_name }}
+        {%- endfor %}
+        {%- if ci.ffi_async_init().is_some() %}
+        _ = uniffiAsyncCalls
+        {%- endif %}
+        try? rustCall {
+            {{ ci.ffi_warmup().name() }}($0)
+        }
+        completion?()
+    }
+}
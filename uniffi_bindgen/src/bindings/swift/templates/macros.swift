@@ -88,6 +88,105 @@
     RustCallStatus *_Nonnull out_status
 {%- endmacro -%}
 
+{#-
+// The arguments of a method, passed on as they are to a call of another one.
+-#}
+{%- macro arg_list_call(func) %}
+    {%- for arg in func.arguments() -%}
+        {{ arg.name()|var_name }}: {{ arg.name()|var_name }}
+        {%- if !loop.last %}, {% endif -%}
+    {%- endfor %}
+{%- endmacro %}
+
+{#-
+// The `async` variant of an `[AlsoAsync]` method, which calls the blocking method on a
+// background queue, and suspends the caller until it returns. With `errors_as_values`, it
+// returns the `Result` that the blocking method returns.
+-#}
+{%- macro async_method_decl(meth) %}
+    @available(iOS 13, macOS 10.15, tvOS 13, watchOS 6, *)
+    func {{ meth.async_method_name()|fn_name }}({% call arg_list_protocol(meth) %}) async {% call _async_method_returns(meth) %}
+{%- endmacro %}
+
+{%- macro async_method(meth) %}
+    @available(iOS 13, macOS 10.15, tvOS 13, watchOS 6, *)
+    public func {{ meth.async_method_name()|fn_name }}({% call arg_list_decl(meth) %}) async {% call _async_method_returns(meth) %} {
+        {%- if meth.throws().is_some() && !self.errors_as_values() %}
+        return try await withCheckedThrowingContinuation { continuation in
+            DispatchQueue.global().async {
+                continuation.resume(with: Result { try self.{{ meth.name()|fn_name }}({% call arg_list_call(meth) %}) })
+            }
+        }
+        {%- else %}
+        return await withCheckedContinuation { continuation in
+            DispatchQueue.global().async {
+                continuation.resume(returning: self.{{ meth.name()|fn_name }}({% call arg_list_call(meth) %}))
+            }
+        }
+        {%- endif %}
+    }
+{%- endmacro %}
+
+{%- macro _async_method_returns(meth) %}
+    {%- if self.errors_as_values() && meth.throws().is_some() %}
+    {%- match meth.return_type() %}
+    {%- when Some with (return_type) %}
+    {%- let value_type = return_type|type_name %}-> {% call result_type(meth, value_type) %}
+    {%- when None %}-> {% call result_type(meth, "Void") %}
+    {%- endmatch %}
+    {%- else %}
+    {%- call throws(meth) -%}
+    {%- match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %}
+    {%- endif %}
+{%- endmacro %}
+
+{#-
+// Call an `[Async]` function or method, suspending until Rust has completed the call. The handle
+// of the call follows the arguments, and the value that it returns comes back in a buffer.
+-#}
+{%- macro to_async_ffi_call(func) %}
+    {%- call _async_ffi_call("", func) %}
+{%- endmacro %}
+
+{%- macro to_async_ffi_call_with_prefix(prefix, func) %}
+    {%- call _async_ffi_call(format!("{}, ", prefix), func) %}
+{%- endmacro %}
+
+{%- macro _async_ffi_call(prefix, func) -%}
+{% call try(func) %} await uniffiAsyncCall(
+            start: { uniffiHandle in
+                {% match func.throws() %}{% when Some with (e) %}try rustCallWithError({{ e|class_name }}.self){% else %}try rustCall(){% endmatch %} {
+                    {{ func.ffi_func().name() }}({{ prefix }}{% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %}, {% endif %}uniffiHandle, $0)
+                }
+            },
+            cancellable: {% if func.throws().is_some() || func.is_cancellable() %}true{% else %}false{% endif %},
+            {%- match func.return_type() %}
+            {%- when Some with (return_type) %}
+            lift: { reader in try {{ "reader"|read_var(return_type) }} }
+            {%- when None %}
+            lift: { _ in }
+            {%- endmatch %}
+            {%- match func.throws() %}
+            {%- when Some with (e) %},
+            liftError: { try {{ e|class_name }}.lift($0) }
+            {%- else %}
+            {%- endmatch %}
+        )
+{%- endmacro %}
+
+{#-
+// With `errors_as_values`, a blocking function or method that throws returns a `Result` of the
+// value it returns and the error it declares. `result_of` starts the closure that catches the
+// error, around the rest of its body.
+-#}
+{%- macro result_type(func, value_type) %}
+{%- match func.throws() %}{% when Some with (e) %}Result<{{ value_type }}, {{ e|class_name }}>{% else %}{{ value_type }}{% endmatch %}
+{%- endmacro -%}
+
+{%- macro result_of(func, value_type) %}
+{%- match func.throws() %}{% when Some with (e) %}uniffiResultOf({{ e|class_name }}.self) { () throws -> {{ value_type }} in{% else %}{% endmatch %}
+{%- endmacro -%}
+
 {%- macro throws(func) %}
 {%- match func.throws() %}{% when Some with (e) %}throws{% else %}{% endmatch %}
 {%- endmacro -%}
@@ -95,3 +194,85 @@
 {%- macro try(func) %}
 {%- match func.throws() %}{% when Some with (e) %}try{% else %}try!{% endmatch %}
 {%- endmacro -%}
+
+{#-
+// The type of the value that a `[Batchable]` function's `BatchResult` holds.
+-#}
+{%- macro batch_result_type(func) -%}
+{%- match func.return_type() %}{% when Some with (return_type) %}{{ return_type|type_name }}{% when None %}Void{% endmatch -%}
+{%- endmacro -%}
+
+{#-
+// Unpack the arguments of a callback interface method from `args`, and call it on the
+// `swiftCallbackInterface`, keeping what it returns in `result`.
+-#}
+{%- macro invoke_callback_method(meth) %}
+        {%- if meth.arguments().len() != 0 %}
+        let reader = Reader(data: Data(rustBuffer: args))
+        {%- endif %}
+        {% if meth.return_type().is_some() %}let result = {% endif -%}
+        {% if meth.throws().is_some() %}try {% endif -%}
+        swiftCallbackInterface.{{ meth.name()|fn_name }}(
+            {%- for arg in meth.arguments() %}
+            {{ arg.name()|var_name }}: try {{ "reader"|read_var(arg) }}{% if !loop.last %},{% endif %}
+            {%- endfor %}
+        )
+{%- endmacro -%}
+
+{#-
+// Unpack the arguments of an `[Async]` callback interface method from `args`, followed by the
+// handle of the future that Rust is waiting on, and call it in a task that completes the future
+// once it has finished.
+-#}
+{%- macro start_async_callback_method(meth) %}
+        let reader = Reader(data: Data(rustBuffer: args))
+        {%- for arg in meth.arguments() %}
+        let uniffiArg{{ loop.index }} = try {{ "reader"|read_var(arg) }}
+        {%- endfor %}
+        let completionHandle: UInt64 = try reader.readInt()
+        guard #available(iOS 13, macOS 10.15, tvOS 13, watchOS 6, *) else {
+            fatalError("[Async] callback interface methods need iOS 13, macOS 10.15, tvOS 13 or watchOS 6")
+        }
+        Task {
+            {%- match meth.throws_type() %}
+            {%- when Some with (e) %}
+            do {
+                {% if meth.return_type().is_some() %}let result = {% endif -%}
+                try await {% call _call_async_callback_method(meth) %}
+                let writer = Writer()
+                writer.writeInt(CALLBACK_SUCCESS)
+                {%- match meth.return_type() %}
+                {%- when Some with (return_type) %}
+                {{ "result"|write_var("writer", return_type) }}
+                {%- when None %}
+                {%- endmatch %}
+                uniffiCompleteCallbackFuture(completionHandle, writer)
+            } catch let error as {{ e|type_name }} {
+                // The error that the method declares is sent back to Rust, to be returned there.
+                let writer = Writer()
+                writer.writeInt(CALLBACK_ERROR)
+                error.write(into: writer)
+                uniffiCompleteCallbackFuture(completionHandle, writer)
+            } catch {
+                uniffiFailCallbackFuture(completionHandle, error)
+            }
+            {%- when None %}
+            {% if meth.return_type().is_some() %}let result = {% endif -%}
+            await {% call _call_async_callback_method(meth) %}
+            let writer = Writer()
+            {%- match meth.return_type() %}
+            {%- when Some with (return_type) %}
+            {{ "result"|write_var("writer", return_type) }}
+            {%- when None %}
+            {%- endmatch %}
+            uniffiCompleteCallbackFuture(completionHandle, writer)
+            {%- endmatch %}
+        }
+        return RustBuffer()
+{%- endmacro -%}
+
+{%- macro _call_async_callback_method(meth) -%}
+swiftCallbackInterface.{{ meth.name()|fn_name }}(
+    {%- for arg in meth.arguments() %}{{ arg.name()|var_name }}: uniffiArg{{ loop.index }}{% if !loop.last %}, {% endif %}{% endfor -%}
+)
+{%- endmacro -%}
@@ -15,6 +15,10 @@ import {{ config.ffi_module_name() }}
 {% include "RustBufferTemplate.swift" %}
 {% include "Helpers.swift" %}
 {% include "RustBufferHelper.swift" %}
+{% include "ComponentInfoTemplate.swift" %}
+{% include "MemoryPressureTemplate.swift" %}
+{% include "WarmupTemplate.swift" %}
+{% include "BatchTemplate.swift" %}
 
 // Public interface members begin here.
 {% for code in self.declaration_code() %}
@@ -0,0 +1,260 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Golden-file tests
+//!
+//! A small change to a backend template can change the generated code for every component,
+//! and the foreign-language tests only notice when that breaks something. To make such changes
+//! reviewable, the code generated for a component can be committed as "golden" files, which
+//! `uniffi-bindgen golden` regenerates and compares:
+//!
+//! ```text
+//! golden/<udl name>/scaffolding/<udl name>.uniffi.rs
+//! golden/<udl name>/kotlin/uniffi/<namespace>/<namespace>.kt
+//! golden/<udl name>/swift/<namespace>.swift
+//! ...
+//! ```
+//!
+//! When the generated code changes on purpose, running with `--update` (or with
+//! `UNIFFI_UPDATE_GOLDEN=1` in the environment, as is handy under `cargo test`) rewrites the
+//! golden files, and the change shows up in the diff for review like any other.
+//!
+//! For this to work, generating code for the same component must always give the same output,
+//! so the generators iterate over sorted collections only. The golden files hold the code as
+//! rendered, without running it through `rustfmt`, `ktlint` and friends, so that they don't
+//! depend on which versions of those tools happen to be installed.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::bindings::TargetLanguage;
+
+/// The default directory holding the golden files, relative to the crate root.
+pub const DEFAULT_GOLDEN_DIR: &str = "golden";
+
+/// The environment variable which, when set, makes the golden-file checks update the golden
+/// files rather than compare against them.
+pub const UPDATE_GOLDEN_ENV_VAR: &str = "UNIFFI_UPDATE_GOLDEN";
+
+/// The contents of a set of generated files, keyed by their path relative to the golden directory.
+pub type GoldenFiles = BTreeMap<PathBuf, String>;
+
+/// The subdirectory of the golden directory that holds the bindings for a language.
+pub fn language_dir(language: TargetLanguage) -> &'static str {
+    match language {
+        TargetLanguage::Kotlin => "kotlin",
+        TargetLanguage::Swift => "swift",
+        TargetLanguage::Python => "python",
+        TargetLanguage::Ruby => "ruby",
+        TargetLanguage::CSharp => "csharp",
+        TargetLanguage::Go => "go",
+        TargetLanguage::Rust => "rust",
+    }
+}
+
+/// A difference between the golden files and the generated code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    /// A file was generated that has no golden file.
+    Added(PathBuf),
+    /// A golden file is no longer generated.
+    Removed(PathBuf),
+    /// A file was generated with different contents, starting at the given (1-based) line.
+    Changed {
+        path: PathBuf,
+        line: usize,
+        golden: String,
+        generated: String,
+    },
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::Added(path) => {
+                write!(f, "{}: generated, but has no golden file", path.display())
+            }
+            Mismatch::Removed(path) => {
+                write!(f, "{}: golden file is no longer generated", path.display())
+            }
+            Mismatch::Changed {
+                path,
+                line,
+                golden,
+                generated,
+            } => write!(
+                f,
+                "{}:{}: expected `{}`, generated `{}`",
+                path.display(),
+                line,
+                golden,
+                generated
+            ),
+        }
+    }
+}
+
+/// Compare the generated files with the golden ones, in order of their paths.
+pub fn compare(golden: &GoldenFiles, generated: &GoldenFiles) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    for (path, contents) in generated {
+        match golden.get(path) {
+            None => mismatches.push(Mismatch::Added(path.clone())),
+            Some(expected) if expected != contents => {
+                mismatches.push(first_difference(path, expected, contents))
+            }
+            Some(_) => (),
+        }
+    }
+    for path in golden.keys() {
+        if !generated.contains_key(path) {
+            mismatches.push(Mismatch::Removed(path.clone()));
+        }
+    }
+    mismatches.sort_by(|a, b| mismatch_path(a).cmp(mismatch_path(b)));
+    mismatches
+}
+
+fn mismatch_path(mismatch: &Mismatch) -> &Path {
+    match mismatch {
+        Mismatch::Added(path) | Mismatch::Removed(path) | Mismatch::Changed { path, .. } => path,
+    }
+}
+
+// Find the first line that differs, so that the report points at it. If one file is a prefix
+// of the other, the shorter one is reported as having an empty line there.
+fn first_difference(path: &Path, golden: &str, generated: &str) -> Mismatch {
+    let mut golden_lines = golden.split('\n');
+    let mut generated_lines = generated.split('\n');
+    let mut line = 1;
+    loop {
+        match (golden_lines.next(), generated_lines.next()) {
+            (Some(a), Some(b)) if a == b => line += 1,
+            (a, b) => {
+                return Mismatch::Changed {
+                    path: path.to_owned(),
+                    line,
+                    golden: a.unwrap_or_default().to_string(),
+                    generated: b.unwrap_or_default().to_string(),
+                }
+            }
+        }
+    }
+}
+
+/// Read every file under a directory, keyed by its path relative to that directory.
+pub fn read_golden_files(dir: &Path) -> Result<GoldenFiles> {
+    let mut files = GoldenFiles::new();
+    read_dir_into(dir, Path::new(""), &mut files)?;
+    Ok(files)
+}
+
+fn read_dir_into(root: &Path, relative: &Path, files: &mut GoldenFiles) -> Result<()> {
+    let dir = root.join(relative);
+    for entry in fs::read_dir(&dir).with_context(|| format!("Failed to read {:?}", dir))? {
+        let entry = entry?;
+        let path = relative.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            read_dir_into(root, &path, files)?;
+        } else {
+            let contents = fs::read_to_string(entry.path())
+                .with_context(|| format!("Failed to read {:?}", entry.path()))?;
+            files.insert(path, contents);
+        }
+    }
+    Ok(())
+}
+
+/// Make the golden directory hold exactly the given files, removing any golden files that are
+/// no longer generated.
+pub fn write_golden_files(dir: &Path, files: &GoldenFiles) -> Result<()> {
+    if dir.is_dir() {
+        for path in read_golden_files(dir)?.keys() {
+            if !files.contains_key(path) {
+                fs::remove_file(dir.join(path))?;
+            }
+        }
+    }
+    for (path, contents) in files {
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, contents).with_context(|| format!("Failed to write {:?}", path))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn files(entries: &[(&str, &str)]) -> GoldenFiles {
+        entries
+            .iter()
+            .map(|(path, contents)| (PathBuf::from(path), contents.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_identical_files_match() {
+        let golden = files(&[
+            ("kotlin/a.kt", "fun a()\n"),
+            ("swift/a.swift", "func a()\n"),
+        ]);
+        assert!(compare(&golden, &golden.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_mismatches_are_reported_in_path_order() {
+        let golden = files(&[
+            ("kotlin/a.kt", "package a\nfun a()\n"),
+            ("python/a.py", "def a():\n"),
+        ]);
+        let generated = files(&[
+            ("kotlin/a.kt", "package a\nfun b()\n"),
+            ("swift/a.swift", "func a()\n"),
+        ]);
+        let mismatches = compare(&golden, &generated);
+        assert_eq!(
+            mismatches,
+            vec![
+                Mismatch::Changed {
+                    path: "kotlin/a.kt".into(),
+                    line: 2,
+                    golden: "fun a()".into(),
+                    generated: "fun b()".into(),
+                },
+                Mismatch::Removed("python/a.py".into()),
+                Mismatch::Added("swift/a.swift".into()),
+            ]
+        );
+        assert_eq!(
+            mismatches[0].to_string(),
+            format!(
+                "{}:2: expected `fun a()`, generated `fun b()`",
+                Path::new("kotlin/a.kt").display()
+            )
+        );
+    }
+
+    #[test]
+    fn test_truncated_file_is_changed() {
+        let golden = files(&[("a.py", "one\ntwo\n")]);
+        let generated = files(&[("a.py", "one\n")]);
+        assert_eq!(
+            compare(&golden, &generated),
+            vec![Mismatch::Changed {
+                path: "a.py".into(),
+                line: 2,
+                golden: "two".into(),
+                generated: "".into(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,193 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Starting a new component
+//!
+//! `uniffi-bindgen init my_component` creates a crate for a new component that builds, and
+//! passes its tests, straight away:
+//!
+//! ```text
+//! my_component/Cargo.toml
+//! my_component/build.rs
+//! my_component/uniffi.toml
+//! my_component/src/lib.rs
+//! my_component/src/my_component.udl
+//! my_component/tests/test_generated_bindings.rs
+//! my_component/tests/bindings/test_my_component.py
+//! my_component/tests/bindings/test_my_component.kts
+//! ```
+//!
+//! The UDL declares a single `hello` function, which `lib.rs` implements, and the smoke tests
+//! call it from Python and Kotlin under `cargo test`. The crate depends on the version of
+//! `uniffi` that matches the `uniffi-bindgen` that created it.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use askama::Template;
+
+#[derive(Template)]
+#[template(escape = "none", path = "InitCargo.toml")]
+struct CargoToml<'a> {
+    name: &'a str,
+    uniffi_version: &'a str,
+}
+
+#[derive(Template)]
+#[template(escape = "none", path = "InitBuild.rs")]
+struct BuildRs<'a> {
+    name: &'a str,
+}
+
+#[derive(Template)]
+#[template(escape = "none", path = "InitLib.rs")]
+struct LibRs<'a> {
+    name: &'a str,
+}
+
+#[derive(Template)]
+#[template(escape = "none", path = "InitComponent.udl")]
+struct ComponentUdl<'a> {
+    name: &'a str,
+}
+
+#[derive(Template)]
+#[template(escape = "none", path = "InitUniffi.toml")]
+struct UniffiToml<'a> {
+    name: &'a str,
+}
+
+#[derive(Template)]
+#[template(escape = "none", path = "InitTests.rs")]
+struct TestsRs<'a> {
+    name: &'a str,
+}
+
+#[derive(Template)]
+#[template(escape = "none", path = "InitSmokeTest.py")]
+struct SmokeTestPy<'a> {
+    name: &'a str,
+}
+
+#[derive(Template)]
+#[template(escape = "none", path = "InitSmokeTest.kts")]
+struct SmokeTestKts<'a> {
+    name: &'a str,
+}
+
+// The component's name becomes its crate name, its UDL namespace, and a module or package name
+// in each of the bindings, so it has to suit all of them.
+fn check_component_name(name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let valid = matches!(chars.next(), Some('a'..='z'))
+        && chars.all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_'));
+    if !valid {
+        bail!(
+            "Invalid component name \"{}\": use lowercase letters, digits and underscores, starting with a letter",
+            name
+        );
+    }
+    Ok(())
+}
+
+// Askama drops the newline at the end of each template, which the files should end with.
+fn render(template: impl Template) -> Result<String> {
+    Ok(template.render()? + "\n")
+}
+
+/// The files of a new component's crate, by their path relative to the crate's directory.
+pub fn component_files(name: &str, uniffi_version: &str) -> Result<BTreeMap<PathBuf, String>> {
+    check_component_name(name)?;
+    let mut files = BTreeMap::new();
+    files.insert(
+        PathBuf::from("Cargo.toml"),
+        render(CargoToml {
+            name,
+            uniffi_version,
+        })?,
+    );
+    files.insert(PathBuf::from("build.rs"), render(BuildRs { name })?);
+    files.insert(PathBuf::from("uniffi.toml"), render(UniffiToml { name })?);
+    files.insert(["src", "lib.rs"].iter().collect(), render(LibRs { name })?);
+    files.insert(
+        Path::new("src").join(format!("{}.udl", name)),
+        render(ComponentUdl { name })?,
+    );
+    files.insert(
+        ["tests", "test_generated_bindings.rs"].iter().collect(),
+        render(TestsRs { name })?,
+    );
+    files.insert(
+        Path::new("tests/bindings").join(format!("test_{}.py", name)),
+        render(SmokeTestPy { name })?,
+    );
+    files.insert(
+        Path::new("tests/bindings").join(format!("test_{}.kts", name)),
+        render(SmokeTestKts { name })?,
+    );
+    Ok(files)
+}
+
+/// Write the files of a new component's crate into `crate_dir`, which mustn't exist yet, so
+/// that nothing is ever overwritten.
+pub fn write_component(crate_dir: &Path, files: &BTreeMap<PathBuf, String>) -> Result<()> {
+    if crate_dir.exists() {
+        bail!("{:?} already exists", crate_dir);
+    }
+    for (path, contents) in files.iter() {
+        let path = crate_dir.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {:?}", parent))?;
+        }
+        fs::write(&path, contents).with_context(|| format!("Failed to write {:?}", path))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::interface::ComponentInterface;
+
+    #[test]
+    fn test_component_files() {
+        let files = component_files("todo_list", "1.2.3").unwrap();
+        let file = |path: &str| files[&path.split('/').collect::<PathBuf>()].as_str();
+        assert_eq!(files.len(), 8);
+        assert!(file("Cargo.toml").contains("name = \"uniffi_todo_list\""));
+        assert!(file("Cargo.toml").contains("uniffi = \"1.2.3\""));
+        assert!(file("build.rs").contains("generate_scaffolding(\"./src/todo_list.udl\")"));
+        assert!(file("src/lib.rs")
+            .contains("include!(concat!(env!(\"OUT_DIR\"), \"/todo_list.uniffi.rs\"));"));
+        assert!(file("tests/test_generated_bindings.rs")
+            .contains("\"tests/bindings/test_todo_list.kts\""));
+        assert!(file("tests/bindings/test_todo_list.py").starts_with("from todo_list import *"));
+
+        // The starter UDL is a valid component in its own right.
+        let ci = ComponentInterface::from_webidl(file("src/todo_list.udl")).unwrap();
+        assert_eq!(ci.namespace(), "todo_list");
+        assert!(ci.get_function_definition("hello").is_some());
+    }
+
+    #[test]
+    fn test_invalid_component_names() {
+        for name in &["", "TodoList", "todo-list", "1st", "_todo", "todo list"] {
+            assert!(component_files(name, "1.2.3").is_err(), "{:?}", name);
+        }
+        assert!(component_files("todo2", "1.2.3").is_ok());
+    }
+
+    #[test]
+    fn test_write_component_refuses_to_overwrite() {
+        let dir = std::env::temp_dir().join(format!("uniffi-init-test-{}", std::process::id()));
+        let files = component_files("todo_list", "1.2.3").unwrap();
+        write_component(&dir, &files).unwrap();
+        assert!(dir.join("src").join("todo_list.udl").is_file());
+        assert!(write_component(&dir, &files).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
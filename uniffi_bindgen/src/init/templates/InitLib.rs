@@ -0,0 +1,8 @@
+// The Rust implementation of the interface declared in `{{ name }}.udl`. The scaffolding that
+// `build.rs` generates from the UDL is included at the bottom, and calls these functions.
+
+fn hello(name: String) -> String {
+    format!("Hello, {}!", name)
+}
+
+include!(concat!(env!("OUT_DIR"), "/{{ name }}.uniffi.rs"));
@@ -0,0 +1,3 @@
+import uniffi.{{ name }}.*
+
+assert(hello("Kotlin") == "Hello, Kotlin!")
@@ -0,0 +1,3 @@
+from {{ name }} import *
+
+assert hello("Python") == "Hello, Python!"
@@ -0,0 +1,10 @@
+// Run the smoke tests in `tests/bindings` against freshly generated bindings, with `cargo test`.
+// These need `uniffi-bindgen` and each language's tools to be installed; to leave out a
+// language, list its extensions in `UNIFFI_TESTS_DISABLE_EXTENSIONS`, e.g. `kts`.
+uniffi_macros::build_foreign_language_testcases!(
+    ["src/{{ name }}.udl"],
+    [
+        "tests/bindings/test_{{ name }}.py",
+        "tests/bindings/test_{{ name }}.kts",
+    ]
+);
@@ -18,6 +18,8 @@ use std::convert::{TryFrom, TryInto};
 
 use anyhow::{bail, Result};
 
+use super::object::Threading;
+
 /// Represents an attribute parsed from UDL, like `[ByRef]` or `[Throws]`.
 ///
 /// This is a convenience enum for parsing UDL attributes and erroring out if we encounter
@@ -25,17 +27,88 @@ use anyhow::{bail, Result};
 /// may influence the properties of things like functions and arguments.
 #[derive(Debug, Clone, Hash)]
 pub(super) enum Attribute {
+    // A method that also gets an `async` or `suspend` variant, which calls it off the caller's thread.
+    AlsoAsync,
+    // A callback interface method that returns through a future, which the foreign implementation completes later.
+    Async,
+    // A function that can also be queued up with others and called in a single FFI call.
+    Batchable,
+    // An object whose methods block, so that it mustn't be used from the app's main thread.
+    Blocking,
+    // A method returning a view of a string or bytes owned by the object, rather than a copy.
+    Borrowed,
     ByRef,
+    // A method whose result never changes for a given object, so the bindings keep it after the first call.
+    Cached,
+    // An `[Async]` function or method that's given a `CancellationToken`, to notice when it's cancelled.
+    Cancellable,
+    Clone,
+    // An object that can be closed explicitly, after which its handle can no longer be used.
+    Closeable,
+    // Like `Wrapped`, for a type that implements `UniffiCustomTypeConverter` instead.
+    Custom,
+    // A callback interface whose primitive-only methods are called through function pointers of their own.
+    Direct,
     Enum,
     Error,
+    // `[ErrorFrom="std::io::Error"]` - Rust error types to convert into a variant of an error.
+    ErrorFrom(Vec<String>),
+    // A function that can also be called by name, through the component's generic invoke function.
+    Invokable,
+    // `[Events=Event]` - An object that emits events of the given type to its listeners.
+    Events(String),
+    // `[ForeignExtension=Name]` - A hand-written foreign class or protocol that an object extends.
+    ForeignExtension(String),
     Name(String),
+    // A method returning bytes that the bindings get a view of, rather than a copy, and free explicitly.
+    NoCopy,
+    // An object that must only be used from the app's main thread.
+    MainThread,
+    // `[Observable=Snapshot]` - An object that notifies observers of changes, with a snapshot record.
+    Observable(String),
+    // An object without constructors or methods, which foreign code only holds on to and hands back.
+    OpaqueToken,
+    // A callback interface method that foreign implementations don't have to provide.
+    Optional,
+    // A callback interface whose calls are delivered one at a time, in order, on a thread of their own.
+    Ordered,
+    // A method returning a sequence, which also gets a variant that returns one page of it at a time.
+    Paginated,
+    // `[Parent=example]` - The namespace that this one is nested inside.
+    Parent(String),
+    // Release the callback interfaces passed to an object's constructor when the object is destroyed.
+    ReleaseCallbacks,
+    // A function or method that takes a `Scope`, which its background work is registered against.
+    Scoped,
     SelfType(SelfType),
+    // A record field holding a secret, which the bindings leave out when they print the record.
+    Sensitive,
+    // An object running in the background, which gets methods for starting, stopping and watching it.
+    Service,
+    // Send the chain of `source()`s of a Rust error along with the error itself.
+    SourceChain,
+    // A callback interface whose foreign implementations say which methods they have.
+    Versioned,
     Threadsafe, // N.B. the `[Threadsafe]` attribute is deprecated and will be removed
     Throws(String),
+    // An interface implemented by any Rust type implementing a trait, held as `Arc<dyn Trait>`.
+    Trait,
     // `[External="crate_name"]` - We can `use crate_name::...` for the type.
     External(String),
+    // `[ExternalInterface="crate_name"]` - The type is an interface of the component in that crate.
+    ExternalInterface(String),
+    // An object that foreign code can also hold weak references to, backed by a Rust `Weak`.
+    WeakRefs,
     // Something hand-written in this crate which wraps a primitive type.
     Wrapped,
+    // Coalesce an empty string/sequence/map in an optional field into `null`.
+    EmptyAsNull,
+    // Coalesce a `null` in an optional string/sequence/map field into an empty value.
+    NullAsEmpty,
+    // A map that keeps its entries in the order they were inserted.
+    OrderedMap,
+    // An object handing out the values of a sequence a chunk at a time.
+    Stream,
 }
 
 impl Attribute {
@@ -57,11 +130,42 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
         match weedle_attribute {
             // Matches plain named attributes like "[ByRef"].
             weedle::attribute::ExtendedAttribute::NoArgs(attr) => match (attr.0).0 {
+                "Batchable" => Ok(Attribute::Batchable),
+                "Invokable" => Ok(Attribute::Invokable),
+                "Borrowed" => Ok(Attribute::Borrowed),
+                "NoCopy" => Ok(Attribute::NoCopy),
+                "MainThread" => Ok(Attribute::MainThread),
+                "Blocking" => Ok(Attribute::Blocking),
                 "ByRef" => Ok(Attribute::ByRef),
+                // `[Pure]` is another name for `[Cached]`.
+                "Cached" | "Pure" => Ok(Attribute::Cached),
+                "Clone" => Ok(Attribute::Clone),
+                "Closeable" => Ok(Attribute::Closeable),
+                "Custom" => Ok(Attribute::Custom),
+                "Direct" => Ok(Attribute::Direct),
                 "Enum" => Ok(Attribute::Enum),
                 "Error" => Ok(Attribute::Error),
                 "Threadsafe" => Ok(Attribute::Threadsafe),
                 "Wrapped" => Ok(Attribute::Wrapped),
+                "EmptyAsNull" => Ok(Attribute::EmptyAsNull),
+                "NullAsEmpty" => Ok(Attribute::NullAsEmpty),
+                "OrderedMap" => Ok(Attribute::OrderedMap),
+                "Stream" => Ok(Attribute::Stream),
+                "ReleaseCallbacks" => Ok(Attribute::ReleaseCallbacks),
+                "Versioned" => Ok(Attribute::Versioned),
+                "SourceChain" => Ok(Attribute::SourceChain),
+                "Optional" => Ok(Attribute::Optional),
+                "OpaqueToken" => Ok(Attribute::OpaqueToken),
+                "Ordered" => Ok(Attribute::Ordered),
+                "Paginated" => Ok(Attribute::Paginated),
+                "AlsoAsync" => Ok(Attribute::AlsoAsync),
+                "Async" => Ok(Attribute::Async),
+                "Cancellable" => Ok(Attribute::Cancellable),
+                "Scoped" => Ok(Attribute::Scoped),
+                "Service" => Ok(Attribute::Service),
+                "Sensitive" => Ok(Attribute::Sensitive),
+                "Trait" => Ok(Attribute::Trait),
+                "WeakRefs" => Ok(Attribute::WeakRefs),
                 _ => anyhow::bail!("ExtendedAttributeNoArgs not supported: {:?}", (attr.0).0),
             },
             // Matches assignment-style attributes like ["Throws=Error"]
@@ -71,6 +175,27 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
                     "Throws" => Ok(Attribute::Throws(name_from_id_or_string(&identity.rhs))),
                     "Self" => Ok(Attribute::SelfType(SelfType::try_from(&identity.rhs)?)),
                     "External" => Ok(Attribute::External(name_from_id_or_string(&identity.rhs))),
+                    "ExternalInterface" => Ok(Attribute::ExternalInterface(
+                        name_from_id_or_string(&identity.rhs),
+                    )),
+                    "Parent" => Ok(Attribute::Parent(name_from_id_or_string(&identity.rhs))),
+                    "ForeignExtension" => Ok(Attribute::ForeignExtension(name_from_id_or_string(
+                        &identity.rhs,
+                    ))),
+                    "Observable" => {
+                        Ok(Attribute::Observable(name_from_id_or_string(&identity.rhs)))
+                    }
+                    "Events" => Ok(Attribute::Events(name_from_id_or_string(&identity.rhs))),
+                    "ErrorFrom" => {
+                        let types: Vec<String> = name_from_id_or_string(&identity.rhs)
+                            .split(',')
+                            .map(|t| t.trim().to_string())
+                            .collect();
+                        if types.iter().any(String::is_empty) {
+                            bail!("[ErrorFrom] needs a comma-separated list of Rust error types");
+                        }
+                        Ok(Attribute::ErrorFrom(types))
+                    }
                     _ => anyhow::bail!(
                         "Attribute identity Identifier not supported: {:?}",
                         identity.lhs_identifier.0
@@ -120,7 +245,8 @@ where
 }
 
 /// Attributes that can be attached to an `enum` definition in the UDL.
-/// There's only one case here: using `[Error]` to mark an enum as an error class.
+/// This can be `[Error]` to mark an enum as an error class, optionally along with
+/// `[SourceChain]` to send the error's chain of sources to the foreign language.
 #[derive(Debug, Clone, Hash, Default)]
 pub(super) struct EnumAttributes(Vec<Attribute>);
 
@@ -128,6 +254,12 @@ impl EnumAttributes {
     pub fn contains_error_attr(&self) -> bool {
         self.0.iter().any(|attr| attr.is_error())
     }
+
+    pub fn has_source_chain(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::SourceChain))
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for EnumAttributes {
@@ -137,9 +269,14 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for EnumAttributes {
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
             Attribute::Error => Ok(()),
+            Attribute::SourceChain => Ok(()),
             _ => bail!(format!("{:?} not supported for enums", attr)),
         })?;
-        Ok(Self(attrs))
+        let attrs = Self(attrs);
+        if attrs.has_source_chain() && !attrs.contains_error_attr() {
+            bail!("the [SourceChain] attribute is only supported on errors");
+        }
+        Ok(attrs)
     }
 }
 
@@ -153,10 +290,109 @@ impl<T: TryInto<EnumAttributes, Error = anyhow::Error>> TryFrom<Option<T>> for E
     }
 }
 
+/// Represents UDL attributes that might appear on a `namespace` definition.
+///
+/// This supports the `[Parent=name]` attribute for namespaces nested inside another one.
+#[derive(Debug, Clone, Hash, Default)]
+pub(super) struct NamespaceAttributes(Vec<Attribute>);
+
+impl NamespaceAttributes {
+    pub(super) fn get_parent(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Parent(inner) => Some(inner.as_ref()),
+            _ => None,
+        })
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for NamespaceAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::Parent(_) => Ok(()),
+            _ => bail!(format!("{:?} not supported for namespaces", attr)),
+        })?;
+        Ok(Self(attrs))
+    }
+}
+
+impl<T: TryInto<NamespaceAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
+    for NamespaceAttributes
+{
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
+/// Represents UDL attributes that might appear on a `callback interface` definition.
+///
+/// This supports the `[Direct]` attribute, for callback interfaces whose methods are called
+/// often enough that serializing their arguments into a `RustBuffer` is a noticeable cost, and
+/// the `[Ordered]` attribute, for callback interfaces whose calls must arrive in the order they
+/// were made, even when Rust makes them from several threads, and the `[Versioned]` attribute,
+/// for callback interfaces whose methods are called by name rather than by position.
+#[derive(Debug, Clone, Hash, Default)]
+pub(super) struct CallbackInterfaceAttributes(Vec<Attribute>);
+
+impl CallbackInterfaceAttributes {
+    pub(super) fn is_direct(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Direct))
+    }
+
+    pub(super) fn is_ordered(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Ordered))
+    }
+
+    pub(super) fn is_versioned(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Versioned))
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for CallbackInterfaceAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::Direct | Attribute::Ordered | Attribute::Versioned => Ok(()),
+            _ => bail!(format!(
+                "{:?} not supported for callback interface definition",
+                attr
+            )),
+        })?;
+        Ok(Self(attrs))
+    }
+}
+
+impl<T: TryInto<CallbackInterfaceAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
+    for CallbackInterfaceAttributes
+{
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
 /// Represents UDL attributes that might appear on a function.
 ///
 /// This supports the `[Throws=ErrorName]` attribute for functions that
-/// can produce an error.
+/// can produce an error, the `[Batchable]` attribute for functions that
+/// can also be called as part of a batch, the `[Invokable]` attribute for
+/// functions that can also be called by name, the `[Scoped]` attribute for
+/// functions that take a `Scope`, the `[Async]` attribute for functions
+/// that are implemented by a Rust `async fn`, and the `[Cancellable]` attribute
+/// for async functions that are given a `CancellationToken`.
 #[derive(Debug, Clone, Hash, Default)]
 pub(super) struct FunctionAttributes(Vec<Attribute>);
 
@@ -169,6 +405,32 @@ impl FunctionAttributes {
             _ => None,
         })
     }
+
+    pub(super) fn is_batchable(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Batchable))
+    }
+
+    pub(super) fn is_invokable(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Invokable))
+    }
+
+    pub(super) fn is_scoped(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Scoped))
+    }
+
+    pub(super) fn is_async(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Async))
+    }
+
+    pub(super) fn is_cancellable(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Cancellable))
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for FunctionAttributes {
@@ -177,10 +439,24 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for FunctionAttribut
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::Throws(_) => Ok(()),
+            Attribute::Throws(_)
+            | Attribute::Batchable
+            | Attribute::Invokable
+            | Attribute::Scoped
+            | Attribute::Async
+            | Attribute::Cancellable => Ok(()),
             _ => bail!(format!("{:?} not supported for functions", attr)),
         })?;
-        Ok(Self(attrs))
+        let attrs = Self(attrs);
+        // Batches and invoked calls return their results straight away, which an async
+        // function doesn't have yet.
+        if attrs.is_async() && (attrs.is_batchable() || attrs.is_invokable()) {
+            bail!("[Async] functions cannot be [Batchable] or [Invokable]");
+        }
+        if attrs.is_cancellable() && !attrs.is_async() {
+            bail!("[Cancellable] functions must also be [Async]");
+        }
+        Ok(attrs)
     }
 }
 
@@ -234,6 +510,109 @@ impl<T: TryInto<ArgumentAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
     }
 }
 
+/// Represents UDL attributes that might appear on the variant of an `[Error] interface`.
+///
+/// This supports the `[ErrorFrom]` attribute, which lists the Rust error types that the
+/// scaffolding should implement `From` for, by converting them into this variant. The special
+/// type `*` makes it the catch-all variant, for an `anyhow::Error` or any boxed error.
+#[derive(Debug, Clone, Hash, Default)]
+pub(super) struct VariantAttributes(Vec<Attribute>);
+
+impl VariantAttributes {
+    pub fn error_from(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .flat_map(|attr| match attr {
+                Attribute::ErrorFrom(types) => types.clone(),
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for VariantAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::ErrorFrom(_) => Ok(()),
+            _ => bail!(format!("{:?} not supported for enum variants", attr)),
+        })?;
+        Ok(Self(attrs))
+    }
+}
+
+impl<T: TryInto<VariantAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
+    for VariantAttributes
+{
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
+/// Represents UDL attributes that might appear on a dictionary member.
+///
+/// This supports the `[EmptyAsNull]` and `[NullAsEmpty]` attributes, which control
+/// whether an empty value and `null` are coalesced for optional string, sequence or
+/// map fields, and the `[Sensitive]` attribute, for fields that hold secrets.
+#[derive(Debug, Clone, Hash, Default)]
+pub(super) struct FieldAttributes(Vec<Attribute>);
+
+impl FieldAttributes {
+    pub fn empty_as_null(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::EmptyAsNull))
+    }
+
+    pub fn null_as_empty(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::NullAsEmpty))
+    }
+
+    pub fn sensitive(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Sensitive))
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for FieldAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::EmptyAsNull => Ok(()),
+            Attribute::NullAsEmpty => Ok(()),
+            Attribute::Sensitive => Ok(()),
+            _ => bail!(format!("{:?} not supported for dictionary members", attr)),
+        })?;
+        let attrs = Self(attrs);
+        // Can't coalesce in both directions at once.
+        if attrs.empty_as_null() && attrs.null_as_empty() {
+            bail!("conflicting attributes on dictionary member");
+        }
+        Ok(attrs)
+    }
+}
+
+impl<T: TryInto<FieldAttributes, Error = anyhow::Error>> TryFrom<Option<T>> for FieldAttributes {
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
 /// Represents UDL attributes that might appear on an `interface` definition.
 #[derive(Debug, Clone, Hash, Default)]
 pub(super) struct InterfaceAttributes(Vec<Attribute>);
@@ -252,6 +631,87 @@ impl InterfaceAttributes {
             .iter()
             .any(|attr| matches!(attr, Attribute::Threadsafe))
     }
+
+    pub fn cloneable(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Clone))
+    }
+
+    pub fn releases_callbacks(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::ReleaseCallbacks))
+    }
+
+    pub fn has_source_chain(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::SourceChain))
+    }
+
+    pub fn closeable(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Closeable))
+    }
+
+    pub fn opaque_token(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::OpaqueToken))
+    }
+
+    pub fn foreign_extension(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::ForeignExtension(name) => Some(name.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub fn observable_snapshot(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Observable(name) => Some(name.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub fn events_type(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Events(name) => Some(name.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub fn service(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Service))
+    }
+
+    pub fn trait_object(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Trait))
+    }
+
+    pub fn weak_refs(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::WeakRefs))
+    }
+
+    pub fn threading(&self) -> Threading {
+        if self
+            .0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::MainThread))
+        {
+            Threading::MainThreadOnly
+        } else if self
+            .0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Blocking))
+        {
+            Threading::Blocking
+        } else {
+            Threading::Threadsafe
+        }
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for InterfaceAttributes {
@@ -263,10 +723,82 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for InterfaceAttribu
             Attribute::Enum => Ok(()),
             Attribute::Error => Ok(()),
             Attribute::Threadsafe => Ok(()),
+            Attribute::Clone => Ok(()),
+            Attribute::ReleaseCallbacks => Ok(()),
+            Attribute::SourceChain => Ok(()),
+            Attribute::Closeable => Ok(()),
+            Attribute::ForeignExtension(_) => Ok(()),
+            Attribute::Observable(_) => Ok(()),
+            Attribute::Events(_) => Ok(()),
+            Attribute::OpaqueToken => Ok(()),
+            Attribute::Service => Ok(()),
+            Attribute::Trait => Ok(()),
+            Attribute::WeakRefs => Ok(()),
+            Attribute::MainThread => Ok(()),
+            Attribute::Blocking => Ok(()),
             _ => bail!(format!("{:?} not supported for interface definition", attr)),
         })?;
-        // Can't be both `[Threadsafe]` and an `[Enum]`.
-        if attrs.len() > 1 {
+        // An `[OpaqueToken]` gets a minimal handle class, with none of the machinery that the
+        // other attributes add to an object.
+        if attrs.len() > 1
+            && attrs
+                .iter()
+                .any(|attr| matches!(attr, Attribute::OpaqueToken))
+        {
+            bail!("the [OpaqueToken] attribute can't be combined with other attributes");
+        }
+        // `[SourceChain]` only makes sense for errors, and doesn't conflict with anything else.
+        let source_chain = attrs
+            .iter()
+            .any(|attr| matches!(attr, Attribute::SourceChain));
+        if source_chain && !attrs.iter().any(|attr| attr.is_error()) {
+            bail!("the [SourceChain] attribute is only supported on errors");
+        }
+        // `[Closeable]`, `[Events]`, `[ForeignExtension]`, `[Observable]`, `[Service]`, `[Trait]`
+        // and `[WeakRefs]` work alongside the other object attributes, but only objects have a
+        // handle to close or hold weakly, a class for the extension to extend, methods for
+        // registering listeners or observers or running in the background, or a Rust trait to
+        // implement.
+        let object_extras = attrs.iter().any(|attr| {
+            matches!(
+                attr,
+                Attribute::Closeable
+                    | Attribute::Events(_)
+                    | Attribute::ForeignExtension(_)
+                    | Attribute::Observable(_)
+                    | Attribute::Service
+                    | Attribute::Trait
+                    | Attribute::WeakRefs
+            )
+        });
+        if object_extras && attrs.iter().any(|attr| attr.is_enum() || attr.is_error()) {
+            bail!("conflicting attributes on interface definition");
+        }
+        // Can only be one of `[Threadsafe]`, `[MainThread]` and `[Blocking]`, which can't go on an
+        // `[Enum]` either, and only objects can be `[Clone]` or
+        // `[ReleaseCallbacks]`. A copy of an object would still be using the callbacks passed
+        // to the original, so those two can't be combined either.
+        let (object_only, others): (Vec<_>, Vec<_>) = attrs
+            .iter()
+            .filter(|attr| {
+                !matches!(
+                    attr,
+                    Attribute::SourceChain
+                        | Attribute::Closeable
+                        | Attribute::Events(_)
+                        | Attribute::ForeignExtension(_)
+                        | Attribute::Observable(_)
+                        | Attribute::Service
+                        | Attribute::Trait
+                        | Attribute::WeakRefs
+                )
+            })
+            .partition(|attr| matches!(attr, Attribute::Clone | Attribute::ReleaseCallbacks));
+        if others.len() > 1
+            || object_only.len() > 1
+            || (!object_only.is_empty()
+                && others.iter().any(|attr| attr.is_enum() || attr.is_error()))
+        {
             bail!("conflicting attributes on interface definition");
         }
         Ok(Self(attrs))
@@ -327,7 +859,18 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ConstructorAttri
 /// Represents UDL attributes that might appear on a method.
 ///
 /// This supports the `[Throws=ErrorName]` attribute for methods that can produce
-/// an error, and the `[Self=ByArc]` attribute for methods that take `Arc<Self>` as receiver.
+/// an error, the `[Self=ByArc]` attribute for methods that take `Arc<Self>` as receiver,
+/// the `[Self=ByMut]` attribute for methods that take `&mut self` as receiver,
+/// the `[Optional]` attribute for callback interface methods that have a default,
+/// the `[Borrowed]` attribute for methods that return a view of data owned by the object,
+/// the `[Paginated]` attribute for methods that return a sequence one page at a time,
+/// the `[AlsoAsync]` attribute for methods that also get an async variant,
+/// the `[Async]` attribute for methods that are implemented by a Rust `async fn`, or for
+/// callback interface methods that return through a future,
+/// the `[Cached]` (or `[Pure]`) attribute for methods whose result the bindings keep,
+/// the `[NoCopy]` attribute for methods whose bytes the bindings get a view of,
+/// the `[Scoped]` attribute for methods that take a `Scope`,
+/// and the `[Cancellable]` attribute for async methods that are given a `CancellationToken`.
 #[derive(Debug, Clone, Hash, Default)]
 pub(super) struct MethodAttributes(Vec<Attribute>);
 
@@ -346,6 +889,58 @@ impl MethodAttributes {
             .iter()
             .any(|attr| matches!(attr, Attribute::SelfType(SelfType::ByArc)))
     }
+
+    pub(super) fn get_self_by_mut(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::SelfType(SelfType::ByMut)))
+    }
+
+    pub(super) fn is_optional(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Optional))
+    }
+
+    pub(super) fn is_borrowed(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Borrowed))
+    }
+
+    pub(super) fn is_paginated(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Paginated))
+    }
+
+    pub(super) fn is_also_async(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::AlsoAsync))
+    }
+
+    pub(super) fn is_async(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Async))
+    }
+
+    pub(super) fn is_cached(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Cached))
+    }
+
+    pub(super) fn is_no_copy(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::NoCopy))
+    }
+
+    pub(super) fn is_scoped(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Scoped))
+    }
+
+    pub(super) fn is_cancellable(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Cancellable))
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for MethodAttributes {
@@ -356,30 +951,112 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for MethodAttributes
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
             Attribute::SelfType(_) => Ok(()),
             Attribute::Throws(_) => Ok(()),
+            Attribute::Optional => Ok(()),
+            Attribute::Borrowed => Ok(()),
+            Attribute::Paginated => Ok(()),
+            Attribute::AlsoAsync => Ok(()),
+            Attribute::Async => Ok(()),
+            Attribute::Cached => Ok(()),
+            Attribute::NoCopy => Ok(()),
+            Attribute::Scoped => Ok(()),
+            Attribute::Cancellable => Ok(()),
             _ => bail!(format!("{:?} not supported for methods", attr)),
         })?;
-        Ok(Self(attrs))
-    }
-}
-
-impl<T: TryInto<MethodAttributes, Error = anyhow::Error>> TryFrom<Option<T>> for MethodAttributes {
-    type Error = anyhow::Error;
-    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
-        match value {
-            None => Ok(Default::default()),
-            Some(v) => v.try_into(),
+        if attrs
+            .iter()
+            .filter(|attr| matches!(attr, Attribute::SelfType(_)))
+            .count()
+            > 1
+        {
+            bail!("conflicting Self attributes on method");
         }
-    }
-}
-
-/// Represents the different possible types of method call receiver.
-///
-/// Actually we only support one of these right now, `[Self=ByArc]`.
-/// We might add more in future, e.g. a `[Self=ByRef]` if there are cases
-/// where we need to force the receiver to be taken by reference.
-#[derive(Debug, Clone, Hash)]
+        let attrs = Self(attrs);
+        if attrs.is_borrowed() {
+            // The view borrows from the object for as long as it lives, so the method can't be
+            // given a reference that only lasts for the call, or fail instead of returning it.
+            if attrs.get_self_by_arc() || attrs.get_self_by_mut() {
+                bail!("[Borrowed] methods must take `&self`, not [Self=ByArc] or [Self=ByMut]");
+            }
+            if attrs.get_throws_err().is_some() {
+                bail!("[Borrowed] methods cannot [Throws]");
+            }
+        }
+        if attrs.is_paginated() {
+            // The paginated variant is implemented on the struct, next to the method it calls.
+            if attrs.get_self_by_arc() || attrs.get_self_by_mut() {
+                bail!("[Paginated] methods must take `&self`, not [Self=ByArc] or [Self=ByMut]");
+            }
+            if attrs.is_borrowed() {
+                bail!("[Paginated] methods cannot be [Borrowed]");
+            }
+        }
+        if attrs.is_cached() {
+            // A method that changes the object could return something different next time, and
+            // a view would have to be kept alive for as long as the object.
+            if attrs.get_self_by_mut() {
+                bail!("[Cached] methods cannot be [Self=ByMut]");
+            }
+            if attrs.is_borrowed() {
+                bail!("[Cached] methods cannot be [Borrowed]");
+            }
+        }
+        if attrs.is_no_copy() {
+            // The caller frees the bytes when it's done with them, so nothing else can keep them.
+            if attrs.is_borrowed() {
+                bail!("[NoCopy] methods cannot be [Borrowed]");
+            }
+            if attrs.is_cached() {
+                bail!("[NoCopy] methods cannot be [Cached]");
+            }
+            if attrs.is_paginated() {
+                bail!("[NoCopy] methods cannot be [Paginated]");
+            }
+            if attrs.is_also_async() {
+                bail!("[NoCopy] methods cannot be [AlsoAsync]");
+            }
+        }
+        if attrs.is_async() {
+            // The future holds on to the object until it's done, so it can't borrow it mutably,
+            // and the method has no result to keep, view or page through until then.
+            if attrs.get_self_by_mut() {
+                bail!("[Async] methods cannot be [Self=ByMut]");
+            }
+            if attrs.is_borrowed()
+                || attrs.is_paginated()
+                || attrs.is_cached()
+                || attrs.is_no_copy()
+                || attrs.is_also_async()
+            {
+                bail!("[Async] methods cannot be [Borrowed], [Paginated], [Cached], [NoCopy] or [AlsoAsync]");
+            }
+        }
+        if attrs.is_cancellable() && !attrs.is_async() {
+            bail!("[Cancellable] methods must also be [Async]");
+        }
+        Ok(attrs)
+    }
+}
+
+impl<T: TryInto<MethodAttributes, Error = anyhow::Error>> TryFrom<Option<T>> for MethodAttributes {
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
+/// Represents the different possible types of method call receiver.
+///
+/// We support `[Self=ByArc]` and `[Self=ByMut]`; methods without this attribute
+/// take their receiver as `&self`.
+/// We might add more in future, e.g. a `[Self=ByRef]` if there are cases
+/// where we need to force the receiver to be taken by reference.
+#[derive(Debug, Clone, Hash)]
 pub(super) enum SelfType {
     ByArc, // Method receiver is `Arc<Self>`.
+    ByMut, // Method receiver is `&mut self`.
 }
 
 impl TryFrom<&weedle::attribute::IdentifierOrString<'_>> for SelfType {
@@ -388,6 +1065,7 @@ impl TryFrom<&weedle::attribute::IdentifierOrString<'_>> for SelfType {
         Ok(match nm {
             weedle::attribute::IdentifierOrString::Identifier(identifier) => match identifier.0 {
                 "ByArc" => SelfType::ByArc,
+                "ByMut" => SelfType::ByMut,
                 _ => bail!("Unsupported Self Type: {:?}", identifier.0),
             },
             weedle::attribute::IdentifierOrString::String(_) => {
@@ -399,7 +1077,8 @@ impl TryFrom<&weedle::attribute::IdentifierOrString<'_>> for SelfType {
 
 /// Represents UDL attributes that might appear on a typedef
 ///
-/// This supports the `[External="crate_name"]` and `[Wrapped]` attributes for types.
+/// This supports the `[External="crate_name"]`, `[ExternalInterface="crate_name"]`,
+/// `[Wrapped]`, `[Custom]`, `[OrderedMap]` and `[Stream]` attributes for types.
 #[derive(Debug, Clone, Hash, Default)]
 pub(super) struct TypedefAttributes(Vec<Attribute>);
 
@@ -408,17 +1087,39 @@ impl TypedefAttributes {
         self.0
             .iter()
             .find_map(|attr| match attr {
-                Attribute::External(crate_name) => Some(crate_name.clone()),
+                Attribute::External(crate_name) | Attribute::ExternalInterface(crate_name) => {
+                    Some(crate_name.clone())
+                }
                 _ => None,
             })
             .expect("must have a crate name")
     }
 
+    pub(super) fn is_external_interface(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::ExternalInterface(_)))
+    }
+
     pub(super) fn is_wrapped(&self) -> bool {
         self.0
             .iter()
             .any(|attr| matches!(attr, Attribute::Wrapped { .. }))
     }
+
+    pub(super) fn is_custom(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Custom))
+    }
+
+    pub(super) fn is_ordered_map(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::OrderedMap))
+    }
+
+    pub(super) fn is_stream(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Stream))
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for TypedefAttributes {
@@ -427,7 +1128,12 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for TypedefAttribute
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::External { .. } | Attribute::Wrapped => Ok(()),
+            Attribute::External { .. }
+            | Attribute::ExternalInterface(_)
+            | Attribute::Wrapped
+            | Attribute::Custom
+            | Attribute::OrderedMap
+            | Attribute::Stream => Ok(()),
             _ => bail!(format!("{:?} not supported for typedefs", attr)),
         })?;
         Ok(Self(attrs))
@@ -477,6 +1183,36 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_error_from() -> Result<()> {
+        let (_, node) = weedle::attribute::ExtendedAttribute::parse(
+            r#"ErrorFrom="std::num::ParseIntError, std::num::ParseFloatError""#,
+        )
+        .unwrap();
+        let attr = Attribute::try_from(&node)?;
+        assert!(matches!(attr, Attribute::ErrorFrom(types)
+            if types == vec!["std::num::ParseIntError", "std::num::ParseFloatError"]));
+
+        let (_, node) = weedle::attribute::ExtendedAttribute::parse(r#"ErrorFrom="a,""#).unwrap();
+        let err = Attribute::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[ErrorFrom] needs a comma-separated list of Rust error types"
+        );
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse(
+            r#"[ErrorFrom="std::io::Error", ErrorFrom="*"]"#,
+        )
+        .unwrap();
+        let attrs = VariantAttributes::try_from(&node)?;
+        assert_eq!(attrs.error_from(), vec!["std::io::Error", "*"]);
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[ByRef]").unwrap();
+        let err = VariantAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "ByRef not supported for enum variants");
+        Ok(())
+    }
+
     #[test]
     fn test_name() -> Result<()> {
         let (_, node) = weedle::attribute::ExtendedAttribute::parse("Name=Value").unwrap();
@@ -498,6 +1234,9 @@ mod test {
         let (_, node) = weedle::attribute::ExtendedAttribute::parse("Self=ByArc").unwrap();
         let attr = Attribute::try_from(&node)?;
         assert!(matches!(attr, Attribute::SelfType(SelfType::ByArc)));
+        let (_, node) = weedle::attribute::ExtendedAttribute::parse("Self=ByMut").unwrap();
+        let attr = Attribute::try_from(&node)?;
+        assert!(matches!(attr, Attribute::SelfType(SelfType::ByMut)));
         let (_, node) = weedle::attribute::ExtendedAttribute::parse("Self=ByMistake").unwrap();
         let err = Attribute::try_from(&node).unwrap_err();
         assert_eq!(err.to_string(), "Unsupported Self Type: \"ByMistake\"");
@@ -565,6 +1304,76 @@ mod test {
         assert!(matches!(attrs.get_throws_err(), None));
     }
 
+    #[test]
+    fn test_batchable_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Batchable, Throws=Error]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_batchable());
+        assert!(matches!(attrs.get_throws_err(), Some("Error")));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Throws=Error]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(!attrs.is_batchable());
+    }
+
+    #[test]
+    fn test_invokable_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Invokable, Batchable]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_invokable());
+        assert!(attrs.is_batchable());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Batchable]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(!attrs.is_invokable());
+        assert!(!attrs.is_scoped());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Scoped, Throws=Error]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_scoped());
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_scoped());
+    }
+
+    #[test]
+    fn test_async_functions() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Async, Throws=Error]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_async());
+        assert!(!attrs.is_batchable());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Async, Batchable]").unwrap();
+        let err = FunctionAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Async] functions cannot be [Batchable] or [Invokable]"
+        );
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Async, Cancellable]").unwrap();
+        assert!(FunctionAttributes::try_from(&node)
+            .unwrap()
+            .is_cancellable());
+        assert!(MethodAttributes::try_from(&node).unwrap().is_cancellable());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Cancellable]").unwrap();
+        let err = FunctionAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Cancellable] functions must also be [Async]"
+        );
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Cancellable] methods must also be [Async]"
+        );
+    }
+
     #[test]
     fn test_other_attributes_not_supported_for_functions() {
         let (_, node) =
@@ -603,6 +1412,109 @@ mod test {
         let attrs = MethodAttributes::try_from(&node).unwrap();
         assert!(attrs.get_self_by_arc());
         assert!(attrs.get_throws_err().is_none());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Self=ByMut, Throws=Error]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(attrs.get_self_by_mut());
+        assert!(!attrs.get_self_by_arc());
+        assert!(attrs.get_throws_err().is_some());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Self=ByMut, Self=ByArc]").unwrap();
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "conflicting Self attributes on method");
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Optional]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_optional());
+        assert!(attrs.get_throws_err().is_none());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Borrowed]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_borrowed());
+        assert!(!attrs.is_optional());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Borrowed, Self=ByArc]").unwrap();
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Borrowed] methods must take `&self`, not [Self=ByArc] or [Self=ByMut]"
+        );
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Borrowed, Throws=Error]").unwrap();
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "[Borrowed] methods cannot [Throws]");
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Paginated, Throws=Error]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_paginated());
+        assert!(attrs.get_throws_err().is_some());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Paginated, Self=ByMut]").unwrap();
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Paginated] methods must take `&self`, not [Self=ByArc] or [Self=ByMut]"
+        );
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[AlsoAsync, Throws=Error]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_also_async());
+        assert!(attrs.get_throws_err().is_some());
+        assert!(!attrs.is_paginated());
+        assert!(!attrs.is_async());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Async, Throws=Error]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_async());
+        assert!(attrs.get_throws_err().is_some());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Async, Self=ByMut]").unwrap();
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "[Async] methods cannot be [Self=ByMut]");
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Async, Cached]").unwrap();
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Async] methods cannot be [Borrowed], [Paginated], [Cached], [NoCopy] or [AlsoAsync]"
+        );
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Pure]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_cached());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Cached, Self=ByMut]").unwrap();
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "[Cached] methods cannot be [Self=ByMut]");
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[NoCopy]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_no_copy());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[NoCopy, Throws=Error]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_no_copy());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[NoCopy, Cached]").unwrap();
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "[NoCopy] methods cannot be [Cached]");
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[NoCopy, AlsoAsync]").unwrap();
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "[NoCopy] methods cannot be [AlsoAsync]");
     }
 
     #[test]
@@ -664,6 +1576,50 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_field_attributes() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[EmptyAsNull]").unwrap();
+        let attrs = FieldAttributes::try_from(&node).unwrap();
+        assert!(attrs.empty_as_null());
+        assert!(!attrs.null_as_empty());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[NullAsEmpty]").unwrap();
+        let attrs = FieldAttributes::try_from(&node).unwrap();
+        assert!(!attrs.empty_as_null());
+        assert!(attrs.null_as_empty());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = FieldAttributes::try_from(&node).unwrap();
+        assert!(!attrs.empty_as_null());
+        assert!(!attrs.null_as_empty());
+        assert!(!attrs.sensitive());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Sensitive, EmptyAsNull]").unwrap();
+        let attrs = FieldAttributes::try_from(&node).unwrap();
+        assert!(attrs.sensitive());
+        assert!(attrs.empty_as_null());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[EmptyAsNull, NullAsEmpty]").unwrap();
+        let err = FieldAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "conflicting attributes on dictionary member"
+        );
+    }
+
+    #[test]
+    fn test_other_attributes_not_supported_for_fields() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[EmptyAsNull, ByRef]").unwrap();
+        let err = FieldAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ByRef not supported for dictionary members"
+        );
+    }
+
     #[test]
     fn test_threadsafe_attribute() {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Threadsafe]").unwrap();
@@ -698,6 +1654,298 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_clone_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Clone]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.cloneable());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.cloneable());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Threadsafe, Clone]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.cloneable());
+        assert!(attrs.threadsafe());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Enum, Clone]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "conflicting attributes on interface definition"
+        );
+    }
+
+    #[test]
+    fn test_release_callbacks_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[ReleaseCallbacks]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.releases_callbacks());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.releases_callbacks());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[ReleaseCallbacks, Clone]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "conflicting attributes on interface definition"
+        );
+    }
+
+    #[test]
+    fn test_foreign_extension_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[ForeignExtension=CounterExt]")
+                .unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.foreign_extension(), Some("CounterExt"));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse(
+            "[Closeable, ForeignExtension=\"CounterExt\"]",
+        )
+        .unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.closeable());
+        assert_eq!(attrs.foreign_extension(), Some("CounterExt"));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Clone]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.foreign_extension(), None);
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Error, ForeignExtension=Ext]")
+                .unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "conflicting attributes on interface definition"
+        );
+    }
+
+    #[test]
+    fn test_observable_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Observable=ProfileState]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.observable_snapshot(), Some("ProfileState"));
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Closeable, Observable=ProfileState]")
+                .unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.closeable());
+        assert_eq!(attrs.observable_snapshot(), Some("ProfileState"));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Clone]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.observable_snapshot(), None);
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Enum, Observable=ProfileState]")
+                .unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "conflicting attributes on interface definition"
+        );
+    }
+
+    #[test]
+    fn test_events_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Events=DownloadEvent]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.events_type(), Some("DownloadEvent"));
+        assert_eq!(attrs.observable_snapshot(), None);
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Clone, Events=DownloadEvent]")
+                .unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.events_type(), Some("DownloadEvent"));
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Error, Events=DownloadEvent]")
+                .unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "conflicting attributes on interface definition"
+        );
+    }
+
+    #[test]
+    fn test_service_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Service, Closeable]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.service());
+        assert!(attrs.closeable());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Clone]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.service());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Error, Service]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "conflicting attributes on interface definition"
+        );
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Service]").unwrap();
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Service not supported for methods");
+    }
+
+    #[test]
+    fn test_trait_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Trait, Closeable]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.trait_object());
+        assert!(attrs.closeable());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Clone]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.trait_object());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Enum, Trait]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "conflicting attributes on interface definition"
+        );
+    }
+
+    #[test]
+    fn test_weak_refs_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[WeakRefs, Clone]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.weak_refs());
+        assert!(attrs.cloneable());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Closeable]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.weak_refs());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Error, WeakRefs]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "conflicting attributes on interface definition"
+        );
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[WeakRefs]").unwrap();
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "WeakRefs not supported for methods");
+    }
+
+    #[test]
+    fn test_threading_attributes() {
+        let threading = |attrs: &str| {
+            let (_, node) = weedle::attribute::ExtendedAttributeList::parse(attrs).unwrap();
+            InterfaceAttributes::try_from(&node).map(|attrs| attrs.threading())
+        };
+        assert_eq!(threading("[Clone]").unwrap(), Threading::Threadsafe);
+        assert_eq!(threading("[Threadsafe]").unwrap(), Threading::Threadsafe);
+        assert_eq!(
+            threading("[MainThread, Closeable]").unwrap(),
+            Threading::MainThreadOnly
+        );
+        assert_eq!(threading("[Blocking]").unwrap(), Threading::Blocking);
+        assert_eq!(
+            threading("[MainThread, Blocking]").unwrap_err().to_string(),
+            "conflicting attributes on interface definition"
+        );
+        assert_eq!(
+            threading("[Enum, MainThread]").unwrap_err().to_string(),
+            "conflicting attributes on interface definition"
+        );
+    }
+
+    #[test]
+    fn test_closeable_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Closeable]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.closeable());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Clone]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.closeable());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Closeable, ReleaseCallbacks]")
+                .unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.closeable());
+        assert!(attrs.releases_callbacks());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Enum, Closeable]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "conflicting attributes on interface definition"
+        );
+    }
+
+    #[test]
+    fn test_opaque_token_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[OpaqueToken]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.opaque_token());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Closeable]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.opaque_token());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[OpaqueToken, Clone]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the [OpaqueToken] attribute can't be combined with other attributes"
+        );
+    }
+
+    #[test]
+    fn test_source_chain_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Error, SourceChain]").unwrap();
+        let attrs = EnumAttributes::try_from(&node).unwrap();
+        assert!(attrs.contains_error_attr());
+        assert!(attrs.has_source_chain());
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.contains_error_attr());
+        assert!(attrs.has_source_chain());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Error]").unwrap();
+        let attrs = EnumAttributes::try_from(&node).unwrap();
+        assert!(!attrs.has_source_chain());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[SourceChain]").unwrap();
+        let err = EnumAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the [SourceChain] attribute is only supported on errors"
+        );
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the [SourceChain] attribute is only supported on errors"
+        );
+    }
+
     #[test]
     fn test_other_attributes_not_supported_for_interfaces() {
         let (_, node) =
@@ -709,17 +1957,84 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_namespace_attributes() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Parent=example]").unwrap();
+        let attrs = NamespaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_parent(), Some("example"));
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Parent=\"example.sync\"]").unwrap();
+        let attrs = NamespaceAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_parent(), Some("example.sync"));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Clone]").unwrap();
+        let err = NamespaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Clone not supported for namespaces");
+    }
+
+    #[test]
+    fn test_callback_interface_attributes() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Direct]").unwrap();
+        let attrs = CallbackInterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_direct());
+        assert!(!attrs.is_ordered());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Ordered]").unwrap();
+        let attrs = CallbackInterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_ordered());
+        assert!(!attrs.is_versioned());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Ordered, Versioned]").unwrap();
+        let attrs = CallbackInterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_ordered());
+        assert!(attrs.is_versioned());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Clone]").unwrap();
+        let err = CallbackInterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Clone not supported for callback interface definition"
+        );
+    }
+
     #[test]
     fn test_typedef_attribute() {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Wrapped]").unwrap();
         let attrs = TypedefAttributes::try_from(&node).unwrap();
         assert!(attrs.is_wrapped());
+        assert!(!attrs.is_custom());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Custom]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_custom());
+        assert!(!attrs.is_wrapped());
 
         let (_, node) =
             weedle::attribute::ExtendedAttributeList::parse("[External=crate_name]").unwrap();
         let attrs = TypedefAttributes::try_from(&node).unwrap();
         assert!(!attrs.is_wrapped());
+        assert!(!attrs.is_external_interface());
         assert_eq!(attrs.get_crate_name(), "crate_name");
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[ExternalInterface=\"other-crate\"]")
+                .unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_external_interface());
+        assert_eq!(attrs.get_crate_name(), "other-crate");
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[OrderedMap]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_ordered_map());
+        assert!(!attrs.is_wrapped());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Stream]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_stream());
+        assert!(!attrs.is_ordered_map());
     }
 
     #[test]
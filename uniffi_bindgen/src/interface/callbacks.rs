@@ -32,11 +32,44 @@
 //! assert_eq!(callback.methods()[0].name(), "hello");
 //! # Ok::<(), anyhow::Error>(())
 //! ```
+//!
+//! Callback interfaces with the `[Direct]` attribute additionally get a C function pointer
+//! for each method that only takes and returns primitive types, which the foreign language
+//! registers through [`CallbackInterface::ffi_init_direct_callbacks`]. Calls to those methods
+//! pass their arguments straight through the function pointer, rather than serializing them
+//! into a `RustBuffer` for the shared `ForeignCallback`, which remains in use for every
+//! other method.
+//!
+//! Calls to callback interfaces with the `[Ordered]` attribute are queued up, and delivered one
+//! at a time, in the order they were made, on a thread of their own for each foreign-language
+//! object. Since a call returns before it's delivered, their methods can't return anything.
+//!
+//! Methods of callback interfaces can be `[Throws=Error]`. The foreign-language implementation
+//! sends the error that it throws back to Rust, where the method returns it as the `Err` of
+//! its `Result`. The buffer that such a method returns starts with a byte that says whether it
+//! returned or threw, followed by the value it returned or the error it threw.
+//!
+//! Methods of callback interfaces can be `[Async]`, for foreign implementations that finish
+//! their work later, in a coroutine or a task. The method of the Rust trait returns a
+//! `uniffi::CallbackFuture`, which Rust code can `.await` or `wait()` for. The proxy adds a
+//! completion handle to the end of the arguments, and the foreign language passes it, with what
+//! the method would have returned if it weren't async, to
+//! [`ComponentInterface::ffi_callback_future_complete`] once the method has finished.
+//!
+//! Callback interfaces with the `[Versioned]` attribute answer a query from Rust, when each
+//! foreign-language object is passed in, with the [`CallbackInterface::version`] of the
+//! interface that the bindings were generated from and the names of the methods they know
+//! about. The Rust proxy then calls each method by name, rather than by its position in the
+//! UDL, so that an implementation from an older version of the interface doesn't receive calls
+//! meant for other methods.
 
+use std::collections::hash_map::DefaultHasher;
+use std::convert::TryFrom;
 use std::hash::{Hash, Hasher};
 
 use anyhow::{bail, Result};
 
+use super::attributes::CallbackInterfaceAttributes;
 use super::ffi::{FFIArgument, FFIFunction, FFIType};
 use super::object::Method;
 use super::types::{IterTypes, Type, TypeIterator};
@@ -47,6 +80,12 @@ pub struct CallbackInterface {
     pub(super) name: String,
     pub(super) methods: Vec<Method>,
     pub(super) ffi_init_callback: FFIFunction,
+    pub(super) is_direct: bool,
+    pub(super) is_ordered: bool,
+    pub(super) is_versioned: bool,
+    // The function pointer type for each method in `direct_methods()`, in the same order.
+    pub(super) ffi_direct_callbacks: Vec<FFIFunction>,
+    pub(super) ffi_init_direct_callbacks: Option<FFIFunction>,
 }
 
 impl CallbackInterface {
@@ -55,6 +94,11 @@ impl CallbackInterface {
             name,
             methods: Default::default(),
             ffi_init_callback: Default::default(),
+            is_direct: false,
+            is_ordered: false,
+            is_versioned: false,
+            ffi_direct_callbacks: Default::default(),
+            ffi_init_direct_callbacks: None,
         }
     }
 
@@ -66,6 +110,14 @@ impl CallbackInterface {
         Type::CallbackInterface(self.name.clone())
     }
 
+    pub fn has_optional_methods(&self) -> bool {
+        self.methods.iter().any(|meth| meth.is_optional())
+    }
+
+    pub fn has_async_methods(&self) -> bool {
+        self.methods.iter().any(|meth| meth.is_async())
+    }
+
     pub fn methods(&self) -> Vec<&Method> {
         self.methods.iter().collect()
     }
@@ -74,8 +126,70 @@ impl CallbackInterface {
         &self.ffi_init_callback
     }
 
+    pub fn is_direct(&self) -> bool {
+        self.is_direct
+    }
+
+    /// Whether calls are delivered in order on a thread of their own, see `[Ordered]`.
+    pub fn is_ordered(&self) -> bool {
+        self.is_ordered
+    }
+
+    /// Whether the foreign-language objects say which methods they have, see `[Versioned]`.
+    pub fn is_versioned(&self) -> bool {
+        self.is_versioned
+    }
+
+    /// A checksum of the interface's name and methods, which changes whenever they do.
+    ///
+    /// The bindings of a `[Versioned]` callback interface report this back to Rust, so
+    /// that it can tell whether they were generated from the same version of the interface.
+    pub fn version(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.name.hash(&mut hasher);
+        self.methods.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// The methods that are called through a function pointer of their own, rather than
+    /// the `ForeignCallback`. This is empty unless the interface has the `[Direct]` attribute.
+    pub fn direct_methods(&self) -> Vec<&Method> {
+        if !self.is_direct {
+            return Vec::new();
+        }
+        self.methods
+            .iter()
+            .filter(|meth| takes_only_primitives(meth))
+            .collect()
+    }
+
+    /// The signature of the function pointer used to call a method directly, if it is one
+    /// of the `direct_methods()`.
+    ///
+    /// This isn't an FFI function that anyone exports, it only describes the foreign-language
+    /// function that is passed to Rust, whose first argument is the handle of the object.
+    pub fn ffi_direct_callback(&self, meth: &Method) -> Option<&FFIFunction> {
+        self.direct_methods()
+            .iter()
+            .position(|m| m.name() == meth.name())
+            .map(|i| &self.ffi_direct_callbacks[i])
+    }
+
+    /// The signatures of the function pointers for all the `direct_methods()`.
+    pub fn ffi_direct_callbacks(&self) -> Vec<&FFIFunction> {
+        self.ffi_direct_callbacks.iter().collect()
+    }
+
+    /// The function that registers the function pointers for the `direct_methods()`,
+    /// one argument for each.
+    pub fn ffi_init_direct_callbacks(&self) -> Option<&FFIFunction> {
+        self.ffi_init_direct_callbacks.as_ref()
+    }
+
     pub fn iter_ffi_function_definitions(&self) -> Vec<FFIFunction> {
-        vec![self.ffi_init_callback.clone()]
+        let mut funcs = vec![self.ffi_init_callback.clone()];
+        funcs.extend(self.ffi_init_direct_callbacks.iter().cloned());
+        funcs
     }
 
     pub(super) fn derive_ffi_funcs(&mut self, ci_prefix: &str) {
@@ -85,9 +199,81 @@ impl CallbackInterface {
             type_: FFIType::ForeignCallback,
         }];
         self.ffi_init_callback.return_type = None;
+
+        if !self.is_direct {
+            return;
+        }
+        let callbacks: Vec<FFIFunction> = self
+            .direct_methods()
+            .iter()
+            .map(|meth| {
+                let mut arguments = vec![FFIArgument {
+                    name: "handle".to_string(),
+                    type_: FFIType::UInt64,
+                }];
+                arguments.extend(meth.arguments().iter().map(|arg| FFIArgument {
+                    name: arg.name().to_string(),
+                    type_: FFIType::from(&arg.type_()),
+                }));
+                FFIFunction {
+                    name: format!(
+                        "ForeignCallback_{}_{}_{}",
+                        ci_prefix,
+                        self.name,
+                        meth.name()
+                    ),
+                    arguments,
+                    return_type: meth.return_type().map(FFIType::from),
+                }
+            })
+            .collect();
+        let arguments = self
+            .direct_methods()
+            .iter()
+            .zip(callbacks.iter())
+            .map(|(meth, callback)| FFIArgument {
+                name: meth.name().to_string(),
+                type_: FFIType::ForeignMethodCallback(callback.name.clone()),
+            })
+            .collect();
+        self.ffi_init_direct_callbacks = Some(FFIFunction {
+            name: format!("ffi_{}_{}_init_direct_callbacks", ci_prefix, self.name),
+            arguments,
+            return_type: None,
+        });
+        self.ffi_direct_callbacks = callbacks;
     }
 }
 
+// Whether the arguments and return value of a method can be passed as they are through
+// a C function pointer. Errors are returned in a `RustBuffer`, so throwing methods can't be,
+// and nor can `[Async]` ones, which return through their completion handle.
+fn takes_only_primitives(meth: &Method) -> bool {
+    let is_primitive = |type_: &Type| {
+        matches!(
+            type_,
+            Type::UInt8
+                | Type::Int8
+                | Type::UInt16
+                | Type::Int16
+                | Type::UInt32
+                | Type::Int32
+                | Type::UInt64
+                | Type::Int64
+                | Type::Float32
+                | Type::Float64
+                | Type::Boolean
+        )
+    };
+    meth.throws().is_none()
+        && !meth.is_async()
+        && meth
+            .arguments()
+            .iter()
+            .all(|arg| is_primitive(&arg.type_()))
+        && meth.return_type().map_or(true, is_primitive)
+}
+
 impl IterTypes for CallbackInterface {
     fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(self.methods.iter().map(IterTypes::iter_types).flatten())
@@ -104,22 +290,82 @@ impl Hash for CallbackInterface {
         //    avoids a weird circular depenendency in the calculation.
         self.name.hash(state);
         self.methods.hash(state);
+        self.is_direct.hash(state);
+        self.is_ordered.hash(state);
+        self.is_versioned.hash(state);
     }
 }
 
 impl APIConverter<CallbackInterface> for weedle::CallbackInterfaceDefinition<'_> {
     fn convert(&self, ci: &mut ComponentInterface) -> Result<CallbackInterface> {
-        if self.attributes.is_some() {
-            bail!("callback interface attributes are not supported yet");
-        }
+        let attributes = CallbackInterfaceAttributes::try_from(self.attributes.as_ref())?;
         if self.inheritance.is_some() {
             bail!("callback interface inheritence is not supported");
         }
         let mut object = CallbackInterface::new(self.identifier.0.to_string());
+        object.is_direct = attributes.is_direct();
+        object.is_ordered = attributes.is_ordered();
+        object.is_versioned = attributes.is_versioned();
+        if object.is_direct && object.is_ordered {
+            bail!(
+                "Callback interface \"{}\" can't be both [Direct] and [Ordered]",
+                object.name
+            )
+        }
         for member in &self.members.body {
             match member {
                 weedle::interface::InterfaceMember::Operation(t) => {
                     let mut method: Method = t.convert(ci)?;
+                    if method.is_borrowed() {
+                        bail!("[Borrowed] is only supported on interface methods")
+                    }
+                    if method.is_paginated() {
+                        bail!("[Paginated] is only supported on interface methods")
+                    }
+                    if method.is_also_async() {
+                        bail!("[AlsoAsync] is only supported on interface methods")
+                    }
+                    if method.is_cached() {
+                        bail!("[Cached] is only supported on interface methods")
+                    }
+                    if method.is_no_copy() {
+                        bail!("[NoCopy] is only supported on interface methods")
+                    }
+                    if method.is_scoped() {
+                        bail!("[Scoped] is only supported on functions and interface methods")
+                    }
+                    if method.is_cancellable() {
+                        bail!("[Cancellable] is only supported on functions and interface methods")
+                    }
+                    if method.is_optional()
+                        && method.return_type().is_some()
+                        && method.default_return_value().is_none()
+                    {
+                        bail!(
+                            "[Optional] callback method \"{}\" must return nothing, or a type with an empty value to return by default",
+                            method.name()
+                        )
+                    }
+                    if method.is_async() && method.is_optional() {
+                        bail!(
+                            "[Async] callback method \"{}\" can't be [Optional]",
+                            method.name()
+                        )
+                    }
+                    if object.is_ordered && method.is_async() {
+                        bail!(
+                            "[Ordered] callback method \"{}\" can't be [Async], because it's delivered after the call returns",
+                            method.name()
+                        )
+                    }
+                    if object.is_ordered
+                        && (method.return_type().is_some() || method.throws().is_some())
+                    {
+                        bail!(
+                            "[Ordered] callback method \"{}\" can't return a value or throw, because it's delivered after the call returns",
+                            method.name()
+                        )
+                    }
                     method.object_name.push_str(object.name.as_str());
                     object.methods.push(method);
                 }
@@ -129,6 +375,12 @@ impl APIConverter<CallbackInterface> for weedle::CallbackInterfaceDefinition<'_>
                 ),
             }
         }
+        if object.is_direct && object.direct_methods().is_empty() {
+            bail!(
+                "[Direct] has no effect on \"{}\", because none of its methods take and return only primitive types",
+                object.name
+            )
+        }
         Ok(object)
     }
 }
@@ -136,6 +388,7 @@ impl APIConverter<CallbackInterface> for weedle::CallbackInterfaceDefinition<'_>
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::interface::Literal;
 
     #[test]
     fn test_empty_interface() {
@@ -179,4 +432,320 @@ mod test {
         assert_eq!(callbacks_two.methods()[0].name(), "two");
         assert_eq!(callbacks_two.methods()[1].name(), "too");
     }
+
+    #[test]
+    fn test_optional_methods() {
+        const UDL: &str = r#"
+            namespace test{};
+            callback interface Listener {
+                void on_start();
+                [Optional]
+                void on_progress(u32 percent);
+                [Optional]
+                u64 count();
+                [Optional]
+                sequence<string>? tags();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let cbi = ci.get_callback_interface_definition("Listener").unwrap();
+        assert!(cbi.has_optional_methods());
+        let methods = cbi.methods();
+        assert!(!methods[0].is_optional());
+        assert!(methods[1].is_optional());
+        assert!(methods[1].default_return_value().is_none());
+        assert!(matches!(
+            methods[2].default_return_value(),
+            Some(Literal::UInt(0, _, Type::UInt64))
+        ));
+        assert!(matches!(
+            methods[3].default_return_value(),
+            Some(Literal::Null)
+        ));
+
+        const UDL2: &str = r#"
+            namespace test{};
+            dictionary Point { u32 x; };
+            callback interface Listener {
+                [Optional]
+                Point origin();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Optional] callback method \"origin\" must return nothing, or a type with an empty value to return by default"
+        );
+
+        const UDL3: &str = r#"
+            namespace test{};
+            interface Counter {
+                [Optional]
+                void reset();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Optional] is only supported on callback interface methods"
+        );
+    }
+
+    #[test]
+    fn test_direct_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Direct]
+            callback interface Renderer {
+                boolean draw(u32 x, f64 scale);
+                string describe();
+                void tick();
+            };
+            callback interface Listener {
+                void on_event(i32 count);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let cbi = ci.get_callback_interface_definition("Renderer").unwrap();
+        assert!(cbi.is_direct());
+        let direct: Vec<&str> = cbi.direct_methods().iter().map(|m| m.name()).collect();
+        assert_eq!(direct, vec!["draw", "tick"]);
+
+        let draw = cbi.ffi_direct_callback(cbi.methods()[0]).unwrap();
+        assert!(draw.name().starts_with("ForeignCallback_test_"));
+        assert!(draw.name().ends_with("_Renderer_draw"));
+        let arg_types: Vec<FFIType> = draw.arguments().iter().map(|a| a.type_()).collect();
+        assert_eq!(
+            arg_types,
+            vec![FFIType::UInt64, FFIType::UInt32, FFIType::Float64]
+        );
+        assert_eq!(draw.return_type(), Some(&FFIType::Int8));
+        assert!(cbi.ffi_direct_callback(cbi.methods()[1]).is_none());
+
+        let init = cbi.ffi_init_direct_callbacks().unwrap();
+        assert!(init.name().ends_with("_Renderer_init_direct_callbacks"));
+        assert_eq!(init.arguments().len(), 2);
+        assert_eq!(
+            init.arguments()[0].type_(),
+            FFIType::ForeignMethodCallback(draw.name().to_string())
+        );
+        assert_eq!(cbi.iter_ffi_function_definitions().len(), 2);
+
+        let listener = ci.get_callback_interface_definition("Listener").unwrap();
+        assert!(!listener.is_direct());
+        assert!(listener.direct_methods().is_empty());
+        assert!(listener.ffi_init_direct_callbacks().is_none());
+
+        const UDL2: &str = r#"
+            namespace test{};
+            [Direct]
+            callback interface Logger {
+                void log(string message);
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Direct] has no effect on \"Logger\", because none of its methods take and return only primitive types"
+        );
+
+        const UDL3: &str = r#"
+            namespace test{};
+            [Clone]
+            callback interface Logger {
+                void log(string message);
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Clone not supported for callback interface definition"
+        );
+    }
+
+    #[test]
+    fn test_ordered_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Ordered]
+            callback interface Listener {
+                void on_event(string name, u32 sequence);
+                void on_done();
+            };
+            callback interface Other {
+                void on_event(string name);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert!(ci
+            .get_callback_interface_definition("Listener")
+            .unwrap()
+            .is_ordered());
+        assert!(!ci
+            .get_callback_interface_definition("Other")
+            .unwrap()
+            .is_ordered());
+
+        const UDL2: &str = r#"
+            namespace test{};
+            [Ordered]
+            callback interface Listener {
+                boolean on_event(string name);
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Ordered] callback method \"on_event\" can't return a value or throw, because it's delivered after the call returns"
+        );
+
+        const UDL3: &str = r#"
+            namespace test{};
+            [Direct, Ordered]
+            callback interface Listener {
+                void on_event(u32 count);
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Callback interface \"Listener\" can't be both [Direct] and [Ordered]"
+        );
+    }
+
+    #[test]
+    fn test_versioned_attribute() {
+        let version = |udl: &str| {
+            let ci = ComponentInterface::from_webidl(udl).unwrap();
+            let cbi = ci.get_callback_interface_definition("Listener").unwrap();
+            assert!(cbi.is_versioned());
+            cbi.version()
+        };
+        let v1 = version(
+            r#"
+            namespace test{};
+            [Versioned]
+            callback interface Listener {
+                void on_start(string task);
+            };
+        "#,
+        );
+        let v2 = version(
+            r#"
+            namespace test{};
+            [Versioned]
+            callback interface Listener {
+                void on_start(string task);
+                [Optional]
+                void on_progress(u32 percent);
+            };
+        "#,
+        );
+        assert_ne!(v1, v2);
+        assert_eq!(
+            v1,
+            version(
+                r#"
+                namespace other{};
+                [Versioned]
+                callback interface Listener {
+                    void on_start(string task);
+                };
+            "#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_throwing_methods() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Error]
+            enum StoreError { "NotFound", "Full" };
+            [Error]
+            enum OtherError { "Oops" };
+            callback interface Store {
+                [Throws=StoreError]
+                string get(string key);
+                [Throws=StoreError]
+                void put(string key, string value);
+                u32 count();
+            };
+            interface Cache {
+                [Throws=OtherError]
+                void clear();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let cbi = ci.get_callback_interface_definition("Store").unwrap();
+        let methods = cbi.methods();
+        assert_eq!(methods[0].throws(), Some("StoreError"));
+        assert_eq!(
+            methods[1].throws_type(),
+            Some(Type::Error("StoreError".into()))
+        );
+        assert!(methods[2].throws().is_none());
+        assert!(ci.is_thrown_by_callback_interface("StoreError"));
+        // Errors thrown by the methods of objects are only ever sent from Rust.
+        assert!(!ci.is_thrown_by_callback_interface("OtherError"));
+    }
+
+    #[test]
+    fn test_async_methods() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Error]
+            enum FetchError { "Offline" };
+            [Direct]
+            callback interface Fetcher {
+                [Async, Throws=FetchError]
+                string fetch(string url);
+                [Async]
+                u32 size(u32 id);
+                u32 count();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let cbi = ci.get_callback_interface_definition("Fetcher").unwrap();
+        assert!(cbi.has_async_methods());
+        assert!(cbi.methods()[0].is_async());
+        assert!(!cbi.methods()[2].is_async());
+        // Async methods return through their completion handle, not a function pointer.
+        let direct: Vec<_> = cbi.direct_methods().iter().map(|m| m.name()).collect();
+        assert_eq!(direct, vec!["count"]);
+        let complete = ci.ffi_callback_future_complete().unwrap();
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .iter()
+            .any(|f| f.name() == complete.name()));
+
+        let ci = ComponentInterface::from_webidl(
+            "namespace test{}; callback interface Listener { void on_event(); };",
+        )
+        .unwrap();
+        assert!(ci.ffi_callback_future_complete().is_none());
+
+        let err = ComponentInterface::from_webidl(
+            "namespace test{}; [Ordered] callback interface Log { [Async] void write(string line); };",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Ordered] callback method \"write\" can't be [Async], because it's delivered after the call returns"
+        );
+        let err = ComponentInterface::from_webidl(
+            "namespace test{}; callback interface Log { [Async, Optional] void flush(); };",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Async] callback method \"flush\" can't be [Optional]"
+        );
+        // Interface methods can be `[Async]` too, and are implemented by an `async fn`.
+        let ci = ComponentInterface::from_webidl(
+            "namespace test{}; interface Log { [Async] void flush(); };",
+        )
+        .unwrap();
+        assert!(ci.get_object_definition("Log").unwrap().methods()[0].is_async());
+    }
 }
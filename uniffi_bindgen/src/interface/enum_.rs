@@ -76,8 +76,11 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
+use std::convert::TryFrom;
+
 use anyhow::{bail, Result};
 
+use super::attributes::{InterfaceAttributes, VariantAttributes};
 use super::record::Field;
 use super::types::{IterTypes, Type, TypeIterator};
 use super::{APIConverter, ComponentInterface};
@@ -151,22 +154,32 @@ impl APIConverter<Enum> for weedle::InterfaceDefinition<'_> {
         if self.inheritance.is_some() {
             bail!("interface inheritence is not supported for enum interfaces");
         }
-        // We don't need to check `self.attributes` here; if calling code has dispatched
-        // to this impl then we already know there was an `[Enum]` attribute.
+        // If calling code has dispatched to this impl then we already know there was an
+        // `[Enum]` or an `[Error]` attribute.
+        let variants = self
+            .members
+            .body
+            .iter()
+            .map::<Result<Variant>, _>(|member| match member {
+                weedle::interface::InterfaceMember::Operation(t) => Ok(t.convert(ci)?),
+                _ => bail!(
+                    "interface member type {:?} not supported in enum interface",
+                    member
+                ),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let attrs = InterfaceAttributes::try_from(self.attributes.as_ref())?;
+        if !attrs.contains_error_attr() {
+            if let Some(variant) = variants.iter().find(|v| !v.error_from.is_empty()) {
+                bail!(
+                    "[ErrorFrom] is only supported on the variants of errors, not \"{}\"",
+                    variant.name
+                );
+            }
+        }
         Ok(Enum {
             name: self.identifier.0.to_string(),
-            variants: self
-                .members
-                .body
-                .iter()
-                .map::<Result<Variant>, _>(|member| match member {
-                    weedle::interface::InterfaceMember::Operation(t) => Ok(t.convert(ci)?),
-                    _ => bail!(
-                        "interface member type {:?} not supported in enum interface",
-                        member
-                    ),
-                })
-                .collect::<Result<Vec<_>>>()?,
+            variants,
             // Enums declared using the `[Enum] interface` syntax might have variants with fields.
             flat: false,
         })
@@ -180,6 +193,8 @@ impl APIConverter<Enum> for weedle::InterfaceDefinition<'_> {
 pub struct Variant {
     pub(super) name: String,
     pub(super) fields: Vec<Field>,
+    // The Rust error types that convert into this variant, from `[ErrorFrom]`.
+    pub(super) error_from: Vec<String>,
 }
 
 impl Variant {
@@ -193,8 +208,27 @@ impl Variant {
     pub fn has_fields(&self) -> bool {
         !self.fields.is_empty()
     }
+
+    /// The Rust error types that the scaffolding converts into this variant of an error,
+    /// not including those of the catch-all variant.
+    pub fn error_from(&self) -> Vec<&str> {
+        self.error_from
+            .iter()
+            .map(String::as_str)
+            .filter(|t| *t != ERROR_FROM_ANY)
+            .collect()
+    }
+
+    /// Whether this is the catch-all variant of an error, which any `anyhow::Error` or boxed
+    /// error converts into.
+    pub fn is_error_catch_all(&self) -> bool {
+        self.error_from.iter().any(|t| t == ERROR_FROM_ANY)
+    }
 }
 
+/// The type that marks the catch-all variant in `[ErrorFrom]`.
+pub(super) const ERROR_FROM_ANY: &str = "*";
+
 impl IterTypes for Variant {
     fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(self.fields.iter().map(IterTypes::iter_types).flatten())
@@ -225,6 +259,7 @@ impl APIConverter<Variant> for weedle::interface::OperationInterfaceMember<'_> {
                 _ => bail!("enum interface members must have plain identifers as names"),
             }
         };
+        let attrs = VariantAttributes::try_from(self.attributes.as_ref())?;
         Ok(Variant {
             name,
             fields: self
@@ -234,6 +269,7 @@ impl APIConverter<Variant> for weedle::interface::OperationInterfaceMember<'_> {
                 .iter()
                 .map(|arg| arg.convert(ci))
                 .collect::<Result<Vec<_>>>()?,
+            error_from: attrs.error_from(),
         })
     }
 }
@@ -82,8 +82,12 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
-use anyhow::Result;
+use std::collections::HashSet;
+use std::convert::TryFrom;
 
+use anyhow::{bail, Result};
+
+use super::attributes::{EnumAttributes, InterfaceAttributes};
 use super::enum_::{Enum, Variant};
 use super::types::{IterTypes, Type, TypeIterator};
 use super::{APIConverter, ComponentInterface};
@@ -98,6 +102,7 @@ use super::{APIConverter, ComponentInterface};
 pub struct Error {
     pub name: String,
     enum_: Enum,
+    source_chain: bool,
 }
 
 impl Error {
@@ -105,6 +110,7 @@ impl Error {
         Self {
             name: enum_.name.clone(),
             enum_,
+            source_chain: false,
         }
     }
 
@@ -129,6 +135,25 @@ impl Error {
     pub fn is_flat(&self) -> bool {
         self.enum_.is_flat()
     }
+
+    /// Whether the error was declared with `[SourceChain]`, in which case the messages of its
+    /// chain of `source()`s are sent across the FFI after the variant data.
+    pub fn has_source_chain(&self) -> bool {
+        self.source_chain
+    }
+
+    /// The type in which the source chain is sent across the FFI, if there is one.
+    pub fn source_chain_type(&self) -> Option<Type> {
+        if self.source_chain {
+            Some(source_chain_type())
+        } else {
+            None
+        }
+    }
+}
+
+fn source_chain_type() -> Type {
+    Type::Sequence(Box::new(Type::String))
 }
 
 impl IterTypes for Error {
@@ -139,14 +164,67 @@ impl IterTypes for Error {
 
 impl APIConverter<Error> for weedle::EnumDefinition<'_> {
     fn convert(&self, ci: &mut ComponentInterface) -> Result<Error> {
-        Ok(Error::from_enum(APIConverter::<Enum>::convert(self, ci)?))
+        let attrs = EnumAttributes::try_from(self.attributes.as_ref())?;
+        let mut error = Error::from_enum(APIConverter::<Enum>::convert(self, ci)?);
+        if attrs.has_source_chain() {
+            ci.types.add_known_type(source_chain_type())?;
+            error.source_chain = true;
+        }
+        Ok(error)
     }
 }
 
 impl APIConverter<Error> for weedle::InterfaceDefinition<'_> {
     fn convert(&self, ci: &mut ComponentInterface) -> Result<Error> {
-        Ok(Error::from_enum(APIConverter::<Enum>::convert(self, ci)?))
+        let attrs = InterfaceAttributes::try_from(self.attributes.as_ref())?;
+        let mut error = Error::from_enum(APIConverter::<Enum>::convert(self, ci)?);
+        if attrs.has_source_chain() {
+            ci.types.add_known_type(source_chain_type())?;
+            error.source_chain = true;
+        }
+        check_error_from(&error)?;
+        Ok(error)
+    }
+}
+
+// The scaffolding can only build the variants that `[ErrorFrom]` converts errors into if it
+// knows what to put in their fields, and each Rust error type can only convert into one of them.
+fn check_error_from(error: &Error) -> Result<()> {
+    let mut seen = HashSet::new();
+    let mut catch_all = None;
+    for variant in error.variants() {
+        if variant.error_from().is_empty() && !variant.is_error_catch_all() {
+            continue;
+        }
+        match variant.fields().as_slice() {
+            [] => (),
+            [field] if field.type_() == Type::String => (),
+            _ => bail!(
+                "[ErrorFrom] variant \"{}\" must have no fields, or a single `string` field for the error message",
+                variant.name()
+            ),
+        }
+        for type_ in variant.error_from() {
+            if !seen.insert(type_) {
+                bail!(
+                    "\"{}\" can only convert into one variant of \"{}\"",
+                    type_,
+                    error.name()
+                );
+            }
+        }
+        if variant.is_error_catch_all() {
+            if let Some(other) = catch_all.replace(variant.name()) {
+                bail!(
+                    "\"{}\" can only have one catch-all variant, but both \"{}\" and \"{}\" are",
+                    error.name(),
+                    other,
+                    variant.name()
+                );
+            }
+        }
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -215,4 +293,84 @@ mod test {
         );
         assert!(!error.is_flat());
     }
+
+    #[test]
+    fn test_source_chain() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Error, SourceChain]
+            enum Flat { "one" };
+            [Error, SourceChain]
+            interface Rich { One(string reason); };
+            [Error]
+            enum Plain { "one" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let chain = Type::Sequence(Box::new(Type::String));
+        for name in &["Flat", "Rich"] {
+            let error = ci.get_error_definition(name).unwrap();
+            assert!(error.has_source_chain());
+            assert_eq!(error.source_chain_type(), Some(chain.clone()));
+        }
+        let error = ci.get_error_definition("Plain").unwrap();
+        assert!(!error.has_source_chain());
+        assert_eq!(error.source_chain_type(), None);
+        assert!(ci.iter_types().contains(&chain));
+    }
+
+    #[test]
+    fn test_error_from() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Error]
+            interface StoreError {
+                [ErrorFrom="std::io::Error"]
+                Io(string message);
+                [ErrorFrom="std::num::ParseIntError, std::str::Utf8Error"]
+                Parse();
+                NotFound(string key);
+                [ErrorFrom="*"]
+                Other(string message);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let error = ci.get_error_definition("StoreError").unwrap();
+        let variants = error.variants();
+        assert_eq!(variants[0].error_from(), vec!["std::io::Error"]);
+        assert_eq!(
+            variants[1].error_from(),
+            vec!["std::num::ParseIntError", "std::str::Utf8Error"]
+        );
+        assert!(variants[2].error_from().is_empty());
+        assert!(variants[3].error_from().is_empty());
+        assert!(variants[3].is_error_catch_all());
+        assert!(!variants[0].is_error_catch_all());
+    }
+
+    #[test]
+    fn test_error_from_errors() {
+        let err = |udl: &str| {
+            ComponentInterface::from_webidl(&format!("namespace test{{}}; {}", udl))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err(r#"[Error] interface E { [ErrorFrom="std::io::Error"] Io(i32 code); };"#),
+            "[ErrorFrom] variant \"Io\" must have no fields, or a single `string` field for the error message"
+        );
+        assert_eq!(
+            err(
+                r#"[Error] interface E { [ErrorFrom="std::io::Error"] A(); [ErrorFrom="std::io::Error"] B(); };"#
+            ),
+            "\"std::io::Error\" can only convert into one variant of \"E\""
+        );
+        assert_eq!(
+            err(r#"[Error] interface E { [ErrorFrom="*"] A(); [ErrorFrom="*"] B(); };"#),
+            "\"E\" can only have one catch-all variant, but both \"A\" and \"B\" are"
+        );
+        assert_eq!(
+            err(r#"[Enum] interface E { [ErrorFrom="std::io::Error"] A(); };"#),
+            "[ErrorFrom] is only supported on the variants of errors, not \"A\""
+        );
+    }
 }
@@ -0,0 +1,563 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Features that not every backend supports
+//!
+//! Some of the bindings, and the WebAssembly scaffolding, only support part of what a component
+//! can declare. Rather than each of them looking for the things it can't generate, and missing
+//! the ones that were added since, they describe what they can generate as [`Capabilities`],
+//! and [`ComponentInterface::unsupported_features`] lists everything in the component that's
+//! beyond them, so that they can report it all at once.
+
+use std::collections::HashSet;
+
+use super::{ComponentInterface, Type};
+use crate::lockfile::type_decl;
+
+/// A feature of a component that a backend might not support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// Functions declared in a namespace with the `[Parent]` attribute.
+    NestedNamespaces,
+    /// `[Invokable]` functions, which can be called by name.
+    Invokable,
+    /// `[Batchable]` functions, which can be called in a batch.
+    Batchable,
+    /// `[Scoped]` functions and methods, which take a `Scope`.
+    Scoped,
+    /// `[Async]` functions, methods and callback methods.
+    Async,
+    /// `[AlsoAsync]` methods, which get an async variant in the bindings.
+    AlsoAsync,
+    /// Methods that take `&mut self`, as requested by `[Self=ByMut]`.
+    MutMethods,
+    /// `[Borrowed]` methods, along with the views they return.
+    Borrowed,
+    /// `[NoCopy]` methods.
+    NoCopy,
+    /// `[Paginated]` methods, along with their page methods.
+    Paginated,
+    /// `[Clone]` objects.
+    Clone,
+    /// `[Closeable]` objects.
+    Closeable,
+    /// `[WeakRefs]` objects.
+    WeakRefs,
+    /// `[ReleaseCallbacks]` objects.
+    ReleaseCallbacks,
+    /// `[OpaqueToken]` objects.
+    OpaqueTokens,
+    /// Objects with a `[ForeignExtension]`.
+    ForeignExtensions,
+    /// `[Observable]` objects.
+    Observable,
+    /// Objects with `[Events]`.
+    Events,
+    /// `[Service]` objects.
+    Services,
+    /// The objects of `[Stream]` typedefs.
+    Streams,
+    /// `[Trait]` interfaces, which are implemented by a Rust trait.
+    Traits,
+    /// Enums whose variants have fields.
+    EnumsWithData,
+    /// Errors whose variants have fields.
+    ErrorsWithData,
+    /// `[SourceChain]` errors.
+    SourceChains,
+    /// Callback interfaces of any kind.
+    CallbackInterfaces,
+    /// `[Direct]` callback interfaces.
+    DirectCallbackInterfaces,
+    /// Callback methods that are `[Throws]`.
+    ThrowingCallbackMethods,
+    /// `[External]` and `[ExternalInterface]` types.
+    ExternalTypes,
+    /// `[Wrapped]` and `[Custom]` types.
+    CustomTypes,
+    /// `[OrderedMap]` types.
+    OrderedMaps,
+    /// The `bigint` type.
+    BigInt,
+    /// The `bytes` type.
+    Bytes,
+    /// The `char` type.
+    Char,
+    /// The `timestamp` and `duration` types.
+    Time,
+}
+
+impl Feature {
+    // A feature that's left out of here is never supported, so that a backend that was written
+    // before the feature existed rejects it rather than ignoring it. Attributes that a backend can
+    // ignore aren't features: `[Ordered]` and `[Cancellable]` only change the scaffolding, bindings
+    // that don't keep what `[Cached]` methods return call them every time, and Rust calls the
+    // methods of `[Versioned]` callback interfaces by position if the bindings don't know them.
+    const ALL: &'static [Feature] = &[
+        Feature::NestedNamespaces,
+        Feature::Invokable,
+        Feature::Batchable,
+        Feature::Scoped,
+        Feature::Async,
+        Feature::AlsoAsync,
+        Feature::MutMethods,
+        Feature::Borrowed,
+        Feature::NoCopy,
+        Feature::Paginated,
+        Feature::Clone,
+        Feature::Closeable,
+        Feature::WeakRefs,
+        Feature::ReleaseCallbacks,
+        Feature::OpaqueTokens,
+        Feature::ForeignExtensions,
+        Feature::Observable,
+        Feature::Events,
+        Feature::Services,
+        Feature::Streams,
+        Feature::Traits,
+        Feature::EnumsWithData,
+        Feature::ErrorsWithData,
+        Feature::SourceChains,
+        Feature::CallbackInterfaces,
+        Feature::DirectCallbackInterfaces,
+        Feature::ThrowingCallbackMethods,
+        Feature::ExternalTypes,
+        Feature::CustomTypes,
+        Feature::OrderedMaps,
+        Feature::BigInt,
+        Feature::Bytes,
+        Feature::Char,
+        Feature::Time,
+    ];
+}
+
+/// A small component that uses each feature, for the tests of the backends to check what they
+/// claim to support against.
+#[cfg(test)]
+pub(crate) const FEATURE_EXAMPLES: &[(Feature, &str)] = &[
+    (
+        Feature::NestedNamespaces,
+        "namespace test {}; [Parent=test] namespace tools { u32 one(); };",
+    ),
+    (
+        Feature::Invokable,
+        "namespace test { [Invokable] string get_setting(string key); };",
+    ),
+    (
+        Feature::Batchable,
+        "namespace test { [Batchable] string get_setting(string key); };",
+    ),
+    (
+        Feature::Scoped,
+        "namespace test { [Scoped] void prefetch(string url); };",
+    ),
+    (Feature::Async, "namespace test { [Async] u32 later(); };"),
+    (
+        Feature::AlsoAsync,
+        "namespace test {}; interface Session { [AlsoAsync] string name(); };",
+    ),
+    (
+        Feature::MutMethods,
+        "namespace test {}; interface Session { [Self=ByMut] void rename(string name); };",
+    ),
+    (
+        Feature::Borrowed,
+        "namespace test {}; interface Document { [Borrowed] string text(); };",
+    ),
+    (
+        Feature::NoCopy,
+        "namespace test {}; interface Frame { [NoCopy] sequence<u8> pixels(); };",
+    ),
+    (
+        Feature::Paginated,
+        "namespace test {}; interface TodoList { [Paginated] sequence<string> get_items(); };",
+    ),
+    (
+        Feature::Clone,
+        "namespace test {}; [Clone] interface Session { string name(); };",
+    ),
+    (
+        Feature::Closeable,
+        "namespace test {}; [Closeable] interface Session { string name(); };",
+    ),
+    (
+        Feature::WeakRefs,
+        "namespace test {}; [WeakRefs] interface Session { string name(); };",
+    ),
+    (
+        Feature::ReleaseCallbacks,
+        r#"
+            namespace test {};
+            callback interface Listener { void changed(); };
+            [ReleaseCallbacks]
+            interface Session { constructor(Listener listener); };
+        "#,
+    ),
+    (
+        Feature::OpaqueTokens,
+        "namespace test { Ticket issue(); }; [OpaqueToken] interface Ticket {};",
+    ),
+    (
+        Feature::ForeignExtensions,
+        "namespace test {}; [ForeignExtension=SessionExt] interface Session { constructor(); };",
+    ),
+    (
+        Feature::Observable,
+        r#"
+            namespace test {};
+            dictionary CounterState { u32 count; };
+            [Observable=CounterState]
+            interface Counter { constructor(); void increment(); };
+        "#,
+    ),
+    (
+        Feature::Events,
+        r#"
+            namespace test {};
+            dictionary Tick { u32 count; };
+            [Events=Tick]
+            interface Metronome { constructor(); void tick(); };
+        "#,
+    ),
+    (
+        Feature::Services,
+        "namespace test {}; [Service] interface Heartbeat { constructor(); };",
+    ),
+    (
+        Feature::Streams,
+        "namespace test { Numbers count_to(u32 n); }; [Stream] typedef sequence<u32> Numbers;",
+    ),
+    (
+        Feature::Traits,
+        "namespace test { Shape make_shape(); }; [Trait] interface Shape { string name(); };",
+    ),
+    (
+        Feature::EnumsWithData,
+        "namespace test { Shape unit(); }; [Enum] interface Shape { Circle(f64 radius); Dot(); };",
+    ),
+    (
+        Feature::ErrorsWithData,
+        "namespace test { [Throws=Oops] void fail(); }; [Error] interface Oops { Bad(string reason); };",
+    ),
+    (
+        Feature::SourceChains,
+        r#"namespace test { [Throws=Oops] void fail(); }; [Error, SourceChain] enum Oops { "Bad" };"#,
+    ),
+    (
+        Feature::CallbackInterfaces,
+        "namespace test { void notify(Listener listener); }; callback interface Listener { void changed(); };",
+    ),
+    (
+        Feature::DirectCallbackInterfaces,
+        "namespace test { void notify(Listener listener); }; [Direct] callback interface Listener { void changed(); };",
+    ),
+    (
+        Feature::ThrowingCallbackMethods,
+        r#"
+            namespace test { void notify(Listener listener); };
+            [Error]
+            enum Oops { "Bad" };
+            callback interface Listener { [Throws=Oops] void changed(); };
+        "#,
+    ),
+    (
+        Feature::ExternalTypes,
+        r#"namespace test { void restock(Stock stock); }; [External="warehouse"] typedef extern Stock;"#,
+    ),
+    (
+        Feature::CustomTypes,
+        "namespace test { void buy(Sku sku); }; [Wrapped] typedef string Sku;",
+    ),
+    (
+        Feature::OrderedMaps,
+        "namespace test { Counts tally(); }; [OrderedMap] typedef record<DOMString, u32> Counts;",
+    ),
+    (Feature::BigInt, "namespace test { bigint factorial(u32 n); };"),
+    (Feature::Bytes, "namespace test { bytes pack(string text); };"),
+    (Feature::Char, "namespace test { char initial(string name); };"),
+    (Feature::Time, "namespace test { timestamp now(); };"),
+];
+
+/// The features that a backend supports.
+#[derive(Debug, Clone)]
+pub struct Capabilities(HashSet<Feature>);
+
+impl Capabilities {
+    /// Every feature, for a backend to take away the ones it doesn't support.
+    pub fn all() -> Self {
+        Capabilities(Feature::ALL.iter().copied().collect())
+    }
+
+    /// These capabilities, without `features`.
+    pub fn without(mut self, features: &[Feature]) -> Self {
+        for feature in features {
+            self.0.remove(feature);
+        }
+        self
+    }
+
+    pub fn supports(&self, feature: Feature) -> bool {
+        self.0.contains(&feature)
+    }
+}
+
+impl ComponentInterface {
+    /// Describe everything in the component that a backend with the `supported` capabilities
+    /// can't generate, one line each, or return nothing if it can generate it all.
+    pub fn unsupported_features(&self, supported: &Capabilities) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut check = |feature: Feature, used: bool, what: String| {
+            if used && !supported.supports(feature) {
+                problems.push(what);
+            }
+        };
+        for func in self.functions.iter() {
+            let what = format!("function `{}`", func.qualified_name());
+            check(
+                Feature::NestedNamespaces,
+                !func.module_path().is_empty(),
+                format!("{}, in a nested namespace", what),
+            );
+            let attributes = [
+                (Feature::Invokable, func.is_invokable(), "[Invokable]"),
+                (Feature::Batchable, func.is_batchable(), "[Batchable]"),
+                (Feature::Scoped, func.is_scoped(), "[Scoped]"),
+                (Feature::Async, func.is_async(), "[Async]"),
+            ];
+            for (feature, used, attribute) in attributes.iter() {
+                check(
+                    *feature,
+                    *used,
+                    format!("{}, which is `{}`", what, attribute),
+                );
+            }
+        }
+        for obj in self.objects.iter() {
+            let what = format!("object `{}`", obj.name());
+            check(
+                Feature::MutMethods,
+                obj.has_mut_methods(),
+                format!("`[Self=ByMut]` methods of `{}`", obj.name()),
+            );
+            let attributes = [
+                (Feature::Clone, obj.is_cloneable(), "is `[Clone]`"),
+                (Feature::Closeable, obj.is_closeable(), "is `[Closeable]`"),
+                (Feature::WeakRefs, obj.has_weak_refs(), "is `[WeakRefs]`"),
+                (
+                    Feature::ReleaseCallbacks,
+                    obj.releases_callbacks(),
+                    "is `[ReleaseCallbacks]`",
+                ),
+                (
+                    Feature::OpaqueTokens,
+                    obj.is_opaque_token(),
+                    "is an `[OpaqueToken]`",
+                ),
+                (
+                    Feature::ForeignExtensions,
+                    obj.foreign_extension().is_some(),
+                    "has a `[ForeignExtension]`",
+                ),
+                (
+                    Feature::Observable,
+                    obj.observable_snapshot().is_some(),
+                    "is `[Observable]`",
+                ),
+                (
+                    Feature::Events,
+                    obj.events_type().is_some(),
+                    "has `[Events]`",
+                ),
+                (Feature::Services, obj.is_service(), "is a `[Service]`"),
+                (
+                    Feature::Streams,
+                    obj.stream_item().is_some(),
+                    "is a `[Stream]`",
+                ),
+                (Feature::Traits, obj.is_trait(), "is a `[Trait]` interface"),
+            ];
+            for (feature, used, attribute) in attributes.iter() {
+                check(*feature, *used, format!("{}, which {}", what, attribute));
+            }
+            for meth in obj.methods() {
+                let what = format!("method `{}.{}`", obj.name(), meth.name());
+                let attributes = [
+                    (Feature::Async, meth.is_async(), "[Async]"),
+                    (Feature::AlsoAsync, meth.is_also_async(), "[AlsoAsync]"),
+                    (Feature::Borrowed, meth.is_borrowed(), "[Borrowed]"),
+                    (Feature::NoCopy, meth.is_no_copy(), "[NoCopy]"),
+                    (Feature::Paginated, meth.is_paginated(), "[Paginated]"),
+                    (Feature::Scoped, meth.is_scoped(), "[Scoped]"),
+                ];
+                for (feature, used, attribute) in attributes.iter() {
+                    check(
+                        *feature,
+                        *used,
+                        format!("{}, which is `{}`", what, attribute),
+                    );
+                }
+            }
+        }
+        for e in self.enums.iter() {
+            check(
+                Feature::EnumsWithData,
+                !e.is_flat(),
+                format!("enum `{}`, which has associated data", e.name()),
+            );
+        }
+        for e in self.errors.iter() {
+            check(
+                Feature::ErrorsWithData,
+                !e.is_flat(),
+                format!("error `{}`, which has associated data", e.name()),
+            );
+            check(
+                Feature::SourceChains,
+                e.has_source_chain(),
+                format!("error `{}`, which is `[SourceChain]`", e.name()),
+            );
+        }
+        for cbi in self.callback_interfaces.iter() {
+            if !supported.supports(Feature::CallbackInterfaces) {
+                check(
+                    Feature::CallbackInterfaces,
+                    true,
+                    format!("callback interface `{}`", cbi.name()),
+                );
+                continue;
+            }
+            check(
+                Feature::DirectCallbackInterfaces,
+                cbi.is_direct(),
+                format!("callback interface `{}`, which is `[Direct]`", cbi.name()),
+            );
+            for meth in cbi.methods() {
+                let what = format!("callback method `{}.{}`", cbi.name(), meth.name());
+                check(
+                    Feature::ThrowingCallbackMethods,
+                    meth.throws().is_some(),
+                    format!("{}, which throws", what),
+                );
+                check(
+                    Feature::Async,
+                    meth.is_async(),
+                    format!("{}, which is `[Async]`", what),
+                );
+            }
+        }
+        for type_ in self.iter_types() {
+            let (feature, what) = match &type_ {
+                Type::BigInt => (Feature::BigInt, type_decl(&type_)),
+                Type::Bytes => (Feature::Bytes, type_decl(&type_)),
+                Type::Char => (Feature::Char, type_decl(&type_)),
+                Type::Timestamp | Type::Duration => (Feature::Time, type_decl(&type_)),
+                Type::External { .. } | Type::ExternalInterface { .. } => (
+                    Feature::ExternalTypes,
+                    format!("external type {}", type_decl(&type_)),
+                ),
+                Type::Wrapped { .. } => (
+                    Feature::CustomTypes,
+                    format!("custom type {}", type_decl(&type_)),
+                ),
+                Type::OrderedMap(_) => (
+                    Feature::OrderedMaps,
+                    format!("ordered map {}", type_decl(&type_)),
+                ),
+                _ => continue,
+            };
+            check(feature, true, what);
+        }
+        problems
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_features() {
+        const UDL: &str = r#"
+            namespace test {
+                [Async]
+                u32 later();
+            };
+            [Parent=test]
+            namespace tools {
+                bytes pack(timestamp when);
+            };
+            [Closeable, WeakRefs]
+            interface Session {
+                [AlsoAsync]
+                string name();
+                [Self=ByMut]
+                void rename(string name);
+            };
+            [Error, SourceChain]
+            enum Oops { "Bad" };
+            callback interface Listener {
+                [Throws=Oops]
+                void changed();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert!(ci.unsupported_features(&Capabilities::all()).is_empty());
+
+        let only = |features: &[Feature]| {
+            let unsupported = Feature::ALL
+                .iter()
+                .copied()
+                .filter(|f| !features.contains(f))
+                .collect::<Vec<_>>();
+            Capabilities::all().without(&unsupported)
+        };
+        assert_eq!(
+            ci.unsupported_features(&only(&[])),
+            vec![
+                "function `later`, which is `[Async]`",
+                "function `tools.pack`, in a nested namespace",
+                "`[Self=ByMut]` methods of `Session`",
+                "object `Session`, which is `[Closeable]`",
+                "object `Session`, which is `[WeakRefs]`",
+                "method `Session.name`, which is `[AlsoAsync]`",
+                "error `Oops`, which is `[SourceChain]`",
+                "callback interface `Listener`",
+                "bytes",
+                "timestamp",
+            ]
+        );
+        // The features of callback interfaces are only checked when they're supported at all.
+        assert_eq!(
+            ci.unsupported_features(
+                &Capabilities::all()
+                    .without(&[Feature::ThrowingCallbackMethods, Feature::WeakRefs])
+            ),
+            vec![
+                "object `Session`, which is `[WeakRefs]`",
+                "callback method `Listener.changed`, which throws",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_feature_examples() {
+        for feature in Feature::ALL {
+            assert!(
+                FEATURE_EXAMPLES.iter().any(|(f, _)| f == feature),
+                "{:?} has no example",
+                feature
+            );
+        }
+        for (feature, udl) in FEATURE_EXAMPLES {
+            let ci = ComponentInterface::from_webidl(udl).unwrap();
+            assert!(ci.unsupported_features(&Capabilities::all()).is_empty());
+            assert!(
+                !ci.unsupported_features(&Capabilities::all().without(&[*feature]))
+                    .is_empty(),
+                "the example of {:?} isn't reported when it's unsupported",
+                feature
+            );
+        }
+    }
+}
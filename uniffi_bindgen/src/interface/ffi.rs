@@ -45,6 +45,13 @@ pub enum FFIType {
     /// A pointer to a single function in to the foreign language.
     /// This function contains all the machinery to make callbacks work on the foreign language side.
     ForeignCallback,
+    /// A pointer to a foreign-language function that implements a single method of a
+    /// `[Direct]` callback interface, named after the function pointer type that describes it.
+    ForeignMethodCallback(String),
+    /// A pointer to the foreign-language function that the Rust code calls once an `[Async]`
+    /// function or method has finished, with the call's completion handle, whether it succeeded,
+    /// and a buffer holding its result.
+    AsyncCompletion,
     // TODO: you can imagine a richer structural typesystem here, e.g. `Ref<String>` or something.
     // We don't need that yet and it's possible we never will, so it isn't here for now.
 }
@@ -37,7 +37,7 @@ use std::hash::{Hash, Hasher};
 use anyhow::{bail, Result};
 
 use super::attributes::{ArgumentAttributes, FunctionAttributes};
-use super::ffi::{FFIArgument, FFIFunction};
+use super::ffi::{FFIArgument, FFIFunction, FFIType};
 use super::literal::{convert_default_value, Literal};
 use super::types::{IterTypes, Type, TypeIterator};
 use super::{APIConverter, ComponentInterface};
@@ -51,6 +51,8 @@ use super::{APIConverter, ComponentInterface};
 #[derive(Debug, Clone)]
 pub struct Function {
     pub(super) name: String,
+    // The path of the nested namespace this function was declared in, if any.
+    pub(super) module_path: Vec<String>,
     pub(super) arguments: Vec<Argument>,
     pub(super) return_type: Option<Type>,
     pub(super) ffi_func: FFIFunction,
@@ -62,6 +64,22 @@ impl Function {
         &self.name
     }
 
+    /// The path of the nested namespace this function was declared in, which is empty for
+    /// functions declared directly in the component's namespace.
+    pub fn module_path(&self) -> Vec<&str> {
+        self.module_path.iter().map(String::as_str).collect()
+    }
+
+    /// The name qualified by its module path, e.g. `sync.engine.start`.
+    pub fn qualified_name(&self) -> String {
+        self.module_path
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::once(self.name.as_str()))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
     pub fn arguments(&self) -> Vec<&Argument> {
         self.arguments.iter().collect()
     }
@@ -88,16 +106,68 @@ impl Function {
             .map(|name| Type::Error(name.to_owned()))
     }
 
+    /// Whether the function can also be queued up in a batch, and called along with the other
+    /// calls in it through a single FFI function.
+    pub fn is_batchable(&self) -> bool {
+        self.attributes.is_batchable()
+    }
+
+    /// Whether the function can also be called by its qualified name, through the component's
+    /// invoke FFI function, by callers that don't have bindings for it.
+    pub fn is_invokable(&self) -> bool {
+        self.attributes.is_invokable()
+    }
+
+    /// Whether the function is marked `[Scoped]`, so that it takes the `Scope` that its
+    /// background work is registered against, as an argument called `scope` ahead of the
+    /// ones that the UDL declares.
+    pub fn is_scoped(&self) -> bool {
+        self.attributes.is_scoped()
+    }
+
+    /// Whether the function is marked `[Async]`, so that its Rust implementation is an
+    /// `async fn`, and the bindings have an async variant that waits for it to finish.
+    pub fn is_async(&self) -> bool {
+        self.attributes.is_async()
+    }
+
+    /// Whether the `[Async]` function is also marked `[Cancellable]`, so that its Rust
+    /// implementation takes a `uniffi::CancellationToken` after the arguments that the UDL
+    /// declares, and carries on until it notices that the call was cancelled. Other `[Async]`
+    /// functions are simply dropped when they're cancelled.
+    pub fn is_cancellable(&self) -> bool {
+        self.attributes.is_cancellable()
+    }
+
     pub fn derive_ffi_func(&mut self, ci_prefix: &str) -> Result<()> {
         self.ffi_func.name.push_str(ci_prefix);
         self.ffi_func.name.push('_');
+        for module in self.module_path.iter() {
+            self.ffi_func.name.push_str(module);
+            self.ffi_func.name.push('_');
+        }
         self.ffi_func.name.push_str(&self.name);
         self.ffi_func.arguments = self.arguments.iter().map(|arg| arg.into()).collect();
         self.ffi_func.return_type = self.return_type.as_ref().map(|rt| rt.into());
+        if self.is_async() {
+            async_ffi_func(&mut self.ffi_func);
+        }
         Ok(())
     }
 }
 
+/// The FFI function of an `[Async]` function or method only starts the call: it takes the
+/// handle of the call's completion after the other arguments, and returns nothing. The Rust
+/// code passes the handle to the completion function that the bindings registered with
+/// `ComponentInterface::ffi_async_init`, once the call has finished.
+pub(super) fn async_ffi_func(ffi_func: &mut FFIFunction) {
+    ffi_func.arguments.push(FFIArgument {
+        name: "uniffi_completion".to_string(),
+        type_: FFIType::UInt64,
+    });
+    ffi_func.return_type = None;
+}
+
 impl IterTypes for Function {
     fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(
@@ -119,6 +189,7 @@ impl Hash for Function {
         //    hash value we're trying to calculate here, so excluding it
         //    avoids a weird circular depenendency in the calculation.
         self.name.hash(state);
+        self.module_path.hash(state);
         self.arguments.hash(state);
         self.return_type.hash(state);
         self.attributes.hash(state);
@@ -142,6 +213,7 @@ impl APIConverter<Function> for weedle::namespace::OperationNamespaceMember<'_>
                 None => bail!("anonymous functions are not supported {:?}", self),
                 Some(id) => id.0.to_string(),
             },
+            module_path: Vec::new(),
             return_type,
             arguments: self.args.body.list.convert(ci)?,
             ffi_func: Default::default(),
@@ -177,6 +249,30 @@ impl Argument {
     }
 }
 
+/// The UDL for the `Scope` interface that `[Scoped]` functions and methods take, which is
+/// declared once for the whole component. The scaffolding re-exports `uniffi::Scope` to
+/// implement it.
+pub(super) const SCOPE_UDL: &str = r#"
+    interface Scope {
+        constructor();
+        void cancel();
+        boolean is_cancelled();
+        void join();
+    };
+"#;
+
+/// The argument that `[Scoped]` functions and methods take ahead of the ones that the UDL
+/// declares.
+pub(super) fn scope_argument() -> Argument {
+    Argument {
+        name: "scope".into(),
+        type_: Type::Object("Scope".into()),
+        by_ref: false,
+        optional: false,
+        default: None,
+    }
+}
+
 impl IterTypes for Argument {
     fn iter_types(&self) -> TypeIterator<'_> {
         self.type_.iter_types()
@@ -112,6 +112,10 @@ pub(super) fn convert_default_value(
             Literal::String(s.0.to_string())
         }
         (weedle::literal::DefaultValue::EmptyArray(_), Type::Sequence(_)) => Literal::EmptySequence,
+        (weedle::literal::DefaultValue::EmptyDictionary(_), Type::Map(..)) => Literal::EmptyMap,
+        (weedle::literal::DefaultValue::EmptyDictionary(_), Type::OrderedMap(_)) => {
+            Literal::EmptyMap
+        }
         (weedle::literal::DefaultValue::String(s), Type::Enum(_)) => {
             Literal::Enum(s.0.to_string(), type_.clone())
         }
@@ -167,6 +171,54 @@ mod test {
             parse_and_convert("null", Type::Optional(Box::new(Type::String)))?,
             Literal::Null
         ));
+        assert!(matches!(
+            parse_and_convert(
+                "{}",
+                Type::Map(Box::new(Type::String), Box::new(Type::Int32))
+            )?,
+            Literal::EmptyMap
+        ));
+        assert!(matches!(
+            parse_and_convert("{}", Type::OrderedMap(Box::new(Type::String)))?,
+            Literal::EmptyMap
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_default_value_conversion() -> Result<()> {
+        let names = Type::Optional(Box::new(Type::Sequence(Box::new(Type::String))));
+        assert!(matches!(
+            parse_and_convert("null", names.clone())?,
+            Literal::Null
+        ));
+        assert!(matches!(
+            parse_and_convert("[]", names)?,
+            Literal::EmptySequence
+        ));
+        let counts = Type::Optional(Box::new(Type::Map(
+            Box::new(Type::String),
+            Box::new(Type::Sequence(Box::new(Type::Int32))),
+        )));
+        assert!(matches!(
+            parse_and_convert("{}", counts)?,
+            Literal::EmptyMap
+        ));
+        assert!(matches!(
+            parse_and_convert(
+                "[]",
+                Type::Optional(Box::new(Type::Optional(Box::new(Type::Sequence(
+                    Box::new(Type::String)
+                )))))
+            )?,
+            Literal::EmptySequence
+        ));
+        assert!(parse_and_convert("{}", Type::Sequence(Box::new(Type::String))).is_err());
+        assert!(parse_and_convert(
+            "[]",
+            Type::Map(Box::new(Type::String), Box::new(Type::Int32))
+        )
+        .is_err());
         Ok(())
     }
     #[test]
@@ -45,13 +45,14 @@
 //!   * Error messages and general developer experience leave a lot to be desired.
 
 use std::{
-    collections::{hash_map::DefaultHasher, HashSet},
+    collections::{hash_map::DefaultHasher, BTreeSet, HashMap, HashSet},
     convert::TryFrom,
     hash::{Hash, Hasher},
     str::FromStr,
 };
 
 use anyhow::{bail, Result};
+use heck::SnakeCase;
 
 pub mod types;
 pub use types::Type;
@@ -61,19 +62,24 @@ mod attributes;
 mod callbacks;
 pub use callbacks::CallbackInterface;
 mod enum_;
-pub use enum_::Enum;
+pub use enum_::{Enum, Variant};
 mod error;
 pub use error::Error;
+mod features;
+#[cfg(test)]
+pub(crate) use features::FEATURE_EXAMPLES;
+pub use features::{Capabilities, Feature};
 mod function;
 pub use function::{Argument, Function};
 mod literal;
 pub use literal::{Literal, Radix};
 mod namespace;
-pub use namespace::Namespace;
+pub use namespace::{Module, Namespace};
 mod object;
-pub use object::{Constructor, Method, Object};
+pub use object::{Constructor, Method, Object, Threading};
 mod record;
 pub use record::{Field, Record};
+mod subset;
 
 pub mod ffi;
 pub use ffi::{FFIArgument, FFIFunction, FFIType};
@@ -81,7 +87,7 @@ pub use ffi::{FFIArgument, FFIFunction, FFIType};
 /// The main public interface for this module, representing the complete details of an interface exposed
 /// by a rust component and the details of consuming it via an extern-C FFI layer.
 ///
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct ComponentInterface {
     /// Every ComponentInterface gets tagged with the version of uniffi used to create it.
     /// This helps us avoid using a lib compiled with one version together with bindings created
@@ -94,10 +100,19 @@ pub struct ComponentInterface {
     /// The high-level API provided by the component.
     enums: Vec<Enum>,
     records: Vec<Record>,
+    /// The functions of the component, including those in nested namespaces.
     functions: Vec<Function>,
+    /// Namespaces nested inside the component's namespace, with parents before their children.
+    modules: Vec<Module>,
     objects: Vec<Object>,
     callback_interfaces: Vec<CallbackInterface>,
     errors: Vec<Error>,
+    /// The `Type::Wrapped` types that are declared `[Custom]`, so implement
+    /// `UniffiCustomTypeConverter` rather than `UniffiCustomTypeWrapper`.
+    custom_types: BTreeSet<String>,
+    /// For a subset of a component, the checksum of the whole component, which is
+    /// what the compiled Rust code knows it by.
+    full_checksum: Option<u64>,
 }
 
 impl<'ci> ComponentInterface {
@@ -126,7 +141,14 @@ impl<'ci> ComponentInterface {
         ci.types.add_type_definitions_from(defns.as_slice())?;
         // With those names resolved, we can build a complete representation of the API.
         APIBuilder::process(&defns, &mut ci)?;
+        ci.add_scopes()?;
+        ci.add_borrowed_views()?;
+        ci.add_paginated_methods()?;
+        ci.add_observers()?;
+        ci.add_events()?;
+        ci.add_services()?;
         ci.check_consistency()?;
+        ci.check_exported_names()?;
         // Now that the high-level API is settled, we can derive the low-level FFI.
         ci.derive_ffi_funcs()?;
         Ok(ci)
@@ -162,15 +184,107 @@ impl<'ci> ComponentInterface {
         self.records.iter().find(|r| r.name == name)
     }
 
-    /// List the definitions for every Function in the interface.
+    /// List the definitions for every Function in the interface, including those declared
+    /// in nested namespaces.
     pub fn iter_function_definitions(&self) -> Vec<Function> {
         self.functions.to_vec()
     }
 
-    /// Get a Function definition by name, or None if no such Function is defined.
+    /// List the definitions for the Functions declared directly in the component's namespace.
+    pub fn iter_namespace_function_definitions(&self) -> Vec<Function> {
+        self.functions
+            .iter()
+            .filter(|f| f.module_path.is_empty())
+            .cloned()
+            .collect()
+    }
+
+    /// List the definitions for the `[Invokable]` Functions, in the order that the UDL declares them.
+    pub fn iter_invokable_function_definitions(&self) -> Vec<Function> {
+        self.functions
+            .iter()
+            .filter(|f| f.is_invokable())
+            .cloned()
+            .collect()
+    }
+
+    /// The signatures of the `[Invokable]` functions, for callers of the invoke FFI function
+    /// that don't have bindings for them.
+    ///
+    /// This is one line per function, with tab-separated fields: the function's qualified name,
+    /// its return type and the error that it throws, each of which is empty if it doesn't have
+    /// one, and then `name:type` for each of its arguments. Types are written as in the UDL.
+    pub fn invoke_signatures(&self) -> String {
+        self.iter_invokable_function_definitions()
+            .iter()
+            .map(|func| {
+                let mut fields = vec![
+                    func.qualified_name(),
+                    func.return_type()
+                        .map(crate::lockfile::type_decl)
+                        .unwrap_or_default(),
+                    func.throws().unwrap_or_default().to_string(),
+                ];
+                fields.extend(func.arguments().iter().map(|arg| {
+                    format!(
+                        "{}:{}",
+                        arg.name(),
+                        crate::lockfile::type_decl(&arg.type_())
+                    )
+                }));
+                fields.join("\t")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// List the definitions for the `[Batchable]` Functions, in the order that the batch FFI
+    /// function numbers them in, starting from 1.
+    pub fn iter_batchable_function_definitions(&self) -> Vec<Function> {
+        self.functions
+            .iter()
+            .filter(|f| f.is_batchable())
+            .cloned()
+            .collect()
+    }
+
+    /// Get a Function definition by its qualified name (e.g. `sync.engine.start` for a function
+    /// in a nested namespace), or None if no such Function is defined.
     pub fn get_function_definition(&self, name: &str) -> Option<&Function> {
         // TODO: probably we could store these internally in a HashMap to make this easier?
-        self.functions.iter().find(|f| f.name == name)
+        self.functions.iter().find(|f| f.qualified_name() == name)
+    }
+
+    /// List every namespace nested inside the component's namespace, parents before children.
+    pub fn iter_module_definitions(&self) -> Vec<Module> {
+        self.modules.to_vec()
+    }
+
+    /// List the namespaces nested directly inside the component's namespace.
+    pub fn iter_top_level_module_definitions(&self) -> Vec<Module> {
+        self.modules
+            .iter()
+            .filter(|m| m.path.len() == 1)
+            .cloned()
+            .collect()
+    }
+
+    /// List the namespaces nested directly inside the given one.
+    pub fn iter_child_module_definitions(&self, module: &Module) -> Vec<Module> {
+        self.modules
+            .iter()
+            .filter(|m| m.parent_path() == module.path())
+            .cloned()
+            .collect()
+    }
+
+    /// List the definitions for the Functions declared in the given nested namespace.
+    pub fn iter_module_function_definitions(&self, module: &Module) -> Vec<Function> {
+        self.functions
+            .iter()
+            .filter(|f| f.module_path == module.path)
+            .cloned()
+            .collect()
     }
 
     /// List the definitions for every Object type in the interface.
@@ -184,6 +298,32 @@ impl<'ci> ComponentInterface {
         self.objects.iter().find(|o| o.name == name)
     }
 
+    /// Whether any object is a `[Service]`, in which case the interface declares the
+    /// `ServiceState` enum for them.
+    pub fn has_services(&self) -> bool {
+        self.objects.iter().any(|o| o.is_service)
+    }
+
+    /// Whether any function or method is `[Scoped]`, in which case the interface declares the
+    /// `Scope` object for them.
+    pub fn has_scopes(&self) -> bool {
+        self.functions.iter().any(|f| f.is_scoped())
+            || self
+                .objects
+                .iter()
+                .any(|o| o.methods.iter().any(|m| m.is_scoped()))
+    }
+
+    /// Whether any function or interface method is `[Async]`, in which case the bindings
+    /// register a completion function with [`ComponentInterface::ffi_async_init`].
+    pub fn has_async_functions(&self) -> bool {
+        self.functions.iter().any(|f| f.is_async())
+            || self
+                .objects
+                .iter()
+                .any(|o| o.methods.iter().any(|m| m.is_async()))
+    }
+
     /// List the definitions for every Callback Interface type in the interface.
     pub fn iter_callback_interface_definitions(&self) -> Vec<CallbackInterface> {
         self.callback_interfaces.to_vec()
@@ -206,6 +346,15 @@ impl<'ci> ComponentInterface {
         self.errors.iter().find(|e| e.name == name)
     }
 
+    /// Whether a method of a callback interface throws the error, in which case the
+    /// foreign language sends it back to Rust, and the scaffolding has to be able to read it.
+    pub fn is_thrown_by_callback_interface(&self, error_name: &str) -> bool {
+        self.callback_interfaces
+            .iter()
+            .flat_map(|cbi| cbi.methods.iter())
+            .any(|meth| meth.throws() == Some(error_name))
+    }
+
     /// Get details about all `Type::External` types
     pub fn iter_external_types(&self) -> Vec<(String, String)> {
         self.types
@@ -217,6 +366,28 @@ impl<'ci> ComponentInterface {
             .collect()
     }
 
+    /// Get details about all `Type::ExternalInterface` types
+    pub fn iter_external_interfaces(&self) -> Vec<(String, String)> {
+        self.types
+            .iter_known_types()
+            .filter_map(|t| match t {
+                Type::ExternalInterface { name, crate_name } => Some((name, crate_name)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The crates of the components that the `Type::ExternalInterface` types come from, each
+    /// listed once.
+    pub fn iter_external_interface_crates(&self) -> Vec<String> {
+        self.iter_external_interfaces()
+            .into_iter()
+            .map(|(_, crate_name)| crate_name)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
     /// Get details about all `Type::Wrapped` types
     pub fn iter_wrapped_types(&self) -> Vec<(String, Type)> {
         self.types
@@ -228,6 +399,18 @@ impl<'ci> ComponentInterface {
             .collect()
     }
 
+    /// Whether the `Type::Wrapped` type with the given name is declared `[Custom]`.
+    pub fn is_custom_type(&self, name: &str) -> bool {
+        self.custom_types.contains(name)
+    }
+
+    /// Whether any of the `Type::Wrapped` types are declared `[Custom]`.
+    pub fn has_custom_types(&self) -> bool {
+        self.iter_wrapped_types()
+            .iter()
+            .any(|(name, _)| self.is_custom_type(name))
+    }
+
     /// Iterate over all known types in the interface.
     pub fn iter_types(&self) -> Vec<Type> {
         self.types.iter_known_types().collect()
@@ -250,13 +433,14 @@ impl<'ci> ComponentInterface {
         RecursiveTypeIterator::new(self, item)
     }
 
-    /// Check whether the given item contains any (possibly nested) Type::Object references.
+    /// Check whether the given item contains any (possibly nested) references to objects, of this
+    /// component or of another one.
     ///
     /// This is important to know in language bindings that cannot integrate object types
     /// tightly with the host GC, and hence need to perform manual destruction of objects.
     pub fn item_contains_object_references<T: IterTypes>(&self, item: &T) -> bool {
         self.iter_types_in_item(item)
-            .any(|t| matches!(t, Type::Object(_)))
+            .any(|t| matches!(t, Type::Object(_) | Type::ExternalInterface { .. }))
     }
 
     /// Check whether the given item contains any (possibly nested) unsigned types
@@ -283,7 +467,7 @@ impl<'ci> ComponentInterface {
     pub fn contains_map_types(&self) -> bool {
         self.types
             .iter_known_types()
-            .any(|t| matches!(t, Type::Map(_)))
+            .any(|t| matches!(t, Type::Map(..)))
     }
 
     /// Calculate a numeric checksum for this ComponentInterface.
@@ -309,6 +493,9 @@ impl<'ci> ComponentInterface {
     /// ensure the guarantees above, or if it might be sensitive to e.g. compiler-driven re-ordering
     /// of struct field. Let's see how it goes...
     pub fn checksum(&self) -> u64 {
+        if let Some(checksum) = self.full_checksum {
+            return checksum;
+        }
         let mut hasher = DefaultHasher::new();
         // Our implementation of `Hash` mixes in all of the public API of the component,
         // as well as the version string of uniffi.
@@ -336,6 +523,20 @@ impl<'ci> ComponentInterface {
         )
     }
 
+    /// Builtin FFI function for fetching metadata about the component, such as its checksum.
+    /// This lets the foreign language bindings report exactly which build of the Rust library
+    /// they are using.
+    ///
+    /// Like [`ComponentInterface::ffi_checksum`], its name doesn't include the checksum, so that
+    /// bindings can still find it in a build of the library that doesn't match them.
+    pub fn ffi_component_info(&self) -> FFIFunction {
+        FFIFunction {
+            name: format!("ffi_{}_uniffi_component_info", self.namespace()),
+            arguments: vec![],
+            return_type: Some(FFIType::RustBuffer),
+        }
+    }
+
     /// Builtin FFI function for allocating a new `RustBuffer`.
     /// This is needed so that the foreign language bindings can create buffers in which to pass
     /// complex data types across the FFI.
@@ -398,6 +599,228 @@ impl<'ci> ComponentInterface {
         }
     }
 
+    /// Builtin FFI function for fetching the checksum that the library was built with.
+    ///
+    /// Unlike the other FFI functions, its name doesn't depend on the checksum, so that the
+    /// foreign language bindings can always find it and report a mismatch with a helpful
+    /// message, rather than failing to find some other symbol.
+    pub fn ffi_checksum(&self) -> FFIFunction {
+        FFIFunction {
+            name: format!("ffi_{}_uniffi_checksum", self.namespace()),
+            arguments: vec![],
+            return_type: Some(FFIType::UInt64),
+        }
+    }
+
+    /// Builtin FFI function for running the component's reset hooks.
+    ///
+    /// The Python and Ruby bindings call it from `reset_library()`, once they've freed every
+    /// object they hold, so that the component can flush any other state before the library
+    /// is reloaded.
+    pub fn ffi_reset(&self) -> FFIFunction {
+        FFIFunction {
+            name: format!("ffi_{}_uniffi_reset", self.ffi_namespace()),
+            arguments: vec![],
+            return_type: None,
+        }
+    }
+
+    /// Builtin FFI function for shutting the library down.
+    ///
+    /// The Kotlin, Python and Ruby bindings call it when their runtime starts to exit, so that
+    /// the component stops calling back into it, see `uniffi::on_shutdown`.
+    pub fn ffi_shutdown(&self) -> FFIFunction {
+        FFIFunction {
+            name: format!("ffi_{}_uniffi_shutdown", self.ffi_namespace()),
+            arguments: vec![],
+            return_type: None,
+        }
+    }
+
+    /// Builtin FFI function for warming the library up.
+    ///
+    /// The Kotlin, Swift and Python bindings call it from `uniffiWarmup()`, once they've loaded
+    /// and checked the library, to run the hooks that the component registered with
+    /// `uniffi::on_warmup`, and start the threads for its `[Async]` calls, if it has any.
+    pub fn ffi_warmup(&self) -> FFIFunction {
+        FFIFunction {
+            name: format!("ffi_{}_uniffi_warmup", self.ffi_namespace()),
+            arguments: vec![],
+            return_type: None,
+        }
+    }
+
+    /// Builtin FFI function for running the component's memory pressure hooks.
+    ///
+    /// The Kotlin and Swift bindings call it from `uniffiOnMemoryPressure()`, with `1` for
+    /// moderate and `2` for critical pressure, so that the component can shrink its caches.
+    pub fn ffi_on_memory_pressure(&self) -> FFIFunction {
+        FFIFunction {
+            name: format!("ffi_{}_uniffi_on_memory_pressure", self.ffi_namespace()),
+            arguments: vec![FFIArgument {
+                name: "level".to_string(),
+                type_: FFIType::Int32,
+            }],
+            return_type: None,
+        }
+    }
+
+    /// Builtin FFI function for calling `[Batchable]` functions in a batch, if there are any.
+    ///
+    /// The Kotlin, Swift and Python bindings call it from `batch`, with each call's function
+    /// index followed by its arguments, and get back each call's result, a status byte followed
+    /// by the return value if it succeeded or the error if it didn't.
+    pub fn ffi_batch(&self) -> Option<FFIFunction> {
+        if !self.functions.iter().any(|f| f.is_batchable()) {
+            return None;
+        }
+        Some(FFIFunction {
+            name: format!("ffi_{}_uniffi_batch", self.ffi_namespace()),
+            arguments: vec![FFIArgument {
+                name: "calls".to_string(),
+                type_: FFIType::RustBuffer,
+            }],
+            return_type: Some(FFIType::RustBuffer),
+        })
+    }
+
+    /// Builtin FFI function for calling an `[Invokable]` function by its qualified name, if
+    /// there are any.
+    ///
+    /// `args` holds the function's arguments, serialized in order, and the result is a status
+    /// byte followed by the return value if it succeeded or the error if it didn't, as for
+    /// [`ComponentInterface::ffi_batch`].
+    pub fn ffi_invoke(&self) -> Option<FFIFunction> {
+        if !self.functions.iter().any(|f| f.is_invokable()) {
+            return None;
+        }
+        Some(FFIFunction {
+            name: format!("ffi_{}_uniffi_invoke", self.ffi_namespace()),
+            arguments: vec![
+                FFIArgument {
+                    name: "name".to_string(),
+                    type_: FFIType::RustBuffer,
+                },
+                FFIArgument {
+                    name: "args".to_string(),
+                    type_: FFIType::RustBuffer,
+                },
+            ],
+            return_type: Some(FFIType::RustBuffer),
+        })
+    }
+
+    /// Builtin FFI function for fetching [`ComponentInterface::invoke_signatures`], if there
+    /// are any `[Invokable]` functions.
+    pub fn ffi_invoke_signatures(&self) -> Option<FFIFunction> {
+        self.ffi_invoke()?;
+        Some(FFIFunction {
+            name: format!("ffi_{}_uniffi_invoke_signatures", self.ffi_namespace()),
+            arguments: vec![],
+            return_type: Some(FFIType::RustBuffer),
+        })
+    }
+
+    /// Builtin FFI function for completing the future that an `[Async]` callback interface
+    /// method returned to Rust, if there are any.
+    ///
+    /// `handle` is the completion handle that the method was called with. If `code` is `0`,
+    /// `result` holds what the method would have returned if it weren't async, and if it's `1`,
+    /// the implementation failed with an exception that it doesn't declare, and `result` holds
+    /// its message, which the future panics with.
+    pub fn ffi_callback_future_complete(&self) -> Option<FFIFunction> {
+        if !self
+            .callback_interfaces
+            .iter()
+            .any(|cbi| cbi.has_async_methods())
+        {
+            return None;
+        }
+        Some(FFIFunction {
+            name: format!(
+                "ffi_{}_uniffi_callback_future_complete",
+                self.ffi_namespace()
+            ),
+            arguments: vec![
+                FFIArgument {
+                    name: "handle".to_string(),
+                    type_: FFIType::UInt64,
+                },
+                FFIArgument {
+                    name: "code".to_string(),
+                    type_: FFIType::Int8,
+                },
+                FFIArgument {
+                    name: "result".to_string(),
+                    type_: FFIType::RustBuffer,
+                },
+            ],
+            return_type: None,
+        })
+    }
+
+    /// Builtin FFI function for registering the function that completes the calls to `[Async]`
+    /// functions and methods, if there are any.
+    ///
+    /// The bindings call it once, before they make the first such call. The Rust code calls
+    /// `completion` from one of its executor threads once a call has finished, with the handle
+    /// that the call was made with and a code: `0` if it returned, when the buffer holds what
+    /// it returned, `1` if it failed with its declared error, when the buffer holds the error,
+    /// and `2` if it panicked, when the buffer holds the message. A call that was cancelled
+    /// before it finished is completed with `3`, and an empty buffer.
+    pub fn ffi_async_init(&self) -> Option<FFIFunction> {
+        if !self.has_async_functions() {
+            return None;
+        }
+        Some(FFIFunction {
+            name: format!("ffi_{}_uniffi_async_init", self.ffi_namespace()),
+            arguments: vec![FFIArgument {
+                name: "completion".to_string(),
+                type_: FFIType::AsyncCompletion,
+            }],
+            return_type: None,
+        })
+    }
+
+    /// Builtin FFI function for cancelling a call to an `[Async]` function or method, by the
+    /// handle that it was made with, if there are any such functions.
+    ///
+    /// The Rust code drops the future of the call, and completes it with the code for a
+    /// cancelled call, unless the function is `[Cancellable]`, in which case it cancels the
+    /// call's `CancellationToken` and leaves the function to finish however it sees fit. A
+    /// handle whose call has already finished is ignored.
+    pub fn ffi_async_cancel(&self) -> Option<FFIFunction> {
+        if !self.has_async_functions() {
+            return None;
+        }
+        Some(FFIFunction {
+            name: format!("ffi_{}_uniffi_cancel", self.ffi_namespace()),
+            arguments: vec![FFIArgument {
+                name: "handle".to_string(),
+                type_: FFIType::UInt64,
+            }],
+            return_type: None,
+        })
+    }
+
+    /// Builtin FFI function for fetching the checksum of the component in `crate_name`, as this
+    /// library was built against it.
+    ///
+    /// The bindings compare it with the checksum that the other component's bindings were
+    /// generated from before passing its objects to or from this library, since the two
+    /// libraries have to agree on what those objects are.
+    pub fn ffi_external_checksum(&self, crate_name: &str) -> FFIFunction {
+        FFIFunction {
+            name: format!(
+                "ffi_{}_uniffi_checksum_of_{}",
+                self.ffi_namespace(),
+                crate_name.replace('-', "_")
+            ),
+            arguments: vec![],
+            return_type: Some(FFIType::UInt64),
+        }
+    }
+
     /// List the definitions of all FFI functions in the interface.
     ///
     /// The set of FFI functions is derived automatically from the set of higher-level types
@@ -417,6 +840,23 @@ impl<'ci> ComponentInterface {
             )
             .chain(self.functions.iter().map(|f| f.ffi_func.clone()))
             .chain(self.iter_rust_buffer_ffi_function_definitions())
+            .chain(std::iter::once(self.ffi_component_info()))
+            .chain(std::iter::once(self.ffi_checksum()))
+            .chain(std::iter::once(self.ffi_reset()))
+            .chain(std::iter::once(self.ffi_shutdown()))
+            .chain(std::iter::once(self.ffi_warmup()))
+            .chain(std::iter::once(self.ffi_on_memory_pressure()))
+            .chain(self.ffi_batch())
+            .chain(self.ffi_invoke())
+            .chain(self.ffi_invoke_signatures())
+            .chain(self.ffi_callback_future_complete())
+            .chain(self.ffi_async_init())
+            .chain(self.ffi_async_cancel())
+            .chain(
+                self.iter_external_interface_crates()
+                    .into_iter()
+                    .map(|crate_name| self.ffi_external_checksum(&crate_name)),
+            )
             .collect()
     }
 
@@ -467,12 +907,40 @@ impl<'ci> ComponentInterface {
     }
 
     /// Called by `APIBuilder` impls to add a newly-parsed namespace definition to the `ComponentInterface`.
-    fn add_namespace_definition(&mut self, defn: Namespace) -> Result<()> {
-        if !self.namespace.is_empty() {
-            bail!("duplicate namespace definition");
+    ///
+    /// Returns the module path for the namespace's functions, which is empty for the component's
+    /// own namespace.
+    fn add_namespace_definition(&mut self, defn: Namespace) -> Result<Vec<String>> {
+        let parent = match defn.parent {
+            None => {
+                if !self.namespace.is_empty() {
+                    bail!("duplicate namespace definition");
+                }
+                self.namespace.push_str(&defn.name);
+                return Ok(Vec::new());
+            }
+            Some(parent) => parent,
+        };
+        // The parent is named by its dotted path, starting from the component's namespace.
+        let mut parts = parent.split('.');
+        let root = parts.next();
+        let mut path: Vec<String> = parts.map(ToString::to_string).collect();
+        let known_parent = !self.namespace.is_empty()
+            && root == Some(self.namespace.as_str())
+            && (path.is_empty() || self.modules.iter().any(|m| m.path == path));
+        if !known_parent {
+            bail!(
+                "namespace \"{}\" is nested inside unknown namespace \"{}\"",
+                defn.name,
+                parent
+            );
         }
-        self.namespace.push_str(&defn.name);
-        Ok(())
+        path.push(defn.name);
+        if self.modules.iter().any(|m| m.path == path) {
+            bail!("duplicate namespace definition: \"{}\"", path.join("."));
+        }
+        self.modules.push(Module { path: path.clone() });
+        Ok(path)
     }
 
     /// Called by `APIBuilder` impls to add a newly-parsed enum definition to the `ComponentInterface`.
@@ -491,10 +959,19 @@ impl<'ci> ComponentInterface {
     fn add_function_definition(&mut self, defn: Function) -> Result<()> {
         // Since functions are not a first-class type, we have to check for duplicates here
         // rather than relying on the type-finding pass to catch them.
-        if self.functions.iter().any(|f| f.name == defn.name) {
-            bail!("duplicate function definition: \"{}\"", defn.name);
+        if self
+            .functions
+            .iter()
+            .any(|f| f.name == defn.name && f.module_path == defn.module_path)
+        {
+            bail!(
+                "duplicate function definition: \"{}\"",
+                defn.qualified_name()
+            );
         }
-        if !matches!(self.types.get_type_definition(defn.name()), None) {
+        if defn.module_path.is_empty()
+            && !matches!(self.types.get_type_definition(defn.name()), None)
+        {
             bail!("Conflicting type definition for \"{}\"", defn.name());
         }
         self.functions.push(defn);
@@ -519,6 +996,297 @@ impl<'ci> ComponentInterface {
         self.errors.push(defn);
     }
 
+    /// Add the object of a `[Stream]` typedef, whose interface is declared by uniffi itself
+    /// rather than in the UDL.
+    fn add_stream_definition(&mut self, name: &str, item: Type) -> Result<()> {
+        let udl = object::stream_udl(name, &item);
+        use weedle::Parse;
+        let (_, defns) = weedle::Definitions::parse(udl.trim()).unwrap();
+        let mut obj: Object = match &defns[0] {
+            weedle::Definition::Interface(d) => d.convert(self)?,
+            _ => unreachable!(),
+        };
+        obj.stream_item = Some(item);
+        self.add_object_definition(obj);
+        Ok(())
+    }
+
+    /// Add the interfaces of the views returned by any `[Borrowed]` methods, which are
+    /// declared by uniffi itself rather than in the UDL.
+    fn add_borrowed_views(&mut self) -> Result<()> {
+        let views: BTreeSet<String> = self
+            .objects
+            .iter()
+            .flat_map(|obj| obj.methods.iter())
+            .filter(|meth| meth.is_borrowed())
+            .filter_map(|meth| match &meth.return_type {
+                Some(Type::Object(name)) => Some(name.clone()),
+                _ => None,
+            })
+            .collect();
+        if views.is_empty() {
+            return Ok(());
+        }
+        for name in views.iter() {
+            if self.types.get_type_definition(name).is_some() {
+                bail!(
+                    "[Borrowed] methods return a \"{}\", which conflicts with a type of the same name",
+                    name
+                );
+            }
+        }
+        use weedle::Parse;
+        let (_, defns) = weedle::Definitions::parse(object::BORROWED_VIEWS_UDL.trim()).unwrap();
+        let defns: Vec<_> = defns
+            .into_iter()
+            .filter(|defn| {
+                matches!(defn, weedle::Definition::Interface(d) if views.contains(d.identifier.0))
+            })
+            .collect();
+        self.types.add_type_definitions_from(defns.as_slice())?;
+        APIBuilder::process(&defns, self)?;
+        for obj in self.objects.iter_mut() {
+            obj.is_borrowed_view = views.contains(&obj.name);
+        }
+        Ok(())
+    }
+
+    /// Add the observer callback interface and the `add_observer` and `remove_observer`
+    /// methods of each `[Observable]` object, which are declared by uniffi itself rather than
+    /// in the UDL.
+    fn add_observers(&mut self) -> Result<()> {
+        let observable: Vec<(String, String)> = self
+            .objects
+            .iter()
+            .filter_map(|obj| Some((obj.name.clone(), obj.observable_snapshot.clone()?)))
+            .collect();
+        for (name, snapshot) in observable.iter() {
+            if self.get_record_definition(snapshot).is_none() {
+                bail!(
+                    "[Observable] interface \"{}\" needs a dictionary for its snapshots, and \"{}\" isn't one",
+                    name,
+                    snapshot
+                );
+            }
+            let observer = format!("{}Observer", name);
+            if self.types.get_type_definition(&observer).is_some() {
+                bail!(
+                    "[Observable] interface \"{}\" adds a \"{}\" callback interface, which conflicts with a type of the same name",
+                    name,
+                    observer
+                );
+            }
+            let udl = object::observer_udl(name, snapshot);
+            use weedle::Parse;
+            let (_, defns) = weedle::Definitions::parse(udl.trim()).unwrap();
+            let (callbacks, interfaces): (Vec<_>, Vec<_>) = defns
+                .into_iter()
+                .partition(|defn| matches!(defn, weedle::Definition::CallbackInterface(_)));
+            self.types.add_type_definitions_from(callbacks.as_slice())?;
+            APIBuilder::process(&callbacks, self)?;
+            self.add_methods_from(
+                name,
+                &interfaces,
+                "reserved on interfaces with the [Observable] attribute",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Add the listener callback interface and the `add_listener` and `remove_listener` methods
+    /// of each object with `[Events]`, which are declared by uniffi itself rather than in the
+    /// UDL.
+    fn add_events(&mut self) -> Result<()> {
+        let sources: Vec<(String, String)> = self
+            .objects
+            .iter()
+            .filter_map(|obj| Some((obj.name.clone(), obj.events_type.clone()?)))
+            .collect();
+        for (name, event) in sources.iter() {
+            if self.get_record_definition(event).is_none()
+                && self.get_enum_definition(event).is_none()
+            {
+                bail!(
+                    "[Events] interface \"{}\" needs a dictionary or an enum for its events, and \"{}\" isn't one",
+                    name,
+                    event
+                );
+            }
+            let listener = format!("{}Listener", name);
+            if self.types.get_type_definition(&listener).is_some() {
+                bail!(
+                    "[Events] interface \"{}\" adds a \"{}\" callback interface, which conflicts with a type of the same name",
+                    name,
+                    listener
+                );
+            }
+            let udl = object::events_udl(name, event);
+            use weedle::Parse;
+            let (_, defns) = weedle::Definitions::parse(udl.trim()).unwrap();
+            let (callbacks, interfaces): (Vec<_>, Vec<_>) = defns
+                .into_iter()
+                .partition(|defn| matches!(defn, weedle::Definition::CallbackInterface(_)));
+            self.types.add_type_definitions_from(callbacks.as_slice())?;
+            APIBuilder::process(&callbacks, self)?;
+            self.add_methods_from(
+                name,
+                &interfaces,
+                "reserved on interfaces with the [Events] attribute",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Add the `ServiceState` enum, and the state observer callback interface and lifecycle
+    /// methods of each `[Service]` object, which are declared by uniffi itself rather than in
+    /// the UDL.
+    fn add_services(&mut self) -> Result<()> {
+        if !self.has_services() {
+            return Ok(());
+        }
+        let services: Vec<String> = self
+            .objects
+            .iter()
+            .filter(|obj| obj.is_service)
+            .map(|obj| obj.name.clone())
+            .collect();
+        use weedle::Parse;
+        if self.types.get_type_definition("ServiceState").is_some() {
+            bail!("[Service] interfaces use a \"ServiceState\" enum, which conflicts with a type of the same name");
+        }
+        let (_, defns) = weedle::Definitions::parse(object::SERVICE_STATE_UDL.trim()).unwrap();
+        self.types.add_type_definitions_from(defns.as_slice())?;
+        APIBuilder::process(&defns, self)?;
+        for name in services.iter() {
+            let observer = format!("{}StateObserver", name);
+            if self.types.get_type_definition(&observer).is_some() {
+                bail!(
+                    "[Service] interface \"{}\" adds a \"{}\" callback interface, which conflicts with a type of the same name",
+                    name,
+                    observer
+                );
+            }
+            let udl = object::service_udl(name);
+            let (_, defns) = weedle::Definitions::parse(udl.trim()).unwrap();
+            let (callbacks, interfaces): (Vec<_>, Vec<_>) = defns
+                .into_iter()
+                .partition(|defn| matches!(defn, weedle::Definition::CallbackInterface(_)));
+            self.types.add_type_definitions_from(callbacks.as_slice())?;
+            APIBuilder::process(&callbacks, self)?;
+            self.add_methods_from(
+                name,
+                &interfaces,
+                "reserved on interfaces with the [Service] attribute",
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Add the `Scope` object, and the argument that takes it to each `[Scoped]` function and
+    /// method, which are declared by uniffi itself rather than in the UDL.
+    fn add_scopes(&mut self) -> Result<()> {
+        if !self.has_scopes() {
+            return Ok(());
+        }
+        if self.types.get_type_definition("Scope").is_some() {
+            bail!("[Scoped] functions and methods take a \"Scope\" interface, which conflicts with a type of the same name");
+        }
+        use weedle::Parse;
+        let (_, defns) = weedle::Definitions::parse(function::SCOPE_UDL.trim()).unwrap();
+        self.types.add_type_definitions_from(defns.as_slice())?;
+        APIBuilder::process(&defns, self)?;
+        let functions = self
+            .functions
+            .iter_mut()
+            .filter(|f| f.is_scoped())
+            .map(|f| &mut f.arguments);
+        let methods = self.objects.iter_mut().flat_map(|o| {
+            o.methods
+                .iter_mut()
+                .filter(|m| m.is_scoped())
+                .map(|m| &mut m.arguments)
+        });
+        for arguments in functions.chain(methods) {
+            if arguments.iter().any(|arg| arg.name == "scope") {
+                bail!("the argument name \"scope\" is reserved on [Scoped] functions and methods");
+            }
+            arguments.insert(0, function::scope_argument());
+        }
+        Ok(())
+    }
+
+    /// Add the page record and the method returning a page of the sequence for each
+    /// `[Paginated]` method, which are declared by uniffi itself rather than in the UDL.
+    fn add_paginated_methods(&mut self) -> Result<()> {
+        let paginated: Vec<Method> = self
+            .objects
+            .iter()
+            .flat_map(|obj| obj.methods.iter().filter(|meth| meth.is_paginated()))
+            .cloned()
+            .collect();
+        for meth in paginated.iter() {
+            let page = meth.page_record().unwrap();
+            if self.types.get_type_definition(&page).is_some() {
+                bail!(
+                    "[Paginated] method \"{}\" adds a \"{}\" dictionary, which conflicts with a type of the same name",
+                    meth.name(),
+                    page
+                );
+            }
+            if let Some(arg) = meth
+                .arguments()
+                .iter()
+                .find(|arg| arg.name() == "offset" || arg.name() == "limit")
+            {
+                bail!(
+                    "the argument name \"{}\" is reserved on [Paginated] methods",
+                    arg.name()
+                );
+            }
+            let udl = object::paginated_udl(meth);
+            use weedle::Parse;
+            let (_, defns) = weedle::Definitions::parse(udl.trim()).unwrap();
+            let (records, interfaces): (Vec<_>, Vec<_>) = defns
+                .into_iter()
+                .partition(|defn| matches!(defn, weedle::Definition::Dictionary(_)));
+            self.types.add_type_definitions_from(records.as_slice())?;
+            APIBuilder::process(&records, self)?;
+            self.add_methods_from(
+                &meth.object_name,
+                &interfaces,
+                &format!("reserved for the [Paginated] method \"{}\"", meth.name()),
+            )?;
+        }
+        Ok(())
+    }
+
+    // Add the methods of the synthesized interface definitions to the object of the same name,
+    // failing if they conflict with the methods it already has.
+    fn add_methods_from(
+        &mut self,
+        name: &str,
+        interfaces: &[weedle::Definition<'_>],
+        reserved: &str,
+    ) -> Result<()> {
+        for defn in interfaces.iter() {
+            if let weedle::Definition::Interface(d) = defn {
+                let extra: Object = d.convert(self)?;
+                let obj = self
+                    .objects
+                    .iter_mut()
+                    .find(|obj| obj.name == name)
+                    .unwrap();
+                for meth in extra.methods.iter() {
+                    if obj.methods.iter().any(|m| m.name == meth.name) {
+                        bail!("the method name \"{}\" is {}", meth.name, reserved);
+                    }
+                }
+                obj.methods.extend(extra.methods);
+            }
+        }
+        Ok(())
+    }
+
     /// Perform global consistency checks on the declared interface.
     ///
     /// This method checks for consistency problems in the declared interface
@@ -528,6 +1296,32 @@ impl<'ci> ComponentInterface {
         if self.namespace.is_empty() {
             bail!("missing namespace definition");
         }
+        // Nested namespaces become classes or modules in the bindings, alongside the types and
+        // functions of the component's namespace, so their names must not clash.
+        for module in self.modules.iter().filter(|m| m.path.len() == 1) {
+            if self.types.get_type_definition(module.name()).is_some()
+                || self
+                    .functions
+                    .iter()
+                    .any(|f| f.module_path.is_empty() && f.name == module.name())
+            {
+                bail!(
+                    "Namespace \"{}\" conflicts with a type or function of the same name",
+                    module.name()
+                );
+            }
+        }
+        // Batches are run through a single FFI function for the whole component.
+        if let Some(func) = self
+            .functions
+            .iter()
+            .find(|f| f.is_batchable() && !f.module_path.is_empty())
+        {
+            bail!(
+                "[Batchable] function \"{}\" must be declared in the component's namespace",
+                func.qualified_name()
+            );
+        }
         // To keep codegen tractable, enum variant names must not shadow type names.
         for e in self.enums.iter() {
             for variant in e.variants.iter() {
@@ -539,6 +1333,22 @@ impl<'ci> ComponentInterface {
                 }
             }
         }
+        // An enum with associated data can't be hashed in all of the foreign languages.
+        for t in self.types.iter_known_types() {
+            if let Type::Map(k, _) = t {
+                if let Type::Enum(name) = k.as_ref() {
+                    if self
+                        .get_enum_definition(name)
+                        .map_or(false, |e| !e.is_flat())
+                    {
+                        bail!(
+                            "Enum \"{}\" can't be used as a map key, because it has associated data",
+                            name
+                        )
+                    }
+                }
+            }
+        }
         Ok(())
     }
 
@@ -557,6 +1367,63 @@ impl<'ci> ComponentInterface {
         for callback in self.callback_interfaces.iter_mut() {
             callback.derive_ffi_funcs(&ci_prefix);
         }
+        self.check_ffi_func_names()
+    }
+
+    /// Check that everything exported can be told apart in the bindings' enum of exports.
+    ///
+    /// Each export is listed under its qualified name, and gets a case of the enum named
+    /// after it in the case of each language, so e.g. `counter_get()` and `Counter.get()`
+    /// would get the same case. Nor can a case take the name of the enum's own members.
+    fn check_exported_names(&self) -> Result<()> {
+        // The members of the generated enums, in snake case.
+        const RESERVED: &[&str] = &["all_cases", "kind", "raw_value", "signature_hash"];
+        let mut cases = HashMap::new();
+        for callable in crate::lockfile::exported_callables(self) {
+            let name = callable.qualified_name();
+            let case = name.to_snake_case();
+            if RESERVED.contains(&case.as_str()) {
+                bail!(
+                    "\"{}\" can't be exported, because the enum of exports uses its name",
+                    name
+                );
+            }
+            if let Some(other) = cases.insert(case, name.clone()) {
+                if other == name {
+                    bail!("\"{}\" is exported more than once", name);
+                }
+                bail!(
+                    "\"{}\" and \"{}\" would have the same name in the enum of exports",
+                    other,
+                    name
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that no two FFI functions ended up with the same symbol name.
+    ///
+    /// The names of functions in nested namespaces are joined to the namespace with a single
+    /// underscore, so e.g. `sync_hello()` and `sync.hello()` would otherwise export the same symbol.
+    fn check_ffi_func_names(&self) -> Result<()> {
+        let mut functions = HashMap::new();
+        for func in self.functions.iter() {
+            if let Some(other) = functions.insert(func.ffi_func.name(), func.qualified_name()) {
+                bail!(
+                    "functions \"{}\" and \"{}\" both have the FFI symbol \"{}\"",
+                    other,
+                    func.qualified_name(),
+                    func.ffi_func.name()
+                );
+            }
+        }
+        let mut seen = HashSet::new();
+        for func in self.iter_ffi_function_definitions() {
+            if !seen.insert(func.name().to_string()) {
+                bail!("FFI symbol \"{}\" is defined more than once", func.name());
+            }
+        }
         Ok(())
     }
 }
@@ -574,12 +1441,14 @@ impl FromStr for ComponentInterface {
 impl Hash for ComponentInterface {
     fn hash<H: Hasher>(&self, state: &mut H) {
         // We can't hash `self.types`, but its contents are implied by the other fields
-        // anyway, so it's safe to ignore it.
+        // anyway, so it's safe to ignore it. `self.custom_types` only changes which trait the
+        // Rust code implements, and not the FFI, so it's ignored too.
         self.uniffi_version.hash(state);
         self.namespace.hash(state);
         self.enums.hash(state);
         self.records.hash(state);
         self.functions.hash(state);
+        self.modules.hash(state);
         self.objects.hash(state);
         self.callback_interfaces.hash(state);
         self.errors.hash(state);
@@ -754,8 +1623,20 @@ impl APIBuilder for weedle::Definition<'_> {
                 let obj = d.convert(ci)?;
                 ci.add_callback_interface_definition(obj);
             }
-            // everything needed for typedefs is done in finder.rs.
-            weedle::Definition::Typedef(_) => {}
+            weedle::Definition::Typedef(d) => {
+                // Apart from the objects of `[Stream]` typedefs, and noting which typedefs are
+                // `[Custom]`, everything needed for typedefs is done in finder.rs.
+                let attrs = attributes::TypedefAttributes::try_from(d.attributes.as_ref())?;
+                if attrs.is_custom() {
+                    ci.custom_types.insert(d.identifier.0.to_string());
+                }
+                if attrs.is_stream() {
+                    match ci.resolve_type_expression(&d.type_)? {
+                        Type::Sequence(item) => ci.add_stream_definition(d.identifier.0, *item)?,
+                        _ => unreachable!("finder.rs checks that streams are sequences"),
+                    }
+                }
+            }
             _ => bail!("don't know how to deal with {:?}", self),
         }
         Ok(())
@@ -840,6 +1721,275 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_checksum_ffi_function_name_is_stable() {
+        // The bindings have to be able to find the checksum before they know that it matches.
+        let ci1 = ComponentInterface::from_webidl(UDL1).unwrap();
+        let ci2 = ComponentInterface::from_webidl(UDL2).unwrap();
+        assert_eq!(ci1.ffi_checksum().name(), "ffi_foobar_uniffi_checksum");
+        assert_eq!(ci2.ffi_checksum().name(), "ffi_hello_uniffi_checksum");
+        assert_eq!(
+            ci1.ffi_component_info().name(),
+            "ffi_foobar_uniffi_component_info"
+        );
+        assert_eq!(
+            ci2.ffi_component_info().name(),
+            "ffi_hello_uniffi_component_info"
+        );
+        assert!(ci1
+            .iter_ffi_function_definitions()
+            .iter()
+            .any(|f| f.name() == "ffi_foobar_uniffi_checksum"));
+    }
+
+    #[test]
+    fn test_reset_ffi_function() {
+        let ci = ComponentInterface::from_webidl(UDL1).unwrap();
+        let reset = ci.ffi_reset();
+        assert!(reset
+            .name()
+            .starts_with(&format!("ffi_{}_", ci.ffi_namespace())));
+        assert!(reset.name().ends_with("_uniffi_reset"));
+        assert!(reset.arguments().is_empty());
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .iter()
+            .any(|f| f.name() == reset.name()));
+    }
+
+    #[test]
+    fn test_shutdown_ffi_function() {
+        let ci = ComponentInterface::from_webidl(UDL1).unwrap();
+        let shutdown = ci.ffi_shutdown();
+        assert!(shutdown.name().ends_with("_uniffi_shutdown"));
+        assert!(shutdown.arguments().is_empty());
+        assert!(shutdown.return_type().is_none());
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .iter()
+            .any(|f| f.name() == shutdown.name()));
+    }
+
+    #[test]
+    fn test_warmup_ffi_function() {
+        let ci = ComponentInterface::from_webidl(UDL1).unwrap();
+        let warmup = ci.ffi_warmup();
+        assert!(warmup.name().ends_with("_uniffi_warmup"));
+        assert!(warmup.arguments().is_empty());
+        assert!(warmup.return_type().is_none());
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .iter()
+            .any(|f| f.name() == warmup.name()));
+    }
+
+    #[test]
+    fn test_memory_pressure_ffi_function() {
+        let ci = ComponentInterface::from_webidl(UDL1).unwrap();
+        let func = ci.ffi_on_memory_pressure();
+        assert!(func.name().ends_with("_uniffi_on_memory_pressure"));
+        assert_eq!(func.arguments().len(), 1);
+        assert_eq!(func.arguments()[0].type_(), FFIType::Int32);
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .iter()
+            .any(|f| f.name() == func.name()));
+    }
+
+    #[test]
+    fn test_invoke_ffi_functions() {
+        let ci = ComponentInterface::from_webidl(UDL1).unwrap();
+        assert!(ci.ffi_invoke().is_none());
+        assert!(ci.ffi_invoke_signatures().is_none());
+
+        const UDL: &str = r#"
+            namespace test {
+                [Invokable]
+                string greet(string name, u32? times);
+                [Invokable, Throws=Oops]
+                void reload();
+                void hidden();
+            };
+            [Parent=test]
+            namespace tools {
+                [Invokable]
+                sequence<u8> pack(record<DOMString, i32> counts);
+            };
+            [Error]
+            enum Oops { "Bad" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert_eq!(
+            ci.invoke_signatures(),
+            "greet\tstring\t\tname:string\ttimes:u32?\n\
+             reload\t\tOops\n\
+             tools.pack\tsequence<u8>\t\tcounts:record<DOMString, i32>"
+        );
+        let func = ci.ffi_invoke().unwrap();
+        assert!(func.name().ends_with("_uniffi_invoke"));
+        assert_eq!(func.arguments().len(), 2);
+        let functions = ci.iter_ffi_function_definitions();
+        assert!(functions.iter().any(|f| f.name() == func.name()));
+        assert!(functions
+            .iter()
+            .any(|f| f.name().ends_with("_uniffi_invoke_signatures")));
+    }
+
+    #[test]
+    fn test_batch_ffi_function() {
+        let ci = ComponentInterface::from_webidl(UDL1).unwrap();
+        assert!(ci.ffi_batch().is_none());
+
+        const UDL: &str = r#"
+            namespace test {
+                [Batchable]
+                string get_name();
+                [Batchable, Throws=Oops]
+                u32 get_count(u32 limit);
+                void reload();
+            };
+            [Error]
+            enum Oops { "Bad" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let batchable = ci.iter_batchable_function_definitions();
+        assert_eq!(
+            batchable.iter().map(|f| f.name()).collect::<Vec<_>>(),
+            vec!["get_name", "get_count"]
+        );
+        let func = ci.ffi_batch().unwrap();
+        assert!(func.name().ends_with("_uniffi_batch"));
+        assert_eq!(func.arguments().len(), 1);
+        assert_eq!(func.arguments()[0].type_(), FFIType::RustBuffer);
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .iter()
+            .any(|f| f.name() == func.name()));
+
+        const NESTED: &str = r#"
+            namespace test {};
+            [Parent=test]
+            namespace sync {
+                [Batchable]
+                u32 get_count();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(NESTED).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Batchable] function \"sync.get_count\" must be declared in the component's namespace"
+        );
+    }
+
+    #[test]
+    fn test_colliding_ffi_symbols() {
+        const UDL: &str = r#"
+            namespace test {
+                void sync_hello();
+            };
+            [Parent=test]
+            namespace sync {
+                void hello();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("functions \"sync_hello\" and \"sync.hello\" both have the FFI symbol"));
+
+        const UDL2: &str = r#"
+            namespace test {
+                u32 Counter_get();
+            };
+            interface Counter {
+                u32 get();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert!(err
+            .to_string()
+            .ends_with("_Counter_get\" is defined more than once"));
+    }
+
+    #[test]
+    fn test_colliding_exported_names() {
+        const UDL: &str = r#"
+            namespace test {
+                u32 counter_get();
+            };
+            interface Counter {
+                u32 get();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "\"counter_get\" and \"Counter.get\" would have the same name in the enum of exports"
+        );
+
+        const UDL2: &str = r#"
+            namespace test {};
+            [Parent=test]
+            namespace Counter {
+                u32 get();
+            };
+            interface Counter {
+                u32 get();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "\"Counter.get\" is exported more than once"
+        );
+
+        const UDL3: &str = r#"
+            namespace test {
+                string signature_hash();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "\"signature_hash\" can't be exported, because the enum of exports uses its name"
+        );
+    }
+
+    #[test]
+    fn test_external_interfaces() {
+        const UDL: &str = r#"
+            namespace app {
+                Session open(Account account);
+                void close(Session session, Session? other);
+            };
+            [ExternalInterface="auth-core"]
+            typedef extern Session;
+            [ExternalInterface="accounts"]
+            typedef extern Account;
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert_eq!(
+            ci.iter_external_interfaces(),
+            vec![
+                ("Account".to_string(), "accounts".to_string()),
+                ("Session".to_string(), "auth-core".to_string())
+            ]
+        );
+        assert_eq!(
+            ci.iter_external_interface_crates(),
+            vec!["accounts".to_string(), "auth-core".to_string()]
+        );
+        // The objects are passed as pointers, and there's a checksum to check for each crate.
+        let open = ci.get_function_definition("open").unwrap().ffi_func();
+        assert_eq!(open.return_type(), Some(&FFIType::RustArcPtr));
+        let name = ci.ffi_external_checksum("auth-core").name().to_string();
+        assert!(name.ends_with("_uniffi_checksum_of_auth_core"));
+        assert!(ci
+            .iter_ffi_function_definitions()
+            .iter()
+            .any(|f| f.name() == name));
+    }
+
     #[test]
     fn test_duplicate_type_names_are_an_error() {
         const UDL: &str = r#"
@@ -909,6 +2059,146 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_async_functions_and_methods() {
+        const UDL: &str = r#"
+            namespace test {
+                [Async, Throws=FetchError]
+                string fetch(string url);
+                void ping();
+            };
+            interface Feed {
+                [Async]
+                u32 refresh();
+                [Async, Cancellable]
+                void watch();
+            };
+            [Error]
+            enum FetchError { "Offline" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert!(ci.has_async_functions());
+        let func = ci.get_function_definition("fetch").unwrap();
+        assert!(func.is_async());
+        assert!(!func.is_cancellable());
+        // The FFI function only starts the call, which completes through its handle.
+        let args: Vec<_> = func
+            .ffi_func()
+            .arguments()
+            .iter()
+            .map(|a| a.name())
+            .collect();
+        assert_eq!(args, vec!["url", "uniffi_completion"]);
+        assert!(func.ffi_func().return_type().is_none());
+        let methods = ci.get_object_definition("Feed").unwrap().methods();
+        assert_eq!(methods[0].ffi_func().arguments().len(), 2);
+        assert!(methods[0].ffi_func().return_type().is_none());
+        // The token is for the Rust implementation, so the FFI function doesn't take one.
+        assert!(methods[1].is_cancellable());
+        assert_eq!(methods[1].ffi_func().arguments().len(), 2);
+        let init = ci.ffi_async_init().unwrap();
+        assert_eq!(init.name(), "ffi_test_uniffi_async_init");
+        assert_eq!(init.arguments()[0].type_(), FFIType::AsyncCompletion);
+        let cancel = ci.ffi_async_cancel().unwrap();
+        assert_eq!(cancel.name(), "ffi_test_uniffi_cancel");
+        assert_eq!(cancel.arguments()[0].type_(), FFIType::UInt64);
+        let names: Vec<_> = ci
+            .iter_ffi_function_definitions()
+            .into_iter()
+            .map(|f| f.name().to_string())
+            .collect();
+        assert!(names.contains(&init.name().to_string()));
+        assert!(names.contains(&cancel.name().to_string()));
+
+        let ci = ComponentInterface::from_webidl("namespace test { void ping(); };").unwrap();
+        assert!(!ci.has_async_functions());
+        assert!(ci.ffi_async_init().is_none());
+        assert!(ci.ffi_async_cancel().is_none());
+
+        let err = |udl: &str| {
+            ComponentInterface::from_webidl(&format!("namespace test{{}}; {}", udl))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err("[MainThread] interface View { [Async] void draw(); };"),
+            "[Async] method \"draw\" can't be on a [MainThread] interface"
+        );
+        assert_eq!(
+            err("interface Feed { [Async] void refresh(); [Self=ByMut] void clear(); };"),
+            "[Async] method \"refresh\" can't be on an interface with [Self=ByMut] methods"
+        );
+        assert_eq!(
+            err("[Trait] interface Feed { [Async] void refresh(); };"),
+            "[Async] method \"refresh\" can't be on a [Trait] interface"
+        );
+        assert_eq!(
+            err("callback interface Feed { [Async, Cancellable] void refresh(); };"),
+            "[Cancellable] is only supported on functions and interface methods"
+        );
+    }
+
+    #[test]
+    fn test_scoped_functions_and_methods() {
+        const UDL: &str = r#"
+            namespace test {
+                [Scoped]
+                void prefetch(string url);
+                void ping();
+            };
+            interface Feed {
+                [Scoped, Throws=FeedError]
+                void refresh(u32 limit);
+            };
+            [Error]
+            enum FeedError { "Offline" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert!(ci.has_scopes());
+        let scope = ci.get_object_definition("Scope").unwrap();
+        let names: Vec<_> = scope.methods().iter().map(|m| m.name()).collect();
+        assert_eq!(names, vec!["cancel", "is_cancelled", "join"]);
+        assert_eq!(scope.constructors().len(), 1);
+
+        let func = ci.get_function_definition("prefetch").unwrap();
+        assert!(func.is_scoped());
+        let args: Vec<_> = func.arguments().iter().map(|a| a.name()).collect();
+        assert_eq!(args, vec!["scope", "url"]);
+        assert_eq!(func.arguments()[0].type_(), Type::Object("Scope".into()));
+        assert_eq!(
+            ci.get_function_definition("ping")
+                .unwrap()
+                .arguments()
+                .len(),
+            0
+        );
+        let meth = &ci.get_object_definition("Feed").unwrap().methods()[0];
+        let args: Vec<_> = meth.arguments().iter().map(|a| a.name()).collect();
+        assert_eq!(args, vec!["scope", "limit"]);
+
+        let ci = ComponentInterface::from_webidl("namespace test { void ping(); };").unwrap();
+        assert!(!ci.has_scopes());
+        assert!(ci.get_object_definition("Scope").is_none());
+
+        let err = |udl: &str| {
+            ComponentInterface::from_webidl(&format!("namespace test{{ {} }};", udl))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err("[Scoped] void prefetch(u32 scope);"),
+            "the argument name \"scope\" is reserved on [Scoped] functions and methods"
+        );
+        assert_eq!(
+            err("[Scoped] void prefetch(); }; interface Scope {"),
+            "[Scoped] functions and methods take a \"Scope\" interface, which conflicts with a type of the same name"
+        );
+        assert_eq!(
+            err("}; callback interface Loader { [Scoped] void load(); "),
+            "[Scoped] is only supported on functions and interface methods"
+        );
+    }
+
     #[test]
     fn test_contains_optional_types() {
         let mut ci = ComponentInterface {
@@ -955,11 +2245,47 @@ mod test {
         // check that `contains_map_types` returns true when there is a Map type in the interface
         assert!(ci
             .types
-            .add_type_definition("Map{}", Type::Map(Box::new(Type::Boolean)))
+            .add_type_definition(
+                "Map{}",
+                Type::Map(Box::new(Type::String), Box::new(Type::Boolean))
+            )
             .is_ok());
         assert!(ci.contains_map_types());
     }
 
+    #[test]
+    fn test_map_keys() {
+        const UDL: &str = r#"
+            namespace test{
+                record<u64, string> names_by_id(record<Color, u32> counts);
+            };
+            enum Color { "Red", "Green" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let func = ci.get_function_definition("names_by_id").unwrap();
+        assert_eq!(
+            func.return_type(),
+            Some(&Type::Map(Box::new(Type::UInt64), Box::new(Type::String)))
+        );
+        assert_eq!(
+            func.arguments()[0].type_(),
+            Type::Map(Box::new(Type::Enum("Color".into())), Box::new(Type::UInt32))
+        );
+
+        const UDL_WITH_DATA: &str = r#"
+            namespace test{
+                void paint(record<Shape, string> labels);
+            };
+            [Enum]
+            interface Shape { Circle(f64 radius); Square(); };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL_WITH_DATA).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Enum \"Shape\" can't be used as a map key, because it has associated data"
+        );
+    }
+
     #[test]
     fn test_no_infinite_recursion_when_walking_types() {
         const UDL: &str = r#"
@@ -60,9 +60,48 @@
 //!
 //! Yeah, it's a bit of an awkward fit syntactically, but it's enough
 //! to get us up and running for a first version of this tool.
+//!
+//! Components with a large API can group their functions into further namespaces, which
+//! are nested inside the component's namespace using the `[Parent]` attribute. WebIDL
+//! identifiers can't contain dots, so the parent is named by its dotted path:
+//!
+//! ```
+//! # let ci = uniffi_bindgen::interface::ComponentInterface::from_webidl(r##"
+//! namespace example {};
+//! [Parent=example]
+//! namespace sync {};
+//! [Parent="example.sync"]
+//! namespace engine {
+//!   void start();
+//! };
+//! # "##)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! Declares a function `start` in the module `sync.engine`, which the Rust code
+//! provides as `sync::engine::start`:
+//!
+//! ```
+//! # let ci = uniffi_bindgen::interface::ComponentInterface::from_webidl(r##"
+//! # namespace example {};
+//! # [Parent=example]
+//! # namespace sync {};
+//! # [Parent="example.sync"]
+//! # namespace engine {
+//! #   void start();
+//! # };
+//! # "##)?;
+//! let func = ci.get_function_definition("sync.engine.start").unwrap();
+//! assert_eq!(func.module_path(), vec!["sync", "engine"]);
+//! assert_eq!(ci.iter_module_definitions().len(), 2);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use std::convert::TryFrom;
 
 use anyhow::{bail, Result};
 
+use super::attributes::NamespaceAttributes;
 use super::{APIBuilder, APIConverter, ComponentInterface};
 
 /// A namespace is currently just a name, but might hold more metadata about
@@ -71,17 +110,52 @@ use super::{APIBuilder, APIConverter, ComponentInterface};
 #[derive(Debug, Clone, Hash)]
 pub struct Namespace {
     pub(super) name: String,
+    // The dotted path of the namespace this one is nested inside, if any.
+    pub(super) parent: Option<String>,
+}
+
+/// A namespace nested inside the component's namespace, used to group related functions.
+///
+/// In the foreign-language bindings this becomes a nested Kotlin `object`, a Swift `enum`
+/// with static functions, a Python submodule or a Ruby module.
+#[derive(Debug, Clone, Hash)]
+pub struct Module {
+    pub(super) path: Vec<String>,
+}
+
+impl Module {
+    pub fn name(&self) -> &str {
+        self.path.last().expect("modules always have a name")
+    }
+
+    /// The names of the modules leading to this one, starting just below the component's
+    /// namespace and ending with this module's own name.
+    pub fn path(&self) -> Vec<&str> {
+        self.path.iter().map(String::as_str).collect()
+    }
+
+    /// The path of the module this one is nested inside, which is empty if this module is
+    /// nested directly inside the component's namespace.
+    pub fn parent_path(&self) -> Vec<&str> {
+        let path = self.path();
+        path[..path.len() - 1].to_vec()
+    }
+
+    /// The dotted path of this module, e.g. `sync.engine`.
+    pub fn qualified_name(&self) -> String {
+        self.path.join(".")
+    }
 }
 
 impl APIBuilder for weedle::NamespaceDefinition<'_> {
     fn process(&self, ci: &mut ComponentInterface) -> Result<()> {
-        if self.attributes.is_some() {
-            bail!("namespace attributes are not supported yet");
-        }
-        ci.add_namespace_definition(Namespace {
+        let attributes = NamespaceAttributes::try_from(self.attributes.as_ref())?;
+        let module_path = ci.add_namespace_definition(Namespace {
             name: self.identifier.0.to_string(),
+            parent: attributes.get_parent().map(ToString::to_string),
         })?;
-        for func in self.members.body.convert(ci)? {
+        for mut func in self.members.body.convert(ci)? {
+            func.module_path = module_path.clone();
             ci.add_function_definition(func)?;
         }
         Ok(())
@@ -129,4 +203,89 @@ mod test {
         let err = ComponentInterface::from_webidl(UDL).unwrap_err();
         assert_eq!(err.to_string(), "duplicate namespace definition");
     }
+
+    #[test]
+    fn test_nested_namespaces() {
+        const UDL: &str = r#"
+            namespace foobar{
+                boolean hello();
+            };
+            [Parent=foobar]
+            namespace sync{
+                void hello();
+            };
+            [Parent="foobar.sync"]
+            namespace engine{
+                void start();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert_eq!(ci.namespace(), "foobar");
+        assert_eq!(ci.iter_function_definitions().len(), 3);
+        assert_eq!(ci.iter_namespace_function_definitions().len(), 1);
+        let modules = ci.iter_module_definitions();
+        assert_eq!(
+            modules
+                .iter()
+                .map(|m| m.qualified_name())
+                .collect::<Vec<_>>(),
+            vec!["sync", "sync.engine"]
+        );
+        assert_eq!(modules[1].name(), "engine");
+        assert_eq!(modules[1].parent_path(), vec!["sync"]);
+        assert_eq!(ci.iter_top_level_module_definitions().len(), 1);
+        assert_eq!(ci.iter_child_module_definitions(&modules[0]).len(), 1);
+
+        // Functions with the same name can live in different modules.
+        let hello = ci.get_function_definition("hello").unwrap();
+        assert!(hello.module_path().is_empty());
+        let sync_hello = ci.get_function_definition("sync.hello").unwrap();
+        assert_eq!(sync_hello.module_path(), vec!["sync"]);
+        assert_eq!(sync_hello.qualified_name(), "sync.hello");
+        assert_ne!(hello.ffi_func().name(), sync_hello.ffi_func().name());
+        let names: Vec<_> = ci
+            .iter_module_function_definitions(&modules[1])
+            .into_iter()
+            .map(|f| f.qualified_name())
+            .collect();
+        assert_eq!(names, vec!["sync.engine.start"]);
+    }
+
+    #[test]
+    fn test_rejects_unknown_parent_namespaces() {
+        const UDL: &str = r#"
+            namespace foobar{};
+            [Parent="foobar.sync"]
+            namespace engine{};
+        "#;
+        let err = ComponentInterface::from_webidl(UDL).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "namespace \"engine\" is nested inside unknown namespace \"foobar.sync\""
+        );
+
+        const UDL2: &str = r#"
+            [Parent=foobar]
+            namespace engine{};
+            namespace foobar{};
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "namespace \"engine\" is nested inside unknown namespace \"foobar\""
+        );
+
+        const UDL3: &str = r#"
+            namespace foobar{};
+            [Parent=foobar]
+            namespace engine{};
+            [Parent=foobar]
+            namespace engine{};
+        "#;
+        let err = ComponentInterface::from_webidl(UDL3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "duplicate namespace definition: \"engine\""
+        );
+    }
 }
@@ -62,13 +62,157 @@ use std::convert::TryFrom;
 use std::hash::{Hash, Hasher};
 
 use anyhow::{bail, Result};
+use heck::CamelCase;
 
 use super::attributes::{ConstructorAttributes, InterfaceAttributes, MethodAttributes};
 use super::ffi::{FFIArgument, FFIFunction, FFIType};
-use super::function::Argument;
+use super::function::{async_ffi_func, Argument};
+use super::literal::{Literal, Radix};
 use super::types::{IterTypes, Type, TypeIterator};
 use super::{APIConverter, ComponentInterface};
 
+/// The name of the view returned by `[Borrowed]` methods that return a `string`.
+pub(super) const BORROWED_STRING: &str = "BorrowedString";
+/// The name of the view returned by `[Borrowed]` methods that return a `sequence<u8>`.
+pub(super) const BORROWED_BYTES: &str = "BorrowedBytes";
+
+/// The interfaces of the views returned by `[Borrowed]` methods, which are added to the
+/// components that use them. They're implemented by the `uniffi::BorrowedString` and
+/// `uniffi::BorrowedBytes` structs in the runtime, and offsets into them are in bytes.
+pub(super) const BORROWED_VIEWS_UDL: &str = r#"
+    interface BorrowedString {
+        u64 byte_length();
+        string substring(u64 start, u64 end);
+        string contents();
+    };
+    interface BorrowedBytes {
+        u64 byte_length();
+        sequence<u8> slice(u64 start, u64 end);
+        sequence<u8> contents();
+    };
+"#;
+
+/// The UDL for what `[Observable=Snapshot]` adds to an object: a callback interface for its
+/// observers, which are told the name of each field that changes along with a snapshot of
+/// the new state, and methods for adding and removing them. The Rust struct provides its
+/// observers by implementing `uniffi::Observable`.
+pub(super) fn observer_udl(object: &str, snapshot: &str) -> String {
+    format!(
+        r#"
+        callback interface {object}Observer {{
+            void on_changed(string field, {snapshot} snapshot);
+        }};
+        interface {object} {{
+            u64 add_observer({object}Observer observer);
+            void remove_observer(u64 id);
+        }};
+        "#,
+        object = object,
+        snapshot = snapshot
+    )
+}
+
+/// The UDL for what `[Events=Event]` adds to an object: a callback interface for its listeners,
+/// which are given each event that it emits, and methods for adding and removing them. The Rust
+/// struct provides its listeners by implementing `uniffi::EventSource`.
+pub(super) fn events_udl(object: &str, event: &str) -> String {
+    format!(
+        r#"
+        callback interface {object}Listener {{
+            void on_event({event} event);
+        }};
+        interface {object} {{
+            u64 add_listener({object}Listener listener);
+            void remove_listener(u64 id);
+        }};
+        "#,
+        object = object,
+        event = event
+    )
+}
+
+/// The UDL for what `[Service]` adds to an object: methods for starting and stopping it and
+/// asking whether it's running, and a callback interface for observers of its state, which
+/// are told each `ServiceState` it moves into. The Rust struct provides its lifecycle by
+/// implementing `uniffi::Service`.
+pub(super) fn service_udl(object: &str) -> String {
+    format!(
+        r#"
+        callback interface {object}StateObserver {{
+            void on_state_changed(ServiceState state);
+        }};
+        interface {object} {{
+            void start();
+            void stop();
+            boolean is_running();
+            u64 add_state_observer({object}StateObserver observer);
+            void remove_state_observer(u64 id);
+        }};
+        "#,
+        object = object
+    )
+}
+
+/// The UDL for the states that `[Service]` objects move through, which is declared once for
+/// all of them. The scaffolding re-exports `uniffi::ServiceState` to implement it.
+pub(super) const SERVICE_STATE_UDL: &str = r#"
+    enum ServiceState { "Stopped", "Starting", "Running", "Stopping" };
+"#;
+
+/// The UDL for the object that a `[Stream]` typedef declares, whose one method takes up to
+/// `max` more items from the stream, and an empty sequence once there are none left. The
+/// scaffolding implements the object as a `uniffi::Stream` of the items.
+pub(super) fn stream_udl(name: &str, item: &Type) -> String {
+    format!(
+        r#"
+        interface {name} {{
+            sequence<{item}> next_chunk(u32 max);
+        }};
+        "#,
+        name = name,
+        item = crate::lockfile::type_decl(item)
+    )
+}
+
+/// The UDL for what `[Paginated]` adds for a method returning a sequence: a record holding one
+/// page of the sequence along with its total length, and a method that takes the method's own
+/// arguments, without their default values, followed by where the page starts and how long it
+/// can be. The scaffolding implements the method by calling the original one.
+pub(super) fn paginated_udl(method: &Method) -> String {
+    let args: Vec<String> = method
+        .arguments
+        .iter()
+        .map(|arg| {
+            format!(
+                "{}{} {}, ",
+                if arg.by_ref { "[ByRef] " } else { "" },
+                crate::lockfile::type_decl(&arg.type_),
+                arg.name
+            )
+        })
+        .collect();
+    format!(
+        r#"
+        dictionary {page} {{
+            sequence<{item}> items;
+            u64 total;
+        }};
+        interface {object} {{
+            {throws}{page} {name}({args}u64 offset, u32 limit);
+        }};
+        "#,
+        page = method.page_record().unwrap(),
+        item = crate::lockfile::type_decl(method.page_item_type().unwrap()),
+        object = method.object_name,
+        throws = match method.throws() {
+            Some(e) => format!("[Throws={}] ", e),
+            None => "".into(),
+        },
+        name = method.page_method_name(),
+        args = args.concat()
+    )
+}
+
 /// An "object" is an opaque type that can be instantiated and passed around by reference,
 /// have methods called on it, and so on - basically your classic Object Oriented Programming
 /// type of deal, except without elaborate inheritence hierarchies.
@@ -89,6 +233,24 @@ pub struct Object {
     pub(super) constructors: Vec<Constructor>,
     pub(super) methods: Vec<Method>,
     pub(super) ffi_func_free: FFIFunction,
+    // Only populated for objects with the `[Clone]` attribute.
+    pub(super) ffi_func_clone: Option<FFIFunction>,
+    // Only populated for objects with the `[WeakRefs]` attribute.
+    pub(super) ffi_func_downgrade: Option<FFIFunction>,
+    pub(super) ffi_func_upgrade: Option<FFIFunction>,
+    pub(super) ffi_func_weak_free: Option<FFIFunction>,
+    pub(super) releases_callbacks: bool,
+    pub(super) is_closeable: bool,
+    pub(super) is_opaque_token: bool,
+    pub(super) foreign_extension: Option<String>,
+    pub(super) observable_snapshot: Option<String>,
+    pub(super) events_type: Option<String>,
+    pub(super) is_service: bool,
+    pub(super) is_borrowed_view: bool,
+    // Only populated for the objects of `[Stream]` typedefs.
+    pub(super) stream_item: Option<Type>,
+    pub(super) is_trait: bool,
+    pub(super) threading: Threading,
     pub(super) uses_deprecated_threadsafe_attribute: bool,
 }
 
@@ -99,6 +261,21 @@ impl Object {
             constructors: Default::default(),
             methods: Default::default(),
             ffi_func_free: Default::default(),
+            ffi_func_clone: None,
+            ffi_func_downgrade: None,
+            ffi_func_upgrade: None,
+            ffi_func_weak_free: None,
+            releases_callbacks: false,
+            is_closeable: false,
+            is_opaque_token: false,
+            foreign_extension: None,
+            observable_snapshot: None,
+            events_type: None,
+            is_service: false,
+            is_borrowed_view: false,
+            stream_item: None,
+            is_trait: false,
+            threading: Threading::Threadsafe,
             uses_deprecated_threadsafe_attribute: false,
         }
     }
@@ -136,6 +313,139 @@ impl Object {
         &self.ffi_func_free
     }
 
+    /// The FFI function that makes a new, independent copy of an instance of a `[Clone]` object.
+    pub fn ffi_object_clone(&self) -> Option<&FFIFunction> {
+        self.ffi_func_clone.as_ref()
+    }
+
+    pub fn is_cloneable(&self) -> bool {
+        self.ffi_func_clone.is_some()
+    }
+
+    /// Whether foreign code can hold weak references to this object, as requested by the
+    /// `[WeakRefs]` attribute. A weak reference doesn't keep the Rust object alive, but can be
+    /// upgraded back into the object as long as something else does.
+    pub fn has_weak_refs(&self) -> bool {
+        self.ffi_func_downgrade.is_some()
+    }
+
+    /// The FFI function that makes a weak reference to an instance of a `[WeakRefs]` object.
+    pub fn ffi_object_downgrade(&self) -> Option<&FFIFunction> {
+        self.ffi_func_downgrade.as_ref()
+    }
+
+    /// The FFI function that turns a weak reference back into the object, whose pointer it
+    /// returns as a `u64`, or zero if the object is gone.
+    pub fn ffi_object_upgrade(&self) -> Option<&FFIFunction> {
+        self.ffi_func_upgrade.as_ref()
+    }
+
+    /// The FFI function that releases a weak reference.
+    pub fn ffi_object_weak_free(&self) -> Option<&FFIFunction> {
+        self.ffi_func_weak_free.as_ref()
+    }
+
+    /// Whether the callback interfaces passed to this object's constructors are released when
+    /// the object is destroyed, as requested by the `[ReleaseCallbacks]` attribute.
+    pub fn releases_callbacks(&self) -> bool {
+        self.releases_callbacks
+    }
+
+    /// Whether foreign code can close this object explicitly, as requested by the `[Closeable]`
+    /// attribute. Once closed, the bindings refuse to pass its handle to Rust again.
+    pub fn is_closeable(&self) -> bool {
+        self.is_closeable
+    }
+
+    /// Whether this is an `[OpaqueToken]`, which has no constructors or methods, so that the
+    /// bindings only need a small handle class that can be compared and released.
+    pub fn is_opaque_token(&self) -> bool {
+        self.is_opaque_token
+    }
+
+    /// The hand-written foreign class or protocol that the bindings for this object extend,
+    /// as named by the `[ForeignExtension]` attribute.
+    pub fn foreign_extension(&self) -> Option<&str> {
+        self.foreign_extension.as_deref()
+    }
+
+    /// The record that's sent to this object's observers, as named by the `[Observable]`
+    /// attribute.
+    pub fn observable_snapshot(&self) -> Option<&str> {
+        self.observable_snapshot.as_deref()
+    }
+
+    /// The callback interface for this object's observers, if it's `[Observable]`.
+    pub fn observer_interface(&self) -> Option<String> {
+        self.observable_snapshot
+            .as_ref()
+            .map(|_| format!("{}Observer", self.name))
+    }
+
+    /// The class that holds the latest snapshot of this object for view models, if it's
+    /// `[Observable]` and the bindings are configured to generate one.
+    pub fn observable_model(&self) -> Option<String> {
+        self.observable_snapshot
+            .as_ref()
+            .map(|_| format!("{}Model", self.name))
+    }
+
+    /// The type of the events that this object emits to its listeners, as named by the
+    /// `[Events]` attribute.
+    pub fn events_type(&self) -> Option<&str> {
+        self.events_type.as_deref()
+    }
+
+    /// The callback interface for this object's listeners, if it has `[Events]`.
+    pub fn listener_interface(&self) -> Option<String> {
+        self.events_type
+            .as_ref()
+            .map(|_| format!("{}Listener", self.name))
+    }
+
+    /// Whether this object has a start/stop lifecycle, as requested by the `[Service]` attribute.
+    pub fn is_service(&self) -> bool {
+        self.is_service
+    }
+
+    /// The callback interface for observers of this object's state, if it's a `[Service]`.
+    pub fn state_observer_interface(&self) -> Option<String> {
+        if self.is_service {
+            Some(format!("{}StateObserver", self.name))
+        } else {
+            None
+        }
+    }
+
+    /// Whether this is one of the views returned by `[Borrowed]` methods, which are declared
+    /// by uniffi itself and implemented by the `uniffi` crate rather than the component.
+    pub fn is_borrowed_view(&self) -> bool {
+        self.is_borrowed_view
+    }
+
+    /// The type of the items, if this is the object of a `[Stream]` typedef, which is
+    /// declared by uniffi itself and which the bindings make iterable.
+    pub fn stream_item(&self) -> Option<&Type> {
+        self.stream_item.as_ref()
+    }
+
+    /// Whether this is a `[Trait]` interface, which any Rust type implementing the trait of the
+    /// same name can implement, so that the scaffolding holds instances as `Arc<dyn Trait>`.
+    pub fn is_trait(&self) -> bool {
+        self.is_trait
+    }
+
+    /// The threads that this object's declaration says it can be used from.
+    pub fn threading(&self) -> Threading {
+        self.threading
+    }
+
+    /// Whether any method on this object takes `&mut self`, in which case the scaffolding
+    /// keeps each instance behind a `RwLock` rather than handing it out directly.
+    pub fn has_mut_methods(&self) -> bool {
+        self.methods.iter().any(|meth| meth.takes_self_by_mut())
+    }
+
     pub fn uses_deprecated_threadsafe_attribute(&self) -> bool {
         self.uses_deprecated_threadsafe_attribute
     }
@@ -143,6 +453,10 @@ impl Object {
     pub fn iter_ffi_function_definitions(&self) -> Vec<FFIFunction> {
         vec![self.ffi_object_free().clone()]
             .into_iter()
+            .chain(self.ffi_func_clone.iter().cloned())
+            .chain(self.ffi_func_downgrade.iter().cloned())
+            .chain(self.ffi_func_upgrade.iter().cloned())
+            .chain(self.ffi_func_weak_free.iter().cloned())
             .chain(self.constructors.iter().map(|f| f.ffi_func.clone()))
             .chain(self.methods.iter().map(|f| f.ffi_func.clone()))
             .collect()
@@ -155,6 +469,36 @@ impl Object {
             type_: FFIType::RustArcPtr,
         }];
         self.ffi_func_free.return_type = None;
+        if let Some(ffi_func_clone) = self.ffi_func_clone.as_mut() {
+            ffi_func_clone.name = format!("ffi_{}_{}_object_clone", ci_prefix, self.name);
+            ffi_func_clone.arguments = vec![FFIArgument {
+                name: "ptr".to_string(),
+                type_: FFIType::RustArcPtr,
+            }];
+            ffi_func_clone.return_type = Some(FFIType::RustArcPtr);
+        }
+        // Weak references are passed across the FFI as pointers too, but they can't be used to
+        // call methods, only to get the object back from `upgrade`. That returns the object's
+        // pointer as a number, like the one a buffer holds, which is zero if the object is gone.
+        let weak_funcs = [
+            (
+                &mut self.ffi_func_downgrade,
+                "downgrade",
+                Some(FFIType::RustArcPtr),
+            ),
+            (&mut self.ffi_func_upgrade, "upgrade", Some(FFIType::UInt64)),
+            (&mut self.ffi_func_weak_free, "weak_free", None),
+        ];
+        for (func, suffix, return_type) in weak_funcs {
+            if let Some(func) = func.as_mut() {
+                func.name = format!("ffi_{}_{}_object_{}", ci_prefix, self.name, suffix);
+                func.arguments = vec![FFIArgument {
+                    name: "ptr".to_string(),
+                    type_: FFIType::RustArcPtr,
+                }];
+                func.return_type = return_type;
+            }
+        }
         for cons in self.constructors.iter_mut() {
             cons.derive_ffi_func(ci_prefix, &self.name)
         }
@@ -188,6 +532,42 @@ impl Hash for Object {
         self.name.hash(state);
         self.constructors.hash(state);
         self.methods.hash(state);
+        self.is_cloneable().hash(state);
+        self.has_weak_refs().hash(state);
+        self.releases_callbacks.hash(state);
+        self.is_closeable.hash(state);
+        self.is_opaque_token.hash(state);
+        self.foreign_extension.hash(state);
+        self.observable_snapshot.hash(state);
+        self.events_type.hash(state);
+        self.is_service.hash(state);
+        self.stream_item.hash(state);
+        self.is_trait.hash(state);
+        self.threading.hash(state);
+    }
+}
+
+/// The threads that an object can be used from, as its declaration in the UDL says. The bindings
+/// record this for tools that check how an app calls the object, but nothing enforces it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Threading {
+    /// Any thread, which is what every object supports.
+    Threadsafe,
+    /// Only the app's main thread, for objects marked `[MainThread]`.
+    MainThreadOnly,
+    /// Any thread but the main one, because the methods of objects marked `[Blocking]` block.
+    Blocking,
+}
+
+impl Threading {
+    /// The name of this threading in the metadata that the bindings and `uniffi-bindgen
+    /// introspect` give out.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Threading::Threadsafe => "threadsafe",
+            Threading::MainThreadOnly => "main-thread-only",
+            Threading::Blocking => "blocking",
+        }
     }
 }
 
@@ -202,6 +582,23 @@ impl APIConverter<Object> for weedle::InterfaceDefinition<'_> {
             None => Default::default(),
         };
         object.uses_deprecated_threadsafe_attribute = attributes.threadsafe();
+        if attributes.cloneable() {
+            object.ffi_func_clone = Some(Default::default());
+        }
+        if attributes.weak_refs() {
+            object.ffi_func_downgrade = Some(Default::default());
+            object.ffi_func_upgrade = Some(Default::default());
+            object.ffi_func_weak_free = Some(Default::default());
+        }
+        object.releases_callbacks = attributes.releases_callbacks();
+        object.is_closeable = attributes.closeable();
+        object.is_opaque_token = attributes.opaque_token();
+        object.foreign_extension = attributes.foreign_extension().map(Into::into);
+        object.observable_snapshot = attributes.observable_snapshot().map(Into::into);
+        object.events_type = attributes.events_type().map(Into::into);
+        object.is_service = attributes.service();
+        object.is_trait = attributes.trait_object();
+        object.threading = attributes.threading();
         // Convert each member into a constructor or method, guarding against duplicate names.
         let mut member_names = HashSet::new();
         for member in &self.members.body {
@@ -218,16 +615,133 @@ impl APIConverter<Object> for weedle::InterfaceDefinition<'_> {
                     if !member_names.insert(method.name.clone()) {
                         bail!("Duplicate interface member name: \"{}\"", method.name())
                     }
+                    if method.is_optional() {
+                        bail!("[Optional] is only supported on callback interface methods")
+                    }
                     method.object_name.push_str(object.name.as_str());
                     object.methods.push(method);
                 }
                 _ => bail!("no support for interface member type {:?} yet", member),
             }
         }
+        if object.releases_callbacks
+            && !object
+                .constructors
+                .iter()
+                .flat_map(IterTypes::iter_types)
+                .any(|t| matches!(t, Type::CallbackInterface(_)))
+        {
+            bail!(
+                "[ReleaseCallbacks] has no effect on \"{}\", because none of its constructors take a callback interface",
+                object.name()
+            )
+        }
+        if object.is_opaque_token && !member_names.is_empty() {
+            bail!(
+                "[OpaqueToken] interface \"{}\" can't have constructors or methods",
+                object.name()
+            )
+        }
+        if let Some(meth) = object
+            .methods
+            .iter()
+            .find(|meth| meth.is_also_async() && member_names.contains(&meth.async_method_name()))
+        {
+            bail!(
+                "the method name \"{}\" is reserved for the [AlsoAsync] method \"{}\"",
+                meth.async_method_name(),
+                meth.name()
+            )
+        }
+        if let Some(meth) = object.methods.iter().find(|meth| meth.is_async()) {
+            // The call runs on one of the Rust executor's threads, and can't hold the lock that
+            // the other methods take while it waits.
+            if object.threading == Threading::MainThreadOnly {
+                bail!(
+                    "[Async] method \"{}\" can't be on a [MainThread] interface",
+                    meth.name()
+                )
+            }
+            if object.has_mut_methods() {
+                bail!(
+                    "[Async] method \"{}\" can't be on an interface with [Self=ByMut] methods",
+                    meth.name()
+                )
+            }
+            // Rust traits can't have `async fn`s.
+            if object.is_trait {
+                bail!(
+                    "[Async] method \"{}\" can't be on a [Trait] interface",
+                    meth.name()
+                )
+            }
+        }
+        if object.is_cloneable() && member_names.contains("clone") {
+            bail!("the method name \"clone\" is reserved on interfaces with the [Clone] attribute")
+        }
+        if object.has_weak_refs() && member_names.contains("downgrade") {
+            bail!("the method name \"downgrade\" is reserved on interfaces with the [WeakRefs] attribute")
+        }
+        if object.is_trait {
+            check_trait_object(&object)?;
+        }
+        if object.has_mut_methods() {
+            if let Some(meth) = object.methods.iter().find(|meth| meth.takes_self_by_arc()) {
+                bail!(
+                    "method \"{}\" cannot take [Self=ByArc] because \"{}\" has [Self=ByMut] methods",
+                    meth.name(),
+                    object.name()
+                )
+            }
+            // A `[Self=ByMut]` method could change the data while a view of it is still around.
+            if let Some(meth) = object.methods.iter().find(|meth| meth.is_borrowed()) {
+                bail!(
+                    "method \"{}\" cannot be [Borrowed] because \"{}\" has [Self=ByMut] methods",
+                    meth.name(),
+                    object.name()
+                )
+            }
+        }
         Ok(object)
     }
 }
 
+// A `[Trait]` interface is a Rust trait, so it has no struct to construct, copy or hold weakly,
+// or to add the methods of `[Observable]`, `[Events]`, `[Service]` or `[Paginated]` to. Its methods are called
+// through an `Arc<dyn Trait>`, which can't be locked for `[Self=ByMut]` or lend out a `[Borrowed]`
+// view.
+fn check_trait_object(object: &Object) -> Result<()> {
+    let unsupported = if !object.constructors.is_empty() {
+        Some("constructors")
+    } else if object.is_cloneable() {
+        Some("the [Clone] attribute")
+    } else if object.has_weak_refs() {
+        Some("the [WeakRefs] attribute")
+    } else if object.observable_snapshot.is_some() {
+        Some("the [Observable] attribute")
+    } else if object.events_type.is_some() {
+        Some("the [Events] attribute")
+    } else if object.is_service {
+        Some("the [Service] attribute")
+    } else if object.methods.iter().any(|meth| meth.takes_self_by_mut()) {
+        Some("[Self=ByMut] methods")
+    } else if object.methods.iter().any(|meth| meth.is_borrowed()) {
+        Some("[Borrowed] methods")
+    } else if object.methods.iter().any(|meth| meth.is_paginated()) {
+        Some("[Paginated] methods")
+    } else {
+        None
+    };
+    match unsupported {
+        Some(what) => bail!(
+            "[Trait] interface \"{}\" can't have {}",
+            object.name(),
+            what
+        ),
+        None => Ok(()),
+    }
+}
+
 // Represents a constructor for an object type.
 //
 // In the FFI, this will be a function that returns a pointer to an instance
@@ -277,7 +791,7 @@ impl Constructor {
         self.ffi_func.return_type = Some(FFIType::RustArcPtr);
     }
 
-    fn is_primary_constructor(&self) -> bool {
+    pub fn is_primary_constructor(&self) -> bool {
         self.name == "new"
     }
 }
@@ -337,6 +851,8 @@ pub struct Method {
     pub(super) name: String,
     pub(super) object_name: String,
     pub(super) return_type: Option<Type>,
+    // For `[Borrowed]` methods, the type declared in the UDL, whose view is the return type.
+    pub(super) borrowed_type: Option<Type>,
     pub(super) arguments: Vec<Argument>,
     pub(super) ffi_func: FFIFunction,
     pub(super) attributes: MethodAttributes,
@@ -390,6 +906,129 @@ impl Method {
         self.attributes.get_self_by_arc()
     }
 
+    pub fn takes_self_by_mut(&self) -> bool {
+        self.attributes.get_self_by_mut()
+    }
+
+    /// Whether this method is marked `[Borrowed]`, and returns a view of a string or bytes
+    /// owned by the object rather than a copy of them.
+    pub fn is_borrowed(&self) -> bool {
+        self.attributes.is_borrowed()
+    }
+
+    /// For `[Borrowed]` methods, the type of the data that the returned view gives access to,
+    /// as it was declared in the UDL.
+    pub fn borrowed_type(&self) -> Option<&Type> {
+        self.borrowed_type.as_ref()
+    }
+
+    /// Whether this method is marked `[Paginated]`, so that the object also has a method which
+    /// returns a page of the sequence that it returns.
+    pub fn is_paginated(&self) -> bool {
+        self.attributes.is_paginated()
+    }
+
+    /// For `[Paginated]` methods, the name of the record holding a page of the sequence.
+    pub fn page_record(&self) -> Option<String> {
+        if !self.is_paginated() {
+            return None;
+        }
+        Some(format!(
+            "{}{}Page",
+            self.object_name,
+            self.name.to_camel_case()
+        ))
+    }
+
+    /// For `[Paginated]` methods, the name of the method that returns a page of the sequence.
+    pub fn page_method_name(&self) -> String {
+        format!("{}_page", self.name)
+    }
+
+    /// For `[Paginated]` methods, the type of the items in the sequence.
+    pub fn page_item_type(&self) -> Option<&Type> {
+        match (self.is_paginated(), self.return_type.as_ref()) {
+            (true, Some(Type::Sequence(t))) => Some(t),
+            _ => None,
+        }
+    }
+
+    /// Whether this method is marked `[AlsoAsync]`, so that the bindings also have an async
+    /// variant of it, which calls the same Rust method without blocking the caller.
+    pub fn is_also_async(&self) -> bool {
+        self.attributes.is_also_async()
+    }
+
+    /// Whether this method is marked `[Async]`. On an interface, its Rust implementation is
+    /// an `async fn`, and the bindings have an async variant that waits for it to finish. On a
+    /// callback interface, its Rust trait method returns a `uniffi::CallbackFuture` that the
+    /// foreign implementation completes later.
+    pub fn is_async(&self) -> bool {
+        self.attributes.is_async()
+    }
+
+    /// Whether this `[Async]` method is also marked `[Cancellable]`, so that its Rust
+    /// implementation takes a `uniffi::CancellationToken` after the arguments that the UDL
+    /// declares, like a `[Cancellable]` function.
+    pub fn is_cancellable(&self) -> bool {
+        self.attributes.is_cancellable()
+    }
+
+    /// Whether this method is marked `[Cached]` (or `[Pure]`), so that the bindings keep the
+    /// value it returns the first time it's called, and return that from then on.
+    pub fn is_cached(&self) -> bool {
+        self.attributes.is_cached()
+    }
+
+    /// Whether this method is marked `[NoCopy]`, so that the bytes it returns are handed to the
+    /// bindings as they are, in a `RustBuffer` of their own rather than serialized into one,
+    /// for the caller to view where they are and free explicitly.
+    pub fn is_no_copy(&self) -> bool {
+        self.attributes.is_no_copy()
+    }
+
+    /// Whether this method is marked `[Scoped]`, so that it takes the `Scope` that its
+    /// background work is registered against, as an argument called `scope` ahead of the
+    /// ones that the UDL declares.
+    pub fn is_scoped(&self) -> bool {
+        self.attributes.is_scoped()
+    }
+
+    /// For `[AlsoAsync]` methods, the name of the async variant.
+    pub fn async_method_name(&self) -> String {
+        format!("{}_async", self.name)
+    }
+
+    /// Whether this is a callback interface method marked `[Optional]`, which foreign
+    /// implementations of the interface can leave out.
+    pub fn is_optional(&self) -> bool {
+        self.attributes.is_optional()
+    }
+
+    /// The value that the default implementation of an `[Optional]` method returns.
+    ///
+    /// This is the "empty" value of the return type: `false`, zero, an empty string,
+    /// sequence or map, or `null`. It's `None` for methods that don't return anything
+    /// (whose default implementation does nothing), and for return types that have no
+    /// obvious empty value.
+    pub fn default_return_value(&self) -> Option<Literal> {
+        Some(match self.return_type.as_ref()? {
+            Type::Boolean => Literal::Boolean(false),
+            Type::String => Literal::String(String::new()),
+            t @ Type::UInt8 | t @ Type::UInt16 | t @ Type::UInt32 | t @ Type::UInt64 => {
+                Literal::UInt(0, Radix::Decimal, t.clone())
+            }
+            t @ Type::Int8 | t @ Type::Int16 | t @ Type::Int32 | t @ Type::Int64 => {
+                Literal::Int(0, Radix::Decimal, t.clone())
+            }
+            t @ Type::Float32 | t @ Type::Float64 => Literal::Float("0.0".into(), t.clone()),
+            Type::Optional(_) => Literal::Null,
+            Type::Sequence(_) => Literal::EmptySequence,
+            Type::Map(..) | Type::OrderedMap(_) => Literal::EmptyMap,
+            _ => return None,
+        })
+    }
+
     pub fn derive_ffi_func(&mut self, ci_prefix: &str, obj_prefix: &str) -> Result<()> {
         self.ffi_func.name.push_str(ci_prefix);
         self.ffi_func.name.push('_');
@@ -398,6 +1037,9 @@ impl Method {
         self.ffi_func.name.push_str(&self.name);
         self.ffi_func.arguments = self.full_arguments().iter().map(Into::into).collect();
         self.ffi_func.return_type = self.return_type.as_ref().map(Into::into);
+        if self.is_async() {
+            async_ffi_func(&mut self.ffi_func);
+        }
         Ok(())
     }
 }
@@ -438,7 +1080,36 @@ impl APIConverter<Method> for weedle::interface::OperationInterfaceMember<'_> {
         if self.modifier.is_some() {
             bail!("method modifiers are not supported")
         }
-        let return_type = ci.resolve_return_type_expression(&self.return_type)?;
+        let mut return_type = ci.resolve_return_type_expression(&self.return_type)?;
+        let attributes = MethodAttributes::try_from(self.attributes.as_ref())?;
+        let mut borrowed_type = None;
+        if attributes.is_borrowed() {
+            let view = match &return_type {
+                Some(Type::String) => BORROWED_STRING,
+                Some(Type::Sequence(t)) if **t == Type::UInt8 => BORROWED_BYTES,
+                _ => bail!("[Borrowed] methods must return a `string` or a `sequence<u8>`"),
+            };
+            borrowed_type = return_type;
+            return_type = Some(ci.types.add_known_type(Type::Object(view.to_string()))?);
+        }
+        if attributes.is_paginated() && !matches!(return_type, Some(Type::Sequence(_))) {
+            bail!("[Paginated] methods must return a `sequence`");
+        }
+        if attributes.is_no_copy() {
+            match &return_type {
+                Some(Type::Bytes) => (),
+                Some(Type::Sequence(t)) if **t == Type::UInt8 => (),
+                _ => bail!("[NoCopy] methods must return `bytes` or a `sequence<u8>`"),
+            }
+        }
+        if attributes.is_cached() {
+            if return_type.is_none() {
+                bail!("[Cached] methods must return a value");
+            }
+            if !self.args.body.list.is_empty() {
+                bail!("[Cached] methods cannot take arguments");
+            }
+        }
         Ok(Method {
             name: match self.identifier {
                 None => bail!("anonymous methods are not supported {:?}", self),
@@ -454,8 +1125,9 @@ impl APIConverter<Method> for weedle::interface::OperationInterfaceMember<'_> {
             object_name: Default::default(),
             arguments: self.args.body.list.convert(ci)?,
             return_type,
+            borrowed_type,
             ffi_func: Default::default(),
-            attributes: MethodAttributes::try_from(self.attributes.as_ref())?,
+            attributes,
         })
     }
 }
@@ -595,4 +1267,789 @@ mod test {
         let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
         assert_eq!(err.to_string(), "Duplicate interface member name: \"new\"");
     }
+
+    #[test]
+    fn test_clone_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Clone]
+            interface Testing {
+                constructor();
+            };
+            interface NotCloneable {
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+        assert!(obj.is_cloneable());
+        let ffi_func = obj.ffi_object_clone().unwrap();
+        assert_eq!(ffi_func.arguments().len(), 1);
+        assert!(matches!(ffi_func.return_type(), Some(FFIType::RustArcPtr)));
+        assert!(obj
+            .iter_ffi_function_definitions()
+            .iter()
+            .any(|f| f.name() == ffi_func.name()));
+
+        let obj = ci.get_object_definition("NotCloneable").unwrap();
+        assert!(!obj.is_cloneable());
+        assert!(obj.ffi_object_clone().is_none());
+
+        const UDL2: &str = r#"
+            namespace test{};
+            [Clone]
+            interface Testing {
+                void clone();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the method name \"clone\" is reserved on interfaces with the [Clone] attribute"
+        );
+    }
+
+    #[test]
+    fn test_weak_refs_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            [WeakRefs]
+            interface Thumbnail {
+                constructor();
+            };
+            interface Photo {
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Thumbnail").unwrap();
+        assert!(obj.has_weak_refs());
+        let downgrade = obj.ffi_object_downgrade().unwrap();
+        assert!(downgrade.name().ends_with("_Thumbnail_object_downgrade"));
+        assert!(matches!(downgrade.return_type(), Some(FFIType::RustArcPtr)));
+        let upgrade = obj.ffi_object_upgrade().unwrap();
+        assert!(matches!(upgrade.return_type(), Some(FFIType::UInt64)));
+        let weak_free = obj.ffi_object_weak_free().unwrap();
+        assert!(weak_free.return_type().is_none());
+        for func in [downgrade, upgrade, weak_free].iter() {
+            assert_eq!(func.arguments().len(), 1);
+            assert!(obj
+                .iter_ffi_function_definitions()
+                .iter()
+                .any(|f| f.name() == func.name()));
+        }
+
+        let obj = ci.get_object_definition("Photo").unwrap();
+        assert!(!obj.has_weak_refs());
+        assert!(obj.ffi_object_downgrade().is_none());
+
+        const UDL2: &str = r#"
+            namespace test{};
+            [WeakRefs]
+            interface Thumbnail {
+                void downgrade();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the method name \"downgrade\" is reserved on interfaces with the [WeakRefs] attribute"
+        );
+
+        const UDL3: &str = r#"
+            namespace test{};
+            [Trait, WeakRefs]
+            interface Shape {};
+        "#;
+        let err = ComponentInterface::from_webidl(UDL3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[Trait] interface \"Shape\" can't have the [WeakRefs] attribute"
+        );
+    }
+
+    #[test]
+    fn test_release_callbacks_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            callback interface Listener {
+                void on_event();
+            };
+            [ReleaseCallbacks]
+            interface Testing {
+                constructor(Listener listener);
+            };
+            interface Leaky {
+                constructor(Listener listener);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert!(ci
+            .get_object_definition("Testing")
+            .unwrap()
+            .releases_callbacks());
+        assert!(!ci
+            .get_object_definition("Leaky")
+            .unwrap()
+            .releases_callbacks());
+
+        const UDL2: &str = r#"
+            namespace test{};
+            [ReleaseCallbacks]
+            interface Testing {
+                constructor(u32 value);
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[ReleaseCallbacks] has no effect on \"Testing\", because none of its constructors take a callback interface"
+        );
+    }
+
+    #[test]
+    fn test_closeable_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Closeable]
+            interface Testing {
+                constructor();
+            };
+            interface Plain {
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert!(ci.get_object_definition("Testing").unwrap().is_closeable());
+        assert!(!ci.get_object_definition("Plain").unwrap().is_closeable());
+    }
+
+    #[test]
+    fn test_opaque_token_attribute() {
+        const UDL: &str = r#"
+            namespace test{
+                Subscription subscribe();
+                void unsubscribe(Subscription subscription);
+            };
+            [OpaqueToken]
+            interface Subscription {};
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Subscription").unwrap();
+        assert!(obj.is_opaque_token());
+        assert_eq!(obj.iter_ffi_function_definitions().len(), 1);
+
+        const UDL2: &str = r#"
+            namespace test{};
+            [OpaqueToken]
+            interface Subscription {
+                void cancel();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[OpaqueToken] interface \"Subscription\" can't have constructors or methods"
+        );
+    }
+
+    #[test]
+    fn test_foreign_extension_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            [ForeignExtension=TestingExt]
+            interface Testing {
+                constructor();
+            };
+            interface Plain {
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert_eq!(
+            ci.get_object_definition("Testing")
+                .unwrap()
+                .foreign_extension(),
+            Some("TestingExt")
+        );
+        assert_eq!(
+            ci.get_object_definition("Plain")
+                .unwrap()
+                .foreign_extension(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_observable_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            dictionary ProfileState {
+                string name;
+            };
+            [Observable=ProfileState]
+            interface Profile {
+                constructor();
+                void set_name(string name);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Profile").unwrap();
+        assert_eq!(obj.observable_snapshot(), Some("ProfileState"));
+        assert_eq!(obj.observer_interface().as_deref(), Some("ProfileObserver"));
+        let names: Vec<_> = obj.methods().iter().map(|m| m.name()).collect();
+        assert_eq!(names, vec!["set_name", "add_observer", "remove_observer"]);
+        let add = &obj.methods()[1];
+        assert_eq!(
+            add.arguments()[0].type_(),
+            Type::CallbackInterface("ProfileObserver".into())
+        );
+        assert_eq!(add.return_type(), Some(&Type::UInt64));
+
+        let cbi = ci
+            .get_callback_interface_definition("ProfileObserver")
+            .unwrap();
+        let on_changed = &cbi.methods()[0];
+        assert_eq!(on_changed.name(), "on_changed");
+        assert_eq!(
+            on_changed.arguments()[1].type_(),
+            Type::Record("ProfileState".into())
+        );
+
+        let err = |udl: &str| {
+            ComponentInterface::from_webidl(&format!("namespace test{{}}; {}", udl))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err("[Observable=Missing] interface Profile {};"),
+            "[Observable] interface \"Profile\" needs a dictionary for its snapshots, and \"Missing\" isn't one"
+        );
+        assert_eq!(
+            err("dictionary S {}; [Observable=S] interface Profile { void add_observer(); };"),
+            "the method name \"add_observer\" is reserved on interfaces with the [Observable] attribute"
+        );
+        assert_eq!(
+            err("dictionary S {}; [Observable=S] interface Profile {}; callback interface ProfileObserver {};"),
+            "[Observable] interface \"Profile\" adds a \"ProfileObserver\" callback interface, which conflicts with a type of the same name"
+        );
+    }
+
+    #[test]
+    fn test_events_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Enum]
+            interface DownloadEvent {
+                Progress(u64 bytes);
+                Finished();
+            };
+            [Events=DownloadEvent]
+            interface Downloader {
+                constructor();
+                void start(string url);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Downloader").unwrap();
+        assert_eq!(obj.events_type(), Some("DownloadEvent"));
+        assert_eq!(
+            obj.listener_interface().as_deref(),
+            Some("DownloaderListener")
+        );
+        assert_eq!(obj.observer_interface(), None);
+        let names: Vec<_> = obj.methods().iter().map(|m| m.name()).collect();
+        assert_eq!(names, vec!["start", "add_listener", "remove_listener"]);
+        assert_eq!(
+            obj.methods()[1].arguments()[0].type_(),
+            Type::CallbackInterface("DownloaderListener".into())
+        );
+
+        let cbi = ci
+            .get_callback_interface_definition("DownloaderListener")
+            .unwrap();
+        let on_event = &cbi.methods()[0];
+        assert_eq!(on_event.name(), "on_event");
+        assert_eq!(
+            on_event.arguments()[0].type_(),
+            Type::Enum("DownloadEvent".into())
+        );
+
+        // Records can be events too.
+        let ci = ComponentInterface::from_webidl(
+            "namespace test{}; dictionary Tick { u64 at; }; [Events=Tick] interface Clock {};",
+        )
+        .unwrap();
+        assert_eq!(
+            ci.get_callback_interface_definition("ClockListener")
+                .unwrap()
+                .methods()[0]
+                .arguments()[0]
+                .type_(),
+            Type::Record("Tick".into())
+        );
+
+        let err = |udl: &str| {
+            ComponentInterface::from_webidl(&format!("namespace test{{}}; {}", udl))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err("[Events=string] interface Clock {};"),
+            "[Events] interface \"Clock\" needs a dictionary or an enum for its events, and \"string\" isn't one"
+        );
+        assert_eq!(
+            err("dictionary Tick {}; [Events=Tick] interface Clock { void remove_listener(u64 id); };"),
+            "the method name \"remove_listener\" is reserved on interfaces with the [Events] attribute"
+        );
+        assert_eq!(
+            err("dictionary Tick {}; [Events=Tick] interface Clock {}; dictionary ClockListener {};"),
+            "[Events] interface \"Clock\" adds a \"ClockListener\" callback interface, which conflicts with a type of the same name"
+        );
+    }
+
+    #[test]
+    fn test_service_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Service]
+            interface Downloader {
+                constructor();
+                void add_url(string url);
+            };
+            [Service]
+            interface Uploader {};
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Downloader").unwrap();
+        assert!(obj.is_service());
+        assert_eq!(
+            obj.state_observer_interface().as_deref(),
+            Some("DownloaderStateObserver")
+        );
+        let names: Vec<_> = obj.methods().iter().map(|m| m.name()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "add_url",
+                "start",
+                "stop",
+                "is_running",
+                "add_state_observer",
+                "remove_state_observer"
+            ]
+        );
+        assert_eq!(obj.methods()[3].return_type(), Some(&Type::Boolean));
+        assert!(ci.get_object_definition("Uploader").unwrap().is_service());
+
+        let state = ci.get_enum_definition("ServiceState").unwrap();
+        let variants: Vec<_> = state.variants().iter().map(|v| v.name()).collect();
+        assert_eq!(variants, vec!["Stopped", "Starting", "Running", "Stopping"]);
+        let cbi = ci
+            .get_callback_interface_definition("UploaderStateObserver")
+            .unwrap();
+        assert_eq!(
+            cbi.methods()[0].arguments()[0].type_(),
+            Type::Enum("ServiceState".into())
+        );
+
+        let err = |udl: &str| {
+            ComponentInterface::from_webidl(&format!("namespace test{{}}; {}", udl))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err("[Service] interface Downloader { void start(); };"),
+            "the method name \"start\" is reserved on interfaces with the [Service] attribute"
+        );
+        assert_eq!(
+            err("enum ServiceState { \"On\" }; [Service] interface Downloader {};"),
+            "[Service] interfaces use a \"ServiceState\" enum, which conflicts with a type of the same name"
+        );
+        assert_eq!(
+            err("[Service] interface Downloader {}; callback interface DownloaderStateObserver {};"),
+            "[Service] interface \"Downloader\" adds a \"DownloaderStateObserver\" callback interface, which conflicts with a type of the same name"
+        );
+    }
+
+    #[test]
+    fn test_paginated_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Error]
+            enum LibraryError { "Closed" };
+            interface Library {
+                [Paginated, Throws=LibraryError]
+                sequence<string> titles([ByRef] string author, boolean? in_print);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Library").unwrap();
+        let names: Vec<_> = obj.methods().iter().map(|m| m.name()).collect();
+        assert_eq!(names, vec!["titles", "titles_page"]);
+        let titles = &obj.methods()[0];
+        assert!(titles.is_paginated());
+        assert_eq!(titles.page_record().as_deref(), Some("LibraryTitlesPage"));
+        assert_eq!(titles.page_item_type(), Some(&Type::String));
+
+        let page = &obj.methods()[1];
+        assert!(!page.is_paginated());
+        assert_eq!(page.throws(), Some("LibraryError"));
+        let args: Vec<_> = page
+            .arguments()
+            .iter()
+            .map(|arg| (arg.name().to_string(), arg.type_(), arg.by_ref()))
+            .collect();
+        assert_eq!(
+            args,
+            vec![
+                ("author".to_string(), Type::String, true),
+                (
+                    "in_print".to_string(),
+                    Type::Optional(Box::new(Type::Boolean)),
+                    false
+                ),
+                ("offset".to_string(), Type::UInt64, false),
+                ("limit".to_string(), Type::UInt32, false),
+            ]
+        );
+        assert_eq!(
+            page.return_type(),
+            Some(&Type::Record("LibraryTitlesPage".into()))
+        );
+        let record = ci.get_record_definition("LibraryTitlesPage").unwrap();
+        let fields: Vec<_> = record
+            .fields()
+            .iter()
+            .map(|f| (f.name().to_string(), f.type_()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("items".to_string(), Type::Sequence(Box::new(Type::String))),
+                ("total".to_string(), Type::UInt64),
+            ]
+        );
+
+        let err = |udl: &str| {
+            ComponentInterface::from_webidl(&format!("namespace test{{}}; {}", udl))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err("interface Library { [Paginated] string title(); };"),
+            "[Paginated] methods must return a `sequence`"
+        );
+        assert_eq!(
+            err("interface Library { [Paginated] sequence<string> titles(u32 limit); };"),
+            "the argument name \"limit\" is reserved on [Paginated] methods"
+        );
+        assert_eq!(
+            err(
+                "interface Library { [Paginated] sequence<string> titles(); void titles_page(); };"
+            ),
+            "the method name \"titles_page\" is reserved for the [Paginated] method \"titles\""
+        );
+        assert_eq!(
+            err("dictionary LibraryTitlesPage {}; interface Library { [Paginated] sequence<string> titles(); };"),
+            "[Paginated] method \"titles\" adds a \"LibraryTitlesPage\" dictionary, which conflicts with a type of the same name"
+        );
+    }
+
+    #[test]
+    fn test_trait_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Trait]
+            interface Shape {
+                string name();
+                [Self=ByArc]
+                Shape scaled(double factor);
+            };
+            interface Canvas {
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert!(ci.get_object_definition("Shape").unwrap().is_trait());
+        assert!(!ci.get_object_definition("Canvas").unwrap().is_trait());
+
+        let err = |udl: &str| {
+            ComponentInterface::from_webidl(&format!("namespace test{{}}; {}", udl))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err("[Trait] interface Shape { constructor(); };"),
+            "[Trait] interface \"Shape\" can't have constructors"
+        );
+        assert_eq!(
+            err("[Trait, Clone] interface Shape {};"),
+            "[Trait] interface \"Shape\" can't have the [Clone] attribute"
+        );
+        assert_eq!(
+            err("[Trait] interface Shape { [Self=ByMut] void grow(); };"),
+            "[Trait] interface \"Shape\" can't have [Self=ByMut] methods"
+        );
+        assert_eq!(
+            err("[Trait] interface Shape { [Borrowed] string name(); };"),
+            "[Trait] interface \"Shape\" can't have [Borrowed] methods"
+        );
+    }
+
+    #[test]
+    fn test_also_async_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Library {
+                [AlsoAsync]
+                sequence<string> titles(string author);
+                void close();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Library").unwrap();
+        // The async variant is only generated in the bindings, so there's no extra method here.
+        let names: Vec<_> = obj.methods().iter().map(|m| m.name()).collect();
+        assert_eq!(names, vec!["titles", "close"]);
+        assert!(obj.methods()[0].is_also_async());
+        assert_eq!(obj.methods()[0].async_method_name(), "titles_async");
+        assert!(!obj.methods()[1].is_also_async());
+
+        let err = |udl: &str| {
+            ComponentInterface::from_webidl(&format!("namespace test{{}}; {}", udl))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err("interface Library { [AlsoAsync] void close(); void close_async(); };"),
+            "the method name \"close_async\" is reserved for the [AlsoAsync] method \"close\""
+        );
+        assert_eq!(
+            err("callback interface Library { [AlsoAsync] void close(); };"),
+            "[AlsoAsync] is only supported on interface methods"
+        );
+    }
+
+    #[test]
+    fn test_cached_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Document {
+                [Cached]
+                string title();
+                [Pure]
+                sequence<string> authors();
+                u32 word_count();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Document").unwrap();
+        assert!(obj.methods()[0].is_cached());
+        assert!(obj.methods()[1].is_cached());
+        assert!(!obj.methods()[2].is_cached());
+
+        let err = |udl: &str| {
+            ComponentInterface::from_webidl(&format!("namespace test{{}}; {}", udl))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err("interface Document { [Cached] void refresh(); };"),
+            "[Cached] methods must return a value"
+        );
+        assert_eq!(
+            err("interface Document { [Cached] string line(u32 number); };"),
+            "[Cached] methods cannot take arguments"
+        );
+        assert_eq!(
+            err("callback interface Document { [Cached] string title(); };"),
+            "[Cached] is only supported on interface methods"
+        );
+    }
+
+    #[test]
+    fn test_no_copy_attribute() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Camera {
+                [NoCopy]
+                bytes frame();
+                [NoCopy, Throws=CameraError]
+                sequence<u8> thumbnail(u32 size);
+                bytes header();
+            };
+            [Error]
+            enum CameraError { "Busy" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Camera").unwrap();
+        assert!(obj.methods()[0].is_no_copy());
+        assert_eq!(obj.methods()[0].return_type(), Some(&Type::Bytes));
+        assert!(obj.methods()[1].is_no_copy());
+        assert!(!obj.methods()[2].is_no_copy());
+
+        let err = |udl: &str| {
+            ComponentInterface::from_webidl(&format!("namespace test{{}}; {}", udl))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err("interface Camera { [NoCopy] string name(); };"),
+            "[NoCopy] methods must return `bytes` or a `sequence<u8>`"
+        );
+        assert_eq!(
+            err("interface Camera { [NoCopy] void reset(); };"),
+            "[NoCopy] methods must return `bytes` or a `sequence<u8>`"
+        );
+        assert_eq!(
+            err("callback interface Camera { [NoCopy] bytes frame(); };"),
+            "[NoCopy] is only supported on interface methods"
+        );
+    }
+
+    #[test]
+    fn test_self_by_mut() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Counter {
+                constructor();
+                u32 get();
+                [Self=ByMut]
+                void increment();
+            };
+            interface Plain {
+                constructor();
+                u32 get();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Counter").unwrap();
+        assert!(obj.has_mut_methods());
+        assert!(!obj.methods()[0].takes_self_by_mut());
+        assert!(obj.methods()[1].takes_self_by_mut());
+        assert!(obj.methods()[1].full_arguments()[0].by_ref());
+
+        let obj = ci.get_object_definition("Plain").unwrap();
+        assert!(!obj.has_mut_methods());
+
+        const UDL2: &str = r#"
+            namespace test{};
+            interface Counter {
+                [Self=ByMut]
+                void increment();
+                [Self=ByArc]
+                u32 get();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "method \"get\" cannot take [Self=ByArc] because \"Counter\" has [Self=ByMut] methods"
+        );
+    }
+
+    #[test]
+    fn test_borrowed_methods() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Document {
+                constructor();
+                [Borrowed]
+                string text();
+                [Borrowed]
+                sequence<u8> data();
+                string title();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Document").unwrap();
+        assert!(!obj.is_borrowed_view());
+        let text = &obj.methods()[0];
+        assert!(text.is_borrowed());
+        assert_eq!(text.borrowed_type(), Some(&Type::String));
+        assert_eq!(
+            text.return_type(),
+            Some(&Type::Object("BorrowedString".into()))
+        );
+        assert_eq!(
+            obj.methods()[1].return_type(),
+            Some(&Type::Object("BorrowedBytes".into()))
+        );
+        assert!(!obj.methods()[2].is_borrowed());
+        assert_eq!(obj.methods()[2].borrowed_type(), None);
+
+        // The views are added to the component, as objects without constructors.
+        let view = ci.get_object_definition("BorrowedString").unwrap();
+        assert!(view.is_borrowed_view());
+        assert!(view.constructors().is_empty());
+        assert_eq!(view.methods().len(), 3);
+        assert!(ci
+            .get_object_definition("BorrowedBytes")
+            .unwrap()
+            .is_borrowed_view());
+
+        // Components that don't use them don't get them.
+        let ci = ComponentInterface::from_webidl(
+            "namespace test{}; interface Plain { string text(); };",
+        )
+        .unwrap();
+        assert!(ci.get_object_definition("BorrowedString").is_none());
+    }
+
+    #[test]
+    fn test_borrowed_methods_errors() {
+        let err = |udl: &str| {
+            ComponentInterface::from_webidl(&format!("namespace test{{}}; {}", udl))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            err("interface Doc { [Borrowed] u32 length(); };"),
+            "[Borrowed] methods must return a `string` or a `sequence<u8>`"
+        );
+        assert_eq!(
+            err("interface Doc { [Borrowed] string text(); [Self=ByMut] void edit(); };"),
+            "method \"text\" cannot be [Borrowed] because \"Doc\" has [Self=ByMut] methods"
+        );
+        assert_eq!(
+            err("interface Doc { [Borrowed] string text(); }; dictionary BorrowedString {};"),
+            "[Borrowed] methods return a \"BorrowedString\", which conflicts with a type of the same name"
+        );
+    }
+
+    #[test]
+    fn test_stream_typedefs() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Stream]
+            typedef sequence<string> Lines;
+            interface Document {
+                constructor();
+                Lines lines();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert_eq!(
+            ci.get_object_definition("Document").unwrap().methods()[0].return_type(),
+            Some(&Type::Object("Lines".into()))
+        );
+        assert_eq!(
+            ci.get_object_definition("Document").unwrap().stream_item(),
+            None
+        );
+
+        // The typedef declares an object without constructors, whose method takes a chunk.
+        let lines = ci.get_object_definition("Lines").unwrap();
+        assert_eq!(lines.stream_item(), Some(&Type::String));
+        assert!(lines.constructors().is_empty());
+        let next_chunk = &lines.methods()[0];
+        assert_eq!(lines.methods().len(), 1);
+        assert_eq!(next_chunk.name(), "next_chunk");
+        assert_eq!(next_chunk.arguments()[0].type_(), Type::UInt32);
+        assert_eq!(
+            next_chunk.return_type(),
+            Some(&Type::Sequence(Box::new(Type::String)))
+        );
+    }
 }
@@ -44,8 +44,11 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
+use std::convert::TryFrom;
+
 use anyhow::{bail, Result};
 
+use super::attributes::FieldAttributes;
 use super::literal::{convert_default_value, Literal};
 use super::types::{IterTypes, Type, TypeIterator};
 use super::{APIConverter, ComponentInterface};
@@ -75,6 +78,25 @@ impl Record {
     pub fn fields(&self) -> Vec<&Field> {
         self.fields.iter().collect()
     }
+
+    /// Whether any of the fields are `[Sensitive]`, so the bindings have to print the record
+    /// themselves, rather than leaving it to the language.
+    pub fn has_sensitive_fields(&self) -> bool {
+        self.fields.iter().any(Field::sensitive)
+    }
+
+    /// The default value of a field, if both it and all the fields after it have one.
+    ///
+    /// This is for languages like Python, where a constructor argument that has a default
+    /// value can't be followed by one that doesn't, so only the trailing fields get theirs.
+    pub fn trailing_default_value(&self, field: &Field) -> Option<Literal> {
+        let index = self.fields.iter().position(|f| f.name == field.name)?;
+        if self.fields[index..].iter().all(|f| f.default.is_some()) {
+            field.default_value()
+        } else {
+            None
+        }
+    }
 }
 
 impl IterTypes for Record {
@@ -105,6 +127,7 @@ pub struct Field {
     pub(super) type_: Type,
     pub(super) required: bool,
     pub(super) default: Option<Literal>,
+    pub(super) attributes: FieldAttributes,
 }
 
 impl Field {
@@ -117,6 +140,18 @@ impl Field {
     pub fn default_value(&self) -> Option<Literal> {
         self.default.clone()
     }
+    /// Whether an empty value in this field should be coalesced into `null`.
+    pub fn empty_as_null(&self) -> bool {
+        self.attributes.empty_as_null()
+    }
+    /// Whether a `null` in this field should be coalesced into an empty value.
+    pub fn null_as_empty(&self) -> bool {
+        self.attributes.null_as_empty()
+    }
+    /// Whether this field holds a secret, which the bindings leave out when they print the record.
+    pub fn sensitive(&self) -> bool {
+        self.attributes.sensitive()
+    }
 }
 
 impl IterTypes for Field {
@@ -127,12 +162,20 @@ impl IterTypes for Field {
 
 impl APIConverter<Field> for weedle::dictionary::DictionaryMember<'_> {
     fn convert(&self, ci: &mut ComponentInterface) -> Result<Field> {
-        if self.attributes.is_some() {
-            bail!("dictionary member attributes are not supported yet");
-        }
+        let attributes = FieldAttributes::try_from(self.attributes.as_ref())?;
         let type_ = ci.resolve_type_expression(&self.type_)?;
-        if let Type::Object(_) = type_ {
-            bail!("Objects cannot currently appear in record fields");
+        if attributes.empty_as_null() || attributes.null_as_empty() {
+            match &type_ {
+                Type::Optional(inner)
+                    if matches!(
+                        inner.as_ref(),
+                        Type::String | Type::Sequence(_) | Type::Map(..) | Type::OrderedMap(_)
+                    ) => {}
+                _ => bail!(
+                    "Field `{}` must be an optional string, sequence or map to coalesce empty values and null",
+                    self.identifier.0
+                ),
+            }
         }
         let default = match self.default {
             None => None,
@@ -143,6 +186,7 @@ impl APIConverter<Field> for weedle::dictionary::DictionaryMember<'_> {
             type_,
             required: self.required.is_some(),
             default,
+            attributes,
         })
     }
 }
@@ -204,6 +248,42 @@ mod test {
         assert!(record.fields()[2].default_value().is_none());
     }
 
+    #[test]
+    fn test_nested_default_values() {
+        const UDL: &str = r#"
+            namespace test{};
+            dictionary Defaults {
+                u32 first = 1;
+                string name;
+                sequence<string>? names = null;
+                sequence<string>? more_names = [];
+                record<DOMString, i32> counts = {};
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let record = ci.get_record_definition("Defaults").unwrap();
+        let fields = record.fields();
+        assert!(matches!(fields[2].default_value(), Some(Literal::Null)));
+        assert!(matches!(
+            fields[3].default_value(),
+            Some(Literal::EmptySequence)
+        ));
+        assert!(matches!(fields[4].default_value(), Some(Literal::EmptyMap)));
+
+        // Only the fields after `name`, which has no default, can use theirs in Python.
+        assert!(fields[0].default_value().is_some());
+        assert!(record.trailing_default_value(fields[0]).is_none());
+        assert!(record.trailing_default_value(fields[1]).is_none());
+        assert!(matches!(
+            record.trailing_default_value(fields[2]),
+            Some(Literal::Null)
+        ));
+        assert!(matches!(
+            record.trailing_default_value(fields[4]),
+            Some(Literal::EmptyMap)
+        ));
+    }
+
     #[test]
     fn test_that_all_field_types_become_known() {
         const UDL: &str = r#"
@@ -235,4 +315,79 @@ mod test {
             .iter()
             .any(|t| t.canonical_name() == "TypeTesting"));
     }
+
+    #[test]
+    fn test_object_fields() {
+        const UDL: &str = r#"
+            namespace test{};
+            dictionary Config {
+                Logger logger;
+                Logger? fallback;
+                u32 level;
+            };
+            dictionary Plain {
+                u32 level;
+            };
+            interface Logger {};
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let record = ci.get_record_definition("Config").unwrap();
+        assert!(matches!(record.fields()[0].type_(), Type::Object(name) if name == "Logger"));
+        assert!(ci.item_contains_object_references(record));
+        let record = ci.get_record_definition("Plain").unwrap();
+        assert!(!ci.item_contains_object_references(record));
+    }
+
+    #[test]
+    fn test_sensitive_fields() {
+        const UDL: &str = r#"
+            namespace test{};
+            dictionary Credentials {
+                string user;
+                [Sensitive] string token;
+            };
+            dictionary Plain {
+                string user;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let record = ci.get_record_definition("Credentials").unwrap();
+        assert!(!record.fields()[0].sensitive());
+        assert!(record.fields()[1].sensitive());
+        assert!(record.has_sensitive_fields());
+        let record = ci.get_record_definition("Plain").unwrap();
+        assert!(!record.has_sensitive_fields());
+    }
+
+    #[test]
+    fn test_null_and_empty_coalescing() {
+        const UDL: &str = r#"
+            namespace test{};
+            dictionary Testing {
+                [EmptyAsNull] string? name;
+                [NullAsEmpty] sequence<u32>? values;
+                record<DOMString, u32>? map;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let record = ci.get_record_definition("Testing").unwrap();
+        assert!(record.fields()[0].empty_as_null());
+        assert!(!record.fields()[0].null_as_empty());
+        assert!(!record.fields()[1].empty_as_null());
+        assert!(record.fields()[1].null_as_empty());
+        assert!(!record.fields()[2].empty_as_null());
+        assert!(!record.fields()[2].null_as_empty());
+
+        const UDL2: &str = r#"
+            namespace test{};
+            dictionary Testing {
+                [EmptyAsNull] string name;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Field `name` must be an optional string, sequence or map to coalesce empty values and null"
+        );
+    }
 }
@@ -0,0 +1,254 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Subsets of a `ComponentInterface`
+//!
+//! Bindings don't have to expose everything a component does. A subset of the component
+//! keeps only some of its functions, constructors and methods, along with the types that
+//! those still use, and drops everything else, so that the bindings generated from it are
+//! smaller and have less to audit.
+//!
+//! The subset still describes the same compiled Rust code, so it keeps the checksum, and
+//! with it the FFI names, of the whole component.
+
+use std::collections::BTreeSet;
+
+use super::{ComponentInterface, IterTypes, Type};
+
+impl ComponentInterface {
+    /// The subset of this component with just the callables that `keep` returns true for.
+    ///
+    /// `keep` is given the qualified name of each function, e.g. `sync.engine.start`, and
+    /// of each constructor and method, e.g. `Engine.new` or `Engine.start`. The records,
+    /// enums, errors, callback interfaces and objects that nothing kept still uses are
+    /// dropped too. An object that's used but whose members have all been dropped is kept,
+    /// so that it can still be passed around.
+    pub fn subset(&self, keep: impl Fn(&str) -> bool) -> ComponentInterface {
+        let mut ci = self.clone();
+        ci.full_checksum = Some(self.checksum());
+        ci.functions.retain(|f| keep(&f.qualified_name()));
+        for obj in ci.objects.iter_mut() {
+            let name = obj.name.clone();
+            obj.constructors
+                .retain(|cons| keep(&format!("{}.{}", name, cons.name)));
+            obj.methods
+                .retain(|meth| keep(&format!("{}.{}", name, meth.name)));
+        }
+
+        let used = ci.used_types();
+        ci.records
+            .retain(|r| used.contains(&Type::Record(r.name.clone())));
+        ci.enums
+            .retain(|e| used.contains(&Type::Enum(e.name.clone())));
+        ci.errors
+            .retain(|e| used.contains(&Type::Error(e.name.clone())));
+        ci.callback_interfaces
+            .retain(|cbi| used.contains(&Type::CallbackInterface(cbi.name.clone())));
+        ci.objects
+            .retain(|obj| used.contains(&Type::Object(obj.name.clone())));
+        let functions = &ci.functions;
+        ci.modules.retain(|module| {
+            functions
+                .iter()
+                .any(|f| f.module_path.starts_with(&module.path))
+        });
+        ci.types.retain_known_types(|t| used.contains(t));
+        ci
+    }
+
+    // The types used by the functions that are left, and by the objects with members left,
+    // along with every type that those types use in turn.
+    fn used_types(&self) -> BTreeSet<Type> {
+        // The panic handling always needs strings.
+        let mut pending = vec![Type::String];
+        for func in self.functions.iter() {
+            pending.extend(func.iter_types().cloned());
+            pending.extend(func.throws().map(|e| Type::Error(e.to_string())));
+        }
+        for obj in self.objects.iter() {
+            if !obj.constructors.is_empty() || !obj.methods.is_empty() {
+                pending.push(Type::Object(obj.name.clone()));
+            }
+        }
+        let mut used = BTreeSet::new();
+        while let Some(type_) = pending.pop() {
+            if used.contains(&type_) {
+                continue;
+            }
+            match &type_ {
+                Type::Optional(t) | Type::Sequence(t) | Type::OrderedMap(t) => {
+                    pending.push(t.as_ref().clone())
+                }
+                Type::Map(k, v) => {
+                    pending.push(k.as_ref().clone());
+                    pending.push(v.as_ref().clone());
+                }
+                Type::Wrapped { prim, .. } => pending.push(prim.as_ref().clone()),
+                Type::Record(name) => {
+                    if let Some(rec) = self.get_record_definition(name) {
+                        pending.extend(rec.iter_types().cloned());
+                    }
+                }
+                Type::Enum(name) => {
+                    if let Some(e) = self.get_enum_definition(name) {
+                        pending.extend(e.iter_types().cloned());
+                    }
+                }
+                Type::Error(name) => {
+                    if let Some(e) = self.get_error_definition(name) {
+                        pending.extend(e.iter_types().cloned());
+                        pending.extend(e.source_chain_type());
+                    }
+                }
+                Type::CallbackInterface(name) => {
+                    if let Some(cbi) = self.get_callback_interface_definition(name) {
+                        for meth in cbi.methods.iter() {
+                            pending.extend(meth.iter_types().cloned());
+                            pending.extend(meth.throws().map(|e| Type::Error(e.to_string())));
+                        }
+                    }
+                }
+                Type::Object(name) => {
+                    if let Some(obj) = self.get_object_definition(name) {
+                        pending.extend(obj.iter_types().cloned());
+                        let throws = obj
+                            .constructors
+                            .iter()
+                            .filter_map(|cons| cons.throws())
+                            .chain(obj.methods.iter().filter_map(|meth| meth.throws()));
+                        pending.extend(throws.map(|e| Type::Error(e.to_string())));
+                    }
+                }
+                _ => (),
+            }
+            used.insert(type_);
+        }
+        used
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const UDL: &str = r#"
+        namespace store {
+            Store open_store(string path);
+            [Throws=StoreError]
+            void compact(Store store, CompactOptions options);
+        };
+        dictionary CompactOptions {
+            boolean aggressive;
+        };
+        dictionary Entry {
+            string key;
+            sequence<u8> value;
+        };
+        [Error]
+        enum StoreError { "Busy" };
+        enum Order { "Ascending", "Descending" };
+        interface Store {
+            constructor();
+            Entry? get(string key);
+            sequence<Entry> scan(Order order);
+            void put(Entry entry);
+            [Name=in_memory]
+            constructor();
+        };
+        interface Cursor {
+            string? next();
+        };
+    "#;
+
+    fn subset(keep: &[&str]) -> ComponentInterface {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        ci.subset(|name| keep.contains(&name))
+    }
+
+    #[test]
+    fn test_subset_keeps_checksum() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let sub = ci.subset(|name| name == "open_store");
+        assert_eq!(sub.checksum(), ci.checksum());
+        assert_eq!(sub.ffi_namespace(), ci.ffi_namespace());
+        assert_eq!(
+            sub.get_function_definition("open_store")
+                .unwrap()
+                .ffi_func()
+                .name(),
+            ci.get_function_definition("open_store")
+                .unwrap()
+                .ffi_func()
+                .name()
+        );
+    }
+
+    #[test]
+    fn test_subset_drops_unused_types() {
+        let sub = subset(&["open_store", "Store.get"]);
+        assert_eq!(sub.iter_function_definitions().len(), 1);
+        assert_eq!(sub.iter_object_definitions().len(), 1);
+        let store = sub.get_object_definition("Store").unwrap();
+        assert!(store.constructors().is_empty());
+        assert_eq!(store.methods().len(), 1);
+        assert!(sub.get_record_definition("Entry").is_some());
+        assert!(sub.get_record_definition("CompactOptions").is_none());
+        assert!(sub.get_error_definition("StoreError").is_none());
+        assert!(sub.get_enum_definition("Order").is_none());
+        assert!(sub.get_object_definition("Cursor").is_none());
+        let types = sub.iter_types();
+        assert!(types.contains(&Type::Optional(Box::new(Type::Record("Entry".into())))));
+        assert!(types.contains(&Type::Sequence(Box::new(Type::UInt8))));
+        assert!(!types.contains(&Type::Sequence(Box::new(Type::Record("Entry".into())))));
+        assert!(!types.contains(&Type::Boolean));
+    }
+
+    #[test]
+    fn test_subset_keeps_errors_and_used_objects() {
+        let sub = subset(&["compact"]);
+        assert!(sub.get_error_definition("StoreError").is_some());
+        assert!(sub.get_record_definition("CompactOptions").is_some());
+        // The function takes a `Store`, so it stays, but without any of its members.
+        let store = sub.get_object_definition("Store").unwrap();
+        assert!(store.constructors().is_empty());
+        assert!(store.methods().is_empty());
+        assert!(sub.iter_types().contains(&Type::String));
+    }
+
+    #[test]
+    fn test_subset_of_object_members() {
+        let sub = subset(&["Store.in_memory", "Store.scan"]);
+        assert!(sub.iter_function_definitions().is_empty());
+        let store = sub.get_object_definition("Store").unwrap();
+        let names: Vec<_> = store.constructors().iter().map(|c| c.name()).collect();
+        assert_eq!(names, vec!["in_memory"]);
+        assert!(sub.get_enum_definition("Order").is_some());
+        assert!(sub.get_record_definition("Entry").is_some());
+    }
+
+    #[test]
+    fn test_subset_drops_empty_modules() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+            namespace app {};
+            [Parent=app]
+            namespace tasks {
+                void add(string title);
+            };
+            [Parent=app]
+            namespace notes {
+                void add(string text);
+            };
+        "#,
+        )
+        .unwrap();
+        let sub = ci.subset(|name| name == "tasks.add");
+        let modules: Vec<_> = sub
+            .iter_module_definitions()
+            .iter()
+            .map(|m| m.qualified_name())
+            .collect();
+        assert_eq!(modules, vec!["tasks"]);
+    }
+}
@@ -96,9 +96,13 @@ impl TypeFinder for weedle::TypedefDefinition<'_> {
         // > let t = types.resolve_type_expression(&self.type_)?;
         // > types.add_type_definition(name, t)
         // But we don't - `typedef`s are reserved for external types.
-        if attrs.is_wrapped() {
+        if attrs.is_wrapped() && attrs.is_custom() {
+            bail!("typedef {} can't be both [Wrapped] and [Custom]", name);
+        }
+        if attrs.is_wrapped() || attrs.is_custom() {
             // A local type which wraps a primitive and for which we will generate an
-            // `FfiConverter` implementation.
+            // `FfiConverter` implementation. `[Custom]` types are the same on the FFI, they
+            // just implement a different trait in Rust.
             let prim = types.resolve_type_expression(&self.type_)?;
             types.add_type_definition(
                 name,
@@ -107,6 +111,50 @@ impl TypeFinder for weedle::TypedefDefinition<'_> {
                     prim: prim.into(),
                 },
             )
+        } else if attrs.is_ordered_map() {
+            // A map that keeps its entries in insertion order. It's a different type from
+            // the plain `record<DOMString, T>` that it's declared as, so we resolve the
+            // value type directly rather than resolving the record.
+            use weedle::types::{MayBeNull, NonAnyType, SingleType};
+            let value_type = match &self.type_.type_ {
+                weedle::types::Type::Single(SingleType::NonAny(NonAnyType::RecordType(
+                    MayBeNull {
+                        type_: record,
+                        q_mark: None,
+                    },
+                ))) => types.resolve_type_expression(record.generics.body.2.as_ref())?,
+                _ => bail!(
+                    "[OrderedMap] typedef {} must be a `record<DOMString, T>`",
+                    name
+                ),
+            };
+            types.add_known_type(Type::String)?;
+            types.add_type_definition(name, Type::OrderedMap(Box::new(value_type)))
+        } else if attrs.is_stream() {
+            // An object that hands out the items of the `sequence<T>` that it's declared as a
+            // chunk at a time. Its interface is added along with the other definitions, once
+            // the item type, which may be declared further on, can be resolved.
+            use weedle::types::{MayBeNull, NonAnyType, SingleType};
+            if !matches!(
+                &self.type_.type_,
+                weedle::types::Type::Single(SingleType::NonAny(NonAnyType::Sequence(MayBeNull {
+                    q_mark: None,
+                    ..
+                })))
+            ) {
+                bail!("[Stream] typedef {} must be a `sequence<T>`", name);
+            }
+            types.add_type_definition(name, Type::Object(name.to_string()))
+        } else if attrs.is_external_interface() {
+            // An interface of the component in another crate, whose objects are passed around
+            // as pointers to the same Rust struct.
+            types.add_type_definition(
+                name,
+                Type::ExternalInterface {
+                    name: name.to_string(),
+                    crate_name: attrs.get_crate_name(),
+                },
+            )
         } else {
             // A crate which can supply an `FfiConverter`.
             // We don't reference `self._type`, so ideally we could insist on it being
@@ -212,6 +260,18 @@ mod test {
 
             [Wrapped]
             typedef string ExternalWrapping;
+
+            [Custom]
+            typedef string Url;
+
+            [ExternalInterface="crate-name"]
+            typedef extern ExternalObject;
+
+            [OrderedMap]
+            typedef record<DOMString, u32> Scores;
+
+            [Stream]
+            typedef sequence<i16> Samples;
         "#,
             |types| {
                 assert!(
@@ -222,6 +282,23 @@ mod test {
                     matches!(types.get_type_definition("ExternalWrapping").unwrap(), Type::Wrapped { name, prim }
                                                                                      if name == "ExternalWrapping" && prim == Box::new(Type::String))
                 );
+                assert!(
+                    matches!(types.get_type_definition("Url").unwrap(), Type::Wrapped { name, prim }
+                                                                        if name == "Url" && prim == Box::new(Type::String))
+                );
+                assert!(
+                    matches!(types.get_type_definition("ExternalObject").unwrap(), Type::ExternalInterface { name, crate_name }
+                                                                                   if name == "ExternalObject" && crate_name == "crate-name")
+                );
+                assert_eq!(
+                    types.get_type_definition("Scores").unwrap(),
+                    Type::OrderedMap(Box::new(Type::UInt32))
+                );
+                assert!(!types.iter_known_types().any(|t| matches!(t, Type::Map(..))));
+                assert_eq!(
+                    types.get_type_definition("Samples").unwrap(),
+                    Type::Object("Samples".into())
+                );
             },
         );
     }
@@ -235,6 +312,34 @@ mod test {
         err.to_string()
     }
 
+    #[test]
+    fn test_ordered_map_must_be_a_map() {
+        assert_eq!(
+            get_err("[OrderedMap] typedef sequence<u32> Scores;"),
+            "[OrderedMap] typedef Scores must be a `record<DOMString, T>`"
+        );
+    }
+
+    #[test]
+    fn test_stream_must_be_a_sequence() {
+        assert_eq!(
+            get_err("[Stream] typedef record<DOMString, u32> Lines;"),
+            "[Stream] typedef Lines must be a `sequence<T>`"
+        );
+        assert_eq!(
+            get_err("[Stream] typedef sequence<string>? Lines;"),
+            "[Stream] typedef Lines must be a `sequence<T>`"
+        );
+    }
+
+    #[test]
+    fn test_wrapped_or_custom() {
+        assert_eq!(
+            get_err("[Wrapped, Custom] typedef string Url;"),
+            "typedef Url can't be both [Wrapped] and [Custom]"
+        );
+    }
+
     #[test]
     #[should_panic]
     fn test_typedef_error_on_no_attr() {
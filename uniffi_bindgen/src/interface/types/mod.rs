@@ -48,7 +48,14 @@ pub enum Type {
     Int64,
     Float32,
     Float64,
+    // A 128-bit signed integer, for values that overflow a u64.
+    BigInt,
+    // A byte string, which is a `Vec<u8>` in Rust, but a byte array in the bindings rather than
+    // a sequence of `u8`s.
+    Bytes,
     Boolean,
+    // A Unicode scalar value, which is a `char` in Rust.
+    Char,
     String,
     Timestamp,
     Duration,
@@ -61,9 +68,15 @@ pub enum Type {
     // Structurally recursive types.
     Optional(Box<Type>),
     Sequence(Box<Type>),
-    Map(/* String, */ Box<Type>),
+    // A map from the key type to the value type.
+    Map(Box<Type>, Box<Type>),
+    // A map from strings to the value type that keeps its entries in the order they were
+    // inserted, from an `[OrderedMap]` typedef.
+    OrderedMap(Box<Type>),
     // An FfiConverter we `use` from an external crate
     External { name: String, crate_name: String },
+    // An object from another component, whose pointer is passed without copying the object.
+    ExternalInterface { name: String, crate_name: String },
     // A local type we will generate an FfiConverter via wrapping a primitive.
     Wrapped { name: String, prim: Box<Type> },
 }
@@ -88,8 +101,11 @@ impl Type {
             Type::UInt64 => "u64".into(),
             Type::Float32 => "f32".into(),
             Type::Float64 => "f64".into(),
+            Type::BigInt => "bigint".into(),
+            Type::Bytes => "bytes".into(),
             Type::String => "string".into(),
             Type::Boolean => "bool".into(),
+            Type::Char => "char".into(),
             // API defined types.
             // Note that these all get unique names, and the parser ensures that the names do not
             // conflict with a builtin type. We add a prefix to the name to guard against pathological
@@ -107,12 +123,17 @@ impl Type {
             // These add a prefix to the name of the underlying type.
             // The component API definition cannot give names to recursive types, so as long as the
             // prefixes we add here are all unique amongst themselves, then we have no chance of
-            // acccidentally generating name collisions.
+            // acccidentally generating name collisions. Maps have two underlying types, so they
+            // separate the key from the value, or a map from the enum `A` to `u32?` would have the
+            // same name as one from the enum `AOptional` to `u32`.
             Type::Optional(t) => format!("Optional{}", t.canonical_name()),
             Type::Sequence(t) => format!("Sequence{}", t.canonical_name()),
-            Type::Map(t) => format!("Map{}", t.canonical_name()),
+            Type::Map(k, v) => format!("Map{}_{}", k.canonical_name(), v.canonical_name()),
+            Type::OrderedMap(t) => format!("OrderedMap{}", t.canonical_name()),
             // A type that exists externally.
-            Type::External { name, .. } | Type::Wrapped { name, .. } => name.to_owned(),
+            Type::External { name, .. }
+            | Type::ExternalInterface { name, .. }
+            | Type::Wrapped { name, .. } => name.to_owned(),
         }
     }
 }
@@ -138,11 +159,13 @@ impl From<&Type> for FFIType {
             Type::Float64 => FFIType::Float64,
             // Booleans lower into an Int8, to work around a bug in JNA.
             Type::Boolean => FFIType::Int8,
+            // Chars lower into their scalar value.
+            Type::Char => FFIType::UInt32,
             // Strings are always owned rust values.
             // We might add a separate type for borrowed strings in future.
             Type::String => FFIType::RustBuffer,
-            // Objects are pointers to an Arc<>
-            Type::Object(_) => FFIType::RustArcPtr,
+            // Objects are pointers to an Arc<>, whichever component they come from.
+            Type::Object(_) | Type::ExternalInterface { .. } => FFIType::RustArcPtr,
             // Callback interfaces are passed as opaque integer handles.
             Type::CallbackInterface(_) => FFIType::UInt64,
             // Other types are serialized into a bytebuffer and deserialized on the other side.
@@ -151,7 +174,10 @@ impl From<&Type> for FFIType {
             | Type::Record(_)
             | Type::Optional(_)
             | Type::Sequence(_)
-            | Type::Map(_)
+            | Type::Map(..)
+            | Type::OrderedMap(_)
+            | Type::BigInt
+            | Type::Bytes
             | Type::Timestamp
             | Type::Duration
             | Type::External { .. } => FFIType::RustBuffer,
@@ -170,7 +196,7 @@ impl From<&Type> for FFIType {
 /// You could imagine this struct doing some clever interning of names and so-on in future,
 /// to reduce the overhead of passing around [Type] instances. For now we just do a whole
 /// lot of cloning.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub(crate) struct TypeUniverse {
     // Named type definitions (including aliases).
     type_definitions: HashMap<String, Type>,
@@ -236,6 +262,14 @@ impl TypeUniverse {
     pub fn iter_known_types(&self) -> impl Iterator<Item = Type> + '_ {
         self.all_known_types.iter().cloned()
     }
+
+    /// Forget the known types that `keep` returns false for, leaving the named definitions.
+    pub(super) fn retain_known_types(&mut self, keep: impl Fn(&Type) -> bool) {
+        self.all_known_types = std::mem::take(&mut self.all_known_types)
+            .into_iter()
+            .filter(|t| keep(t))
+            .collect();
+    }
 }
 
 /// An abstract type for an iterator over &Type references.
@@ -286,7 +320,10 @@ impl<T: IterTypes> IterTypes for Option<T> {
 impl IterTypes for Type {
     fn iter_types(&self) -> TypeIterator<'_> {
         let nested_types = match self {
-            Type::Optional(t) | Type::Sequence(t) | Type::Map(t) => Some(t.iter_types()),
+            Type::Optional(t) | Type::Sequence(t) | Type::OrderedMap(t) => Some(t.iter_types()),
+            Type::Map(k, v) => {
+                Some(Box::new(k.iter_types().chain(v.iter_types())) as TypeIterator<'_>)
+            }
             _ => None,
         };
         Box::new(std::iter::once(self).chain(nested_types.into_iter().flatten()))
@@ -315,6 +352,26 @@ mod test_type {
             .canonical_name(),
             "OptionalSequenceTypeExample"
         );
+        assert_eq!(
+            Type::Map(
+                Box::new(Type::Enum("A".into())),
+                Box::new(Type::Optional(Box::new(Type::UInt32)))
+            )
+            .canonical_name(),
+            "MapTypeA_Optionalu32"
+        );
+        assert_ne!(
+            Type::Map(
+                Box::new(Type::Enum("A".into())),
+                Box::new(Type::Optional(Box::new(Type::UInt32)))
+            )
+            .canonical_name(),
+            Type::Map(
+                Box::new(Type::Enum("AOptional".into())),
+                Box::new(Type::UInt32)
+            )
+            .canonical_name()
+        );
     }
 }
 
@@ -119,12 +119,45 @@ impl TypeResolver for weedle::types::SequenceType<'_> {
     }
 }
 
+impl TypeResolver for weedle::types::RecordKeyType<'_> {
+    fn resolve_type_expression(&self, types: &mut TypeUniverse) -> Result<Type> {
+        match self {
+            weedle::types::RecordKeyType::DOM(_) => types.add_known_type(Type::String),
+            weedle::types::RecordKeyType::NonAny(t) => {
+                let key_type = t.resolve_type_expression(types)?;
+                // The foreign languages need to be able to hash and compare the keys, so we
+                // only support the types that they can all use as keys out of the box. Whether
+                // an enum has associated data isn't known yet, so that's checked later.
+                match key_type {
+                    Type::String
+                    | Type::UInt8
+                    | Type::Int8
+                    | Type::UInt16
+                    | Type::Int16
+                    | Type::UInt32
+                    | Type::Int32
+                    | Type::UInt64
+                    | Type::Int64
+                    | Type::Enum(_) => Ok(key_type),
+                    _ => bail!(
+                        "map keys must be strings, integers or enums, not {:?}",
+                        key_type
+                    ),
+                }
+            }
+            _ => bail!(
+                "WebIDL byte or USV string keys not implemented ({:?}); consider using DOMString or string",
+                self
+            ),
+        }
+    }
+}
+
 impl TypeResolver for weedle::types::RecordType<'_> {
     fn resolve_type_expression(&self, types: &mut TypeUniverse) -> Result<Type> {
-        let t = (&self.generics.body.2).resolve_type_expression(types)?;
-        // Maps always have string keys, make sure the `String` type is known.
-        types.add_known_type(Type::String)?;
-        types.add_known_type(Type::Map(Box::new(t)))
+        let k = self.generics.body.0.resolve_type_expression(types)?;
+        let v = (&self.generics.body.2).resolve_type_expression(types)?;
+        types.add_known_type(Type::Map(Box::new(k), Box::new(v)))
     }
 }
 
@@ -181,6 +214,9 @@ pub(in super::super) fn resolve_builtin_type(name: &str) -> Option<Type> {
         "i64" => Some(Type::Int64),
         "f32" => Some(Type::Float32),
         "f64" => Some(Type::Float64),
+        "bigint" => Some(Type::BigInt),
+        "bytes" => Some(Type::Bytes),
+        "char" => Some(Type::Char),
         "timestamp" => Some(Type::Timestamp),
         "duration" => Some(Type::Duration),
         _ => None,
@@ -251,11 +287,11 @@ mod test {
         assert_eq!(types.iter_known_types().count(), 0);
         let (_, expr) = weedle::types::Type::parse("record<DOMString, float>").unwrap();
         let t = types.resolve_type_expression(expr).unwrap();
-        assert_eq!(t.canonical_name(), "Mapf32");
+        assert_eq!(t.canonical_name(), "Mapstring_f32");
         assert_eq!(types.iter_known_types().count(), 3);
         assert!(types
             .iter_known_types()
-            .any(|t| t.canonical_name() == "Mapf32"));
+            .any(|t| t.canonical_name() == "Mapstring_f32"));
         assert!(types
             .iter_known_types()
             .any(|t| t.canonical_name() == "string"));
@@ -264,6 +300,28 @@ mod test {
             .any(|t| t.canonical_name() == "f32"));
     }
 
+    #[test]
+    fn test_resolving_map_type_with_other_keys() -> Result<()> {
+        let mut types = TypeUniverse::default();
+        types.add_type_definition("Color", Type::Enum("Color".into()))?;
+        let (_, expr) = weedle::types::Type::parse("record<u64, string>").unwrap();
+        let t = types.resolve_type_expression(expr).unwrap();
+        assert_eq!(t, Type::Map(Box::new(Type::UInt64), Box::new(Type::String)));
+        assert!(types.iter_known_types().any(|t| t == &Type::UInt64));
+
+        let (_, expr) = weedle::types::Type::parse("record<Color, u32>").unwrap();
+        let t = types.resolve_type_expression(expr).unwrap();
+        assert_eq!(t.canonical_name(), "MapTypeColor_u32");
+
+        let (_, expr) = weedle::types::Type::parse("record<double, u32>").unwrap();
+        let err = types.resolve_type_expression(expr).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "map keys must be strings, integers or enums, not Float64"
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_error_on_unknown_type() -> Result<()> {
         let mut types = TypeUniverse::default();
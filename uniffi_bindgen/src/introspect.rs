@@ -0,0 +1,123 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Component introspection
+//!
+//! `uniffi-bindgen introspect` prints a JSON description of a component's objects, for tools
+//! that check how an app uses them, like linters that look for calls on the wrong thread:
+//!
+//! ```text
+//! {
+//!   "namespace": "todolist",
+//!   "objects": [
+//!     {
+//!       "name": "TodoList",
+//!       "threading": "main-thread-only",
+//!       "methods": ["add_item", "get_items"]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! `threading` is `threadsafe` for objects that can be used from any thread, which is the
+//! default, `main-thread-only` for those marked `[MainThread]`, and `blocking` for those
+//! marked `[Blocking]`, which can be used from any thread apart from the main one. The
+//! bindings also give each object class a `UNIFFI_THREADING` constant with the same value.
+//! The objects are sorted by name, and their methods are in the order that the UDL declares
+//! them.
+
+use crate::interface::*;
+
+/// Describe the objects of a component as JSON.
+pub fn introspection_json(ci: &ComponentInterface) -> String {
+    let mut objects = ci.iter_object_definitions();
+    objects.sort_by(|a, b| a.name().cmp(b.name()));
+    let objects: Vec<_> = objects
+        .iter()
+        .map(|obj| {
+            let methods: Vec<_> = obj.methods().iter().map(|m| json_string(m.name())).collect();
+            format!(
+                "    {{\n      \"name\": {},\n      \"threading\": {},\n      \"methods\": [{}]\n    }}",
+                json_string(obj.name()),
+                json_string(obj.threading().as_str()),
+                methods.join(", ")
+            )
+        })
+        .collect();
+    format!(
+        "{{\n  \"namespace\": {},\n  \"objects\": [{}]\n}}\n",
+        json_string(ci.namespace()),
+        if objects.is_empty() {
+            String::new()
+        } else {
+            format!("\n{}\n  ", objects.join(",\n"))
+        }
+    )
+}
+
+fn json_string(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_introspection_json() {
+        const UDL: &str = r#"
+            namespace test {};
+            [MainThread]
+            interface View {
+                void draw();
+                void resize(u32 width, u32 height);
+            };
+            [Blocking]
+            interface Database {};
+            interface Counter {};
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert_eq!(
+            introspection_json(&ci),
+            r#"{
+  "namespace": "test",
+  "objects": [
+    {
+      "name": "Counter",
+      "threading": "threadsafe",
+      "methods": []
+    },
+    {
+      "name": "Database",
+      "threading": "blocking",
+      "methods": []
+    },
+    {
+      "name": "View",
+      "threading": "main-thread-only",
+      "methods": ["draw", "resize"]
+    }
+  ]
+}
+"#
+        );
+
+        let ci = ComponentInterface::from_webidl("namespace test {};").unwrap();
+        assert_eq!(
+            introspection_json(&ci),
+            "{\n  \"namespace\": \"test\",\n  \"objects\": []\n}\n"
+        );
+        assert_eq!(json_string("a\"b\\c\n"), r#""a\"b\\c\u000a""#);
+    }
+}
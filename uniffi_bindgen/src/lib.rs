@@ -99,39 +99,52 @@ use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
 use std::io::prelude::*;
 use std::{
-    collections::HashMap,
+    collections::BTreeMap,
     env,
     fs::File,
     path::{Path, PathBuf},
     process::Command,
+    rc::Rc,
 };
 
 pub mod backend;
 pub mod bindings;
+pub mod golden;
+pub mod init;
 pub mod interface;
+pub mod introspect;
+pub mod lockfile;
+pub mod prune;
 pub mod scaffolding;
+pub mod stats;
+pub mod strict;
 
 use bindings::TargetLanguage;
 use interface::ComponentInterface;
-use scaffolding::RustScaffolding;
+use lockfile::ApiLock;
+use scaffolding::{RustScaffolding, ScaffoldingTarget};
 
 // Generate the infrastructural Rust code for implementing the UDL interface,
-// such as the `extern "C"` function definitions and record data types.
+// such as the `extern "C"` function definitions and record data types, or the
+// `wasm_bindgen` exports for the `target` of `ScaffoldingTarget::Wasm`. With `roots`,
+// only the part of the interface that they reference is exported.
 pub fn generate_component_scaffolding<P: AsRef<Path>>(
     udl_file: P,
     config_file_override: Option<P>,
     out_dir_override: Option<P>,
+    target: ScaffoldingTarget,
+    roots: Option<&[&str]>,
     format_code: bool,
 ) -> Result<()> {
     let config_file_override = config_file_override.as_ref().map(|p| p.as_ref());
     let out_dir_override = out_dir_override.as_ref().map(|p| p.as_ref());
     let udl_file = udl_file.as_ref();
-    let component = parse_udl(udl_file)?;
-    let _config = get_config(
+    let component = parse_and_prune_udl(udl_file, roots)?;
+    let config = get_config(
         &component,
         guess_crate_root(udl_file)?,
         config_file_override,
-    );
+    )?;
     let mut filename = Path::new(&udl_file)
         .file_stem()
         .ok_or_else(|| anyhow!("not a file"))?
@@ -141,8 +154,17 @@ pub fn generate_component_scaffolding<P: AsRef<Path>>(
     out_dir.push(filename);
     let mut f =
         File::create(&out_dir).map_err(|e| anyhow!("Failed to create output file: {:?}", e))?;
-    write!(f, "{}", RustScaffolding::new(&component))
-        .map_err(|e| anyhow!("Failed to write output file: {:?}", e))?;
+    let scaffolding = match target {
+        ScaffoldingTarget::Native => {
+            let scaffolding = RustScaffolding::new(&component).to_string();
+            scaffolding::abi_check::check_scaffolding_abi(&component, &scaffolding)?;
+            scaffolding
+        }
+        ScaffoldingTarget::Wasm => scaffolding::wasm::generate_wasm_scaffolding(&component)?,
+    };
+    let scaffolding = config
+        .with_post_processors(|| backend::post_process::post_process(&out_dir, scaffolding))?;
+    write!(f, "{}", scaffolding).map_err(|e| anyhow!("Failed to write output file: {:?}", e))?;
     if format_code {
         Command::new("rustfmt").arg(&out_dir).status()?;
     }
@@ -150,43 +172,56 @@ pub fn generate_component_scaffolding<P: AsRef<Path>>(
 }
 
 // Generate the bindings in the target languages that call the scaffolding
-// Rust code.
+// Rust code. `python_backend` overrides the backend that the config picks for Python.
+// With `roots`, the bindings are only for the part of the interface that they reference,
+// which must match the scaffolding.
 pub fn generate_bindings<P: AsRef<Path>>(
     udl_file: P,
     config_file_override: Option<P>,
     target_languages: Vec<&str>,
     out_dir_override: Option<P>,
+    python_backend: Option<bindings::python::PythonBackend>,
+    roots: Option<&[&str]>,
     try_format_code: bool,
 ) -> Result<()> {
     let out_dir_override = out_dir_override.as_ref().map(|p| p.as_ref());
     let config_file_override = config_file_override.as_ref().map(|p| p.as_ref());
     let udl_file = udl_file.as_ref();
 
-    let component = parse_udl(udl_file)?;
-    let config = get_config(
+    let component = parse_and_prune_udl(udl_file, roots)?;
+    let mut config = get_config(
         &component,
         guess_crate_root(udl_file)?,
         config_file_override,
     )?;
-    let out_dir = get_out_dir(udl_file, out_dir_override)?;
-    for language in target_languages {
-        bindings::write_bindings(
-            &config.bindings,
-            &component,
-            &out_dir,
-            language.try_into()?,
-            try_format_code,
-        )?;
+    if let Some(backend) = python_backend {
+        config.bindings = config.bindings.with_python_backend(backend);
     }
-    Ok(())
+    let out_dir = get_out_dir(udl_file, out_dir_override)?;
+    config.with_post_processors(|| -> Result<()> {
+        for language in target_languages {
+            bindings::write_bindings(
+                &config.bindings,
+                &component,
+                &out_dir,
+                language.try_into()?,
+                try_format_code,
+            )?;
+        }
+        Ok(())
+    })
 }
 
 // Run tests against the foreign language bindings (generated and compiled at the same time).
 // Note that the cdylib we're testing against must be built already.
+//
+// Test scripts for a language whose backend lacks any of the `required_features` are skipped,
+// with a note printed to say so, rather than failing the run.
 pub fn run_tests<P: AsRef<Path>>(
     cdylib_dir: P,
     udl_files: &[&str],
     test_scripts: Vec<&str>,
+    required_features: &[&str],
     config_file_override: Option<P>,
 ) -> Result<()> {
     // XXX - this is just for tests, so one config_file_override for all .udl files doesn't really
@@ -198,8 +233,8 @@ pub fn run_tests<P: AsRef<Path>>(
     let cdylib_dir = cdylib_dir.as_ref();
     let config_file_override = config_file_override.as_ref().map(|p| p.as_ref());
 
-    // Group the test scripts by language first.
-    let mut language_tests: HashMap<TargetLanguage, Vec<String>> = HashMap::new();
+    // Group the test scripts by language first, running the languages in a stable order.
+    let mut language_tests: BTreeMap<TargetLanguage, Vec<String>> = BTreeMap::new();
 
     for test_script in test_scripts {
         let lang: TargetLanguage = PathBuf::from(test_script)
@@ -213,6 +248,23 @@ pub fn run_tests<P: AsRef<Path>>(
     }
 
     for (lang, test_scripts) in language_tests {
+        let mut missing_features = Vec::new();
+        for feature in required_features {
+            if !lang.supports_feature(feature)? {
+                missing_features.push(*feature);
+            }
+        }
+        if !missing_features.is_empty() {
+            for test_script in test_scripts {
+                println!(
+                    "Skipping {}: the {:?} backend does not support {}",
+                    test_script,
+                    lang,
+                    missing_features.join(", ")
+                );
+            }
+            continue;
+        }
         for udl_file in udl_files {
             let crate_root = guess_crate_root(Path::new(udl_file))?;
             let component = parse_udl(Path::new(udl_file))?;
@@ -227,6 +279,289 @@ pub fn run_tests<P: AsRef<Path>>(
     Ok(())
 }
 
+// Write an API lockfile recording the public API of the UDL interface, so that changes to it
+// can be reviewed. By default this is an `api.lock` file in the root of the crate.
+pub fn generate_api_lockfile<P: AsRef<Path>>(
+    udl_file: P,
+    lockfile_override: Option<P>,
+) -> Result<()> {
+    let udl_file = udl_file.as_ref();
+    let lockfile_override = lockfile_override.as_ref().map(|p| p.as_ref());
+    let component = parse_udl(udl_file)?;
+    let lockfile = get_lockfile_path(udl_file, lockfile_override)?;
+    let mut f =
+        File::create(&lockfile).map_err(|e| anyhow!("Failed to create lockfile: {:?}", e))?;
+    write!(
+        f,
+        "{}",
+        ApiLock::from_component_interface(&component).render()
+    )
+    .map_err(|e| anyhow!("Failed to write lockfile: {:?}", e))?;
+    Ok(())
+}
+
+// Check that the API lockfile is up-to-date with the UDL interface, failing with a summary of
+// the changes if not.
+pub fn verify_api_lockfile<P: AsRef<Path>>(
+    udl_file: P,
+    lockfile_override: Option<P>,
+) -> Result<()> {
+    let udl_file = udl_file.as_ref();
+    let lockfile_override = lockfile_override.as_ref().map(|p| p.as_ref());
+    let component = parse_udl(udl_file)?;
+    let lockfile = get_lockfile_path(udl_file, lockfile_override)?;
+    let contents = slurp_file(&lockfile).with_context(|| {
+        format!(
+            "Failed to read API lockfile from {:?}; run `uniffi-bindgen lock` to create it",
+            &lockfile
+        )
+    })?;
+    let recorded = ApiLock::parse(&contents)
+        .with_context(|| format!("Invalid API lockfile {:?}", &lockfile))?;
+    ApiLock::from_component_interface(&component)
+        .verify(&recorded)
+        .with_context(|| format!("API lockfile {:?} is out of date", &lockfile))
+}
+
+// Print a JSON description of the component's objects, including the threads that each of them
+// can be used from, for tools that check how an app uses the component.
+pub fn print_introspection<P: AsRef<Path>>(udl_file: P) -> Result<()> {
+    let component = parse_udl(udl_file.as_ref())?;
+    print!("{}", introspect::introspection_json(&component));
+    Ok(())
+}
+
+// Check that the UDL file describes a valid component. In strict mode, this also fails if the
+// public API uses bare strings or maps that aren't allowed by the `[strict]` config.
+pub fn check_component<P: AsRef<Path>>(
+    udl_file: P,
+    config_file_override: Option<P>,
+    strict: bool,
+) -> Result<()> {
+    let config_file_override = config_file_override.as_ref().map(|p| p.as_ref());
+    let udl_file = udl_file.as_ref();
+    let component = parse_udl(udl_file)?;
+    if !strict {
+        return Ok(());
+    }
+    let config = get_config(
+        &component,
+        guess_crate_root(udl_file)?,
+        config_file_override,
+    )?;
+    let violations = strict::check_strict(&component, &config.strict);
+    if !violations.is_empty() {
+        bail!(
+            "The component API uses {} bare string(s) or map(s). Use more specific types, \
+             or allow them in the `[strict]` section of `uniffi.toml`:\n{}",
+            violations.len(),
+            violations
+                .iter()
+                .map(|v| format!("  {}", v))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+    Ok(())
+}
+
+// Generate the scaffolding and the bindings in the target languages, and compare them with the
+// golden files for the UDL interface, failing with a list of the differences. By default the
+// golden files are in `golden/<udl name>/` in the root of the crate. With `update` set, or
+// `UNIFFI_UPDATE_GOLDEN` in the environment, the golden files are rewritten instead.
+pub fn check_golden_files<P: AsRef<Path>>(
+    udl_file: P,
+    config_file_override: Option<P>,
+    target_languages: Vec<&str>,
+    golden_dir_override: Option<P>,
+    update: bool,
+) -> Result<()> {
+    let config_file_override = config_file_override.as_ref().map(|p| p.as_ref());
+    let golden_dir_override = golden_dir_override.as_ref().map(|p| p.as_ref());
+    let udl_file = udl_file.as_ref();
+    let golden_dir = get_golden_dir(udl_file, golden_dir_override)?;
+    let generated = generate_golden_files(udl_file, config_file_override, target_languages)?;
+    if update || env::var_os(golden::UPDATE_GOLDEN_ENV_VAR).is_some() {
+        return golden::write_golden_files(&golden_dir, &generated);
+    }
+    if !golden_dir.is_dir() {
+        bail!(
+            "No golden files found in {:?}; run `uniffi-bindgen golden --update` to create them",
+            &golden_dir
+        );
+    }
+    let mismatches = golden::compare(&golden::read_golden_files(&golden_dir)?, &generated);
+    if !mismatches.is_empty() {
+        bail!(
+            "The generated code differs from the golden files in {:?} in {} place(s). If the \
+             change is intended, run `uniffi-bindgen golden --update` and review the diff:\n{}",
+            &golden_dir,
+            mismatches.len(),
+            mismatches
+                .iter()
+                .map(|m| format!("  {}", m))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+    Ok(())
+}
+
+// Create the crate for a new component called `name`, in a directory of that name inside
+// `parent_dir`, or the current directory if that's not given.
+pub fn init_component<P: AsRef<Path>>(name: &str, parent_dir: Option<P>) -> Result<()> {
+    let parent_dir = match parent_dir.as_ref() {
+        Some(dir) => dir.as_ref().to_path_buf(),
+        None => env::current_dir()?,
+    };
+    let files = init::component_files(name, BINDGEN_VERSION)?;
+    let crate_dir = parent_dir.join(name);
+    init::write_component(&crate_dir, &files)?;
+    println!("Created component `{}` in {:?}", name, crate_dir);
+    Ok(())
+}
+
+// Print how much the component exposes and how many lines of code are generated for it, along
+// with the types that the backends for the target languages don't support yet. A language whose
+// bindings fail to generate is reported as such, rather than failing the whole report.
+//
+// With `roots`, the report is for the part of the component that they reference, and lists what
+// was pruned. With `by_declaration`, it also breaks the generated code down by declaration,
+// which generates it again for each of them.
+pub fn report_stats<P: AsRef<Path>>(
+    udl_file: P,
+    config_file_override: Option<P>,
+    target_languages: Vec<&str>,
+    roots: Option<&[&str]>,
+    by_declaration: bool,
+) -> Result<()> {
+    let config_file_override = config_file_override.as_ref().map(|p| p.as_ref());
+    let udl_file = udl_file.as_ref();
+    let full = parse_udl(udl_file)?;
+    let config = get_config(&full, guess_crate_root(udl_file)?, config_file_override)?;
+    let component = match roots {
+        Some(roots) => prune::prune(&full, roots)?,
+        None => full.clone(),
+    };
+    let mut languages = Vec::new();
+    for language in target_languages {
+        let target: TargetLanguage = language.try_into()?;
+        languages.push(stats::LanguageStats {
+            language: target,
+            generated_lines: count_generated_lines(udl_file, &config, &component, target),
+            unsupported: stats::unsupported_types(&component, target)?,
+        });
+    }
+    let by_declaration = if by_declaration {
+        // Only the languages whose bindings generate at all are worth breaking down.
+        let targets: Vec<TargetLanguage> = languages
+            .iter()
+            .filter(|lang| lang.generated_lines.is_ok())
+            .map(|lang| lang.language)
+            .collect();
+        Some(stats::declaration_sizes(
+            &component,
+            targets.clone(),
+            |ci| stats::GeneratedLines {
+                scaffolding: stats::count_lines(&RustScaffolding::new(ci).to_string()),
+                bindings: targets
+                    .iter()
+                    .map(|target| count_generated_lines(udl_file, &config, ci, *target).ok())
+                    .collect(),
+            },
+        )?)
+    } else {
+        None
+    };
+    let report = stats::StatsReport {
+        component: stats::ComponentStats::new(&component),
+        scaffolding_lines: stats::count_lines(&RustScaffolding::new(&component).to_string()),
+        languages,
+        unreferenced: roots.map(|_| prune::Unreferenced::between(&full, &component)),
+        by_declaration,
+    };
+    println!("{}", report);
+    Ok(())
+}
+
+// The lines of code in the bindings generated for the component in the target language, or why
+// they couldn't be generated.
+fn count_generated_lines(
+    udl_file: &Path,
+    config: &Config,
+    component: &ComponentInterface,
+    language: TargetLanguage,
+) -> std::result::Result<usize, String> {
+    generate_files(udl_file, config, component, vec![language])
+        .map(|files| {
+            files
+                .iter()
+                .filter(|(path, _)| path.starts_with(golden::language_dir(language)))
+                .map(|(_, contents)| stats::count_lines(contents))
+                .sum()
+        })
+        .map_err(|e| format!("{:#}", e))
+}
+
+// Generate the files to compare with the golden ones, in a staging directory that's removed
+// again afterwards.
+fn generate_golden_files(
+    udl_file: &Path,
+    config_file_override: Option<&Path>,
+    target_languages: Vec<&str>,
+) -> Result<golden::GoldenFiles> {
+    let component = parse_udl(udl_file)?;
+    let config = get_config(
+        &component,
+        guess_crate_root(udl_file)?,
+        config_file_override,
+    )?;
+    let target_languages = target_languages
+        .into_iter()
+        .map(|language| language.try_into())
+        .collect::<Result<_>>()?;
+    generate_files(udl_file, &config, &component, target_languages)
+}
+
+// Generate the scaffolding and bindings for the component into a staging directory, and read
+// them back in, removing the directory again afterwards.
+fn generate_files(
+    udl_file: &Path,
+    config: &Config,
+    component: &ComponentInterface,
+    target_languages: Vec<TargetLanguage>,
+) -> Result<golden::GoldenFiles> {
+    let staging_dir = env::temp_dir().join(format!(
+        "uniffi-golden-{}-{}",
+        component.namespace(),
+        std::process::id()
+    ));
+    if staging_dir.exists() {
+        std::fs::remove_dir_all(&staging_dir)?;
+    }
+    let result = (|| -> Result<golden::GoldenFiles> {
+        let scaffolding_dir = staging_dir.join("scaffolding");
+        std::fs::create_dir_all(&scaffolding_dir)?;
+        let mut filename = udl_file
+            .file_stem()
+            .ok_or_else(|| anyhow!("not a file"))?
+            .to_os_string();
+        filename.push(".uniffi.rs");
+        std::fs::write(
+            scaffolding_dir.join(filename),
+            RustScaffolding::new(component).to_string(),
+        )?;
+        for language in target_languages {
+            let out_dir = staging_dir.join(golden::language_dir(language));
+            std::fs::create_dir_all(&out_dir)?;
+            bindings::write_bindings(&config.bindings, component, &out_dir, language, false)?;
+        }
+        golden::read_golden_files(&staging_dir)
+    })();
+    std::fs::remove_dir_all(&staging_dir)?;
+    result
+}
+
 /// Guess the root directory of the crate from the path of its UDL file.
 ///
 /// For now, we assume that the UDL file is in `./src/something.udl` relative
@@ -256,16 +591,31 @@ fn get_config(
         None => crate_root.join("uniffi.toml").canonicalize().ok(),
     };
 
-    match config_file {
+    let config = match config_file {
         Some(path) => {
             let contents = slurp_file(&path)
                 .with_context(|| format!("Failed to read config file from {:?}", &path))?;
             let loaded_config: Config = toml::de::from_str(&contents)
                 .with_context(|| format!("Failed to generate config from file {:?}", &path))?;
-            Ok(loaded_config.merge_with(&default_config))
+            loaded_config.merge_with(&default_config)
         }
-        None => Ok(default_config),
+        None => default_config,
+    };
+
+    // Renames for migrating hand-written bindings live in a file of their own, since they
+    // can run to thousands of entries.
+    let renames_file = crate_root.join(backend::renames::DEFAULT_RENAMES_FILE);
+    if !renames_file.is_file() {
+        return Ok(config);
     }
+    let contents = slurp_file(&renames_file)
+        .with_context(|| format!("Failed to read renames from {:?}", &renames_file))?;
+    let renames: backend::renames::RenamesFile = toml::de::from_str(&contents)
+        .with_context(|| format!("Failed to parse renames from {:?}", &renames_file))?;
+    Ok(Config {
+        bindings: config.bindings.with_renames_from(&renames),
+        ..config
+    })
 }
 
 fn get_out_dir(udl_file: &Path, out_dir_override: Option<&Path>) -> Result<PathBuf> {
@@ -283,6 +633,34 @@ fn get_out_dir(udl_file: &Path, out_dir_override: Option<&Path>) -> Result<PathB
     })
 }
 
+fn get_golden_dir(udl_file: &Path, golden_dir_override: Option<&Path>) -> Result<PathBuf> {
+    Ok(match golden_dir_override {
+        Some(path) => path.to_owned(),
+        None => {
+            let name = udl_file.file_stem().ok_or_else(|| anyhow!("not a file"))?;
+            guess_crate_root(udl_file)?
+                .join(golden::DEFAULT_GOLDEN_DIR)
+                .join(name)
+        }
+    })
+}
+
+fn get_lockfile_path(udl_file: &Path, lockfile_override: Option<&Path>) -> Result<PathBuf> {
+    Ok(match lockfile_override {
+        Some(path) => path.to_owned(),
+        None => guess_crate_root(udl_file)?.join(lockfile::DEFAULT_LOCKFILE_NAME),
+    })
+}
+
+// Parse the UDL file, and prune what the `roots` don't reference, if there are any.
+fn parse_and_prune_udl(udl_file: &Path, roots: Option<&[&str]>) -> Result<ComponentInterface> {
+    let component = parse_udl(udl_file)?;
+    match roots {
+        Some(roots) => prune::prune(&component, roots),
+        None => Ok(component),
+    }
+}
+
 fn parse_udl(udl_file: &Path) -> Result<ComponentInterface> {
     let udl =
         slurp_file(udl_file).map_err(|_| anyhow!("Failed to read UDL from {:?}", &udl_file))?;
@@ -301,12 +679,31 @@ fn slurp_file(file_name: &Path) -> Result<String> {
 struct Config {
     #[serde(default)]
     bindings: bindings::Config,
+    #[serde(default)]
+    strict: strict::StrictConfig,
+    #[serde(default)]
+    post_process: Option<Vec<backend::post_process::PostProcessCommand>>,
+}
+
+impl Config {
+    // Run `f` with the `[[post_process]]` commands installed for the files it generates.
+    fn with_post_processors<T>(&self, f: impl FnOnce() -> T) -> T {
+        let commands = self
+            .post_process
+            .iter()
+            .flatten()
+            .map(|cmd| Rc::new(cmd.clone()) as Rc<dyn backend::post_process::PostProcessor>)
+            .collect();
+        backend::post_process::with_post_processors(commands, f)
+    }
 }
 
 impl From<&ComponentInterface> for Config {
     fn from(ci: &ComponentInterface) -> Self {
         Config {
             bindings: ci.into(),
+            strict: Default::default(),
+            post_process: None,
         }
     }
 }
@@ -319,6 +716,8 @@ impl MergeWith for Config {
     fn merge_with(&self, other: &Self) -> Self {
         Config {
             bindings: self.bindings.merge_with(&other.bindings),
+            strict: self.strict.merge_with(&other.strict),
+            post_process: self.post_process.merge_with(&other.post_process),
         }
     }
 }
@@ -333,8 +732,20 @@ impl<T: Clone> MergeWith for Option<T> {
     }
 }
 
+// Entries in `self` take precedence over those for the same key in `other`.
+impl<K: Clone + Ord, V: Clone> MergeWith for BTreeMap<K, V> {
+    fn merge_with(&self, other: &Self) -> Self {
+        other
+            .iter()
+            .chain(self.iter())
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+}
+
 pub fn run_main() -> Result<()> {
-    const POSSIBLE_LANGUAGES: &[&str] = &["kotlin", "python", "swift", "ruby"];
+    const POSSIBLE_LANGUAGES: &[&str] =
+        &["kotlin", "python", "swift", "ruby", "csharp", "go", "rust"];
     let matches = clap::App::new("uniffi-bindgen")
         .about("Scaffolding and bindings generator for Rust")
         .version(clap::crate_version!())
@@ -364,6 +775,33 @@ pub fn run_main() -> Result<()> {
                         .long("--no-format")
                         .help("Do not try to format the generated bindings"),
                 )
+                .arg(
+                    clap::Arg::with_name("locked")
+                        .long("--locked")
+                        .help("Fail if the API lockfile in the crate root is missing or out of date"),
+                )
+                .arg(
+                    clap::Arg::with_name("prune_unused")
+                        .long("--prune-unused")
+                        .requires("roots")
+                        .help("Leave out everything that the roots don't reference"),
+                )
+                .arg(
+                    clap::Arg::with_name("roots")
+                        .long("--roots")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .requires("prune_unused")
+                        .help("Function(s), constructor(s) or method(s) that the app calls, such as `open_store` or `Store.get`, or `Store` for all of an object's members"),
+                )
+                .arg(
+                    clap::Arg::with_name("python_backend")
+                        .long("--python-backend")
+                        .takes_value(true)
+                        .possible_values(&["ctypes", "pyo3"])
+                        .help("How the Python bindings call into Rust: a ctypes module (the default), or a PyO3 extension module for the crate to include"),
+                )
                 .arg(clap::Arg::with_name("udl_file").required(true))
                 .arg(
                     clap::Arg::with_name("config")
@@ -399,6 +837,149 @@ pub fn run_main() -> Result<()> {
                         .long("--no-format")
                         .help("Do not format the generated code with rustfmt (useful for maintainers)"),
                 )
+                .arg(
+                    clap::Arg::with_name("locked")
+                        .long("--locked")
+                        .help("Fail if the API lockfile in the crate root is missing or out of date"),
+                )
+                .arg(
+                    clap::Arg::with_name("prune_unused")
+                        .long("--prune-unused")
+                        .requires("roots")
+                        .help("Leave out everything that the roots don't reference"),
+                )
+                .arg(
+                    clap::Arg::with_name("roots")
+                        .long("--roots")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .requires("prune_unused")
+                        .help("Function(s), constructor(s) or method(s) that the app calls, such as `open_store` or `Store.get`, or `Store` for all of an object's members"),
+                )
+                .arg(
+                    clap::Arg::with_name("target")
+                        .long("--target")
+                        .takes_value(true)
+                        .possible_values(&["native", "wasm"])
+                        .help("What to export the component as: `extern \"C\"` functions for the foreign-language bindings (the default), or `wasm_bindgen` exports for JavaScript"),
+                )
+                .arg(clap::Arg::with_name("udl_file").required(true)),
+        )
+        .subcommand(
+            clap::SubCommand::with_name("lock")
+                .about("Record the public API of a component in a lockfile, for review")
+                .arg(
+                    clap::Arg::with_name("lockfile")
+                    .long("--lockfile")
+                    .takes_value(true)
+                    .help("Path to the API lockfile. Default is `api.lock` in the crate root.")
+                )
+                .arg(
+                    clap::Arg::with_name("verify")
+                        .long("--verify")
+                        .help("Check that the lockfile is up-to-date instead of writing it"),
+                )
+                .arg(clap::Arg::with_name("udl_file").required(true)),
+        )
+        .subcommand(
+            clap::SubCommand::with_name("check")
+                .about("Check that a UDL file describes a valid component")
+                .arg(
+                    clap::Arg::with_name("strict")
+                        .long("--strict")
+                        .help("Also fail if the public API uses bare strings or maps, unless allowed by the `[strict]` config"),
+                )
+                .arg(
+                    clap::Arg::with_name("config")
+                    .long("--config-path")
+                    .takes_value(true)
+                    .help("Path to the optional uniffi config file. If not provided, uniffi-bindgen will try to guess it from the UDL's file location.")
+                )
+                .arg(clap::Arg::with_name("udl_file").required(true)),
+        )
+        .subcommand(
+            clap::SubCommand::with_name("introspect")
+                .about("Print a JSON description of the component's objects, such as the threads they can be used from")
+                .arg(clap::Arg::with_name("udl_file").required(true)),
+        )
+        .subcommand(
+            clap::SubCommand::with_name("golden")
+                .about("Compare the generated scaffolding and bindings with the committed golden files")
+                .arg(
+                    clap::Arg::with_name("language")
+                        .required(true)
+                        .takes_value(true)
+                        .long("--language")
+                        .short("-l")
+                        .multiple(true)
+                        .number_of_values(1)
+                        .possible_values(POSSIBLE_LANGUAGES)
+                        .help("Foreign language(s) whose bindings to compare"),
+                )
+                .arg(
+                    clap::Arg::with_name("golden_dir")
+                    .long("--golden-dir")
+                    .takes_value(true)
+                    .help("Directory holding the golden files. Default is `golden/<udl name>` in the crate root.")
+                )
+                .arg(
+                    clap::Arg::with_name("update")
+                        .long("--update")
+                        .help("Rewrite the golden files with the generated code instead of comparing"),
+                )
+                .arg(
+                    clap::Arg::with_name("config")
+                    .long("--config-path")
+                    .takes_value(true)
+                    .help("Path to the optional uniffi config file. If not provided, uniffi-bindgen will try to guess it from the UDL's file location.")
+                )
+                .arg(clap::Arg::with_name("udl_file").required(true)),
+        )
+        .subcommand(
+            clap::SubCommand::with_name("init")
+                .about("Create the crate for a new component, with a starter UDL and smoke tests")
+                .arg(
+                    clap::Arg::with_name("out_dir")
+                    .long("--out-dir")
+                    .short("-o")
+                    .takes_value(true)
+                    .help("Directory to create the component's crate in. Default is the current directory.")
+                )
+                .arg(clap::Arg::with_name("name").required(true).help("Name of the component, such as `my_component`")),
+        )
+        .subcommand(
+            clap::SubCommand::with_name("stats")
+                .about("Report the size of the component's API and of the code generated for it")
+                .arg(
+                    clap::Arg::with_name("language")
+                        .takes_value(true)
+                        .long("--language")
+                        .short("-l")
+                        .multiple(true)
+                        .number_of_values(1)
+                        .possible_values(POSSIBLE_LANGUAGES)
+                        .help("Foreign language(s) to report on. Default is all of them."),
+                )
+                .arg(
+                    clap::Arg::with_name("roots")
+                        .long("--roots")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .help("Report on the part of the component that these reference, as `--prune-unused` would generate it, and list what's left out"),
+                )
+                .arg(
+                    clap::Arg::with_name("by_declaration")
+                        .long("--by-declaration")
+                        .help("Also break the generated code down by function and object, which is slower"),
+                )
+                .arg(
+                    clap::Arg::with_name("config")
+                    .long("--config-path")
+                    .takes_value(true)
+                    .help("Path to the optional uniffi config file. If not provided, uniffi-bindgen will try to guess it from the UDL's file location.")
+                )
                 .arg(clap::Arg::with_name("udl_file").required(true)),
         )
         .subcommand(
@@ -407,6 +988,15 @@ pub fn run_main() -> Result<()> {
             .arg(clap::Arg::with_name("cdylib_dir").required(true).help("Path to the directory containing the cdylib the scripts will be testing against."))
             .arg(clap::Arg::with_name("udl_file").required(true))
             .arg(clap::Arg::with_name("test_scripts").required(true).multiple(true).help("Foreign language(s) test scripts to run"))
+            .arg(
+                clap::Arg::with_name("requires")
+                .long("--requires")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .possible_values(bindings::OPTIONAL_FEATURES)
+                .help("Optional feature(s) the test scripts need; scripts for backends that lack them are skipped.")
+            )
             .arg(
                 clap::Arg::with_name("config")
                 .long("--config-path")
@@ -415,25 +1005,90 @@ pub fn run_main() -> Result<()> {
             )
         )
         .get_matches();
+    // The `--roots` to prune the component down to, if any.
+    fn roots<'a>(m: &'a clap::ArgMatches<'a>) -> Option<Vec<&'a str>> {
+        m.values_of("roots").map(|v| v.collect())
+    }
     match matches.subcommand() {
-        ("generate", Some(m)) => crate::generate_bindings(
+        ("generate", Some(m)) => {
+            if m.is_present("locked") {
+                crate::verify_api_lockfile(m.value_of_os("udl_file").unwrap(), None)?;
+            }
+            crate::generate_bindings(
+                m.value_of_os("udl_file").unwrap(), // Required
+                m.value_of_os("config"),
+                m.values_of("language").unwrap().collect(), // Required
+                m.value_of_os("out_dir"),
+                m.value_of("python_backend").map(str::parse).transpose()?,
+                roots(m).as_deref(),
+                !m.is_present("no_format"),
+            )?
+        }
+        ("scaffolding", Some(m)) => {
+            if m.is_present("locked") {
+                crate::verify_api_lockfile(m.value_of_os("udl_file").unwrap(), None)?;
+            }
+            crate::generate_component_scaffolding(
+                m.value_of_os("udl_file").unwrap(), // Required
+                m.value_of_os("config"),
+                m.value_of_os("out_dir"),
+                m.value_of("target")
+                    .map(str::parse)
+                    .transpose()?
+                    .unwrap_or_default(),
+                roots(m).as_deref(),
+                !m.is_present("no_format"),
+            )?
+        }
+        ("lock", Some(m)) => {
+            if m.is_present("verify") {
+                crate::verify_api_lockfile(
+                    m.value_of_os("udl_file").unwrap(), // Required
+                    m.value_of_os("lockfile"),
+                )?
+            } else {
+                crate::generate_api_lockfile(
+                    m.value_of_os("udl_file").unwrap(), // Required
+                    m.value_of_os("lockfile"),
+                )?
+            }
+        }
+        ("check", Some(m)) => crate::check_component(
+            m.value_of_os("udl_file").unwrap(), // Required
+            m.value_of_os("config"),
+            m.is_present("strict"),
+        )?,
+        ("introspect", Some(m)) => crate::print_introspection(
+            m.value_of_os("udl_file").unwrap(), // Required
+        )?,
+        ("golden", Some(m)) => crate::check_golden_files(
             m.value_of_os("udl_file").unwrap(), // Required
             m.value_of_os("config"),
             m.values_of("language").unwrap().collect(), // Required
+            m.value_of_os("golden_dir"),
+            m.is_present("update"),
+        )?,
+        ("init", Some(m)) => crate::init_component(
+            m.value_of("name").unwrap(), // Required
             m.value_of_os("out_dir"),
-            !m.is_present("no_format"),
         )?,
-        ("scaffolding", Some(m)) => crate::generate_component_scaffolding(
+        ("stats", Some(m)) => crate::report_stats(
             m.value_of_os("udl_file").unwrap(), // Required
             m.value_of_os("config"),
-            m.value_of_os("out_dir"),
-            !m.is_present("no_format"),
+            m.values_of("language")
+                .map(|v| v.collect())
+                .unwrap_or_else(|| POSSIBLE_LANGUAGES.to_vec()),
+            roots(m).as_deref(),
+            m.is_present("by_declaration"),
         )?,
         ("test", Some(m)) => {
             crate::run_tests(
                 m.value_of_os("cdylib_dir").unwrap(), // Required
                 &[&m.value_of_os("udl_file").unwrap().to_string_lossy()], // Required
                 m.values_of("test_scripts").unwrap().collect(), // Required
+                &m.values_of("requires")
+                    .map(|v| v.collect::<Vec<_>>())
+                    .unwrap_or_default(),
                 m.value_of_os("config"),
             )?
         }
@@ -0,0 +1,723 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # API lockfiles
+//!
+//! An `api.lock` file records the public API of a component as a sorted list of
+//! one-line declarations, plus a digest of those lines. It's intended to be checked
+//! in alongside the UDL file, so that any change to the FFI surface of a component
+//! shows up as a change to the lockfile, and can be reviewed as such.
+//!
+//! Each line is written in a UDL-like syntax and stands on its own, naming the item it
+//! belongs to. For example, after a short explanatory header, the lockfile for a small
+//! component might look like:
+//!
+//! ```text
+//! # digest: 9d1a4c6b01b7e5f2
+//! dictionary Point { f64 x, f64 y }
+//! function [Throws=ArithmeticError] u64 add(u64 a, u64 b)
+//! interface Counter constructor new()
+//! interface Counter method u64 get()
+//! namespace arithmetic
+//! ```
+//!
+//! Unlike [`ComponentInterface::checksum`], the contents of the lockfile don't depend on
+//! the version of uniffi or of Rust, only on the API described by the UDL. Reordering
+//! the declarations in the UDL file doesn't change the lockfile either.
+
+use std::collections::BTreeSet;
+
+use anyhow::{bail, Result};
+
+use crate::interface::*;
+
+/// The name of the lockfile, which by default lives in the root of the component's crate.
+pub const DEFAULT_LOCKFILE_NAME: &str = "api.lock";
+
+const HEADER: &str = "\
+# This file is generated by `uniffi-bindgen lock`; do not edit it by hand.
+# It lists the public API of the component, so that changes to that API
+# can be reviewed. Check it is up-to-date with `uniffi-bindgen lock --verify`.
+";
+
+const DIGEST_PREFIX: &str = "# digest: ";
+
+/// The public API of a component, as recorded in an `api.lock` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiLock {
+    lines: BTreeSet<String>,
+}
+
+impl ApiLock {
+    pub fn from_component_interface(ci: &ComponentInterface) -> Self {
+        let mut lines = BTreeSet::new();
+        lines.insert(format!("namespace {}", ci.namespace()));
+        for e in ci.iter_enum_definitions() {
+            lines.insert(format!(
+                "enum {} {{ {} }}",
+                e.name(),
+                variants_decl(e.variants())
+            ));
+        }
+        for e in ci.iter_error_definitions() {
+            lines.insert(format!(
+                "error {} {{ {} }}{}",
+                e.name(),
+                variants_decl(e.variants()),
+                if e.has_source_chain() {
+                    " [SourceChain]"
+                } else {
+                    ""
+                }
+            ));
+        }
+        for rec in ci.iter_record_definitions() {
+            lines.insert(format!(
+                "dictionary {} {{ {} }}",
+                rec.name(),
+                fields_decl(rec.fields())
+            ));
+        }
+        for obj in ci.iter_object_definitions() {
+            let mut attributes: Vec<String> = [
+                (obj.is_opaque_token(), "[OpaqueToken]"),
+                (obj.is_cloneable(), "[Clone]"),
+                (obj.has_weak_refs(), "[WeakRefs]"),
+                (obj.releases_callbacks(), "[ReleaseCallbacks]"),
+                (obj.is_closeable(), "[Closeable]"),
+            ]
+            .iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, attribute)| attribute.to_string())
+            .collect();
+            if let Some(ext) = obj.foreign_extension() {
+                attributes.push(format!("[ForeignExtension={}]", ext));
+            }
+            if let Some(snapshot) = obj.observable_snapshot() {
+                attributes.push(format!("[Observable={}]", snapshot));
+            }
+            if let Some(events) = obj.events_type() {
+                attributes.push(format!("[Events={}]", events));
+            }
+            if obj.is_service() {
+                attributes.push("[Service]".into());
+            }
+            if obj.is_trait() {
+                attributes.push("[Trait]".into());
+            }
+            match obj.threading() {
+                Threading::Threadsafe => (),
+                Threading::MainThreadOnly => attributes.push("[MainThread]".into()),
+                Threading::Blocking => attributes.push("[Blocking]".into()),
+            }
+            lines.insert(format!(
+                "interface {}{}",
+                obj.name(),
+                attributes
+                    .iter()
+                    .map(|attribute| format!(" {}", attribute))
+                    .collect::<String>()
+            ));
+        }
+        for callable in exported_callables(ci) {
+            lines.insert(callable.declaration);
+        }
+        for cbi in ci.iter_callback_interface_definitions() {
+            lines.insert(format!(
+                "callback interface {}{}",
+                cbi.name(),
+                if cbi.is_direct() { " [Direct]" } else { "" }
+            ));
+            for meth in cbi.methods() {
+                lines.insert(format!(
+                    "callback interface {} method {}",
+                    cbi.name(),
+                    method_decl(meth)
+                ));
+            }
+        }
+        for (name, crate_name) in ci.iter_external_types() {
+            lines.insert(format!("typedef extern {} from {}", name, crate_name));
+        }
+        for (name, prim) in ci.iter_wrapped_types() {
+            lines.insert(format!("typedef wrapped {} = {}", name, type_decl(&prim)));
+        }
+        Self { lines }
+    }
+
+    /// Parse the contents of an existing lockfile.
+    ///
+    /// This fails if the lockfile has no digest, or if its digest doesn't match its
+    /// contents, which most likely means that it was edited by hand.
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut digest = None;
+        let mut lines = BTreeSet::new();
+        for line in contents.lines() {
+            if let Some(value) = line.strip_prefix(DIGEST_PREFIX) {
+                digest = Some(value.trim().to_string());
+            } else if !line.starts_with('#') && !line.trim().is_empty() {
+                lines.insert(line.to_string());
+            }
+        }
+        let lock = Self { lines };
+        match digest {
+            None => bail!("The API lockfile is missing its digest"),
+            Some(digest) if digest != lock.digest() => bail!(
+                "The API lockfile digest does not match its contents; it may have been edited by hand. \
+                 Run `uniffi-bindgen lock` to regenerate it."
+            ),
+            Some(_) => Ok(lock),
+        }
+    }
+
+    /// A digest of the API, as a hex string.
+    ///
+    /// This is a 64-bit FNV-1a hash, which is trivial to compute and is stable across
+    /// versions of Rust. Like `ComponentInterface::checksum`, it's designed to catch
+    /// accidents not attacks, so there's no need for it to be cryptographically secure.
+    pub fn digest(&self) -> String {
+        fnv1a_digest(self.lines.iter())
+    }
+
+    /// Render the contents of the lockfile.
+    pub fn render(&self) -> String {
+        let mut contents = String::from(HEADER);
+        contents.push_str(DIGEST_PREFIX);
+        contents.push_str(&self.digest());
+        contents.push('\n');
+        for line in self.lines.iter() {
+            contents.push_str(line);
+            contents.push('\n');
+        }
+        contents
+    }
+
+    /// Check that a previously-recorded lockfile matches this API.
+    ///
+    /// On mismatch, the error lists the declarations that were added and removed.
+    pub fn verify(&self, recorded: &ApiLock) -> Result<()> {
+        if self == recorded {
+            return Ok(());
+        }
+        let mut changes = Vec::new();
+        for line in recorded.lines.difference(&self.lines) {
+            changes.push(format!("  - {}", line));
+        }
+        for line in self.lines.difference(&recorded.lines) {
+            changes.push(format!("  + {}", line));
+        }
+        bail!(
+            "The component API has changed without the API lockfile being updated. \
+             Review the following changes and run `uniffi-bindgen lock` to accept them:\n{}",
+            changes.join("\n")
+        )
+    }
+}
+
+/// A function, constructor or method that foreign-language code can call.
+///
+/// These are listed in the generated bindings so that routing layers can look calls up
+/// by name. Each has a signature hash, derived from its line in the lockfile, that changes
+/// whenever the signature does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportedCallable {
+    kind: &'static str,
+    object: Option<String>,
+    name: String,
+    declaration: String,
+}
+
+impl ExportedCallable {
+    /// One of `"function"`, `"constructor"` or `"method"`.
+    pub fn kind(&self) -> &str {
+        self.kind
+    }
+
+    /// The name of the interface this belongs to, if it isn't a top-level function.
+    pub fn object(&self) -> Option<&str> {
+        self.object.as_deref()
+    }
+
+    /// The name of the function, constructor or method, as written in the UDL. Functions in
+    /// nested namespaces are named by their dotted path, e.g. `sync.engine.start`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name qualified by its interface, e.g. `Counter.get`, or just the name for
+    /// top-level functions.
+    pub fn qualified_name(&self) -> String {
+        match &self.object {
+            Some(object) => format!("{}.{}", object, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// The line for this in the lockfile, such as `interface Counter method u32 get()`.
+    pub fn declaration(&self) -> &str {
+        &self.declaration
+    }
+
+    /// A hash of the signature, as a hex string.
+    pub fn signature_hash(&self) -> String {
+        fnv1a_digest(std::iter::once(&self.declaration))
+    }
+}
+
+/// List everything that foreign-language code can call, in declaration order.
+pub fn exported_callables(ci: &ComponentInterface) -> Vec<ExportedCallable> {
+    let mut callables = Vec::new();
+    for func in ci.iter_function_definitions() {
+        callables.push(ExportedCallable {
+            kind: "function",
+            object: None,
+            name: func.qualified_name(),
+            declaration: format!(
+                "function {}{}{} {}({})",
+                attributes_decl(&[
+                    // A `[Batchable]` function can also be called through the batch FFI function,
+                    (func.is_batchable(), "[Batchable]"),
+                    // and an `[Invokable]` one by name, through the invoke FFI function.
+                    (func.is_invokable(), "[Invokable]"),
+                    (func.is_scoped(), "[Scoped]"),
+                    // An `[Async]` function returns through its completion handle instead,
+                    (func.is_async(), "[Async]"),
+                    // and the Rust implementation of a `[Cancellable]` one takes a token as well.
+                    (func.is_cancellable(), "[Cancellable]"),
+                ]),
+                throws_decl(func.throws()),
+                return_decl(func.return_type()),
+                func.qualified_name(),
+                arguments_decl(func.arguments())
+            ),
+        });
+    }
+    for obj in ci.iter_object_definitions() {
+        for cons in obj.constructors() {
+            callables.push(ExportedCallable {
+                kind: "constructor",
+                object: Some(obj.name().to_string()),
+                name: cons.name().to_string(),
+                declaration: format!(
+                    "interface {} {}constructor {}({})",
+                    obj.name(),
+                    throws_decl(cons.throws()),
+                    cons.name(),
+                    arguments_decl(cons.arguments())
+                ),
+            });
+        }
+        for meth in obj.methods() {
+            callables.push(ExportedCallable {
+                kind: "method",
+                object: Some(obj.name().to_string()),
+                name: meth.name().to_string(),
+                declaration: format!("interface {} method {}", obj.name(), method_decl(meth)),
+            });
+        }
+    }
+    callables
+}
+
+// The hash described in `ApiLock::digest`, over some lines.
+fn fnv1a_digest<'a>(lines: impl Iterator<Item = &'a String>) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for line in lines {
+        for byte in line.bytes().chain(std::iter::once(b'\n')) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// How a type is written in the UDL.
+pub(crate) fn type_decl(type_: &Type) -> String {
+    match type_ {
+        Type::UInt8 => "u8".into(),
+        Type::Int8 => "i8".into(),
+        Type::UInt16 => "u16".into(),
+        Type::Int16 => "i16".into(),
+        Type::UInt32 => "u32".into(),
+        Type::Int32 => "i32".into(),
+        Type::UInt64 => "u64".into(),
+        Type::Int64 => "i64".into(),
+        Type::Float32 => "f32".into(),
+        Type::Float64 => "f64".into(),
+        Type::BigInt => "bigint".into(),
+        Type::Bytes => "bytes".into(),
+        Type::Boolean => "boolean".into(),
+        Type::Char => "char".into(),
+        Type::String => "string".into(),
+        Type::Timestamp => "timestamp".into(),
+        Type::Duration => "duration".into(),
+        Type::Object(name)
+        | Type::Record(name)
+        | Type::Enum(name)
+        | Type::Error(name)
+        | Type::CallbackInterface(name)
+        | Type::External { name, .. }
+        | Type::ExternalInterface { name, .. }
+        | Type::Wrapped { name, .. } => name.clone(),
+        Type::Optional(inner) => format!("{}?", type_decl(inner)),
+        Type::Sequence(inner) => format!("sequence<{}>", type_decl(inner)),
+        Type::Map(key, value) => match key.as_ref() {
+            Type::String => format!("record<DOMString, {}>", type_decl(value)),
+            _ => format!("record<{}, {}>", type_decl(key), type_decl(value)),
+        },
+        Type::OrderedMap(inner) => format!("[OrderedMap] record<DOMString, {}>", type_decl(inner)),
+    }
+}
+
+fn literal_decl(literal: &Literal) -> String {
+    match literal {
+        Literal::Boolean(v) => v.to_string(),
+        Literal::String(s) | Literal::Enum(s, _) => format!("{:?}", s),
+        Literal::UInt(v, Radix::Decimal, _) => v.to_string(),
+        Literal::UInt(v, Radix::Hexadecimal, _) => format!("0x{:x}", v),
+        Literal::UInt(v, Radix::Octal, _) => format!("0{:o}", v),
+        // Negative integers are always decimal, see `convert_default_value`.
+        Literal::Int(v, Radix::Hexadecimal, _) => format!("0x{:x}", v),
+        Literal::Int(v, Radix::Octal, _) => format!("0{:o}", v),
+        Literal::Int(v, Radix::Decimal, _) => v.to_string(),
+        Literal::Float(s, _) => s.clone(),
+        Literal::EmptySequence => "[]".into(),
+        Literal::EmptyMap => "{}".into(),
+        Literal::Null => "null".into(),
+    }
+}
+
+fn default_decl(default: Option<Literal>) -> String {
+    match default {
+        Some(literal) => format!(" = {}", literal_decl(&literal)),
+        None => String::new(),
+    }
+}
+
+fn throws_decl(throws: Option<&str>) -> String {
+    match throws {
+        Some(err) => format!("[Throws={}] ", err),
+        None => String::new(),
+    }
+}
+
+fn return_decl(return_type: Option<&Type>) -> String {
+    match return_type {
+        Some(type_) => type_decl(type_),
+        None => "void".into(),
+    }
+}
+
+fn arguments_decl(arguments: Vec<&Argument>) -> String {
+    arguments
+        .iter()
+        .map(|arg| {
+            format!(
+                "{}{} {}{}",
+                if arg.by_ref() { "[ByRef] " } else { "" },
+                type_decl(&arg.type_()),
+                arg.name(),
+                default_decl(arg.default_value())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn fields_decl(fields: Vec<&Field>) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            let attr = if field.empty_as_null() {
+                "[EmptyAsNull] "
+            } else if field.null_as_empty() {
+                "[NullAsEmpty] "
+            } else {
+                ""
+            };
+            format!(
+                "{}{} {}{}",
+                attr,
+                type_decl(&field.type_()),
+                field.name(),
+                default_decl(field.default_value())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn variants_decl(variants: Vec<&Variant>) -> String {
+    variants
+        .iter()
+        .map(|v| {
+            if v.has_fields() {
+                format!("{}({})", v.name(), fields_decl(v.fields()))
+            } else {
+                v.name().to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// The attributes that are set, each followed by a space.
+fn attributes_decl(attributes: &[(bool, &str)]) -> String {
+    attributes
+        .iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, attribute)| format!("{} ", attribute))
+        .collect()
+}
+
+fn method_decl(meth: &Method) -> String {
+    format!(
+        "{}{}{} {}({})",
+        attributes_decl(&[
+            (meth.takes_self_by_arc(), "[Self=ByArc]"),
+            (meth.takes_self_by_mut(), "[Self=ByMut]"),
+            (meth.is_optional(), "[Optional]"),
+            (meth.is_borrowed(), "[Borrowed]"),
+            (meth.is_paginated(), "[Paginated]"),
+            (meth.is_cached(), "[Cached]"),
+            // The bytes that a `[NoCopy]` method returns cross the FFI differently, so it's part
+            // of the ABI.
+            (meth.is_no_copy(), "[NoCopy]"),
+            (meth.is_scoped(), "[Scoped]"),
+            (meth.is_also_async(), "[AlsoAsync]"),
+            // So does what an `[Async]` method returns, through its completion handle.
+            (meth.is_async(), "[Async]"),
+            (meth.is_cancellable(), "[Cancellable]"),
+        ]),
+        throws_decl(meth.throws()),
+        // A `[Borrowed]` method is declared as returning the data that its view gives access to.
+        return_decl(meth.borrowed_type().or_else(|| meth.return_type())),
+        meth.name(),
+        arguments_decl(meth.arguments())
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const UDL: &str = r#"
+        namespace test {
+            [Throws=Oops]
+            u64 add(u64 a, optional u64 b = 0x10);
+        };
+        [Error]
+        enum Oops { "Overflow" };
+        dictionary Point {
+            f64 x;
+            sequence<string>? tags = null;
+        };
+        [Clone]
+        interface Counter {
+            constructor();
+            u32 get();
+            [Self=ByMut]
+            void increment();
+        };
+    "#;
+
+    #[test]
+    fn test_lockfile_contents() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let lock = ApiLock::from_component_interface(&ci);
+        let contents = lock.render();
+        assert!(contents.starts_with(HEADER));
+        let lines: Vec<_> = contents
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "dictionary Point { f64 x, sequence<string>? tags = null }",
+                "error Oops { Overflow }",
+                "function [Throws=Oops] u64 add(u64 a, u64 b = 0x10)",
+                "interface Counter [Clone]",
+                "interface Counter constructor new()",
+                "interface Counter method [Self=ByMut] void increment()",
+                "interface Counter method u32 get()",
+                "namespace test",
+            ]
+        );
+        assert_eq!(ApiLock::parse(&contents).unwrap(), lock);
+    }
+
+    #[test]
+    fn test_lockfile_ignores_declaration_order() {
+        const UDL2: &str = r#"
+            namespace test {
+                [Throws=Oops]
+                u64 add(u64 a, optional u64 b = 0x10);
+            };
+            [Clone]
+            interface Counter {
+                [Self=ByMut]
+                void increment();
+                u32 get();
+                constructor();
+            };
+            dictionary Point {
+                f64 x;
+                sequence<string>? tags = null;
+            };
+            [Error]
+            enum Oops { "Overflow" };
+        "#;
+        let lock =
+            ApiLock::from_component_interface(&ComponentInterface::from_webidl(UDL).unwrap());
+        let lock2 =
+            ApiLock::from_component_interface(&ComponentInterface::from_webidl(UDL2).unwrap());
+        assert_eq!(lock.digest(), lock2.digest());
+        assert!(lock.verify(&lock2).is_ok());
+    }
+
+    #[test]
+    fn test_exported_callables() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let callables = exported_callables(&ci);
+        let names: Vec<_> = callables
+            .iter()
+            .map(|c| (c.kind(), c.qualified_name()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("function", "add".to_string()),
+                ("constructor", "Counter.new".to_string()),
+                ("method", "Counter.get".to_string()),
+                ("method", "Counter.increment".to_string()),
+            ]
+        );
+        assert_eq!(callables[2].object(), Some("Counter"));
+        assert_eq!(callables[2].signature_hash().len(), 16);
+        assert_ne!(callables[2].signature_hash(), callables[3].signature_hash());
+
+        // Changing a signature changes its hash, but not the hashes of anything else.
+        let ci2 = ComponentInterface::from_webidl(&UDL.replace("u32 get()", "u64 get()")).unwrap();
+        let callables2 = exported_callables(&ci2);
+        assert_ne!(
+            callables[2].signature_hash(),
+            callables2[2].signature_hash()
+        );
+        assert_eq!(
+            callables[3].signature_hash(),
+            callables2[3].signature_hash()
+        );
+    }
+
+    #[test]
+    fn test_callable_attributes() {
+        const UDL: &str = r#"
+            namespace test {
+                [Scoped]
+                void prefetch(string url);
+                [Batchable, Throws=Oops]
+                u32 get_limit(string key);
+                [Invokable]
+                string get_setting(string key);
+                [Async, Cancellable]
+                string fetch(string url);
+            };
+            interface Ticker {
+                [Scoped]
+                void start();
+                [Async, Cancellable]
+                void wait();
+            };
+            [Error]
+            enum Oops { "Missing" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let declarations: Vec<_> = exported_callables(&ci)
+            .iter()
+            .map(|c| c.declaration().to_string())
+            .collect();
+        assert_eq!(
+            declarations,
+            vec![
+                "function [Scoped] void prefetch(Scope scope, string url)",
+                "function [Batchable] [Throws=Oops] u32 get_limit(string key)",
+                "function [Invokable] string get_setting(string key)",
+                "function [Async] [Cancellable] string fetch(string url)",
+                "interface Ticker method [Scoped] void start(Scope scope)",
+                "interface Ticker method [Async] [Cancellable] void wait()",
+                "interface Scope constructor new()",
+                "interface Scope method void cancel()",
+                "interface Scope method boolean is_cancelled()",
+                "interface Scope method void join()",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interface_attributes() {
+        const UDL: &str = r#"
+            namespace test {};
+            dictionary CounterState { u32 count; };
+            [Observable=CounterState]
+            interface Counter {
+                constructor();
+                [Self=ByArc, Cached]
+                string name();
+                [Paginated]
+                sequence<u32> history();
+            };
+            dictionary Tick { u32 count; };
+            [Events=Tick]
+            interface Metronome { constructor(); };
+            [Service]
+            interface Heartbeat { constructor(); };
+            [Trait]
+            interface Shape { string name(); };
+        "#;
+        let lock =
+            ApiLock::from_component_interface(&ComponentInterface::from_webidl(UDL).unwrap());
+        for line in &[
+            "interface Counter [Observable=CounterState]",
+            "interface Counter method [Self=ByArc] [Cached] string name()",
+            "interface Counter method [Paginated] sequence<u32> history()",
+            "interface Metronome [Events=Tick]",
+            "interface Heartbeat [Service]",
+            "interface Shape [Trait]",
+        ] {
+            assert!(
+                lock.lines.contains(*line),
+                "{:?} isn't in the lockfile",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn test_lockfile_verification() {
+        const UDL2: &str = r#"
+            namespace test {
+                [Throws=Oops]
+                u64 add(u64 a, u64 b);
+            };
+            [Error]
+            enum Oops { "Overflow" };
+        "#;
+        let lock =
+            ApiLock::from_component_interface(&ComponentInterface::from_webidl(UDL).unwrap());
+        let lock2 =
+            ApiLock::from_component_interface(&ComponentInterface::from_webidl(UDL2).unwrap());
+        let err = lock2.verify(&lock).unwrap_err().to_string();
+        assert!(err.contains("  - function [Throws=Oops] u64 add(u64 a, u64 b = 0x10)"));
+        assert!(err.contains("  + function [Throws=Oops] u64 add(u64 a, u64 b)"));
+        assert!(err.contains("  - interface Counter method u32 get()"));
+        assert!(!err.contains("namespace test"));
+
+        let tampered = lock.render().replace("u32 get()", "u64 get()");
+        let err = ApiLock::parse(&tampered).unwrap_err();
+        assert!(err.to_string().contains("may have been edited by hand"));
+        let err = ApiLock::parse("namespace test\n").unwrap_err();
+        assert_eq!(err.to_string(), "The API lockfile is missing its digest");
+    }
+}
@@ -0,0 +1,221 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Pruning what the entry points of an app don't use
+//!
+//! An app that only calls a few of a component's functions and methods still pays, in binary
+//! size, for the scaffolding and converters of everything else the component declares. Given
+//! those entry points as roots, `uniffi-bindgen` can leave out everything that they don't
+//! reference, either directly or through the types that they use:
+//!
+//! ```text
+//! uniffi-bindgen scaffolding --prune-unused --roots open_store --roots Store src/store.udl
+//! uniffi-bindgen generate --language kotlin --prune-unused --roots open_store --roots Store src/store.udl
+//! ```
+//!
+//! The roots are named the same way as the entries of a `[bindings.<language>.filter]`
+//! table: a function, such as `open_store`, a constructor or method, such as `Store.get`,
+//! or everything under a prefix, such as `Store` for all of an object's members. A root that
+//! doesn't name anything in the component is an error.
+//!
+//! Unlike a filter, pruning applies to the scaffolding too, so the FFI functions of whatever
+//! was pruned aren't exported from the library at all. The bindings must then be generated
+//! with the same roots, or they'll fail to find those functions when they're called. The Rust
+//! code behind them is still compiled, and the compiler may warn that it's never used.
+//!
+//! `uniffi-bindgen stats --roots ...` lists what would be pruned before committing to it.
+
+use std::fmt;
+
+use anyhow::Result;
+
+use crate::backend::filter::ApiFilter;
+use crate::interface::ComponentInterface;
+use crate::lockfile::exported_callables;
+
+/// The part of the component that the `roots` reference.
+pub fn prune(ci: &ComponentInterface, roots: &[&str]) -> Result<ComponentInterface> {
+    let filter = ApiFilter::including(roots.iter().map(|root| root.to_string()).collect());
+    Ok(filter.apply(ci)?.into_owned())
+}
+
+/// The names of the top-level declarations that the code generated for a component can be
+/// broken down by: each function, and each object, along with its constructors and methods.
+/// These are also valid roots.
+pub fn declarations(ci: &ComponentInterface) -> Vec<String> {
+    ci.iter_function_definitions()
+        .iter()
+        .map(|func| func.qualified_name())
+        .chain(
+            ci.iter_object_definitions()
+                .iter()
+                .map(|obj| obj.name().to_string()),
+        )
+        .collect()
+}
+
+/// The component without the given declaration, or anything that only it used.
+pub fn without(ci: &ComponentInterface, declaration: &str) -> Result<ComponentInterface> {
+    let filter = ApiFilter::excluding(vec![declaration.to_string()]);
+    Ok(filter.apply(ci)?.into_owned())
+}
+
+/// What pruning the component left out, by the kind of declaration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unreferenced {
+    pub kinds: Vec<(&'static str, Vec<String>)>,
+}
+
+impl Unreferenced {
+    /// What's in the `full` component, but not in the `pruned` one.
+    pub fn between(full: &ComponentInterface, pruned: &ComponentInterface) -> Self {
+        fn missing(full: Vec<String>, pruned: Vec<String>) -> Vec<String> {
+            full.into_iter()
+                .filter(|name| !pruned.contains(name))
+                .collect()
+        }
+        let callables = |ci: &ComponentInterface, object: bool| -> Vec<String> {
+            exported_callables(ci)
+                .iter()
+                .filter(|c| c.object().is_some() == object)
+                .map(|c| c.qualified_name())
+                .collect()
+        };
+        let objects = |ci: &ComponentInterface| -> Vec<String> {
+            ci.iter_object_definitions()
+                .iter()
+                .map(|obj| obj.name().to_string())
+                .collect()
+        };
+        let records = |ci: &ComponentInterface| -> Vec<String> {
+            ci.iter_record_definitions()
+                .iter()
+                .map(|rec| rec.name().to_string())
+                .collect()
+        };
+        let enums = |ci: &ComponentInterface| -> Vec<String> {
+            ci.iter_enum_definitions()
+                .iter()
+                .map(|e| e.name().to_string())
+                .collect()
+        };
+        let errors = |ci: &ComponentInterface| -> Vec<String> {
+            ci.iter_error_definitions()
+                .iter()
+                .map(|e| e.name().to_string())
+                .collect()
+        };
+        let callback_interfaces = |ci: &ComponentInterface| -> Vec<String> {
+            ci.iter_callback_interface_definitions()
+                .iter()
+                .map(|cbi| cbi.name().to_string())
+                .collect()
+        };
+        Unreferenced {
+            kinds: vec![
+                (
+                    "functions",
+                    missing(callables(full, false), callables(pruned, false)),
+                ),
+                (
+                    "constructors and methods",
+                    missing(callables(full, true), callables(pruned, true)),
+                ),
+                ("objects", missing(objects(full), objects(pruned))),
+                ("records", missing(records(full), records(pruned))),
+                ("enums", missing(enums(full), enums(pruned))),
+                ("errors", missing(errors(full), errors(pruned))),
+                (
+                    "callback interfaces",
+                    missing(callback_interfaces(full), callback_interfaces(pruned)),
+                ),
+            ],
+        }
+    }
+
+    /// Whether pruning left nothing out.
+    pub fn is_empty(&self) -> bool {
+        self.kinds.iter().all(|(_, names)| names.is_empty())
+    }
+}
+
+impl fmt::Display for Unreferenced {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unreferenced from the roots, and pruned:")?;
+        if self.is_empty() {
+            return write!(f, "\n  nothing");
+        }
+        for (kind, names) in self.kinds.iter() {
+            if !names.is_empty() {
+                write!(f, "\n  {}: {}", kind, names.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const UDL: &str = r#"
+        namespace store {
+            Store open_store(string path);
+            [Throws=StoreError]
+            void compact(Store store, CompactOptions options);
+        };
+        dictionary CompactOptions {
+            boolean aggressive;
+        };
+        [Error]
+        enum StoreError { "Busy" };
+        interface Store {
+            constructor();
+            string? get(string key);
+            void put(string key, string value);
+        };
+        interface Cursor {
+            string? next();
+        };
+    "#;
+
+    #[test]
+    fn test_prune() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let pruned = prune(&ci, &["open_store", "Store.get"]).unwrap();
+        assert_eq!(pruned.checksum(), ci.checksum());
+        assert_eq!(
+            Unreferenced::between(&ci, &pruned).to_string(),
+            "\
+Unreferenced from the roots, and pruned:
+  functions: compact
+  constructors and methods: Store.new, Store.put, Cursor.next
+  objects: Cursor
+  records: CompactOptions
+  errors: StoreError"
+        );
+
+        let everything = prune(&ci, &["open_store", "compact", "Store", "Cursor"]).unwrap();
+        assert_eq!(
+            Unreferenced::between(&ci, &everything).to_string(),
+            "Unreferenced from the roots, and pruned:\n  nothing"
+        );
+
+        let err = prune(&ci, &["Store.delete"]).unwrap_err();
+        assert!(err.to_string().contains("\"Store.delete\""));
+    }
+
+    #[test]
+    fn test_declarations() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert_eq!(
+            declarations(&ci),
+            vec!["open_store", "compact", "Store", "Cursor"]
+        );
+        let rest = without(&ci, "compact").unwrap();
+        assert!(rest.get_function_definition("compact").is_none());
+        assert!(rest.get_record_definition("CompactOptions").is_none());
+        assert!(rest.get_object_definition("Store").is_some());
+    }
+}
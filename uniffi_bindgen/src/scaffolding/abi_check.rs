@@ -0,0 +1,493 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Cross-checking the C ABI of the generated scaffolding
+//!
+//! The foreign-language bindings never see the Rust scaffolding. They declare the `extern "C"`
+//! functions they expect to call based on the [`FFIFunction`]s in the [`ComponentInterface`],
+//! and the scaffolding templates independently define those functions. If the two ever disagree,
+//! say because a template grew an extra argument, nothing complains at build time: the mismatch
+//! only shows up as a crash (or worse, silent memory corruption) at runtime.
+//!
+//! This module does roughly what `cbindgen` would do, but only for the small subset of Rust
+//! that the scaffolding uses for its FFI: it finds every `extern "C" fn` in the generated
+//! source, parses the types of its arguments and return value back into [`FFIType`]s, and
+//! compares the resulting [`Signature`] against the one that the bindings believe it has.
+//! Argument names are deliberately ignored, since they don't affect the ABI.
+
+use std::collections::{BTreeMap, HashSet};
+
+use anyhow::{bail, Result};
+
+use crate::interface::{ComponentInterface, FFIFunction, FFIType};
+
+/// The type of an argument of an `extern "C"` function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbiType {
+    Ffi(FFIType),
+    /// The `&mut RustCallStatus` out-parameter that every FFI function takes last, for
+    /// reporting errors.
+    CallStatus,
+}
+
+/// The signature of an `extern "C"` function, as far as its ABI goes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub arguments: Vec<AbiType>,
+    pub return_type: Option<FFIType>,
+}
+
+impl Signature {
+    fn of_ffi_function(func: &FFIFunction) -> Self {
+        let mut arguments: Vec<_> = func
+            .arguments()
+            .iter()
+            .map(|arg| AbiType::Ffi(arg.type_()))
+            .collect();
+        arguments.push(AbiType::CallStatus);
+        Signature {
+            arguments,
+            return_type: func.return_type().cloned(),
+        }
+    }
+
+    /// How a C header would declare a function called `name` with this signature, for
+    /// reporting any differences.
+    pub fn c_declaration(&self, name: &str) -> String {
+        let arguments: Vec<_> = self
+            .arguments
+            .iter()
+            .map(|arg| match arg {
+                AbiType::Ffi(type_) => c_type(type_),
+                AbiType::CallStatus => "RustCallStatus *".to_string(),
+            })
+            .collect();
+        let return_type = match &self.return_type {
+            Some(type_) => c_type(type_),
+            None => "void".to_string(),
+        };
+        format!("{} {}({})", return_type, name, arguments.join(", "))
+    }
+}
+
+/// Check that the `extern "C"` functions defined in the given scaffolding source match
+/// the FFI functions declared by the component interface.
+pub fn check_scaffolding_abi(ci: &ComponentInterface, scaffolding: &str) -> Result<()> {
+    let expected = expected_signatures(ci);
+    let actual = scaffolding_signatures(ci, scaffolding)?;
+    let mut problems = Vec::new();
+    for (name, signature) in expected.iter() {
+        match actual.get(name) {
+            None => problems.push(format!(
+                "  missing from the scaffolding: {}",
+                signature.c_declaration(name)
+            )),
+            Some(found) if found != signature => problems.push(format!(
+                "  mismatched signature for `{}`:\n    bindings expect:    {}\n    scaffolding defines: {}",
+                name,
+                signature.c_declaration(name),
+                found.c_declaration(name)
+            )),
+            Some(_) => {}
+        }
+    }
+    for (name, signature) in actual.iter() {
+        if !expected.contains_key(name) {
+            problems.push(format!(
+                "  not declared by the bindings: {}",
+                signature.c_declaration(name)
+            ));
+        }
+    }
+    if !problems.is_empty() {
+        bail!(
+            "The generated scaffolding does not match the FFI declared by the component interface:\n{}",
+            problems.join("\n")
+        );
+    }
+    Ok(())
+}
+
+/// The signatures of every FFI function that the bindings expect to call, keyed by name.
+pub fn expected_signatures(ci: &ComponentInterface) -> BTreeMap<String, Signature> {
+    ci.iter_ffi_function_definitions()
+        .iter()
+        .map(|func| (func.name().to_string(), Signature::of_ffi_function(func)))
+        .collect()
+}
+
+/// The signatures of every `extern "C"` function defined in the scaffolding source for the
+/// component, keyed by name.
+pub fn scaffolding_signatures(
+    ci: &ComponentInterface,
+    source: &str,
+) -> Result<BTreeMap<String, Signature>> {
+    // The scaffolding declares a function pointer type for each of the callbacks of a
+    // `[Direct]` callback interface, named after the callback.
+    let callback_types: HashSet<String> = ci
+        .iter_callback_interface_definitions()
+        .iter()
+        .flat_map(|cbi| {
+            cbi.ffi_direct_callbacks()
+                .into_iter()
+                .map(|callback| callback.name().to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let parse_type = |type_: &str| -> Result<AbiType> {
+        let type_ = type_.split_whitespace().collect::<Vec<_>>().join(" ");
+        Ok(AbiType::Ffi(match type_.as_str() {
+            "i8" => FFIType::Int8,
+            "u8" => FFIType::UInt8,
+            "i16" => FFIType::Int16,
+            "u16" => FFIType::UInt16,
+            "i32" => FFIType::Int32,
+            "u32" => FFIType::UInt32,
+            "i64" => FFIType::Int64,
+            "u64" => FFIType::UInt64,
+            "f32" => FFIType::Float32,
+            "f64" => FFIType::Float64,
+            "*const std::os::raw::c_void" => FFIType::RustArcPtr,
+            "uniffi::RustBuffer" => FFIType::RustBuffer,
+            "uniffi::ForeignBytes" => FFIType::ForeignBytes,
+            "uniffi::ForeignCallback" => FFIType::ForeignCallback,
+            "uniffi::AsyncCompletion" => FFIType::AsyncCompletion,
+            "&mut uniffi::RustCallStatus" => return Ok(AbiType::CallStatus),
+            name if callback_types.contains(name) => {
+                FFIType::ForeignMethodCallback(name.to_string())
+            }
+            _ => bail!("Unsupported type in extern function signature: `{}`", type_),
+        }))
+    };
+
+    let source = strip_comments(source);
+    let mut signatures = BTreeMap::new();
+    let mut rest = source.as_str();
+    while let Some(pos) = rest.find("extern \"C\" fn") {
+        rest = &rest[pos + "extern \"C\" fn".len()..];
+        // Without a name, this is a function pointer type rather than a function.
+        let name_start = rest.trim_start();
+        if name_start.len() == rest.len() || !name_start.starts_with(is_ident_char) {
+            continue;
+        }
+        rest = name_start;
+        let name_len = rest
+            .find(|c: char| !is_ident_char(c))
+            .unwrap_or_else(|| rest.len());
+        let name = rest[..name_len].to_string();
+        rest = rest[name_len..].trim_start();
+        let (arguments, after) = match split_parenthesized(rest) {
+            Some(split) => split,
+            None => bail!("Malformed argument list for extern function `{}`", name),
+        };
+        let mut argument_types = Vec::new();
+        for argument in split_top_level(arguments) {
+            let argument = argument.trim();
+            if argument.is_empty() {
+                continue;
+            }
+            let type_ = match argument.find(':') {
+                Some(colon) if is_pattern(&argument[..colon]) => &argument[colon + 1..],
+                _ => bail!(
+                    "Malformed argument `{}` for extern function `{}`",
+                    argument,
+                    name
+                ),
+            };
+            argument_types.push(parse_type(type_)?);
+        }
+        rest = after.trim_start();
+        let body = match find_top_level(rest, '{') {
+            Some(body) => body,
+            None => bail!("Missing body for extern function `{}`", name),
+        };
+        let tail = rest[..body].trim();
+        let return_type = if tail.is_empty() {
+            None
+        } else if let Some(type_) = tail.strip_prefix("->") {
+            match parse_type(type_)? {
+                AbiType::Ffi(type_) => Some(type_),
+                AbiType::CallStatus => bail!(
+                    "Extern function `{}` returns a `RustCallStatus` reference",
+                    name
+                ),
+            }
+        } else {
+            bail!("Unexpected `{}` after extern function `{}`", tail, name)
+        };
+        rest = &rest[body..];
+        let signature = Signature {
+            arguments: argument_types,
+            return_type,
+        };
+        if signatures.insert(name.clone(), signature).is_some() {
+            bail!("Extern function `{}` is defined more than once", name);
+        }
+    }
+    Ok(signatures)
+}
+
+fn c_type(type_: &FFIType) -> String {
+    match type_ {
+        FFIType::Int8 => "int8_t",
+        FFIType::UInt8 => "uint8_t",
+        FFIType::Int16 => "int16_t",
+        FFIType::UInt16 => "uint16_t",
+        FFIType::Int32 => "int32_t",
+        FFIType::UInt32 => "uint32_t",
+        FFIType::Int64 => "int64_t",
+        FFIType::UInt64 => "uint64_t",
+        FFIType::Float32 => "float",
+        FFIType::Float64 => "double",
+        FFIType::RustArcPtr => "void *",
+        FFIType::RustBuffer => "RustBuffer",
+        FFIType::ForeignBytes => "ForeignBytes",
+        FFIType::ForeignCallback => "ForeignCallback",
+        FFIType::AsyncCompletion => "UniFfiAsyncCompletion",
+        FFIType::ForeignMethodCallback(name) => return name.clone(),
+    }
+    .to_string()
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// Whether this is the name of an argument, which might be `mut`.
+fn is_pattern(pattern: &str) -> bool {
+    let pattern = pattern.trim();
+    let name = pattern.strip_prefix("mut ").unwrap_or(pattern).trim();
+    !name.is_empty() && name.chars().all(is_ident_char)
+}
+
+// Split `(...)...` into what's inside the parentheses and what follows them, if the parentheses
+// are balanced.
+fn split_parenthesized(source: &str) -> Option<(&str, &str)> {
+    if !source.starts_with('(') {
+        return None;
+    }
+    let mut depth = 0;
+    for (i, c) in source.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((&source[1..i], &source[i + 1..]));
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// The position of the first `wanted` that isn't nested in any brackets, looking past the `>`
+// of any `->`.
+fn find_top_level(source: &str, wanted: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut previous = ' ';
+    for (i, c) in source.char_indices() {
+        if c == wanted && depth == 0 {
+            return Some(i);
+        }
+        match c {
+            '(' | '[' | '<' => depth += 1,
+            '>' if previous == '-' => {}
+            ')' | ']' | '>' => depth -= 1,
+            _ => {}
+        }
+        previous = c;
+    }
+    None
+}
+
+// Split a list at the commas that separate its items, but not those in the types of the items.
+fn split_top_level(mut source: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    while let Some(comma) = find_top_level(source, ',') {
+        items.push(&source[..comma]);
+        source = &source[comma + 1..];
+    }
+    items.push(source);
+    items
+}
+
+// Remove `//` and `/* */` comments, which the templates are fond of putting in signatures,
+// leaving string literals alone.
+fn strip_comments(source: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                while let Some(&next) = chars.peek() {
+                    if next == '\n' {
+                        break;
+                    }
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+                result.push(' ');
+            }
+            ('"', _) => {
+                result.push(c);
+                let mut escaped = false;
+                for next in chars.by_ref() {
+                    result.push(next);
+                    match next {
+                        '\\' if !escaped => escaped = true,
+                        '"' if !escaped => break,
+                        _ => escaped = false,
+                    }
+                }
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scaffolding::RustScaffolding;
+
+    const UDL: &str = r#"
+        namespace test {
+            [Throws=Oops]
+            string do_it(u8 a, sequence<i64> b);
+            void register(Listener listener);
+        };
+        [Error]
+        enum Oops { "Bad" };
+        dictionary Point { f64 x; f32 y; };
+        [Clone]
+        interface Thing {
+            constructor(Point p);
+            [Name=from_raw]
+            constructor(u64 raw);
+            [Self=ByMut]
+            void set(u16 value);
+            timestamp when();
+            boolean is_done();
+        };
+        [Direct]
+        callback interface Listener {
+            void on_event(i32 count);
+        };
+    "#;
+
+    #[test]
+    fn test_generated_scaffolding_matches_bindings() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        check_scaffolding_abi(&ci, &scaffolding).unwrap();
+        let signatures = scaffolding_signatures(&ci, &scaffolding).unwrap();
+        assert_eq!(signatures.len(), ci.iter_ffi_function_definitions().len());
+    }
+
+    #[test]
+    fn test_parses_extern_functions() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let source = r#"
+            // pub extern "C" fn commented_out(x: i8) {}
+            type Callback = unsafe extern "C" fn(i32) -> i32;
+            #[no_mangle]
+            pub extern "C" fn make(
+                value: u64,call_status: &mut uniffi::RustCallStatus
+            ) -> *const std::os::raw::c_void /* *const Thing */ {
+                log("extern \"C\" fn fake(x: String) {} // not a comment");
+            }
+            pub unsafe extern "C" fn nothing() {}
+        "#;
+        let signatures = scaffolding_signatures(&ci, source).unwrap();
+        assert_eq!(signatures.len(), 2);
+        assert_eq!(
+            signatures["make"],
+            Signature {
+                arguments: vec![AbiType::Ffi(FFIType::UInt64), AbiType::CallStatus],
+                return_type: Some(FFIType::RustArcPtr),
+            }
+        );
+        assert_eq!(
+            signatures["make"].c_declaration("make"),
+            "void * make(uint64_t, RustCallStatus *)"
+        );
+        assert_eq!(
+            signatures["nothing"].c_declaration("nothing"),
+            "void nothing()"
+        );
+
+        let err = |source: &str| scaffolding_signatures(&ci, source).unwrap_err().to_string();
+        assert_eq!(
+            err("extern \"C\" fn bad(s: String) {}"),
+            "Unsupported type in extern function signature: `String`"
+        );
+        // The only callback types are those of the interface's `[Direct]` callback interfaces.
+        assert_eq!(
+            err("extern \"C\" fn bad(cb: ForeignCallback_Other) {}"),
+            "Unsupported type in extern function signature: `ForeignCallback_Other`"
+        );
+        assert_eq!(
+            err("extern \"C\" fn bad(u64) {}"),
+            "Malformed argument `u64` for extern function `bad`"
+        );
+        assert_eq!(
+            err("extern \"C\" fn bad(x: u8"),
+            "Malformed argument list for extern function `bad`"
+        );
+        assert_eq!(
+            err("extern \"C\" fn bad(x: u8);"),
+            "Missing body for extern function `bad`"
+        );
+        assert_eq!(
+            err("extern \"C\" fn twice() {} extern \"C\" fn twice() {}"),
+            "Extern function `twice` is defined more than once"
+        );
+    }
+
+    #[test]
+    fn test_parses_direct_callback_types() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let cbi = ci.get_callback_interface_definition("Listener").unwrap();
+        let callback = cbi.ffi_direct_callbacks()[0].name().to_string();
+        let source = format!(
+            "extern \"C\" fn init(on_event: {}, call_status: &mut uniffi::RustCallStatus) {{}}",
+            callback
+        );
+        let signatures = scaffolding_signatures(&ci, &source).unwrap();
+        assert_eq!(
+            signatures["init"].arguments,
+            vec![
+                AbiType::Ffi(FFIType::ForeignMethodCallback(callback)),
+                AbiType::CallStatus
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reports_drift() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci)
+            .to_string()
+            .replace("a: u8,", "a: u16,");
+        let err = check_scaffolding_abi(&ci, &scaffolding).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("bindings expect:    RustBuffer test_"));
+        assert!(err
+            .to_string()
+            .contains("(uint16_t, RustBuffer, RustCallStatus *)"));
+    }
+}
@@ -2,27 +2,82 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use askama::Template;
 
 use super::interface::*;
+use super::lockfile::{exported_callables, ExportedCallable};
 use heck::SnakeCase;
 
+pub mod abi_check;
+pub mod wasm;
+
+/// What the scaffolding exports the component as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaffoldingTarget {
+    /// `extern "C"` functions, which the foreign-language bindings call, which is the default.
+    Native,
+    /// `wasm_bindgen` exports, for compiling the component to WebAssembly to use it from
+    /// JavaScript, see [`wasm`].
+    Wasm,
+}
+
+impl Default for ScaffoldingTarget {
+    fn default() -> Self {
+        ScaffoldingTarget::Native
+    }
+}
+
+impl std::str::FromStr for ScaffoldingTarget {
+    type Err = anyhow::Error;
+    fn from_str(value: &str) -> Result<Self> {
+        Ok(match value {
+            "native" => ScaffoldingTarget::Native,
+            "wasm" => ScaffoldingTarget::Wasm,
+            _ => bail!(
+                "Unknown scaffolding target: \"{}\" (expected \"native\" or \"wasm\")",
+                value
+            ),
+        })
+    }
+}
+
 #[derive(Template)]
 #[template(syntax = "rs", escape = "none", path = "scaffolding_template.rs")]
 pub struct RustScaffolding<'a> {
     ci: &'a ComponentInterface,
     uniffi_version: &'static str,
+    callables: Vec<ExportedCallable>,
 }
 impl<'a> RustScaffolding<'a> {
     pub fn new(ci: &'a ComponentInterface) -> Self {
         Self {
             ci,
             uniffi_version: crate::BINDGEN_VERSION,
+            callables: exported_callables(ci),
         }
     }
+
+    fn udl_declaration(&self, kind: &str, qualified_name: String) -> String {
+        udl_declaration(&self.callables, kind, qualified_name)
+    }
+
+    // The signatures of the `[Invokable]` functions, as a Rust string literal.
+    fn invoke_signatures_rs(&self) -> String {
+        format!("{:?}", self.ci.invoke_signatures())
+    }
+}
+
+// The declaration of a function, constructor or method, as it's written in the API lockfile,
+// for quoting next to the call to its Rust implementation.
+fn udl_declaration(callables: &[ExportedCallable], kind: &str, qualified_name: String) -> String {
+    callables
+        .iter()
+        .find(|callable| callable.kind() == kind && callable.qualified_name() == qualified_name)
+        .map(|callable| callable.declaration().to_string())
+        .unwrap_or(qualified_name)
 }
-mod filters {
+pub(crate) mod filters {
     use super::*;
     use std::fmt;
 
@@ -38,21 +93,71 @@ mod filters {
             Type::UInt64 => "u64".into(),
             Type::Float32 => "f32".into(),
             Type::Float64 => "f64".into(),
+            Type::BigInt => "i128".into(),
+            Type::Bytes => "std::vec::Vec<u8>".into(),
             Type::Boolean => "bool".into(),
+            Type::Char => "char".into(),
             Type::String => "String".into(),
             Type::Timestamp => "std::time::SystemTime".into(),
             Type::Duration => "std::time::Duration".into(),
             Type::Enum(name) | Type::Record(name) | Type::Error(name) => name.clone(),
-            Type::Object(name) => format!("std::sync::Arc<{}>", name),
+            Type::Object(name) => format!("std::sync::Arc<{}>", object_inner_rs(name)?),
             Type::CallbackInterface(name) => format!("Box<dyn {}>", name),
             Type::Optional(t) => format!("std::option::Option<{}>", type_rs(t)?),
             Type::Sequence(t) => format!("std::vec::Vec<{}>", type_rs(t)?),
-            Type::Map(t) => format!("std::collections::HashMap<String, {}>", type_rs(t)?),
-            Type::External { .. } => panic!("External types coming to a uniffi near you soon!"),
-            Type::Wrapped { .. } => panic!("Wrapped types coming to a uniffi near you soon!"),
+            Type::Map(k, v) => format!(
+                "std::collections::HashMap<{}, {}>",
+                type_rs(k)?,
+                type_rs(v)?
+            ),
+            Type::OrderedMap(t) => {
+                format!("uniffi::deps::indexmap::IndexMap<String, {}>", type_rs(t)?)
+            }
+            // Only the `FfiConverter` of an external type is imported, so we name the type through it.
+            Type::External { name, .. } => {
+                format!(
+                    "<FfiConverterType{} as uniffi::FfiConverter>::RustType",
+                    name
+                )
+            }
+            Type::ExternalInterface { name, crate_name } => {
+                format!("std::sync::Arc<{}::{}>", crate_name_rs(crate_name)?, name)
+            }
+            Type::Wrapped { name, .. } => name.clone(),
         })
     }
 
+    /// Get the Rust type that a function or method returns, apart from any error.
+    pub fn return_type_rs(type_: &Option<&Type>) -> Result<String, askama::Error> {
+        match type_ {
+            Some(type_) => type_rs(type_),
+            None => Ok("()".into()),
+        }
+    }
+
+    /// Get the Rust type that a `[Borrowed]` method returns a reference to.
+    pub fn borrowed_rs(type_: &Type) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Type::String => "str".into(),
+            _ => "[u8]".into(),
+        })
+    }
+
+    /// Get the view that a `[Borrowed]` method returns, given the type of the data.
+    pub fn borrowed_view_rs(type_: &Type) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Type::String => "uniffi::BorrowedString".into(),
+            _ => "uniffi::BorrowedBytes".into(),
+        })
+    }
+
+    /// Get the Rust path of a top-level function, including the modules of any nested namespace.
+    pub fn function_path_rs(func: &Function) -> Result<String, askama::Error> {
+        let mut path: Vec<&str> = func.module_path();
+        path.push(func.name());
+        Ok(path.join("::"))
+    }
+
     pub fn type_ffi(type_: &FFIType) -> Result<String, askama::Error> {
         Ok(match type_ {
             FFIType::Int8 => "i8".into(),
@@ -69,6 +174,8 @@ mod filters {
             FFIType::RustBuffer => "uniffi::RustBuffer".into(),
             FFIType::ForeignBytes => "uniffi::ForeignBytes".into(),
             FFIType::ForeignCallback => "uniffi::ForeignCallback".into(),
+            FFIType::ForeignMethodCallback(name) => name.clone(),
+            FFIType::AsyncCompletion => "uniffi::AsyncCompletion".into(),
         })
     }
 
@@ -82,24 +189,35 @@ mod filters {
             // Timestamp/Duraration are handled by standard types
             Type::Timestamp => "std::time::SystemTime".into(),
             Type::Duration => "std::time::Duration".into(),
-            // Object is handled by Arc<T>
-            Type::Object(name) => format!("std::sync::Arc<{}>", name),
+            // The objects of other crates are handled by Arc<T>
+            Type::ExternalInterface { .. } => type_rs(type_)?,
             // Other user-defined types are handled by a unit-struct that we generate.  The
             // FfiConverter implementation for this can be found in one of the scaffolding template code.
             //
-            // We generate a unit-struct to sidestep Rust's orphan rules (ADR-0006).
+            // We generate a unit-struct to sidestep Rust's orphan rules (ADR-0006). For objects
+            // it usually hands the work to Arc<T>, which can't lower the `Arc<dyn Trait>` of a
+            // `[Trait]` interface to a single pointer.
             //
             // CallbackInterface is handled by special case code on both the scaffolding and
             // bindings side.  It's not a unit-struct, but the same name generation code works.
-            Type::Enum(_) | Type::Record(_) | Type::Error(_) | Type::CallbackInterface(_) => {
+            Type::Object(_)
+            | Type::Enum(_)
+            | Type::Record(_)
+            | Type::Error(_)
+            | Type::CallbackInterface(_) => {
                 format!("FfiConverter{}", type_.canonical_name())
             }
             // Wrapper types are implemented by generics that wrap the FfiConverter implementation of the
             // inner type.
             Type::Optional(inner) => format!("std::option::Option<{}>", ffi_converter_name(inner)?),
             Type::Sequence(inner) => format!("std::vec::Vec<{}>", ffi_converter_name(inner)?),
-            Type::Map(inner) => format!(
-                "std::collections::HashMap<String, {}>",
+            Type::Map(k, v) => format!(
+                "std::collections::HashMap<{}, {}>",
+                ffi_converter_name(k)?,
+                ffi_converter_name(v)?
+            ),
+            Type::OrderedMap(inner) => format!(
+                "uniffi::deps::indexmap::IndexMap<String, {}>",
                 ffi_converter_name(inner)?
             ),
             // External and Wrapped bytes have FfiConverters with a predictable name based on the type name.
@@ -117,11 +235,22 @@ mod filters {
             Type::UInt64 => "u64".into(),
             Type::Float32 => "f32".into(),
             Type::Float64 => "f64".into(),
+            Type::BigInt => "i128".into(),
+            Type::Bytes => "std::vec::Vec<u8>".into(),
             Type::String => "String".into(),
             Type::Boolean => "bool".into(),
+            Type::Char => "char".into(),
         })
     }
 
+    /// Get the name of the type that an object's `Arc` points to.
+    ///
+    /// This is a type alias generated in the object's scaffolding. It's usually just the
+    /// user's struct, but objects with `[Self=ByMut]` methods are wrapped in a `RwLock`.
+    pub fn object_inner_rs(name: &str) -> askama::Result<String> {
+        Ok(format!("UniffiObject{}", name))
+    }
+
     // Map a type to Rust code that specifies the FfiConverter implementation.
     //
     // This outputs something like `<TheFfiConverterStruct as FfiConverter>`
@@ -137,3 +266,562 @@ mod filters {
         Ok(nm.to_string().to_snake_case())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_calls_are_checked_against_the_udl() {
+        const UDL: &str = r#"
+            namespace test {
+                [Throws=Oops]
+                u32 parse([ByRef] string input);
+            };
+            [Error]
+            enum Oops { "Bad" };
+            interface Counter {
+                constructor(u32 start);
+                [Self=ByArc]
+                u32 get();
+                void reset();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        let check_fn = |name: &str| {
+            let start = scaffolding
+                .find(&format!("fn uniffi_check_{}", name))
+                .unwrap();
+            let end = start + scaffolding[start..].find("\n}").unwrap();
+            scaffolding[start..end]
+                .lines()
+                .map(str::trim)
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let func = ci.get_function_definition("parse").unwrap();
+        assert_eq!(
+            check_fn(func.ffi_func().name()),
+            format!(
+                "fn uniffi_check_{}(input: &String) -> std::result::Result<u32, Oops> { \
+                 parse(input).map_err(Into::into) // Does the Rust implementation match \
+                 `function [Throws=Oops] u32 parse([ByRef] string input)` in the UDL?",
+                func.ffi_func().name()
+            )
+        );
+
+        let obj = ci.get_object_definition("Counter").unwrap();
+        let cons = check_fn(obj.constructors()[0].ffi_func().name());
+        assert!(cons.contains("(start: u32) -> Counter {"));
+        assert!(cons.contains("Counter::new(start) // "));
+        let get = check_fn(obj.methods()[0].ffi_func().name());
+        assert!(get.contains("(ptr: std::sync::Arc<UniffiObjectCounter>) -> u32 {"));
+        assert!(get.contains("Counter::get(ptr) // Does the Rust implementation match `interface Counter method [Self=ByArc] u32 get()`"));
+        let reset = check_fn(obj.methods()[1].ffi_func().name());
+        assert!(reset.contains("(ptr: &Counter) -> () {"));
+    }
+
+    #[test]
+    fn test_error_from_generates_from_impls() {
+        const UDL: &str = r#"
+            namespace test {};
+            [Error]
+            interface StoreError {
+                [ErrorFrom="std::io::Error, std::fmt::Error"]
+                Io(string message);
+                [ErrorFrom="std::num::ParseIntError"]
+                Parse();
+                [ErrorFrom="*"]
+                Other(string reason);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        let from_impl = |source: &str| {
+            let start = scaffolding
+                .find(&format!("impl From<{}> for StoreError {{", source))
+                .unwrap();
+            let end = start + scaffolding[start..].find("\n}").unwrap();
+            scaffolding[start..end]
+                .lines()
+                .map(str::trim)
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        assert!(
+            from_impl("std::io::Error").ends_with("StoreError::Io { message: err.to_string() } }")
+        );
+        assert!(from_impl("std::fmt::Error").contains("StoreError::Io {"));
+        assert!(from_impl("std::num::ParseIntError").ends_with("let _ = err; StoreError::Parse }"));
+        assert!(from_impl("uniffi::deps::anyhow::Error")
+            .ends_with("StoreError::Other { reason: err.to_string() } }"));
+        assert!(
+            from_impl("Box<dyn std::error::Error + Send + Sync>").contains("StoreError::Other {")
+        );
+    }
+
+    #[test]
+    fn test_borrowed_methods_return_references() {
+        const UDL: &str = r#"
+            namespace test {};
+            interface Document {
+                [Borrowed]
+                string line(u32 index);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        let meth = &ci.get_object_definition("Document").unwrap().methods()[0];
+        assert!(scaffolding.contains(&format!(
+            "fn uniffi_check_{}<'a>(ptr: &'a Document, index: u32) -> &'a str {{",
+            meth.ffi_func().name()
+        )));
+        assert!(scaffolding.contains("Document::line(ptr, index) // "));
+        assert!(scaffolding.contains("let _view = uniffi::BorrowedString::new(_owner, |owner| {"));
+        assert!(scaffolding.contains("pub use uniffi::BorrowedString;"));
+        assert!(!scaffolding.contains("pub use uniffi::BorrowedBytes;"));
+    }
+
+    #[test]
+    fn test_stream_typedefs_are_uniffi_streams() {
+        const UDL: &str = r#"
+            namespace test {};
+            [Stream]
+            typedef sequence<Document> Documents;
+            interface Document {
+                Documents children();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(scaffolding.contains(
+            "pub type Documents = uniffi::Stream<std::sync::Arc<UniffiObjectDocument>>;"
+        ));
+        assert!(scaffolding.contains("pub type UniffiObjectDocuments = Documents;"));
+        assert!(scaffolding.contains("Documents::next_chunk(ptr, max) // "));
+    }
+
+    #[test]
+    fn test_custom_typedefs_implement_the_converter() {
+        const UDL: &str = r#"
+            namespace test {
+                Url parse_url(string text);
+                Handle next_handle();
+            };
+            [Custom]
+            typedef string Url;
+            [Wrapped]
+            typedef i64 Handle;
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(scaffolding.contains("trait UniffiCustomTypeConverter {"));
+        assert!(scaffolding.contains("trait UniffiCustomTypeWrapper {"));
+        // `[Custom]` types are given a `UniffiCustomTypeWrapper` by way of their converter, and
+        // `[Wrapped]` types implement it themselves.
+        assert!(scaffolding.contains("impl UniffiCustomTypeWrapper for Url {"));
+        assert!(scaffolding.contains("<Url as UniffiCustomTypeConverter>::into_custom(val)"));
+        assert!(!scaffolding.contains("impl UniffiCustomTypeWrapper for Handle {"));
+        assert!(scaffolding.contains("pub struct FfiConverterTypeUrl;"));
+        assert!(scaffolding.contains("pub struct FfiConverterTypeHandle;"));
+
+        // Components without `[Custom]` types don't get the converter trait.
+        let ci = ComponentInterface::from_webidl(&UDL.replace("[Custom]", "[Wrapped]")).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(!scaffolding.contains("UniffiCustomTypeConverter"));
+    }
+
+    #[test]
+    fn test_trait_interfaces_are_held_as_trait_objects() {
+        const UDL: &str = r#"
+            namespace test {
+                Shape make_shape();
+            };
+            [Trait]
+            interface Shape {
+                string name();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        let obj = ci.get_object_definition("Shape").unwrap();
+        assert!(scaffolding.contains("pub type UniffiObjectShape = dyn Shape;"));
+        assert!(
+            scaffolding.contains("unsafe impl uniffi::FfiConverter for FfiConverterTypeShape {")
+        );
+        assert!(scaffolding.contains("Box::into_raw(Box::new(obj)) as Self::FfiType"));
+        assert!(scaffolding.contains(
+            "drop(unsafe { Box::from_raw(ptr as *mut std::sync::Arc<UniffiObjectShape>) })"
+        ));
+        assert!(scaffolding.contains(&format!(
+            "fn uniffi_check_{}(ptr: &dyn Shape) -> String {{",
+            obj.methods()[0].ffi_func().name()
+        )));
+        let func = ci.get_function_definition("make_shape").unwrap();
+        assert!(scaffolding.contains(&format!(
+            "fn uniffi_check_{}() -> std::sync::Arc<UniffiObjectShape> {{",
+            func.ffi_func().name()
+        )));
+    }
+
+    #[test]
+    fn test_weak_refs_objects_hand_out_weak_pointers() {
+        const UDL: &str = r#"
+            namespace test {};
+            [WeakRefs]
+            interface Thumbnail {
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        let obj = ci.get_object_definition("Thumbnail").unwrap();
+        for func in [
+            obj.ffi_object_downgrade(),
+            obj.ffi_object_upgrade(),
+            obj.ffi_object_weak_free(),
+        ]
+        .iter()
+        {
+            assert!(scaffolding.contains(&format!("pub extern \"C\" fn {}(", func.unwrap().name())));
+        }
+        assert!(scaffolding.contains("std::sync::Weak::into_raw(std::sync::Arc::downgrade(&obj))"));
+        assert!(scaffolding.contains("Some(obj) => std::sync::Arc::into_raw(obj) as u64,"));
+    }
+
+    #[test]
+    fn test_ordered_callbacks_are_queued() {
+        const UDL: &str = r#"
+            namespace test {};
+            [Ordered]
+            callback interface Listener {
+                void on_event(string name);
+            };
+            callback interface Logger {
+                void log(string message);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(scaffolding.contains("delivery: uniffi::OrderedDelivery,"));
+        assert!(scaffolding.contains("delivery: uniffi::OrderedDelivery::new(\"Listener\"),"));
+        assert!(scaffolding.contains("self.delivery.deliver(move || {"));
+        // Only the ordered interface's proxy queues its calls.
+        assert_eq!(scaffolding.matches("self.delivery.deliver(").count(), 1);
+        assert_eq!(scaffolding.matches("self.handle.invoke(").count(), 1);
+    }
+
+    #[test]
+    fn test_callback_methods_return_thrown_errors() {
+        const UDL: &str = r#"
+            namespace test {};
+            [Error]
+            enum StoreError { "NotFound" };
+            [Error]
+            enum OtherError { "Oops" };
+            callback interface Store {
+                [Throws=StoreError]
+                string get(string key);
+                [Throws=StoreError]
+                void clear();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(scaffolding.contains("Result<String, StoreError>"));
+        assert!(scaffolding.contains("Result<(), StoreError>"));
+        assert!(scaffolding.contains("if uniffi::callback_threw(&mut ret_buf, \"Store.get\") {"));
+        assert_eq!(
+            scaffolding
+                .matches("return Err(<FfiConverterTypeStoreError as uniffi::FfiConverter>::try_read(&mut ret_buf).unwrap());")
+                .count(),
+            2
+        );
+        // Only the error that the callback interface throws can be read back into Rust.
+        assert_eq!(
+            scaffolding
+                .matches("panic!(\"try_read not supported for flat errors\");")
+                .count(),
+            1
+        );
+        assert!(scaffolding.contains("1 => StoreError::NotFound,"));
+    }
+
+    #[test]
+    fn test_async_callback_methods_return_futures() {
+        const UDL: &str = r#"
+            namespace test {};
+            [Error]
+            enum FetchError { "Offline" };
+            callback interface Fetcher {
+                [Async, Throws=FetchError]
+                string fetch(string url);
+                [Async]
+                u32 size();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(scaffolding.contains("-> uniffi::CallbackFuture<Result<String, FetchError>>"));
+        assert!(scaffolding.contains("-> uniffi::CallbackFuture<u32>"));
+        assert!(scaffolding
+            .contains("uniffi::CallbackFuture::pending(\"Fetcher.fetch\", |vec: Vec<u8>| {"));
+        // The completion handle follows the arguments, even for methods that don't take any.
+        assert_eq!(
+            scaffolding
+                .matches("<u64 as uniffi::FfiConverter>::write(completion_handle, &mut args_buf);")
+                .count(),
+            2
+        );
+        let complete = ci.ffi_callback_future_complete().unwrap();
+        assert!(scaffolding.contains(&format!("pub extern \"C\" fn {}(", complete.name())));
+    }
+
+    #[test]
+    fn test_async_functions_are_spawned_on_the_executor() {
+        const UDL: &str = r#"
+            namespace test {
+                [Async, Throws=FetchError]
+                string fetch(string url);
+            };
+            [Error]
+            enum FetchError { "Offline" };
+            interface Client {
+                constructor();
+                [Async]
+                void flush();
+                [Async, Cancellable]
+                u32 watch(u32 limit);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(scaffolding.contains("async fn uniffi_check_"));
+        assert_eq!(
+            scaffolding
+                .matches(
+                    "uniffi::spawn_async_call(&UNIFFI_ASYNC_CALLS, uniffi_completion, async move {"
+                )
+                .count(),
+            2
+        );
+        assert!(scaffolding.contains("uniffi_completion: u64,"));
+        // The component keeps its calls apart from those of any other component in the library.
+        assert!(scaffolding.contains(
+            "static UNIFFI_ASYNC_CALLS: uniffi::AsyncCalls = uniffi::AsyncCalls::new();"
+        ));
+        let init = ci.ffi_async_init().unwrap();
+        assert!(scaffolding.contains(&format!("pub extern \"C\" fn {}(", init.name())));
+        // Warming the library up starts the threads that run the calls.
+        assert!(scaffolding.contains("uniffi::start_async_executor();"));
+        // A `[Cancellable]` method is given the call's token, after its arguments.
+        assert!(scaffolding.contains(
+            "uniffi::spawn_cancellable_async_call(&UNIFFI_ASYNC_CALLS, uniffi_completion, move |uniffi_token| async move {"
+        ));
+        assert!(scaffolding.contains("ptr: &Client, limit: u32, token: uniffi::CancellationToken)"));
+        assert!(scaffolding.contains("(&ptr, limit, uniffi_token).await"));
+        let cancel = ci.ffi_async_cancel().unwrap();
+        assert!(scaffolding.contains(&format!(
+            "pub extern \"C\" fn {}(handle: u64, call_status: &mut uniffi::RustCallStatus)",
+            cancel.name()
+        )));
+        abi_check::check_scaffolding_abi(&ci, &scaffolding).unwrap();
+    }
+
+    #[test]
+    fn test_scoped_functions_take_the_reexported_scope() {
+        const UDL: &str = r#"
+            namespace test {
+                [Scoped]
+                void prefetch(string url);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(scaffolding.contains("pub use uniffi::Scope;"));
+        assert!(scaffolding.contains("scope: *const std::os::raw::c_void,"));
+        assert!(scaffolding
+            .contains("<FfiConverterTypeScope as uniffi::FfiConverter>::try_lift(scope)"));
+        assert!(scaffolding.contains("scope: std::sync::Arc<Scope>, url: String"));
+
+        let ci = ComponentInterface::from_webidl("namespace test { void ping(); };").unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(!scaffolding.contains("uniffi::Scope"));
+    }
+
+    #[test]
+    fn test_observable_objects_get_observer_methods() {
+        const UDL: &str = r#"
+            namespace test {};
+            dictionary ProfileState {
+                string name;
+            };
+            [Observable=ProfileState]
+            interface Profile {
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(scaffolding.contains("trait ProfileObserver: Send + Sync {"));
+        assert!(
+            scaffolding.contains("fn on_changed(&self, field: String, snapshot: ProfileState);")
+        );
+        assert!(scaffolding.contains("impl Profile {"));
+        assert!(scaffolding.contains("uniffi::Observable::observers(self).remove(id)"));
+        assert!(scaffolding.contains("Profile::add_observer(ptr, observer) // "));
+    }
+
+    #[test]
+    fn test_event_sources_get_listener_methods() {
+        const UDL: &str = r#"
+            namespace test {};
+            [Enum]
+            interface DownloadEvent {
+                Finished();
+            };
+            [Events=DownloadEvent]
+            interface Downloader {
+                constructor();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(scaffolding.contains("trait DownloaderListener: Send + Sync {"));
+        assert!(scaffolding.contains("fn on_event(&self, event: DownloadEvent);"));
+        assert!(scaffolding.contains("uniffi::EventSource::listeners(self).remove(id)"));
+        assert!(scaffolding.contains("Downloader::add_listener(ptr, listener) // "));
+        assert!(!scaffolding.contains("uniffi::Observable"));
+    }
+
+    #[test]
+    fn test_paginated_methods_get_page_methods() {
+        const UDL: &str = r#"
+            namespace test {};
+            [Error]
+            enum LibraryError { "Closed" };
+            interface Library {
+                [Paginated]
+                sequence<string> titles([ByRef] string author);
+                [Paginated, Throws=LibraryError]
+                sequence<Library> branches();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(scaffolding.contains("pub struct LibraryTitlesPage {"));
+        assert!(scaffolding.contains("pub items: std::vec::Vec<String>,"));
+        assert!(scaffolding.contains(
+            "fn titles_page(&self, author: &String, offset: u64, limit: u32) -> LibraryTitlesPage {"
+        ));
+        assert!(scaffolding.contains("uniffi::paginate(self.titles(author), offset, limit);"));
+        assert!(
+            scaffolding.contains("pub items: std::vec::Vec<std::sync::Arc<UniffiObjectLibrary>>,")
+        );
+        assert!(scaffolding.contains("-> std::result::Result<LibraryBranchesPage, LibraryError> {"));
+        assert!(scaffolding.contains("uniffi::paginate(self.branches()?, offset, limit);"));
+        assert!(scaffolding.contains("Library::titles_page(ptr, author, offset, limit) // "));
+    }
+
+    #[test]
+    fn test_batchable_functions_are_called_from_the_batch() {
+        const UDL: &str = r#"
+            namespace test {
+                [Batchable]
+                string name();
+                [Batchable, Throws=Oops]
+                u32 parse([ByRef] string input);
+                void reload();
+            };
+            [Error]
+            enum Oops { "Bad" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        let batch = ci.ffi_batch().unwrap();
+        assert!(scaffolding.contains(&format!(
+            "pub extern \"C\" fn {}(calls: uniffi::RustBuffer, call_status: &mut uniffi::RustCallStatus) -> uniffi::RustBuffer {{",
+            batch.name()
+        )));
+        let parse = ci.get_function_definition("parse").unwrap();
+        assert!(scaffolding.contains("2 => {"));
+        assert!(scaffolding
+            .contains("let input = <String as uniffi::FfiConverter>::try_read(&mut uniffi_buf)"));
+        assert!(scaffolding.contains(&format!(
+            "match uniffi_check_{}(&input) {{",
+            parse.ffi_func().name()
+        )));
+        assert!(scaffolding.contains(
+            "<FfiConverterTypeOops as uniffi::FfiConverter>::write(err, &mut uniffi_results);"
+        ));
+        assert!(!scaffolding.contains("3 => {"));
+
+        // Components without any `[Batchable]` functions don't get the batch function at all.
+        let ci = ComponentInterface::from_webidl("namespace test { void reload(); };").unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(!scaffolding.contains("_uniffi_batch"));
+    }
+
+    #[test]
+    fn test_invokable_functions_are_called_by_name() {
+        const UDL: &str = r#"
+            namespace test {
+                [Invokable]
+                string greet([ByRef] string name);
+                [Invokable, Throws=Oops]
+                void reload();
+                void hidden();
+            };
+            [Error]
+            enum Oops { "Bad" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        let invoke = ci.ffi_invoke().unwrap();
+        assert!(scaffolding.contains(&format!(
+            "pub extern \"C\" fn {}(name: uniffi::RustBuffer, args: uniffi::RustBuffer, call_status: &mut uniffi::RustCallStatus) -> uniffi::RustBuffer {{",
+            invoke.name()
+        )));
+        let greet = ci.get_function_definition("greet").unwrap();
+        assert!(scaffolding.contains("\"greet\" => {"));
+        assert!(scaffolding.contains(&format!(
+            "let _retval = uniffi_check_{}(&name);",
+            greet.ffi_func().name()
+        )));
+        assert!(scaffolding.contains("\"reload\" => {"));
+        assert!(!scaffolding.contains("\"hidden\" => {"));
+        assert!(scaffolding.contains(
+            "<String as uniffi::FfiConverter>::lower(\"greet\\tstring\\t\\tname:string\\nreload\\t\\tOops\".to_string())"
+        ));
+
+        // Components without any `[Invokable]` functions don't get the invoke functions at all.
+        let ci = ComponentInterface::from_webidl("namespace test { void reload(); };").unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(!scaffolding.contains("_uniffi_invoke"));
+    }
+
+    #[test]
+    fn test_calls_and_locks_are_tracked_for_deadlocks() {
+        const UDL: &str = r#"
+            namespace test {};
+            callback interface Listener {
+                void on_change(u32 value);
+            };
+            interface Counter {
+                [Self=ByMut]
+                void increment(Listener listener);
+                u32 value();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = RustScaffolding::new(&ci).to_string();
+        assert!(scaffolding
+            .contains("let _uniffi_call = uniffi::enter_rust_call(\"Counter.increment\");"));
+        assert!(scaffolding.contains("&mut *uniffi::write_object(&*("));
+        assert!(scaffolding.contains("&*uniffi::read_object(&*("));
+        assert!(scaffolding.contains("), \"Counter\")"));
+        assert!(scaffolding
+            .contains("let _uniffi_call = uniffi::enter_foreign_call(\"Listener.on_change\");"));
+    }
+}
@@ -0,0 +1,58 @@
+{#
+// The bindings call this from `batch { ... }`, to make all the calls to `[Batchable]` functions
+// that were queued up in the batch at once. `calls` holds each call's function, numbered from 1
+// in the order that the UDL declares them, followed by its arguments.
+// The result holds a status byte for each call, `0` followed by the return value if it succeeded,
+// or `1` followed by the error if it failed.
+#}
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ batch.name() }}(calls: uniffi::RustBuffer, call_status: &mut uniffi::RustCallStatus) -> uniffi::RustBuffer {
+    uniffi::deps::log::debug!("{{ batch.name() }}");
+    uniffi::call_with_output(call_status, || {
+        uniffi::check_not_shut_down();
+        let uniffi_calls = calls.destroy_into_vec();
+        let mut uniffi_buf = uniffi_calls.as_slice();
+        let mut uniffi_results = std::vec::Vec::new();
+        while !uniffi_buf.is_empty() {
+            match <i32 as uniffi::FfiConverter>::try_read(&mut uniffi_buf).expect("Failed to read the batch") {
+            {%- for func in ci.iter_batchable_function_definitions() %}
+                {{ loop.index }} => {
+                    {%- for arg in func.arguments() %}
+                    let {{ arg.name() }} = {{ arg.type_()|ffi_converter }}::try_read(&mut uniffi_buf).expect("Failed to read arg '{{ arg.name() }}' of a batched call to {{ func.name() }}");
+                    {%- endfor %}
+                    {%- match func.throws_type() %}
+                    {%- when Some with (e) %}
+                    match uniffi_check_{{ func.ffi_func().name() }}({% call rs::batch_args(func) %}) {
+                        Ok(_retval) => {
+                            <i8 as uniffi::FfiConverter>::write(0, &mut uniffi_results);
+                            {%- match func.return_type() %}
+                            {%- when Some with (return_type) %}
+                            {{ return_type|ffi_converter }}::write(_retval, &mut uniffi_results);
+                            {%- when None %}
+                            {%- endmatch %}
+                        }
+                        Err(err) => {
+                            <i8 as uniffi::FfiConverter>::write(1, &mut uniffi_results);
+                            {{ e|ffi_converter }}::write(err, &mut uniffi_results);
+                        }
+                    }
+                    {%- when None %}
+                    {%- match func.return_type() %}
+                    {%- when Some with (return_type) %}
+                    let _retval = uniffi_check_{{ func.ffi_func().name() }}({% call rs::batch_args(func) %});
+                    <i8 as uniffi::FfiConverter>::write(0, &mut uniffi_results);
+                    {{ return_type|ffi_converter }}::write(_retval, &mut uniffi_results);
+                    {%- when None %}
+                    uniffi_check_{{ func.ffi_func().name() }}({% call rs::batch_args(func) %});
+                    <i8 as uniffi::FfiConverter>::write(0, &mut uniffi_results);
+                    {%- endmatch %}
+                    {%- endmatch %}
+                }
+            {%- endfor %}
+                index => panic!("Unknown batched call {}", index),
+            }
+        }
+        uniffi::RustBuffer::from_vec(uniffi_results)
+    })
+}
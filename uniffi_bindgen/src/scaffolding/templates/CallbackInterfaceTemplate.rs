@@ -8,7 +8,22 @@
 //    is the object that client code interacts with.
 //    - for each method, arguments will be packed into a `RustBuffer` and sent over the `ForeignCallback` to be 
 //      unpacked and called. The return value is packed into another `RustBuffer` and sent back to Rust.
-//    - a `Drop` `impl`, which tells the foreign language to forget about the real callback object.
+//    - for methods that throw, the returned `RustBuffer` starts with a byte that says whether it holds
+//      the return value or the error, which is returned as the `Err` of the method's `Result`.
+//    - it holds a `uniffi::ForeignCallbackHandle`, which tells the foreign language to forget about the
+//      real callback object when it is dropped.
+//  * for `[Direct]` callback interfaces, a function pointer type and a `uniffi::ForeignMethodInternals`
+//    for each method that only takes and returns primitives, and an init function to accept them all.
+//    Those methods call their function pointer with the lowered arguments if one was registered,
+//    and fall back to the `ForeignCallback` otherwise.
+//  * for `[Ordered]` callback interfaces, a `uniffi::OrderedDelivery` in the proxy, which the
+//    methods queue their calls on instead of making them on the calling thread.
+//  * for `[Async]` methods, a `uniffi::CallbackFuture` that the method returns straight away, whose
+//    completion handle is packed after the arguments. The foreign language completes it with the
+//    `RustBuffer` that the method would have returned if it weren't async.
+//  * for `[Versioned]` callback interfaces, the `uniffi::CallbackCapabilities` of the foreign
+//    object in the proxy, which the methods look up the index to call with by name, and which
+//    the proxy hands out through `uniffi::VersionedCallback`.
 #}
 {% let trait_name = cbi.name() -%}
 {% let trait_impl = cbi.type_()|ffi_converter_name -%}
@@ -20,22 +35,55 @@ static {{ foreign_callback_internals }}: uniffi::ForeignCallbackInternals = unif
 
 #[doc(hidden)]
 #[no_mangle]
-pub extern "C" fn {{ cbi.ffi_init_callback().name() }}(callback: uniffi::ForeignCallback) {
-    {{ foreign_callback_internals }}.set_callback(callback);
+pub extern "C" fn {{ cbi.ffi_init_callback().name() }}(callback: uniffi::ForeignCallback, call_status: &mut uniffi::RustCallStatus) {
+    uniffi::call_with_output(call_status, || {
+        {{ foreign_callback_internals }}.set_callback(callback);
+    })
 }
 
+{%- match cbi.ffi_init_direct_callbacks() %}
+{%- when Some with (ffi_init_direct) %}
+
+// Register the foreign functions for calling each `[Direct]` method without a `RustBuffer`.
+{%- for meth in cbi.direct_methods() %}
+{%- match cbi.ffi_direct_callback(meth) %}
+{%- when Some with (callback) %}
+#[doc(hidden)]
+#[allow(non_camel_case_types)]
+pub type {{ callback.name() }} = unsafe extern "C" fn(
+    {%- for arg in callback.arguments() %}{{ arg.type_()|type_ffi }}{% if loop.last %}{% else %}, {% endif %}{% endfor -%}
+){% match callback.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_ffi }}{% when None %}{% endmatch %};
+
+#[doc(hidden)]
+static {{ format!("foreign_callback_{}_{}_internals", trait_name, meth.name())|upper }}: uniffi::ForeignMethodInternals<{{ callback.name() }}> = uniffi::ForeignMethodInternals::new();
+{%- when None %}
+{%- endmatch %}
+{%- endfor %}
+
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ ffi_init_direct.name() }}(
+    {%- call rs::arg_list_ffi_decl(ffi_init_direct) %}) {
+    uniffi::call_with_output(call_status, || {
+        {%- for meth in cbi.direct_methods() %}
+        {{ format!("foreign_callback_{}_{}_internals", trait_name, meth.name())|upper }}.set_callback({{ meth.name() }});
+        {%- endfor %}
+    })
+}
+{%- when None %}
+{%- endmatch %}
+
 // Make an implementation which will shell out to the foreign language.
 #[doc(hidden)]
 #[derive(Debug)]
 struct {{ trait_impl }} {
-  handle: u64
-}
-
-impl Drop for {{ trait_impl }} {
-    fn drop(&mut self) {
-        let callback = {{ foreign_callback_internals }}.get_callback().unwrap();
-        unsafe { callback(self.handle, uniffi::IDX_CALLBACK_FREE, Default::default()) };
-    }
+  handle: std::sync::Arc<uniffi::ForeignCallbackHandle>,
+  {%- if cbi.is_ordered() %}
+  delivery: uniffi::OrderedDelivery,
+  {%- endif %}
+  {%- if cbi.is_versioned() %}
+  capabilities: uniffi::CallbackCapabilities,
+  {%- endif %}
 }
 
 uniffi::deps::static_assertions::assert_impl_all!({{ trait_impl }}: Send);
@@ -46,15 +94,60 @@ impl {{ trait_name }} for {{ trait_impl }} {
     {#- Method declaration #}
     fn {{ meth.name() -}}
     ({% call rs::arg_list_decl_with_prefix("&self", meth) %})
+    {%- if meth.is_async() %} -> uniffi::CallbackFuture<{% call rs::callback_return_type(meth) %}>
+    {% else %}
+    {%- match meth.throws_type() %}
+    {%- when Some with (e) %} -> Result<{% match meth.return_type() %}{% when Some with (return_type) %}{{ return_type|type_rs }}{% when None %}(){% endmatch %}, {{ e|type_rs }}>
+    {% when None %}
     {%- match meth.return_type() %}
     {%- when Some with (return_type) %} -> {{ return_type|type_rs }}
     {% else -%}
-    {%- endmatch -%} { 
+    {%- endmatch -%}
+    {%- endmatch -%}
+    {%- endif -%} { 
     {#- Method body #}
         uniffi::deps::log::debug!("{{ cbi.name() }}.{{ meth.name() }}");
+        let _uniffi_call = uniffi::enter_foreign_call("{{ cbi.name() }}.{{ meth.name() }}");
+
+    {#- Calling the method's own function pointer, if the foreign language registered one. #}
+        {%- match cbi.ffi_direct_callback(meth) %}
+        {%- when Some with (callback) %}
+        if let Some(callback) = {{ format!("foreign_callback_{}_{}_internals", trait_name, meth.name())|upper }}.get_callback() {
+            return self.handle.invoke_direct(|callback_handle| {
+                {%- match meth.return_type() %}
+                {%- when Some with (return_type) %}
+                let ret = unsafe { callback(callback_handle{% for arg in meth.arguments() %}, {{ arg.type_()|ffi_converter }}::lower({{ arg.name() }}){% endfor %}) };
+                {{ return_type|ffi_converter }}::try_lift(ret).unwrap()
+                {%- when None %}
+                unsafe { callback(callback_handle{% for arg in meth.arguments() %}, {{ arg.type_()|ffi_converter }}::lower({{ arg.name() }}){% endfor %}) }
+                {%- endmatch %}
+            });
+        }
+        {%- when None %}
+        {%- endmatch %}
+        {%- if cbi.is_versioned() %}
+
+    {#- Finding the method by name, in case the foreign object is from another version of the interface. #}
+        let method_index = match self.capabilities.method_index("{{ meth.name() }}", {{ loop.index }}) {
+            Some(index) => index,
+            {%- if meth.is_async() && meth.return_type().is_none() %}
+            None => return uniffi::CallbackFuture::ready({% if meth.throws().is_some() %}Ok(()){% else %}(){% endif %}),
+            {%- else if meth.is_async() && meth.default_return_value().is_some() %}
+            None => return uniffi::CallbackFuture::ready({% if meth.throws().is_some() %}Ok(Default::default()){% else %}Default::default(){% endif %}),
+            {%- else if meth.return_type().is_none() %}
+            None => return{% if meth.throws().is_some() %} Ok(()){% endif %},
+            {%- else if meth.default_return_value().is_some() %}
+            None => return {% if meth.throws().is_some() %}Ok(Default::default()){% else %}Default::default(){% endif %},
+            {%- else %}
+            None => panic!("The foreign implementation of {{ cbi.name() }} doesn't have the `{{ meth.name() }}` method"),
+            {%- endif %}
+        };
+        {%- else %}
+        let method_index = {{ loop.index }};
+        {%- endif %}
 
     {#- Packing args into a RustBuffer #}
-        {% if meth.arguments().len() == 0 -%}
+        {% if meth.arguments().len() == 0 && !meth.is_async() -%}
         let args_buf = Vec::new();
         {% else -%}
         let mut args_buf = Vec::new();
@@ -62,13 +155,67 @@ impl {{ trait_name }} for {{ trait_impl }} {
         {%- for arg in meth.arguments() %}
         {{ arg.type_()|ffi_converter }}::write({{ arg.name() }}, &mut args_buf);
         {%- endfor -%}
+        {%- if cbi.is_ordered() %}
+
+    {#- Queueing the call, after the arguments have been packed on the calling thread. #}
+        let handle = std::sync::Arc::clone(&self.handle);
+        self.delivery.deliver(move || {
+            let ret_rbuf = handle.invoke(method_index, uniffi::RustBuffer::from_vec(args_buf));
+            uniffi::RustBuffer::destroy(ret_rbuf);
+        });
+    }
+        {%- else if meth.is_async() %}
+
+    {#- Registering the future, which unpacks what the method returned like the other methods do, and sending its handle #}
+        let (completion_handle, future) = uniffi::CallbackFuture::pending("{{ cbi.name() }}.{{ meth.name() }}", |vec: Vec<u8>| {
+            {%- match meth.throws_type() %}
+            {%- when Some with (e) %}
+            let mut ret_buf = vec.as_slice();
+            if uniffi::callback_threw(&mut ret_buf, "{{ cbi.name() }}.{{ meth.name() }}") {
+                return Err({{ e|ffi_converter }}::try_read(&mut ret_buf).unwrap());
+            }
+            {%- match meth.return_type() %}
+            {%- when Some with (return_type) %}
+            Ok({{ return_type|ffi_converter }}::try_read(&mut ret_buf).unwrap())
+            {%- when None %}
+            Ok(())
+            {%- endmatch %}
+            {%- when None %}
+            {%- match meth.return_type() %}
+            {%- when Some with (return_type) %}
+            let mut ret_buf = vec.as_slice();
+            {{ return_type|ffi_converter }}::try_read(&mut ret_buf).unwrap()
+            {%- when None %}
+            drop(vec);
+            {%- endmatch %}
+            {%- endmatch %}
+        });
+        <u64 as uniffi::FfiConverter>::write(completion_handle, &mut args_buf);
+        let ret_rbuf = self.handle.invoke(method_index, uniffi::RustBuffer::from_vec(args_buf));
+        uniffi::RustBuffer::destroy(ret_rbuf);
+        future
+    }
+        {%- else %}
         let args_rbuf = uniffi::RustBuffer::from_vec(args_buf);
 
     {#- Calling into foreign code. #}
-        let callback = {{ foreign_callback_internals }}.get_callback().unwrap();
-        let ret_rbuf = unsafe { callback(self.handle, {{ loop.index }}, args_rbuf) };
+        let ret_rbuf = self.handle.invoke(method_index, args_rbuf);
 
-    {#- Unpacking the RustBuffer to return to Rust #}
+    {#- Unpacking the RustBuffer to return to Rust, as the error that the method threw if it did #}
+        {% match meth.throws_type() -%}
+        {% when Some with (e) -%}
+        let vec = ret_rbuf.destroy_into_vec();
+        let mut ret_buf = vec.as_slice();
+        if uniffi::callback_threw(&mut ret_buf, "{{ cbi.name() }}.{{ meth.name() }}") {
+            return Err({{ e|ffi_converter }}::try_read(&mut ret_buf).unwrap());
+        }
+        {%- match meth.return_type() %}
+        {%- when Some with (return_type) %}
+        Ok({{ return_type|ffi_converter }}::try_read(&mut ret_buf).unwrap())
+        {%- when None %}
+        Ok(())
+        {%- endmatch %}
+        {% when None -%}
         {% match meth.return_type() -%}
         {% when Some with (return_type) -%}
         let vec = ret_rbuf.destroy_into_vec();
@@ -77,7 +224,9 @@ impl {{ trait_name }} for {{ trait_impl }} {
         {%- else -%}
         uniffi::RustBuffer::destroy(ret_rbuf);
         {%- endmatch %}
+        {%- endmatch %}
     }
+        {%- endif %}
     {%- endfor %}
 }
 
@@ -104,7 +253,19 @@ unsafe impl uniffi::FfiConverter for {{ trait_impl }} {
     }
 
     fn try_lift(v: Self::FfiType) -> uniffi::deps::anyhow::Result<Self::RustType> {
-        Ok(Box::new(Self { handle: v }))
+        let handle = uniffi::ForeignCallbackHandle::new(v, &{{ foreign_callback_internals }});
+        {%- if cbi.is_versioned() %}
+        let capabilities = uniffi::CallbackCapabilities::query(&handle, {{ cbi.version() }});
+        {%- endif %}
+        Ok(Box::new(Self {
+            handle,
+            {%- if cbi.is_ordered() %}
+            delivery: uniffi::OrderedDelivery::new("{{ trait_name }}"),
+            {%- endif %}
+            {%- if cbi.is_versioned() %}
+            capabilities,
+            {%- endif %}
+        }))
     }
 
     fn try_read(buf: &mut &[u8]) -> uniffi::deps::anyhow::Result<Self::RustType> {
@@ -113,3 +274,11 @@ unsafe impl uniffi::FfiConverter for {{ trait_impl }} {
         <Self as uniffi::FfiConverter>::try_lift(buf.get_u64())
     }
 }
+{%- if cbi.is_versioned() %}
+
+impl uniffi::VersionedCallback for {{ trait_impl }} {
+    fn capabilities(&self) -> &uniffi::CallbackCapabilities {
+        &self.capabilities
+    }
+}
+{%- endif %}
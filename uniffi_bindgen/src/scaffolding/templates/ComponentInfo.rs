@@ -0,0 +1,69 @@
+// Metadata about this component, so that support tooling can find out exactly which build
+// of the library an app is using. The foreign-language bindings fetch it via the FFI.
+#[cfg(debug_assertions)]
+const UNIFFI_BUILD_PROFILE: &str = "debug";
+#[cfg(not(debug_assertions))]
+const UNIFFI_BUILD_PROFILE: &str = "release";
+
+#[doc(hidden)]
+pub const UNIFFI_COMPONENT_INFO: uniffi::ComponentInfo = uniffi::ComponentInfo {
+    name: "{{ ci.namespace() }}",
+    uniffi_version: "{{ uniffi_version }}",
+    checksum: {{ ci.checksum() }},
+    build_profile: UNIFFI_BUILD_PROFILE,
+};
+
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ ci.ffi_component_info().name() }}(call_status: &mut uniffi::RustCallStatus) -> uniffi::RustBuffer {
+    uniffi::call_with_output(call_status, || {
+        <String as uniffi::FfiConverter>::lower(UNIFFI_COMPONENT_INFO.to_ffi_string())
+    })
+}
+
+// The bindings call this before anything else, to check that they were generated from the
+// same component interface as this library. Its name doesn't depend on the checksum, so
+// that they can always find it.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ ci.ffi_checksum().name() }}(call_status: &mut uniffi::RustCallStatus) -> u64 {
+    uniffi::call_with_output(call_status, || UNIFFI_COMPONENT_INFO.checksum)
+}
+
+
+// The Python and Ruby bindings call this from `reset_library()`, once they've freed every object
+// they hold, to run the reset hooks that the component registered with `uniffi::on_reset`.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ ci.ffi_reset().name() }}(call_status: &mut uniffi::RustCallStatus) {
+    uniffi::call_with_output(call_status, uniffi::run_reset_hooks)
+}
+
+// The Kotlin, Python and Ruby bindings call this when their runtime starts to exit, to stop any
+// more callbacks into it, and fail any more calls from it, before it's torn down.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ ci.ffi_shutdown().name() }}(call_status: &mut uniffi::RustCallStatus) {
+    uniffi::call_with_output(call_status, uniffi::shut_down)
+}
+
+// The Kotlin, Swift and Python bindings call this from `uniffiWarmup()`, to run the hooks that the
+// component registered with `uniffi::on_warmup`, off the thread that will make the first real call.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ ci.ffi_warmup().name() }}(call_status: &mut uniffi::RustCallStatus) {
+    uniffi::call_with_output(call_status, || {
+        {%- if ci.ffi_async_init().is_some() %}
+        uniffi::start_async_executor();
+        {%- endif %}
+        uniffi::run_warmup_hooks()
+    })
+}
+
+// The Kotlin and Swift bindings call this from `uniffiOnMemoryPressure()`, to run the hooks that
+// the component registered with `uniffi::on_memory_pressure`.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ ci.ffi_on_memory_pressure().name() }}(level: i32, call_status: &mut uniffi::RustCallStatus) {
+    uniffi::call_with_output(call_status, || uniffi::run_memory_pressure_hooks(level))
+}
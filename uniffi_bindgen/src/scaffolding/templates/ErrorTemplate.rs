@@ -23,6 +23,9 @@ impl uniffi::RustBufferFfiConverter for {{ e.type_()|ffi_converter_name }} {
     fn write(obj: {{ e.name() }}, buf: &mut std::vec::Vec<u8>) {
         use uniffi::deps::bytes::BufMut;
         let msg = obj.to_string();
+        {%- if e.has_source_chain() %}
+        let causes = uniffi::error_source_chain(&obj);
+        {%- endif %}
         match obj {
             {%- for variant in e.variants() %}
             {{ e.name() }}::{{ variant.name() }}{..} => {
@@ -31,12 +34,38 @@ impl uniffi::RustBufferFfiConverter for {{ e.type_()|ffi_converter_name }} {
             },
             {%- endfor %}
         };
+        {%- if e.has_source_chain() %}
+        // The messages of the error's sources follow the variant data.
+        <Vec<String> as uniffi::FfiConverter>::write(causes, buf);
+        {%- endif %}
     }
 
+    {%- if ci.is_thrown_by_callback_interface(e.name()) %}
+
+    // Callback interfaces throw this error from the foreign language. There's nowhere to put
+    // the message, so the Rust variants can't have fields.
+    fn try_read(buf: &mut &[u8]) -> uniffi::deps::anyhow::Result<{{ e.name() }}> {
+        use uniffi::deps::bytes::Buf;
+        uniffi::check_remaining(buf, 4)?;
+        let error = match buf.get_i32() {
+            {%- for variant in e.variants() %}
+            {{ loop.index }} => {{ e.name() }}::{{ variant.name() }},
+            {%- endfor %}
+            v => uniffi::deps::anyhow::bail!("Invalid {{ e.name() }} enum value: {}", v),
+        };
+        <String as uniffi::FfiConverter>::try_read(buf)?;
+        {%- if e.has_source_chain() %}
+        <Vec<String> as uniffi::FfiConverter>::try_read(buf)?;
+        {%- endif %}
+        Ok(error)
+    }
+    {%- else %}
+
     fn try_read(_buf: &mut &[u8]) -> uniffi::deps::anyhow::Result<{{ e.name() }}> {
-        // It's not currently possible to send errors from the foreign language *into* Rust.
+        // Flat errors are only sent from the foreign language *into* Rust by callback interfaces.
         panic!("try_read not supported for flat errors");
     }
+    {%- endif %}
 
     {% else %}
 
@@ -49,6 +78,9 @@ impl uniffi::RustBufferFfiConverter for {{ e.type_()|ffi_converter_name }} {
 
     fn write(obj: {{ e.name() }}, buf: &mut std::vec::Vec<u8>) {
         use uniffi::deps::bytes::BufMut;
+        {%- if e.has_source_chain() %}
+        let causes = uniffi::error_source_chain(&obj);
+        {%- endif %}
         match obj {
             {%- for variant in e.variants() %}
             {{ e.name() }}::{{ variant.name() }}{% if variant.has_fields() %} { {% for field in variant.fields() %}{{ field.name() }}, {%- endfor %} }{% else %}{..}{% endif %} => {
@@ -59,14 +91,17 @@ impl uniffi::RustBufferFfiConverter for {{ e.type_()|ffi_converter_name }} {
             },
             {%- endfor %}
         };
+        {%- if e.has_source_chain() %}
+        // The messages of the error's sources follow the variant data.
+        <Vec<String> as uniffi::FfiConverter>::write(causes, buf);
+        {%- endif %}
     }
 
     fn try_read(buf: &mut &[u8]) -> uniffi::deps::anyhow::Result<{{ e.name() }}> {
-        // It's not currently supported to send errors from the foreign language *into* Rust,
-        // but this is what the supporting code might look like...
+        // Callback interfaces send errors from the foreign language *into* Rust.
         use uniffi::deps::bytes::Buf;
         uniffi::check_remaining(buf, 4)?;
-        Ok(match buf.get_i32() {
+        let error = match buf.get_i32() {
             {%- for variant in e.variants() %}
             {{ loop.index }} => {{ e.name() }}::{{ variant.name() }}{% if variant.has_fields() %} {
                 {% for field in variant.fields() %}
@@ -75,9 +110,45 @@ impl uniffi::RustBufferFfiConverter for {{ e.type_()|ffi_converter_name }} {
             }{% endif %},
             {%- endfor %}
             v => uniffi::deps::anyhow::bail!("Invalid {{ e.name() }} enum value: {}", v),
-        })
+        };
+        {%- if e.has_source_chain() %}
+        // There's nowhere to put the sources on the Rust side, so they're discarded.
+        <Vec<String> as uniffi::FfiConverter>::try_read(buf)?;
+        {%- endif %}
+        Ok(error)
     }
     {% endif %}
 }
 
 impl uniffi::FfiError for {{ e.type_()|ffi_converter_name }} { }
+
+{#-
+// Variants with `[ErrorFrom]` get a `From` impl for each of the Rust error types that it lists,
+// so that `?` converts those errors into the variant. Its field, if it has one, is the message.
+#}
+{%- for variant in e.variants() %}
+{%- for source in variant.error_from() %}
+
+impl From<{{ source }}> for {{ e.name() }} {
+    fn from(err: {{ source }}) -> Self {
+        {%- call rs::error_from_variant(e, variant) %}
+    }
+}
+{%- endfor %}
+{%- if variant.is_error_catch_all() %}
+
+// The catch-all variant takes any other error, as an `anyhow::Error` or boxed.
+impl From<uniffi::deps::anyhow::Error> for {{ e.name() }} {
+    fn from(err: uniffi::deps::anyhow::Error) -> Self {
+        {%- call rs::error_from_variant(e, variant) %}
+    }
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for {{ e.name() }} {
+    fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        {%- call rs::error_from_variant(e, variant) %}
+    }
+}
+{%- endif %}
+{%- endfor %}
+
@@ -6,7 +6,30 @@
 use {{ crate_name|crate_name_rs }}::FfiConverterType{{ name }};
 {% endfor %}
 
-// More complicated locally `Wrapped` types - we generate FfiConverter.
+// Objects of other components are passed as pointers to the same Rust struct, so the bindings
+// check that the other component's library was built from the same interface as the one that
+// this library was built against, before passing any of them.
+{% for crate_name in ci.iter_external_interface_crates() %}
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ ci.ffi_external_checksum(crate_name).name() }}(call_status: &mut uniffi::RustCallStatus) -> u64 {
+    uniffi::call_with_output(call_status, || {{ crate_name|crate_name_rs }}::UNIFFI_COMPONENT_INFO.checksum)
+}
+{% endfor %}
+
+{%- if ci.has_custom_types() %}
+
+// A trait that's in our crate for our `[Custom]` types to implement, converting them from and into
+// the builtin type that they're passed across the FFI as.
+trait UniffiCustomTypeConverter {
+    type Builtin;
+
+    fn into_custom(val: Self::Builtin) -> uniffi::Result<Self> where Self: Sized;
+    fn from_custom(obj: Self) -> Self::Builtin;
+}
+{%- endif %}
+
+// More complicated locally `Wrapped` and `Custom` types - we generate FfiConverter.
 {% for (name, prim) in ci.iter_wrapped_types() %}
 {% if loop.first %}
 
@@ -19,6 +42,21 @@ trait UniffiCustomTypeWrapper {
 }
 
 {%- endif -%}
+{%- if ci.is_custom_type(name) %}
+
+// Type `{{ name }}` is `[Custom]`, so it's converted with `UniffiCustomTypeConverter`.
+impl UniffiCustomTypeWrapper for {{ name }} {
+    type Wrapped = <{{ name }} as UniffiCustomTypeConverter>::Builtin;
+
+    fn wrap(val: Self::Wrapped) -> uniffi::Result<Self> {
+        <{{ name }} as UniffiCustomTypeConverter>::into_custom(val)
+    }
+
+    fn unwrap(obj: Self) -> Self::Wrapped {
+        <{{ name }} as UniffiCustomTypeConverter>::from_custom(obj)
+    }
+}
+{%- endif %}
 
 // Type `{{ name }}` wraps a `{{ prim.canonical_name() }}`
 #[doc(hidden)]
@@ -0,0 +1,69 @@
+{#
+// Scripting layers and the like call this to call an `[Invokable]` function by its qualified
+// name, without bindings for it. `args` holds the function's arguments, serialized in order, as
+// the signatures below describe them. The result is a status byte, `0` followed by the return
+// value if the call succeeded, or `1` followed by the error if it failed, as for batches.
+#}
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ invoke.name() }}(name: uniffi::RustBuffer, args: uniffi::RustBuffer, call_status: &mut uniffi::RustCallStatus) -> uniffi::RustBuffer {
+    uniffi::deps::log::debug!("{{ invoke.name() }}");
+    uniffi::call_with_output(call_status, || {
+        uniffi::check_not_shut_down();
+        let uniffi_name = <String as uniffi::FfiConverter>::try_lift(name).expect("Failed to read the name of an invoked function");
+        let uniffi_args = args.destroy_into_vec();
+        let mut uniffi_buf = uniffi_args.as_slice();
+        let mut uniffi_results = std::vec::Vec::new();
+        match uniffi_name.as_str() {
+        {%- for func in ci.iter_invokable_function_definitions() %}
+            "{{ func.qualified_name() }}" => {
+                {%- for arg in func.arguments() %}
+                let {{ arg.name() }} = {{ arg.type_()|ffi_converter }}::try_read(&mut uniffi_buf).expect("Failed to read arg '{{ arg.name() }}' of an invoked call to {{ func.qualified_name() }}");
+                {%- endfor %}
+                if !uniffi_buf.is_empty() {
+                    panic!("Too many arguments for an invoked call to {{ func.qualified_name() }}");
+                }
+                {%- match func.throws_type() %}
+                {%- when Some with (e) %}
+                match uniffi_check_{{ func.ffi_func().name() }}({% call rs::batch_args(func) %}) {
+                    Ok(_retval) => {
+                        <i8 as uniffi::FfiConverter>::write(0, &mut uniffi_results);
+                        {%- match func.return_type() %}
+                        {%- when Some with (return_type) %}
+                        {{ return_type|ffi_converter }}::write(_retval, &mut uniffi_results);
+                        {%- when None %}
+                        {%- endmatch %}
+                    }
+                    Err(err) => {
+                        <i8 as uniffi::FfiConverter>::write(1, &mut uniffi_results);
+                        {{ e|ffi_converter }}::write(err, &mut uniffi_results);
+                    }
+                }
+                {%- when None %}
+                {%- match func.return_type() %}
+                {%- when Some with (return_type) %}
+                let _retval = uniffi_check_{{ func.ffi_func().name() }}({% call rs::batch_args(func) %});
+                <i8 as uniffi::FfiConverter>::write(0, &mut uniffi_results);
+                {{ return_type|ffi_converter }}::write(_retval, &mut uniffi_results);
+                {%- when None %}
+                uniffi_check_{{ func.ffi_func().name() }}({% call rs::batch_args(func) %});
+                <i8 as uniffi::FfiConverter>::write(0, &mut uniffi_results);
+                {%- endmatch %}
+                {%- endmatch %}
+            }
+        {%- endfor %}
+            name => panic!("Unknown invoked function {}", name),
+        }
+        uniffi::RustBuffer::from_vec(uniffi_results)
+    })
+}
+
+// The signatures of the functions above, one per line, as `ComponentInterface::invoke_signatures`
+// describes them.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ invoke_signatures.name() }}(call_status: &mut uniffi::RustCallStatus) -> uniffi::RustBuffer {
+    uniffi::call_with_output(call_status, || {
+        <String as uniffi::FfiConverter>::lower({{ self.invoke_signatures_rs() }}.to_string())
+    })
+}
@@ -25,11 +25,212 @@ fn uniffi_note_threadsafe_deprecation_{{ obj.name() }}() {}
 {% endif %}
 
 
+{% if obj.is_borrowed_view() -%}
+// The views returned by `[Borrowed]` methods are implemented in the `uniffi` crate.
+#[doc(hidden)]
+pub use uniffi::{{ obj.name() }};
+{%- endif %}
+
+{%- match obj.stream_item() %}
+{%- when Some with (item) %}
+// The objects of `[Stream]` typedefs are implemented in the `uniffi` crate, and the Rust code that
+// returns one makes it from an iterator with `{{ obj.name() }}::new`.
+pub type {{ obj.name() }} = uniffi::Stream<{{ item|type_rs }}>;
+{%- when None %}
+{%- endmatch %}
+
+{% let inner = obj.name()|object_inner_rs -%}
+{%- if obj.is_trait() %}
+// `[Trait]` interfaces are implemented by any type implementing the trait, so each instance is
+// an `Arc<dyn {{ obj.name() }}>`.
+#[doc(hidden)]
+pub type {{ inner }} = dyn {{ obj.name() }};
+
+// The trait objects must be `Sync + Send`, which is easiest to ensure by making them supertraits.
+uniffi::deps::static_assertions::assert_impl_all!(dyn {{ obj.name() }}: Sync, Send);
+{%- else if obj.has_mut_methods() %}
+// This object has `[Self=ByMut]` methods, so we keep each instance behind a `RwLock`. Methods that
+// take `&self` acquire a read lock, those that take `&mut self` acquire a write lock.
+#[doc(hidden)]
+pub type {{ inner }} = std::sync::RwLock<{{ obj.name() }}>;
+
+// The `RwLock` takes care of `Sync`, but the struct itself must still be `Send`. The generated
+// scaffolding will fail to compile if it is not, but unfortunately it fails with an unactionably
+// obscure error message. By asserting the requirement explicitly, we help Rust produce a more
+// scrutable error message and thus help the user debug why the requirement isn't being met.
+uniffi::deps::static_assertions::assert_impl_all!({{ obj.name() }}: Send);
+{%- else %}
+#[doc(hidden)]
+pub type {{ inner }} = {{ obj.name() }};
+
 // All Object structs must be `Sync + Send`. The generated scaffolding will fail to compile
 // if they are not, but unfortunately it fails with an unactionably obscure error message.
 // By asserting the requirement explicitly, we help Rust produce a more scrutable error message
 // and thus help the user debug why the requirement isn't being met.
 uniffi::deps::static_assertions::assert_impl_all!({{ obj.name() }}: Sync, Send);
+{%- endif %}
+
+
+{% let ffi_converter_name = obj.type_()|ffi_converter_name -%}
+#[doc(hidden)]
+pub struct {{ ffi_converter_name }};
+
+{%- if obj.is_trait() %}
+
+// An `Arc<dyn Trait>` is twice the size of a pointer, so we hand out a pointer to a box holding
+// it instead, which is what `{{ obj.ffi_object_free().name() }}` frees.
+unsafe impl uniffi::FfiConverter for {{ ffi_converter_name }} {
+    type RustType = std::sync::Arc<{{ inner }}>;
+    type FfiType = *const std::os::raw::c_void;
+
+    fn lower(obj: Self::RustType) -> Self::FfiType {
+        Box::into_raw(Box::new(obj)) as Self::FfiType
+    }
+
+    fn try_lift(v: Self::FfiType) -> uniffi::Result<Self::RustType> {
+        // The box is owned by the foreign-language code, so we only clone the `Arc` inside it.
+        let foreign_arc = unsafe { &*(v as *const std::sync::Arc<{{ inner }}>) };
+        Ok(std::sync::Arc::clone(foreign_arc))
+    }
+
+    fn write(obj: Self::RustType, buf: &mut Vec<u8>) {
+        <u64 as uniffi::FfiConverter>::write(Self::lower(obj) as u64, buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> uniffi::Result<Self::RustType> {
+        Self::try_lift(<u64 as uniffi::FfiConverter>::try_read(buf)? as Self::FfiType)
+    }
+}
+{%- else %}
+
+// Everything else is handed out as the pointer of an `Arc`, by the `FfiConverter` for `Arc<T>`.
+unsafe impl uniffi::FfiConverter for {{ ffi_converter_name }} {
+    type RustType = std::sync::Arc<{{ inner }}>;
+    type FfiType = *const std::os::raw::c_void;
+
+    fn lower(obj: Self::RustType) -> Self::FfiType {
+        <Self::RustType as uniffi::FfiConverter>::lower(obj)
+    }
+
+    fn try_lift(v: Self::FfiType) -> uniffi::Result<Self::RustType> {
+        <Self::RustType as uniffi::FfiConverter>::try_lift(v)
+    }
+
+    fn write(obj: Self::RustType, buf: &mut Vec<u8>) {
+        <Self::RustType as uniffi::FfiConverter>::write(obj, buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> uniffi::Result<Self::RustType> {
+        <Self::RustType as uniffi::FfiConverter>::try_read(buf)
+    }
+}
+{%- endif %}
+
+{%- match obj.observer_interface() %}
+{%- when Some with (observer) %}
+
+// `[Observable]` objects get a callback interface for their observers, and methods for adding and
+// removing them, which keep the observers in the `uniffi::Observers` that the struct provides by
+// implementing `uniffi::Observable`.
+trait {{ observer }}: Send + Sync {
+    fn on_changed(&self, field: String, snapshot: {{ obj.observable_snapshot().unwrap() }});
+}
+
+impl {{ obj.name() }} {
+    fn add_observer(&self, observer: Box<dyn {{ observer }}>) -> u64 {
+        uniffi::Observable::observers(self).add(move |field, snapshot| observer.on_changed(field.to_string(), snapshot))
+    }
+
+    fn remove_observer(&self, id: u64) {
+        uniffi::Observable::observers(self).remove(id)
+    }
+}
+{%- when None %}
+{%- endmatch %}
+
+{%- match obj.listener_interface() %}
+{%- when Some with (listener) %}
+
+// Objects with `[Events]` get a callback interface for their listeners, and methods for adding and
+// removing them, which keep the listeners in the `uniffi::Listeners` that the struct provides by
+// implementing `uniffi::EventSource`.
+trait {{ listener }}: Send + Sync {
+    fn on_event(&self, event: {{ obj.events_type().unwrap() }});
+}
+
+impl {{ obj.name() }} {
+    fn add_listener(&self, listener: Box<dyn {{ listener }}>) -> u64 {
+        uniffi::EventSource::listeners(self).add(move |event| listener.on_event(event))
+    }
+
+    fn remove_listener(&self, id: u64) {
+        uniffi::EventSource::listeners(self).remove(id)
+    }
+}
+{%- when None %}
+{%- endmatch %}
+
+{%- match obj.state_observer_interface() %}
+{%- when Some with (observer) %}
+
+// `[Service]` objects get a callback interface for observers of their state, and methods for
+// starting, stopping and watching them, which use the `uniffi::ServiceLifecycle` that the struct
+// provides by implementing `uniffi::Service`.
+trait {{ observer }}: Send + Sync {
+    fn on_state_changed(&self, state: uniffi::ServiceState);
+}
+
+impl {{ obj.name() }} {
+    fn start(&self) {
+        uniffi::Service::start(self)
+    }
+
+    fn stop(&self) {
+        uniffi::Service::stop(self)
+    }
+
+    fn is_running(&self) -> bool {
+        uniffi::Service::is_running(self)
+    }
+
+    fn add_state_observer(&self, observer: Box<dyn {{ observer }}>) -> u64 {
+        uniffi::Service::lifecycle(self).add_observer(move |state| observer.on_state_changed(state))
+    }
+
+    fn remove_state_observer(&self, id: u64) {
+        uniffi::Service::lifecycle(self).remove_observer(id)
+    }
+}
+{%- when None %}
+{%- endmatch %}
+
+{%- for meth in obj.methods() %}
+{%- if meth.is_paginated() %}
+{%- let page = meth.page_record().unwrap() %}
+
+// `[Paginated]` methods get a record holding a page of the sequence that they return, and a method
+// returning one, which calls the method and takes the page out of what it returns.
+pub struct {{ page }} {
+    pub items: {{ meth.return_type()|return_type_rs }},
+    pub total: u64,
+}
+
+impl {{ obj.name() }} {
+    #[allow(clippy::too_many_arguments, clippy::ptr_arg)]
+    fn {{ meth.page_method_name() }}(&self, {% for arg in meth.arguments() %}{{ arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.type_()|type_rs }}, {% endfor %}offset: u64, limit: u32)
+    {%- match meth.throws() %}
+    {%- when Some with (e) %} -> std::result::Result<{{ page }}, {{ e }}> {
+        let (items, total) = uniffi::paginate(self.{{ meth.name() }}({% for arg in meth.arguments() %}{{ arg.name() }}{% if !loop.last %}, {% endif %}{% endfor %})?, offset, limit);
+        Ok({{ page }} { items, total })
+    }
+    {%- when None %} -> {{ page }} {
+        let (items, total) = uniffi::paginate(self.{{ meth.name() }}({% for arg in meth.arguments() %}{{ arg.name() }}{% if !loop.last %}, {% endif %}{% endfor %}), offset, limit);
+        {{ page }} { items, total }
+    }
+    {%- endmatch %}
+}
+{%- endif %}
+{%- endfor %}
 
 {% let ffi_free = obj.ffi_object_free() -%}
 #[doc(hidden)]
@@ -37,36 +238,164 @@ uniffi::deps::static_assertions::assert_impl_all!({{ obj.name() }}: Sync, Send);
 pub extern "C" fn {{ ffi_free.name() }}(ptr: *const std::os::raw::c_void, call_status: &mut uniffi::RustCallStatus) {
     uniffi::call_with_output(call_status, || {
         assert!(!ptr.is_null());
+        {%- if obj.is_trait() %}
+        {#- turn it back into the box holding the Arc and explicitly drop it. #}
+        drop(unsafe { Box::from_raw(ptr as *mut std::sync::Arc<{{ inner }}>) })
+        {%- else %}
         {#- turn it into an Arc and explicitly drop it. #}
-        drop(unsafe { std::sync::Arc::from_raw(ptr as *const {{ obj.name() }}) })
+        {%- if obj.releases_callbacks() %}
+        // The callbacks are only released once this is the last reference to the object.
+        uniffi::release_object_callbacks(unsafe { std::sync::Arc::from_raw(ptr as *const {{ inner }}) })
+        {%- else %}
+        drop(unsafe { std::sync::Arc::from_raw(ptr as *const {{ inner }}) })
+        {%- endif %}
+        {%- endif %}
     })
 }
 
+{%- match obj.ffi_object_clone() %}
+{%- when Some with (ffi_clone) %}
+
+// Objects with the `[Clone]` attribute hand out a brand new instance, rather than another
+// reference to the existing one, so their Rust struct must be `Clone`.
+uniffi::deps::static_assertions::assert_impl_all!({{ obj.name() }}: Clone);
+
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ ffi_clone.name() }}(ptr: *const std::os::raw::c_void, call_status: &mut uniffi::RustCallStatus) -> *const std::os::raw::c_void {
+    uniffi::call_with_output(call_status, || {
+        uniffi::check_not_shut_down();
+        assert!(!ptr.is_null());
+        {#- The foreign-language code still owns this `Arc`, we only borrow its contents. #}
+        let obj = unsafe { &*(ptr as *const {{ inner }}) };
+        {%- if obj.has_mut_methods() %}
+        let obj = obj.read().unwrap().clone();
+        {{ obj.type_()|ffi_converter }}::lower(std::sync::Arc::new(std::sync::RwLock::new(obj)))
+        {%- else %}
+        {{ obj.type_()|ffi_converter }}::lower(std::sync::Arc::new(obj.clone()))
+        {%- endif %}
+    })
+}
+{%- when None %}
+{%- endmatch %}
+
+{%- if obj.has_weak_refs() %}
+
+// Objects with the `[WeakRefs]` attribute hand out `Weak` references to their `Arc`, as pointers
+// that can only be upgraded back into the object or freed. Upgrading returns the object's pointer
+// as a number, as it's written into buffers, so that it can be zero once the object is gone.
+{%- match obj.ffi_object_downgrade() %}
+{%- when Some with (ffi_downgrade) %}
+
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ ffi_downgrade.name() }}(ptr: *const std::os::raw::c_void, call_status: &mut uniffi::RustCallStatus) -> *const std::os::raw::c_void {
+    uniffi::call_with_output(call_status, || {
+        assert!(!ptr.is_null());
+        {#- The foreign-language code still owns this `Arc`, so it mustn't be dropped here. #}
+        let obj = std::mem::ManuallyDrop::new(unsafe { std::sync::Arc::from_raw(ptr as *const {{ inner }}) });
+        std::sync::Weak::into_raw(std::sync::Arc::downgrade(&obj)) as *const std::os::raw::c_void
+    })
+}
+{%- when None %}
+{%- endmatch %}
+{%- match obj.ffi_object_upgrade() %}
+{%- when Some with (ffi_upgrade) %}
+
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ ffi_upgrade.name() }}(ptr: *const std::os::raw::c_void, call_status: &mut uniffi::RustCallStatus) -> u64 {
+    uniffi::call_with_output(call_status, || {
+        uniffi::check_not_shut_down();
+        assert!(!ptr.is_null());
+        {#- The weak reference stays with the foreign-language code, which frees it separately. #}
+        let weak = std::mem::ManuallyDrop::new(unsafe { std::sync::Weak::from_raw(ptr as *const {{ inner }}) });
+        match weak.upgrade() {
+            Some(obj) => std::sync::Arc::into_raw(obj) as u64,
+            None => 0,
+        }
+    })
+}
+{%- when None %}
+{%- endmatch %}
+{%- match obj.ffi_object_weak_free() %}
+{%- when Some with (ffi_weak_free) %}
+
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ ffi_weak_free.name() }}(ptr: *const std::os::raw::c_void, call_status: &mut uniffi::RustCallStatus) {
+    uniffi::call_with_output(call_status, || {
+        assert!(!ptr.is_null());
+        drop(unsafe { std::sync::Weak::from_raw(ptr as *const {{ inner }}) })
+    })
+}
+{%- when None %}
+{%- endmatch %}
+{%- endif %}
+
 {%- for cons in obj.constructors() %}
+{% call rs::udl_check_fn(cons, "", format!("{}::{}", obj.name(), cons.name()), obj.name(), self.udl_declaration("constructor", format!("{}.{}", obj.name(), cons.name()))) %}
+
     #[doc(hidden)]
     #[no_mangle]
     pub extern "C" fn {{ cons.ffi_func().name() }}(
         {%- call rs::arg_list_ffi_decl(cons.ffi_func()) %}) -> *const std::os::raw::c_void /* *const {{ obj.name() }} */ {
         uniffi::deps::log::debug!("{{ cons.ffi_func().name() }}");
+        let _uniffi_call = uniffi::enter_rust_call("{{ obj.name() }}.{{ cons.name() }}");
         {% if obj.uses_deprecated_threadsafe_attribute() %}
         uniffi_note_threadsafe_deprecation_{{ obj.name() }}();
         {% endif %}
 
         // If the constructor does not have the same signature as declared in the UDL, then
         // this attempt to call it will fail with a (somewhat) helpful compiler error.
+        {%- if obj.releases_callbacks() %}
+        // Any callback interfaces passed to the constructor are released along with the object.
+        let (ptr, callbacks) = uniffi::collect_foreign_callbacks(|| {
+            {% call rs::to_rs_constructor_call(obj, cons) %}
+        });
+        // Safety: a non-null `ptr` is the reference to the new object, which is still held.
+        unsafe { uniffi::register_object_callbacks(ptr as *const {{ inner }}, callbacks) };
+        ptr
+        {%- else %}
         {% call rs::to_rs_constructor_call(obj, cons) %}
+        {%- endif %}
     }
 {%- endfor %}
 
 {%- for meth in obj.methods() %}
+{%- let callee = format!("{}::{}", obj.name(), meth.name()) %}
+{%- let declaration = self.udl_declaration("method", format!("{}.{}", obj.name(), meth.name())) %}
+{%- if meth.is_borrowed() %}
+{% call rs::udl_check_borrowed_fn(meth, obj.name(), callee, meth.borrowed_type().unwrap()|borrowed_rs, declaration) %}
+{%- else if meth.is_async() && meth.takes_self_by_arc() %}
+{% call rs::udl_check_async_fn(meth, format!("std::sync::Arc<{}>", inner), callee, meth.return_type()|return_type_rs, declaration) %}
+{%- else if meth.is_async() %}
+{% call rs::udl_check_async_fn(meth, format!("&{}", obj.name()), callee, meth.return_type()|return_type_rs, declaration) %}
+{%- else if meth.takes_self_by_arc() %}
+{% call rs::udl_check_fn(meth, format!("std::sync::Arc<{}>", inner), callee, meth.return_type()|return_type_rs, declaration) %}
+{%- else if obj.is_trait() %}
+{% call rs::udl_check_fn(meth, format!("&dyn {}", obj.name()), callee, meth.return_type()|return_type_rs, declaration) %}
+{%- else if meth.takes_self_by_mut() %}
+{% call rs::udl_check_fn(meth, format!("&mut {}", obj.name()), callee, meth.return_type()|return_type_rs, declaration) %}
+{%- else %}
+{% call rs::udl_check_fn(meth, format!("&{}", obj.name()), callee, meth.return_type()|return_type_rs, declaration) %}
+{%- endif %}
+
     #[doc(hidden)]
     #[no_mangle]
     pub extern "C" fn {{ meth.ffi_func().name() }}(
         {%- call rs::arg_list_ffi_decl(meth.ffi_func()) %}
     ) {% call rs::return_signature(meth) %} {
         uniffi::deps::log::debug!("{{ meth.ffi_func().name() }}");
+        let _uniffi_call = uniffi::enter_rust_call("{{ obj.name() }}.{{ meth.name() }}");
         // If the method does not have the same signature as declared in the UDL, then
         // this attempt to call it will fail with a (somewhat) helpful compiler error.
+        {%- if meth.is_borrowed() %}
+        {% call rs::to_rs_borrowed_method_call(obj, meth) %}
+        {%- else if meth.is_async() %}
+        {% call rs::to_rs_async_call(meth) %}
+        {%- else %}
         {% call rs::to_rs_method_call(obj, meth) %}
+        {%- endif %}
     }
 {% endfor %}
@@ -6,6 +6,9 @@
 //
 // We define a unit-struct to implement the trait to sidestep Rust's orphan rule (ADR-0006). It's
 // public so other crates can refer to it via an `[External='crate'] typedef`
+//
+// Fields marked `[EmptyAsNull]` or `[NullAsEmpty]` are normalized when written and when read,
+// so values crossing the FFI in either direction always use the coalesced form.
 #}
 
 #[doc(hidden)]
@@ -19,14 +22,26 @@ impl uniffi::RustBufferFfiConverter for {{ rec.type_()|ffi_converter_name }} {
         // If the provided struct doesn't match the fields declared in the UDL, then
         // the generated code here will fail to compile with somewhat helpful error.
         {%- for field in rec.fields() %}
+        {%- if field.empty_as_null() %}
+        {{ field.type_()|ffi_converter }}::write(obj.{{ field.name() }}.filter(|v| !v.is_empty()), buf);
+        {%- else if field.null_as_empty() %}
+        {{ field.type_()|ffi_converter }}::write(Some(obj.{{ field.name() }}.unwrap_or_default()), buf);
+        {%- else %}
         {{ field.type_()|ffi_converter }}::write(obj.{{ field.name() }}, buf);
+        {%- endif %}
         {%- endfor %}
     }
 
     fn try_read(buf: &mut &[u8]) -> uniffi::deps::anyhow::Result<{{ rec.name() }}> {
         Ok({{ rec.name() }} {
             {%- for field in rec.fields() %}
+            {%- if field.empty_as_null() %}
+                {{ field.name() }}: {{ field.type_()|ffi_converter }}::try_read(buf)?.filter(|v| !v.is_empty()),
+            {%- else if field.null_as_empty() %}
+                {{ field.name() }}: Some({{ field.type_()|ffi_converter }}::try_read(buf)?.unwrap_or_default()),
+            {%- else %}
                 {{ field.name() }}: {{ field.type_()|ffi_converter }}::try_read(buf)?,
+            {%- endif %}
             {%- endfor %}
         })
     }
@@ -3,7 +3,16 @@
 // rust function of the same name. We provide a `pub extern "C"` wrapper that does type conversions to
 // send data across the FFI, which will fail to compile if the provided function does not match what's
 // specified in the UDL.    
+//
+// The FFI function of an `[Async]` function starts the future that its `async fn` returns, and
+// completes the call once it has finished.
 #}
+{%- if func.is_async() %}
+{% call rs::udl_check_async_fn(func, "", func|function_path_rs, func.return_type()|return_type_rs, self.udl_declaration("function", func.qualified_name())) %}
+{%- else %}
+{% call rs::udl_check_fn(func, "", func|function_path_rs, func.return_type()|return_type_rs, self.udl_declaration("function", func.qualified_name())) %}
+{%- endif %}
+
 #[doc(hidden)]
 #[no_mangle]
 pub extern "C" fn {{ func.ffi_func().name() }}(
@@ -12,5 +21,10 @@ pub extern "C" fn {{ func.ffi_func().name() }}(
     // If the provided function does not match the signature specified in the UDL
     // then this attempt to call it will not compile, and will give guidance as to why.
     uniffi::deps::log::debug!("{{ func.ffi_func().name() }}");
+    let _uniffi_call = uniffi::enter_rust_call("{{ func.qualified_name() }}");
+    {%- if func.is_async() %}
+    {% call rs::to_rs_async_call(func) %}
+    {%- else %}
     {% call rs::to_rs_function_call(func) %}
+    {%- endif %}
 }
@@ -2,12 +2,91 @@
 // Template to receive calls into rust.
 #}
 
+{#-
+// Call the Rust implementation of a function, constructor or method, through the checking
+// function generated for it by `udl_check_fn` below.
+-#}
 {%- macro to_rs_call(func) -%}
-{{ func.name() }}({% call _arg_list_rs_call(func) -%})
+uniffi_check_{{ func.ffi_func().name() }}({% call _arg_list_rs_call(func) -%})
+{%- endmacro -%}
+
+{#-
+// A function with exactly the signature that the UDL declares, which calls the Rust implementation.
+// The scaffolding only ever calls the implementation through one of these, so if it doesn't match
+// the UDL, the compiler points at this small function, and a comment on the same line as the call
+// quotes the declaration involved; the compiler only shows that single line as context.
+//
+// `receiver` is the type of the object that a method is called on, or empty for functions and
+// constructors, `callee` is the path of the implementation, and `return_type` is the Rust type
+// that it returns, apart from any error.
+-#}
+{%- macro udl_check_fn(func, receiver, callee, return_type, declaration) %}
+#[allow(non_snake_case, clippy::too_many_arguments, clippy::ptr_arg, clippy::unused_unit)]
+fn uniffi_check_{{ func.ffi_func().name() }}(
+    {%- if !receiver.is_empty() %}ptr: {{ receiver }}{% if func.arguments().len() > 0 %}, {% endif %}{% endif %}
+    {%- for arg in func.arguments() %}
+        {{- arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.type_()|type_rs }}{% if !loop.last %}, {% endif %}
+    {%- endfor -%}
+)
+{%- match func.throws() %}
+{%- when Some with (e) %} -> std::result::Result<{{ return_type }}, {{ e }}>
+{%- when None %} -> {{ return_type }}
+{%- endmatch %} {
+    {{ callee }}(
+        {%- if !receiver.is_empty() %}ptr{% if func.arguments().len() > 0 %}, {% endif %}{% endif %}
+        {%- for arg in func.arguments() %}{{ arg.name() }}{% if !loop.last %}, {% endif %}{% endfor -%}
+    ){% if func.throws().is_some() %}.map_err(Into::into){% endif %} // Does the Rust implementation match `{{ declaration }}` in the UDL?
+}
+{%- endmacro -%}
+
+{#-
+// Like `udl_check_fn`, for an `[Async]` function or method, whose Rust implementation is an
+// `async fn`. The future it returns is polled on the executor in the `uniffi` crate, so it must be
+// `Send`. A `[Cancellable]` one also takes the call's `CancellationToken`, after the arguments.
+-#}
+{%- macro udl_check_async_fn(func, receiver, callee, return_type, declaration) %}
+#[allow(non_snake_case, clippy::too_many_arguments, clippy::ptr_arg, clippy::unused_unit)]
+async fn uniffi_check_{{ func.ffi_func().name() }}(
+    {%- if !receiver.is_empty() %}ptr: {{ receiver }}{% if func.arguments().len() > 0 %}, {% endif %}{% endif %}
+    {%- for arg in func.arguments() %}
+        {{- arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.type_()|type_rs }}{% if !loop.last %}, {% endif %}
+    {%- endfor -%}
+    {%- if func.is_cancellable() %}{% if !receiver.is_empty() || func.arguments().len() > 0 %}, {% endif %}token: uniffi::CancellationToken{% endif -%}
+)
+{%- match func.throws() %}
+{%- when Some with (e) %} -> std::result::Result<{{ return_type }}, {{ e }}>
+{%- when None %} -> {{ return_type }}
+{%- endmatch %} {
+    {{ callee }}(
+        {%- if !receiver.is_empty() %}ptr{% if func.arguments().len() > 0 %}, {% endif %}{% endif %}
+        {%- for arg in func.arguments() %}{{ arg.name() }}{% if !loop.last %}, {% endif %}{% endfor -%}
+        {%- if func.is_cancellable() %}{% if !receiver.is_empty() || func.arguments().len() > 0 %}, {% endif %}token{% endif -%}
+    ).await{% if func.throws().is_some() %}.map_err(Into::into){% endif %} // Does the Rust implementation match `{{ declaration }}` in the UDL?
+}
+{%- endmacro -%}
+
+{#-
+// Like `udl_check_fn`, for a `[Borrowed]` method, which returns a reference into the object
+// that it's called on: `borrowed` is the type of the data, `str` or `[u8]`.
+-#}
+{%- macro udl_check_borrowed_fn(meth, object, callee, borrowed, declaration) %}
+#[allow(non_snake_case, clippy::too_many_arguments, clippy::ptr_arg, clippy::needless_lifetimes)]
+fn uniffi_check_{{ meth.ffi_func().name() }}<'a>(
+    ptr: &'a {{ object }}
+    {%- for arg in meth.arguments() %}, {{ arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.type_()|type_rs }}{% endfor -%}
+) -> &'a {{ borrowed }} {
+    {{ callee }}(ptr{% for arg in meth.arguments() %}, {{ arg.name() }}{% endfor %}) // Does the Rust implementation match `{{ declaration }}` in the UDL?
+}
 {%- endmacro -%}
 
 {%- macro _arg_list_rs_call(func) %}
     {%- for arg in func.full_arguments() %}
+        {%- call _arg_rs_call(func, arg) %}
+        {%- if !loop.last %}, {% endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{%- macro _arg_rs_call(func, arg) %}
         match {{- arg.type_()|ffi_converter }}::try_lift({{ arg.name() }}) {
         {%- if arg.by_ref() %}
             Ok(ref val) => val,
@@ -17,22 +96,71 @@
 
         {# If this function returns an error, we attempt to downcast errors doing arg
             conversions to this error. If the downcast fails or the function doesn't
-            return an error, we just panic.
+            return an error, we give up on the call, which reports strings that aren't
+            valid utf8 as such and panics otherwise.
         #}
         {%- match func.throws() -%}
         {% when Some with (e) %}
             Err(err) => {
                 match err.downcast::<{{ e }}>() {
                     Ok(actual_error) => return Err({{ func.throws_type().unwrap()|ffi_converter }}::lower(actual_error)),
-                    Err(ohno) => panic!("Failed to convert arg '{}': {}", "{{ arg.name() }}", ohno),
+                    Err(ohno) => uniffi::lift_arg_failed("{{ arg.name() }}", ohno),
                 }
             }
         {% else %}
-            Err(err) => panic!("Failed to convert arg '{}': {}", "{{ arg.name() }}", err),
+            Err(err) => uniffi::lift_arg_failed("{{ arg.name() }}", err),
         {% endmatch %}
         }
+{%- endmacro -%}
+
+{#-
+// Lift an argument into a value of its own, rather than a temporary that the call borrows, for
+// the future of an `[Async]` call to keep until it's done.
+-#}
+{%- macro _arg_rs_lift(func, arg) %}
+        match {{- arg.type_()|ffi_converter }}::try_lift({{ arg.name() }}) {
+            Ok(val) => val,
+        {%- match func.throws() -%}
+        {% when Some with (e) %}
+            Err(err) => {
+                match err.downcast::<{{ e }}>() {
+                    Ok(actual_error) => return Err({{ func.throws_type().unwrap()|ffi_converter }}::lower(actual_error)),
+                    Err(ohno) => uniffi::lift_arg_failed("{{ arg.name() }}", ohno),
+                }
+            }
+        {% else %}
+            Err(err) => uniffi::lift_arg_failed("{{ arg.name() }}", err),
+        {% endmatch %}
+        }
+{%- endmacro -%}
+
+{#-
+// Call a method on an object that's kept behind a `RwLock`, taking a read or write lock
+// on the receiver depending on whether the method takes `&self` or `&mut self`. In debug
+// builds, `uniffi` reports the deadlock rather than waiting if the lock will never be free.
+-#}
+{%- macro _locked_method_rs_call(obj, meth) -%}
+uniffi_check_{{ meth.ffi_func().name() }}(
+    {%- for arg in meth.full_arguments() %}
+        {%- if loop.first %}
+            {%- if meth.takes_self_by_mut() -%}
+            &mut *uniffi::write_object(&*({% call _arg_rs_call(meth, arg) %}), "{{ obj.name() }}")
+            {%- else -%}
+            &*uniffi::read_object(&*({% call _arg_rs_call(meth, arg) %}), "{{ obj.name() }}")
+            {%- endif %}
+        {%- else %}
+            {%- call _arg_rs_call(meth, arg) %}
+        {%- endif %}
         {%- if !loop.last %}, {% endif %}
-    {%- endfor %}
+    {%- endfor %})
+{%- endmacro -%}
+
+{%- macro _method_rs_call(obj, meth) -%}
+{%- if obj.has_mut_methods() -%}
+{% call _locked_method_rs_call(obj, meth) %}
+{%- else -%}
+{% call to_rs_call(meth) %}
+{%- endif -%}
 {%- endmacro -%}
 
 {#-
@@ -55,6 +183,17 @@
     {%- endif %}
 {%- endmacro -%}
 
+{#-
+// The type that a callback interface method returns, or that its `uniffi::CallbackFuture`
+// holds, for `[Async]` methods.
+-#}
+{%- macro callback_return_type(meth) %}
+    {%- match meth.throws_type() %}
+    {%- when Some with (e) %}Result<{% match meth.return_type() %}{% when Some with (return_type) %}{{ return_type|type_rs }}{% when None %}(){% endmatch %}, {{ e|type_rs }}>
+    {%- when None %}{% match meth.return_type() %}{% when Some with (return_type) %}{{ return_type|type_rs }}{% when None %}(){% endmatch %}
+    {%- endmatch %}
+{%- endmacro -%}
+
 {% macro return_signature(func) %}{% match func.ffi_func().return_type() %}{% when Some with (return_type) %} -> {% call return_type_func(func) %}{%- else -%}{%- endmatch -%}{%- endmacro -%}
 
 {% macro return_type_func(func) %}{% match func.ffi_func().return_type() %}{% when Some with (return_type) %}{{ return_type|type_ffi }}{%- else -%}(){%- endmatch -%}{%- endmacro -%}
@@ -62,60 +201,182 @@
 {% macro ret(func) %}{% match func.return_type() %}{% when Some with (return_type) %}{{ return_type|ffi_converter }}::lower(_retval){% else %}_retval{% endmatch %}{% endmacro %}
 
 {% macro construct(obj, cons) %}
-    {{- obj.name() }}::{% call to_rs_call(cons) -%}
+    {%- call to_rs_call(cons) -%}
 {% endmacro %}
 
+{% macro wrap_new(obj) %}{% if obj.has_mut_methods() %}std::sync::RwLock::new(_new){% else %}_new{% endif %}{% endmacro %}
+
 {% macro to_rs_constructor_call(obj, cons) %}
 {% match cons.throws_type() %}
 {% when Some with (e) %}
     uniffi::call_with_result(call_status, || {
+        uniffi::check_not_shut_down();
         let _new = {% call construct(obj, cons) %}.map_err(Into::into).map_err({{ e|ffi_converter }}::lower)?;
-        let _arc = std::sync::Arc::new(_new);
+        let _arc = std::sync::Arc::new({% call wrap_new(obj) %});
         Ok({{ obj.type_()|ffi_converter }}::lower(_arc))
     })
 {% else %}
     uniffi::call_with_output(call_status, || {
+        uniffi::check_not_shut_down();
         let _new = {% call construct(obj, cons) %};
-        let _arc = std::sync::Arc::new(_new);
+        let _arc = std::sync::Arc::new({% call wrap_new(obj) %});
         {{ obj.type_()|ffi_converter }}::lower(_arc)
     })
 {% endmatch %}
 {% endmacro %}
 
+{#-
+// A `[NoCopy]` method's bytes are handed over in a `RustBuffer` of their own, rather than
+// serialized into a new one, for the bindings to view in place until they free it.
+-#}
 {% macro to_rs_method_call(obj, meth) -%}
 {% match meth.throws_type() -%}
 {% when Some with (e) -%}
 uniffi::call_with_result(call_status, || {
-    let _retval =  {{ obj.name() }}::{% call to_rs_call(meth) %}.map_err(Into::into).map_err({{ e|ffi_converter }}::lower)?;
+    uniffi::check_not_shut_down();
+    let _retval =  {% call _method_rs_call(obj, meth) %}.map_err(Into::into).map_err({{ e|ffi_converter }}::lower)?;
+    {%- if meth.is_no_copy() %}
+    Ok(uniffi::RustBuffer::from_vec(_retval))
+    {%- else %}
     Ok({% call ret(meth) %})
+    {%- endif %}
 })
 {% else %}
 uniffi::call_with_output(call_status, || {
+    uniffi::check_not_shut_down();
     {% match meth.return_type() -%}
     {% when Some with (return_type) -%}
-    let retval = {{ obj.name() }}::{% call to_rs_call(meth) %};
+    let retval = {% call _method_rs_call(obj, meth) %};
+    {%- if meth.is_no_copy() %}
+    uniffi::RustBuffer::from_vec(retval)
+    {%- else %}
     {{ return_type|ffi_converter }}::lower(retval)
+    {%- endif %}
     {% else -%}
-    {{ obj.name() }}::{% call to_rs_call(meth) %}
+    {% call _method_rs_call(obj, meth) %}
     {% endmatch -%}
 })
 {% endmatch -%}
 {% endmacro -%}
 
+{#-
+// Call a `[Borrowed]` method, and hand out a view of the data that it returns, which keeps
+// the object alive for as long as the view is. Such methods can't throw, or take `self` by
+// `Arc` or `&mut`, so the object's `Arc` holds the struct itself.
+-#}
+{% macro to_rs_borrowed_method_call(obj, meth) -%}
+uniffi::call_with_output(call_status, || {
+    uniffi::check_not_shut_down();
+    {%- for arg in meth.full_arguments() %}{% if loop.first %}
+    let _owner = {% call _arg_rs_call(meth, arg) %};
+    {%- endif %}{% endfor %}
+    {%- match meth.return_type() %}
+    {%- when Some with (return_type) %}
+    let _view = {{ meth.borrowed_type().unwrap()|borrowed_view_rs }}::new(_owner, |owner| {
+        uniffi_check_{{ meth.ffi_func().name() }}(owner
+        {%- for arg in meth.arguments() %}, {% call _arg_rs_call(meth, arg) %}{% endfor %})
+    });
+    {{ return_type|ffi_converter }}::lower(std::sync::Arc::new(_view))
+    {%- when None %}
+    {%- endmatch %}
+})
+{%- endmacro -%}
+
+{#-
+// Build the variant of an error that `[ErrorFrom]` converts `err` into, with the error's
+// message as the variant's field if it has one.
+-#}
+{%- macro error_from_variant(e, variant) %}
+        {%- match variant.fields().first() %}
+        {%- when Some with (field) %}
+        {{ e.name() }}::{{ variant.name() }} { {{ field.name() }}: err.to_string() }
+        {%- when None %}
+        let _ = err;
+        {{ e.name() }}::{{ variant.name() }}
+        {%- endmatch %}
+{%- endmacro -%}
+
+{#-
+// The arguments of a `[Batchable]` or `[Invokable]` function, once they've been read from the
+// batch or the invoked call.
+-#}
+{%- macro batch_args(func) %}
+    {%- for arg in func.arguments() %}{% if arg.by_ref() %}&{% endif %}{{ arg.name() }}{% if !loop.last %}, {% endif %}{% endfor %}
+{%- endmacro -%}
+
+{#- Functions declared in a nested namespace live in the matching Rust module. #}
+{%- macro _module_path_rs(func) -%}
+{%- for module in func.module_path() %}{{ module }}::{% endfor -%}
+{%- endmacro -%}
+
+{%- macro _async_rs_result(func) %}
+        {%- match func.throws_type() %}
+        {%- when Some with (e) %}match uniffi_check_{{ func.ffi_func().name() }}({% call _async_args_rs(func) %}).await {
+            Ok(val) => val,
+            Err(err) => return Err({{ e|ffi_converter }}::lower(err)),
+        }
+        {%- when None %}uniffi_check_{{ func.ffi_func().name() }}({% call _async_args_rs(func) %}).await
+        {%- endmatch %}
+{%- endmacro -%}
+
+{%- macro _async_args_rs(func) %}
+    {%- for arg in func.full_arguments() %}{% if arg.by_ref() %}&{% endif %}{{ arg.name() }}{% if !loop.last %}, {% endif %}{% endfor %}
+    {%- if func.is_cancellable() %}{% if func.full_arguments().len() > 0 %}, {% endif %}uniffi_token{% endif %}
+{%- endmacro -%}
+
+{#-
+// Start an `[Async]` function or method: lift its arguments, which fails the call straight away
+// if they can't be, and then start its future on the executor, which completes the call through
+// `uniffi_completion` with the value that it returns, written into a buffer, or its error. The
+// future of a `[Cancellable]` one is given the call's token.
+-#}
+{% macro to_rs_async_call(func) %}
+{%- if func.throws().is_some() %}
+uniffi::call_with_result(call_status, || {
+{%- else %}
+uniffi::call_with_output(call_status, || {
+{%- endif %}
+    uniffi::check_not_shut_down();
+    {%- for arg in func.full_arguments() %}
+    let {{ arg.name() }} = {% call _arg_rs_lift(func, arg) %};
+    {%- endfor %}
+    {%- if func.is_cancellable() %}
+    uniffi::spawn_cancellable_async_call(&UNIFFI_ASYNC_CALLS, uniffi_completion, move |uniffi_token| async move {
+    {%- else %}
+    uniffi::spawn_async_call(&UNIFFI_ASYNC_CALLS, uniffi_completion, async move {
+    {%- endif %}
+        {%- match func.return_type() %}
+        {%- when Some with (return_type) %}
+        let _retval = {% call _async_rs_result(func) %};
+        let mut _buf = Vec::new();
+        {{ return_type|ffi_converter }}::write(_retval, &mut _buf);
+        Ok::<_, uniffi::RustBuffer>(uniffi::RustBuffer::from_vec(_buf))
+        {%- when None %}
+        {% call _async_rs_result(func) %};
+        Ok::<_, uniffi::RustBuffer>(uniffi::RustBuffer::new())
+        {%- endmatch %}
+    });
+    {%- if func.throws().is_some() %}
+    Ok(())
+    {%- endif %}
+})
+{% endmacro %}
+
 {% macro to_rs_function_call(func) %}
 {% match func.throws_type() %}
 {% when Some with (e) %}
 uniffi::call_with_result(call_status, || {
+    uniffi::check_not_shut_down();
     let _retval = {% call to_rs_call(func) %}.map_err(Into::into).map_err({{ e|ffi_converter }}::lower)?;
     Ok({% call ret(func) %})
 })
 {% else %}
 uniffi::call_with_output(call_status, || {
+    uniffi::check_not_shut_down();
     {% match func.return_type() -%}
     {% when Some with (return_type) -%}
     {{ return_type|ffi_converter }}::lower({% call to_rs_call(func) %})
     {% else -%}
-    {% if func.full_arguments().is_empty() %}#[allow(clippy::redundant_closure)]{% endif %}
     {% call to_rs_call(func) %}
     {% endmatch -%}
 })
@@ -10,11 +10,25 @@ uniffi::assert_compatible_version!("{{ uniffi_version }}"); // Please check that
 
 {% include "RustBuffer.rs" %}
 
+{% include "ComponentInfo.rs" %}
+
 // Error definitions, corresponding to `error` in the UDL.
 {% for e in ci.iter_error_definitions() %}
 {% include "ErrorTemplate.rs" %}
 {% endfor %}
 
+{%- if ci.has_services() %}
+// The states of `[Service]` objects, which are declared by uniffi itself and implemented in the
+// `uniffi` crate.
+pub use uniffi::ServiceState;
+{% endif %}
+
+{%- if ci.has_scopes() %}
+// The `Scope` object that `[Scoped]` functions and methods take, which is declared by uniffi
+// itself and implemented in the `uniffi` crate.
+pub use uniffi::Scope;
+{% endif %}
+
 // Enum defitions, corresponding to `enum` in UDL.
 {% for e in ci.iter_enum_definitions() %}
 {% include "EnumTemplate.rs" %}
@@ -30,11 +44,68 @@ uniffi::assert_compatible_version!("{{ uniffi_version }}"); // Please check that
 {% include "TopLevelFunctionTemplate.rs" %}
 {% endfor -%}
 
+{%- match ci.ffi_batch() %}
+{%- when Some with (batch) %}
+// Batched calls to `[Batchable]` functions.
+{% include "BatchTemplate.rs" %}
+{%- when None %}
+{%- endmatch %}
+
+{%- match ci.ffi_invoke() %}
+{%- when Some with (invoke) %}
+{%- let invoke_signatures = ci.ffi_invoke_signatures().unwrap() %}
+// Calls to `[Invokable]` functions by name.
+{% include "InvokeTemplate.rs" %}
+{%- when None %}
+{%- endmatch %}
+
 // Object definitions, corresponding to UDL `interface` definitions.
 {% for obj in ci.iter_object_definitions() %}
 {% include "ObjectTemplate.rs" %}
 {% endfor %}
 
+{%- match ci.ffi_callback_future_complete() %}
+{%- when Some with (complete) %}
+// The bindings call this once an `[Async]` callback interface method has finished, to complete
+// the future that it returned to Rust.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ complete.name() }}(handle: u64, code: i8, result: uniffi::RustBuffer, call_status: &mut uniffi::RustCallStatus) {
+    uniffi::call_with_output(call_status, || uniffi::complete_callback_future(handle, code, result))
+}
+{%- when None %}
+{%- endmatch %}
+{%- match ci.ffi_async_init() %}
+{%- when Some with (init) %}
+
+// The completion and unfinished calls of this component's `[Async]` functions and methods, kept
+// apart from those of any other component in the same library, whose bindings number their
+// calls with the same handles.
+#[doc(hidden)]
+static UNIFFI_ASYNC_CALLS: uniffi::AsyncCalls = uniffi::AsyncCalls::new();
+
+// The bindings call this before they make the first call to an `[Async]` function or method,
+// with the function that completes those calls once their futures have finished.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ init.name() }}(completion: uniffi::AsyncCompletion, call_status: &mut uniffi::RustCallStatus) {
+    uniffi::call_with_output(call_status, || uniffi::init_async_completion(&UNIFFI_ASYNC_CALLS, completion))
+}
+{%- when None %}
+{%- endmatch %}
+{%- match ci.ffi_async_cancel() %}
+{%- when Some with (cancel) %}
+
+// The bindings call this when the coroutine or task waiting for a call to an `[Async]` function
+// or method is cancelled, with the handle that the call was made with.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ cancel.name() }}(handle: u64, call_status: &mut uniffi::RustCallStatus) {
+    uniffi::call_with_output(call_status, || uniffi::cancel_async_call(&UNIFFI_ASYNC_CALLS, handle))
+}
+{%- when None %}
+{%- endmatch %}
+
 // Callback Interface definitions, corresponding to UDL `callback interface` definitions.
 {% for cbi in ci.iter_callback_interface_definitions() %}
 {% include "CallbackInterfaceTemplate.rs" %}
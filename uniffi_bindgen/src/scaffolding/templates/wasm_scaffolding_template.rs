@@ -0,0 +1,87 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+//
+// The WebAssembly scaffolding for the `{{ ci.namespace() }}` component, which exports its functions
+// and objects to JavaScript with `wasm_bindgen`, rather than as `extern "C"` functions. The crate
+// has to depend on `wasm-bindgen` itself.
+{% import "macros.rs" as rs %}
+
+// Check for compatibility between `uniffi` and `uniffi_bindgen` versions.
+// Note that we have an error message on the same line as the assertion.
+// This is important, because if the assertion fails, the compiler only
+// seems to show that single line as context for the user.
+uniffi::assert_compatible_version!("{{ uniffi_version }}"); // Please check that you depend on version {{ uniffi_version }} of the `uniffi` crate.
+
+// Top level functions, corresponding to UDL `namespace` functions.
+{%- for func in ci.iter_function_definitions() %}
+{% call rs::udl_check_fn(func, "", func|function_path_rs, func.return_type()|return_type_rs, self.udl_declaration("function", func.qualified_name())) %}
+{% endfor %}
+
+// Object definitions, corresponding to UDL `interface` definitions.
+{%- for obj in ci.iter_object_definitions() %}
+{%- let inner = obj.name()|object_inner_rs %}
+
+#[doc(hidden)]
+pub type {{ inner }} = {% if obj.is_trait() %}dyn {% endif %}{{ obj.name() }};
+{%- for cons in obj.constructors() %}
+{% call rs::udl_check_fn(cons, "", format!("{}::{}", obj.name(), cons.name()), obj.name(), self.udl_declaration("constructor", format!("{}.{}", obj.name(), cons.name()))) %}
+{%- endfor %}
+{%- for meth in obj.methods() %}
+{%- let callee = format!("{}::{}", obj.name(), meth.name()) %}
+{%- let declaration = self.udl_declaration("method", format!("{}.{}", obj.name(), meth.name())) %}
+{%- if meth.takes_self_by_arc() %}
+{% call rs::udl_check_fn(meth, format!("std::sync::Arc<{}>", inner), callee, meth.return_type()|return_type_rs, declaration) %}
+{%- else if obj.is_trait() %}
+{% call rs::udl_check_fn(meth, format!("&dyn {}", obj.name()), callee, meth.return_type()|return_type_rs, declaration) %}
+{%- else %}
+{% call rs::udl_check_fn(meth, format!("&{}", obj.name()), callee, meth.return_type()|return_type_rs, declaration) %}
+{%- endif %}
+{%- endfor %}
+{% endfor %}
+
+// The exports, which call the Rust implementation through the functions above that check its
+// signatures against the UDL.
+#[allow(non_snake_case, clippy::too_many_arguments, clippy::unused_unit, clippy::let_unit_value)]
+mod uniffi_wasm {
+    use wasm_bindgen::prelude::*;
+    {%- for func in ci.iter_function_definitions() %}
+
+    #[wasm_bindgen(js_name = "{{ func.name()|js_name }}")]
+    pub fn {{ func.name() }}({{ func.arguments()|arg_list_wasm }}) -> {{ func.return_type()|return_type_wasm(func.throws()) }} {
+        let ret = super::uniffi_check_{{ func.ffi_func().name() }}({{ func.arguments()|arg_values_wasm }}){{ func.throws()|map_err_wasm }};
+        {{ func.return_type()|return_value_wasm(func.throws()) }}
+    }
+    {%- endfor %}
+    {%- for obj in ci.iter_object_definitions() %}
+    {%- let class = obj.name()|wasm_class_rs %}
+
+    #[wasm_bindgen(js_name = "{{ obj.name() }}")]
+    pub struct {{ class }}(std::sync::Arc<super::{{ obj.name()|object_inner_rs }}>);
+
+    #[wasm_bindgen(js_class = "{{ obj.name() }}")]
+    impl {{ class }} {
+        {%- for cons in obj.constructors() %}
+
+        {%- if cons.is_primary_constructor() %}
+        #[wasm_bindgen(constructor)]
+        {%- else %}
+        #[wasm_bindgen(js_name = "{{ cons.name()|js_name }}")]
+        {%- endif %}
+        pub fn {{ cons.name() }}({{ cons.arguments()|arg_list_wasm }}) -> {% if cons.throws().is_some() %}Result<Self, JsError>{% else %}Self{% endif %} {
+            let obj = super::uniffi_check_{{ cons.ffi_func().name() }}({{ cons.arguments()|arg_values_wasm }}){{ cons.throws()|map_err_wasm }};
+            {% if cons.throws().is_some() %}Ok(Self(std::sync::Arc::new(obj))){% else %}Self(std::sync::Arc::new(obj)){% endif %}
+        }
+        {%- endfor %}
+        {%- for meth in obj.methods() %}
+
+        #[wasm_bindgen(js_name = "{{ meth.name()|js_name }}")]
+        pub fn {{ meth.name() }}(&self{% if !meth.arguments().is_empty() %}, {% endif %}{{ meth.arguments()|arg_list_wasm }}) -> {{ meth.return_type()|return_type_wasm(meth.throws()) }} {
+            let ret = super::uniffi_check_{{ meth.ffi_func().name() }}(
+                {%- if meth.takes_self_by_arc() %}std::sync::Arc::clone(&self.0){% else %}&self.0{% endif %}
+                {%- if !meth.arguments().is_empty() %}, {% endif %}{{ meth.arguments()|arg_values_wasm }}){{ meth.throws()|map_err_wasm }};
+            {{ meth.return_type()|return_value_wasm(meth.throws()) }}
+        }
+        {%- endfor %}
+    }
+    {%- endfor %}
+}
@@ -0,0 +1,323 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # WebAssembly scaffolding
+//!
+//! With `uniffi-bindgen scaffolding --target wasm`, or `uniffi_build::generate_wasm_scaffolding`,
+//! the scaffolding exports the component to JavaScript with `wasm_bindgen`, rather than as
+//! `extern "C"` functions that pass pointers and `RustBuffer`s around. It's `include!`d into the
+//! component's crate in the same way, and the crate depends on `wasm-bindgen` itself, so that
+//! building it for `wasm32-unknown-unknown` and running `wasm-bindgen` on the result gives a
+//! JavaScript module for the component. There are no foreign-language bindings to generate.
+//!
+//! It only supports the part of the component interface that `wasm_bindgen` can convert by
+//! itself: functions and objects, whose arguments and return values are numbers, booleans,
+//! strings, optionals of those, sequences of numbers, or objects. Objects are JavaScript classes
+//! that hold an `Arc` of the Rust struct, and errors are thrown as JavaScript `Error`s with the
+//! Rust error's message. Generating it for a component that uses anything else fails, listing
+//! what isn't supported.
+
+use anyhow::{bail, Result};
+use askama::Template;
+
+use crate::interface::*;
+use crate::lockfile::{exported_callables, type_decl, ExportedCallable};
+
+#[derive(Template)]
+#[template(syntax = "rs", escape = "none", path = "wasm_scaffolding_template.rs")]
+pub struct WasmScaffolding<'a> {
+    ci: &'a ComponentInterface,
+    uniffi_version: &'static str,
+    callables: Vec<ExportedCallable>,
+}
+
+impl<'a> WasmScaffolding<'a> {
+    pub fn new(ci: &'a ComponentInterface) -> Self {
+        Self {
+            ci,
+            uniffi_version: crate::BINDGEN_VERSION,
+            callables: exported_callables(ci),
+        }
+    }
+
+    fn udl_declaration(&self, kind: &str, qualified_name: String) -> String {
+        super::udl_declaration(&self.callables, kind, qualified_name)
+    }
+}
+
+fn is_number(type_: &Type) -> bool {
+    matches!(
+        type_,
+        Type::UInt8
+            | Type::Int8
+            | Type::UInt16
+            | Type::Int16
+            | Type::UInt32
+            | Type::Int32
+            | Type::UInt64
+            | Type::Int64
+            | Type::Float32
+            | Type::Float64
+    )
+}
+
+// Whether `wasm_bindgen` can convert values of this type by itself. Objects are wrapped in a
+// class of their own, so they're only supported as arguments and return values, not inside others.
+fn is_convertible(type_: &Type) -> bool {
+    match type_ {
+        Type::Boolean | Type::String => true,
+        Type::Optional(t) => is_number(t) || matches!(**t, Type::Boolean | Type::String),
+        Type::Sequence(t) => is_number(t),
+        t => is_number(t),
+    }
+}
+
+fn is_supported(type_: &Type) -> bool {
+    matches!(type_, Type::Object(_)) || is_convertible(type_)
+}
+
+/// What the WebAssembly scaffolding can generate, apart from the types that it can't convert.
+pub fn capabilities() -> Capabilities {
+    Capabilities::all().without(&[
+        Feature::NestedNamespaces,
+        Feature::Invokable,
+        Feature::Batchable,
+        Feature::Scoped,
+        Feature::Async,
+        Feature::MutMethods,
+        Feature::Borrowed,
+        Feature::Paginated,
+        Feature::Clone,
+        Feature::Closeable,
+        Feature::WeakRefs,
+        Feature::ReleaseCallbacks,
+        Feature::OpaqueTokens,
+        Feature::ForeignExtensions,
+        Feature::Observable,
+        Feature::Events,
+        Feature::Services,
+        Feature::Streams,
+        Feature::Traits,
+        Feature::CallbackInterfaces,
+    ])
+}
+
+// The things in the component that the WebAssembly scaffolding doesn't support yet, to report
+// them all at once.
+fn unsupported_features(ci: &ComponentInterface) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut check_callable = |what: String, args: Vec<&Argument>, ret: Option<&Type>| {
+        for arg in args {
+            if !is_supported(&arg.type_()) {
+                problems.push(format!(
+                    "argument `{}` of {}, of type {}",
+                    arg.name(),
+                    what,
+                    type_decl(&arg.type_())
+                ));
+            } else if arg.default_value().is_some() {
+                problems.push(format!("the default value of `{}` in {}", arg.name(), what));
+            }
+        }
+        if let Some(ret) = ret {
+            if !is_supported(ret) {
+                problems.push(format!("the return type of {}, {}", what, type_decl(ret)));
+            }
+        }
+    };
+    for func in ci.iter_function_definitions() {
+        let what = format!("function `{}`", func.qualified_name());
+        check_callable(what, func.arguments(), func.return_type());
+    }
+    for obj in ci.iter_object_definitions() {
+        for cons in obj.constructors() {
+            let what = format!("constructor `{}.{}`", obj.name(), cons.name());
+            check_callable(what, cons.arguments(), None);
+        }
+        for meth in obj.methods() {
+            let what = format!("method `{}.{}`", obj.name(), meth.name());
+            check_callable(what, meth.arguments(), meth.return_type());
+        }
+    }
+    problems.extend(ci.unsupported_features(&capabilities()));
+    problems
+}
+
+/// Generate the WebAssembly scaffolding for the component, as Rust source.
+pub fn generate_wasm_scaffolding(ci: &ComponentInterface) -> Result<String> {
+    let problems = unsupported_features(ci);
+    if !problems.is_empty() {
+        bail!(
+            "The WebAssembly scaffolding doesn't support:\n  {}",
+            problems.join("\n  ")
+        );
+    }
+    WasmScaffolding::new(ci)
+        .render()
+        .map_err(|_| anyhow::anyhow!("failed to render WebAssembly scaffolding"))
+}
+
+mod filters {
+    use super::*;
+    use crate::scaffolding::filters as rs;
+    use heck::MixedCase;
+    use std::fmt;
+
+    pub use rs::{function_path_rs, object_inner_rs, return_type_rs, type_rs};
+
+    /// The name that JavaScript code calls a function or method by.
+    pub fn js_name(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(nm.to_string().to_mixed_case())
+    }
+
+    /// The Rust name of the class that wraps an object.
+    pub fn wasm_class_rs(name: &str) -> Result<String, askama::Error> {
+        Ok(format!("Wasm{}", name))
+    }
+
+    // The Rust type that a `#[wasm_bindgen]` function takes for an argument. Objects are taken
+    // by reference, because JavaScript can't use them any more once they've been moved into Rust.
+    fn arg_type_wasm(arg: &Argument) -> Result<String, askama::Error> {
+        match arg.type_() {
+            Type::Object(name) => Ok(format!("&{}", wasm_class_rs(&name)?)),
+            t => type_rs(&t),
+        }
+    }
+
+    // How to pass an argument on to the function that checks the UDL signature.
+    fn arg_value_wasm(arg: &Argument) -> Result<String, askama::Error> {
+        let name = arg.name().to_string();
+        Ok(match (arg.type_(), arg.by_ref()) {
+            (Type::Object(_), true) => format!("&{}.0", name),
+            (Type::Object(_), false) => format!("std::sync::Arc::clone(&{}.0)", name),
+            (_, true) => format!("&{}", name),
+            (_, false) => name,
+        })
+    }
+
+    /// The arguments of a `#[wasm_bindgen]` function or method.
+    pub fn arg_list_wasm(args: &[&Argument]) -> Result<String, askama::Error> {
+        let args: Result<Vec<String>, askama::Error> = args
+            .iter()
+            .map(|arg| Ok(format!("{}: {}", arg.name(), arg_type_wasm(arg)?)))
+            .collect();
+        Ok(args?.join(", "))
+    }
+
+    /// The arguments to pass on to the function that checks the UDL signature.
+    pub fn arg_values_wasm(args: &[&Argument]) -> Result<String, askama::Error> {
+        let values: Result<Vec<String>, askama::Error> =
+            args.iter().map(|arg| arg_value_wasm(arg)).collect();
+        Ok(values?.join(", "))
+    }
+
+    /// Throw the error that the Rust implementation returns, if it can, as a JavaScript `Error`.
+    pub fn map_err_wasm(throws: &Option<&str>) -> Result<String, askama::Error> {
+        Ok(match throws {
+            Some(_) => ".map_err(|e| JsError::new(&e.to_string()))?".into(),
+            None => "".into(),
+        })
+    }
+
+    /// The Rust type that a `#[wasm_bindgen]` function or method returns.
+    pub fn return_type_wasm(
+        type_: &Option<&Type>,
+        throws: &Option<&str>,
+    ) -> Result<String, askama::Error> {
+        let ret = match type_ {
+            Some(Type::Object(name)) => wasm_class_rs(name)?,
+            Some(t) => type_rs(t)?,
+            None => "()".into(),
+        };
+        Ok(match throws {
+            Some(_) => format!("Result<{}, JsError>", ret),
+            None => ret,
+        })
+    }
+
+    /// Wrap a value returned by the Rust implementation for `wasm_bindgen`.
+    pub fn return_value_wasm(
+        type_: &Option<&Type>,
+        throws: &Option<&str>,
+    ) -> Result<String, askama::Error> {
+        let ret = match type_ {
+            Some(Type::Object(name)) => format!("{}(ret)", wasm_class_rs(name)?),
+            _ => "ret".into(),
+        };
+        Ok(match throws {
+            Some(_) => format!("Ok({})", ret),
+            None => ret,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_wasm_scaffolding() {
+        const UDL: &str = r#"
+            namespace geometry {
+                [Throws=GeometryError]
+                f64 gradient(Line line);
+                sequence<f64>? intercepts([ByRef] string label);
+            };
+            [Error]
+            enum GeometryError { "Vertical" };
+            interface Line {
+                constructor(f64 slope);
+                [Name=flat]
+                constructor();
+                Line rotated([ByRef] Line other);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let scaffolding = generate_wasm_scaffolding(&ci).unwrap();
+        assert!(!scaffolding.contains("#[no_mangle]"));
+        assert!(scaffolding.contains("#[wasm_bindgen(js_name = \"gradient\")]"));
+        assert!(scaffolding.contains("pub fn gradient(line: &WasmLine) -> Result<f64, JsError> {"));
+        assert!(scaffolding.contains(".map_err(|e| JsError::new(&e.to_string()))?;"));
+        assert!(
+            scaffolding.contains("(label: String) -> std::option::Option<std::vec::Vec<f64>> {")
+        );
+        assert!(scaffolding.contains("#[wasm_bindgen(js_name = \"Line\")]"));
+        assert!(
+            scaffolding.contains("pub struct WasmLine(std::sync::Arc<super::UniffiObjectLine>);")
+        );
+        assert!(scaffolding.contains("#[wasm_bindgen(constructor)]"));
+        assert!(scaffolding.contains("pub fn rotated(&self, other: &WasmLine) -> WasmLine {"));
+        assert!(scaffolding.contains("(&self.0, &other.0)"));
+        let func = ci.get_function_definition("intercepts").unwrap();
+        assert!(scaffolding.contains(&format!(
+            "super::uniffi_check_{}(&label)",
+            func.ffi_func().name()
+        )));
+    }
+
+    #[test]
+    fn test_wasm_unsupported_features() {
+        const UDL: &str = r#"
+            namespace geometry {
+                Point origin();
+                void draw(sequence<Line> lines, optional u32 width = 1);
+            };
+            dictionary Point { f64 x; f64 y; };
+            interface Line {};
+            callback interface Canvas {
+                void clear();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let err = generate_wasm_scaffolding(&ci).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The WebAssembly scaffolding doesn't support:
+  the return type of function `origin`, Point
+  argument `lines` of function `draw`, of type sequence<Line>
+  the default value of `width` in function `draw`
+  callback interface `Canvas`"
+        );
+    }
+}
@@ -0,0 +1,541 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Component statistics
+//!
+//! `uniffi-bindgen stats` reports how big a component's API is, how much code gets generated
+//! for it, and which of its types each backend can't handle yet:
+//!
+//! ```text
+//! Component `todolist`:
+//!   functions: 4
+//!   objects: 1 (1 constructor, 6 methods)
+//!   records: 1 (3 fields)
+//!   enums: 0 (0 variants)
+//!   errors: 1 (4 variants)
+//!   callback interfaces: 0 (0 methods)
+//!
+//! Generated lines of code, before formatting:
+//!   scaffolding: 702
+//!   kotlin: 1409
+//!   ruby: 516
+//!
+//! Unsupported types:
+//!   ruby: none
+//! ```
+//!
+//! The counts are of the component as the bindings see it, so they include the borrowed views
+//! and observer methods that uniffi adds. The lines of code are counted in the generated files
+//! as they're rendered, leaving out blank lines, which makes them an estimate; the formatters
+//! for each language will move things around somewhat.
+
+use std::fmt;
+
+use anyhow::Result;
+
+use crate::bindings::{TargetLanguage, OPTIONAL_FEATURES};
+use crate::golden::language_dir;
+use crate::interface::*;
+use crate::prune::{self, Unreferenced};
+
+/// Counts of what a component exposes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentStats {
+    pub namespace: String,
+    pub functions: usize,
+    pub objects: usize,
+    pub constructors: usize,
+    pub methods: usize,
+    pub records: usize,
+    pub record_fields: usize,
+    pub enums: usize,
+    pub enum_variants: usize,
+    pub errors: usize,
+    pub error_variants: usize,
+    pub callback_interfaces: usize,
+    pub callback_methods: usize,
+}
+
+impl ComponentStats {
+    pub fn new(ci: &ComponentInterface) -> Self {
+        let objects = ci.iter_object_definitions();
+        let records = ci.iter_record_definitions();
+        let enums = ci.iter_enum_definitions();
+        let errors = ci.iter_error_definitions();
+        let callback_interfaces = ci.iter_callback_interface_definitions();
+        ComponentStats {
+            namespace: ci.namespace().to_string(),
+            functions: ci.iter_function_definitions().len(),
+            objects: objects.len(),
+            constructors: objects.iter().map(|obj| obj.constructors().len()).sum(),
+            methods: objects.iter().map(|obj| obj.methods().len()).sum(),
+            records: records.len(),
+            record_fields: records.iter().map(|rec| rec.fields().len()).sum(),
+            enums: enums.len(),
+            enum_variants: enums.iter().map(|e| e.variants().len()).sum(),
+            errors: errors.len(),
+            error_variants: errors.iter().map(|e| e.variants().len()).sum(),
+            callback_interfaces: callback_interfaces.len(),
+            callback_methods: callback_interfaces
+                .iter()
+                .map(|cbi| cbi.methods().len())
+                .sum(),
+        }
+    }
+}
+
+/// An optional feature that a component uses but a backend doesn't support, along with the
+/// types that need it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsupported {
+    pub feature: &'static str,
+    pub types: Vec<String>,
+}
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({})",
+            self.feature.replace('_', " "),
+            self.types.join(", ")
+        )
+    }
+}
+
+// The names of the types in the component that need the given optional feature.
+fn types_needing(ci: &ComponentInterface, feature: &str) -> Vec<String> {
+    let mut names: Vec<String> = match feature {
+        // Not types as such, but the functions and methods that are `[Async]`.
+        "async_functions" => ci
+            .iter_function_definitions()
+            .iter()
+            .filter(|func| func.is_async())
+            .map(|func| func.qualified_name())
+            .chain(ci.iter_object_definitions().iter().flat_map(|obj| {
+                obj.methods()
+                    .into_iter()
+                    .filter(|meth| meth.is_async())
+                    .map(move |meth| format!("{}.{}", obj.name(), meth.name()))
+            }))
+            .collect(),
+        "callback_interfaces" => ci
+            .iter_callback_interface_definitions()
+            .iter()
+            .map(|cbi| cbi.name().to_string())
+            .collect(),
+        "external_types" => ci
+            .iter_types()
+            .iter()
+            .filter_map(|t| match t {
+                Type::External { name, .. } | Type::ExternalInterface { name, .. } => {
+                    Some(name.clone())
+                }
+                _ => None,
+            })
+            .collect(),
+        "wrapped_types" => ci
+            .iter_types()
+            .iter()
+            .filter_map(|t| match t {
+                Type::Wrapped { name, .. } => Some(name.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// The optional features that the component uses, but that the backend for `language` doesn't
+/// support, so that its bindings can't be generated or won't work.
+pub fn unsupported_types(
+    ci: &ComponentInterface,
+    language: TargetLanguage,
+) -> Result<Vec<Unsupported>> {
+    let mut unsupported = Vec::new();
+    for &feature in OPTIONAL_FEATURES.iter() {
+        if language.supports_feature(feature)? {
+            continue;
+        }
+        let types = types_needing(ci, feature);
+        if !types.is_empty() {
+            unsupported.push(Unsupported { feature, types });
+        }
+    }
+    Ok(unsupported)
+}
+
+/// The number of lines of code in some generated source, not counting blank lines.
+pub fn count_lines(source: &str) -> usize {
+    source
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .count()
+}
+
+/// The lines of code generated for some part of a component, in the scaffolding and in the
+/// bindings for each of the report's languages, or `None` where they didn't generate.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GeneratedLines {
+    pub scaffolding: usize,
+    pub bindings: Vec<Option<usize>>,
+}
+
+impl GeneratedLines {
+    // The lines that are generated for `self`, but not for `other`.
+    fn minus(&self, other: &GeneratedLines) -> GeneratedLines {
+        GeneratedLines {
+            scaffolding: self.scaffolding.saturating_sub(other.scaffolding),
+            bindings: self
+                .bindings
+                .iter()
+                .zip(other.bindings.iter())
+                .map(|(a, b)| Some(a.as_ref()?.saturating_sub(*b.as_ref()?)))
+                .collect(),
+        }
+    }
+}
+
+/// How much of the generated code each declaration accounts for: what would no longer be
+/// generated without it, which includes the types that nothing else uses. What several
+/// declarations share, and what's generated whatever the component declares, are counted
+/// separately.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclarationSizes {
+    pub languages: Vec<TargetLanguage>,
+    /// The declarations, from the one that accounts for the most scaffolding down.
+    pub declarations: Vec<(String, GeneratedLines)>,
+    pub shared: GeneratedLines,
+    pub always: GeneratedLines,
+}
+
+/// Break down the code generated for the component by declaration, where `measure` counts the
+/// lines generated for a component, in the bindings for each of the `languages`.
+pub fn declaration_sizes(
+    ci: &ComponentInterface,
+    languages: Vec<TargetLanguage>,
+    measure: impl Fn(&ComponentInterface) -> GeneratedLines,
+) -> Result<DeclarationSizes> {
+    let total = measure(ci);
+    let mut declarations = Vec::new();
+    for name in prune::declarations(ci) {
+        let lines = total.minus(&measure(&prune::without(ci, &name)?));
+        declarations.push((name, lines));
+    }
+    declarations.sort_by(|(_, a), (_, b)| b.scaffolding.cmp(&a.scaffolding));
+    let always = measure(&ci.subset(|_| false));
+    let shared = declarations
+        .iter()
+        .fold(total.minus(&always), |rest, (_, lines)| rest.minus(lines));
+    Ok(DeclarationSizes {
+        languages,
+        declarations,
+        shared,
+        always,
+    })
+}
+
+impl DeclarationSizes {
+    fn write_row(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        name: &str,
+        lines: &GeneratedLines,
+    ) -> fmt::Result {
+        write!(f, "\n  {}: scaffolding {}", name, lines.scaffolding)?;
+        for (language, lines) in self.languages.iter().zip(lines.bindings.iter()) {
+            match lines {
+                Some(lines) => write!(f, ", {} {}", language_dir(*language), lines)?,
+                None => write!(f, ", {} failed to generate", language_dir(*language))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for DeclarationSizes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Generated lines of code by declaration, with what only it uses:"
+        )?;
+        for (name, lines) in self.declarations.iter() {
+            self.write_row(f, name, lines)?;
+        }
+        self.write_row(f, "(shared by several)", &self.shared)?;
+        self.write_row(f, "(always generated)", &self.always)
+    }
+}
+
+/// What the stats report says about one language.
+#[derive(Debug, Clone)]
+pub struct LanguageStats {
+    pub language: TargetLanguage,
+    /// The lines of generated bindings, or why they couldn't be generated.
+    pub generated_lines: std::result::Result<usize, String>,
+    pub unsupported: Vec<Unsupported>,
+}
+
+/// The report printed by `uniffi-bindgen stats`.
+#[derive(Debug, Clone)]
+pub struct StatsReport {
+    pub component: ComponentStats,
+    pub scaffolding_lines: usize,
+    pub languages: Vec<LanguageStats>,
+    /// What was pruned from the component, when the report is for the part of it that some
+    /// roots reference.
+    pub unreferenced: Option<Unreferenced>,
+    pub by_declaration: Option<DeclarationSizes>,
+}
+
+// E.g. "1 constructor" or "6 methods".
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+impl fmt::Display for StatsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = &self.component;
+        writeln!(f, "Component `{}`:", c.namespace)?;
+        writeln!(f, "  functions: {}", c.functions)?;
+        writeln!(
+            f,
+            "  objects: {} ({}, {})",
+            c.objects,
+            plural(c.constructors, "constructor"),
+            plural(c.methods, "method")
+        )?;
+        writeln!(
+            f,
+            "  records: {} ({})",
+            c.records,
+            plural(c.record_fields, "field")
+        )?;
+        writeln!(
+            f,
+            "  enums: {} ({})",
+            c.enums,
+            plural(c.enum_variants, "variant")
+        )?;
+        writeln!(
+            f,
+            "  errors: {} ({})",
+            c.errors,
+            plural(c.error_variants, "variant")
+        )?;
+        writeln!(
+            f,
+            "  callback interfaces: {} ({})",
+            c.callback_interfaces,
+            plural(c.callback_methods, "method")
+        )?;
+        writeln!(f)?;
+        writeln!(f, "Generated lines of code, before formatting:")?;
+        writeln!(f, "  scaffolding: {}", self.scaffolding_lines)?;
+        for lang in self.languages.iter() {
+            match &lang.generated_lines {
+                Ok(lines) => writeln!(f, "  {}: {}", language_dir(lang.language), lines)?,
+                Err(e) => writeln!(
+                    f,
+                    "  {}: failed to generate: {}",
+                    language_dir(lang.language),
+                    e
+                )?,
+            }
+        }
+        writeln!(f)?;
+        write!(f, "Unsupported types:")?;
+        for lang in self.languages.iter() {
+            write!(f, "\n  {}: ", language_dir(lang.language))?;
+            if lang.unsupported.is_empty() {
+                write!(f, "none")?;
+            } else {
+                let features: Vec<String> =
+                    lang.unsupported.iter().map(|u| u.to_string()).collect();
+                write!(f, "{}", features.join("; "))?;
+            }
+        }
+        if let Some(unreferenced) = &self.unreferenced {
+            write!(f, "\n\n{}", unreferenced)?;
+        }
+        if let Some(by_declaration) = &self.by_declaration {
+            write!(f, "\n\n{}", by_declaration)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const UDL: &str = r#"
+        namespace shop {
+            void restock(Stock stock, Sku sku);
+            Basket new_basket();
+        };
+        [Wrapped]
+        typedef string Sku;
+        [External="warehouse"]
+        typedef extern Stock;
+        dictionary Item {
+            Sku sku;
+            u32 quantity;
+        };
+        enum PaymentKind { "Card", "Cash", "Voucher" };
+        [Error]
+        enum BasketError { "Empty", "OutOfStock" };
+        interface Basket {
+            constructor();
+            [Name=with_items]
+            constructor(sequence<Item> items);
+            void add(Item item);
+            [Throws=BasketError]
+            void check_out(PaymentKind kind, Listener listener);
+        };
+        callback interface Listener {
+            void on_paid();
+            void on_failed(string reason);
+        };
+    "#;
+
+    #[test]
+    fn test_component_stats() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert_eq!(
+            ComponentStats::new(&ci),
+            ComponentStats {
+                namespace: "shop".into(),
+                functions: 2,
+                objects: 1,
+                constructors: 2,
+                methods: 2,
+                records: 1,
+                record_fields: 2,
+                enums: 1,
+                enum_variants: 3,
+                errors: 1,
+                error_variants: 2,
+                callback_interfaces: 1,
+                callback_methods: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_unsupported_types() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let features = |language| -> Vec<String> {
+            unsupported_types(&ci, language)
+                .unwrap()
+                .iter()
+                .map(|u| u.to_string())
+                .collect()
+        };
+        assert!(features(TargetLanguage::Kotlin).is_empty());
+        assert_eq!(
+            features(TargetLanguage::Python),
+            vec!["callback interfaces (Listener)"]
+        );
+        assert_eq!(
+            features(TargetLanguage::Ruby),
+            vec![
+                "callback interfaces (Listener)",
+                "external types (Stock)",
+                "wrapped types (Sku)"
+            ]
+        );
+
+        let ci = ComponentInterface::from_webidl(
+            "namespace test { [Async] void sync(); }; interface Client { [Async] u32 fetch(); };",
+        )
+        .unwrap();
+        assert!(unsupported_types(&ci, TargetLanguage::Kotlin)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            unsupported_types(&ci, TargetLanguage::Ruby).unwrap()[0].to_string(),
+            "async functions (Client.fetch, sync)"
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let report = StatsReport {
+            component: ComponentStats::new(&ci),
+            scaffolding_lines: count_lines("fn a() {}\n\n  \nfn b() {}\n"),
+            languages: vec![
+                LanguageStats {
+                    language: TargetLanguage::Swift,
+                    generated_lines: Ok(120),
+                    unsupported: unsupported_types(&ci, TargetLanguage::Swift).unwrap(),
+                },
+                LanguageStats {
+                    language: TargetLanguage::Ruby,
+                    generated_lines: Err("no callback interfaces".into()),
+                    unsupported: Vec::new(),
+                },
+            ],
+            unreferenced: None,
+            by_declaration: None,
+        };
+        assert_eq!(
+            report.to_string(),
+            "\
+Component `shop`:
+  functions: 2
+  objects: 1 (2 constructors, 2 methods)
+  records: 1 (2 fields)
+  enums: 1 (3 variants)
+  errors: 1 (2 variants)
+  callback interfaces: 1 (2 methods)
+
+Generated lines of code, before formatting:
+  scaffolding: 2
+  swift: 120
+  ruby: failed to generate: no callback interfaces
+
+Unsupported types:
+  swift: none
+  ruby: none"
+        );
+    }
+
+    #[test]
+    fn test_declaration_sizes() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        // Stand-ins for the lines of scaffolding and bindings: a line for each declaration,
+        // and for each callable.
+        let measure = |ci: &ComponentInterface| GeneratedLines {
+            scaffolding: ci.iter_function_definitions().len()
+                + ci.iter_object_definitions().len()
+                + ci.iter_record_definitions().len()
+                + ci.iter_enum_definitions().len()
+                + ci.iter_error_definitions().len()
+                + ci.iter_callback_interface_definitions().len(),
+            bindings: vec![Some(crate::lockfile::exported_callables(ci).len())],
+        };
+        let sizes = declaration_sizes(&ci, vec![TargetLanguage::Swift], measure).unwrap();
+        // `Basket` is used by `new_basket` too, so it counts as shared. Nothing else uses
+        // its members' types, so they count towards it.
+        assert_eq!(
+            sizes.to_string(),
+            "\
+Generated lines of code by declaration, with what only it uses:
+  Basket: scaffolding 4, swift 4
+  restock: scaffolding 1, swift 1
+  new_basket: scaffolding 1, swift 1
+  (shared by several): scaffolding 1, swift 0
+  (always generated): scaffolding 0, swift 0"
+        );
+    }
+}
@@ -0,0 +1,264 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Strict mode
+//!
+//! `uniffi-bindgen check --strict` flags the parts of a component's public API that pass data
+//! around as bare strings, or as string-keyed maps. These are easy to reach for, but they leave
+//! the foreign-language code guessing at what values are valid, when an enum, a dictionary or
+//! a `[Wrapped]` typedef would spell it out in the type system.
+//!
+//! Each use is reported against the item it belongs to: a function (`greet`, or
+//! `sync.engine.start` in a nested namespace), a constructor or method (`Counter.get`),
+//! a callback interface method (`Listener.on_event`), a dictionary field (`Point.label`),
+//! or a field of an enum or error variant (`Shape.Named.name`). Some strings really are just
+//! strings, so uses can be allowed by listing them in the `[strict]` section of `uniffi.toml`:
+//!
+//! ```toml
+//! [strict]
+//! allow = ["greet", "Counter"]
+//! ```
+//!
+//! An entry allows the item it names along with everything inside it, so `Counter` above
+//! allows all the constructors and methods of that interface.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::interface::*;
+use crate::MergeWith;
+
+/// The `[strict]` section of `uniffi.toml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StrictConfig {
+    allow: Option<Vec<String>>,
+}
+
+impl StrictConfig {
+    /// The items that are allowed to use strings and maps.
+    pub fn allow(&self) -> Vec<String> {
+        self.allow.clone().unwrap_or_default()
+    }
+
+    fn is_allowed(&self, site: &str) -> bool {
+        self.allow.iter().flatten().any(|entry| {
+            site == entry
+                || (site.starts_with(entry.as_str()) && site[entry.len()..].starts_with('.'))
+        })
+    }
+}
+
+impl MergeWith for StrictConfig {
+    fn merge_with(&self, other: &Self) -> Self {
+        StrictConfig {
+            allow: self.allow.merge_with(&other.allow),
+        }
+    }
+}
+
+/// A use of a string or map in the public API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    site: String,
+    description: String,
+}
+
+impl Violation {
+    /// The item the use belongs to, e.g. `Counter.get`, as it would be written in the allowlist.
+    pub fn site(&self) -> &str {
+        &self.site
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.site, self.description)
+    }
+}
+
+/// Find every use of a bare string or a map in the public API that isn't allowed by the config.
+pub fn check_strict(ci: &ComponentInterface, config: &StrictConfig) -> Vec<Violation> {
+    let mut checker = Checker {
+        config,
+        violations: Vec::new(),
+    };
+    for func in ci.iter_function_definitions() {
+        checker.check_callable(&func.qualified_name(), func.arguments(), func.return_type());
+    }
+    // The views returned by `[Borrowed]` methods are part of uniffi, not the component's API.
+    for obj in ci
+        .iter_object_definitions()
+        .into_iter()
+        .filter(|obj| !obj.is_borrowed_view())
+    {
+        for cons in obj.constructors() {
+            let site = format!("{}.{}", obj.name(), cons.name());
+            checker.check_callable(&site, cons.arguments(), None);
+        }
+        for meth in obj.methods() {
+            let site = format!("{}.{}", obj.name(), meth.name());
+            checker.check_callable(&site, meth.arguments(), meth.return_type());
+        }
+    }
+    for cbi in ci.iter_callback_interface_definitions() {
+        for meth in cbi.methods() {
+            let site = format!("{}.{}", cbi.name(), meth.name());
+            checker.check_callable(&site, meth.arguments(), meth.return_type());
+        }
+    }
+    for rec in ci.iter_record_definitions() {
+        for field in rec.fields() {
+            let site = format!("{}.{}", rec.name(), field.name());
+            checker.check(&site, "field", &field.type_());
+        }
+    }
+    for e in ci.iter_enum_definitions() {
+        checker.check_variants(e.name(), e.variants());
+    }
+    // The message of a flat error is always a string, but that's not something the UDL
+    // author chose, so only the fields of rich errors are checked.
+    for e in ci.iter_error_definitions() {
+        if !e.is_flat() {
+            checker.check_variants(e.name(), e.variants());
+        }
+    }
+    checker.violations
+}
+
+struct Checker<'a> {
+    config: &'a StrictConfig,
+    violations: Vec<Violation>,
+}
+
+impl<'a> Checker<'a> {
+    fn check_callable(&mut self, site: &str, arguments: Vec<&Argument>, ret: Option<&Type>) {
+        for arg in arguments {
+            self.check(site, &format!("argument `{}`", arg.name()), &arg.type_());
+        }
+        if let Some(type_) = ret {
+            self.check(site, "return type", type_);
+        }
+    }
+
+    fn check_variants(&mut self, name: &str, variants: Vec<&Variant>) {
+        for variant in variants {
+            for field in variant.fields() {
+                let site = format!("{}.{}.{}", name, variant.name(), field.name());
+                self.check(&site, "field", &field.type_());
+            }
+        }
+    }
+
+    fn check(&mut self, site: &str, what: &str, type_: &Type) {
+        if self.config.is_allowed(site) {
+            return;
+        }
+        let problem = match stringly_typed(type_) {
+            Some(problem) => problem,
+            None => return,
+        };
+        self.violations.push(Violation {
+            site: site.to_string(),
+            description: format!("{} {}", what, problem),
+        });
+    }
+}
+
+// Describe how a type is stringly-typed, if it is. Strings inside optionals and sequences
+// count too, but a `[Wrapped]` typedef of a string has been given a type of its own.
+fn stringly_typed(type_: &Type) -> Option<&'static str> {
+    match type_ {
+        Type::String => Some("is a bare `string`; consider an enum or a `[Wrapped]` typedef"),
+        Type::Map(key, _) if **key == Type::String => {
+            Some("is a `record<DOMString, T>` map; consider a dictionary or a sequence of them")
+        }
+        Type::OrderedMap(_) => {
+            Some("is a `record<DOMString, T>` map; consider a dictionary or a sequence of them")
+        }
+        Type::Optional(inner) | Type::Sequence(inner) | Type::Map(_, inner) => {
+            stringly_typed(inner)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const UDL: &str = r#"
+        namespace test {
+            string greet(string name);
+            u32 add(u32 a, u32 b);
+        };
+        [Wrapped]
+        typedef string Email;
+        dictionary Contact {
+            Email email;
+            sequence<string>? nicknames;
+        };
+        [Enum]
+        interface Shape {
+            Named(string name);
+            Circle(f64 radius);
+        };
+        [Error]
+        enum Oops { "Bad" };
+        interface Counter {
+            constructor(record<DOMString, u32> initial);
+            u32 get();
+        };
+        callback interface Listener {
+            void on_event(string event);
+        };
+    "#;
+
+    fn sites(violations: &[Violation]) -> Vec<&str> {
+        violations.iter().map(|v| v.site()).collect()
+    }
+
+    #[test]
+    fn test_flags_strings_and_maps() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let violations = check_strict(&ci, &StrictConfig::default());
+        assert_eq!(
+            sites(&violations),
+            vec![
+                "greet",
+                "greet",
+                "Counter.new",
+                "Listener.on_event",
+                "Contact.nicknames",
+                "Shape.Named.name",
+            ]
+        );
+        assert_eq!(
+            violations[0].to_string(),
+            "`greet`: argument `name` is a bare `string`; consider an enum or a `[Wrapped]` typedef"
+        );
+        assert_eq!(
+            violations[1].to_string(),
+            "`greet`: return type is a bare `string`; consider an enum or a `[Wrapped]` typedef"
+        );
+        assert!(violations[2]
+            .to_string()
+            .starts_with("`Counter.new`: argument `initial` is a `record<DOMString, T>` map"));
+    }
+
+    #[test]
+    fn test_allowlist() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let config = StrictConfig {
+            allow: Some(vec![
+                "greet".into(),
+                "Shape".into(),
+                "Contact.nick".into(),
+                "Listener.on_event".into(),
+            ]),
+        };
+        let violations = check_strict(&ci, &config);
+        assert_eq!(sites(&violations), vec!["Counter.new", "Contact.nicknames"]);
+    }
+}
@@ -26,6 +26,31 @@ use std::process::Command;
 /// command-line tool. This is mostly useful for developers who are working on uniffi
 /// itself and need to test out their changes to the bindings generator.
 pub fn generate_scaffolding(udl_file: &str) -> Result<()> {
+    generate_scaffolding_for_target(udl_file, "native", &[])
+}
+
+/// Generate the rust "scaffolding" for compiling a uniffi component to WebAssembly.
+///
+/// This works like [`generate_scaffolding`], but the generated file exports the component
+/// to JavaScript with `wasm_bindgen`, instead of as `pub extern "C"` functions, so the crate
+/// must depend on `wasm-bindgen` itself. Build scripts can check the `CARGO_CFG_TARGET_ARCH`
+/// environment variable for `wasm32` to pick between the two.
+pub fn generate_wasm_scaffolding(udl_file: &str) -> Result<()> {
+    generate_scaffolding_for_target(udl_file, "wasm", &[])
+}
+
+/// Generate the rust "scaffolding" for just the part of a uniffi component that an app uses.
+///
+/// This works like [`generate_scaffolding`], but only exports the functions, constructors and
+/// methods that the `roots` name, such as `open_store` or `Store.get`, or `Store` for all of an
+/// object's members, along with the types that they use. The bindings must be generated with
+/// the same roots, by passing `--prune-unused` and a `--roots` for each of them to
+/// `uniffi-bindgen generate`.
+pub fn generate_pruned_scaffolding(udl_file: &str, roots: &[&str]) -> Result<()> {
+    generate_scaffolding_for_target(udl_file, "native", roots)
+}
+
+fn generate_scaffolding_for_target(udl_file: &str, target: &str, roots: &[&str]) -> Result<()> {
     println!("cargo:rerun-if-changed={}", udl_file);
     // The UNIFFI_TESTS_DISABLE_EXTENSIONS variable disables some bindings, but it is evaluated
     // at *build* time, so we need to rebuild when it changes.
@@ -34,13 +59,26 @@ pub fn generate_scaffolding(udl_file: &str) -> Result<()> {
     // Calling the command line helps making sure that the generated swift/Kotlin/whatever
     // bindings were generated with the same version of uniffi as the Rust scaffolding code.
     let out_dir = env::var("OUT_DIR").map_err(|_| anyhow::anyhow!("$OUT_DIR missing?!"))?;
-    run_uniffi_bindgen_scaffolding(&out_dir, udl_file)
+    run_uniffi_bindgen_scaffolding(&out_dir, udl_file, target, roots)
 }
 
 #[cfg(not(feature = "builtin-bindgen"))]
-fn run_uniffi_bindgen_scaffolding(out_dir: &str, udl_file: &str) -> Result<()> {
-    let status = Command::new("uniffi-bindgen")
-        .args(&["scaffolding", "--out-dir", out_dir, udl_file])
+fn run_uniffi_bindgen_scaffolding(
+    out_dir: &str,
+    udl_file: &str,
+    target: &str,
+    roots: &[&str],
+) -> Result<()> {
+    let mut command = Command::new("uniffi-bindgen");
+    command.args(&["scaffolding", "--out-dir", out_dir, "--target", target]);
+    if !roots.is_empty() {
+        command.arg("--prune-unused");
+        for root in roots {
+            command.args(&["--roots", root]);
+        }
+    }
+    let status = command
+        .arg(udl_file)
         .status()
         .context("failed to run `uniffi-bindgen` - have you installed it via `cargo install uniffi_bindgen`?")?;
     if !status.success() {
@@ -50,6 +88,18 @@ fn run_uniffi_bindgen_scaffolding(out_dir: &str, udl_file: &str) -> Result<()> {
 }
 
 #[cfg(feature = "builtin-bindgen")]
-fn run_uniffi_bindgen_scaffolding(out_dir: &str, udl_file: &str) -> Result<()> {
-    uniffi_bindgen::generate_component_scaffolding(udl_file, None, Some(out_dir), true)
+fn run_uniffi_bindgen_scaffolding(
+    out_dir: &str,
+    udl_file: &str,
+    target: &str,
+    roots: &[&str],
+) -> Result<()> {
+    uniffi_bindgen::generate_component_scaffolding(
+        udl_file,
+        None,
+        Some(out_dir),
+        target.parse()?,
+        if roots.is_empty() { None } else { Some(roots) },
+        true,
+    )
 }
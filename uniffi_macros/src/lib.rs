@@ -26,6 +26,21 @@ use syn::{bracketed, punctuated::Punctuated, LitStr, Token};
 /// one or more file paths relative to the crate root directory.
 /// It will produce one `#[test]` function per file, in a manner designed to
 /// play nicely with `cargo test` and its test filtering options.
+///
+/// Not every foreign language backend supports every feature of UniFFI. If the
+/// component's tests depend on such features, list them in an optional third
+/// argument, like so:
+///
+/// ```rs
+/// uniffi_macros::build_foreign_language_testcases!(
+///     ["src/callbacks.udl"],
+///     ["tests/bindings/test_callbacks.kts", "tests/bindings/test_callbacks.py"],
+///     ["callback_interfaces"]
+/// );
+/// ```
+///
+/// Test scripts for a language whose backend lacks any of the listed features will
+/// be skipped, with a note printed to say so, rather than failing.
 #[proc_macro]
 pub fn build_foreign_language_testcases(paths: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let paths = syn::parse_macro_input!(paths as FilePaths);
@@ -44,6 +59,8 @@ pub fn build_foreign_language_testcases(paths: proc_macro::TokenStream) -> proc_
         })
         .collect::<Vec<proc_macro2::TokenStream>>();
 
+    let required_features = &paths.required_features;
+
     // For each test file found, generate a matching testcase.
     let test_functions = paths.test_scripts
         .iter()
@@ -67,7 +84,7 @@ pub fn build_foreign_language_testcases(paths: proc_macro::TokenStream) -> proc_
                 #maybe_ignore
                 #[test]
                 fn #test_name () -> uniffi::deps::anyhow::Result<()> {
-                    uniffi::testing::run_foreign_language_testcase(#pkg_dir, &[ #(#udl_files),* ], #test_file_path)
+                    uniffi::testing::run_foreign_language_testcase(#pkg_dir, &[ #(#udl_files),* ], #test_file_path, &[ #(#required_features),* ])
                 }
             }
         })
@@ -78,6 +95,82 @@ pub fn build_foreign_language_testcases(paths: proc_macro::TokenStream) -> proc_
     proc_macro::TokenStream::from(test_module)
 }
 
+/// A macro to build a testcase comparing a component's generated code with its golden files.
+///
+/// Invoke the macro with one or more udl files as the first argument, then the foreign
+/// languages whose bindings should be compared, like so:
+///
+/// ```rs
+/// uniffi_macros::build_golden_file_testcases!(
+///     ["src/callbacks.udl"],
+///     ["kotlin", "swift"]
+/// );
+/// ```
+///
+/// It will produce a single `#[test]` function that fails if the scaffolding or bindings
+/// generated for any of the udl files differ from the golden files committed in the crate's
+/// `golden/` directory. Run the tests with `UNIFFI_UPDATE_GOLDEN=1` set in the environment to
+/// create or update the golden files.
+#[proc_macro]
+pub fn build_golden_file_testcases(args: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let args = syn::parse_macro_input!(args as GoldenFileArgs);
+    let pkg_dir = env::var("CARGO_MANIFEST_DIR")
+        .expect("Missing $CARGO_MANIFEST_DIR, cannot build tests for golden files");
+    let udl_files = &args
+        .udl_files
+        .iter()
+        .map(|file_path| {
+            let pathbuf: PathBuf = [&pkg_dir, file_path].iter().collect();
+            let path = pathbuf.to_string_lossy();
+            quote! { #path }
+        })
+        .collect::<Vec<proc_macro2::TokenStream>>();
+    let languages = &args.languages;
+    let test_module = quote! {
+        #[test]
+        fn uniffi_golden_file_testcase() -> uniffi::deps::anyhow::Result<()> {
+            uniffi::testing::run_golden_file_testcase(#pkg_dir, &[ #(#udl_files),* ], &[ #(#languages),* ])
+        }
+    };
+    proc_macro::TokenStream::from(test_module)
+}
+
+/// The udl files and languages given to `build_golden_file_testcases!`.
+#[derive(Debug)]
+struct GoldenFileArgs {
+    udl_files: Vec<String>,
+    languages: Vec<String>,
+}
+
+impl syn::parse::Parse for GoldenFileArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let udl_array;
+        bracketed!(udl_array in input);
+        let udl_files = Punctuated::<LitStr, Token![,]>::parse_terminated(&udl_array)?
+            .iter()
+            .map(|s| s.value())
+            .collect();
+
+        let _comma: Token![,] = input.parse()?;
+
+        let languages_array;
+        bracketed!(languages_array in input);
+        let languages = Punctuated::<LitStr, Token![,]>::parse_terminated(&languages_array)?
+            .iter()
+            .map(|s| s.value())
+            .collect();
+
+        if input.peek(Token![,]) {
+            let _comma: Token![,] = input.parse()?;
+        }
+
+        Ok(GoldenFileArgs {
+            udl_files,
+            languages,
+        })
+    }
+}
+
 // UNIFFI_TESTS_DISABLE_EXTENSIONS contains a comma-sep'd list of extensions (without leading `.`)
 fn should_skip_path(path: &Path) -> bool {
     let ext = path.extension().expect("File has no extension!");
@@ -91,6 +184,7 @@ fn should_skip_path(path: &Path) -> bool {
 struct FilePaths {
     udl_files: Vec<String>,
     test_scripts: Vec<String>,
+    required_features: Vec<String>,
 }
 
 impl syn::parse::Parse for FilePaths {
@@ -111,9 +205,29 @@ impl syn::parse::Parse for FilePaths {
             .map(|s| s.value())
             .collect();
 
+        // The list of required features is optional, and we allow a trailing comma
+        // either way.
+        let mut required_features = Vec::new();
+        if input.peek(Token![,]) {
+            let _comma: Token![,] = input.parse()?;
+            if !input.is_empty() {
+                let features_array;
+                bracketed!(features_array in input);
+                required_features =
+                    Punctuated::<LitStr, Token![,]>::parse_terminated(&features_array)?
+                        .iter()
+                        .map(|s| s.value())
+                        .collect();
+                if input.peek(Token![,]) {
+                    let _comma: Token![,] = input.parse()?;
+                }
+            }
+        }
+
         Ok(FilePaths {
             udl_files,
             test_scripts,
+            required_features,
         })
     }
 }